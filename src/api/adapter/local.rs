@@ -7,6 +7,7 @@ use bytes::Bytes;
 use std::{borrow::Borrow, fmt::Debug, net::SocketAddr, sync::Arc};
 
 use futures_util::TryFutureExt;
+use tokio::sync::oneshot::Sender;
 
 use crate::api::adapter::{MockServerAdapter, ServerAdapterError};
 
@@ -17,14 +18,18 @@ use crate::{
 
 use crate::common::data::{ActiveForwardingRule, ActiveMock, ActiveProxyRule, ActiveRecording};
 
+#[cfg(feature = "record")]
+use crate::common::data::PlaybackConfig;
 use crate::common::data::{
-    ClosestMatch, ForwardingRuleConfig, MockDefinition, ProxyRuleConfig, RecordingRuleConfig,
-    RequestRequirements,
+    ClosestMatch, ForwardingRuleConfig, HttpMockRequest, MockDefinition, MockStats,
+    ProxyRuleConfig, RecordingRuleConfig, RequestRequirements, StateSnapshot, UnmatchedRequest,
 };
 
 pub struct LocalMockServerAdapter {
     pub addr: SocketAddr,
     state: Arc<HttpMockStateManager>,
+    // The server shuts down once this sender is dropped.
+    _shutdown: Option<Sender<()>>,
 }
 
 impl LocalMockServerAdapter {
@@ -32,6 +37,20 @@ impl LocalMockServerAdapter {
         LocalMockServerAdapter {
             addr,
             state: local_state,
+            _shutdown: None,
+        }
+    }
+
+    /// Creates an adapter for a server that shuts down when the adapter is dropped.
+    pub(crate) fn with_shutdown(
+        addr: SocketAddr,
+        local_state: Arc<HttpMockStateManager>,
+        shutdown: Sender<()>,
+    ) -> Self {
+        LocalMockServerAdapter {
+            addr,
+            state: local_state,
+            _shutdown: Some(shutdown),
         }
     }
 }
@@ -76,6 +95,17 @@ impl MockServerAdapter for LocalMockServerAdapter {
         Ok(active_mock)
     }
 
+    async fn create_mocks(
+        &self,
+        mocks: &[MockDefinition],
+    ) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        let active_mocks = self
+            .state
+            .add_mocks(mocks.to_vec(), false)
+            .map_err(|e| UpstreamError(e.to_string()))?;
+        Ok(active_mocks)
+    }
+
     async fn fetch_mock(&self, mock_id: usize) -> Result<ActiveMock, ServerAdapterError> {
         let mock = self
             .state
@@ -85,6 +115,19 @@ impl MockServerAdapter for LocalMockServerAdapter {
         Ok(mock)
     }
 
+    async fn list_mocks(&self) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        Ok(self.state.read_mocks())
+    }
+
+    async fn fetch_mock_stats(&self, mock_id: usize) -> Result<MockStats, ServerAdapterError> {
+        let stats = self
+            .state
+            .mock_stats(mock_id)
+            .map_err(|e| UpstreamError(e.to_string()))?
+            .ok_or_else(|| MockNotFound(mock_id))?;
+        Ok(stats)
+    }
+
     async fn delete_mock(&self, mock_id: usize) -> Result<(), ServerAdapterError> {
         self.state
             .delete_mock(mock_id)
@@ -92,11 +135,26 @@ impl MockServerAdapter for LocalMockServerAdapter {
         Ok(())
     }
 
+    async fn delete_mocks(&self, mock_ids: &[usize]) -> Result<(), ServerAdapterError> {
+        self.state
+            .delete_mocks(mock_ids)
+            .map_err(|e| UpstreamError(format!("Cannot delete mocks: {:?}", e)))?;
+        Ok(())
+    }
+
     async fn delete_all_mocks(&self) -> Result<(), ServerAdapterError> {
         self.state.delete_all_mocks();
         Ok(())
     }
 
+    async fn fetch_mocks_by_tag(&self, tag: &str) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        Ok(self.state.read_mocks_by_tag(tag))
+    }
+
+    async fn delete_mocks_by_tag(&self, tag: &str) -> Result<usize, ServerAdapterError> {
+        Ok(self.state.delete_mocks_by_tag(tag))
+    }
+
     async fn verify(
         &self,
         mock_rr: &RequestRequirements,
@@ -113,6 +171,45 @@ impl MockServerAdapter for LocalMockServerAdapter {
         Ok(())
     }
 
+    async fn received_requests(
+        &self,
+        mock_id: usize,
+    ) -> Result<Vec<HttpMockRequest>, ServerAdapterError> {
+        let requests = self
+            .state
+            .received_requests(mock_id)
+            .map_err(|e| UpstreamError(e.to_string()))?
+            .ok_or_else(|| MockNotFound(mock_id))?;
+        Ok(requests)
+    }
+
+    async fn unmatched_requests(&self) -> Result<Vec<UnmatchedRequest>, ServerAdapterError> {
+        Ok(self.state.unmatched_requests())
+    }
+
+    async fn set_default_headers(
+        &self,
+        headers: &[(String, String)],
+    ) -> Result<(), ServerAdapterError> {
+        self.state.set_default_response_headers(headers.to_vec());
+        Ok(())
+    }
+
+    async fn export_state(&self) -> Result<StateSnapshot, ServerAdapterError> {
+        self.state
+            .export_state()
+            .map_err(|e| UpstreamError(format!("Cannot export state: {}", e)))
+    }
+
+    async fn import_state(
+        &self,
+        snapshot: &StateSnapshot,
+    ) -> Result<Vec<usize>, ServerAdapterError> {
+        self.state
+            .import_state(snapshot.clone())
+            .map_err(|e| UpstreamError(format!("Cannot import state: {}", e)))
+    }
+
     async fn create_forwarding_rule(
         &self,
         config: ForwardingRuleConfig,
@@ -176,10 +273,11 @@ impl MockServerAdapter for LocalMockServerAdapter {
     async fn create_mocks_from_recording<'a>(
         &self,
         recording_file_content: &'a str,
+        config: PlaybackConfig,
     ) -> Result<Vec<usize>, ServerAdapterError> {
         Ok(self
             .state
-            .load_mocks_from_recording(recording_file_content)
+            .load_mocks_from_recording(recording_file_content, config)
             .map_err(|err| UpstreamError(err.to_string()))?)
     }
 }
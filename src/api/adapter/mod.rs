@@ -7,10 +7,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::common::data::{ActiveForwardingRule, ActiveMock, ActiveProxyRule};
 
-use crate::common::data::{ActiveRecording, ClosestMatch, MockDefinition, RequestRequirements};
+use crate::common::data::{
+    ActiveRecording, ClosestMatch, HttpMockRequest, MockDefinition, MockStats, RequestRequirements,
+    StateSnapshot, UnmatchedRequest,
+};
 
 pub mod local;
 
+#[cfg(feature = "record")]
+use crate::common::data::PlaybackConfig;
 use crate::common::data::{ForwardingRuleConfig, ProxyRuleConfig, RecordingRuleConfig};
 
 use thiserror::Error;
@@ -46,15 +51,42 @@ pub trait MockServerAdapter {
     async fn reset(&self) -> Result<(), ServerAdapterError>;
 
     async fn create_mock(&self, mock: &MockDefinition) -> Result<ActiveMock, ServerAdapterError>;
+    /// Creates all mocks in a single operation. The returned mocks are in the same order as the
+    /// definitions.
+    async fn create_mocks(
+        &self,
+        mocks: &[MockDefinition],
+    ) -> Result<Vec<ActiveMock>, ServerAdapterError>;
     async fn fetch_mock(&self, mock_id: usize) -> Result<ActiveMock, ServerAdapterError>;
+    async fn list_mocks(&self) -> Result<Vec<ActiveMock>, ServerAdapterError>;
+    async fn fetch_mock_stats(&self, mock_id: usize) -> Result<MockStats, ServerAdapterError>;
     async fn delete_mock(&self, mock_id: usize) -> Result<(), ServerAdapterError>;
+    async fn delete_mocks(&self, mock_ids: &[usize]) -> Result<(), ServerAdapterError>;
     async fn delete_all_mocks(&self) -> Result<(), ServerAdapterError>;
+    async fn fetch_mocks_by_tag(&self, tag: &str) -> Result<Vec<ActiveMock>, ServerAdapterError>;
+    async fn delete_mocks_by_tag(&self, tag: &str) -> Result<usize, ServerAdapterError>;
 
     async fn verify(
         &self,
         rr: &RequestRequirements,
     ) -> Result<Option<ClosestMatch>, ServerAdapterError>;
     async fn delete_history(&self) -> Result<(), ServerAdapterError>;
+    async fn received_requests(
+        &self,
+        mock_id: usize,
+    ) -> Result<Vec<HttpMockRequest>, ServerAdapterError>;
+    async fn unmatched_requests(&self) -> Result<Vec<UnmatchedRequest>, ServerAdapterError>;
+
+    async fn set_default_headers(
+        &self,
+        headers: &[(String, String)],
+    ) -> Result<(), ServerAdapterError>;
+
+    async fn export_state(&self) -> Result<StateSnapshot, ServerAdapterError>;
+    async fn import_state(
+        &self,
+        snapshot: &StateSnapshot,
+    ) -> Result<Vec<usize>, ServerAdapterError>;
 
     async fn create_forwarding_rule(
         &self,
@@ -84,5 +116,6 @@ pub trait MockServerAdapter {
     async fn create_mocks_from_recording<'a>(
         &self,
         recording_file_content: &'a str,
+        config: PlaybackConfig,
     ) -> Result<Vec<usize>, ServerAdapterError>;
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "record")]
+use crate::common::data::PlaybackConfig;
 use crate::common::data::{ForwardingRuleConfig, ProxyRuleConfig, RecordingRuleConfig};
 use std::{borrow::Borrow, net::SocketAddr, sync::Arc};
 
@@ -6,7 +8,7 @@ use crate::api::{
         ServerAdapterError,
         ServerAdapterError::{
             InvalidMockDefinitionError, JsonDeserializationError, JsonSerializationError,
-            UpstreamError,
+            MockNotFound, UpstreamError,
         },
     },
     MockServerAdapter,
@@ -14,11 +16,13 @@ use crate::api::{
 use async_trait::async_trait;
 use bytes::Bytes;
 use http::{Request, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::common::{
     data::{
         ActiveForwardingRule, ActiveMock, ActiveProxyRule, ActiveRecording, ClosestMatch,
-        MockDefinition, RequestRequirements,
+        HttpMockRequest, MockDefinition, MockStats, RequestRequirements, StateSnapshot,
+        UnmatchedRequest,
     },
     http::HttpClient,
 };
@@ -45,6 +49,51 @@ impl RemoteMockServerAdapter {
         }
     }
 
+    fn validate_mock_definition(&self, mock: &MockDefinition) -> Result<(), ServerAdapterError> {
+        self.validate_request_requirements(&mock.request)?;
+
+        if mock.response.after_respond.is_some() {
+            return Err(InvalidMockDefinitionError(
+                "Callback requests are not supported when using a remote mock server".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sends mock definitions to the given path of the mock server and returns the created
+    /// mocks. `entity` names what is created in the error message (e.g., "mock").
+    async fn post_mock_definitions<D, R>(
+        &self,
+        path: &str,
+        definitions: &D,
+        entity: &str,
+    ) -> Result<R, ServerAdapterError>
+    where
+        D: Serialize + ?Sized,
+        R: DeserializeOwned,
+    {
+        let json = serde_json::to_string(definitions).map_err(|e| JsonSerializationError(e))?;
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("http://{}{}", &self.address(), path))
+            .header("content-type", "application/json")
+            .body(Bytes::from(json))
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::CREATED.as_u16() {
+            return Err(UpstreamError(format!(
+                "Could not create {}. Expected response status 201 but was {} (response body = '{}')",
+                entity, status, body
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))
+    }
+
     async fn do_request(&self, req: Request<Bytes>) -> Result<(u16, String), ServerAdapterError> {
         let (code, body_bytes) = self.do_request_raw(req).await?;
 
@@ -121,32 +170,54 @@ impl MockServerAdapter for RemoteMockServerAdapter {
     }
 
     async fn create_mock(&self, mock: &MockDefinition) -> Result<ActiveMock, ServerAdapterError> {
-        self.validate_request_requirements(&mock.request)?;
+        self.validate_mock_definition(mock)?;
 
-        let json = serde_json::to_string(mock).map_err(|e| JsonSerializationError(e))?;
+        self.post_mock_definitions("/__httpmock__/mocks", mock, "mock")
+            .await
+    }
 
+    async fn fetch_mock_stats(&self, mock_id: usize) -> Result<MockStats, ServerAdapterError> {
         let request = Request::builder()
-            .method("POST")
-            .uri(format!("http://{}/__httpmock__/mocks", &self.address()))
-            .header("content-type", "application/json")
-            .body(Bytes::from(json))
+            .method("GET")
+            .uri(format!(
+                "http://{}/__httpmock__/mocks/{}/stats",
+                &self.address(),
+                mock_id
+            ))
+            .body(Bytes::new())
             .map_err(|e| UpstreamError(e.to_string()))?;
 
         let (status, body) = self.do_request(request).await?;
 
-        if status != StatusCode::CREATED.as_u16() {
+        if status == StatusCode::NOT_FOUND {
+            return Err(MockNotFound(mock_id));
+        }
+
+        if status != StatusCode::OK {
             return Err(UpstreamError(format!(
-                "Could not create mock. Expected response status 201 but was {} (response body = '{}')",
+                "Could not fetch mock statistics from the mock server. Expected response status 200 but was {} (response body = '{}')",
                 status, body
             )));
         }
 
-        let response: ActiveMock =
+        let response: MockStats =
             serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
 
         Ok(response)
     }
 
+    async fn create_mocks(
+        &self,
+        mocks: &[MockDefinition],
+    ) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        for mock in mocks {
+            self.validate_mock_definition(mock)?;
+        }
+
+        self.post_mock_definitions("/__httpmock__/mocks/batch", mocks, "mocks")
+            .await
+    }
+
     async fn fetch_mock(&self, mock_id: usize) -> Result<ActiveMock, ServerAdapterError> {
         let request = Request::builder()
             .method("GET")
@@ -173,6 +244,28 @@ impl MockServerAdapter for RemoteMockServerAdapter {
         Ok(response)
     }
 
+    async fn list_mocks(&self) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("http://{}/__httpmock__/mocks", &self.address()))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not fetch mocks from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        let response: Vec<ActiveMock> =
+            serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
+
+        Ok(response)
+    }
+
     async fn delete_mock(&self, mock_id: usize) -> Result<(), ServerAdapterError> {
         let request = Request::builder()
             .method("DELETE")
@@ -196,6 +289,31 @@ impl MockServerAdapter for RemoteMockServerAdapter {
         Ok(())
     }
 
+    async fn delete_mocks(&self, mock_ids: &[usize]) -> Result<(), ServerAdapterError> {
+        let json = serde_json::to_string(mock_ids).map_err(|e| JsonSerializationError(e))?;
+
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!(
+                "http://{}/__httpmock__/mocks/delete",
+                &self.address()
+            ))
+            .header("content-type", "application/json")
+            .body(Bytes::from(json))
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::NO_CONTENT {
+            return Err(UpstreamError(format!(
+                "Could not delete mocks from the mock server. Expected response status 204 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        Ok(())
+    }
+
     async fn delete_all_mocks(&self) -> Result<(), ServerAdapterError> {
         let request = Request::builder()
             .method("DELETE")
@@ -215,6 +333,52 @@ impl MockServerAdapter for RemoteMockServerAdapter {
         Ok(())
     }
 
+    async fn fetch_mocks_by_tag(&self, tag: &str) -> Result<Vec<ActiveMock>, ServerAdapterError> {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!(
+                "http://{}/__httpmock__/mocks/tagged?tag={}",
+                &self.address(),
+                url::form_urlencoded::byte_serialize(tag.as_bytes()).collect::<String>()
+            ))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not fetch mocks with tag '{}' from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                tag, status, body
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))
+    }
+
+    async fn delete_mocks_by_tag(&self, tag: &str) -> Result<usize, ServerAdapterError> {
+        let request = Request::builder()
+            .method("DELETE")
+            .uri(format!(
+                "http://{}/__httpmock__/mocks/tagged?tag={}",
+                &self.address(),
+                url::form_urlencoded::byte_serialize(tag.as_bytes()).collect::<String>()
+            ))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not delete mocks with tag '{}' from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                tag, status, body
+            )));
+        }
+
+        serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))
+    }
+
     async fn verify(
         &self,
         requirements: &RequestRequirements,
@@ -266,6 +430,142 @@ impl MockServerAdapter for RemoteMockServerAdapter {
         Ok(())
     }
 
+    async fn received_requests(
+        &self,
+        mock_id: usize,
+    ) -> Result<Vec<HttpMockRequest>, ServerAdapterError> {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!(
+                "http://{}/__httpmock__/mocks/{}/requests",
+                &self.address(),
+                mock_id
+            ))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status == StatusCode::NOT_FOUND {
+            return Err(MockNotFound(mock_id));
+        }
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not fetch received requests from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        let response: Vec<HttpMockRequest> =
+            serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
+
+        Ok(response)
+    }
+
+    async fn unmatched_requests(&self) -> Result<Vec<UnmatchedRequest>, ServerAdapterError> {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!(
+                "http://{}/__httpmock__/history/unmatched",
+                &self.address()
+            ))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not fetch unmatched requests from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        let response: Vec<UnmatchedRequest> =
+            serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
+
+        Ok(response)
+    }
+
+    async fn set_default_headers(
+        &self,
+        headers: &[(String, String)],
+    ) -> Result<(), ServerAdapterError> {
+        let json = serde_json::to_string(headers).map_err(|e| JsonSerializationError(e))?;
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri(format!(
+                "http://{}/__httpmock__/default_headers",
+                &self.address()
+            ))
+            .header("content-type", "application/json")
+            .body(Bytes::from(json))
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::NO_CONTENT {
+            return Err(UpstreamError(format!(
+                "Could not set default headers on the mock server. Expected response status 204 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn export_state(&self) -> Result<StateSnapshot, ServerAdapterError> {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("http://{}/__httpmock__/state", &self.address()))
+            .body(Bytes::new())
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not export state from the mock server. Expected response status 200 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        let snapshot: StateSnapshot =
+            serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
+
+        Ok(snapshot)
+    }
+
+    async fn import_state(
+        &self,
+        snapshot: &StateSnapshot,
+    ) -> Result<Vec<usize>, ServerAdapterError> {
+        let json = serde_json::to_string(snapshot).map_err(|e| JsonSerializationError(e))?;
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri(format!("http://{}/__httpmock__/state", &self.address()))
+            .header("content-type", "application/json")
+            .body(Bytes::from(json))
+            .map_err(|e| UpstreamError(e.to_string()))?;
+
+        let (status, body) = self.do_request(request).await?;
+
+        if status != StatusCode::OK {
+            return Err(UpstreamError(format!(
+                "Could not import state into the mock server. Expected response status 200 but was {} (response body = '{}')",
+                status, body
+            )));
+        }
+
+        let mock_ids: Vec<usize> =
+            serde_json::from_str(&body).map_err(|e| JsonDeserializationError(e))?;
+
+        Ok(mock_ids)
+    }
+
     async fn create_forwarding_rule(
         &self,
         config: ForwardingRuleConfig,
@@ -530,12 +830,15 @@ impl MockServerAdapter for RemoteMockServerAdapter {
     async fn create_mocks_from_recording<'a>(
         &self,
         recording_file_content: &'a str,
+        config: PlaybackConfig,
     ) -> Result<Vec<usize>, ServerAdapterError> {
         let request = Request::builder()
             .method("POST")
             .uri(format!(
-                "http://{}/__httpmock__/recordings",
+                "http://{}/__httpmock__/recordings?ordered={}&replay_with_timing={}",
                 &self.address(),
+                config.ordered,
+                config.replay_with_timing
             ))
             .body(Bytes::from(recording_file_content.to_owned()))
             .map_err(|e| UpstreamError(e.to_string()))?;
@@ -0,0 +1,65 @@
+use crate::{
+    common::{
+        data::CallbackRequest,
+        util::{update_cell, HttpMockBytes},
+    },
+    Method,
+};
+use bytes::Bytes;
+use std::{cell::Cell, convert::TryInto, rc::Rc, time::Duration};
+
+/// Configures an HTTP request that the mock server sends after it has responded to a request
+/// (see [`Then::after_respond`](crate::Then::after_respond)).
+pub struct CallbackRequestBuilder {
+    pub(crate) request: Rc<Cell<CallbackRequest>>,
+}
+
+impl CallbackRequestBuilder {
+    /// Sets the HTTP method of the callback request. Defaults to `POST`.
+    pub fn method<TryIntoMethod: TryInto<Method>>(mut self, method: TryIntoMethod) -> Self
+    where
+        <TryIntoMethod as TryInto<Method>>::Error: std::fmt::Debug,
+    {
+        let method = method
+            .try_into()
+            .expect("cannot convert method into httpmock::Method");
+
+        update_cell(&self.request, |r| r.method = Some(method.to_string()));
+        self
+    }
+
+    /// Sets the absolute URL the callback request is sent to, such as
+    /// `http://localhost:8080/webhooks`. This is required.
+    pub fn url<IntoString: Into<String>>(mut self, url: IntoString) -> Self {
+        update_cell(&self.request, |r| r.url = Some(url.into()));
+        self
+    }
+
+    /// Adds a header to the callback request.
+    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
+        update_cell(&self.request, |r| {
+            r.headers.push((name.into(), value.into()));
+        });
+        self
+    }
+
+    /// Sets the body of the callback request.
+    pub fn body<SliceRef: AsRef<[u8]>>(mut self, body: SliceRef) -> Self {
+        update_cell(&self.request, |r| {
+            r.body = Some(HttpMockBytes::from(Bytes::copy_from_slice(body.as_ref())));
+        });
+        self
+    }
+
+    /// Sets how long the mock server waits after responding before it sends the callback
+    /// request. By default, the request is sent right away.
+    pub fn delay<D: Into<Duration>>(mut self, delay: D) -> Self {
+        let delay = delay.into();
+        update_cell(&self.request, |r| r.delay = Some(delay.as_millis() as u64));
+        self
+    }
+}
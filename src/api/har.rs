@@ -0,0 +1,177 @@
+use serde_json::Value;
+use std::convert::TryFrom;
+use url::Url;
+
+use crate::common::{
+    data::{MockDefinition, MockServerHttpResponse, RequestRequirements},
+    util::HttpMockBytes,
+};
+
+/// Response headers that describe how the recorded body was transferred. HAR files store the
+/// decoded body, so these headers would not fit the served body anymore.
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &[
+    "content-encoding",
+    "content-length",
+    "transfer-encoding",
+    "connection",
+];
+
+/// Creates one mock definition per entry of a HAR (HTTP Archive) document. Each mock matches
+/// the method, path and query parameters of the recorded request and serves the recorded
+/// response.
+pub(crate) fn mock_definitions_from_har(content: &str) -> Result<Vec<MockDefinition>, String> {
+    let doc: Value =
+        serde_json::from_str(content).map_err(|err| format!("cannot parse HAR file: {}", err))?;
+
+    let entries = doc
+        .get("log")
+        .and_then(|log| log.get("entries"))
+        .and_then(Value::as_array)
+        .ok_or_else(|| "document is not a HAR file: log.entries is missing".to_string())?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            entry_to_mock(entry).map_err(|err| format!("invalid HAR entry {}: {}", idx, err))
+        })
+        .collect()
+}
+
+fn entry_to_mock(entry: &Value) -> Result<MockDefinition, String> {
+    let request = entry.get("request").ok_or("request is missing")?;
+    let response = entry.get("response").ok_or("response is missing")?;
+
+    Ok(MockDefinition {
+        request: to_request_requirements(request)?,
+        response: to_response(response)?,
+    })
+}
+
+fn to_request_requirements(request: &Value) -> Result<RequestRequirements, String> {
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or("request.method is missing")?;
+    let url = request
+        .get("url")
+        .and_then(Value::as_str)
+        .ok_or("request.url is missing")?;
+    let url = Url::parse(url).map_err(|err| format!("invalid request.url '{}': {}", url, err))?;
+
+    let mut requirements = RequestRequirements::new();
+    requirements.method = Some(method.to_uppercase());
+    requirements.path = Some(url.path().to_string());
+
+    // Every parameter is matched on its own, so the order of the query parameters does not
+    // need to be the same as in the recorded request.
+    let query_params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    if !query_params.is_empty() {
+        requirements.query_param = Some(query_params);
+    }
+
+    Ok(requirements)
+}
+
+fn to_response(response: &Value) -> Result<MockServerHttpResponse, String> {
+    let status = response
+        .get("status")
+        .and_then(Value::as_u64)
+        .ok_or("response.status is missing")?;
+
+    let mut response_template = MockServerHttpResponse::new();
+    response_template.status =
+        Some(u16::try_from(status).map_err(|_| format!("invalid response.status {}", status))?);
+
+    let headers: Vec<(String, String)> = response
+        .get("headers")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|header| {
+            let name = header.get("name").and_then(Value::as_str)?;
+            let value = header.get("value").and_then(Value::as_str)?;
+            Some((name.to_string(), value.to_string()))
+        })
+        // HTTP/2 pseudo-headers (e.g., ":status") are part of the status line.
+        .filter(|(name, _)| !name.starts_with(':'))
+        .filter(|(name, _)| !SKIPPED_RESPONSE_HEADERS.contains(&name.to_lowercase().as_str()))
+        .collect();
+    if !headers.is_empty() {
+        response_template.headers = Some(headers);
+    }
+
+    let content = response.get("content");
+    if let Some(text) = content.and_then(|c| c.get("text")).and_then(Value::as_str) {
+        let body = match content
+            .and_then(|c| c.get("encoding"))
+            .and_then(Value::as_str)
+        {
+            Some("base64") => base64::decode(text)
+                .map_err(|err| format!("cannot decode base64 response body: {}", err))?,
+            _ => text.as_bytes().to_vec(),
+        };
+        response_template.body = Some(HttpMockBytes::from(bytes::Bytes::from(body)));
+    }
+
+    Ok(response_template)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api::har::mock_definitions_from_har;
+    use serde_json::json;
+
+    #[test]
+    fn mock_definitions_from_har_test() {
+        let har = json!({
+            "log": {
+                "entries": [{
+                    "request": { "method": "get", "url": "https://example.com/users?b=2&a=1" },
+                    "response": {
+                        "status": 200,
+                        "headers": [
+                            { "name": ":status", "value": "200" },
+                            { "name": "Content-Type", "value": "text/plain" },
+                            { "name": "Content-Encoding", "value": "gzip" }
+                        ],
+                        "content": { "text": "aGVsbG8=", "encoding": "base64" }
+                    }
+                }]
+            }
+        });
+
+        let mocks = mock_definitions_from_har(&har.to_string()).unwrap();
+        assert_eq!(mocks.len(), 1);
+
+        let request = &mocks[0].request;
+        assert_eq!(request.method.as_deref(), Some("GET"));
+        assert_eq!(request.path.as_deref(), Some("/users"));
+        assert_eq!(
+            request.query_param,
+            Some(vec![
+                ("b".to_string(), "2".to_string()),
+                ("a".to_string(), "1".to_string())
+            ])
+        );
+
+        let response = &mocks[0].response;
+        assert_eq!(response.status, Some(200));
+        assert_eq!(
+            response.headers,
+            Some(vec![("Content-Type".to_string(), "text/plain".to_string())])
+        );
+        assert_eq!(response.body.as_ref().unwrap().as_ref(), b"hello");
+    }
+
+    #[test]
+    fn invalid_har_entries_are_rejected() {
+        let err = mock_definitions_from_har(r#"{ "log": {} }"#).err().unwrap();
+        assert_eq!(err, "document is not a HAR file: log.entries is missing");
+
+        let har =
+            json!({ "log": { "entries": [{ "request": { "method": "GET" }, "response": {} }] } });
+        let err = mock_definitions_from_har(&har.to_string()).err().unwrap();
+        assert_eq!(err, "invalid HAR entry 0: request.url is missing");
+    }
+}
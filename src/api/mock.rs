@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::api::server::MockServer;
 
-use crate::common::util::Join;
+use crate::common::{
+    data::{ClosestMatch, HttpMockRequest, MockStats, RawRequest},
+    util::Join,
+};
 
 /// Provides a reference to a mock configuration stored on a [MockServer](struct.MockServer.html).
 /// This structure is used for interacting with, monitoring, and managing a specific mock's lifecycle,
@@ -326,7 +329,95 @@ impl<'a> Mock<'a> {
             .await
             .expect("Cannot contact mock server");
 
-        output::fail_with(active_mock.call_counter, hits, closest_match)
+        output::fail_with(
+            active_mock.call_counter,
+            hits,
+            closest_match,
+            &active_mock.shadowed_by,
+        )
+    }
+
+    /// Verifies that the mock server did not receive any HTTP request matching all the request
+    /// conditions defined for this mock.
+    ///
+    /// This is the negative counterpart of [Mock::assert](struct.Mock.html#method.assert) and is
+    /// useful to make sure that an endpoint is never called, for example to verify that a cached
+    /// value is used instead of falling back to the network.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/fallback");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Act: Send a request that does not match the mock
+    /// reqwest::blocking::get(&server.url("/primary")).unwrap();
+    ///
+    /// // Assert
+    /// mock.assert_not_called();
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if at least one matching request was received, in which case
+    /// the failure message contains the offending requests, or if there are issues with the
+    /// mock server's availability.
+    pub fn assert_not_called(&self) {
+        self.assert_not_called_async().join()
+    }
+
+    /// Asynchronously verifies that the mock server did not receive any HTTP request matching
+    /// all the request conditions defined for this mock.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     // Arrange
+    ///     let server = MockServer::start_async().await;
+    ///     let mock = server
+    ///         .mock_async(|when, then| {
+    ///             when.path("/fallback");
+    ///             then.status(200);
+    ///         })
+    ///         .await;
+    ///
+    ///     // Act: Send a request that does not match the mock
+    ///     reqwest::get(&server.url("/primary")).await.unwrap();
+    ///
+    ///     // Assert
+    ///     mock.assert_not_called_async().await;
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if at least one matching request was received, in which case
+    /// the failure message contains the offending requests, or if there are issues with the
+    /// mock server's availability.
+    pub async fn assert_not_called_async(&self) {
+        let server_adapter = self.server.server_adapter.as_ref().unwrap();
+
+        let active_mock = server_adapter
+            .fetch_mock(self.id)
+            .await
+            .expect("cannot deserialize mock server response");
+
+        if active_mock.call_counter == 0 {
+            return;
+        }
+
+        let requests = server_adapter
+            .received_requests(self.id)
+            .await
+            .expect("cannot fetch received requests from mock server");
+
+        output::fail_with_unexpected_calls(active_mock.call_counter, &requests)
     }
 
     /// Returns the number of times the specified mock has been triggered on the mock server.
@@ -484,6 +575,283 @@ impl<'a> Mock<'a> {
         response.call_counter
     }
 
+    /// Returns statistics about how often and when this mock served requests, without making
+    /// any assertions. This is useful to investigate flaky tests.
+    ///
+    /// The time of the last hit is taken from the system clock of the mock server (wall-clock
+    /// time). It is not monotonic, so it can jump if the system clock is adjusted.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/hits");
+    ///     then.status(200);
+    /// });
+    ///
+    /// assert_eq!(mock.stats().last_hit_at, None);
+    ///
+    /// reqwest::blocking::get(server.url("/hits")).unwrap();
+    ///
+    /// let stats = mock.stats();
+    /// assert_eq!(stats.hits, 1);
+    /// assert!(stats.last_hit_at.is_some());
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub fn stats(&self) -> MockStats {
+        self.stats_async().join()
+    }
+
+    /// Asynchronously returns statistics about how often and when this mock served requests.
+    /// See [`stats`](#method.stats) for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///     let mock = server
+    ///         .mock_async(|when, then| {
+    ///             when.path("/hits");
+    ///             then.status(200);
+    ///         })
+    ///         .await;
+    ///
+    ///     reqwest::get(server.url("/hits")).await.unwrap();
+    ///
+    ///     assert_eq!(mock.stats_async().await.hits, 1);
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub async fn stats_async(&self) -> MockStats {
+        self.server
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .fetch_mock_stats(self.id)
+            .await
+            .expect("cannot fetch mock statistics from mock server")
+    }
+
+    /// Returns the most recent request that the mock server received and that matches this mock,
+    /// or `None` if no such request has been received yet. Requests are looked up in the request
+    /// history of the server, so requests that were received before the history was cleared
+    /// are not considered.
+    ///
+    /// This is useful to inspect values that the code under test generated, such as a
+    /// signature header or an ID in the request body.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/orders");
+    ///     then.status(201);
+    /// });
+    ///
+    /// // Act
+    /// Client::new()
+    ///     .post(&server.url("/orders"))
+    ///     .header("x-request-id", "abc-123")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert
+    /// let request = mock.last_request().expect("no request received");
+    /// assert_eq!(request.headers().get("x-request-id").unwrap(), "abc-123");
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub fn last_request(&self) -> Option<HttpMockRequest> {
+        self.last_request_async().join()
+    }
+
+    /// Asynchronously returns the most recent request that the mock server received and that
+    /// matches this mock, or `None` if no such request has been received yet.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     // Arrange
+    ///     let server = MockServer::start_async().await;
+    ///     let mock = server
+    ///         .mock_async(|when, then| {
+    ///             when.path("/orders");
+    ///             then.status(201);
+    ///         })
+    ///         .await;
+    ///
+    ///     // Act
+    ///     reqwest::get(&server.url("/orders?id=42")).await.unwrap();
+    ///
+    ///     // Assert
+    ///     let request = mock.last_request_async().await.expect("no request received");
+    ///     assert_eq!(request.uri().query(), Some("id=42"));
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub async fn last_request_async(&self) -> Option<HttpMockRequest> {
+        let mut requests = self
+            .server
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .received_requests(self.id)
+            .await
+            .expect("cannot fetch received requests from mock server");
+
+        requests.pop()
+    }
+
+    /// Returns the most recent request that matches this mock exactly as the client sent it,
+    /// before the mock server normalized it (see [`HttpMockRequest::raw_request`]). The header
+    /// lines keep their original case, whitespace and order, which makes it possible to verify
+    /// signatures that are calculated over the canonical form of a request (e.g., HMAC signing).
+    ///
+    /// Returns `None` if no matching request has been received yet or if the raw form of the
+    /// request is not known, which is the case for HTTP/2 requests.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/webhook");
+    ///     then.status(204);
+    /// });
+    ///
+    /// // Act
+    /// let mut stream = TcpStream::connect(server.address()).unwrap();
+    /// stream
+    ///     .write_all(b"POST /webhook HTTP/1.1\r\nX-Signature: abc\r\nHost: localhost\r\nContent-Length: 2\r\nConnection: close\r\n\r\nhi")
+    ///     .unwrap();
+    /// stream.read_to_end(&mut Vec::new()).unwrap();
+    ///
+    /// // Assert
+    /// let raw = mock.last_raw_request().expect("no raw request received");
+    /// assert_eq!(raw.start_line, "POST /webhook HTTP/1.1");
+    /// assert_eq!(raw.header_lines[0], "X-Signature: abc");
+    /// assert_eq!(raw.body, "hi");
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub fn last_raw_request(&self) -> Option<RawRequest> {
+        self.last_raw_request_async().join()
+    }
+
+    /// Asynchronously returns the most recent request that matches this mock exactly as the
+    /// client sent it. See [`Mock::last_raw_request`] for details.
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub async fn last_raw_request_async(&self) -> Option<RawRequest> {
+        self.last_request_async()
+            .await
+            .and_then(|request| request.raw_request())
+    }
+
+    /// Returns the request from the request history that came closest to matching this mock,
+    /// together with a structured description of every mismatch, or `None` if the server did
+    /// not receive any request that failed to match this mock.
+    ///
+    /// This is the same information that [`assert`](#method.assert) prints when it fails, in a
+    /// machine-readable form. [`ClosestMatch`](crate::ClosestMatch) implements
+    /// [`Serialize`](serde::Serialize), so it can be written to test reports as JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/orders");
+    ///     then.status(201);
+    /// });
+    ///
+    /// // Act
+    /// reqwest::blocking::get(&server.url("/invoices")).unwrap();
+    ///
+    /// // Assert
+    /// let closest_match = mock.closest_match().expect("no request received");
+    /// assert_eq!(closest_match.mismatches[0].entity, "path");
+    ///
+    /// let json = serde_json::to_string(&closest_match).unwrap();
+    /// assert!(json.contains("/invoices"));
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub fn closest_match(&self) -> Option<ClosestMatch> {
+        self.closest_match_async().join()
+    }
+
+    /// Asynchronously returns the request from the request history that came closest to
+    /// matching this mock, or `None` if the server did not receive any request that failed to
+    /// match this mock. See [`closest_match`](#method.closest_match) for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     // Arrange
+    ///     let server = MockServer::start_async().await;
+    ///     let mock = server
+    ///         .mock_async(|when, then| {
+    ///             when.path("/orders");
+    ///             then.status(201);
+    ///         })
+    ///         .await;
+    ///
+    ///     // Act
+    ///     reqwest::get(&server.url("/orders")).await.unwrap();
+    ///
+    ///     // Assert: the only received request matched the mock
+    ///     assert!(mock.closest_match_async().await.is_none());
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if there are issues accessing the mock server.
+    pub async fn closest_match_async(&self) -> Option<ClosestMatch> {
+        let server_adapter = self.server.server_adapter.as_ref().unwrap();
+
+        let active_mock = server_adapter
+            .fetch_mock(self.id)
+            .await
+            .expect("cannot deserialize mock server response");
+
+        server_adapter
+            .verify(&active_mock.definition.request)
+            .await
+            .expect("Cannot contact mock server")
+    }
+
     /// Removes the specified mock from the mock server. This operation is useful for testing scenarios
     /// where the mock should no longer intercept requests, effectively simulating an environment
     /// where certain endpoints may go offline or change behavior dynamically during testing.
@@ -706,14 +1074,12 @@ impl<'a> MockSet<'a> {
     }
 
     pub async fn delete_async(&self) {
-        for id in &self.ids {
-            self.server
-                .server_adapter
-                .as_ref()
-                .unwrap()
-                .delete_mock(*id)
-                .await
-                .expect("could not delete mock from server");
-        }
+        self.server
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .delete_mocks(&self.ids)
+            .await
+            .expect("could not delete mocks from server");
     }
 }
@@ -12,17 +12,31 @@ pub use adapter::remote::RemoteMockServerAdapter;
 #[cfg(feature = "record")]
 pub use proxy::{Recording, RecordingRuleBuilder};
 
+#[cfg(feature = "proxy")]
+pub use callback::CallbackRequestBuilder;
+#[cfg(feature = "protobuf")]
+pub use protobuf::ProtobufDescriptor;
 #[cfg(feature = "proxy")]
 pub use proxy::{ForwardingRule, ForwardingRuleBuilder, ProxyRule, ProxyRuleBuilder};
 
 use crate::common;
 pub use mock::{Mock, MockExt};
+pub use pool::{MockServerPool, PooledMockServer};
 pub use server::MockServer;
-pub use spec::{Then, When};
+pub use spec::{ConditionGroup, Then, When};
 
 mod adapter;
+#[cfg(feature = "proxy")]
+mod callback;
+#[cfg(feature = "record")]
+mod har;
 mod mock;
-mod output;
+#[cfg(feature = "openapi")]
+mod openapi;
+pub(crate) mod output;
+mod pool;
+#[cfg(feature = "protobuf")]
+mod protobuf;
 mod proxy;
 mod server;
 pub mod spec;
@@ -30,4 +44,4 @@ pub mod spec;
 /// Type alias for [regex::Regex](../regex/struct.Regex.html).
 pub type Regex = common::data::HttpMockRegex;
 
-pub use crate::common::data::Method;
+pub use crate::common::data::{ClosestMatch, Fault, Method, Mismatch, MockStats};
@@ -0,0 +1,328 @@
+use serde_json::{Map, Value};
+
+use crate::common::{
+    data::{HttpMockRegex, MockDefinition, MockServerHttpResponse, RequestRequirements},
+    util::HttpMockBytes,
+};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// The maximum schema nesting depth used when deriving example values. Deeper (e.g., recursive)
+/// schemas are represented by `null`.
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+/// Creates one mock definition per operation of an OpenAPI 3.x document (in YAML or JSON format).
+/// Operations that cannot be represented as a mock (e.g., because they do not declare any
+/// response or refer to external documents) are skipped.
+pub(crate) fn mock_definitions_from_openapi(content: &str) -> Result<Vec<MockDefinition>, String> {
+    let doc: Value = serde_yaml::from_str(content)
+        .map_err(|err| format!("cannot parse OpenAPI document: {}", err))?;
+
+    match doc.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => {}
+        Some(version) => return Err(format!("unsupported OpenAPI version: {}", version)),
+        None => return Err("document is not an OpenAPI 3 document".to_string()),
+    }
+
+    let base_path = base_path(&doc);
+    let paths = match doc.get("paths").and_then(Value::as_object) {
+        Some(paths) => paths,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut definitions = Vec::new();
+    for (path, path_item) in paths {
+        let path_item = match resolve(&doc, path_item) {
+            Some(item) => item,
+            None => {
+                log::warn!("skipping OpenAPI path '{}': cannot resolve reference", path);
+                continue;
+            }
+        };
+
+        for method in METHODS {
+            let operation = match path_item.get(*method) {
+                Some(operation) => operation,
+                None => continue,
+            };
+
+            match operation_to_mock(&doc, &base_path, path, method, operation) {
+                Some(definition) => definitions.push(definition),
+                None => log::warn!(
+                    "skipping OpenAPI operation {} {}: cannot derive a response",
+                    method.to_uppercase(),
+                    path
+                ),
+            }
+        }
+    }
+
+    Ok(definitions)
+}
+
+fn operation_to_mock(
+    doc: &Value,
+    base_path: &str,
+    path: &str,
+    method: &str,
+    operation: &Value,
+) -> Option<MockDefinition> {
+    let (status, response) = select_response(doc, operation)?;
+
+    let mut request = RequestRequirements::new();
+    request.method = Some(method.to_uppercase());
+
+    let full_path = format!("{}{}", base_path, path);
+    if full_path.contains('{') {
+        request.path_matches = Some(vec![HttpMockRegex::from(path_template_regex(&full_path))]);
+    } else {
+        request.path = Some(full_path);
+    }
+
+    let mut response_template = MockServerHttpResponse::new();
+    response_template.status = Some(status);
+
+    let content = response.get("content").and_then(Value::as_object);
+    if let Some((media_type, media)) = content.and_then(select_media_type) {
+        if let Some(body) = media_example(doc, media) {
+            let body = match (&body, is_json_media_type(media_type)) {
+                (Value::String(text), false) => text.clone(),
+                _ => body.to_string(),
+            };
+
+            response_template.headers =
+                Some(vec![("content-type".to_string(), media_type.to_string())]);
+            response_template.body = Some(HttpMockBytes::from(bytes::Bytes::from(body)));
+        }
+    }
+
+    Some(MockDefinition {
+        request,
+        response: response_template,
+    })
+}
+
+/// Selects the first success (2xx) response, or the first response with an explicit status code
+/// if no success response is declared. A `default` response is served with status code 200.
+fn select_response<'a>(doc: &'a Value, operation: &'a Value) -> Option<(u16, &'a Value)> {
+    let responses = operation.get("responses")?.as_object()?;
+
+    let mut codes: Vec<(u16, &Value)> = responses
+        .iter()
+        .filter_map(|(code, res)| code.parse::<u16>().ok().map(|code| (code, res)))
+        .collect();
+    codes.sort_by_key(|(code, _)| *code);
+
+    let (status, response) = codes
+        .iter()
+        .find(|(code, _)| (200..300).contains(code))
+        .or_else(|| codes.first())
+        .copied()
+        .or_else(|| responses.get("default").map(|res| (200, res)))?;
+
+    Some((status, resolve(doc, response)?))
+}
+
+fn select_media_type(content: &Map<String, Value>) -> Option<(&String, &Value)> {
+    content
+        .iter()
+        .find(|(media_type, _)| is_json_media_type(media_type))
+        .or_else(|| content.iter().next())
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    let media_type = media_type.to_lowercase();
+    media_type.starts_with("application/json") || media_type.contains("+json")
+}
+
+fn media_example(doc: &Value, media: &Value) -> Option<Value> {
+    if let Some(example) = media.get("example") {
+        return Some(example.clone());
+    }
+
+    let first_example = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next());
+    if let Some(example) = first_example.and_then(|e| resolve(doc, e)) {
+        if let Some(value) = example.get("value") {
+            return Some(value.clone());
+        }
+    }
+
+    media
+        .get("schema")
+        .map(|schema| schema_example(doc, schema, 0))
+}
+
+/// Derives an example value from a JSON schema.
+fn schema_example(doc: &Value, schema: &Value, depth: usize) -> Value {
+    let schema = match resolve(doc, schema) {
+        Some(schema) if depth < MAX_SCHEMA_DEPTH => schema,
+        _ => return Value::Null,
+    };
+
+    for key in &["example", "default"] {
+        if let Some(value) = schema.get(*key) {
+            return value.clone();
+        }
+    }
+
+    if let Some(value) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return value.clone();
+    }
+
+    if let Some(schemas) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for schema in schemas {
+            if let Value::Object(properties) = schema_example(doc, schema, depth + 1) {
+                merged.extend(properties);
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    for key in &["oneOf", "anyOf"] {
+        if let Some(schema) = schema
+            .get(*key)
+            .and_then(Value::as_array)
+            .and_then(|schemas| schemas.first())
+        {
+            return schema_example(doc, schema, depth + 1);
+        }
+    }
+
+    let schema_type = schema
+        .get("type")
+        .and_then(Value::as_str)
+        .or_else(|| schema.get("properties").map(|_| "object"));
+
+    match schema_type {
+        Some("object") => {
+            let mut object = Map::new();
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, property) in properties {
+                    object.insert(name.clone(), schema_example(doc, property, depth + 1));
+                }
+            }
+            Value::Object(object)
+        }
+        Some("array") => match schema.get("items") {
+            Some(items) => Value::Array(vec![schema_example(doc, items, depth + 1)]),
+            None => Value::Array(Vec::new()),
+        },
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::from(true),
+        Some("string") => Value::from(match schema.get("format").and_then(Value::as_str) {
+            Some("date-time") => "1970-01-01T00:00:00Z",
+            Some("date") => "1970-01-01",
+            Some("uuid") => "00000000-0000-0000-0000-000000000000",
+            Some("email") => "user@example.com",
+            Some("uri") => "https://example.com",
+            _ => "string",
+        }),
+        _ => Value::Null,
+    }
+}
+
+/// Resolves local references (`$ref: '#/components/...'`). Returns `None` if the reference
+/// cannot be resolved, e.g. because it points to an external document.
+fn resolve<'a>(doc: &'a Value, value: &'a Value) -> Option<&'a Value> {
+    let mut value = value;
+
+    // Limits the number of followed references to avoid endless loops on cyclic references.
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let reference = match value.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference,
+            None => return Some(value),
+        };
+
+        let pointer = reference.strip_prefix('#')?;
+        value = doc.pointer(pointer)?;
+    }
+
+    None
+}
+
+/// Returns the path of the first server URL (e.g., `/v1` for `https://api.example.com/v1`)
+/// that all operation paths are relative to.
+fn base_path(doc: &Value) -> String {
+    let url = doc
+        .get("servers")
+        .and_then(Value::as_array)
+        .and_then(|servers| servers.first())
+        .and_then(|server| server.get("url"))
+        .and_then(Value::as_str)
+        .unwrap_or("");
+
+    let path = match url.find("://") {
+        Some(idx) => url[idx + 3..].find('/').map_or("", |p| &url[idx + 3 + p..]),
+        None => url,
+    };
+
+    // Server variables cannot be resolved to a concrete path.
+    if path.contains('{') {
+        return String::new();
+    }
+
+    path.trim_end_matches('/').to_string()
+}
+
+/// Converts an OpenAPI path template (e.g., `/users/{id}`) into a regex that matches the path
+/// with arbitrary values for each path parameter.
+fn path_template_regex(path: &str) -> String {
+    let mut regex = String::from("^");
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        regex.push_str(&regex::escape(&rest[..start]));
+        regex.push_str("[^/]+");
+        rest = match rest[start..].find('}') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+
+    regex.push_str(&regex::escape(rest));
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod test {
+    use crate::api::openapi::{base_path, path_template_regex};
+    use serde_json::json;
+
+    #[test]
+    fn path_template_regex_test() {
+        assert_eq!(path_template_regex("/users"), "^/users$");
+        assert_eq!(
+            path_template_regex("/users/{id}/posts/{post.id}"),
+            "^/users/[^/]+/posts/[^/]+$"
+        );
+        assert_eq!(
+            path_template_regex("/files/{name}.json"),
+            "^/files/[^/]+\\.json$"
+        );
+    }
+
+    #[test]
+    fn base_path_test() {
+        let doc = |url: &str| json!({ "servers": [{ "url": url }] });
+        assert_eq!(base_path(&doc("https://api.example.com/v1/")), "/v1");
+        assert_eq!(base_path(&doc("https://api.example.com")), "");
+        assert_eq!(base_path(&doc("/api")), "/api");
+        assert_eq!(
+            base_path(&doc("https://{region}.example.com/{version}")),
+            ""
+        );
+        assert_eq!(base_path(&json!({})), "");
+    }
+}
@@ -1,9 +1,9 @@
-use std::io::Write;
+use std::{collections::BTreeMap, io::Write};
 
 use crate::common::{
     data::{
-        ClosestMatch, Diff, DiffResult, FunctionComparison, KeyValueComparison,
-        KeyValueComparisonKeyValuePair, Mismatch, SingleValueComparison,
+        ClosestMatch, Diff, DiffResult, FunctionComparison, HttpMockRequest, KeyValueComparison,
+        KeyValueComparisonKeyValuePair, Mismatch, SingleValueComparison, UnmatchedRequest,
     },
     util::title_case,
 };
@@ -16,15 +16,30 @@ use colored::Colorize;
 
 const QUOTED_TEXT: &'static str = "quoted for better readability";
 
-pub fn fail_with(actual_hits: usize, expected_hits: usize, closest_match: Option<ClosestMatch>) {
+pub fn fail_with(
+    actual_hits: usize,
+    expected_hits: usize,
+    closest_match: Option<ClosestMatch>,
+    shadowed_by: &BTreeMap<usize, usize>,
+) {
+    let shadowing_note = create_shadowing_note(shadowed_by);
+
     match closest_match {
-        None => assert!(false, "No request has been received by the mock server."),
+        None if shadowed_by.is_empty() => {
+            assert!(false, "No request has been received by the mock server.")
+        }
+        None => assert!(
+            false,
+            "{} of {} expected requests matched the mock specification.\n{}",
+            actual_hits, expected_hits, shadowing_note
+        ),
         Some(closest_match) => {
             let mut output = String::new();
             output.push_str(&format!(
                 "{} of {} expected requests matched the mock specification.\n",
                 actual_hits, expected_hits
             ));
+            output.push_str(&shadowing_note);
             output.push_str(&format!(
                 "Here is a comparison with the most similar unmatched request (request number {}): \n\n",
                 closest_match.request_index + 1
@@ -53,6 +68,86 @@ pub fn fail_with(actual_hits: usize, expected_hits: usize, closest_match: Option
     }
 }
 
+pub fn fail_with_unmatched_requests(unmatched_requests: &[UnmatchedRequest]) {
+    let mut output = format!(
+        "{} request(s) did not match any mock.\n",
+        unmatched_requests.len()
+    );
+
+    for unmatched in unmatched_requests {
+        output.push_str(&format!(
+            "\nRequest number {}: {} {}\n",
+            unmatched.request_index + 1,
+            unmatched.request.method(),
+            unmatched.request.uri()
+        ));
+
+        match unmatched.closest_mock_id {
+            None => output.push_str("No mocks were defined on the mock server.\n"),
+            Some(mock_id) => {
+                output.push_str(&format!(
+                    "Here is a comparison with the most similar mock (mock ID {}): \n\n",
+                    mock_id
+                ));
+
+                for (idx, mm) in unmatched.mismatches.iter().enumerate() {
+                    output.push_str(&create_mismatch_output(idx, mm).0);
+                }
+            }
+        }
+    }
+
+    assert!(false, "{}", output)
+}
+
+pub fn fail_with_unexpected_calls(call_count: usize, requests: &[HttpMockRequest]) {
+    let mut output = format!(
+        "Expected the mock not to be called, but {} request(s) matched the mock specification.\n",
+        call_count
+    );
+
+    if requests.len() < call_count {
+        output.push_str(&format!(
+            "Only {} of them are still in the request history.\n",
+            requests.len()
+        ));
+    }
+
+    for (idx, request) in requests.iter().enumerate() {
+        output.push_str(&format!(
+            "\nRequest {}: {} {}\n",
+            idx + 1,
+            request.method(),
+            request.uri()
+        ));
+
+        for (name, value) in request.headers_vec() {
+            output.push_str(&format!("{}: {}\n", name, value));
+        }
+
+        let body = request.body_string();
+        if !body.is_empty() {
+            output.push_str(&format!("\n{}\n", body));
+        }
+    }
+
+    assert!(false, "{}", output)
+}
+
+fn create_shadowing_note(shadowed_by: &BTreeMap<usize, usize>) -> String {
+    if shadowed_by.is_empty() {
+        return String::new();
+    }
+
+    let mock_ids: Vec<String> = shadowed_by.keys().map(|id| id.to_string()).collect();
+
+    format!(
+        "{} request(s) matching this mock were served by a mock with a higher priority (mock ID(s): {}).\n",
+        shadowed_by.values().sum::<usize>(),
+        mock_ids.join(", ")
+    )
+}
+
 pub fn create_mismatch_output(
     idx: usize,
     mismatch: &Mismatch,
@@ -115,6 +210,10 @@ fn handle_single_value_comparison(
 
     writeln!(tw, "\nReceived:\n{}", comparison.actual).unwrap();
 
+    if let Some(reason) = &mismatch.reason {
+        writeln!(tw, "\nReason:\n{}", reason).unwrap();
+    }
+
     (
         comparison.expected.to_string(),
         comparison.actual.to_string(),
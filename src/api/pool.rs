@@ -0,0 +1,114 @@
+use std::{
+    ops::Deref,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use async_object_pool::Pool;
+
+use crate::{
+    api::{
+        server::{start_local_server, MockServer},
+        MockServerAdapter,
+    },
+    common::util::Join,
+    server::HttpMockServerBuilder,
+};
+
+/// A bounded pool of local mock servers that can be reused across tests.
+///
+/// Servers are started lazily the first time they are needed, up to `max_servers` servers.
+/// Each call to [`get`](#method.get) hands out a [`PooledMockServer`] that has exclusive
+/// access to one of the servers. When the `PooledMockServer` is dropped, the server is reset
+/// and returned to the pool. If all servers are in use, [`get`](#method.get) blocks until a
+/// server is returned.
+///
+/// Unlike the shared pool used by [`MockServer::start`](struct.MockServer.html#method.start),
+/// a `MockServerPool` is owned by the caller, so its size does not depend on the
+/// `HTTPMOCK_MAX_SERVERS` environment variable. The servers shut down when the pool and all
+/// servers handed out by it have been dropped.
+///
+/// # Example
+/// ```rust
+/// use httpmock::MockServerPool;
+///
+/// let pool = MockServerPool::new(2);
+///
+/// let server = pool.get();
+/// let mock = server.mock(|when, then| {
+///     when.path("/hello");
+///     then.status(200);
+/// });
+///
+/// let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+/// assert_eq!(response.status(), 200);
+/// mock.assert();
+/// ```
+#[derive(Clone)]
+pub struct MockServerPool {
+    pool: Arc<Pool<Arc<dyn MockServerAdapter + Send + Sync>>>,
+}
+
+impl MockServerPool {
+    /// Creates a new pool that starts at most `max_servers` mock servers.
+    ///
+    /// # Panics
+    /// This method will panic if `max_servers` is zero.
+    pub fn new(max_servers: usize) -> Self {
+        assert!(
+            max_servers > 0,
+            "a mock server pool requires at least one server"
+        );
+        Self {
+            pool: Arc::new(Pool::new(max_servers)),
+        }
+    }
+
+    /// Asynchronously takes a mock server from the pool. A new server is started if no server
+    /// is free and the pool has not reached its maximum size yet. Otherwise, this method waits
+    /// until another `PooledMockServer` is dropped.
+    pub async fn get_async(&self) -> PooledMockServer {
+        let adapter = self
+            .pool
+            .take_or_create(|| start_local_server(HttpMockServerBuilder::new()).join())
+            .await;
+
+        PooledMockServer {
+            server: MockServer::from(adapter, self.pool.clone()).await,
+        }
+    }
+
+    /// Takes a mock server from the pool. See [`get_async`](#method.get_async) for details.
+    ///
+    /// Attention: *THIS METHOD WILL BLOCK* the executing function until a server is available.
+    pub fn get(&self) -> PooledMockServer {
+        self.get_async().join()
+    }
+}
+
+/// A mock server that was taken from a [`MockServerPool`]. It can be used just like a
+/// [`MockServer`]. When it is dropped, the server is reset and returned to the pool. This also
+/// happens when a test panics, so that a failing test does not take a server away from the pool.
+pub struct PooledMockServer {
+    server: MockServer,
+}
+
+impl Deref for PooledMockServer {
+    type Target = MockServer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.server
+    }
+}
+
+impl Drop for PooledMockServer {
+    fn drop(&mut self) {
+        // This may run while a failing test is unwinding. A panic in here would abort the
+        // process, so a failed reset is only logged. The server is returned to the pool
+        // either way and is reset again before it is handed out the next time.
+        let server = &self.server;
+        if panic::catch_unwind(AssertUnwindSafe(|| server.reset())).is_err() {
+            log::warn!("cannot reset pooled mock server before returning it to the pool");
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bytes::Bytes;
+
+use crate::{common::util::HttpMockBytes, server::matchers::protobuf::message_descriptor};
+
+/// Describes the protobuf message type of request bodies, so that their fields can be matched
+/// using [`When::protobuf_field`](crate::When::protobuf_field).
+///
+/// A descriptor consists of an encoded `FileDescriptorSet`, as produced by
+/// `protoc --include_imports --descriptor_set_out=...` or `prost-build`, and the fully qualified
+/// name of a message type that is defined in it.
+///
+/// **Note:** This type is only available if the `protobuf` feature is enabled.
+#[derive(Clone, Debug)]
+pub struct ProtobufDescriptor {
+    pub(crate) file_descriptor_set: HttpMockBytes,
+    pub(crate) message_name: String,
+}
+
+impl ProtobufDescriptor {
+    /// Creates a descriptor for the message type `message_name` (e.g., `shop.v1.Order`).
+    ///
+    /// # Panics
+    /// Panics if `file_descriptor_set` is not a valid encoded `FileDescriptorSet` or does not
+    /// define the message type.
+    pub fn new<IntoBytes: Into<Vec<u8>>, IntoString: Into<String>>(
+        file_descriptor_set: IntoBytes,
+        message_name: IntoString,
+    ) -> Self {
+        let file_descriptor_set = file_descriptor_set.into();
+        let message_name = message_name.into();
+
+        if let Err(err) = message_descriptor(&file_descriptor_set, &message_name) {
+            panic!("{}", err);
+        }
+
+        Self {
+            file_descriptor_set: HttpMockBytes::from(Bytes::from(file_descriptor_set)),
+            message_name,
+        }
+    }
+
+    /// Returns the fully qualified name of the message type.
+    pub fn message_name(&self) -> &str {
+        &self.message_name
+    }
+}
@@ -3,36 +3,49 @@ use crate::api::spec::{Then, When};
 use crate::api::RemoteMockServerAdapter;
 #[cfg(feature = "remote")]
 use crate::common::http::HttpMockHttpClient;
+#[cfg(feature = "remote")]
+use std::{collections::HashMap, sync::Mutex};
 
 use crate::{
-    api::{LocalMockServerAdapter, MockServerAdapter},
+    api::{output, LocalMockServerAdapter, MockServerAdapter},
     common::{
-        data::{MockDefinition, MockServerHttpResponse, RequestRequirements},
+        data::{
+            ActiveMock, MockDefinition, MockServerHttpResponse, RequestRequirements, StateSnapshot,
+        },
         runtime,
-        util::{read_env, with_retry, Join},
+        util::{read_env, read_file_async, with_retry, write_file, Join},
     },
 };
 
 #[cfg(feature = "proxy")]
 use crate::{
     api::proxy::{ForwardingRule, ForwardingRuleBuilder, ProxyRule, ProxyRuleBuilder},
-    common::{
-        data::{ForwardingRuleConfig, ProxyRuleConfig},
-        util::read_file_async,
-    },
+    common::data::{ForwardingRuleConfig, ProxyRuleConfig},
 };
 
 #[cfg(feature = "record")]
 use crate::api::{
-    common::data::RecordingRuleConfig,
-    mock::MockSet,
+    common::data::{PlaybackConfig, RecordingRuleConfig},
     proxy::{Recording, RecordingRuleBuilder},
 };
 
+#[cfg(any(feature = "record", feature = "openapi"))]
+use crate::api::mock::MockSet;
+
+#[cfg(feature = "openapi")]
+use crate::{api::openapi::mock_definitions_from_openapi, common::util::read_file};
+
+#[cfg(feature = "record")]
+use crate::{
+    api::har::mock_definitions_from_har, server::persistence::deserialize_mock_def_from_yaml,
+};
 #[cfg(feature = "record")]
 use std::path::PathBuf;
 
-use crate::server::{state::HttpMockStateManager, HttpMockServerBuilder};
+use crate::server::{
+    state::{HttpMockStateManager, DEFAULT_HISTORY_LIMIT},
+    HttpMockServerBuilder,
+};
 
 use crate::Mock;
 use async_object_pool::Pool;
@@ -41,6 +54,7 @@ use std::{
     cell::Cell,
     future::pending,
     net::{SocketAddr, ToSocketAddrs},
+    path::Path,
     rc::Rc,
     sync::Arc,
     thread,
@@ -63,7 +77,7 @@ pub struct MockServer {
 }
 
 impl MockServer {
-    async fn from(
+    pub(crate) async fn from(
         server_adapter: Arc<dyn MockServerAdapter + Send + Sync>,
         pool: Arc<Pool<Arc<dyn MockServerAdapter + Send + Sync>>>,
     ) -> Self {
@@ -98,7 +112,8 @@ impl MockServer {
             .find(|addr| addr.is_ipv4())
             .expect("Not able to resolve the provided host name to an IPv4 address");
 
-        let adapter = REMOTE_SERVER_POOL_REF
+        let pool = remote_server_pool(addr);
+        let adapter = pool
             .take_or_create(|| {
                 Arc::new(RemoteMockServerAdapter::new(
                     addr,
@@ -106,7 +121,7 @@ impl MockServer {
                 ))
             })
             .await;
-        Self::from(adapter, REMOTE_SERVER_POOL_REF.clone()).await
+        Self::from(adapter, pool).await
     }
 
     /// Synchronously connects to a remote mock server running in standalone mode.
@@ -215,6 +230,143 @@ impl MockServer {
         Self::start_async().join()
     }
 
+    /// Starts a new `MockServer` asynchronously that is configured using the provided
+    /// [`HttpMockServerBuilder`](crate::server::HttpMockServerBuilder).
+    ///
+    /// Unlike [`start_async`](#method.start_async), the server is not taken from the pool of
+    /// mock servers, but is started specifically for the caller. The server is shut down when
+    /// the returned `MockServer` goes out of scope. This is useful if a test requires server
+    /// settings that differ from the defaults, such as a custom request history limit or
+    /// named matchers.
+    ///
+    /// # Parameters
+    /// - `builder`: The builder holding the server configuration.
+    ///
+    /// # Returns
+    /// An instance of `Self` representing the started mock server.
+    ///
+    /// # Panics
+    /// This method will panic if the server cannot be built or started.
+    pub async fn start_with_async(builder: HttpMockServerBuilder) -> Self {
+        let adapter = start_local_server(builder).await;
+        Self::from(adapter, Arc::new(Pool::new(1))).await
+    }
+
+    /// Starts a new `MockServer` synchronously that is configured using the provided
+    /// [`HttpMockServerBuilder`](crate::server::HttpMockServerBuilder).
+    /// See [`start_with_async`](#method.start_with_async) for details.
+    ///
+    /// # Parameters
+    /// - `builder`: The builder holding the server configuration.
+    ///
+    /// # Returns
+    /// An instance of `Self` representing the started mock server.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::{server::HttpMockServerBuilder, MockServer};
+    ///
+    /// // Keep at most the last two requests in the request history
+    /// let server = MockServer::start_with(HttpMockServerBuilder::new().history_limit(2));
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// for _ in 0..3 {
+    ///     reqwest::blocking::get(server.url("/hello")).unwrap();
+    /// }
+    ///
+    /// // Call counts are not affected by the history limit
+    /// mock.assert_calls(3);
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if the server cannot be built or started.
+    pub fn start_with(builder: HttpMockServerBuilder) -> MockServer {
+        Self::start_with_async(builder).join()
+    }
+
+    /// Starts a new `MockServer` as a task on the given Tokio runtime instead of on a dedicated
+    /// thread with its own runtime (see [`start_async`](#method.start_async)). This is useful
+    /// for test frameworks and applications that already own a Tokio runtime and want all
+    /// tasks to run on it, e.g., to control time with `tokio::time::pause` or to avoid
+    /// starting additional threads.
+    ///
+    /// Like [`start_with_async`](#method.start_with_async), the server is not taken from the
+    /// pool of mock servers. It is shut down when the returned `MockServer` goes out of scope.
+    ///
+    /// # Supported executors
+    /// This method requires a Tokio runtime, which includes executors that are built on Tokio,
+    /// such as `actix-rt` (pass `tokio::runtime::Handle::current()` from within an actix
+    /// test). The server only makes progress while the runtime is running, so requests to it
+    /// must be sent asynchronously. Blocking clients (e.g., `reqwest::blocking`) deadlock on a
+    /// single-threaded runtime, because the server cannot run while the runtime is blocked.
+    ///
+    /// For other executors, such as `async-std`, use [`start_async`](#method.start_async) or
+    /// [`start_with_async`](#method.start_with_async), which do not depend on the executor
+    /// the caller runs on.
+    ///
+    /// # Parameters
+    /// - `handle`: The handle of the Tokio runtime to run the server on.
+    ///
+    /// # Returns
+    /// An instance of `Self` representing the started mock server.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let rt = tokio::runtime::Builder::new_current_thread()
+    ///     .enable_all()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_on(tokio::runtime::Handle::current()).await;
+    ///
+    ///     let mock = server
+    ///         .mock_async(|when, then| {
+    ///             when.path("/hello");
+    ///             then.status(200);
+    ///         })
+    ///         .await;
+    ///
+    ///     let response = reqwest::get(server.url("/hello")).await.unwrap();
+    ///
+    ///     mock.assert_async().await;
+    ///     assert_eq!(response.status(), 200);
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if the server cannot be built or started.
+    pub async fn start_on(handle: tokio::runtime::Handle) -> Self {
+        Self::start_with_on(HttpMockServerBuilder::new(), handle).await
+    }
+
+    /// Starts a new `MockServer` that is configured using the provided
+    /// [`HttpMockServerBuilder`](crate::server::HttpMockServerBuilder) as a task on the given
+    /// Tokio runtime. See [`start_on`](#method.start_on) for details.
+    ///
+    /// # Parameters
+    /// - `builder`: The builder holding the server configuration.
+    /// - `handle`: The handle of the Tokio runtime to run the server on.
+    ///
+    /// # Returns
+    /// An instance of `Self` representing the started mock server.
+    ///
+    /// # Panics
+    /// This method will panic if the server cannot be built or started.
+    pub async fn start_with_on(
+        builder: HttpMockServerBuilder,
+        handle: tokio::runtime::Handle,
+    ) -> Self {
+        let adapter = start_local_server_on(builder, Some(handle)).await;
+        Self::from(adapter, Arc::new(Pool::new(1))).await
+    }
+
     /// Returns the hostname of the `MockServer`.
     ///
     /// By default, this is `127.0.0.1`. In standalone mode, the hostname will be
@@ -238,6 +390,10 @@ impl MockServer {
 
     /// Returns the TCP port that the mock server is listening on.
     ///
+    /// The server is bound before [`start`](#method.start) or [`start_async`](#method.start_async)
+    /// return, so if the port was chosen by the operating system, the returned value is the
+    /// assigned port and the server accepts connections on it right away.
+    ///
     /// # Returns
     /// A `u16` representing the port number of the `MockServer`.
     ///
@@ -278,6 +434,8 @@ impl MockServer {
 
     /// Builds the URL for a specific path on the mock server.
     ///
+    /// IPv6 addresses are enclosed in square brackets (e.g., `https://[::1]:34567/hello`).
+    ///
     /// # Arguments
     /// * `path` - A string slice representing the specific path on the mock server.
     ///
@@ -299,11 +457,13 @@ impl MockServer {
     /// ```
     #[cfg(feature = "https")]
     pub fn url<S: Into<String>>(&self, path: S) -> String {
-        return format!("https://{}{}", self.address(), path.into());
+        return format!("https://{}{}", url_authority(self.address()), path.into());
     }
 
     /// Builds the URL for a specific path on the mock server.
     ///
+    /// IPv6 addresses are enclosed in square brackets (e.g., `http://[::1]:34567/hello`).
+    ///
     /// # Arguments
     /// * `path` - A string slice representing the specific path on the mock server.
     ///
@@ -327,7 +487,7 @@ impl MockServer {
     /// ```
     #[cfg(not(feature = "https"))]
     pub fn url<S: Into<String>>(&self, path: S) -> String {
-        return format!("http://{}{}", self.address(), path.into());
+        return format!("http://{}{}", url_authority(self.address()), path.into());
     }
 
     /// Builds the base URL for the mock server.
@@ -359,6 +519,83 @@ impl MockServer {
         self.url("")
     }
 
+    /// Returns the URL scheme that the URL builder methods of the mock server use.
+    ///
+    /// # Returns
+    /// `"https"` if the `https` feature is enabled and `"http"` otherwise.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let expected_scheme = if cfg!(feature = "https") { "https" } else { "http" };
+    /// assert_eq!(expected_scheme, server.scheme());
+    /// assert!(server.base_url().starts_with(server.scheme()));
+    /// ```
+    pub fn scheme(&self) -> &'static str {
+        if cfg!(feature = "https") {
+            "https"
+        } else {
+            "http"
+        }
+    }
+
+    /// Builds the URL for a specific path on the mock server and appends the provided query
+    /// parameters. Parameter names and values are URL-encoded, so they can contain reserved
+    /// characters such as `&`, `=` or spaces.
+    ///
+    /// # Arguments
+    /// * `path` - The path on the mock server. If it already contains a query string, the
+    ///   parameters are appended to it.
+    /// * `query` - The query parameters as name-value pairs, in the order they should appear in
+    ///   the URL.
+    ///
+    /// # Returns
+    /// A `String` representing the full URL for the given path and query parameters.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/search")
+    ///         .query_param("q", "rock & roll")
+    ///         .query_param("page", "2");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let url = server.url_with_query("/search", &[("q", "rock & roll"), ("page", "2")]);
+    /// assert_eq!(url, format!("{}/search?q=rock+%26+roll&page=2", server.base_url()));
+    ///
+    /// let response = reqwest::blocking::get(url).unwrap();
+    ///
+    /// assert_eq!(response.status(), 200);
+    /// mock.assert();
+    /// ```
+    pub fn url_with_query<S, K, V>(&self, path: S, query: &[(K, V)]) -> String
+    where
+        S: Into<String>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut url = self.url(path);
+        if query.is_empty() {
+            return url;
+        }
+
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query.iter().map(|(k, v)| (k.as_ref(), v.as_ref())))
+            .finish();
+
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&query);
+        url
+    }
+
     /// Creates a [Mock](struct.Mock.html) object on the mock server.
     ///
     /// # Arguments
@@ -427,26 +664,178 @@ impl MockServer {
     where
         SpecFn: FnOnce(When, Then),
     {
-        let mut req = Rc::new(Cell::new(RequestRequirements::new()));
-        let mut res = Rc::new(Cell::new(MockServerHttpResponse::new()));
-
-        spec_fn(
-            When {
-                expectations: req.clone(),
-            },
-            Then {
-                response_template: res.clone(),
-            },
-        );
-
         let response = self
             .server_adapter
             .as_ref()
             .unwrap()
-            .create_mock(&MockDefinition {
-                request: req.take(),
-                response: res.take(),
+            .create_mock(&mock_definition(spec_fn))
+            .await
+            .expect("Cannot deserialize mock server response");
+
+        Mock {
+            id: response.id,
+            server: self,
+        }
+    }
+
+    /// Creates multiple [Mock](struct.Mock.html) objects on the mock server in a single
+    /// operation. A remote mock server receives all mocks in one request. Either all mocks are
+    /// created or none of them.
+    ///
+    /// # Arguments
+    /// * `specs` - Closures that each take a `When` and `Then` to configure one mock.
+    ///
+    /// # Returns
+    /// The created `Mock` objects, in the same order as the closures.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::{MockServer, Then, When};
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let paths = ["/one", "/two", "/three"];
+    /// let mocks = server.with_mocks(paths.iter().map(|path| {
+    ///     move |when: When, then: Then| {
+    ///         when.path(*path);
+    ///         then.status(200).body(*path);
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(get(&server.url("/two")).unwrap().text().unwrap(), "/two");
+    ///
+    /// mocks[0].assert_calls(0);
+    /// mocks[1].assert();
+    /// ```
+    pub fn with_mocks<I, SpecFn>(&self, specs: I) -> Vec<Mock>
+    where
+        I: IntoIterator<Item = SpecFn>,
+        SpecFn: FnOnce(When, Then),
+    {
+        self.with_mocks_async(specs).join()
+    }
+
+    /// Creates multiple [Mock](struct.Mock.html) objects on the mock server asynchronously in a
+    /// single operation. A remote mock server receives all mocks in one request. Either all
+    /// mocks are created or none of them.
+    ///
+    /// # Arguments
+    /// * `specs` - Closures that each take a `When` and `Then` to configure one mock.
+    ///
+    /// # Returns
+    /// The created `Mock` objects, in the same order as the closures.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::{MockServer, Then, When};
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     let specs: Vec<Box<dyn Fn(When, Then)>> = vec![
+    ///         Box::new(|when, then| {
+    ///             when.path("/one");
+    ///             then.status(200);
+    ///         }),
+    ///         Box::new(|when, then| {
+    ///             when.path("/two");
+    ///             then.status(201);
+    ///         }),
+    ///     ];
+    ///     let mocks = server.with_mocks_async(specs).await;
+    ///
+    ///     assert_eq!(get(&server.url("/two")).await.unwrap().status(), 201);
+    ///
+    ///     mocks[1].assert_async().await;
+    /// });
+    /// ```
+    pub async fn with_mocks_async<'a, I, SpecFn>(&'a self, specs: I) -> Vec<Mock<'a>>
+    where
+        I: IntoIterator<Item = SpecFn>,
+        SpecFn: FnOnce(When, Then),
+    {
+        let definitions: Vec<MockDefinition> = specs.into_iter().map(mock_definition).collect();
+
+        let active_mocks = self
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .create_mocks(&definitions)
+            .await
+            .expect("could not create mocks on server");
+
+        active_mocks
+            .into_iter()
+            .map(|active_mock| Mock {
+                id: active_mock.id,
+                server: self,
             })
+            .collect()
+    }
+
+    /// Creates a [Mock](struct.Mock.html) object on the mock server from a YAML mock definition.
+    /// The YAML document uses the same format as static mock files and recordings (see
+    /// [MockServer::playback_from_yaml](struct.MockServer.html#method.playback_from_yaml)), which
+    /// makes it possible to paste a mock definition inline into a test.
+    ///
+    /// The string must contain exactly one YAML document.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock_from_yaml_str(r#"
+    /// when:
+    ///   method: GET
+    ///   path: /hello
+    /// then:
+    ///   status: 200
+    ///   body: world
+    /// "#);
+    ///
+    /// let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.text().unwrap(), "world");
+    /// ```
+    ///
+    /// # Panics
+    /// This method will panic if the YAML document cannot be parsed into a mock definition. The
+    /// panic message contains the line and column of the error in the YAML document.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub fn mock_from_yaml_str(&self, yaml: &str) -> Mock {
+        self.mock_from_yaml_str_async(yaml).join()
+    }
+
+    /// Asynchronously creates a [Mock](struct.Mock.html) object on the mock server from a YAML
+    /// mock definition. See [MockServer::mock_from_yaml_str](struct.MockServer.html#method.mock_from_yaml_str)
+    /// for details.
+    ///
+    /// # Panics
+    /// This method will panic if the YAML document cannot be parsed into a mock definition.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub async fn mock_from_yaml_str_async(&self, yaml: &str) -> Mock<'_> {
+        let definition = deserialize_mock_def_from_yaml(yaml)
+            .unwrap_or_else(|err| panic!("cannot create mock from YAML: {}", err));
+
+        let response = self
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .create_mock(&definition)
             .await
             .expect("Cannot deserialize mock server response");
 
@@ -517,6 +906,629 @@ impl MockServer {
         }
     }
 
+    /// Clears the request history of the mock server, while keeping all [Mock](struct.Mock.html)
+    /// objects. This is useful in loop-based tests, where the same mocks are reused for every
+    /// iteration. Call counts of mocks are not reset.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// get(&server.url("/hello")).unwrap();
+    /// assert!(mock.last_request().is_some());
+    ///
+    /// server.reset_history();
+    ///
+    /// assert!(mock.last_request().is_none());
+    /// assert_eq!(get(&server.url("/hello")).unwrap().status(), 200);
+    /// ```
+    pub fn reset_history(&self) {
+        self.reset_history_async().join()
+    }
+
+    /// Clears the request history of the mock server, while keeping all [Mock](struct.Mock.html)
+    /// objects. Call counts of mocks are not reset.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     let mock = server.mock_async(|when, then| {
+    ///         when.path("/hello");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     get(&server.url("/hello")).await.unwrap();
+    ///     server.reset_history_async().await;
+    ///
+    ///     assert!(mock.last_request_async().await.is_none());
+    /// });
+    /// ```
+    pub async fn reset_history_async(&self) {
+        if let Some(server_adapter) = &self.server_adapter {
+            with_retry(3, || server_adapter.delete_history())
+                .await
+                .expect("Cannot reset mock server (task: delete request history).");
+        }
+    }
+
+    /// Verifies that every request in the request history of the mock server was served by a
+    /// mock. Requests that did not match any mock often indicate a bug in the code under test,
+    /// so this method complements [Mock::assert](struct.Mock.html#method.assert) with a check
+    /// that covers all requests the server received.
+    ///
+    /// Requests that were handled by a forwarding or proxy rule are not part of the request
+    /// history and are therefore not considered.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::get;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// get(server.url("/hello")).unwrap();
+    ///
+    /// server.assert_no_unmatched_requests();
+    /// ```
+    ///
+    /// # Panics
+    /// This method panics if the request history contains a request that did not match any mock.
+    /// The panic message contains a comparison of each such request with the mock that came
+    /// closest to matching it.
+    pub fn assert_no_unmatched_requests(&self) {
+        self.assert_no_unmatched_requests_async().join()
+    }
+
+    /// Verifies that every request in the request history of the mock server was served by a
+    /// mock. See [assert_no_unmatched_requests](#method.assert_no_unmatched_requests) for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::get;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server.mock_async(|when, then| {
+    ///         when.path("/hello");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     get(server.url("/hello")).await.unwrap();
+    ///
+    ///     server.assert_no_unmatched_requests_async().await;
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method panics if the request history contains a request that did not match any mock.
+    pub async fn assert_no_unmatched_requests_async(&self) {
+        let unmatched_requests = self
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .unmatched_requests()
+            .await
+            .expect("Cannot contact mock server");
+
+        if !unmatched_requests.is_empty() {
+            output::fail_with_unmatched_requests(&unmatched_requests);
+        }
+    }
+
+    /// Deletes all [Mock](struct.Mock.html) objects from the mock server, while keeping its
+    /// request history. Forwarding and proxy rules, recordings and default headers are not
+    /// affected either.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// server.reset_mocks();
+    ///
+    /// assert_eq!(get(&server.url("/hello")).unwrap().status(), 404);
+    /// ```
+    pub fn reset_mocks(&self) {
+        self.reset_mocks_async().join()
+    }
+
+    /// Deletes all [Mock](struct.Mock.html) objects from the mock server, while keeping its
+    /// request history.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server.mock_async(|when, then| {
+    ///         when.path("/hello");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     server.reset_mocks_async().await;
+    ///
+    ///     assert_eq!(get(&server.url("/hello")).await.unwrap().status(), 404);
+    /// });
+    /// ```
+    pub async fn reset_mocks_async(&self) {
+        if let Some(server_adapter) = &self.server_adapter {
+            with_retry(3, || server_adapter.delete_all_mocks())
+                .await
+                .expect("Cannot reset mock server (task: delete mocks).");
+        }
+    }
+
+    /// Deletes the given [Mock](struct.Mock.html) objects from the mock server in a single
+    /// operation. All other mocks and the request history remain untouched.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m1 = server.mock(|when, then| {
+    ///     when.path("/one");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let m2 = server.mock(|when, then| {
+    ///     when.path("/two");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let m3 = server.mock(|when, then| {
+    ///     when.path("/three");
+    ///     then.status(200);
+    /// });
+    ///
+    /// server.delete_mocks(&[&m1, &m2]);
+    ///
+    /// assert_eq!(get(&server.url("/one")).unwrap().status(), 404);
+    /// assert_eq!(get(&server.url("/two")).unwrap().status(), 404);
+    /// assert_eq!(get(&server.url("/three")).unwrap().status(), 200);
+    /// ```
+    pub fn delete_mocks(&self, mocks: &[&Mock<'_>]) {
+        self.delete_mocks_async(mocks).join()
+    }
+
+    /// Deletes the given [Mock](struct.Mock.html) objects from the mock server in a single
+    /// operation. All other mocks and the request history remain untouched.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     let m1 = server.mock_async(|when, then| {
+    ///         when.path("/one");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     let m2 = server.mock_async(|when, then| {
+    ///         when.path("/two");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     server.delete_mocks_async(&[&m1, &m2]).await;
+    ///
+    ///     assert_eq!(get(&server.url("/one")).await.unwrap().status(), 404);
+    ///     assert_eq!(get(&server.url("/two")).await.unwrap().status(), 404);
+    /// });
+    /// ```
+    pub async fn delete_mocks_async(&self, mocks: &[&Mock<'_>]) {
+        let ids: Vec<usize> = mocks.iter().map(|m| m.id).collect();
+
+        self.server_adapter
+            .as_ref()
+            .unwrap()
+            .delete_mocks(&ids)
+            .await
+            .expect("could not delete mocks from server");
+    }
+
+    /// Returns all mocks that are currently registered on the mock server, ordered by their ID.
+    /// This includes mocks that were created by other [MockServer](struct.MockServer.html)
+    /// instances connected to the same remote server and static mocks.
+    ///
+    /// The returned [ActiveMock](crate::ActiveMock) objects are snapshots that contain the
+    /// mock definitions and call counters at the time of the call. They implement
+    /// [Serialize](serde::Serialize), which makes it easy to dump the current mock
+    /// configuration, for example when a test fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/users");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let mocks = server.active_mocks();
+    ///
+    /// assert_eq!(mocks.len(), 1);
+    /// assert_eq!(mocks[0].definition.request.path.as_deref(), Some("/users"));
+    /// println!("{}", serde_json::to_string_pretty(&mocks).unwrap());
+    /// ```
+    pub fn active_mocks(&self) -> Vec<ActiveMock> {
+        self.active_mocks_async().join()
+    }
+
+    /// Returns all mocks that are currently registered on the mock server, ordered by their ID.
+    /// See [active_mocks](#method.active_mocks) for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server.mock_async(|when, then| {
+    ///         when.path("/users");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     assert_eq!(server.active_mocks_async().await.len(), 1);
+    /// });
+    /// ```
+    pub async fn active_mocks_async(&self) -> Vec<ActiveMock> {
+        self.server_adapter
+            .as_ref()
+            .unwrap()
+            .list_mocks()
+            .await
+            .expect("cannot fetch mocks from mock server")
+    }
+
+    /// Deletes all mocks that were tagged with the given tag (see [`When::tag`](crate::When::tag))
+    /// in a single operation and returns the number of deleted mocks. All other mocks and the
+    /// request history remain untouched. Static mocks cannot be deleted and are skipped.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/billing/invoices").tag("billing");
+    ///     then.status(200);
+    /// });
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/users").tag("users");
+    ///     then.status(200);
+    /// });
+    ///
+    /// assert_eq!(server.delete_mocks_by_tag("billing"), 1);
+    ///
+    /// assert_eq!(get(&server.url("/billing/invoices")).unwrap().status(), 404);
+    /// assert_eq!(get(&server.url("/users")).unwrap().status(), 200);
+    /// ```
+    pub fn delete_mocks_by_tag(&self, tag: &str) -> usize {
+        self.delete_mocks_by_tag_async(tag).join()
+    }
+
+    /// Deletes all mocks that were tagged with the given tag in a single operation and returns
+    /// the number of deleted mocks. See [delete_mocks_by_tag](#method.delete_mocks_by_tag)
+    /// for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server.mock_async(|when, then| {
+    ///         when.path("/billing/invoices").tag("billing");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     assert_eq!(server.delete_mocks_by_tag_async("billing").await, 1);
+    ///     assert_eq!(get(&server.url("/billing/invoices")).await.unwrap().status(), 404);
+    /// });
+    /// ```
+    pub async fn delete_mocks_by_tag_async(&self, tag: &str) -> usize {
+        self.server_adapter
+            .as_ref()
+            .unwrap()
+            .delete_mocks_by_tag(tag)
+            .await
+            .expect("could not delete mocks from server")
+    }
+
+    /// Verifies that the mocks tagged with the given tag (see [`When::tag`](crate::When::tag))
+    /// served exactly `times` requests in total. This is useful to verify a group of related
+    /// mocks that were created by helper functions without access to the individual
+    /// [Mock](struct.Mock.html) handles.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// for path in ["/billing/invoices", "/billing/payments"] {
+    ///     server.mock(|when, then| {
+    ///         when.path(path).tag("billing");
+    ///         then.status(200);
+    ///     });
+    /// }
+    ///
+    /// get(&server.url("/billing/invoices")).unwrap();
+    /// get(&server.url("/billing/payments")).unwrap();
+    ///
+    /// server.assert_tag_called("billing", 2);
+    /// ```
+    ///
+    /// # Panics
+    /// This method panics if no mock carries the tag or if the tagged mocks served a different
+    /// number of requests. The panic message lists the number of requests each tagged mock
+    /// served.
+    pub fn assert_tag_called(&self, tag: &str, times: usize) {
+        self.assert_tag_called_async(tag, times).join()
+    }
+
+    /// Verifies that the mocks tagged with the given tag served exactly `times` requests in
+    /// total. See [assert_tag_called](#method.assert_tag_called) for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server.mock_async(|when, then| {
+    ///         when.path("/billing/invoices").tag("billing");
+    ///         then.status(200);
+    ///     }).await;
+    ///
+    ///     get(&server.url("/billing/invoices")).await.unwrap();
+    ///
+    ///     server.assert_tag_called_async("billing", 1).await;
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// This method panics if no mock carries the tag or if the tagged mocks served a different
+    /// number of requests.
+    pub async fn assert_tag_called_async(&self, tag: &str, times: usize) {
+        let mocks = self
+            .server_adapter
+            .as_ref()
+            .unwrap()
+            .fetch_mocks_by_tag(tag)
+            .await
+            .expect("cannot fetch mocks from mock server");
+
+        if mocks.is_empty() {
+            panic!("No mock with tag '{}' found", tag);
+        }
+
+        let calls: usize = mocks.iter().map(|mock| mock.call_counter).sum();
+        if calls != times {
+            let details: Vec<String> = mocks
+                .iter()
+                .map(|mock| format!("mock ID {}: {}", mock.id, mock.call_counter))
+                .collect();
+            panic!(
+                "The mocks with tag '{}' were expected to serve {} request(s) in total, but served {} ({})",
+                tag,
+                times,
+                calls,
+                details.join(", ")
+            );
+        }
+    }
+
+    /// Replaces the headers that the mock server adds to every response (see
+    /// [`HttpMockServerBuilder::default_response_header`](crate::server::HttpMockServerBuilder::default_response_header)).
+    /// Mocks that explicitly set a header with the same name take precedence.
+    /// Passing an empty list removes all default headers.
+    ///
+    /// The headers stay in effect until the mock server is reset, which restores the default
+    /// headers that the server was configured with.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::blocking::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.set_default_headers(vec![("access-control-allow-origin", "*")]);
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = get(&server.url("/hello")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    /// ```
+    pub fn set_default_headers<Name: Into<String>, Value: Into<String>>(
+        &self,
+        headers: Vec<(Name, Value)>,
+    ) {
+        self.set_default_headers_async(headers).join()
+    }
+
+    /// Replaces the headers that the mock server adds to every response.
+    /// See [`MockServer::set_default_headers`] for details.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reqwest::get;
+    /// use httpmock::MockServer;
+    ///
+    /// let rt = tokio::runtime::Runtime::new().unwrap();
+    /// rt.block_on(async {
+    ///     let server = MockServer::start_async().await;
+    ///
+    ///     server
+    ///         .set_default_headers_async(vec![("x-request-id", "42")])
+    ///         .await;
+    ///
+    ///     let response = get(&server.url("/hello")).await.unwrap();
+    ///
+    ///     assert_eq!(response.headers()["x-request-id"], "42");
+    /// });
+    /// ```
+    pub async fn set_default_headers_async<Name: Into<String>, Value: Into<String>>(
+        &self,
+        headers: Vec<(Name, Value)>,
+    ) {
+        let headers: Vec<(String, String)> = headers
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+
+        self.server_adapter
+            .as_ref()
+            .unwrap()
+            .set_default_headers(&headers)
+            .await
+            .expect("could not set default headers on server");
+    }
+
+    /// Saves all mocks of this mock server to a file, so that they can be restored later using
+    /// [`MockServer::load_state`] (e.g., after the mock server was restarted).
+    ///
+    /// The file contains a versioned JSON document. Static mocks (e.g., loaded from a static
+    /// mock directory) are not included, because the server loads them on startup anyway.
+    ///
+    /// # Errors
+    /// Returns an error if a mock uses custom matcher functions (such as [`When::is_true`](crate::When::is_true)),
+    /// because they cannot be persisted, or if the file cannot be written.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200).body("world");
+    /// });
+    ///
+    /// let path = std::env::temp_dir().join("httpmock_save_state_doctest.json");
+    /// server.save_state(&path).unwrap();
+    ///
+    /// // Restore the mocks on another server
+    /// let restored = MockServer::start();
+    /// restored.load_state(&path).unwrap();
+    ///
+    /// let response = reqwest::blocking::get(restored.url("/hello")).unwrap();
+    /// assert_eq!(response.text().unwrap(), "world");
+    /// ```
+    pub fn save_state<PathRef: AsRef<Path>>(
+        &self,
+        path: PathRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_state_async(path).join()
+    }
+
+    /// Asynchronously saves all mocks of this mock server to a file.
+    /// See [`MockServer::save_state`] for details.
+    pub async fn save_state_async<PathRef: AsRef<Path>>(
+        &self,
+        path: PathRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = self.server_adapter.as_ref().unwrap().export_state().await?;
+
+        let json = serde_json::to_vec_pretty(&snapshot)?;
+        write_file(path, &bytes::Bytes::from(json), true).await?;
+
+        Ok(())
+    }
+
+    /// Loads mocks from a file that was created using [`MockServer::save_state`].
+    /// All mocks that are currently configured on this mock server (except static mocks) are
+    /// replaced by the mocks from the file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, if it was created by an incompatible
+    /// version of this library, or if it contains invalid mock definitions. In that case,
+    /// the currently configured mocks are left unchanged.
+    pub fn load_state<PathRef: AsRef<Path>>(
+        &self,
+        path: PathRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.load_state_async(path).join()
+    }
+
+    /// Asynchronously loads mocks from a file that was created using [`MockServer::save_state`].
+    /// See [`MockServer::load_state`] for details.
+    pub async fn load_state_async<PathRef: AsRef<Path>>(
+        &self,
+        path: PathRef,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let content = read_file_async(path.as_ref()).await?;
+        let snapshot = StateSnapshot::from_json(&content)?;
+
+        self.server_adapter
+            .as_ref()
+            .unwrap()
+            .import_state(&snapshot)
+            .await?;
+
+        Ok(())
+    }
+
     /// Configures the mock server to forward the request to the target host by replacing the host name,
     /// but only if the request expectations are met. If the request is recorded, the recording will
     /// **NOT** contain the host name as an expectation to allow the recording to be reused.
@@ -640,23 +1652,26 @@ impl MockServer {
         ForwardingRuleBuilderFn: FnOnce(ForwardingRuleBuilder),
         IntoString: Into<String>,
     {
-        let mut headers = Rc::new(Cell::new(Vec::new()));
-        let mut req = Rc::new(Cell::new(RequestRequirements::new()));
+        let mut config = Rc::new(Cell::new(ForwardingRuleConfig {
+            target_base_url: target_base_url.into(),
+            request_requirements: RequestRequirements::new(),
+            request_header_changes: Vec::new(),
+            upstream_timeout: None,
+            retries: 0,
+            upstream_proxy: None,
+            danger_accept_invalid_certs: false,
+            on_error: None,
+        }));
 
         rule(ForwardingRuleBuilder {
-            headers: headers.clone(),
-            request_requirements: req.clone(),
+            config: config.clone(),
         });
 
         let response = self
             .server_adapter
             .as_ref()
             .unwrap()
-            .create_forwarding_rule(ForwardingRuleConfig {
-                target_base_url: target_base_url.into(),
-                request_requirements: req.take(),
-                request_header: headers.take(),
-            })
+            .create_forwarding_rule(config.take())
             .await
             .expect("Cannot deserialize mock server response");
 
@@ -799,22 +1814,23 @@ impl MockServer {
     where
         ProxyRuleBuilderFn: FnOnce(ProxyRuleBuilder),
     {
-        let mut headers = Rc::new(Cell::new(Vec::new()));
-        let mut req = Rc::new(Cell::new(RequestRequirements::new()));
+        let mut config = Rc::new(Cell::new(ProxyRuleConfig {
+            request_requirements: RequestRequirements::new(),
+            request_header: Vec::new(),
+            upstream_timeout: None,
+            retries: 0,
+            danger_accept_invalid_certs: false,
+        }));
 
         rule(ProxyRuleBuilder {
-            headers: headers.clone(),
-            request_requirements: req.clone(),
+            config: config.clone(),
         });
 
         let response = self
             .server_adapter
             .as_ref()
             .unwrap()
-            .create_proxy_rule(ProxyRuleConfig {
-                request_requirements: req.take(),
-                request_header: headers.take(),
-            })
+            .create_proxy_rule(config.take())
             .await
             .expect("Cannot deserialize mock server response");
 
@@ -1002,6 +2018,12 @@ impl MockServer {
             request_requirements: RequestRequirements::new(),
             record_headers: Vec::new(),
             record_response_delays: false,
+            redact_headers: Vec::new(),
+            redact_query_params: Vec::new(),
+            redact_json_paths: Vec::new(),
+            redaction_placeholder: None,
+            skip_response_larger_than: None,
+            skip_content_types: Vec::new(),
         }));
 
         rule(RecordingRuleBuilder {
@@ -1179,19 +2201,8 @@ impl MockServer {
     /// This method is only available when the `record` feature is enabled.
     #[cfg(feature = "record")]
     pub async fn playback_async<IntoPathBuf: Into<PathBuf>>(&self, path: IntoPathBuf) -> MockSet {
-        let path = path.into();
-        let content = read_file_async(&path).await.expect(&format!(
-            "could not read from file {}",
-            path.as_os_str()
-                .to_str()
-                .map_or(String::new(), |p| p.to_string())
-        ));
-
-        return self
-            .playback_from_yaml_async(
-                String::from_utf8(content).expect("cannot convert file content to UTF-8"),
-            )
-            .await;
+        let content = read_recording_file(path.into()).await;
+        return self.playback_from_yaml_async(content).await;
     }
 
     /// Configures the mock server to respond with the recorded responses based on a provided recording
@@ -1311,11 +2322,293 @@ impl MockServer {
         &self,
         content: AsStrRef,
     ) -> MockSet {
+        self.load_recording_async(content.as_ref(), PlaybackConfig::default())
+            .await
+    }
+
+    /// Reads a recording file and plays back the recorded interactions strictly in the recorded
+    /// order. Each recorded interaction serves exactly one request and only after all
+    /// interactions that were recorded before it have been served. This faithfully reproduces a
+    /// recorded conversation, including repeated identical requests that received different
+    /// responses.
+    ///
+    /// While an ordered playback is active, the mock server responds with status code 500 to
+    /// every request that no other mock matches. The response body explains how the request
+    /// differs from the next recorded interaction, or that all interactions were played back
+    /// already.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: A path to the file containing the recorded interactions. This can be any type
+    ///           that implements `Into<PathBuf>`, such as a `&str` or `String`.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were loaded from the recording file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// // A recording of two identical requests that received different responses.
+    /// let recording = r#"
+    /// when:
+    ///   method: GET
+    ///   path: /job
+    /// then:
+    ///   status: 200
+    ///   body: pending
+    /// ---
+    /// when:
+    ///   method: GET
+    ///   path: /job
+    /// then:
+    ///   status: 200
+    ///   body: done
+    /// "#;
+    ///
+    /// let path = std::env::temp_dir().join("httpmock_playback_ordered_example.yaml");
+    /// std::fs::write(&path, recording).unwrap();
+    ///
+    /// let server = MockServer::start();
+    /// server.playback_ordered(&path);
+    ///
+    /// let get = || reqwest::blocking::get(server.url("/job")).unwrap();
+    ///
+    /// // The recorded responses are served in the recorded order ...
+    /// assert_eq!(get().text().unwrap(), "pending");
+    /// assert_eq!(get().text().unwrap(), "done");
+    ///
+    /// // ... and each of them only once.
+    /// assert_eq!(get().status(), 500);
+    /// ```
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub fn playback_ordered<IntoPathBuf: Into<PathBuf>>(&self, path: IntoPathBuf) -> MockSet {
+        self.playback_ordered_async(path).join()
+    }
+
+    /// Asynchronously reads a recording file and plays back the recorded interactions strictly
+    /// in the recorded order. See [`playback_ordered`](#method.playback_ordered) for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: A path to the file containing the recorded interactions. This can be any type
+    ///           that implements `Into<PathBuf>`, such as a `&str` or `String`.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were loaded from the recording file.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub async fn playback_ordered_async<IntoPathBuf: Into<PathBuf>>(
+        &self,
+        path: IntoPathBuf,
+    ) -> MockSet {
+        let content = read_recording_file(path.into()).await;
+        let config = PlaybackConfig {
+            ordered: true,
+            ..PlaybackConfig::default()
+        };
+        self.load_recording_async(&content, config).await
+    }
+
+    /// Reads a recording file and plays back the recorded interactions with the upstream
+    /// response times that were observed during recording. Each response is delayed by the
+    /// `duration_ms` value that was recorded for it, so that tests against a slow real API
+    /// keep realistic latencies.
+    ///
+    /// Responses that already define a `delay` (e.g., because they were recorded with
+    /// `record_response_delays`) keep that delay. Recordings without `duration_ms` values
+    /// are played back without any delay, just like with [`playback`](#method.playback).
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: A path to the file containing the recorded interactions. This can be any type
+    ///           that implements `Into<PathBuf>`, such as a `&str` or `String`.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were loaded from the recording file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::MockServer;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// // A recording of a request that took 200 milliseconds to be answered by the upstream server.
+    /// let recording = r#"
+    /// when:
+    ///   method: GET
+    ///   path: /report
+    /// then:
+    ///   status: 200
+    ///   body: done
+    ///   duration_ms: 200
+    /// "#;
+    ///
+    /// let path = std::env::temp_dir().join("httpmock_playback_with_timing_example.yaml");
+    /// std::fs::write(&path, recording).unwrap();
+    ///
+    /// let server = MockServer::start();
+    /// server.playback_with_timing(&path);
+    ///
+    /// let start = Instant::now();
+    /// let response = reqwest::blocking::get(server.url("/report")).unwrap();
+    ///
+    /// assert_eq!(response.text().unwrap(), "done");
+    /// assert!(start.elapsed() >= Duration::from_millis(200));
+    /// ```
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub fn playback_with_timing<IntoPathBuf: Into<PathBuf>>(&self, path: IntoPathBuf) -> MockSet {
+        self.playback_with_timing_async(path).join()
+    }
+
+    /// Asynchronously reads a recording file and plays back the recorded interactions with
+    /// the recorded upstream response times.
+    /// See [`playback_with_timing`](#method.playback_with_timing) for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: A path to the file containing the recorded interactions. This can be any type
+    ///           that implements `Into<PathBuf>`, such as a `&str` or `String`.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were loaded from the recording file.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub async fn playback_with_timing_async<IntoPathBuf: Into<PathBuf>>(
+        &self,
+        path: IntoPathBuf,
+    ) -> MockSet {
+        let content = read_recording_file(path.into()).await;
+        let config = PlaybackConfig {
+            replay_with_timing: true,
+            ..PlaybackConfig::default()
+        };
+        self.load_recording_async(&content, config).await
+    }
+
+    /// Reads a HAR (HTTP Archive) file, such as one exported from a browser's developer tools,
+    /// and configures the mock server to answer requests with the recorded responses.
+    ///
+    /// One mock is created for each entry of the file. It matches the method, path and query
+    /// parameters of the recorded request and responds with the recorded status code, headers
+    /// and body. Query parameters are matched individually, so requests may list them in a
+    /// different order than the recorded request. Hosts and ports are not matched.
+    ///
+    /// HAR files contain decoded response bodies, so the recorded `Content-Encoding`,
+    /// `Content-Length` and `Transfer-Encoding` headers are not served. If the file contains
+    /// multiple entries for the same request, the first one is served.
+    ///
+    /// Requests that do not match any entry receive the usual `404` response, and
+    /// [`Mock::assert`](struct.Mock.html#method.assert) reports the closest match as usual.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: A path to the HAR file. This can be any type that implements `Into<PathBuf>`,
+    ///           such as a `&str` or `String`.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were created from the HAR file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let har = r#"{
+    ///   "log": {
+    ///     "entries": [{
+    ///       "request": { "method": "GET", "url": "https://api.example.com/users?page=2&limit=10" },
+    ///       "response": {
+    ///         "status": 200,
+    ///         "headers": [{ "name": "Content-Type", "value": "application/json" }],
+    ///         "content": { "text": "[]" }
+    ///       }
+    ///     }]
+    ///   }
+    /// }"#;
+    ///
+    /// let path = std::env::temp_dir().join("httpmock_playback_har_example.har");
+    /// std::fs::write(&path, har).unwrap();
+    ///
+    /// let server = MockServer::start();
+    /// server.playback_har(&path);
+    ///
+    /// let response = reqwest::blocking::get(server.url("/users?limit=10&page=2")).unwrap();
+    ///
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.text().unwrap(), "[]");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be read or is not a valid HAR file.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub fn playback_har<IntoPathBuf: Into<PathBuf>>(&self, path: IntoPathBuf) -> MockSet {
+        self.playback_har_async(path).join()
+    }
+
+    /// Asynchronously reads a HAR (HTTP Archive) file and configures the mock server to answer
+    /// requests with the recorded responses. See [`playback_har`](#method.playback_har) for
+    /// details.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `record` feature is enabled.
+    #[cfg(feature = "record")]
+    pub async fn playback_har_async<IntoPathBuf: Into<PathBuf>>(
+        &self,
+        path: IntoPathBuf,
+    ) -> MockSet {
+        let content = read_recording_file(path.into()).await;
+        let definitions =
+            mock_definitions_from_har(&content).unwrap_or_else(|err| panic!("{}", err));
+
+        let mut ids = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            let mock = self
+                .server_adapter
+                .as_ref()
+                .unwrap()
+                .create_mock(&definition)
+                .await
+                .expect("Cannot deserialize mock server response");
+            ids.push(mock.id);
+        }
+
+        MockSet { ids, server: self }
+    }
+
+    #[cfg(feature = "record")]
+    async fn load_recording_async(&self, content: &str, config: PlaybackConfig) -> MockSet {
         let response = self
             .server_adapter
             .as_ref()
             .unwrap()
-            .create_mocks_from_recording(content.as_ref())
+            .create_mocks_from_recording(content, config)
             .await
             .expect("Cannot deserialize mock server response");
 
@@ -1324,6 +2617,105 @@ impl MockServer {
             server: self,
         }
     }
+
+    /// Creates stub mocks from an OpenAPI 3.x document. One mock is created for each operation
+    /// of the document. It matches the operation's method and path and responds with the
+    /// status code of the first declared success response.
+    ///
+    /// The response body is taken from the first declared example of the response content.
+    /// If there is no example, an example body is derived from the response schema.
+    /// Path templates (e.g., `/users/{id}`) are matched using [`When::path_matches`],
+    /// so that any value is accepted for path parameters. Operations that cannot be represented
+    /// as a mock, such as operations without any declared response, are skipped.
+    ///
+    /// # Parameters
+    ///
+    /// * `path_or_content`: The path to an OpenAPI document file, or the content of an OpenAPI
+    ///   document. Both YAML and JSON documents are supported.
+    ///
+    /// # Returns
+    ///
+    /// * `MockSet`: An object representing the set of mocks that were created from the document.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::MockServer;
+    ///
+    /// let spec = r#"
+    /// openapi: 3.0.0
+    /// info:
+    ///   title: Users
+    ///   version: 1.0.0
+    /// paths:
+    ///   /users/{id}:
+    ///     get:
+    ///       responses:
+    ///         '200':
+    ///           description: A user
+    ///           content:
+    ///             application/json:
+    ///               example: { "id": 1, "name": "Peter Griffin" }
+    /// "#;
+    ///
+    /// let server = MockServer::start();
+    /// server.mocks_from_openapi(spec);
+    ///
+    /// let response = reqwest::blocking::get(server.url("/users/1")).unwrap();
+    ///
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.text().unwrap(), r#"{"id":1,"name":"Peter Griffin"}"#);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the document cannot be read or is not a valid OpenAPI 3.x document.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `openapi` feature is enabled.
+    #[cfg(feature = "openapi")]
+    pub fn mocks_from_openapi<AsStrRef: AsRef<str>>(&self, path_or_content: AsStrRef) -> MockSet {
+        self.mocks_from_openapi_async(path_or_content).join()
+    }
+
+    /// Asynchronously creates stub mocks from an OpenAPI 3.x document.
+    /// See [`MockServer::mocks_from_openapi`] for details.
+    ///
+    /// # Feature
+    ///
+    /// This method is only available when the `openapi` feature is enabled.
+    #[cfg(feature = "openapi")]
+    pub async fn mocks_from_openapi_async<AsStrRef: AsRef<str>>(
+        &self,
+        path_or_content: AsStrRef,
+    ) -> MockSet {
+        let path_or_content = path_or_content.as_ref();
+
+        let content = if Path::new(path_or_content).is_file() {
+            let content = read_file(path_or_content).expect("cannot read OpenAPI document");
+            String::from_utf8(content).expect("cannot convert OpenAPI document to UTF-8")
+        } else {
+            path_or_content.to_string()
+        };
+
+        let definitions =
+            mock_definitions_from_openapi(&content).unwrap_or_else(|err| panic!("{}", err));
+
+        let mut ids = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            let mock = self
+                .server_adapter
+                .as_ref()
+                .unwrap()
+                .create_mock(&definition)
+                .await
+                .expect("Cannot deserialize mock server response");
+            ids.push(mock.id);
+        }
+
+        MockSet { ids, server: self }
+    }
 }
 
 /// Implements the `Drop` trait for `MockServer`.
@@ -1346,9 +2738,119 @@ impl Drop for MockServer {
     }
 }
 
+/// Reads the content of a recording file.
+#[cfg(feature = "record")]
+async fn read_recording_file(path: PathBuf) -> String {
+    let content = read_file_async(&path).await.expect(&format!(
+        "could not read from file {}",
+        path.as_os_str()
+            .to_str()
+            .map_or(String::new(), |p| p.to_string())
+    ));
+
+    String::from_utf8(content).expect("cannot convert file content to UTF-8")
+}
+
+/// Builds a mock definition by passing `When` and `Then` to a mock specification closure.
+fn mock_definition<SpecFn>(spec_fn: SpecFn) -> MockDefinition
+where
+    SpecFn: FnOnce(When, Then),
+{
+    let req = Rc::new(Cell::new(RequestRequirements::new()));
+    let res = Rc::new(Cell::new(MockServerHttpResponse::new()));
+
+    spec_fn(
+        When {
+            expectations: req.clone(),
+        },
+        Then {
+            response_template: res.clone(),
+        },
+    );
+
+    MockDefinition {
+        request: req.take(),
+        response: res.take(),
+    }
+}
+
+/// Formats `addr` as the authority part of a URL. IPv6 addresses are enclosed in square
+/// brackets and the `%` in front of a zone identifier is percent-encoded (RFC 6874).
+fn url_authority(addr: &SocketAddr) -> String {
+    match addr {
+        SocketAddr::V4(addr) => addr.to_string(),
+        SocketAddr::V6(addr) if addr.scope_id() != 0 => {
+            format!("[{}%25{}]:{}", addr.ip(), addr.scope_id(), addr.port())
+        }
+        SocketAddr::V6(addr) => format!("[{}]:{}", addr.ip(), addr.port()),
+    }
+}
+
+/// Starts a local mock server on a dedicated thread. The server shuts down when the returned
+/// adapter is dropped.
+pub(crate) async fn start_local_server(
+    builder: HttpMockServerBuilder,
+) -> Arc<dyn MockServerAdapter + Send + Sync> {
+    start_local_server_on(builder, None).await
+}
+
+/// Starts a local mock server as a task on the given Tokio runtime or, if no runtime is given,
+/// on a dedicated thread. The server shuts down when the returned adapter is dropped.
+async fn start_local_server_on(
+    builder: HttpMockServerBuilder,
+    handle: Option<tokio::runtime::Handle>,
+) -> Arc<dyn MockServerAdapter + Send + Sync> {
+    let (addr_sender, addr_receiver) = channel::<SocketAddr>();
+    let (shutdown_sender, shutdown_receiver) = channel::<()>();
+
+    let (srv, state_manager) = builder
+        .build_with_shared_state()
+        .expect("cannot build mock server");
+
+    let shutdown = async {
+        // Resolves when the sender is dropped together with the server adapter.
+        let _ = shutdown_receiver.await;
+    };
+    match handle {
+        Some(handle) => {
+            let server_fn = srv.start_with_signals(Some(addr_sender), shutdown);
+            handle.spawn(async move {
+                if let Err(err) = server_fn.await {
+                    log::error!("Server execution failed: {:?}", err);
+                }
+            });
+        }
+        None if srv.worker_threads().is_some() => {
+            thread::spawn(move || {
+                srv.run_with_signals(Some(addr_sender), shutdown)
+                    .expect("Server execution failed");
+            });
+        }
+        None => {
+            let server_fn = srv.start_with_signals(Some(addr_sender), shutdown);
+            thread::spawn(move || {
+                runtime::block_on_current_thread(server_fn).expect("Server execution failed");
+            });
+        }
+    }
+
+    let addr = addr_receiver.await.expect("Cannot get server address");
+    Arc::new(LocalMockServerAdapter::with_shutdown(
+        addr,
+        state_manager,
+        shutdown_sender,
+    ))
+}
+
 const LOCAL_SERVER_ADAPTER_GENERATOR: fn() -> Arc<dyn MockServerAdapter + Send + Sync> = || {
     let (addr_sender, addr_receiver) = channel::<SocketAddr>();
-    let state_manager = Arc::new(HttpMockStateManager::default());
+    let history_limit = read_env(
+        "HTTPMOCK_REQUEST_HISTORY_LIMIT",
+        &DEFAULT_HISTORY_LIMIT.to_string(),
+    )
+    .parse::<usize>()
+    .expect("Cannot parse environment variable HTTPMOCK_REQUEST_HISTORY_LIMIT as an integer");
+    let state_manager = Arc::new(HttpMockStateManager::new(history_limit));
     let srv = HttpMockServerBuilder::new()
         .build_with_state(state_manager.clone())
         .expect("cannot build mock server");
@@ -1371,8 +2873,25 @@ lazy_static! {
             .expect("Cannot parse environment variable HTTPMOCK_MAX_SERVERS as an integer");
         Arc::new(Pool::new(max_servers))
     };
-    static ref REMOTE_SERVER_POOL_REF: Arc<Pool<Arc<dyn MockServerAdapter + Send + Sync>>> =
-        Arc::new(Pool::new(1));
+}
+
+#[cfg(feature = "remote")]
+lazy_static! {
+    // Adapters of remote servers are pooled per server address, so that connecting to one
+    // server never hands out an adapter that talks to another one.
+    static ref REMOTE_SERVER_POOLS: Mutex<HashMap<SocketAddr, Arc<Pool<Arc<dyn MockServerAdapter + Send + Sync>>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Returns the adapter pool for the remote mock server at `addr`.
+#[cfg(feature = "remote")]
+fn remote_server_pool(addr: SocketAddr) -> Arc<Pool<Arc<dyn MockServerAdapter + Send + Sync>>> {
+    REMOTE_SERVER_POOLS
+        .lock()
+        .unwrap()
+        .entry(addr)
+        .or_insert_with(|| Arc::new(Pool::new(1)))
+        .clone()
 }
 
 #[cfg(feature = "remote")]
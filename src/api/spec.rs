@@ -1,16 +1,42 @@
+#[cfg(feature = "protobuf")]
+use crate::{
+    api::protobuf::ProtobufDescriptor,
+    common::data::ProtobufFieldExpectation,
+    server::matchers::protobuf::{message_descriptor, resolve_field_path},
+};
+#[cfg(feature = "proxy")]
+use crate::{
+    api::{callback::CallbackRequestBuilder, proxy::ForwardingRuleBuilder},
+    common::data::{CallbackRequest, ForwardingRuleConfig, ForwardingTarget},
+};
 use crate::{
     common::{
-        data::{MockServerHttpResponse, RequestRequirements},
-        util::{get_test_resource_file_path, read_file, update_cell, HttpMockBytes},
+        data::{
+            quote_entity_tag, AnyOfPatterns, AnyOfValues, BodySizeRange, CallCountResponse, Fault,
+            JsonArrayElements, JsonSchema, MockServerHttpResponse, NamedMatcherCall, PathTemplate,
+            RequestRequirements,
+        },
+        util::{
+            get_test_resource_file_path, is_valid_reason_phrase, normalize_http_version,
+            normalize_url, read_file, seeded_random_bytes, update_cell, validate_template,
+            HttpMockBytes,
+        },
     },
     prelude::HttpMockRequest,
+    server::matchers::comparison::split_authority,
     Method, Regex,
 };
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
-    cell::Cell, convert::TryInto, path::Path, rc::Rc, str::FromStr, sync::Arc, time::Duration,
+    cell::Cell,
+    convert::TryInto,
+    path::Path,
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 
 /// A function that encapsulates one or more
@@ -191,6 +217,55 @@ impl When {
     }
     // @docs-group: Scheme
 
+    /// Sets the expected subject common name (CN) of the client certificate that the request was
+    /// sent with (mutual TLS).
+    ///
+    /// Client certificates are only requested by the mock server if it was started with a client
+    /// CA certificate (see `HttpMockServerBuilder::https_client_ca_cert`, requires the `https`
+    /// feature). Otherwise, or if the client did not present a certificate, requests never match
+    /// this requirement and the mismatch report explains why.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server (without mutual TLS)
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that only matches requests authenticated with a client certificate
+    /// let mock = server.mock(|when, then| {
+    ///     when.client_cert_cn("billing-service");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // A plain HTTP request does not carry a client certificate, so it does not match
+    /// let response = reqwest::blocking::get(server.url("/invoices")).unwrap();
+    ///
+    /// // Ensure the mock did not respond
+    /// assert_eq!(response.status(), 404);
+    ///
+    /// // Ensure that the mock was not called
+    /// mock.assert_calls(0);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `common_name`: The expected common name of the client certificate subject.
+    ///
+    /// # Returns
+    /// The modified `When` instance to allow for method chaining.
+    ///
+    pub fn client_cert_cn<IntoString: Into<String>>(mut self, common_name: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.client_cert_cn.is_none() {
+                e.client_cert_cn = Some(Vec::new());
+            }
+            e.client_cert_cn.as_mut().unwrap().push(common_name.into())
+        });
+        self
+    }
+    // @docs-group: Scheme
+
     /// Sets the expected HTTP method for which the mock server should respond.
     ///
     /// This method ensures that the mock server only matches requests that use the specified HTTP method,
@@ -242,6 +317,55 @@ impl When {
     }
     // @docs-group: Method
 
+    /// Sets a list of HTTP methods of which the request method must be one. This is useful for
+    /// endpoints that accept several methods, so that a single mock can serve all of them.
+    ///
+    /// This can be combined with [`method_not`](#method.method_not) to exclude some of the
+    /// listed methods again. If the request method is not in the list, the mismatch output
+    /// lists all accepted methods along with the received one. Calling this method again
+    /// replaces the previously set list.
+    ///
+    /// **Note**: Method matching is case-insensitive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method_in(&[PUT, PATCH]).path("/users/1");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let client = Client::new();
+    /// let put = client.put(server.url("/users/1")).send().unwrap();
+    /// let patch = client.patch(server.url("/users/1")).send().unwrap();
+    /// let post = client.post(server.url("/users/1")).send().unwrap();
+    ///
+    /// assert_eq!(put.status(), 200);
+    /// assert_eq!(patch.status(), 200);
+    /// assert_eq!(post.status(), 404);
+    /// mock.assert_calls(2);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `methods`: The HTTP methods of which the request method must be one.
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow for method chaining.
+    ///
+    pub fn method_in(mut self, methods: &[Method]) -> Self {
+        let methods = methods.iter().map(|m| m.to_string()).collect();
+        update_cell(&self.expectations, |e| {
+            e.method_in = Some(AnyOfValues(methods))
+        });
+        self
+    }
+    // @docs-group: Method
+
     /// Excludes the specified HTTP method from the requests the mock server will respond to.
     ///
     /// This method ensures that the mock server does not respond to requests using the given HTTP method,
@@ -295,6 +419,54 @@ impl When {
     }
     // @docs-group: Method
 
+    /// Sets the HTTP version that requests must use, as sent in the request line of an HTTP/1
+    /// request or as negotiated for the connection (e.g., `HTTP/2.0` for HTTP/2 requests).
+    ///
+    /// This is useful to verify the protocol version a client actually sends, for example when
+    /// middleware behaves differently for `HTTP/1.0` and `HTTP/1.1`.
+    ///
+    /// **Note**: The `HTTP/` prefix is optional and case-insensitive, and a missing minor
+    /// version is added, so `"HTTP/2"`, `"http/2.0"` and `"2"` all match HTTP/2 requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that only matches HTTP/1.1 requests
+    /// let mock = server.mock(|when, then| {
+    ///     when.http_version("HTTP/1.1");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Send an HTTP/1.1 request to the server
+    /// let response = reqwest::blocking::get(server.url("/")).unwrap();
+    ///
+    /// // Ensure the request was successful
+    /// assert_eq!(response.status(), 200);
+    ///
+    /// // Ensure that the mock was called
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Parameters
+    /// - `version`: The HTTP version that requests must use, such as `"HTTP/1.0"`, `"HTTP/1.1"` or `"HTTP/2"`.
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow for method chaining.
+    ///
+    pub fn http_version<IntoString: Into<String>>(mut self, version: IntoString) -> Self {
+        let version = normalize_http_version(&version.into());
+        update_cell(&self.expectations, |e| {
+            e.http_version = Some(version);
+        });
+        self
+    }
+    // @docs-group: Method
+
     /// Sets the expected host name. This constraint is especially useful when working with
     /// proxy or forwarding rules, but it can also be used to serve mocks (e.g., when using a mock
     /// server as a proxy).
@@ -772,6 +944,155 @@ impl When {
     }
     // @docs-group: Host
 
+    /// Sets the absolute URL that the request must target, such as `http://example.com/search?q=1`.
+    ///
+    /// The URL of a request is reconstructed from its scheme, host, port, path and query string,
+    /// so this works no matter if the client sent the full URL in the request line (as clients do
+    /// when using the mock server as a forward proxy) or only the path. Both URLs are normalized
+    /// before they are compared: scheme and host are case-insensitive and the default port of the
+    /// scheme (80 for `http`, 443 for `https`) may be omitted. The query string is compared
+    /// exactly as it was received, including the order of its parameters.
+    ///
+    /// This matcher is applied in addition to all other matchers. If `host`, `port`, `path` or
+    /// any other matcher is also set, a request must satisfy all of them. Unlike
+    /// [`host`](#method.host), this matcher does not treat `localhost` and `127.0.0.1` as equal.
+    ///
+    /// # Parameters
+    /// - `url`: The absolute URL, including scheme and host.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.url("http://github.com/search?q=httpmock");
+    ///     then.body("This is a mock response");
+    /// });
+    ///
+    /// let client = Client::builder()
+    ///     .proxy(reqwest::Proxy::all(&server.base_url()).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let response = client.get("http://github.com/search?q=httpmock").send().unwrap();
+    ///
+    /// assert_eq!(response.text().unwrap(), "This is a mock response");
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to enable method chaining.
+    ///
+    /// # Errors
+    /// This function will panic if the provided value is not a valid absolute URL.
+    pub fn url<IntoString: Into<String>>(mut self, url: IntoString) -> Self {
+        let url = normalize_url(&url.into()).expect("cannot parse provided value as absolute URL");
+        update_cell(&self.expectations, |e| e.url = Some(url));
+        self
+    }
+    // @docs-group: Host
+
+    /// Sets a regular expression that the absolute URL of the request must match.
+    ///
+    /// The URL is reconstructed from the scheme, host, port, path and query string of the
+    /// request and normalized the same way as in [`url`](#method.url): the host is lowercase
+    /// and the port is only included if it is not the default port of the scheme
+    /// (e.g., `http://example.com/a?b=c` or `https://example.com:8443/a`).
+    ///
+    /// To add multiple patterns, invoke this function multiple times. This matcher is applied
+    /// in addition to `host`, `port`, `path` and all other matchers.
+    ///
+    /// # Parameters
+    /// - `regex`: The regular expression that the absolute URL must match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.url_matches(r"^https?://(www\.)?github\.com/users/\w+$");
+    ///     then.body("This is a mock response");
+    /// });
+    ///
+    /// let client = Client::builder()
+    ///     .proxy(reqwest::Proxy::all(&server.base_url()).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let response = client.get("http://github.com/users/alice").send().unwrap();
+    ///
+    /// assert_eq!(response.text().unwrap(), "This is a mock response");
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to enable method chaining.
+    ///
+    /// # Errors
+    /// This function will panic if the provided regex pattern is invalid.
+    pub fn url_matches<TryIntoRegex: TryInto<Regex>>(mut self, regex: TryIntoRegex) -> Self
+    where
+        <TryIntoRegex as TryInto<Regex>>::Error: std::fmt::Debug,
+    {
+        let regex = regex
+            .try_into()
+            .expect("cannot convert provided value into regex");
+        update_cell(&self.expectations, |e| {
+            e.url_matches.get_or_insert_with(Vec::new).push(regex);
+        });
+        self
+    }
+    // @docs-group: Host
+
+    /// Sets the exact value that the `Host` header of incoming requests must have.
+    ///
+    /// Unlike [`host`](#method.host), which compares the host name (without the port)
+    /// case-insensitively and falls back to the URL authority, this matcher compares the raw
+    /// `Host` header value as it was sent, including the port and the letter case. This is useful
+    /// in reverse proxy setups, where the `Host` header can differ from the server the request
+    /// was actually sent to. Requests without a `Host` header (e.g., HTTP/2 requests that only
+    /// carry the `:authority` pseudo-header) do not match.
+    ///
+    /// # Parameters
+    /// - `value`: The expected value of the `Host` header (e.g., `api.example.com:8080`).
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.host_header("api.example.com");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // The request is sent to the mock server, but carries a different Host header
+    /// let response = Client::new()
+    ///     .get(server.url("/"))
+    ///     .header("Host", "api.example.com")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to enable method chaining.
+    pub fn host_header<IntoString: Into<String>>(self, value: IntoString) -> Self {
+        update_cell(&self.expectations, |e| e.host_header = Some(value.into()));
+        self
+    }
+    // @docs-group: Host
+
     /// Specifies the expected port number for incoming requests to match.
     ///
     /// This constraint is especially useful when working with proxy or forwarding rules, but it
@@ -887,9 +1208,71 @@ impl When {
     }
     // @docs-group: Port
 
+    /// Specifies the authority (host and port) that incoming requests must match, such as
+    /// `github.com:8080`. This is especially useful when using the mock server as a proxy.
+    ///
+    /// Unlike combining [`host`](#method.host) and [`port`](#method.port), default ports are
+    /// normalized against the request scheme:
+    /// - A request that does not specify a port is treated as if it was sent to the default port
+    ///   of its scheme (80 for `http`, 443 for `https`). A request to `http://github.com`
+    ///   therefore matches `authority("github.com:80")`.
+    /// - An authority without a port (e.g., `authority("github.com")`) expects the default port
+    ///   of the request scheme, so it matches `http://github.com` and `https://github.com:443`,
+    ///   but not `http://github.com:8080`.
+    ///
+    /// Host names are compared case-insensitively. IPv6 addresses must be enclosed in brackets
+    /// (e.g., `[::1]:8080`).
+    ///
+    /// # Parameters
+    /// - `authority`: The expected authority in the form `host` or `host:port`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.authority("github.com:80");
+    ///     then.body("This is a mock response");
+    /// });
+    ///
+    /// let client = Client::builder()
+    ///     .proxy(reqwest::Proxy::all(&server.base_url()).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // The request does not specify a port, so the default port of "http" (80) is used
+    /// let response = client.get("http://github.com").send().unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.text().unwrap(), "This is a mock response");
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `authority` is not a valid authority.
+    ///
+    /// # Returns
+    /// The updated `When` instance to enable method chaining.
+    ///
+    pub fn authority<IntoString: Into<String>>(self, authority: IntoString) -> Self {
+        let authority = authority.into();
+        if let Err(err) = split_authority(&authority) {
+            panic!("{}", err);
+        }
+
+        update_cell(&self.expectations, |e| e.authority = Some(authority));
+        self
+    }
+    // @docs-group: Port
+
     /// Specifies the expected URL path that incoming requests must match for the mock server to respond.
     /// This is useful for targeting specific endpoints, such as API routes, to ensure only relevant requests trigger the mock response.
     ///
+    /// Paths are compared exactly, so `path("/test")` does not match a request to `/test/`.
+    /// Use [`strict_trailing_slash`](#method.strict_trailing_slash) to ignore trailing slashes.
+    ///
     /// # Parameters
     /// - `path`: A string or other value convertible to `String` that represents the expected URL path.
     ///
@@ -931,31 +1314,68 @@ impl When {
     }
     // @docs-group: Path
 
-    /// Specifies the URL path that incoming requests must *not* match for the mock server to respond.
-    /// This is helpful when you need to exclude specific endpoints while allowing others through.
+    /// Controls whether [`path`](#method.path) and [`path_not`](#method.path_not) treat a
+    /// trailing slash as significant.
     ///
-    /// To add multiple excluded paths, invoke this function multiple times.
+    /// By default, trailing slashes are significant: `path("/users")` matches a request to
+    /// `/users`, but not to `/users/`. When set to `false`, a single trailing slash is ignored
+    /// on both sides of the comparison, so `/users` and `/users/` are considered equal.
     ///
     /// # Parameters
-    /// - `path`: A string or other value convertible to `String` that represents the URL path to exclude.
+    /// - `strict`: `true` to compare paths exactly (the default), `false` to ignore a trailing slash.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that will not match requests to `/exclude`
     /// let mock = server.mock(|when, then| {
-    ///     when.path_not("/exclude");
-    ///     then.status(200);  // Respond with status 200 for all other paths
+    ///     when.path("/users").strict_trailing_slash(false);
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request to a path that does not match the exclusion
-    /// let response = reqwest::blocking::get(server.url("/include")).unwrap();
+    /// assert_eq!(reqwest::blocking::get(server.url("/users")).unwrap().status(), 200);
+    /// assert_eq!(reqwest::blocking::get(server.url("/users/")).unwrap().status(), 200);
     ///
-    /// // Ensure the request was successful
+    /// mock.assert_calls(2);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance, allowing method chaining for additional configuration.
+    pub fn strict_trailing_slash(mut self, strict: bool) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.strict_trailing_slash = Some(strict);
+        });
+        self
+    }
+    // @docs-group: Path
+
+    /// Specifies the URL path that incoming requests must *not* match for the mock server to respond.
+    /// This is helpful when you need to exclude specific endpoints while allowing others through.
+    ///
+    /// To add multiple excluded paths, invoke this function multiple times.
+    ///
+    /// # Parameters
+    /// - `path`: A string or other value convertible to `String` that represents the URL path to exclude.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that will not match requests to `/exclude`
+    /// let mock = server.mock(|when, then| {
+    ///     when.path_not("/exclude");
+    ///     then.status(200);  // Respond with status 200 for all other paths
+    /// });
+    ///
+    /// // Make a request to a path that does not match the exclusion
+    /// let response = reqwest::blocking::get(server.url("/include")).unwrap();
+    ///
+    /// // Ensure the request was successful
     /// assert_eq!(response.status(), 200);
     ///
     /// // Verify that the mock was called at least once
@@ -1326,6 +1746,112 @@ impl When {
     }
     // @docs-group: Path
 
+    /// Specifies a glob pattern that the URL path must match for the mock server to respond.
+    /// This is a more concise alternative to [`path_matches`](#method.path_matches) for common
+    /// wildcard patterns.
+    ///
+    /// The following wildcards are supported:
+    /// - `*` matches any sequence of characters within a single path segment, i.e., it does not
+    ///   match `/`.
+    /// - `**` matches any sequence of characters across path segments. `/**/` also matches a
+    ///   single `/`, so `/api/**/items` matches `/api/items` as well.
+    ///
+    /// All other characters are matched literally against the whole path.
+    ///
+    /// # Parameters
+    /// - `pattern`: The glob pattern to match against the URL path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that matches paths like "/api/v1/items", but not "/api/v1/v2/items"
+    /// let mock = server.mock(|when, then| {
+    ///     when.path_glob("/api/*/items");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Make a request to a path that matches the glob pattern
+    /// let response = reqwest::blocking::get(server.url("/api/v1/items")).unwrap();
+    ///
+    /// // Ensure the request was successful
+    /// assert_eq!(response.status(), 200);
+    ///
+    /// // Verify that the mock server returned the expected response
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    ///
+    pub fn path_glob<IntoString: Into<String>>(mut self, pattern: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.path_glob.is_none() {
+                e.path_glob = Some(Vec::new());
+            }
+            e.path_glob.as_mut().unwrap().push(pattern.into())
+        });
+        self
+    }
+    // @docs-group: Path
+
+    /// Specifies a path template, such as `/users/{id}`, that the URL path must match for the
+    /// mock server to respond. Each `{name}` placeholder matches exactly one non-empty path
+    /// segment, so requests with missing or additional segments do not match. All other
+    /// characters are matched literally against the whole path.
+    ///
+    /// The captured segments can be used in a response body template under the name
+    /// `path.<name>` (see [`Then::body_template`](struct.Then.html#method.body_template)).
+    ///
+    /// Parameter names may only contain ASCII letters, digits and underscores, and each name
+    /// may only be used once per template. Mocks with invalid templates are rejected when they
+    /// are created.
+    ///
+    /// # Parameters
+    /// - `template`: The path template to match against the URL path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that matches paths like "/users/42", but not "/users/42/orders"
+    /// let mock = server.mock(|when, then| {
+    ///     when.path_template("/users/{id}");
+    ///     then.status(200)
+    ///         .body_template(r#"{"id": "{{ path.id }}"}"#);
+    /// });
+    ///
+    /// // Make a request to a path that matches the template
+    /// let response = reqwest::blocking::get(server.url("/users/42")).unwrap();
+    ///
+    /// // Ensure the captured path parameter was rendered into the response body
+    /// assert_eq!(response.text().unwrap(), r#"{"id": "42"}"#);
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    ///
+    pub fn path_template<IntoString: Into<String>>(mut self, template: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.path_template.is_none() {
+                e.path_template = Some(Vec::new());
+            }
+            e.path_template
+                .as_mut()
+                .unwrap()
+                .push(PathTemplate::from(template.into()))
+        });
+        self
+    }
+    // @docs-group: Path
+
     /// Specifies a required query parameter for the request.
     /// This function ensures that the specified query parameter (key-value pair) must be included
     /// in the request URL for the mock server to respond.
@@ -1903,6 +2429,49 @@ impl When {
     }
     // @docs-group: Query Parameters
 
+    /// Specifies that the value of the query parameter with the given name must match a regular
+    /// expression pattern. Unlike [`query_param_matches`](#method.query_param_matches), the
+    /// name is compared exactly (case-sensitive), so there is no need to anchor and escape it.
+    /// If the parameter occurs multiple times, at least one of its values must match.
+    ///
+    /// # Parameters
+    /// - `name`: The exact name of the query parameter.
+    /// - `value_regex`: A regular expression pattern for the query parameter's value to match against.
+    ///
+    /// # Example
+    /// ```rust
+    /// // Arrange
+    /// use httpmock::prelude::*;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects the query parameter "page" to have a numeric value
+    /// let m = server.mock(|when, then| {
+    ///     when.query_param_value_matches("page", r"^\d+$");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    /// });
+    ///
+    /// // Act: Make a request with a numeric value for the query parameter "page"
+    /// reqwest::blocking::get(&server.url("/search?page=12")).unwrap();
+    ///
+    /// // Assert: Verify that the mock was called at least once
+    /// m.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    ///
+    pub fn query_param_value_matches<KeyString: Into<String>, ValueRegex: Into<Regex>>(
+        self,
+        name: KeyString,
+        value_regex: ValueRegex,
+    ) -> Self {
+        let name_regex = format!("^{}$", regex::escape(&name.into()));
+        self.query_param_matches(name_regex, value_regex)
+    }
+    // @docs-group: Query Parameters
+
     /// Specifies that the count of query parameters with keys and values matching specific regular
     /// expression patterns must equal a specified number for the request to match.
     /// This function ensures that the number of query parameters whose keys and values match the
@@ -1961,215 +2530,220 @@ impl When {
     }
     // @docs-group: Query Parameters
 
-    /// Sets the expected HTTP header and its value for the request to match.
-    /// This function ensures that the specified header with the given value is present in the request.
-    /// Header names are case-insensitive, as per RFC 2616.
+    /// Specifies that a query parameter that may occur multiple times in the request URL
+    /// (e.g., `?tag=a&tag=b`) must have exactly the given values, irrespective of their order.
+    /// Values are compared as a multiset, so a value that is expected twice must occur twice.
+    ///
+    /// Use [`When::query_param_includes_all`](#method.query_param_includes_all) if the request
+    /// may contain additional values.
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `value`: The expected value of the HTTP header.
+    /// - `name`: The name of the query parameter.
+    /// - `values`: The complete list of expected values.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header with a specific value
-    /// let mock = server.mock(|when, then| {
-    ///     when.header("Authorization", "token 1234567890");
-    ///     then.status(200);  // Respond with a 200 status code if the header and value are present
+    /// let m = server.mock(|when, then| {
+    ///     when.query_param_all("tag", &["a", "b", "c"]);
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with the specified value
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token 1234567890")
-    ///     .send()
-    ///     .unwrap();
+    /// reqwest::blocking::get(&server.url("/search?tag=c&tag=a&tag=b")).unwrap();
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// m.assert();
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    ///
-    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn query_param_all<IntoString: Into<String>, ValueRef: AsRef<str>>(
         mut self,
-        name: KeyString,
-        value: ValueString,
+        name: IntoString,
+        values: &[ValueRef],
     ) -> Self {
+        let name = name.into();
+        let values = values.iter().map(|v| v.as_ref().to_string()).collect();
+
         update_cell(&self.expectations, |e| {
-            if e.header.is_none() {
-                e.header = Some(Vec::new());
+            if e.query_param_all.is_none() {
+                e.query_param_all = Some(Vec::new());
             }
-            e.header.as_mut().unwrap().push((name.into(), value.into()));
+            e.query_param_all.as_mut().unwrap().push((name, values));
         });
         self
     }
-    // @docs-group: Headers
+    // @docs-group: Query Parameters
 
-    /// Sets the requirement that the HTTP request must not contain a specific header with the specified value.
-    /// This function ensures that the specified header with the given value is absent in the request.
-    /// Header names are case-insensitive, as per RFC 2616.
-    ///
-    /// This function may be called multiple times to add multiple excluded headers.
+    /// Specifies that a query parameter that may occur multiple times in the request URL
+    /// (e.g., `?tag=a&tag=b`) must have at least the given values, irrespective of their order.
+    /// Additional values are allowed. Values are compared as a multiset, so a value that is
+    /// expected twice must occur at least twice.
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `value`: The value of the HTTP header that must not be present.
+    /// - `name`: The name of the query parameter.
+    /// - `values`: The values that the query parameter must include.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header with a specific value to be absent
-    /// let mock = server.mock(|when, then| {
-    ///     when.header_not("Authorization", "token 1234567890");
-    ///     then.status(200);  // Respond with a 200 status code if the header and value are absent
+    /// let m = server.mock(|when, then| {
+    ///     when.query_param_includes_all("tag", &["a", "b"]);
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with a different value
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token abcdefg")
-    ///     .send()
-    ///     .unwrap();
+    /// reqwest::blocking::get(&server.url("/search?tag=b&tag=c&tag=a")).unwrap();
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// m.assert();
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    ///
-    pub fn header_not<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn query_param_includes_all<IntoString: Into<String>, ValueRef: AsRef<str>>(
         mut self,
-        name: KeyString,
-        value: ValueString,
+        name: IntoString,
+        values: &[ValueRef],
     ) -> Self {
+        let name = name.into();
+        let values = values.iter().map(|v| v.as_ref().to_string()).collect();
+
         update_cell(&self.expectations, |e| {
-            if e.header_not.is_none() {
-                e.header_not = Some(Vec::new());
+            if e.query_param_includes_all.is_none() {
+                e.query_param_includes_all = Some(Vec::new());
             }
-            e.header_not
+            e.query_param_includes_all
                 .as_mut()
                 .unwrap()
-                .push((name.into(), value.into()));
+                .push((name, values));
         });
         self
     }
-    // @docs-group: Headers
+    // @docs-group: Query Parameters
 
-    /// Sets the requirement that the HTTP request must contain a specific header.
-    /// The presence of the header is checked, but its value is not validated.
-    /// For value validation, refer to [Mock::expect_header](struct.Mock.html#method.expect_header).
+    /// Specifies the exact query string that the request URL must have, as it was sent by the
+    /// client. The query string is compared without decoding it and without splitting it into
+    /// parameters, so this also works for encodings that the query parameter matchers cannot
+    /// express, such as repeated keys without values or `;` separators.
+    ///
+    /// The value must not contain the leading `?`. A request without a query string has an
+    /// empty raw query string.
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive, as per RFC 2616.
+    /// - `query`: The expected query string.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header to be present in the request
-    /// let mock = server.mock(|when, then| {
-    ///     when.header_exists("Authorization");
-    ///     then.status(200);  // Respond with a 200 status code if the header is present
+    /// let m = server.mock(|when, then| {
+    ///     when.raw_query("flag;flag;id=a%2Fb");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token 1234567890")
-    ///     .send()
-    ///     .unwrap();
+    /// reqwest::blocking::get(&server.url("/search?flag;flag;id=a%2Fb")).unwrap();
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// m.assert();
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    ///
-    pub fn header_exists<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
+    pub fn raw_query<IntoString: Into<String>>(mut self, query: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_exists.is_none() {
-                e.header_exists = Some(Vec::new());
-            }
-            e.header_exists.as_mut().unwrap().push(name.into());
+            e.raw_query = Some(query.into());
         });
         self
     }
-    // @docs-group: Headers
+    // @docs-group: Query Parameters
 
-    /// Sets the requirement that the HTTP request must not contain a specific header.
-    /// This function ensures that the specified header is absent in the request.
-    /// Header names are case-insensitive, as per RFC 2616.
-    ///
-    /// This function may be called multiple times to add multiple excluded headers.
+    /// Specifies a substring that the undecoded query string of the request URL must contain.
+    /// See [`raw_query`](#method.raw_query) for details about the raw query string.
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `substring`: The substring that the query string must contain.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header to be absent in the request
-    /// let mock = server.mock(|when, then| {
-    ///     when.header_missing("Authorization");
-    ///     then.status(200);  // Respond with a 200 status code if the header is absent
+    /// let m = server.mock(|when, then| {
+    ///     when.raw_query_includes("filter=a%2Cb");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that does not include the "Authorization" header
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .send()
-    ///     .unwrap();
+    /// reqwest::blocking::get(&server.url("/search?page=2&filter=a%2Cb")).unwrap();
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// m.assert();
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    ///
-    pub fn header_missing<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
+    pub fn raw_query_includes<IntoString: Into<String>>(mut self, substring: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_missing.is_none() {
-                e.header_missing = Some(Vec::new());
-            }
-            e.header_missing.as_mut().unwrap().push(name.into());
+            e.raw_query_includes
+                .get_or_insert_with(Vec::new)
+                .push(substring.into());
         });
         self
     }
-    // @docs-group: Headers
+    // @docs-group: Query Parameters
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value contains a specified substring.
-    /// This function ensures that the specified header is present and its value contains the given substring.
-    /// Header names are case-insensitive, as per RFC 2616.
+    /// Specifies a regular expression that the undecoded query string of the request URL must
+    /// match. See [`raw_query`](#method.raw_query) for details about the raw query string.
     ///
-    /// This function may be called multiple times to check multiple headers and substrings.
+    /// # Parameters
+    /// - `regex`: The regular expression that the query string must match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.raw_query_matches(r"^id=\d+(;id=\d+)*$");
+    ///     then.status(200);
+    /// });
+    ///
+    /// reqwest::blocking::get(&server.url("/items?id=1;id=2;id=3")).unwrap();
+    ///
+    /// m.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    ///
+    /// # Errors
+    /// This function will panic if the provided regex pattern is invalid.
+    pub fn raw_query_matches<TryIntoRegex: TryInto<Regex>>(mut self, regex: TryIntoRegex) -> Self
+    where
+        <TryIntoRegex as TryInto<Regex>>::Error: std::fmt::Debug,
+    {
+        let regex = regex
+            .try_into()
+            .expect("cannot convert provided value into regex");
+        update_cell(&self.expectations, |e| {
+            e.raw_query_matches.get_or_insert_with(Vec::new).push(regex);
+        });
+        self
+    }
+    // @docs-group: Query Parameters
+
+    /// Sets the expected HTTP header and its value for the request to match.
+    /// This function ensures that the specified header with the given value is present in the request.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
     /// # Parameters
     /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `substring`: The substring that the header value must contain.
+    /// - `value`: The expected value of the HTTP header.
     ///
     /// # Example
     /// ```rust
@@ -2179,13 +2753,13 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to contain "token"
+    /// // Create a mock that expects the "Authorization" header with a specific value
     /// let mock = server.mock(|when, then| {
-    ///     when.header_includes("Authorization", "token");
-    ///     then.status(200);  // Respond with a 200 status code if the header value contains the substring
+    ///     when.header("Authorization", "token 1234567890");
+    ///     then.status(200);  // Respond with a 200 status code if the header and value are present
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with the specified substring in its value
+    /// // Make a request that includes the "Authorization" header with the specified value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
     ///     .header("Authorization", "token 1234567890")
@@ -2199,89 +2773,88 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_includes<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        substring: ValueString,
+        value: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_includes.is_none() {
-                e.header_includes = Some(Vec::new());
+            if e.header.is_none() {
+                e.header = Some(Vec::new());
             }
-            e.header_includes
-                .as_mut()
-                .unwrap()
-                .push((name.into(), substring.into()));
+            e.header.as_mut().unwrap().push((name.into(), value.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value does not contain a specified substring.
-    /// This function ensures that the specified header is present and its value does not contain the given substring.
-    /// Header names are case-insensitive, as per RFC 2616.
+    /// Sets the expected HTTP header and its value for the request to match, where the header
+    /// name must match with exact case.
     ///
-    /// This function may be called multiple times to check multiple headers and substrings.
+    /// Header names are case-insensitive as per RFC 9110, which is how [`header`](#method.header)
+    /// matches them. This method is meant for testing clients against servers that incorrectly
+    /// treat header names as case-sensitive, so it requires the header name to be sent in
+    /// exactly the given case. The header value is matched like in [`header`](#method.header).
+    ///
+    /// The original case of header names is only known for HTTP/1 requests. HTTP/2 requires
+    /// lowercase header names, so only lowercase names can match HTTP/2 requests.
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `substring`: The substring that the header value must not contain.
+    /// - `name`: The HTTP header name in the exact case the request must contain it.
+    /// - `value`: The expected value of the HTTP header.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
     ///
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to not contain "Bearer"
+    /// // Create a mock that expects the header name "X-API-Key" in this exact case
     /// let mock = server.mock(|when, then| {
-    ///     when.header_excludes("Authorization", "Bearer");
-    ///     then.status(200);  // Respond with a 200 status code if the header value does not contain the substring
+    ///     when.header_name_exact("X-API-Key", "secret");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header without the forbidden substring in its value
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token 1234567890")
-    ///     .send()
-    ///     .unwrap();
+    /// // Send requests with the header name in different cases
+    /// for name in ["x-api-key", "X-API-Key"] {
+    ///     let mut stream = TcpStream::connect(server.address()).unwrap();
+    ///     write!(stream, "GET / HTTP/1.1\r\nHost: localhost\r\n{}: secret\r\nConnection: close\r\n\r\n", name).unwrap();
+    ///     stream.read_to_end(&mut Vec::new()).unwrap();
+    /// }
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// // Only the request with the exact header name matched
+    /// mock.assert_calls(1);
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_excludes<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn header_name_exact<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        substring: ValueString,
+        value: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_excludes.is_none() {
-                e.header_excludes = Some(Vec::new());
-            }
-            e.header_excludes
-                .as_mut()
-                .unwrap()
-                .push((name.into(), substring.into()));
+            e.header_name_exact
+                .get_or_insert_with(Vec::new)
+                .push((name.into(), value.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value starts with a specified prefix.
-    /// This function ensures that the specified header is present and its value starts with the given prefix.
+    /// Sets the requirement that the HTTP request must not contain a specific header with the specified value.
+    /// This function ensures that the specified header with the given value is absent in the request.
     /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// This function may be called multiple times to check multiple headers and prefixes.
+    /// This function may be called multiple times to add multiple excluded headers.
     ///
     /// # Parameters
     /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `prefix`: The prefix that the header value must start with.
+    /// - `value`: The value of the HTTP header that must not be present.
     ///
     /// # Example
     /// ```rust
@@ -2291,16 +2864,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to start with "token"
+    /// // Create a mock that expects the "Authorization" header with a specific value to be absent
     /// let mock = server.mock(|when, then| {
-    ///     when.header_prefix("Authorization", "token");
-    ///     then.status(200);  // Respond with a 200 status code if the header value starts with the prefix
+    ///     when.header_not("Authorization", "token 1234567890");
+    ///     then.status(200);  // Respond with a 200 status code if the header and value are absent
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with the specified prefix in its value
+    /// // Make a request that includes the "Authorization" header with a different value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token 1234567890")
+    ///     .header("Authorization", "token abcdefg")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2311,33 +2884,30 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_prefix<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn header_not<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        prefix: ValueString,
+        value: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_prefix.is_none() {
-                e.header_prefix = Some(Vec::new());
+            if e.header_not.is_none() {
+                e.header_not = Some(Vec::new());
             }
-            e.header_prefix
+            e.header_not
                 .as_mut()
                 .unwrap()
-                .push((name.into(), prefix.into()));
+                .push((name.into(), value.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value ends with a specified suffix.
-    /// This function ensures that the specified header is present and its value ends with the given suffix.
-    /// Header names are case-insensitive, as per RFC 2616.
-    ///
-    /// This function may be called multiple times to check multiple headers and suffixes.
+    /// Sets the requirement that the HTTP request must contain a specific header.
+    /// The presence of the header is checked, but its value is not validated.
+    /// For value validation, refer to [Mock::expect_header](struct.Mock.html#method.expect_header).
     ///
     /// # Parameters
-    /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `suffix`: The suffix that the header value must end with.
+    /// - `name`: The HTTP header name. Header names are case-insensitive, as per RFC 2616.
     ///
     /// # Example
     /// ```rust
@@ -2347,13 +2917,13 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to end with "7890"
+    /// // Create a mock that expects the "Authorization" header to be present in the request
     /// let mock = server.mock(|when, then| {
-    ///     when.header_suffix("Authorization", "7890");
-    ///     then.status(200);  // Respond with a 200 status code if the header value ends with the suffix
+    ///     when.header_exists("Authorization");
+    ///     then.status(200);  // Respond with a 200 status code if the header is present
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with the specified suffix in its value
+    /// // Make a request that includes the "Authorization" header
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
     ///     .header("Authorization", "token 1234567890")
@@ -2367,33 +2937,25 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_suffix<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        suffix: ValueString,
-    ) -> Self {
+    pub fn header_exists<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_suffix.is_none() {
-                e.header_suffix = Some(Vec::new());
+            if e.header_exists.is_none() {
+                e.header_exists = Some(Vec::new());
             }
-            e.header_suffix
-                .as_mut()
-                .unwrap()
-                .push((name.into(), suffix.into()));
+            e.header_exists.as_mut().unwrap().push(name.into());
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value does not start with a specified prefix.
-    /// This function ensures that the specified header is present and its value does not start with the given prefix.
+    /// Sets the requirement that the HTTP request must not contain a specific header.
+    /// This function ensures that the specified header is absent in the request.
     /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// This function may be called multiple times to check multiple headers and prefixes.
+    /// This function may be called multiple times to add multiple excluded headers.
     ///
     /// # Parameters
     /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `prefix`: The prefix that the header value must not start with.
     ///
     /// # Example
     /// ```rust
@@ -2403,16 +2965,15 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to not start with "Bearer"
+    /// // Create a mock that expects the "Authorization" header to be absent in the request
     /// let mock = server.mock(|when, then| {
-    ///     when.header_prefix_not("Authorization", "Bearer");
-    ///     then.status(200);  // Respond with a 200 status code if the header value does not start with the prefix
+    ///     when.header_missing("Authorization");
+    ///     then.status(200);  // Respond with a 200 status code if the header is absent
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header without the "Bearer" prefix in its value
+    /// // Make a request that does not include the "Authorization" header
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2423,33 +2984,26 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        prefix: ValueString,
-    ) -> Self {
+    pub fn header_missing<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_prefix_not.is_none() {
-                e.header_prefix_not = Some(Vec::new());
+            if e.header_missing.is_none() {
+                e.header_missing = Some(Vec::new());
             }
-            e.header_prefix_not
-                .as_mut()
-                .unwrap()
-                .push((name.into(), prefix.into()));
+            e.header_missing.as_mut().unwrap().push(name.into());
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose value does not end with a specified suffix.
-    /// This function ensures that the specified header is present and its value does not end with the given suffix.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value contains a specified substring.
+    /// This function ensures that the specified header is present and its value contains the given substring.
     /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// This function may be called multiple times to check multiple headers and suffixes.
+    /// This function may be called multiple times to check multiple headers and substrings.
     ///
     /// # Parameters
     /// - `name`: The HTTP header name. Header names are case-insensitive.
-    /// - `suffix`: The suffix that the header value must not end with.
+    /// - `substring`: The substring that the header value must contain.
     ///
     /// # Example
     /// ```rust
@@ -2459,13 +3013,13 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's value to not end with "abc"
+    /// // Create a mock that expects the "Authorization" header's value to contain "token"
     /// let mock = server.mock(|when, then| {
-    ///     when.header_suffix_not("Authorization", "abc");
-    ///     then.status(200);  // Respond with a 200 status code if the header value does not end with the suffix
+    ///     when.header_includes("Authorization", "token");
+    ///     then.status(200);  // Respond with a 200 status code if the header value contains the substring
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header without the "abc" suffix in its value
+    /// // Make a request that includes the "Authorization" header with the specified substring in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
     ///     .header("Authorization", "token 1234567890")
@@ -2479,33 +3033,33 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
+    pub fn header_includes<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        suffix: ValueString,
+        substring: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_suffix_not.is_none() {
-                e.header_suffix_not = Some(Vec::new());
+            if e.header_includes.is_none() {
+                e.header_includes = Some(Vec::new());
             }
-            e.header_suffix_not
+            e.header_includes
                 .as_mut()
                 .unwrap()
-                .push((name.into(), suffix.into()));
+                .push((name.into(), substring.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific header whose key and value match the specified regular expressions.
-    /// This function ensures that the specified header is present and both its key and value match the given regular expressions.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value does not contain a specified substring.
+    /// This function ensures that the specified header is present and its value does not contain the given substring.
     /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// This function may be called multiple times to check multiple headers and patterns.
+    /// This function may be called multiple times to check multiple headers and substrings.
     ///
     /// # Parameters
-    /// - `key_regex`: The regular expression that the header key must match.
-    /// - `value_regex`: The regular expression that the header value must match.
+    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `substring`: The substring that the header value must not contain.
     ///
     /// # Example
     /// ```rust
@@ -2515,14 +3069,13 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the "Authorization" header's key to match the regex "^Auth.*"
-    /// // and its value to match the regex "token .*"
+    /// // Create a mock that expects the "Authorization" header's value to not contain "Bearer"
     /// let mock = server.mock(|when, then| {
-    ///     when.header_matches("^Auth.*", "token .*");
-    ///     then.status(200);  // Respond with a 200 status code if the header key and value match the patterns
+    ///     when.header_excludes("Authorization", "Bearer");
+    ///     then.status(200);  // Respond with a 200 status code if the header value does not contain the substring
     /// });
     ///
-    /// // Make a request that includes the "Authorization" header with a value matching the regex
+    /// // Make a request that includes the "Authorization" header without the forbidden substring in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
     ///     .header("Authorization", "token 1234567890")
@@ -2536,34 +3089,33 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_matches<KeyString: Into<Regex>, ValueString: Into<Regex>>(
+    pub fn header_excludes<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
-        key_regex: KeyString,
-        value_regex: ValueString,
+        name: KeyString,
+        substring: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_matches.is_none() {
-                e.header_matches = Some(Vec::new());
+            if e.header_excludes.is_none() {
+                e.header_excludes = Some(Vec::new());
             }
-            e.header_matches
+            e.header_excludes
                 .as_mut()
                 .unwrap()
-                .push((key_regex.into(), value_regex.into()));
+                .push((name.into(), substring.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the requirement that the HTTP request must contain a specific number of headers whose keys and values match specified patterns.
-    /// This function ensures that the specified number of headers with keys and values matching the given patterns are present in the request.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value starts with a specified prefix.
+    /// This function ensures that the specified header is present and its value starts with the given prefix.
     /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// This function may be called multiple times to check multiple patterns and counts.
+    /// This function may be called multiple times to check multiple headers and prefixes.
     ///
     /// # Parameters
-    /// - `key_pattern`: The pattern that the header keys must match.
-    /// - `value_pattern`: The pattern that the header values must match.
-    /// - `count`: The number of headers with keys and values matching the patterns that must be present.
+    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `prefix`: The prefix that the header value must start with.
     ///
     /// # Example
     /// ```rust
@@ -2573,18 +3125,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects at least 2 headers whose keys match the regex "^X-Custom-Header.*"
-    /// // and values match the regex "value.*"
+    /// // Create a mock that expects the "Authorization" header's value to start with "token"
     /// let mock = server.mock(|when, then| {
-    ///     when.header_count("^X-Custom-Header.*", "value.*", 2);
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.header_prefix("Authorization", "token");
+    ///     then.status(200);  // Respond with a 200 status code if the header value starts with the prefix
     /// });
     ///
-    /// // Make a request that includes the required headers
+    /// // Make a request that includes the "Authorization" header with the specified prefix in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("x-custom-header-1", "value1")
-    ///     .header("X-Custom-Header-2", "value2")
+    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2595,54 +3145,33 @@ impl When {
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
     ///
-    pub fn header_count<
-        KeyRegex: TryInto<Regex>,
-        ValueRegex: TryInto<Regex>,
-        IntoUsize: TryInto<usize>,
-    >(
+    pub fn header_prefix<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
-        key_pattern: KeyRegex,
-        value_pattern: ValueRegex,
-        count: IntoUsize,
-    ) -> Self
-    where
-        <KeyRegex as TryInto<Regex>>::Error: std::fmt::Debug,
-        <ValueRegex as TryInto<Regex>>::Error: std::fmt::Debug,
-        <IntoUsize as TryInto<usize>>::Error: std::fmt::Debug,
-    {
-        let count = match count.try_into() {
-            Ok(c) => c,
-            Err(_) => panic!("parameter count must be a positive integer that fits into a usize"),
-        };
-
-        let key_pattern = key_pattern.try_into().expect("cannot convert key to regex");
-        let value_pattern = value_pattern
-            .try_into()
-            .expect("cannot convert key to regex");
-
+        name: KeyString,
+        prefix: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.header_count.is_none() {
-                e.header_count = Some(Vec::new());
+            if e.header_prefix.is_none() {
+                e.header_prefix = Some(Vec::new());
             }
-            e.header_count.as_mut().unwrap().push((
-                key_pattern.into(),
-                value_pattern.into(),
-                count,
-            ));
+            e.header_prefix
+                .as_mut()
+                .unwrap()
+                .push((name.into(), prefix.into()));
         });
         self
     }
     // @docs-group: Headers
 
-    /// Sets the cookie that needs to exist in the HTTP request.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value ends with a specified suffix.
+    /// This function ensures that the specified header is present and its value ends with the given suffix.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie. Must be a case-sensitive match.
-    /// - `value`: The expected value of the cookie.
+    /// This function may be called multiple times to check multiple headers and suffixes.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `suffix`: The suffix that the header value must end with.
     ///
     /// # Example
     /// ```rust
@@ -2652,16 +3181,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with the value "1234567890"
+    /// // Create a mock that expects the "Authorization" header's value to end with "7890"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie("SESSIONID", "1234567890");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.header_suffix("Authorization", "7890");
+    ///     then.status(200);  // Respond with a 200 status code if the header value ends with the suffix
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request that includes the "Authorization" header with the specified suffix in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
+    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2671,30 +3200,34 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie<KeyString: Into<String>, ValueString: Into<String>>(
+    ///
+    pub fn header_suffix<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        value: ValueString,
+        suffix: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie.is_none() {
-                e.cookie = Some(Vec::new());
+            if e.header_suffix.is_none() {
+                e.header_suffix = Some(Vec::new());
             }
-            e.cookie.as_mut().unwrap().push((name.into(), value.into()));
+            e.header_suffix
+                .as_mut()
+                .unwrap()
+                .push((name.into(), suffix.into()));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the cookie that should not exist or should not have a specific value in the HTTP request.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value does not start with a specified prefix.
+    /// This function ensures that the specified header is present and its value does not start with the given prefix.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie. Must be a case-sensitive match.
-    /// - `value`: The value that the cookie should not have.
+    /// This function may be called multiple times to check multiple headers and prefixes.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `prefix`: The prefix that the header value must not start with.
     ///
     /// # Example
     /// ```rust
@@ -2704,16 +3237,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" to not have the value "1234567890"
+    /// // Create a mock that expects the "Authorization" header's value to not start with "Bearer"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_not("SESSIONID", "1234567890");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.header_prefix_not("Authorization", "Bearer");
+    ///     then.status(200);  // Respond with a 200 status code if the header value does not start with the prefix
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request that includes the "Authorization" header without the "Bearer" prefix in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=0987654321; CONSENT=1")
+    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2723,32 +3256,34 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_not<KeyString: Into<String>, ValueString: Into<String>>(
+    ///
+    pub fn header_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
         name: KeyString,
-        value: ValueString,
+        prefix: ValueString,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_not.is_none() {
-                e.cookie_not = Some(Vec::new());
+            if e.header_prefix_not.is_none() {
+                e.header_prefix_not = Some(Vec::new());
             }
-            e.cookie_not
+            e.header_prefix_not
                 .as_mut()
                 .unwrap()
-                .push((name.into(), value.into()));
+                .push((name.into(), prefix.into()));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist in the HTTP request.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a specific header whose value does not end with a specified suffix.
+    /// This function ensures that the specified header is present and its value does not end with the given suffix.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
+    /// This function may be called multiple times to check multiple headers and suffixes.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `name`: The HTTP header name. Header names are case-insensitive.
+    /// - `suffix`: The suffix that the header value must not end with.
     ///
     /// # Example
     /// ```rust
@@ -2758,16 +3293,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID"
+    /// // Create a mock that expects the "Authorization" header's value to not end with "abc"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_exists("SESSIONID");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.header_suffix_not("Authorization", "abc");
+    ///     then.status(200);  // Respond with a 200 status code if the header value does not end with the suffix
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request that includes the "Authorization" header without the "abc" suffix in its value
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
+    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2777,25 +3312,34 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_exists<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
+    ///
+    pub fn header_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        suffix: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_exists.is_none() {
-                e.cookie_exists = Some(Vec::new());
+            if e.header_suffix_not.is_none() {
+                e.header_suffix_not = Some(Vec::new());
             }
-            e.cookie_exists.as_mut().unwrap().push(name.into());
+            e.header_suffix_not
+                .as_mut()
+                .unwrap()
+                .push((name.into(), suffix.into()));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must not exist in the HTTP request.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a specific header whose key and value match the specified regular expressions.
+    /// This function ensures that the specified header is present and both its key and value match the given regular expressions.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must not exist.
+    /// This function may be called multiple times to check multiple headers and patterns.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `key_regex`: The regular expression that the header key must match.
+    /// - `value_regex`: The regular expression that the header value must match.
     ///
     /// # Example
     /// ```rust
@@ -2805,16 +3349,17 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" not to exist
+    /// // Create a mock that expects the "Authorization" header's key to match the regex "^Auth.*"
+    /// // and its value to match the regex "token .*"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_missing("SESSIONID");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.header_matches("^Auth.*", "token .*");
+    ///     then.status(200);  // Respond with a 200 status code if the header key and value match the patterns
     /// });
     ///
-    /// // Make a request that does not include the excluded cookie
+    /// // Make a request that includes the "Authorization" header with a value matching the regex
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; CONSENT=1")
+    ///     .header("Authorization", "token 1234567890")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2824,26 +3369,35 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_missing<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
+    ///
+    pub fn header_matches<KeyString: Into<Regex>, ValueString: Into<Regex>>(
+        mut self,
+        key_regex: KeyString,
+        value_regex: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_missing.is_none() {
-                e.cookie_missing = Some(Vec::new());
+            if e.header_matches.is_none() {
+                e.header_matches = Some(Vec::new());
             }
-            e.cookie_missing.as_mut().unwrap().push(name.into());
+            e.header_matches
+                .as_mut()
+                .unwrap()
+                .push((key_regex.into(), value_regex.into()));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must contain the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a specific number of headers whose keys and values match specified patterns.
+    /// This function ensures that the specified number of headers with keys and values matching the given patterns are present in the request.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_substring`: The substring that must be present in the cookie value.
+    /// This function may be called multiple times to check multiple patterns and counts.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `key_pattern`: The pattern that the header keys must match.
+    /// - `value_pattern`: The pattern that the header values must match.
+    /// - `count`: The number of headers with keys and values matching the patterns that must be present.
     ///
     /// # Example
     /// ```rust
@@ -2853,16 +3407,18 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value containing "1234"
+    /// // Create a mock that expects at least 2 headers whose keys match the regex "^X-Custom-Header.*"
+    /// // and values match the regex "value.*"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_includes("SESSIONID", "1234");
+    ///     when.header_count("^X-Custom-Header.*", "value.*", 2);
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request that includes the required headers
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=abc1234def; CONSENT=1")
+    ///     .header("x-custom-header-1", "value1")
+    ///     .header("X-Custom-Header-2", "value2")
     ///     .send()
     ///     .unwrap();
     ///
@@ -2872,143 +3428,142 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_includes<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value_substring: ValueString,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.cookie_includes.is_none() {
-                e.cookie_includes = Some(Vec::new());
+    ///
+    pub fn header_count<
+        KeyRegex: TryInto<Regex>,
+        ValueRegex: TryInto<Regex>,
+        IntoUsize: TryInto<usize>,
+    >(
+        mut self,
+        key_pattern: KeyRegex,
+        value_pattern: ValueRegex,
+        count: IntoUsize,
+    ) -> Self
+    where
+        <KeyRegex as TryInto<Regex>>::Error: std::fmt::Debug,
+        <ValueRegex as TryInto<Regex>>::Error: std::fmt::Debug,
+        <IntoUsize as TryInto<usize>>::Error: std::fmt::Debug,
+    {
+        let count = match count.try_into() {
+            Ok(c) => c,
+            Err(_) => panic!("parameter count must be a positive integer that fits into a usize"),
+        };
+
+        let key_pattern = key_pattern.try_into().expect("cannot convert key to regex");
+        let value_pattern = value_pattern
+            .try_into()
+            .expect("cannot convert key to regex");
+
+        update_cell(&self.expectations, |e| {
+            if e.header_count.is_none() {
+                e.header_count = Some(Vec::new());
             }
-            e.cookie_includes
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value_substring.into()));
+            e.header_count.as_mut().unwrap().push((
+                key_pattern.into(),
+                value_pattern.into(),
+                count,
+            ));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must not contain the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain at least `count` headers in total.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_substring`: The substring that must not be present in the cookie value.
+    /// Every occurrence of a header counts, so a header that is sent three times counts as
+    /// three headers. Headers that the HTTP client adds automatically (e.g., `Host`,
+    /// `Content-Length` or `Accept`) count as well.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `count`: The minimum number of headers.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value not containing "1234"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_excludes("SESSIONID", "1234");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.path("/").total_header_count_min(4);
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookie
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef; CONSENT=1")
+    /// let response = Client::new()
+    ///     .get(server.url("/"))
+    ///     .header("x-a", "1")
+    ///     .header("x-b", "2")
+    ///     .header("x-b", "3")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Verify that the mock was called at least once
     /// mock.assert();
+    /// assert_eq!(response.status(), 200);
     /// ```
     ///
     /// # Returns
-    /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_excludes<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value_substring: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining.
+    pub fn total_header_count_min(self, count: usize) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_excludes.is_none() {
-                e.cookie_excludes = Some(Vec::new());
-            }
-            e.cookie_excludes
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value_substring.into()));
+            e.total_header_count_min = Some(count);
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must start with the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain at most `count` headers in total.
+    /// This is useful to test how a gateway handles requests with suspiciously many headers.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_prefix`: The substring that must be at the start of the cookie value.
+    /// Every occurrence of a header counts, so a header that is sent three times counts as
+    /// three headers. Headers that the HTTP client adds automatically (e.g., `Host`,
+    /// `Content-Length` or `Accept`) count as well.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `count`: The maximum number of headers.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value starting with "1234"
+    /// // Requests with more than 50 headers do not match and receive a 404 response.
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_prefix("SESSIONID", "1234");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.path("/").total_header_count_max(50);
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookie
-    /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234abcdef; CONSENT=1")
-    ///     .send()
-    ///     .unwrap();
+    /// let mut request = Client::new().get(server.url("/"));
+    /// for i in 0..60 {
+    ///     request = request.header("x-flood", i.to_string());
+    /// }
+    /// let response = request.send().unwrap();
     ///
-    /// // Verify that the mock was called at least once
-    /// mock.assert();
+    /// mock.assert_calls(0);
+    /// assert_eq!(response.status(), 404);
     /// ```
     ///
     /// # Returns
-    /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_prefix<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value_prefix: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining.
+    pub fn total_header_count_max(self, count: usize) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_prefix.is_none() {
-                e.cookie_prefix = Some(Vec::new());
-            }
-            e.cookie_prefix
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value_prefix.into()));
+            e.total_header_count_max = Some(count);
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must end with the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that a header with the given name must appear exactly `count` times
+    /// in the HTTP request, regardless of its values.
+    /// This is useful for headers that may legitimately be sent multiple times, such as `Forwarded`.
+    /// Header names are case-insensitive, as per RFC 2616.
     ///
-    /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_suffix`: The substring that must be at the end of the cookie value.
+    /// This function may be called multiple times to check the counts of multiple headers.
     ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// # Parameters
+    /// - `name`: The name of the header.
+    /// - `count`: The number of times the header must appear in the request.
     ///
     /// # Example
     /// ```rust
@@ -3018,52 +3573,50 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value ending with "7890"
+    /// // Create a mock that expects the "Forwarded" header to be sent exactly twice
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_suffix("SESSIONID", "7890");
+    ///     when.header_value_count("Forwarded", 2);
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request that includes the header twice
     /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef7890; CONSENT=1")
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("forwarded", "for=192.0.2.60")
+    ///     .header("forwarded", "for=198.51.100.17")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Verify that the mock was called at least once
+    /// // Verify that the mock was called once
     /// mock.assert();
     /// ```
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_suffix<KeyString: Into<String>, ValueString: Into<String>>(
+    ///
+    pub fn header_value_count<IntoString: Into<String>>(
         mut self,
-        name: KeyString,
-        value_suffix: ValueString,
+        name: IntoString,
+        count: usize,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.cookie_suffix.is_none() {
-                e.cookie_suffix = Some(Vec::new());
+            if e.header_value_count.is_none() {
+                e.header_value_count = Some(Vec::new());
             }
-            e.cookie_suffix
+            e.header_value_count
                 .as_mut()
                 .unwrap()
-                .push((name.into(), value_suffix.into()));
+                .push((name.into(), count));
         });
         self
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must not start with the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a `User-Agent` header with the
+    /// specified value. This is a shorthand for `header("user-agent", value)`.
     ///
     /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_prefix`: The substring that must not be at the start of the cookie value.
-    ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// - `value`: The expected value of the `User-Agent` header.
     ///
     /// # Example
     /// ```rust
@@ -3073,16 +3626,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value not starting with "1234"
+    /// // Create a mock that expects a specific user agent
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_prefix_not("SESSIONID", "1234");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.user_agent("my-client/1.0");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request with the expected user agent
     /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcd1234; CONSENT=1")
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("User-Agent", "my-client/1.0")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3092,33 +3645,17 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value_prefix: ValueString,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.cookie_prefix_not.is_none() {
-                e.cookie_prefix_not = Some(Vec::new());
-            }
-            e.cookie_prefix_not
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value_prefix.into()));
-        });
-        self
+    ///
+    pub fn user_agent<IntoString: Into<String>>(self, value: IntoString) -> Self {
+        self.header("user-agent", value)
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with the specified name must exist and its value must not end with the specified substring.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain a `User-Agent` header whose value
+    /// contains the specified substring. This is a shorthand for `header_includes("user-agent", substring)`.
     ///
     /// # Parameters
-    /// - `name`: The name of the cookie that must exist.
-    /// - `value_suffix`: The substring that must not be at the end of the cookie value.
-    ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// - `substring`: The substring that the `User-Agent` header value must contain.
     ///
     /// # Example
     /// ```rust
@@ -3128,16 +3665,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie named "SESSIONID" with a value not ending with "7890"
+    /// // Create a mock that expects the user agent to mention "my-client"
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_suffix_not("SESSIONID", "7890");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.user_agent_includes("my-client");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookie
-    ///  Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef1234; CONSENT=1")
+    /// // Make a request with a matching user agent
+    /// Client::new()
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("User-Agent", "my-client/1.0 (linux)")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3147,33 +3684,17 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value_suffix: ValueString,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.cookie_suffix_not.is_none() {
-                e.cookie_suffix_not = Some(Vec::new());
-            }
-            e.cookie_suffix_not
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value_suffix.into()));
-        });
-        self
+    ///
+    pub fn user_agent_includes<IntoString: Into<String>>(self, substring: IntoString) -> Self {
+        self.header_includes("user-agent", substring)
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with a name matching the specified regex must exist and its value must match the specified regex.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain an `Accept` header with the
+    /// specified value. This is a shorthand for `header("accept", value)`.
     ///
     /// # Parameters
-    /// - `key_regex`: The regex pattern that the cookie name must match.
-    /// - `value_regex`: The regex pattern that the cookie value must match.
-    ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// - `value`: The expected value of the `Accept` header.
     ///
     /// # Example
     /// ```rust
@@ -3183,17 +3704,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie with a name matching the regex "^SESSION"
-    /// // and a value matching the regex "^[0-9]{10}$"
+    /// // Create a mock that expects the client to accept JSON
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_matches(r"^SESSION", r"^[0-9]{10}$");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.accept("application/json");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookie
+    /// // Make a request with the expected Accept header
     /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("Accept", "application/json")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3203,34 +3723,19 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_matches<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
-        mut self,
-        key_regex: KeyRegex,
-        value_regex: ValueRegex,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.cookie_matches.is_none() {
-                e.cookie_matches = Some(Vec::new());
-            }
-            e.cookie_matches
-                .as_mut()
-                .unwrap()
-                .push((key_regex.into(), value_regex.into()));
-        });
-        self
+    ///
+    pub fn accept<IntoString: Into<String>>(self, value: IntoString) -> Self {
+        self.header("accept", value)
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the requirement that a cookie with a name and value matching the specified regexes must appear a specified number of times in the HTTP request.
-    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
-    /// **Attention**: Cookie names are **case-sensitive**.
+    /// Sets the requirement that the HTTP request must contain an `Accept` header whose value
+    /// contains the specified substring. This is useful when clients send a list of media types,
+    /// such as `text/html, application/json;q=0.9`. This is a shorthand for
+    /// `header_includes("accept", substring)`.
     ///
     /// # Parameters
-    /// - `key_regex`: The regex pattern that the cookie name must match.
-    /// - `value_regex`: The regex pattern that the cookie value must match.
-    /// - `count`: The number of times a cookie with a matching name and value must appear.
-    ///
-    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
+    /// - `substring`: The substring that the `Accept` header value must contain.
     ///
     /// # Example
     /// ```rust
@@ -3240,17 +3745,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects a cookie with a name matching the regex "^SESSION"
-    /// // and a value matching the regex "^[0-9]{10}$" to appear exactly twice
+    /// // Create a mock that expects JSON to be among the accepted media types
     /// let mock = server.mock(|when, then| {
-    ///     when.cookie_count(r"^SESSION", r"^[0-9]{10}$", 2);
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.accept_includes("application/json");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request that includes the required cookies
+    /// // Make a request that accepts multiple media types
     /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Cookie", "SESSIONID=1234567890; TRACK=12345; SESSIONTOKEN=0987654321; CONSENT=1")
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("Accept", "text/html, application/json;q=0.9")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3260,32 +3764,17 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn cookie_count<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
-        mut self,
-        key_regex: KeyRegex,
-        value_regex: ValueRegex,
-        count: usize,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.cookie_count.is_none() {
-                e.cookie_count = Some(Vec::new());
-            }
-            e.cookie_count
-                .as_mut()
-                .unwrap()
-                .push((key_regex.into(), value_regex.into(), count));
-        });
-        self
+    ///
+    pub fn accept_includes<IntoString: Into<String>>(self, substring: IntoString) -> Self {
+        self.header_includes("accept", substring)
     }
-    // @docs-group: Cookies
+    // @docs-group: Headers
 
-    /// Sets the required HTTP request body content.
-    /// This method specifies that the HTTP request body must match the provided content exactly.
-    ///
-    /// **Note**: The body content is case-sensitive and must be an exact match.
+    /// Sets the requirement that the HTTP request must contain a `Content-Type` header with the
+    /// specified value. This is a shorthand for `header("content-type", value)`.
     ///
     /// # Parameters
-    /// - `body`: The required HTTP request body content. This parameter accepts any type that can be converted into a `String`.
+    /// - `value`: The expected value of the `Content-Type` header.
     ///
     /// # Example
     /// ```rust
@@ -3295,16 +3784,17 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to be "The Great Gatsby"
+    /// // Create a mock that expects a JSON request body
     /// let mock = server.mock(|when, then| {
-    ///     when.body("The Great Gatsby");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.method(POST).content_type("application/json");
+    ///     then.status(201);
     /// });
     ///
-    /// // Make a request with the required body content
+    /// // Make a request with the expected Content-Type header
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby")
+    ///     .header("Content-Type", "application/json")
+    ///     .body("{}")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3314,21 +3804,18 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn body<IntoString: Into<String>>(mut self, body: IntoString) -> Self {
-        update_cell(&self.expectations, |e| {
-            e.body = Some(HttpMockBytes::from(Bytes::from(body.into())));
-        });
-        self
+    ///
+    pub fn content_type<IntoString: Into<String>>(self, value: IntoString) -> Self {
+        self.header("content-type", value)
     }
-    // @docs-group: Body
+    // @docs-group: Headers
 
-    /// Sets the condition that the HTTP request body content must not match the specified value.
-    /// This method ensures that the request body does not contain the provided content exactly.
-    ///
-    /// **Note**: The body content is case-sensitive and must be an exact mismatch.
+    /// Sets the requirement that the HTTP request must contain a `Content-Type` header whose value
+    /// contains the specified substring. This is useful to ignore parameters such as
+    /// `charset=utf-8`. This is a shorthand for `header_includes("content-type", substring)`.
     ///
     /// # Parameters
-    /// - `body`: The body content that the HTTP request must not contain. This parameter accepts any type that can be converted into a `String`.
+    /// - `substring`: The substring that the `Content-Type` header value must contain.
     ///
     /// # Example
     /// ```rust
@@ -3338,16 +3825,17 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to not be "The Great Gatsby"
+    /// // Create a mock that expects a JSON request body regardless of the charset
     /// let mock = server.mock(|when, then| {
-    ///     when.body_not("The Great Gatsby");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.method(POST).content_type_includes("application/json");
+    ///     then.status(201);
     /// });
     ///
-    /// // Make a request with a different body content
+    /// // Make a request with a Content-Type header that includes a charset
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("A Tale of Two Cities")
+    ///     .header("Content-Type", "application/json; charset=utf-8")
+    ///     .body("{}")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3357,27 +3845,18 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn body_not<IntoString: Into<String>>(mut self, body: IntoString) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.body_not.is_none() {
-                e.body_not = Some(Vec::new());
-            }
-            e.body_not
-                .as_mut()
-                .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(body.into())));
-        });
-        self
+    ///
+    pub fn content_type_includes<IntoString: Into<String>>(self, substring: IntoString) -> Self {
+        self.header_includes("content-type", substring)
     }
-    // @docs-group: Body
+    // @docs-group: Headers
 
-    /// Sets the condition that the HTTP request body content must contain the specified substring.
-    /// This method ensures that the request body includes the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the requirement that the HTTP request must contain an `Authorization` header with the
+    /// specified value. This is a shorthand for `header("authorization", value)`.
     ///
     /// # Parameters
-    /// - `substring`: The substring that the HTTP request body must contain. This parameter accepts any type that can be converted into a `String`.
+    /// - `value`: The expected value of the `Authorization` header, including the scheme
+    ///   (e.g., `"Bearer abc123"`).
     ///
     /// # Example
     /// ```rust
@@ -3387,16 +3866,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to contain the substring "Gatsby"
+    /// // Create a mock that expects a specific bearer token
     /// let mock = server.mock(|when, then| {
-    ///     when.body_includes("Gatsby");
-    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    ///     when.authorization("Bearer abc123");
+    ///     then.status(200);
     /// });
     ///
-    /// // Make a request with the required substring in the body content
+    /// // Make a request with the expected Authorization header
     /// Client::new()
-    ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby is a novel.")
+    ///     .get(&format!("http://{}/test", server.address()))
+    ///     .header("Authorization", "Bearer abc123")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3406,27 +3885,21 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn body_includes<IntoString: Into<String>>(mut self, substring: IntoString) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.body_includes.is_none() {
-                e.body_includes = Some(Vec::new());
-            }
-            e.body_includes
-                .as_mut()
-                .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(substring.into())));
-        });
-        self
+    ///
+    pub fn authorization<IntoString: Into<String>>(self, value: IntoString) -> Self {
+        self.header("authorization", value)
     }
-    // @docs-group: Body
+    // @docs-group: Headers
 
-    /// Sets the condition that the HTTP request body content must not contain the specified substring.
-    /// This method ensures that the request body does not include the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the cookie that needs to exist in the HTTP request.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `substring`: The substring that the HTTP request body must not contain. This parameter accepts any type that can be converted into a `String`.
+    /// - `name`: The name of the cookie. Must be a case-sensitive match.
+    /// - `value`: The expected value of the cookie.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3436,16 +3909,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to not contain the substring "Gatsby"
+    /// // Create a mock that expects a cookie named "SESSIONID" with the value "1234567890"
     /// let mock = server.mock(|when, then| {
-    ///     when.body_excludes("Gatsby");
+    ///     when.cookie("SESSIONID", "1234567890");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with a different body content
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("A Tale of Two Cities is a novel.")
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3455,27 +3928,30 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn body_excludes<IntoString: Into<String>>(mut self, substring: IntoString) -> Self {
+    pub fn cookie<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.body_excludes.is_none() {
-                e.body_excludes = Some(Vec::new());
+            if e.cookie.is_none() {
+                e.cookie = Some(Vec::new());
             }
-            e.body_excludes
-                .as_mut()
-                .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(substring.into())));
+            e.cookie.as_mut().unwrap().push((name.into(), value.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must begin with the specified substring.
-    /// This method ensures that the request body starts with the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the cookie that should not exist or should not have a specific value in the HTTP request.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `prefix`: The substring that the HTTP request body must begin with. This parameter accepts any type that can be converted into a `String`.
+    /// - `name`: The name of the cookie. Must be a case-sensitive match.
+    /// - `value`: The value that the cookie should not have.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3485,16 +3961,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to begin with the substring "The Great"
+    /// // Create a mock that expects a cookie named "SESSIONID" to not have the value "1234567890"
     /// let mock = server.mock(|when, then| {
-    ///     when.body_prefix("The Great");
+    ///     when.cookie_not("SESSIONID", "1234567890");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with the required prefix in the body content
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby is a novel.")
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=0987654321; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3504,27 +3980,32 @@ impl When {
     ///
     /// # Returns
     /// The updated `When` instance to allow method chaining for additional configuration.
-    pub fn body_prefix<IntoString: Into<String>>(mut self, prefix: IntoString) -> Self {
+    pub fn cookie_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.body_prefix.is_none() {
-                e.body_prefix = Some(Vec::new());
+            if e.cookie_not.is_none() {
+                e.cookie_not = Some(Vec::new());
             }
-            e.body_prefix
+            e.cookie_not
                 .as_mut()
                 .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(prefix.into())));
+                .push((name.into(), value.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must end with the specified substring.
-    /// This method ensures that the request body concludes with the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the requirement that a cookie with the specified name must exist in the HTTP request.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `suffix`: The substring that the HTTP request body must end with. This parameter accepts any type that can be converted into a `String`.
+    /// - `name`: The name of the cookie that must exist.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3534,16 +4015,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to end with the substring "a novel."
+    /// // Create a mock that expects a cookie named "SESSIONID"
     /// let mock = server.mock(|when, then| {
-    ///     when.body_suffix("a novel.");
+    ///     when.cookie_exists("SESSIONID");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with the required suffix in the body content
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby is a novel.")
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3552,28 +4033,26 @@ impl When {
     /// ```
     ///
     /// # Returns
-    /// The updated `When’ instance to allow method chaining for additional configuration.
-    pub fn body_suffix<IntoString: Into<String>>(mut self, suffix: IntoString) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_exists<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.body_suffix.is_none() {
-                e.body_suffix = Some(Vec::new());
+            if e.cookie_exists.is_none() {
+                e.cookie_exists = Some(Vec::new());
             }
-            e.body_suffix
-                .as_mut()
-                .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(suffix.into())));
+            e.cookie_exists.as_mut().unwrap().push(name.into());
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must not begin with the specified substring.
-    /// This method ensures that the request body does not start with the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the requirement that a cookie with the specified name must not exist in the HTTP request.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `prefix`: The substring that the HTTP request body must not begin with. This parameter accepts any type that can be converted into a `String`.
+    /// - `name`: The name of the cookie that must not exist.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3583,16 +4062,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to not begin with the substring "Error:"
+    /// // Create a mock that expects a cookie named "SESSIONID" not to exist
     /// let mock = server.mock(|when, then| {
-    ///     when.body_prefix_not("Error:");
+    ///     when.cookie_missing("SESSIONID");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with a different body content
+    /// // Make a request that does not include the excluded cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("Success: Operation completed.")
+    ///     .header("Cookie", "TRACK=12345; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3601,28 +4080,27 @@ impl When {
     /// ```
     ///
     /// # Returns
-    /// The updated `When’ instance to allow method chaining for additional configuration.
-    pub fn body_prefix_not<IntoString: Into<String>>(mut self, prefix: IntoString) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_missing<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.body_prefix_not.is_none() {
-                e.body_prefix_not = Some(Vec::new());
+            if e.cookie_missing.is_none() {
+                e.cookie_missing = Some(Vec::new());
             }
-            e.body_prefix_not
-                .as_mut()
-                .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(prefix.into())));
+            e.cookie_missing.as_mut().unwrap().push(name.into());
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must not end with the specified substring.
-    /// This method ensures that the request body does not conclude with the provided content as a substring.
-    ///
-    /// **Note**: The body content is case-sensitive.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must contain the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `suffix`: The substring that the HTTP request body must not end with. This parameter accepts any type that can be converted into a `String`.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_substring`: The substring that must be present in the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3632,16 +4110,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to not end with the substring "a novel."
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value containing "1234"
     /// let mock = server.mock(|when, then| {
-    ///     when.body_suffix_not("a novel.");
+    ///     when.cookie_includes("SESSIONID", "1234");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with a different body content
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby is a story.")
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=abc1234def; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3650,28 +4128,34 @@ impl When {
     /// ```
     ///
     /// # Returns
-    /// The updated `When’ instance to allow method chaining for additional configuration.
-    pub fn body_suffix_not<IntoString: Into<String>>(mut self, suffix: IntoString) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.body_suffix_not.is_none() {
-                e.body_suffix_not = Some(Vec::new());
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_includes<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_substring: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.cookie_includes.is_none() {
+                e.cookie_includes = Some(Vec::new());
             }
-            e.body_suffix_not
+            e.cookie_includes
                 .as_mut()
                 .unwrap()
-                .push(HttpMockBytes::from(Bytes::from(suffix.into())));
+                .push((name.into(), value_substring.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must match the specified regular expression.
-    /// This method ensures that the request body fully conforms to the provided regex pattern.
-    ///
-    /// **Note**: The regex matching is case-sensitive unless the regex is explicitly defined to be case-insensitive.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must not contain the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `pattern`: The regular expression pattern that the HTTP request body must match. This parameter accepts any type that can be converted into a `Regex`.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_substring`: The substring that must not be present in the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
@@ -3681,16 +4165,16 @@ impl When {
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to match the regex pattern "^The Great Gatsby.*"
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value not containing "1234"
     /// let mock = server.mock(|when, then| {
-    ///     when.body_matches("^The Great Gatsby.*");
+    ///     when.cookie_excludes("SESSIONID", "1234");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with a body that matches the regex pattern
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .body("The Great Gatsby is a novel by F. Scott Fitzgerald.")
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3699,53 +4183,53 @@ impl When {
     /// ```
     ///
     /// # Returns
-    /// The updated `When’ instance to allow method chaining for additional configuration.
-    pub fn body_matches<IntoRegex: Into<Regex>>(mut self, pattern: IntoRegex) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_excludes<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_substring: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.body_matches.is_none() {
-                e.body_matches = Some(Vec::new());
+            if e.cookie_excludes.is_none() {
+                e.cookie_excludes = Some(Vec::new());
             }
-            e.body_matches.as_mut().unwrap().push(pattern.into());
+            e.cookie_excludes
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value_substring.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the condition that the HTTP request body content must match the specified JSON structure.
-    /// This method ensures that the request body exactly matches the JSON value provided.
-    ///
-    /// **Note**: The body content is case-sensitive.
-    ///
-    /// **Note**: This method does not automatically verify the `Content-Type` header.
-    /// If specific content type verification is required (e.g., `application/json`),
-    /// you must add this expectation manually.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must start with the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `json_value`: The JSON structure that the HTTP request body must match. This parameter accepts any type that can be converted into a `serde_json::Value`.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_prefix`: The substring that must be at the start of the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
-    /// use serde_json::json;
     ///
     /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Create a mock that expects the request body to match a specific JSON structure
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value starting with "1234"
     /// let mock = server.mock(|when, then| {
-    ///     when.json_body(json!({
-    ///         "title": "The Great Gatsby",
-    ///         "author": "F. Scott Fitzgerald"
-    ///     }));
+    ///     when.cookie_prefix("SESSIONID", "1234");
     ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Make a request with a JSON body that matches the expected structure
+    /// // Make a request that includes the required cookie
     /// Client::new()
     ///     .post(&format!("http://{}/test", server.address()))
-    ///     .header("Content-Type", "application/json") // It's important to set the Content-Type header manually
-    ///     .body(r#"{"title":"The Great Gatsby","author":"F. Scott Fitzgerald"}"#)
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234abcdef; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
@@ -3754,950 +4238,2747 @@ impl When {
     /// ```
     ///
     /// # Returns
-    /// The updated `When’ instance to allow method chaining for additional configuration.
-    pub fn json_body<JsonValue: Into<Value>>(mut self, json_value: JsonValue) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_prefix<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_prefix: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            e.json_body = Some(json_value.into());
+            if e.cookie_prefix.is_none() {
+                e.cookie_prefix = Some(Vec::new());
+            }
+            e.cookie_prefix
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value_prefix.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the expected JSON body using a serializable serde object.
-    /// This function automatically serializes the given object into a JSON string using serde.
-    ///
-    /// **Note**: This method does not automatically verify the `Content-Type` header.
-    /// If specific content type verification is required (e.g., `application/json`),
-    /// you must add this expectation manually.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must end with the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `body`: The HTTP body object to be serialized to JSON. This object should implement both `serde::Serialize` and `serde::Deserialize`.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_suffix`: The substring that must be at the end of the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
-    /// use serde_json::json;
-    /// use serde::{Serialize, Deserialize};
-    ///
-    /// #[derive(Serialize, Deserialize)]
-    /// struct TestUser {
-    ///     name: String,
-    /// }
-    ///
-    /// // Initialize logging (optional, for debugging purposes)
-    /// let _ = env_logger::try_init();
     ///
-    /// // Start the mock server
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// // Set up a mock endpoint
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/user")
-    ///         .header("content-type", "application/json")
-    ///         .json_body_obj(&TestUser { name: String::from("Fred") });
-    ///     then.status(200);
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value ending with "7890"
+    /// let mock = server.mock(|when, then| {
+    ///     when.cookie_suffix("SESSIONID", "7890");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Send a POST request with a JSON body
-    /// let response = Client::new()
-    ///     .post(&format!("http://{}/user", server.address()))
-    ///     .header("content-type", "application/json")
-    ///     .body(json!(&TestUser { name: "Fred".to_string() }).to_string())
+    /// // Make a request that includes the required cookie
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef7890; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Assert the mock was called and the response status is as expected
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
-    /// This method is particularly useful when you need to test server responses to structured JSON data. It helps
-    /// ensure that the JSON serialization and deserialization processes are correctly implemented in your API handling logic.
-    pub fn json_body_obj<'a, T>(self, body: &T) -> Self
-    where
-        T: Serialize + Deserialize<'a>,
-    {
-        let json_value = serde_json::to_value(body).expect("Cannot serialize json body to JSON");
-        self.json_body(json_value)
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_suffix<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_suffix: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.cookie_suffix.is_none() {
+                e.cookie_suffix = Some(Vec::new());
+            }
+            e.cookie_suffix
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value_suffix.into()));
+        });
+        self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the expected partial JSON body to check for specific content within a larger JSON structure.
-    ///
-    /// **Attention:** The partial JSON string must be a valid JSON string and should represent a substructure
-    /// of the full JSON object. It can omit irrelevant attributes but must maintain any necessary object hierarchy.
-    ///
-    /// **Note:** This method does not automatically set the `Content-Type` header to `application/json`.
-    /// You must explicitly set this header in your requests.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must not start with the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `partial_body`: The partial JSON content to check for. This must be a valid JSON string.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_prefix`: The substring that must not be at the start of the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
-    /// Suppose your application sends the following JSON request body:
-    /// ```json
-    /// {
-    ///     "parent_attribute": "Some parent data goes here",
-    ///     "child": {
-    ///         "target_attribute": "Example",
-    ///         "other_attribute": "Another value"
-    ///     }
-    /// }
-    /// ```
-    /// To verify the presence of `target_attribute` with the value `Example` without needing the entire JSON object:
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value not starting with "1234"
     /// let mock = server.mock(|when, then| {
-    ///     when.json_body_includes(r#"
-    ///         {
-    ///             "child": {
-    ///                 "target_attribute": "Example"
-    ///             }
-    ///         }
-    ///     "#);
-    ///     then.status(200);
+    ///     when.cookie_prefix_not("SESSIONID", "1234");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Send a POST request with a JSON body
-    /// let response = Client::new()
-    ///     .post(&format!("http://{}/some/path", server.address()))
-    ///     .header("content-type", "application/json")
-    ///     .body(r#"
-    ///         {
-    ///             "parent_attribute": "Some parent data goes here",
-    ///             "child": {
-    ///                 "target_attribute": "Example",
-    ///                 "other_attribute": "Another value"
-    ///             }
-    ///         }
-    ///     "#)
+    /// // Make a request that includes the required cookie
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcd1234; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Assert the mock was called and the response status is as expected
+    /// // Verify that the mock was called at least once
     /// mock.assert();
-    /// assert_eq!(response.status(), 200);
     /// ```
-    /// It's important that the partial JSON contains the full object hierarchy necessary to reach the target attribute.
-    /// Irrelevant attributes such as `parent_attribute` and `child.other_attribute` can be omitted.
-    pub fn json_body_includes<IntoString: Into<String>>(mut self, partial: IntoString) -> Self {
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_prefix: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.json_body_includes.is_none() {
-                e.json_body_includes = Some(Vec::new());
+            if e.cookie_prefix_not.is_none() {
+                e.cookie_prefix_not = Some(Vec::new());
             }
-            let value = Value::from_str(&partial.into())
-                .expect("cannot convert JSON string to serde value");
-            e.json_body_includes.as_mut().unwrap().push(value);
-        });
-        self
+            e.cookie_prefix_not
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value_prefix.into()));
+        });
+        self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets the expected partial JSON body to ensure that specific content is not present within a larger JSON structure.
-    ///
-    /// **Attention:** The partial JSON string must be a valid JSON string and should represent a substructure
-    /// of the full JSON object. It can omit irrelevant attributes but must maintain any necessary object hierarchy.
-    ///
-    /// **Note:** This method does not automatically set the `Content-Type` header to `application/json`.
-    /// You must explicitly set this header in your requests.
+    /// Sets the requirement that a cookie with the specified name must exist and its value must not end with the specified substring.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `partial_body`: The partial JSON content to check for exclusion. This must be a valid JSON string.
+    /// - `name`: The name of the cookie that must exist.
+    /// - `value_suffix`: The substring that must not be at the end of the cookie value.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
-    /// Suppose your application sends the following JSON request body:
-    /// ```json
-    /// {
-    ///     "parent_attribute": "Some parent data goes here",
-    ///     "child": {
-    ///         "target_attribute": "Example",
-    ///         "other_attribute": "Another value"
-    ///     }
-    /// }
-    /// ```
-    /// To verify the absence of `target_attribute` with the value `Example`:
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
+    /// // Create a mock that expects a cookie named "SESSIONID" with a value not ending with "7890"
     /// let mock = server.mock(|when, then| {
-    ///     when.json_body_excludes(r#"
-    ///         {
-    ///             "child": {
-    ///                 "target_attribute": "Example"
-    ///             }
-    ///         }
-    ///     "#);
-    ///     then.status(200);
+    ///     when.cookie_suffix_not("SESSIONID", "7890");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Send a POST request with a JSON body
-    /// let response = Client::new()
-    ///     .post(&format!("http://{}/some/path", server.address()))
-    ///     .header("content-type", "application/json")
-    ///     .body(r#"
-    ///         {
-    ///             "parent_attribute": "Some parent data goes here",
-    ///             "child": {
-    ///                 "other_attribute": "Another value"
-    ///             }
-    ///         }
-    ///     "#)
+    /// // Make a request that includes the required cookie
+    ///  Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=abcdef1234; CONSENT=1")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Assert the mock was called and the response status is as expected
+    /// // Verify that the mock was called at least once
     /// mock.assert();
-    /// assert_eq!(response.status(), 200);
     /// ```
-    /// It's important that the partial JSON contains the full object hierarchy necessary to reach the target attribute.
-    /// Irrelevant attributes such as `parent_attribute` and `child.other_attribute` in the example can be omitted.
-    pub fn json_body_excludes<IntoString: Into<String>>(mut self, partial: IntoString) -> Self {
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value_suffix: ValueString,
+    ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.json_body_excludes.is_none() {
-                e.json_body_excludes = Some(Vec::new());
+            if e.cookie_suffix_not.is_none() {
+                e.cookie_suffix_not = Some(Vec::new());
             }
-            let value = Value::from_str(&partial.into())
-                .expect("cannot convert JSON string to serde value");
-            e.json_body_excludes.as_mut().unwrap().push(value);
+            e.cookie_suffix_not
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value_suffix.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Adds a key-value pair to the requirements for an `application/x-www-form-urlencoded` request body.
-    ///
-    /// This method sets an expectation for a specific key-value pair to be included in the request body
-    /// of an `application/x-www-form-urlencoded` POST request. Each key and value are URL-encoded as specified
-    /// by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
-    ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// Sets the requirement that a cookie with a name matching the specified regex must exist and its value must match the specified regex.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `key`: The key of the key-value pair to set as a requirement.
-    /// - `value`: The value of the key-value pair to set as a requirement.
+    /// - `key_regex`: The regex pattern that the cookie name must match.
+    /// - `value_regex`: The regex pattern that the cookie value must match.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple("name", "Peter Griffin")
-    ///        .form_urlencoded_tuple("town", "Quahog");
-    ///    then.status(202);
+    /// // Create a mock that expects a cookie with a name matching the regex "^SESSION"
+    /// // and a value matching the regex "^[0-9]{10}$"
+    /// let mock = server.mock(|when, then| {
+    ///     when.cookie_matches(r"^SESSION", r"^[0-9]{10}$");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request that includes the required cookie
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Cookie", "TRACK=12345; SESSIONID=1234567890; CONSENT=1")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value pair added to the `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple<KeyString: Into<String>, ValueString: Into<String>>(
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_matches<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
         mut self,
-        key: KeyString,
-        value: ValueString,
+        key_regex: KeyRegex,
+        value_regex: ValueRegex,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple.is_none() {
-                e.form_urlencoded_tuple = Some(Vec::new());
+            if e.cookie_matches.is_none() {
+                e.cookie_matches = Some(Vec::new());
             }
-            e.form_urlencoded_tuple
+            e.cookie_matches
                 .as_mut()
                 .unwrap()
-                .push((key.into(), value.into()));
+                .push((key_regex.into(), value_regex.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Adds a key-value pair to the negative requirements for an `application/x-www-form-urlencoded` request body.
-    ///
-    /// This method sets an expectation for a specific key-value pair to be excluded from the request body
-    /// of an `application/x-www-form-urlencoded` POST request. Each key and value are URL-encoded as specified
-    /// by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
-    ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// Sets the requirement that a cookie with a name and value matching the specified regexes must appear a specified number of times in the HTTP request.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html).
+    /// **Attention**: Cookie names are **case-sensitive**.
     ///
     /// # Parameters
-    /// - `key`: The key of the key-value pair to set as a requirement.
-    /// - `value`: The value of the key-value pair to set as a requirement.
+    /// - `key_regex`: The regex pattern that the cookie name must match.
+    /// - `value_regex`: The regex pattern that the cookie value must match.
+    /// - `count`: The number of times a cookie with a matching name and value must appear.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_not("name", "Peter Griffin");
-    ///    then.status(202);
+    /// // Create a mock that expects a cookie with a name matching the regex "^SESSION"
+    /// // and a value matching the regex "^[0-9]{10}$" to appear exactly twice
+    /// let mock = server.mock(|when, then| {
+    ///     when.cookie_count(r"^SESSION", r"^[0-9]{10}$", 2);
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Lois%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request that includes the required cookies
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Cookie", "SESSIONID=1234567890; TRACK=12345; SESSIONTOKEN=0987654321; CONSENT=1")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value pair added to the negative `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_not<KeyString: Into<String>, ValueString: Into<String>>(
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_count<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
         mut self,
-        key: KeyString,
-        value: ValueString,
+        key_regex: KeyRegex,
+        value_regex: ValueRegex,
+        count: usize,
     ) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_not.is_none() {
-                e.form_urlencoded_tuple_not = Some(Vec::new());
+            if e.cookie_count.is_none() {
+                e.cookie_count = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_not
+            e.cookie_count
                 .as_mut()
                 .unwrap()
-                .push((key.into(), value.into()));
+                .push((key_regex.into(), value_regex.into(), count));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets a requirement for the existence of a key in an `application/x-www-form-urlencoded` request body.
-    ///
-    /// This method sets an expectation that a specific key must be present in the request body of an
-    /// `application/x-www-form-urlencoded` POST request, regardless of its value. The key is URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
-    ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// Sets the requirement that the HTTP request must contain at least `count` cookies.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html), so malformed
+    /// cookies that the parser drops are not counted. Use
+    /// [`raw_cookie_includes`](#method.raw_cookie_includes) to match them.
     ///
     /// # Parameters
-    /// - `key`: The key that must exist in the `application/x-www-form-urlencoded` request body.
+    /// - `count`: The minimum number of cookies.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_exists("name")
-    ///        .form_urlencoded_tuple_exists("town");
-    ///    then.status(202);
+    /// let mock = server.mock(|when, then| {
+    ///     when.cookie_count_min(2);
+    ///     then.status(200);
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// Client::new()
+    ///     .get(server.url("/"))
+    ///     .header("Cookie", "SESSIONID=1234567890; CONSENT=1")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key existence requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_exists<IntoString: Into<String>>(
-        mut self,
-        key: IntoString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_count_min(self, count: usize) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_exists.is_none() {
-                e.form_urlencoded_tuple_exists = Some(Vec::new());
-            }
-            e.form_urlencoded_tuple_exists
-                .as_mut()
-                .unwrap()
-                .push(key.into());
+            e.cookie_count_min = Some(count);
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets a requirement that a key must be absent in an `application/x-www-form-urlencoded` request body.
-    ///
-    /// This method sets an expectation that a specific key must not be present in the request body of an
-    /// `application/x-www-form-urlencoded` POST request. The key is URL-encoded as specified by the
-    /// [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
-    ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// Sets the requirement that the HTTP request must contain at most `count` cookies.
+    /// A request without a `Cookie` header contains no cookies.
+    /// Cookie parsing follows [RFC-6265](https://tools.ietf.org/html/rfc6265.html), so malformed
+    /// cookies that the parser drops are not counted.
     ///
     /// # Parameters
-    /// - `key`: The key that must be absent in the `application/x-www-form-urlencoded` request body.
+    /// - `count`: The maximum number of cookies.
+    ///
+    /// > Note: This function is only available when the `cookies` feature is enabled. This feature is enabled by default.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_missing("name")
-    ///        .form_urlencoded_tuple_missing("town");
-    ///    then.status(202);
+    /// let mock = server.mock(|when, then| {
+    ///     when.cookie_count_max(1);
+    ///     then.status(200);
     /// });
     ///
     /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("city=Quahog&occupation=Cartoonist")
-    ///    .send()
-    ///    .unwrap();
+    ///     .get(server.url("/"))
+    ///     .header("Cookie", "SESSIONID=1234567890; TRACK=12345; CONSENT=1")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// mock.assert_calls(0);
+    /// assert_eq!(response.status(), 404);
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key absence requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_missing<IntoString: Into<String>>(
-        mut self,
-        key: IntoString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn cookie_count_max(self, count: usize) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_missing.is_none() {
-                e.form_urlencoded_tuple_missing = Some(Vec::new());
-            }
-            e.form_urlencoded_tuple_missing
-                .as_mut()
-                .unwrap()
-                .push(key.into());
+            e.cookie_count_max = Some(count);
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must contain a specific substring.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must contain a specified substring
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the substring are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the requirement that the unparsed `Cookie` header of the HTTP request must contain
+    /// the specified substring (case-sensitive).
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// Unlike the other cookie matchers, the header is not parsed into cookies, so this also
+    /// matches malformed cookies that [RFC-6265](https://tools.ietf.org/html/rfc6265.html)
+    /// parsing drops. If a request contains multiple `Cookie` headers (e.g., with HTTP/2), they
+    /// are joined with `; `. Requests without a `Cookie` header do not match.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `substring`: The substring that must be present in the value associated with the key.
+    /// - `substring`: The substring that the `Cookie` header must contain.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_includes("name", "Griffin")
-    ///        .form_urlencoded_tuple_includes("town", "Quahog");
-    ///    then.status(202);
+    /// let mock = server.mock(|when, then| {
+    ///     when.raw_cookie_includes("=broken=");
+    ///     then.status(200);
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// Client::new()
+    ///     .get(server.url("/"))
+    ///     .header("Cookie", "SESSIONID=1234567890; =broken=")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value substring requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_includes<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        substring: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn raw_cookie_includes<IntoString: Into<String>>(self, substring: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_includes.is_none() {
-                e.form_urlencoded_tuple_includes = Some(Vec::new());
-            }
-            e.form_urlencoded_tuple_includes
-                .as_mut()
-                .unwrap()
-                .push((key.into(), substring.into()));
+            e.raw_cookie_includes
+                .get_or_insert_with(Vec::new)
+                .push(substring.into());
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Cookies
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not contain a specific substring.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must not contain a specified substring
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the substring are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the required HTTP request body content.
+    /// This method specifies that the HTTP request body must match the provided content exactly.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive and must be an exact match.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `substring`: The substring that must not be present in the value associated with the key.
+    /// - `body`: The required HTTP request body content. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_excludes("name", "Griffin");
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to be "The Great Gatsby"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body("The Great Gatsby");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Lois%20Smith&city=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with the required body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value substring exclusion requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_excludes<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        substring: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body<IntoString: Into<String>>(mut self, body: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_excludes.is_none() {
-                e.form_urlencoded_tuple_excludes = Some(Vec::new());
-            }
-            e.form_urlencoded_tuple_excludes
-                .as_mut()
-                .unwrap()
-                .push((key.into(), substring.into()));
+            e.body = Some(HttpMockBytes::from(Bytes::from(body.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must start with a specific prefix.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must start with a specified prefix
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the prefix are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must not match the specified value.
+    /// This method ensures that the request body does not contain the provided content exactly.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive and must be an exact mismatch.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `prefix`: The prefix that must appear at the start of the value associated with the key.
+    /// - `body`: The body content that the HTTP request must not contain. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_prefix("name", "Pete")
-    ///        .form_urlencoded_tuple_prefix("town", "Qua");
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to not be "The Great Gatsby"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_not("The Great Gatsby");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with a different body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("A Tale of Two Cities")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value prefix requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_prefix<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        prefix: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_not<IntoString: Into<String>>(mut self, body: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_prefix.is_none() {
-                e.form_urlencoded_tuple_prefix = Some(Vec::new());
+            if e.body_not.is_none() {
+                e.body_not = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_prefix
+            e.body_not
                 .as_mut()
                 .unwrap()
-                .push((key.into(), prefix.into()));
+                .push(HttpMockBytes::from(Bytes::from(body.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not start with a specific prefix.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must not start with a specified prefix
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the prefix are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must contain the specified substring.
+    /// This method ensures that the request body includes the provided content as a substring.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `prefix`: The prefix that must not appear at the start of the value associated with the key.
+    /// - `substring`: The substring that the HTTP request body must contain. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_prefix_not("name", "Lois")
-    ///        .form_urlencoded_tuple_prefix_not("town", "Hog");
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to contain the substring "Gatsby"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_includes("Gatsby");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with the required substring in the body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby is a novel.")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value prefix exclusion requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        prefix: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_includes<IntoString: Into<String>>(mut self, substring: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_prefix_not.is_none() {
-                e.form_urlencoded_tuple_prefix_not = Some(Vec::new());
+            if e.body_includes.is_none() {
+                e.body_includes = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_prefix_not
+            e.body_includes
                 .as_mut()
                 .unwrap()
-                .push((key.into(), prefix.into()));
+                .push(HttpMockBytes::from(Bytes::from(substring.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must end with a specific suffix.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must end with a specified suffix
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the suffix are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must not contain the specified substring.
+    /// This method ensures that the request body does not include the provided content as a substring.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `suffix`: The suffix that must appear at the end of the value associated with the key.
+    /// - `substring`: The substring that the HTTP request body must not contain. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_suffix("name", "Griffin")
-    ///        .form_urlencoded_tuple_suffix("town", "hog");
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to not contain the substring "Gatsby"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_excludes("Gatsby");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with a different body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("A Tale of Two Cities is a novel.")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value suffix requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_suffix<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        suffix: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_excludes<IntoString: Into<String>>(mut self, substring: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_suffix.is_none() {
-                e.form_urlencoded_tuple_suffix = Some(Vec::new());
+            if e.body_excludes.is_none() {
+                e.body_excludes = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_suffix
+            e.body_excludes
                 .as_mut()
                 .unwrap()
-                .push((key.into(), suffix.into()));
+                .push(HttpMockBytes::from(Bytes::from(substring.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not end with a specific suffix.
-    ///
-    /// This method sets an expectation that the value associated with a specific key must not end with a specified suffix
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the suffix are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must begin with the specified substring.
+    /// This method ensures that the request body starts with the provided content as a substring.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive.
     ///
     /// # Parameters
-    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
-    /// - `suffix`: The suffix that must not appear at the end of the value associated with the key.
+    /// - `prefix`: The substring that the HTTP request body must begin with. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_suffix_not("name", "Smith")
-    ///        .form_urlencoded_tuple_suffix_not("town", "ville");
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to begin with the substring "The Great"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_prefix("The Great");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with the required prefix in the body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby is a novel.")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value suffix exclusion requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        key: KeyString,
-        suffix: ValueString,
-    ) -> Self {
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_prefix<IntoString: Into<String>>(mut self, prefix: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_suffix_not.is_none() {
-                e.form_urlencoded_tuple_suffix_not = Some(Vec::new());
+            if e.body_prefix.is_none() {
+                e.body_prefix = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_suffix_not
+            e.body_prefix
                 .as_mut()
                 .unwrap()
-                .push((key.into(), suffix.into()));
+                .push(HttpMockBytes::from(Bytes::from(prefix.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement that a key-value pair in an `application/x-www-form-urlencoded` request body must match specific regular expressions.
-    ///
-    /// This method sets an expectation that the key and the value in a key-value pair must match the specified regular expressions
-    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and value regular expressions are URL-encoded
-    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must end with the specified substring.
+    /// This method ensures that the request body concludes with the provided content as a substring.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive.
     ///
     /// # Parameters
-    /// - `key_regex`: The regular expression that the key must match in the `application/x-www-form-urlencoded` request body.
-    /// - `value_regex`: The regular expression that the value must match in the `application/x-www-form-urlencoded` request body.
+    /// - `suffix`: The substring that the HTTP request body must end with. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
-    /// use regex::Regex;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let key_regex = Regex::new(r"^name$").unwrap();
-    /// let value_regex = Regex::new(r"^Peter\sGriffin$").unwrap();
-    ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_matches(key_regex, value_regex);
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to end with the substring "a novel."
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_suffix("a novel.");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
-    ///    .header("content-type", "application/x-www-form-urlencoded")
-    ///    .body("name=Peter%20Griffin&town=Quahog")
-    ///    .send()
-    ///    .unwrap();
+    /// // Make a request with the required suffix in the body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby is a novel.")
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
     /// ```
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value regex matching requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_matches<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
-        mut self,
-        key_regex: KeyRegex,
-        value_regex: ValueRegex,
-    ) -> Self {
+    /// The updated `When’ instance to allow method chaining for additional configuration.
+    pub fn body_suffix<IntoString: Into<String>>(mut self, suffix: IntoString) -> Self {
         update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_matches.is_none() {
-                e.form_urlencoded_tuple_matches = Some(Vec::new());
+            if e.body_suffix.is_none() {
+                e.body_suffix = Some(Vec::new());
             }
-            e.form_urlencoded_tuple_matches
+            e.body_suffix
                 .as_mut()
                 .unwrap()
-                .push((key_regex.into(), value_regex.into()));
+                .push(HttpMockBytes::from(Bytes::from(suffix.into())));
         });
         self
     }
     // @docs-group: Body
 
-    /// Sets a requirement for the number of times a key-value pair matching specific regular expressions appears in an `application/x-www-form-urlencoded` request body.
-    ///
-    /// This method sets an expectation that the key-value pair must appear a specific number of times in the request body of an
-    /// `application/x-www-form-urlencoded` POST request. The key and value regular expressions are URL-encoded as specified by the
-    /// [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    /// Sets the condition that the HTTP request body content must not begin with the specified substring.
+    /// This method ensures that the request body does not start with the provided content as a substring.
     ///
-    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
-    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    /// **Note**: The body content is case-sensitive.
     ///
     /// # Parameters
-    /// - `key_regex`: The regular expression that the key must match in the `application/x-www-form-urlencoded` request body.
-    /// - `value_regex`: The regular expression that the value must match in the `application/x-www-form-urlencoded` request body.
-    /// - `count`: The number of times the key-value pair matching the regular expressions must appear.
+    /// - `prefix`: The substring that the HTTP request body must not begin with. This parameter accepts any type that can be converted into a `String`.
     ///
     /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
-    /// use regex::Regex;
     ///
-    /// // Arrange
+    /// // Start a new mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.method(POST)
-    ///        .path("/example")
-    ///        .header("content-type", "application/x-www-form-urlencoded")
-    ///        .form_urlencoded_tuple_count(
-    ///            Regex::new(r"^name$").unwrap(),
-    ///            Regex::new(r".*Griffin$").unwrap(),
-    ///            2
-    ///        );
-    ///    then.status(202);
+    /// // Create a mock that expects the request body to not begin with the substring "Error:"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_prefix_not("Error:");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
     /// });
     ///
-    /// // Act
-    /// let response = Client::new()
-    ///    .post(server.url("/example"))
+    /// // Make a request with a different body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("Success: Operation completed.")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When’ instance to allow method chaining for additional configuration.
+    pub fn body_prefix_not<IntoString: Into<String>>(mut self, prefix: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.body_prefix_not.is_none() {
+                e.body_prefix_not = Some(Vec::new());
+            }
+            e.body_prefix_not
+                .as_mut()
+                .unwrap()
+                .push(HttpMockBytes::from(Bytes::from(prefix.into())));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body content must not end with the specified substring.
+    /// This method ensures that the request body does not conclude with the provided content as a substring.
+    ///
+    /// **Note**: The body content is case-sensitive.
+    ///
+    /// # Parameters
+    /// - `suffix`: The substring that the HTTP request body must not end with. This parameter accepts any type that can be converted into a `String`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects the request body to not end with the substring "a novel."
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_suffix_not("a novel.");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    /// });
+    ///
+    /// // Make a request with a different body content
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby is a story.")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When’ instance to allow method chaining for additional configuration.
+    pub fn body_suffix_not<IntoString: Into<String>>(mut self, suffix: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.body_suffix_not.is_none() {
+                e.body_suffix_not = Some(Vec::new());
+            }
+            e.body_suffix_not
+                .as_mut()
+                .unwrap()
+                .push(HttpMockBytes::from(Bytes::from(suffix.into())));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body content must match the specified regular expression.
+    /// This method ensures that the request body fully conforms to the provided regex pattern.
+    ///
+    /// **Note**: The regex matching is case-sensitive unless the regex is explicitly defined to be case-insensitive.
+    ///
+    /// When this method is called multiple times, the request body must match all of the
+    /// provided patterns. Use [`body_matches_any`](#method.body_matches_any) if the body
+    /// only needs to match one of several patterns.
+    ///
+    /// # Parameters
+    /// - `pattern`: The regular expression pattern that the HTTP request body must match. This parameter accepts any type that can be converted into a `Regex`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects the request body to match the regex pattern "^The Great Gatsby.*"
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_matches("^The Great Gatsby.*");
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    /// });
+    ///
+    /// // Make a request with a body that matches the regex pattern
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .body("The Great Gatsby is a novel by F. Scott Fitzgerald.")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When’ instance to allow method chaining for additional configuration.
+    pub fn body_matches<IntoRegex: Into<Regex>>(mut self, pattern: IntoRegex) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.body_matches.is_none() {
+                e.body_matches = Some(Vec::new());
+            }
+            e.body_matches.as_mut().unwrap().push(pattern.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body must match the specified regular expression
+    /// after it was decompressed according to the `Content-Encoding` request header. Supported
+    /// content encodings are `gzip`, `deflate` and `br`. If several encodings are listed, they
+    /// are removed in reverse order. Requests without a `Content-Encoding` header are matched
+    /// against the body as it was received.
+    ///
+    /// If the body cannot be decompressed (e.g., because the encoding is not supported or the
+    /// body is corrupted), the request does not match. The mismatch report names the content
+    /// encoding that was tried.
+    ///
+    /// # Parameters
+    /// - `pattern`: The regular expression pattern that the decompressed HTTP request body must match.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use std::io::Write;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_decompressed_matches("\"event\":\\s*\"signup\"");
+    ///     then.status(202);
+    /// });
+    ///
+    /// // Compress the request body with gzip
+    /// let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    /// encoder.write_all(br#"{"event": "signup"}"#).unwrap();
+    /// let body = encoder.finish().unwrap();
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/events"))
+    ///     .header("Content-Encoding", "gzip")
+    ///     .body(body)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    ///
+    /// # Feature
+    /// This method is only available when the `compression` feature is enabled.
+    #[cfg(feature = "compression")]
+    pub fn body_decompressed_matches<IntoRegex: Into<Regex>>(mut self, pattern: IntoRegex) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.body_decompressed_matches
+                .get_or_insert_with(Vec::new)
+                .push(pattern.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body content must match at least one of the
+    /// specified regular expressions.
+    ///
+    /// When this method is called multiple times, the request body must match at least one
+    /// pattern of each call. If none of the patterns match, the mismatch report lists all of
+    /// them.
+    ///
+    /// # Parameters
+    /// - `patterns`: The regular expression patterns of which the HTTP request body must match at least one.
+    ///
+    /// # Panics
+    /// Panics if no patterns are provided.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.body_matches_any(["order-\\d+", "invoice-\\d+"]);
+    ///     then.status(200);
+    /// });
+    ///
+    /// let client = Client::new();
+    /// let order = client.post(server.url("/test")).body("order-42").send().unwrap();
+    /// let invoice = client.post(server.url("/test")).body("invoice-7").send().unwrap();
+    /// let other = client.post(server.url("/test")).body("receipt-1").send().unwrap();
+    ///
+    /// assert_eq!(order.status(), 200);
+    /// assert_eq!(invoice.status(), 200);
+    /// assert_eq!(other.status(), 404);
+    /// mock.assert_calls(2);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_matches_any<IntoRegex, Patterns>(mut self, patterns: Patterns) -> Self
+    where
+        IntoRegex: Into<Regex>,
+        Patterns: IntoIterator<Item = IntoRegex>,
+    {
+        let patterns: Vec<_> = patterns.into_iter().map(Into::into).collect();
+        assert!(
+            !patterns.is_empty(),
+            "body_matches_any requires at least one pattern"
+        );
+
+        update_cell(&self.expectations, |e| {
+            if e.body_matches_any.is_none() {
+                e.body_matches_any = Some(Vec::new());
+            }
+            e.body_matches_any
+                .as_mut()
+                .unwrap()
+                .push(AnyOfPatterns(patterns));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the size of the HTTP request body in bytes must be within the
+    /// specified range (inclusive). The body content itself is not inspected. A request without
+    /// a body has a body size of 0.
+    ///
+    /// # Parameters
+    /// - `min`: The minimum body size in bytes.
+    /// - `max`: The maximum body size in bytes.
+    ///
+    /// # Panics
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// // Reject uploads that are larger than 1 KiB
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/upload").body_size(1025, usize::MAX);
+    ///     then.status(413);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/upload"))
+    ///     .body(vec![0u8; 2048])
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 413);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_size(mut self, min: usize, max: usize) -> Self {
+        assert!(
+            min <= max,
+            "minimum body size ({}) must not be greater than the maximum body size ({})",
+            min,
+            max
+        );
+
+        update_cell(&self.expectations, |e| {
+            if e.body_size.is_none() {
+                e.body_size = Some(Vec::new());
+            }
+            e.body_size
+                .as_mut()
+                .unwrap()
+                .push(BodySizeRange { min, max });
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the size of the HTTP request body must be exactly the specified
+    /// number of bytes. A request without a body has a body size of 0.
+    ///
+    /// # Parameters
+    /// - `size`: The expected body size in bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/upload").body_size_eq(5);
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/upload"))
+    ///     .body("hello")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn body_size_eq(self, size: usize) -> Self {
+        self.body_size(size, size)
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request must carry a body.
+    ///
+    /// A request is considered to have a body if its body is at least one byte long. A body that
+    /// is present but empty (e.g., a request with a `Content-Length: 0` header) counts as no body.
+    /// Overrides an earlier call to [`When::no_body`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/upload").has_body();
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/upload"))
+    ///     .body("hello")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn has_body(self) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.has_body = Some(true);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request must not carry a body.
+    ///
+    /// A body that is present but empty (e.g., a request with a `Content-Length: 0` header)
+    /// counts as no body, so such requests match as well. Overrides an earlier call to
+    /// [`When::has_body`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(GET).path("/users").no_body();
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = Client::new().get(server.url("/users")).send().unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn no_body(self) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.has_body = Some(false);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body content must match the specified JSON structure.
+    /// This method ensures that the request body exactly matches the JSON value provided.
+    ///
+    /// **Note**: The body content is case-sensitive.
+    ///
+    /// **Note**: This method does not automatically verify the `Content-Type` header.
+    /// If specific content type verification is required (e.g., `application/json`),
+    /// you must add this expectation manually.
+    ///
+    /// # Parameters
+    /// - `json_value`: The JSON structure that the HTTP request body must match. This parameter accepts any type that can be converted into a `serde_json::Value`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde_json::json;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects the request body to match a specific JSON structure
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body(json!({
+    ///         "title": "The Great Gatsby",
+    ///         "author": "F. Scott Fitzgerald"
+    ///     }));
+    ///     then.status(200);  // Respond with a 200 status code if the condition is met
+    /// });
+    ///
+    /// // Make a request with a JSON body that matches the expected structure
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Content-Type", "application/json") // It's important to set the Content-Type header manually
+    ///     .body(r#"{"title":"The Great Gatsby","author":"F. Scott Fitzgerald"}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When’ instance to allow method chaining for additional configuration.
+    pub fn json_body<JsonValue: Into<Value>>(mut self, json_value: JsonValue) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.json_body = Some(json_value.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the expected JSON body using a serializable serde object.
+    /// This function automatically serializes the given object into a JSON string using serde.
+    ///
+    /// **Note**: This method does not automatically verify the `Content-Type` header.
+    /// If specific content type verification is required (e.g., `application/json`),
+    /// you must add this expectation manually.
+    ///
+    /// # Parameters
+    /// - `body`: The HTTP body object to be serialized to JSON. This object should implement both `serde::Serialize` and `serde::Deserialize`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde_json::json;
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct TestUser {
+    ///     name: String,
+    /// }
+    ///
+    /// // Initialize logging (optional, for debugging purposes)
+    /// let _ = env_logger::try_init();
+    ///
+    /// // Start the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Set up a mock endpoint
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/user")
+    ///         .header("content-type", "application/json")
+    ///         .json_body_obj(&TestUser { name: String::from("Fred") });
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Send a POST request with a JSON body
+    /// let response = Client::new()
+    ///     .post(&format!("http://{}/user", server.address()))
+    ///     .header("content-type", "application/json")
+    ///     .body(json!(&TestUser { name: "Fred".to_string() }).to_string())
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert the mock was called and the response status is as expected
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// This method is particularly useful when you need to test server responses to structured JSON data. It helps
+    /// ensure that the JSON serialization and deserialization processes are correctly implemented in your API handling logic.
+    pub fn json_body_obj<'a, T>(self, body: &T) -> Self
+    where
+        T: Serialize + Deserialize<'a>,
+    {
+        let json_value = serde_json::to_value(body).expect("Cannot serialize json body to JSON");
+        self.json_body(json_value)
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body must not be equal to the specified JSON
+    /// structure. Values are compared structurally, in the same way as by
+    /// [`json_body`](#method.json_body), and the condition is met whenever they differ.
+    ///
+    /// **Attention**: A request body that is not valid JSON (including an empty body) is never
+    /// equal to the specified value, so such requests **do** match this condition. Combine this
+    /// method with [`is_json`](#method.is_json) if the body must be valid JSON.
+    ///
+    /// # Parameters
+    /// - `json_value`: The JSON structure that the HTTP request body must not be equal to. This parameter accepts any type that can be converted into a `serde_json::Value`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde_json::json;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects any request body other than an empty JSON object
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_not(json!({}));
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Make a request with a JSON body that differs from the specified value
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Content-Type", "application/json")
+    ///     .body(r#"{"title":"The Great Gatsby"}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn json_body_not<JsonValue: Into<Value>>(mut self, json_value: JsonValue) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.json_body_not = Some(json_value.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the condition that the HTTP request body must match the specified JSON structure,
+    /// ignoring the order of array elements at every nesting level.
+    ///
+    /// Arrays are compared as multisets: they must have the same length, and each expected
+    /// element must be paired with a distinct request element that is deeply equal to it.
+    /// Deep equality applies the same rules recursively, so nested arrays and arrays of objects
+    /// are compared regardless of their order too. Objects are compared structurally (same keys
+    /// with equal values, independent of key order) and all other values must be identical.
+    /// Numbers are compared by their JSON value, so `1` does not equal `1.0`.
+    ///
+    /// **Note**: This method does not automatically verify the `Content-Type` header.
+    /// If specific content type verification is required (e.g., `application/json`),
+    /// you must add this expectation manually.
+    ///
+    /// # Parameters
+    /// - `json_value`: The JSON structure that the HTTP request body must match. This parameter accepts any type that can be converted into a `serde_json::Value`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde_json::json;
+    ///
+    /// // Start a new mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Create a mock that expects a set of tags and items in any order
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_unordered(json!({
+    ///         "tags": ["fiction", "classic"],
+    ///         "items": [{ "id": 1 }, { "id": 2 }]
+    ///     }));
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Make a request with the same arrays in a different order
+    /// Client::new()
+    ///     .post(&format!("http://{}/test", server.address()))
+    ///     .header("Content-Type", "application/json")
+    ///     .body(r#"{"items":[{"id":2},{"id":1}],"tags":["classic","fiction"]}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Verify that the mock was called at least once
+    /// mock.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn json_body_unordered<JsonValue: Into<Value>>(mut self, json_value: JsonValue) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.json_body_unordered = Some(json_value.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the expected partial JSON body to check for specific content within a larger JSON structure.
+    ///
+    /// **Attention:** The partial JSON string must be a valid JSON string and should represent a substructure
+    /// of the full JSON object. It can omit irrelevant attributes but must maintain any necessary object hierarchy.
+    ///
+    /// **Note:** This method does not automatically set the `Content-Type` header to `application/json`.
+    /// You must explicitly set this header in your requests.
+    ///
+    /// # Parameters
+    /// - `partial_body`: The partial JSON content to check for. This must be a valid JSON string.
+    ///
+    /// # Example
+    /// Suppose your application sends the following JSON request body:
+    /// ```json
+    /// {
+    ///     "parent_attribute": "Some parent data goes here",
+    ///     "child": {
+    ///         "target_attribute": "Example",
+    ///         "other_attribute": "Another value"
+    ///     }
+    /// }
+    /// ```
+    /// To verify the presence of `target_attribute` with the value `Example` without needing the entire JSON object:
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_includes(r#"
+    ///         {
+    ///             "child": {
+    ///                 "target_attribute": "Example"
+    ///             }
+    ///         }
+    ///     "#);
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Send a POST request with a JSON body
+    /// let response = Client::new()
+    ///     .post(&format!("http://{}/some/path", server.address()))
+    ///     .header("content-type", "application/json")
+    ///     .body(r#"
+    ///         {
+    ///             "parent_attribute": "Some parent data goes here",
+    ///             "child": {
+    ///                 "target_attribute": "Example",
+    ///                 "other_attribute": "Another value"
+    ///             }
+    ///         }
+    ///     "#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert the mock was called and the response status is as expected
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    /// It's important that the partial JSON contains the full object hierarchy necessary to reach the target attribute.
+    /// Irrelevant attributes such as `parent_attribute` and `child.other_attribute` can be omitted.
+    ///
+    /// **Attention:** Arrays in the partial JSON are compared position by position: `[1, 2]` is
+    /// included in `[1, 2, 3]`, but `[2]` is not. Use
+    /// [`json_body_includes_array`](#method.json_body_includes_array) to check that an array
+    /// contains some elements regardless of their position.
+    pub fn json_body_includes<IntoString: Into<String>>(mut self, partial: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.json_body_includes.is_none() {
+                e.json_body_includes = Some(Vec::new());
+            }
+            let value = Value::from_str(&partial.into())
+                .expect("cannot convert JSON string to serde value");
+            e.json_body_includes.as_mut().unwrap().push(value);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the requirement that the HTTP request body is JSON that contains an array at the
+    /// specified location, and that this array includes all of the given elements in any order.
+    ///
+    /// The location is a JSON pointer as defined in [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// such as `/order/items`. Use an empty string if the request body itself is the array.
+    ///
+    /// Elements are compared the same way as in [`json_body_includes`](#method.json_body_includes):
+    /// an expected object matches an array element that has *at least* the expected fields, so
+    /// `{"id": 1}` matches `{"id": 1, "name": "book"}`. All other values must be equal. Each
+    /// array element can only be matched by one expected element, so two expected elements
+    /// require two matching array elements. The array may contain additional elements.
+    ///
+    /// # Parameters
+    /// - `pointer`: A JSON pointer to the array.
+    /// - `elements`: The elements the array must include. Each element is serialized to JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde_json::json;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_includes_array("/items", &[json!({ "id": 3 }), json!({ "id": 1 })]);
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/orders"))
+    ///     .body(r#"{ "items": [{ "id": 1, "qty": 2 }, { "id": 2 }, { "id": 3, "qty": 1 }] }"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining.
+    ///
+    /// # Errors
+    /// This function will panic if an element cannot be serialized to JSON.
+    pub fn json_body_includes_array<IntoString: Into<String>, T: Serialize>(
+        mut self,
+        pointer: IntoString,
+        elements: &[T],
+    ) -> Self {
+        let elements = elements
+            .iter()
+            .map(|element| {
+                serde_json::to_value(element).expect("cannot serialize array element to JSON")
+            })
+            .collect();
+
+        update_cell(&self.expectations, |e| {
+            e.json_body_includes_array
+                .get_or_insert_with(Vec::new)
+                .push(JsonArrayElements {
+                    pointer: pointer.into(),
+                    elements,
+                });
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the expected partial JSON body to ensure that specific content is not present within a larger JSON structure.
+    ///
+    /// **Attention:** The partial JSON string must be a valid JSON string and should represent a substructure
+    /// of the full JSON object. It can omit irrelevant attributes but must maintain any necessary object hierarchy.
+    ///
+    /// **Note:** This method does not automatically set the `Content-Type` header to `application/json`.
+    /// You must explicitly set this header in your requests.
+    ///
+    /// # Parameters
+    /// - `partial_body`: The partial JSON content to check for exclusion. This must be a valid JSON string.
+    ///
+    /// # Example
+    /// Suppose your application sends the following JSON request body:
+    /// ```json
+    /// {
+    ///     "parent_attribute": "Some parent data goes here",
+    ///     "child": {
+    ///         "target_attribute": "Example",
+    ///         "other_attribute": "Another value"
+    ///     }
+    /// }
+    /// ```
+    /// To verify the absence of `target_attribute` with the value `Example`:
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_excludes(r#"
+    ///         {
+    ///             "child": {
+    ///                 "target_attribute": "Example"
+    ///             }
+    ///         }
+    ///     "#);
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Send a POST request with a JSON body
+    /// let response = Client::new()
+    ///     .post(&format!("http://{}/some/path", server.address()))
+    ///     .header("content-type", "application/json")
+    ///     .body(r#"
+    ///         {
+    ///             "parent_attribute": "Some parent data goes here",
+    ///             "child": {
+    ///                 "other_attribute": "Another value"
+    ///             }
+    ///         }
+    ///     "#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert the mock was called and the response status is as expected
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    /// It's important that the partial JSON contains the full object hierarchy necessary to reach the target attribute.
+    /// Irrelevant attributes such as `parent_attribute` and `child.other_attribute` in the example can be omitted.
+    pub fn json_body_excludes<IntoString: Into<String>>(mut self, partial: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.json_body_excludes.is_none() {
+                e.json_body_excludes = Some(Vec::new());
+            }
+            let value = Value::from_str(&partial.into())
+                .expect("cannot convert JSON string to serde value");
+            e.json_body_excludes.as_mut().unwrap().push(value);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the requirement that the HTTP request body is JSON that contains a value at the
+    /// specified location, regardless of what the value is.
+    ///
+    /// The location is a JSON pointer as defined in [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// such as `/user/id` or `/items/0/id`. Array elements are addressed by their index.
+    ///
+    /// **Attention:** A key that is present but set to `null` counts as present. Use
+    /// [`json_body_includes`](#method.json_body_includes) if the value must not be `null`.
+    ///
+    /// # Parameters
+    /// - `pointer`: A JSON pointer to the value that must be present.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_has_key("/meta/requestId");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/orders"))
+    ///     .body(r#"{ "meta": { "requestId": "7b0c4f0e" }, "item": "book" }"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining.
+    pub fn json_body_has_key<IntoString: Into<String>>(mut self, pointer: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.json_body_has_key
+                .get_or_insert_with(Vec::new)
+                .push(pointer.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the requirement that the HTTP request body is JSON that does not contain a value at
+    /// the specified location. A request body that is not valid JSON also meets this requirement.
+    ///
+    /// The location is a JSON pointer as defined in [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// such as `/user/id` or `/items/0/id`.
+    ///
+    /// **Attention:** A key that is present but set to `null` counts as present, so a body like
+    /// `{ "debug": null }` does not meet the requirement `json_body_missing_key("/debug")`.
+    ///
+    /// # Parameters
+    /// - `pointer`: A JSON pointer to the value that must be absent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.json_body_missing_key("/debug");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/orders"))
+    ///     .body(r#"{ "item": "book" }"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining.
+    pub fn json_body_missing_key<IntoString: Into<String>>(mut self, pointer: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.json_body_missing_key
+                .get_or_insert_with(Vec::new)
+                .push(pointer.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be JSON that is valid according to the provided JSON schema.
+    ///
+    /// If the request body is not valid, the mismatch report contains the first schema violation
+    /// that was found, including the location of the offending value within the request body.
+    ///
+    /// **Note:** This method is only available if the `json-schema` feature is enabled.
+    ///
+    /// # Parameters
+    /// - `schema`: The JSON schema as a string.
+    ///
+    /// # Panics
+    /// Panics if the provided schema is not valid JSON. Creating the mock panics if the schema
+    /// cannot be compiled, or if the mock server was built without the `json-schema` feature.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/users").json_schema(r#"
+    ///         {
+    ///             "type": "object",
+    ///             "properties": {
+    ///                 "name": { "type": "string" },
+    ///                 "age": { "type": "integer", "minimum": 0 }
+    ///             },
+    ///             "required": ["name"]
+    ///         }
+    ///     "#);
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/users"))
+    ///     .body(r#"{"name": "Peter Griffin", "age": 45}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the JSON schema requirement added.
+    #[cfg(feature = "json-schema")]
+    pub fn json_schema<IntoString: Into<String>>(mut self, schema: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.json_schema.is_none() {
+                e.json_schema = Some(Vec::new());
+            }
+            let value = Value::from_str(&schema.into())
+                .expect("cannot convert JSON schema string to serde value");
+            e.json_schema
+                .as_mut()
+                .unwrap()
+                .push(JsonSchema::from(value));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be JSON that is valid according to the JSON schema stored in
+    /// the specified file. See [`When::json_schema`] for details.
+    ///
+    /// **Note:** This method is only available if the `json-schema` feature is enabled.
+    ///
+    /// # Parameters
+    /// - `resource_file_path`: The path to the schema file. The path can be absolute or relative
+    ///   to the crate root directory.
+    ///
+    /// # Panics
+    /// Panics if the file cannot be read or does not contain valid JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST)
+    ///         .path("/users")
+    ///         .json_schema_from_file("tests/resources/user_schema.json");
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/users"))
+    ///     .body(r#"{"name": "Peter Griffin"}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the JSON schema requirement added.
+    #[cfg(feature = "json-schema")]
+    pub fn json_schema_from_file<IntoString: Into<String>>(
+        self,
+        resource_file_path: IntoString,
+    ) -> Self {
+        let resource_file_path = resource_file_path.into();
+        let path = Path::new(&resource_file_path);
+        let absolute_path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
+                "Cannot create absolute path from string '{}'",
+                &resource_file_path
+            )),
+        };
+        let content = read_file(&absolute_path).expect(&format!(
+            "Cannot read from file {}",
+            absolute_path.to_str().expect("Invalid OS path")
+        ));
+        self.json_schema(String::from_utf8(content).expect("JSON schema file is not valid UTF-8"))
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be an encoded protobuf message in which a field has the
+    /// specified value.
+    ///
+    /// The request body is decoded using the message type of the provided descriptor. The field
+    /// is addressed by its name. Fields of nested messages are addressed with a dot-separated
+    /// path, such as `customer.name`. The field must be a singular scalar or enum field. Repeated
+    /// and map fields are not supported. Fields that are not set in the request body have their
+    /// default value, just like they have in generated protobuf code.
+    ///
+    /// The value is compared to the JSON representation of the field value: numbers are compared
+    /// by value, `bytes` fields are represented as a base64 string, and enum fields can be
+    /// compared using the name or the number of the enum value. If the request body cannot be
+    /// decoded, the mismatch report contains the decoding error.
+    ///
+    /// **Note:** This method is only available if the `protobuf` feature is enabled.
+    ///
+    /// # Parameters
+    /// - `descriptor`: The descriptor of the message type of the request body.
+    /// - `field_path`: The dot-separated path to the field.
+    /// - `value`: The expected field value.
+    ///
+    /// # Panics
+    /// Panics if the message type has no field at `field_path` or if the field is not a singular
+    /// scalar or enum field.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use httpmock::ProtobufDescriptor;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// // shop.desc is an encoded FileDescriptorSet, as produced by
+    /// // protoc --include_imports --descriptor_set_out=shop.desc shop.proto
+    /// let descriptor_set = std::fs::read("tests/resources/shop.desc").unwrap();
+    /// let order = ProtobufDescriptor::new(descriptor_set, "shop.v1.Order");
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST)
+    ///         .path("/orders")
+    ///         .protobuf_field(&order, "id", 42)
+    ///         .protobuf_field(&order, "customer.name", "Alice");
+    ///     then.status(201);
+    /// });
+    ///
+    /// // Order { id: 42, customer: Customer { name: "Alice" } }
+    /// let body = b"\x08\x2a\x12\x07\x0a\x05Alice".to_vec();
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/orders"))
+    ///     .header("content-type", "application/x-protobuf")
+    ///     .body(body)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the protobuf field requirement added.
+    #[cfg(feature = "protobuf")]
+    pub fn protobuf_field<IntoString: Into<String>, IntoValue: Into<Value>>(
+        mut self,
+        descriptor: &ProtobufDescriptor,
+        field_path: IntoString,
+        value: IntoValue,
+    ) -> Self {
+        let field_path = field_path.into();
+
+        let message =
+            message_descriptor(&descriptor.file_descriptor_set.0, &descriptor.message_name)
+                .expect("invalid protobuf descriptor");
+        if let Err(err) = resolve_field_path(&message, &field_path) {
+            panic!("{}", err);
+        }
+
+        update_cell(&self.expectations, |e| {
+            e.protobuf_field
+                .get_or_insert_with(Vec::new)
+                .push(ProtobufFieldExpectation {
+                    file_descriptor_set: descriptor.file_descriptor_set.clone(),
+                    message_name: descriptor.message_name.clone(),
+                    field_path,
+                    value: value.into(),
+                });
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be valid JSON, without making any assertions about its content.
+    ///
+    /// If the body cannot be parsed, the mismatch report contains the parser error including the
+    /// line and column where parsing failed.
+    ///
+    /// **Note:** This method does not check the `Content-Type` header. If you want to verify that
+    /// the request declares a JSON body, you must explicitly add a header expectation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/users").is_json();
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/users"))
+    ///     .body(r#"{"name": "Peter Griffin"}"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the body format requirement added.
+    pub fn is_json(mut self) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.is_json = Some(true);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be a well-formed XML document, without making any assertions
+    /// about its content.
+    ///
+    /// If the body cannot be parsed, the mismatch report contains the parser error including the
+    /// line and column where parsing failed.
+    ///
+    /// **Note:** This method does not check the `Content-Type` header and does not validate the
+    /// document against a schema or DTD.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/orders").is_valid_xml();
+    ///     then.status(201);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/orders"))
+    ///     .body(r#"<order><item id="42"/></order>"#)
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 201);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the body format requirement added.
+    pub fn is_valid_xml(mut self) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.is_valid_xml = Some(true);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Requires the request body to be valid `application/x-www-form-urlencoded` data, without
+    /// making any assertions about the contained key-value pairs.
+    ///
+    /// A body is considered valid if every percent sign is followed by two hexadecimal digits.
+    /// Like most form parsers, unencoded characters such as `/`, `:` or `@` are accepted. An
+    /// empty body is valid. If the body is invalid, the mismatch report contains the byte
+    /// position of the first malformed percent-escape.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.method(POST).path("/login").is_form_urlencoded();
+    ///     then.status(200);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .post(server.url("/login"))
+    ///     .header("content-type", "application/x-www-form-urlencoded")
+    ///     .body("user=peter&password=p%40ssword")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the body format requirement added.
+    pub fn is_form_urlencoded(mut self) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.is_form_urlencoded = Some(true);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Adds a key-value pair to the requirements for an `application/x-www-form-urlencoded` request body.
+    ///
+    /// This method sets an expectation for a specific key-value pair to be included in the request body
+    /// of an `application/x-www-form-urlencoded` POST request. Each key and value are URL-encoded as specified
+    /// by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key of the key-value pair to set as a requirement.
+    /// - `value`: The value of the key-value pair to set as a requirement.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple("name", "Peter Griffin")
+    ///        .form_urlencoded_tuple("town", "Quahog");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value pair added to the `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        value: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple.is_none() {
+                e.form_urlencoded_tuple = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple
+                .as_mut()
+                .unwrap()
+                .push((key.into(), value.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Adds a key-value pair to the negative requirements for an `application/x-www-form-urlencoded` request body.
+    ///
+    /// This method sets an expectation for a specific key-value pair to be excluded from the request body
+    /// of an `application/x-www-form-urlencoded` POST request. Each key and value are URL-encoded as specified
+    /// by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key of the key-value pair to set as a requirement.
+    /// - `value`: The value of the key-value pair to set as a requirement.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_not("name", "Peter Griffin");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Lois%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value pair added to the negative `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        value: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_not.is_none() {
+                e.form_urlencoded_tuple_not = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_not
+                .as_mut()
+                .unwrap()
+                .push((key.into(), value.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement for the existence of a key in an `application/x-www-form-urlencoded` request body.
+    ///
+    /// This method sets an expectation that a specific key must be present in the request body of an
+    /// `application/x-www-form-urlencoded` POST request, regardless of its value. The key is URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key that must exist in the `application/x-www-form-urlencoded` request body.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_exists("name")
+    ///        .form_urlencoded_tuple_exists("town");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key existence requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_exists<IntoString: Into<String>>(
+        mut self,
+        key: IntoString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_exists.is_none() {
+                e.form_urlencoded_tuple_exists = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_exists
+                .as_mut()
+                .unwrap()
+                .push(key.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key must be absent in an `application/x-www-form-urlencoded` request body.
+    ///
+    /// This method sets an expectation that a specific key must not be present in the request body of an
+    /// `application/x-www-form-urlencoded` POST request. The key is URL-encoded as specified by the
+    /// [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key that must be absent in the `application/x-www-form-urlencoded` request body.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_missing("name")
+    ///        .form_urlencoded_tuple_missing("town");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("city=Quahog&occupation=Cartoonist")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key absence requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_missing<IntoString: Into<String>>(
+        mut self,
+        key: IntoString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_missing.is_none() {
+                e.form_urlencoded_tuple_missing = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_missing
+                .as_mut()
+                .unwrap()
+                .push(key.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must contain a specific substring.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must contain a specified substring
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the substring are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `substring`: The substring that must be present in the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_includes("name", "Griffin")
+    ///        .form_urlencoded_tuple_includes("town", "Quahog");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value substring requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_includes<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        substring: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_includes.is_none() {
+                e.form_urlencoded_tuple_includes = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_includes
+                .as_mut()
+                .unwrap()
+                .push((key.into(), substring.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not contain a specific substring.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must not contain a specified substring
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the substring are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `substring`: The substring that must not be present in the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_excludes("name", "Griffin");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Lois%20Smith&city=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value substring exclusion requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_excludes<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        substring: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_excludes.is_none() {
+                e.form_urlencoded_tuple_excludes = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_excludes
+                .as_mut()
+                .unwrap()
+                .push((key.into(), substring.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must start with a specific prefix.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must start with a specified prefix
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the prefix are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `prefix`: The prefix that must appear at the start of the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_prefix("name", "Pete")
+    ///        .form_urlencoded_tuple_prefix("town", "Qua");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value prefix requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_prefix<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        prefix: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_prefix.is_none() {
+                e.form_urlencoded_tuple_prefix = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_prefix
+                .as_mut()
+                .unwrap()
+                .push((key.into(), prefix.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not start with a specific prefix.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must not start with a specified prefix
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the prefix are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `prefix`: The prefix that must not appear at the start of the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_prefix_not("name", "Lois")
+    ///        .form_urlencoded_tuple_prefix_not("town", "Hog");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value prefix exclusion requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_prefix_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        prefix: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_prefix_not.is_none() {
+                e.form_urlencoded_tuple_prefix_not = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_prefix_not
+                .as_mut()
+                .unwrap()
+                .push((key.into(), prefix.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must end with a specific suffix.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must end with a specified suffix
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the suffix are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `suffix`: The suffix that must appear at the end of the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_suffix("name", "Griffin")
+    ///        .form_urlencoded_tuple_suffix("town", "hog");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value suffix requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_suffix<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        suffix: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_suffix.is_none() {
+                e.form_urlencoded_tuple_suffix = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_suffix
+                .as_mut()
+                .unwrap()
+                .push((key.into(), suffix.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key's value in an `application/x-www-form-urlencoded` request body must not end with a specific suffix.
+    ///
+    /// This method sets an expectation that the value associated with a specific key must not end with a specified suffix
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and the suffix are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key`: The key in the `application/x-www-form-urlencoded` request body.
+    /// - `suffix`: The suffix that must not appear at the end of the value associated with the key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_suffix_not("name", "Smith")
+    ///        .form_urlencoded_tuple_suffix_not("town", "ville");
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value suffix exclusion requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_suffix_not<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        key: KeyString,
+        suffix: ValueString,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_suffix_not.is_none() {
+                e.form_urlencoded_tuple_suffix_not = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_suffix_not
+                .as_mut()
+                .unwrap()
+                .push((key.into(), suffix.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement that a key-value pair in an `application/x-www-form-urlencoded` request body must match specific regular expressions.
+    ///
+    /// This method sets an expectation that the key and the value in a key-value pair must match the specified regular expressions
+    /// in the request body of an `application/x-www-form-urlencoded` POST request. The key and value regular expressions are URL-encoded
+    /// as specified by the [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key_regex`: The regular expression that the key must match in the `application/x-www-form-urlencoded` request body.
+    /// - `value_regex`: The regular expression that the value must match in the `application/x-www-form-urlencoded` request body.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use regex::Regex;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let key_regex = Regex::new(r"^name$").unwrap();
+    /// let value_regex = Regex::new(r"^Peter\sGriffin$").unwrap();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_matches(key_regex, value_regex);
+    ///    then.status(202);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
+    ///    .header("content-type", "application/x-www-form-urlencoded")
+    ///    .body("name=Peter%20Griffin&town=Quahog")
+    ///    .send()
+    ///    .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value regex matching requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_matches<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
+        mut self,
+        key_regex: KeyRegex,
+        value_regex: ValueRegex,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_matches.is_none() {
+                e.form_urlencoded_tuple_matches = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_matches
+                .as_mut()
+                .unwrap()
+                .push((key_regex.into(), value_regex.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a requirement for the number of times a key-value pair matching specific regular expressions appears in an `application/x-www-form-urlencoded` request body.
+    ///
+    /// This method sets an expectation that the key-value pair must appear a specific number of times in the request body of an
+    /// `application/x-www-form-urlencoded` POST request. The key and value regular expressions are URL-encoded as specified by the
+    /// [URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+    ///
+    /// **Note**: The mock server does not automatically verify that the HTTP method is POST as per spec.
+    /// If you want to verify that the request method is POST, you must explicitly set it in your mock configuration.
+    ///
+    /// # Parameters
+    /// - `key_regex`: The regular expression that the key must match in the `application/x-www-form-urlencoded` request body.
+    /// - `value_regex`: The regular expression that the value must match in the `application/x-www-form-urlencoded` request body.
+    /// - `count`: The number of times the key-value pair matching the regular expressions must appear.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use regex::Regex;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.method(POST)
+    ///        .path("/example")
+    ///        .header("content-type", "application/x-www-form-urlencoded")
+    ///        .form_urlencoded_tuple_count(
+    ///            Regex::new(r"^name$").unwrap(),
+    ///            Regex::new(r".*Griffin$").unwrap(),
+    ///            2
+    ///        );
+    ///    then.status(202);
+    /// });
+    ///
+    /// // Act
+    /// let response = Client::new()
+    ///    .post(server.url("/example"))
     ///    .header("content-type", "application/x-www-form-urlencoded")
     ///    .body("name=Peter%20Griffin&name=Lois%20Griffin&town=Quahog")
     ///    .send()
@@ -4705,630 +6986,2761 @@ impl When {
     ///
     /// // Assert
     /// m.assert();
-    /// assert_eq!(response.status(), 202);
+    /// assert_eq!(response.status(), 202);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new key-value count requirement added to the
+    /// `application/x-www-form-urlencoded` expectations.
+    pub fn form_urlencoded_tuple_count<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
+        mut self,
+        key_regex: KeyRegex,
+        value_regex: ValueRegex,
+        count: usize,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.form_urlencoded_tuple_count.is_none() {
+                e.form_urlencoded_tuple_count = Some(Vec::new());
+            }
+            e.form_urlencoded_tuple_count.as_mut().unwrap().push((
+                key_regex.into(),
+                value_regex.into(),
+                count,
+            ));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Adds a custom matcher for expected HTTP requests. If this function returns true, the request
+    /// is considered a match, and the mock server will respond to the request
+    /// (given all other criteria are also met).
+    ///
+    /// You can use this function to create custom expectations for your mock server based on any aspect
+    /// of the `HttpMockRequest` object.
+    ///
+    /// # Parameters
+    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.matches(|req: &HttpMockRequest| {
+    ///         req.uri().path().contains("es")
+    ///    });
+    ///    then.status(200);
+    /// });
+    ///
+    /// // Act: Send the HTTP request
+    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
+    #[deprecated(
+        since = "0.8.0",
+        note = "Please use the `is_true` and `is_false` function instead"
+    )]
+    pub fn matches(
+        mut self,
+        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
+    ) -> Self {
+        return self.is_true(matcher);
+    }
+    // @docs-group: Custom
+
+    /// Adds a custom matcher for expected HTTP requests. If this function returns true, the request
+    /// is considered a match, and the mock server will respond to the request
+    /// (given all other criteria are also met).
+    ///
+    /// You can use this function to create custom expectations for your mock server based on any aspect
+    /// of the `HttpMockRequest` object.
+    ///
+    /// # Parameters
+    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.is_true(|req: &HttpMockRequest| {
+    ///         req.uri().path().contains("es")
+    ///    });
+    ///    then.status(200);
+    /// });
+    ///
+    /// // Act: Send the HTTP request
+    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
+    pub fn is_true(
+        mut self,
+        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.is_true.is_none() {
+                e.is_true = Some(Vec::new());
+            }
+            e.is_true.as_mut().unwrap().push(Arc::new(matcher));
+        });
+        self
+    }
+    // @docs-group: Custom
+
+    /// Adds a custom matcher for expected HTTP requests. If this function returns false, the request
+    /// is considered a match, and the mock server will respond to the request
+    /// (given all other criteria are also met).
+    ///
+    /// You can use this function to create custom expectations for your mock server based on any aspect
+    /// of the `HttpMockRequest` object.
+    ///
+    /// # Parameters
+    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Arrange
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///    when.is_false(|req: &HttpMockRequest| {
+    ///         req.uri().path().contains("es")
+    ///    });
+    ///    then.status(404);
+    /// });
+    ///
+    /// // Act: Send the HTTP request
+    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 404);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
+    pub fn is_false(
+        mut self,
+        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.is_false.is_none() {
+                e.is_false = Some(Vec::new());
+            }
+            e.is_false.as_mut().unwrap().push(Arc::new(matcher));
+        });
+        self
+    }
+    // @docs-group: Custom
+
+    /// Adds a matcher that was registered on the server under a name using
+    /// [`HttpMockServerBuilder::register_matcher`](crate::server::HttpMockServerBuilder::register_matcher).
+    /// The request is considered a match if the registered function returns `true` when
+    /// called with the request and the given arguments.
+    ///
+    /// In contrast to [`When::is_true`](#method.is_true), only the name and the arguments are
+    /// sent to the server, so named matchers also work with remote (standalone) mock servers.
+    /// Creating a mock fails if the server has no matcher registered under the given name.
+    ///
+    /// # Parameters
+    /// - `name`: The name under which the matcher was registered on the server.
+    /// - `args`: JSON arguments that are passed to the matcher function.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::{server::HttpMockServerBuilder, When};
+    /// use serde_json::json;
+    ///
+    /// // On the server side (e.g., in a standalone server binary)
+    /// let server = HttpMockServerBuilder::new()
+    ///     .register_matcher("has_query_param", |req, args| {
+    ///         let name = args["name"].as_str().unwrap_or_default();
+    ///         req.uri().query().map_or(false, |q| q.contains(name))
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // On the client side
+    /// let mock_spec = |when: When| when.matcher_named("has_query_param", json!({ "name": "page" }));
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn matcher_named<IntoString: Into<String>>(
+        mut self,
+        name: IntoString,
+        args: Value,
+    ) -> Self {
+        update_cell(&self.expectations, |e| {
+            if e.matcher_named.is_none() {
+                e.matcher_named = Some(Vec::new());
+            }
+            e.matcher_named.as_mut().unwrap().push(NamedMatcherCall {
+                name: name.into(),
+                args,
+            });
+        });
+        self
+    }
+    // @docs-group: Custom
+
+    /// Sets the priority of the mock. When a request matches more than one mock, the mock with
+    /// the highest priority serves the request.
+    ///
+    /// Mocks without an explicit priority have a priority of `0`. If multiple matching mocks
+    /// share the same priority, the mock that was created first serves the request.
+    /// Negative values can be used to define fallback mocks.
+    ///
+    /// This describes the default match strategy of the server. Servers that are configured
+    /// with [`HttpMockServerBuilder::match_strategy`](crate::server::HttpMockServerBuilder::match_strategy)
+    /// may use priorities only to break ties or ignore them completely.
+    ///
+    /// # Parameters
+    /// - `priority`: The priority of the mock. Higher values are evaluated first.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// // A generic mock that matches all requests to "/search"
+    /// let generic_mock = server.mock(|when, then| {
+    ///     when.path("/search");
+    ///     then.status(200).body("generic");
+    /// });
+    ///
+    /// // A more specific mock that was created later, but takes precedence
+    /// let specific_mock = server.mock(|when, then| {
+    ///     when.path("/search")
+    ///         .query_param("query", "metallica")
+    ///         .priority(10);
+    ///     then.status(200).body("specific");
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/search?query=metallica")).unwrap();
+    ///
+    /// specific_mock.assert();
+    /// generic_mock.assert_calls(0);
+    /// assert_eq!(response.text().unwrap(), "specific");
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the priority set.
+    pub fn priority(mut self, priority: i32) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.priority = Some(priority);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Limits the number of requests the mock serves. After the mock has responded `times`
+    /// times, it no longer matches any request, so that other mocks (e.g., a fallback mock
+    /// defined later or with a lower priority) serve subsequent requests.
+    ///
+    /// This is useful to test retry logic, for example by letting a mock fail a fixed number of
+    /// times before another mock responds successfully.
+    ///
+    /// Requests that are not served by an exhausted mock are not counted as calls of that mock,
+    /// so assertions such as [`Mock::assert_calls`](crate::Mock::assert_calls) report the
+    /// number of requests the mock actually served.
+    ///
+    /// # Parameters
+    /// - `times`: The maximum number of requests the mock serves.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// // Fails the first two requests
+    /// let failing_mock = server.mock(|when, then| {
+    ///     when.path("/orders").times(2);
+    ///     then.status(503);
+    /// });
+    ///
+    /// // Serves all subsequent requests
+    /// let fallback_mock = server.mock(|when, then| {
+    ///     when.path("/orders");
+    ///     then.status(200);
+    /// });
+    ///
+    /// let statuses: Vec<u16> = (0..3)
+    ///     .map(|_| {
+    ///         reqwest::blocking::get(server.url("/orders"))
+    ///             .unwrap()
+    ///             .status()
+    ///             .as_u16()
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(statuses, vec![503, 503, 200]);
+    /// failing_mock.assert_calls(2);
+    /// fallback_mock.assert_calls(1);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the limit set.
+    pub fn times(mut self, times: usize) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.times = Some(times);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Adds a tag to the mock. Tags do not influence which requests the mock matches. Instead,
+    /// they group related mocks, so that all mocks with a tag can be deleted or verified at once
+    /// (see [`MockServer::delete_mocks_by_tag`](crate::MockServer::delete_mocks_by_tag) and
+    /// [`MockServer::assert_tag_called`](crate::MockServer::assert_tag_called)) without keeping
+    /// track of the individual [`Mock`](crate::Mock) handles.
+    ///
+    /// This method can be called multiple times to add more than one tag to the mock.
+    ///
+    /// # Parameters
+    /// - `tag`: The tag to add to the mock.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/billing/invoices").tag("billing");
+    ///     then.status(200);
+    /// });
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/billing/payments").tag("billing");
+    ///     then.status(200);
+    /// });
+    ///
+    /// reqwest::blocking::get(server.url("/billing/invoices")).unwrap();
+    ///
+    /// server.assert_tag_called("billing", 1);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the tag added.
+    pub fn tag<IntoString: Into<String>>(mut self, tag: IntoString) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.tags.get_or_insert_with(Vec::new).push(tag.into());
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Lets the mock expire after the given time to live. Once expired, the mock is removed
+    /// from the mock server, and requests behave as if the mock had never been created. This
+    /// is useful for long-running mock servers (e.g., in standalone mode) to prevent stale
+    /// mocks from accumulating.
+    ///
+    /// The time to live starts when the mock server receives the mock. An expired mock never
+    /// serves a request, but its removal is best-effort: the server sweeps expired mocks
+    /// periodically (about once per second), so a mock can remain visible to methods like
+    /// [`Mock::calls`](crate::Mock::calls) for a short time after it expired. Once it has
+    /// been removed, these methods panic because the mock no longer exists.
+    ///
+    /// If this method is combined with [`expires_at`](#method.expires_at), the mock expires
+    /// at whichever point in time comes first.
+    ///
+    /// # Parameters
+    /// - `ttl`: The time after which the mock expires.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/feature-flag").expires_in(Duration::from_secs(1));
+    ///     then.status(200);
+    /// });
+    ///
+    /// let status = || reqwest::blocking::get(server.url("/feature-flag")).unwrap().status();
+    ///
+    /// assert_eq!(status(), 200);
+    /// std::thread::sleep(Duration::from_millis(1500));
+    /// assert_eq!(status(), 404);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the time to live set.
+    pub fn expires_in<D: Into<Duration>>(mut self, ttl: D) -> Self {
+        let ttl = ttl.into();
+        update_cell(&self.expectations, |e| {
+            e.expires_in = Some(ttl.as_millis() as u64);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Lets the mock expire at the given point in time. The point in time is compared with the
+    /// system clock of the mock server, which may differ from the local clock when using a
+    /// remote mock server. See [`expires_in`](#method.expires_in) for details on how expired
+    /// mocks are removed.
+    ///
+    /// # Parameters
+    /// - `time`: The point in time at which the mock expires.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/maintenance")
+    ///         .expires_at(SystemTime::now() + Duration::from_secs(3600));
+    ///     then.status(503);
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/maintenance")).unwrap();
+    /// assert_eq!(response.status(), 503);
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: Returns the modified `When` object with the expiry time set.
+    pub fn expires_at(mut self, time: SystemTime) -> Self {
+        update_cell(&self.expectations, |e| {
+            e.expires_at = Some(time);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Applies a specified function to enhance or modify the `When` instance. This method allows for the
+    /// encapsulation of multiple matching conditions into a single function, maintaining a clear and fluent
+    /// interface for setting up HTTP request expectations.
+    ///
+    /// This method is particularly useful for reusing common setup patterns across multiple test scenarios,
+    /// promoting cleaner and more maintainable test code.
+    ///
+    /// # Parameters
+    /// - `func`: A function that takes a `When` instance and returns it after applying some conditions.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use httpmock::{prelude::*, When};
+    /// use httpmock::Method::POST;
+    ///
+    /// // Function to apply a standard authorization and content type setup for JSON POST requests
+    /// fn is_authorized_json_post_request(when: When) -> When {
+    ///     when.method(POST)
+    ///         .header("Authorization", "SOME API KEY")
+    ///         .header("Content-Type", "application/json")
+    /// }
+    ///
+    /// // Usage example demonstrating how to maintain fluent interface style with complex setups.
+    /// // This approach keeps the chain of conditions clear and readable, enhancing test legibility
+    /// let server = MockServer::start();
+    /// let m = server.mock(|when, then| {
+    ///     when.query_param("user_id", "12345")
+    ///         .and(is_authorized_json_post_request) // apply the function to include common setup
+    ///         .json_body_includes(r#"{"key": "value"}"#); // additional specific condition
+    ///     then.status(200);
+    /// });
+    /// ```
+    ///
+    /// # Returns
+    /// `When`: The modified `When` instance with additional conditions applied, suitable for further chaining.
+    pub fn and(mut self, func: impl FnOnce(When) -> When) -> Self {
+        func(self)
+    }
+    // @docs-group: Miscellaneous
+
+    /// Specifies a group of alternative conditions of which at least one must be met by the
+    /// request. Each alternative is added with [`ConditionGroup::when`](ConditionGroup::when)
+    /// and can use all methods of `When`, including further groups. All other conditions of the
+    /// mock must still be met as well.
+    ///
+    /// If no alternative matches, the mismatch output lists every alternative together with
+    /// the conditions it failed and its distance to the request.
+    ///
+    /// # Parameters
+    /// - `group`: A function that adds the alternative conditions to a `ConditionGroup`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.method(GET).any_of(|group| {
+    ///         group
+    ///             .when(|when| when.path("/a"))
+    ///             .when(|when| when.path("/b"));
+    ///     });
+    ///     then.status(200);
+    /// });
+    ///
+    /// reqwest::blocking::get(&server.url("/a")).unwrap();
+    /// reqwest::blocking::get(&server.url("/b")).unwrap();
+    ///
+    /// m.assert_calls(2);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if no alternative is added to the group.
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn any_of(mut self, group: impl FnOnce(&mut ConditionGroup)) -> Self {
+        let mut condition_group = ConditionGroup::default();
+        group(&mut condition_group);
+
+        if condition_group.conditions.is_empty() {
+            panic!("any_of requires at least one alternative condition");
+        }
+
+        update_cell(&self.expectations, |e| {
+            if e.any_of.is_none() {
+                e.any_of = Some(Vec::new());
+            }
+            e.any_of.as_mut().unwrap().push(condition_group.conditions);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+
+    /// Specifies a group of conditions that must all be met by the request. This is mostly
+    /// useful inside an alternative of [`When::any_of`](When::any_of), or to bundle conditions
+    /// that belong together.
+    ///
+    /// # Parameters
+    /// - `group`: A function that adds the conditions to a `ConditionGroup`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.any_of(|group| {
+    ///         group
+    ///             .when(|when| {
+    ///                 when.all_of(|group| {
+    ///                     group
+    ///                         .when(|when| when.method(GET))
+    ///                         .when(|when| when.path("/items"));
+    ///                 })
+    ///             })
+    ///             .when(|when| when.method(POST).path("/search"));
+    ///     });
+    ///     then.status(200);
+    /// });
+    ///
+    /// reqwest::blocking::get(&server.url("/items")).unwrap();
+    ///
+    /// m.assert();
+    /// ```
+    ///
+    /// # Returns
+    /// The updated `When` instance to allow method chaining for additional configuration.
+    pub fn all_of(mut self, group: impl FnOnce(&mut ConditionGroup)) -> Self {
+        let mut condition_group = ConditionGroup::default();
+        group(&mut condition_group);
+
+        update_cell(&self.expectations, |e| {
+            if e.all_of.is_none() {
+                e.all_of = Some(Vec::new());
+            }
+            e.all_of
+                .as_mut()
+                .unwrap()
+                .extend(condition_group.conditions);
+        });
+        self
+    }
+    // @docs-group: Miscellaneous
+}
+
+/// A group of request conditions that is created using [`When::any_of`](When::any_of) or
+/// [`When::all_of`](When::all_of). Each condition is specified like the conditions of a mock
+/// and may consist of multiple requirements.
+#[derive(Default)]
+pub struct ConditionGroup {
+    conditions: Vec<RequestRequirements>,
+}
+
+impl ConditionGroup {
+    /// Adds a condition to the group.
+    ///
+    /// # Parameters
+    /// - `spec`: A function that specifies the condition using a `When` instance.
+    ///
+    /// # Returns
+    /// The `ConditionGroup` instance to allow adding further conditions.
+    pub fn when(&mut self, spec: impl FnOnce(When) -> When) -> &mut Self {
+        let expectations = Rc::new(Cell::new(RequestRequirements::new()));
+
+        spec(When {
+            expectations: expectations.clone(),
+        });
+
+        self.conditions.push(expectations.take());
+        self
+    }
+}
+
+/// Represents the configuration of HTTP responses in a mock server environment.
+///
+/// The `Then` structure is used to define the details of the HTTP response that will be sent if
+/// an incoming request meets the conditions specified by a corresponding `When` structure. It
+/// allows for detailed customization of response aspects such as status codes, headers, body
+/// content, and delays. This structure is integral to defining how the mock server behaves when
+/// it receives a request that matches the defined expectations.
+pub struct Then {
+    pub(crate) response_template: Rc<Cell<MockServerHttpResponse>>,
+}
+
+impl Then {
+    /// Configures the HTTP response status code that the mock server will return.
+    ///
+    /// # Parameters
+    /// - `status`: A `u16` HTTP status code that the mock server should return for the configured request.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// Demonstrates setting a 200 OK status for a request to the path `/hello`.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200);
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    ///
+    /// // Check that the mock was called as expected and the response status is as configured
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    pub fn status<U16: TryInto<u16>>(mut self, status: U16) -> Self
+    where
+        <U16 as TryInto<u16>>::Error: std::fmt::Debug,
+    {
+        update_cell(&self.response_template, |r| {
+            r.status = Some(
+                status
+                    .try_into()
+                    .expect("cannot parse status code to usize"),
+            );
+        });
+        self
+    }
+    // @docs-group: Status
+
+    /// Configures the mock server to take the HTTP response status code from the specified
+    /// request header, so that a single mock can serve many different status codes. This is
+    /// useful when the client under test should decide which scenario it wants to trigger.
+    ///
+    /// If the request does not contain the header, or the header value is not a number, the
+    /// status code set with [`Then::status`](#method.status) is returned (`200` if none was set).
+    /// If the header contains a number that is not a valid HTTP status code (e.g., `1000`),
+    /// the mock server responds with status code `400` and a body that explains the error.
+    ///
+    /// # Parameters
+    /// - `header_name`: The name of the request header that contains the status code.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200).status_from_header("x-mock-status");
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .get(server.url("/hello"))
+    ///     .header("x-mock-status", "503")
+    ///     .send()
+    ///     .unwrap();
+    /// assert_eq!(response.status(), 503);
+    ///
+    /// // Without the header, the configured status code is returned
+    /// let response = Client::new().get(server.url("/hello")).send().unwrap();
+    /// assert_eq!(response.status(), 200);
+    ///
+    /// m.assert_calls(2);
+    /// ```
+    pub fn status_from_header<IntoString: Into<String>>(mut self, header_name: IntoString) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.status_from_header = Some(header_name.into());
+        });
+        self
+    }
+    // @docs-group: Status
+
+    /// Sets a custom reason phrase for the HTTP/1 status line of the response, such as
+    /// `HTTP/1.1 418 I'm a teapot`. Without it, the standard reason phrase for the status code
+    /// is sent. The phrase is used regardless of the status code. HTTP/2 responses have no
+    /// reason phrase, so it is ignored for them.
+    ///
+    /// # Parameters
+    /// - `phrase`: The reason phrase. It may only contain tabs, spaces and visible ASCII characters.
+    ///
+    /// # Panics
+    /// Panics if the phrase contains other characters, such as line breaks or non-ASCII characters,
+    /// because they would produce an invalid status line.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/coffee");
+    ///     then.status(418).reason("I'm a teapot");
+    /// });
+    ///
+    /// let mut stream = TcpStream::connect(server.address()).unwrap();
+    /// stream
+    ///     .write_all(b"GET /coffee HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+    ///     .unwrap();
+    ///
+    /// let mut response = String::new();
+    /// stream.read_to_string(&mut response).unwrap();
+    ///
+    /// m.assert();
+    /// assert!(response.starts_with("HTTP/1.1 418 I'm a teapot\r\n"));
+    /// ```
+    pub fn reason<IntoString: Into<String>>(mut self, phrase: IntoString) -> Self {
+        let phrase = phrase.into();
+        assert!(
+            is_valid_reason_phrase(&phrase),
+            "invalid reason phrase '{}': only tabs, spaces and visible ASCII characters are allowed",
+            phrase.escape_debug()
+        );
+
+        update_cell(&self.response_template, |r| {
+            r.reason = Some(phrase);
+        });
+        self
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `200 OK`. This is a shorthand for
+    /// [`status(200)`](#method.status). Similar shorthands exist for other common status codes,
+    /// such as [`created`](#method.created), [`not_found`](#method.not_found) or
+    /// [`internal_server_error`](#method.internal_server_error).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.ok().body("hi");
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// ```
+    pub fn ok(self) -> Self {
+        self.status(200)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `201 Created`. This is a shorthand for
+    /// [`status(201)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn created(self) -> Self {
+        self.status(201)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `202 Accepted`. This is a shorthand for
+    /// [`status(202)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn accepted(self) -> Self {
+        self.status(202)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `204 No Content` and makes sure that no response
+    /// body is sent, as required by
+    /// [RFC 9110, Section 15.3.5](https://datatracker.ietf.org/doc/html/rfc9110#section-15.3.5).
+    ///
+    /// A response body that was configured before this method is called is removed and a
+    /// warning is logged. A mock that configures a body afterwards is rejected when it is
+    /// created.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.method(DELETE).path("/users/1");
+    ///     then.no_content();
+    /// });
+    ///
+    /// let response = reqwest::blocking::Client::new()
+    ///     .delete(server.url("/users/1"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.status(), 204);
+    /// assert_eq!(response.text().unwrap(), "");
+    /// ```
+    pub fn no_content(self) -> Self {
+        self.status_without_body(204)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `304 Not Modified` and makes sure that no response
+    /// body is sent, as required by
+    /// [RFC 9110, Section 15.4.5](https://datatracker.ietf.org/doc/html/rfc9110#section-15.4.5).
+    ///
+    /// A response body that was configured before this method is called is removed and a
+    /// warning is logged. A mock that configures a body afterwards is rejected when it is
+    /// created.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn not_modified(self) -> Self {
+        self.status_without_body(304)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `400 Bad Request`. This is a shorthand for
+    /// [`status(400)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn bad_request(self) -> Self {
+        self.status(400)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `401 Unauthorized`. This is a shorthand for
+    /// [`status(401)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn unauthorized(self) -> Self {
+        self.status(401)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `403 Forbidden`. This is a shorthand for
+    /// [`status(403)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn forbidden(self) -> Self {
+        self.status(403)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `404 Not Found`. This is a shorthand for
+    /// [`status(404)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn not_found(self) -> Self {
+        self.status(404)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `409 Conflict`. This is a shorthand for
+    /// [`status(409)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn conflict(self) -> Self {
+        self.status(409)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `500 Internal Server Error`. This is a shorthand for
+    /// [`status(500)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn internal_server_error(self) -> Self {
+        self.status(500)
+    }
+    // @docs-group: Status
+
+    /// Sets the HTTP response status code to `503 Service Unavailable`. This is a shorthand for
+    /// [`status(503)`](#method.status).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    pub fn service_unavailable(self) -> Self {
+        self.status(503)
+    }
+    // @docs-group: Status
+
+    /// Responds with `429 Too Many Requests` and a `Retry-After` header that tells the client
+    /// how long to wait before sending another request. This is useful for testing how clients
+    /// back off when they hit a rate limit.
+    ///
+    /// As required by the HTTP specification, the `Retry-After` header contains an integer
+    /// number of seconds. Fractions of a second are rounded up, so that clients never retry
+    /// earlier than requested. Use [`rate_limited_until`](#method.rate_limited_until) to send a
+    /// date instead. A `Retry-After` header that was set before is replaced.
+    ///
+    /// # Parameters
+    /// - `retry_after`: How long the client should wait before retrying.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/api");
+    ///     then.rate_limited(Duration::from_millis(1500));
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = reqwest::blocking::get(server.url("/api")).unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 429);
+    /// assert_eq!(response.headers()["retry-after"], "2");
+    /// ```
+    pub fn rate_limited<D: Into<Duration>>(mut self, retry_after: D) -> Self {
+        let retry_after = retry_after.into();
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        update_cell(&self.response_template, |r| {
+            r.set_rate_limited(seconds.to_string());
+        });
+        self
+    }
+    // @docs-group: Status
+
+    /// Responds with `429 Too Many Requests` and a `Retry-After` header that contains the time
+    /// after which the client may send another request, formatted as an HTTP date (e.g.,
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`). See [`rate_limited`](#method.rate_limited) for a
+    /// `Retry-After` header that contains a number of seconds instead.
+    ///
+    /// # Parameters
+    /// - `retry_at`: The time after which the client may retry. HTTP dates have a precision of
+    ///   one second, so fractions of a second are dropped.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/api");
+    ///     then.rate_limited_until(UNIX_EPOCH + Duration::from_secs(1445412480));
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = reqwest::blocking::get(server.url("/api")).unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 429);
+    /// assert_eq!(response.headers()["retry-after"], "Wed, 21 Oct 2015 07:28:00 GMT");
+    /// ```
+    pub fn rate_limited_until(mut self, retry_at: SystemTime) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.set_rate_limited(httpdate::fmt_http_date(retry_at));
+        });
+        self
+    }
+    // @docs-group: Status
+
+    fn status_without_body(self, status: u16) -> Self {
+        update_cell(&self.response_template, |r| {
+            let has_body = r.body.is_some()
+                || r.body_file.is_some()
+                || r.body_template.is_some()
+                || r.json_body_merge.is_some();
+            if has_body {
+                log::warn!(
+                    "removing the configured response body because responses with status code {} must not have a body",
+                    status
+                );
+            }
+
+            r.status = Some(status);
+            r.body = None;
+            r.body_file = None;
+            r.body_template = None;
+            r.json_body_merge = None;
+        });
+        self
+    }
+
+    /// Configures the HTTP response body that the mock server will return.
+    ///
+    /// # Parameters
+    /// - `body`: The content of the response body, provided as a type that can be referenced as a byte slice.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// Demonstrates setting a response body for a request to the path `/hello` with a 200 OK status.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200)
+    ///         .body("ohi!");
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = Client::new()
+    ///     .get(server.url("/hello"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Check that the mock was called as expected and the response body is as configured
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.text().unwrap(), "ohi!");
+    /// ```
+    pub fn body<SliceRef: AsRef<[u8]>>(mut self, body: SliceRef) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.body = Some(HttpMockBytes::from(Bytes::copy_from_slice(body.as_ref())));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Configures a binary HTTP response body that the mock server returns byte for byte.
+    ///
+    /// The bytes are stored as they are, without any conversion to a string, so this method is
+    /// well suited for images and other binary payloads. The `Content-Length` header is set to
+    /// the number of bytes. When the mock is saved as YAML (e.g., in a recording), bodies that
+    /// are not valid UTF-8 are stored base64 encoded in the `body_base64` field.
+    ///
+    /// # Parameters
+    /// - `body`: The content of the response body, such as a `Vec<u8>` or `bytes::Bytes`.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// // The first bytes of a PNG image, which are not valid UTF-8
+    /// let image = vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/logo.png");
+    ///     then.status(200)
+    ///         .header("content-type", "image/png")
+    ///         .body_bytes(image.clone());
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/logo.png")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.headers()["content-length"], "8");
+    /// assert_eq!(response.bytes().unwrap().to_vec(), image);
+    /// ```
+    pub fn body_bytes<IntoBytes: Into<Bytes>>(mut self, body: IntoBytes) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.body = Some(HttpMockBytes::from(body.into()));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a response body of `size` pseudo-random bytes that are generated from `seed`.
+    ///
+    /// The same seed and size always produce the same bytes, on every platform, which makes
+    /// this method useful for reproducible fuzz or property tests, e.g., to test how a client
+    /// handles checksums of downloaded content. The bytes are generated when the mock is
+    /// created, so this also works with remote mock servers. The `Content-Length` header is set
+    /// to `size`.
+    ///
+    /// # Parameters
+    /// - `size`: The number of bytes in the response body.
+    /// - `seed`: The seed of the random number generator.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/blob");
+    ///     then.status(200).random_body(1024, 42);
+    /// });
+    ///
+    /// let first = reqwest::blocking::get(server.url("/blob")).unwrap();
+    /// assert_eq!(first.headers()["content-length"], "1024");
+    /// let first = first.bytes().unwrap();
+    ///
+    /// let second = reqwest::blocking::get(server.url("/blob")).unwrap().bytes().unwrap();
+    ///
+    /// m.assert_calls(2);
+    /// assert_eq!(first.len(), 1024);
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn random_body(mut self, size: usize, seed: u64) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.body = Some(HttpMockBytes::from(Bytes::from(seeded_random_bytes(
+                size, seed,
+            ))));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Configures a response body template that is rendered for each request.
+    ///
+    /// Placeholders are written as `{{ name }}`. The following placeholders are supported:
+    /// - `{{ path.<name> }}`: The path parameter `<name>` captured by a path template
+    ///   (see [`When::path_template`](struct.When.html#method.path_template)).
+    /// - `{{ query.<name> }}`: The value of the first query parameter `<name>`.
+    /// - `{{ request.header.<name> }}`: The value of the first request header `<name>`. Header
+    ///   names are compared case-insensitively.
+    ///
+    /// Unknown placeholders are rendered as an empty string and a warning is logged.
+    /// A body template cannot be combined with [`body`](#method.body) or
+    /// [`body_from_file`](#method.body_from_file).
+    ///
+    /// # Parameters
+    /// - `template`: The response body template.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure a mock that echoes the requested user ID
+    /// let m = server.mock(|when, then| {
+    ///     when.path_template("/users/{id}");
+    ///     then.status(200)
+    ///         .header("content-type", "application/json")
+    ///         .body_template(r#"{"id": {{ path.id }}, "name": "User {{ path.id }}"}"#);
+    /// });
+    ///
+    /// // Send a request and verify the rendered response body
+    /// let response = reqwest::blocking::get(server.url("/users/7")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.text().unwrap(), r#"{"id": 7, "name": "User 7"}"#);
+    /// ```
+    pub fn body_template<IntoString: Into<String>>(mut self, template: IntoString) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.body_template = Some(template.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Configures the HTTP response body with content loaded from a specified file on the mock server.
+    ///
+    /// # Parameters
+    /// - `resource_file_path`: A string representing the path to the file whose contents will be used as the response body. The path can be absolute or relative to the server's running directory.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the specified file cannot be read, or if the path provided cannot be resolved to an absolute path.
+    ///
+    /// # Example
+    /// Demonstrates setting the response body from a file for a request to the path `/hello` with a 200 OK status.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/hello");
+    ///     then.status(200)
+    ///         .body_from_file("tests/resources/simple_body.txt");
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = Client::new()
+    ///     .get(server.url("/hello"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Check that the mock was called as expected and the response body matches the file contents
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.text().unwrap(), "ohi!");
+    /// ```
+    pub fn body_from_file<IntoString: Into<String>>(
+        mut self,
+        resource_file_path: IntoString,
+    ) -> Self {
+        let resource_file_path = resource_file_path.into();
+        let path = Path::new(&resource_file_path);
+        let absolute_path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
+                "Cannot create absolute path from string '{}'",
+                &resource_file_path
+            )),
+        };
+        let content = read_file(&absolute_path).expect(&format!(
+            "Cannot read from file {}",
+            absolute_path.to_str().expect("Invalid OS path")
+        ));
+        self.body(content)
+    }
+    // @docs-group: Body
+
+    /// Configures a response body template that is loaded from a file and rendered for each
+    /// request. See [`body_template`](#method.body_template) for the supported placeholders.
+    ///
+    /// The file is read and checked for malformed placeholders when the mock is created.
+    /// Placeholders that cannot be resolved for a request are rendered as an empty string and
+    /// a warning is logged.
+    ///
+    /// # Parameters
+    /// - `resource_file_path`: A string representing the path to the template file. The path can be absolute or relative to the server's running directory.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the specified file cannot be read, is not valid UTF-8 or contains a malformed
+    /// placeholder, or if the path provided cannot be resolved to an absolute path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // The template file contains placeholders for a path parameter, a query parameter
+    /// // and a request header
+    /// let m = server.mock(|when, then| {
+    ///     when.path_template("/users/{id}");
+    ///     then.status(200)
+    ///         .body_from_template_file("tests/resources/user_template.json");
+    /// });
+    ///
+    /// // Send a request and verify the rendered response body
+    /// let response = Client::new()
+    ///     .get(server.url("/users/7?q=admins"))
+    ///     .header("X-Tenant", "acme")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(
+    ///     response.text().unwrap().trim_end(),
+    ///     r#"{"id": "7", "query": "admins", "tenant": "acme"}"#
+    /// );
+    /// ```
+    pub fn body_from_template_file<IntoString: Into<String>>(
+        self,
+        resource_file_path: IntoString,
+    ) -> Self {
+        let resource_file_path = resource_file_path.into();
+        let path = Path::new(&resource_file_path);
+        let absolute_path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
+                "Cannot create absolute path from string '{}'",
+                &resource_file_path
+            )),
+        };
+        let absolute_path_str = absolute_path.to_str().expect("Invalid OS path");
+
+        let content = read_file(&absolute_path)
+            .expect(&format!("Cannot read from file {}", absolute_path_str));
+        let template = String::from_utf8(content).expect(&format!(
+            "Template file {} does not contain valid UTF-8",
+            absolute_path_str
+        ));
+        if let Err(err) = validate_template(&template) {
+            panic!("Invalid template file {}: {}", absolute_path_str, err);
+        }
+
+        self.body_template(template)
+    }
+    // @docs-group: Body
+
+    /// Configures the HTTP response body to be streamed from a specified file on the mock server.
+    ///
+    /// Unlike [`Then::body_from_file`], the file is not loaded into memory when the mock is created.
+    /// Instead, the mock server reads the file from disk in chunks while writing the response.
+    /// The `Content-Length` header is derived from the file metadata at the time the response is sent.
+    /// This is useful for large response fixtures, such as simulating big file downloads.
+    ///
+    /// The path refers to a file on the machine that runs the mock server. When connected to a
+    /// remote mock server (see [`MockServer::connect`](struct.MockServer.html#method.connect)),
+    /// use an absolute path that exists on the remote machine.
+    ///
+    /// Recordings only capture forwarded and proxied traffic, so streamed bodies are never part
+    /// of a recording. Saved or exported mock definitions keep the file path, not its contents.
+    ///
+    /// # Parameters
+    /// - `resource_file_path`: A string representing the path to the file that will be streamed as the response body. The path can be absolute or relative to the crate root directory.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the path provided cannot be resolved to an absolute path. Creating the mock panics
+    /// if the mock server cannot find a regular file at the specified path.
+    ///
+    /// # Example
+    /// Demonstrates streaming the response body from a file for a request to the path `/download`.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/download");
+    ///     then.status(200)
+    ///         .body_from_file_stream("tests/resources/simple_body.txt");
+    /// });
+    ///
+    /// // Send a request and verify the response
+    /// let response = Client::new()
+    ///     .get(server.url("/download"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Check that the mock was called as expected and the response body matches the file contents
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.content_length(), Some(4));
+    /// assert_eq!(response.text().unwrap(), "ohi!");
+    /// ```
+    pub fn body_from_file_stream<IntoString: Into<String>>(
+        mut self,
+        resource_file_path: IntoString,
+    ) -> Self {
+        let resource_file_path = resource_file_path.into();
+        let path = Path::new(&resource_file_path);
+        let absolute_path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
+                "Cannot create absolute path from string '{}'",
+                &resource_file_path
+            )),
+        };
+
+        let absolute_path_str = absolute_path.to_str().expect("Invalid OS path");
+
+        update_cell(&self.response_template, |r| {
+            r.body = None;
+            r.body_file = Some(absolute_path_str.to_string());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the JSON body for the HTTP response that will be returned by the mock server.
+    ///
+    /// This function accepts a JSON object that must be serializable and deserializable by serde.
+    /// Note that this method does not automatically set the "Content-Type" header to "application/json".
+    /// You will need to set this header manually if required.
+    ///
+    /// # Parameters
+    /// - `body`: The HTTP response body in the form of a `serde_json::Value` object.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// Demonstrates how to set a JSON body and a matching "Content-Type" header for a mock response.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use serde_json::{Value, json};
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let _ = env_logger::try_init();
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/user");
+    ///     then.status(200)
+    ///         .header("content-type", "application/json")
+    ///         .json_body(json!({ "name": "Hans" }));
+    /// });
+    ///
+    /// // Act
+    /// let response = Client::new()
+    ///     .get(server.url("/user"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Get the status code first
+    /// let status = response.status();
+    ///
+    /// // Extract the text from the response
+    /// let response_text = response.text().unwrap();
+    ///
+    /// // Deserialize the JSON response
+    /// let user: Value =
+    ///     serde_json::from_str(&response_text).expect("cannot deserialize JSON");
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(status, 200);
+    /// assert_eq!(user["name"], "Hans");
+    /// ```
+    pub fn json_body<V: Into<Value>>(mut self, body: V) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.body = Some(HttpMockBytes::from(Bytes::from(body.into().to_string())));
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets the JSON body that will be returned by the mock server using a serializable serde object.
+    ///
+    /// This method converts the provided object into a JSON string. It does not automatically set
+    /// the "Content-Type" header to "application/json", so you must set this header manually if it's
+    /// needed.
+    ///
+    /// # Parameters
+    /// - `body`: A reference to an object that implements the `serde::Serialize` trait.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the object cannot be serialized into a JSON string.
+    ///
+    /// # Example
+    /// Demonstrates setting a JSON body and the corresponding "Content-Type" header for a user object.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct TestUser {
+    ///     name: String,
+    /// }
+    ///
+    /// // Arrange
+    /// let _ = env_logger::try_init();
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/user");
+    ///     then.status(200)
+    ///         .header("content-type", "application/json")
+    ///         .json_body_obj(&TestUser {
+    ///             name: String::from("Hans"),
+    ///         });
+    /// });
+    ///
+    /// // Act
+    /// let response = Client::new()
+    ///     .get(server.url("/user"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Get the status code first
+    /// let status = response.status();
+    ///
+    /// // Extract the text from the response
+    /// let response_text = response.text().unwrap();
+    ///
+    /// // Deserialize the JSON response into a TestUser object
+    /// let user: TestUser =
+    ///     serde_json::from_str(&response_text).unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(status, 200);
+    /// assert_eq!(user.name, "Hans");
+    /// ```
+    pub fn json_body_obj<T: Serialize>(self, body: &T) -> Self {
+        let json_body =
+            serde_json::to_value(body).expect("Failed to serialize object to JSON string");
+        self.json_body(json_body)
+    }
+    // @docs-group: Body
+
+    /// Sets the JSON body for the HTTP response from a JSON file.
+    ///
+    /// The file is read and parsed when the mock is created, so a missing file or invalid JSON
+    /// is reported right away instead of when the first request arrives. A `Content-Type`
+    /// header with the value `application/json` is added, unless a `Content-Type` header has
+    /// already been set before calling this method.
+    ///
+    /// In YAML mock definitions, the same can be achieved with the `json_body_file` attribute.
+    /// Relative paths in YAML files are resolved against the working directory of the mock server.
+    ///
+    /// # Parameters
+    /// - `resource_file_path`: The path to the JSON file. Relative paths are resolved against the
+    ///   project directory (the directory containing `Cargo.toml`).
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Panics
+    /// Panics if the file cannot be read or does not contain valid JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use serde_json::Value;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/user");
+    ///     then.status(200).json_body_from_file("tests/resources/user.json");
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/user")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.headers()["content-type"], "application/json");
+    ///
+    /// let user: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+    /// assert_eq!(user["name"], "Fred");
+    /// ```
+    pub fn json_body_from_file<IntoString: Into<String>>(
+        mut self,
+        resource_file_path: IntoString,
+    ) -> Self {
+        let resource_file_path = resource_file_path.into();
+        let path = Path::new(&resource_file_path);
+        let absolute_path = match path.is_absolute() {
+            true => path.to_path_buf(),
+            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
+                "Cannot create absolute path from string '{}'",
+                &resource_file_path
+            )),
+        };
+
+        update_cell(&self.response_template, |r| {
+            if let Err(err) = r.set_json_body_from_file(&absolute_path) {
+                panic!("{}", err);
+            }
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a newline-delimited JSON (NDJSON) body for the HTTP response. Each value is
+    /// serialized on its own line, and every line, including the last one, is terminated by a
+    /// newline character as required by the NDJSON format. A `Content-Type` header with the
+    /// value `application/x-ndjson` is added, unless a `Content-Type` header has already been
+    /// set before calling this method.
+    ///
+    /// Use [`ndjson_without_trailing_newline`](#method.ndjson_without_trailing_newline) to
+    /// omit the newline after the last line, and [`stream_lines`](#method.stream_lines) to
+    /// send the values one by one with a delay in between.
+    ///
+    /// # Parameters
+    /// - `values`: The JSON values to send, one per line.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/events");
+    ///     then.status(200)
+    ///         .ndjson(vec![json!({ "id": 1 }), json!({ "id": 2 })]);
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/events")).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    /// assert_eq!(response.text().unwrap(), "{\"id\":1}\n{\"id\":2}\n");
+    /// ```
+    pub fn ndjson<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        update_cell(&self.response_template, |r| {
+            r.set_ndjson_body(values, true);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets a newline-delimited JSON (NDJSON) body like [`ndjson`](#method.ndjson), but
+    /// without a newline after the last line. This is useful to test how clients deal with
+    /// streams whose last line is not terminated.
+    ///
+    /// # Parameters
+    /// - `values`: The JSON values to send, one per line.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// server.mock(|when, then| {
+    ///     when.path("/events");
+    ///     then.status(200)
+    ///         .ndjson_without_trailing_newline(vec![json!(1), json!(2)]);
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/events")).unwrap();
+    /// assert_eq!(response.text().unwrap(), "1\n2");
+    /// ```
+    pub fn ndjson_without_trailing_newline<V: Into<Value>>(
+        self,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        update_cell(&self.response_template, |r| {
+            r.set_ndjson_body(values, false);
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Deep-merges a partial JSON object onto the JSON body set with [`Then::json_body`]
+    /// (or [`Then::json_body_obj`]).
+    ///
+    /// This is useful to share a base response body across tests and only override a few
+    /// attributes per test. The merge is applied by the mock server when the response is sent,
+    /// so it always operates on the final base body, regardless of the order of builder calls.
+    /// Objects are merged recursively, while all other values, including arrays, replace the
+    /// corresponding value of the base body wholesale. If this method is called multiple times,
+    /// the partial objects are merged in the order they were provided.
+    ///
+    /// # Parameters
+    /// - `partial`: The partial JSON object to merge onto the base body.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Creating the mock fails if no base body was set, or if the base body or `partial`
+    /// is not a JSON object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use serde_json::{json, Value};
+    /// use reqwest::blocking::Client;
+    ///
+    /// let base_user = json!({
+    ///     "name": "Peter Griffin",
+    ///     "address": { "town": "Quahog", "street": "31 Spooner Street" },
+    ///     "roles": ["father", "husband"]
+    /// });
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/user");
+    ///     then.status(200)
+    ///         .json_body_merge(json!({ "address": { "town": "Springfield" }, "roles": ["admin"] }))
+    ///         .json_body(base_user);
+    /// });
+    ///
+    /// let response = Client::new()
+    ///     .get(server.url("/user"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// let user: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+    ///
+    /// m.assert();
+    /// assert_eq!(user, json!({
+    ///     "name": "Peter Griffin",
+    ///     "address": { "town": "Springfield", "street": "31 Spooner Street" },
+    ///     "roles": ["admin"]
+    /// }));
+    /// ```
+    pub fn json_body_merge<V: Into<Value>>(mut self, partial: V) -> Self {
+        update_cell(&self.response_template, |r| {
+            if r.json_body_merge.is_none() {
+                r.json_body_merge = Some(Vec::new());
+            }
+            r.json_body_merge.as_mut().unwrap().push(partial.into());
+        });
+        self
+    }
+    // @docs-group: Body
+
+    /// Sets an HTTP header that the mock server will return in the response.
+    ///
+    /// This method configures a response header to be included when the mock server handles a request.
+    ///
+    /// # Parameters
+    /// - `name`: The name of the header to set.
+    /// - `value`: The value of the header.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// Demonstrates setting the "Expires" header for a response to a request to the root path.
+    ///
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let _ = env_logger::try_init();
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/");
+    ///     then.status(200)
+    ///         .header("Expires", "Wed, 21 Oct 2050 07:28:00 GMT");
+    /// });
+    ///
+    /// // Act
+    /// let response = Client::new()
+    ///     .get(server.url("/"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert
+    /// m.assert();
+    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(
+    ///     response.headers().get("Expires").unwrap().to_str().unwrap(),
+    ///     "Wed, 21 Oct 2050 07:28:00 GMT"
+    /// );
     /// ```
+    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
+        update_cell(&self.response_template, |r| {
+            if r.headers.is_none() {
+                r.headers = Some(Vec::new());
+            }
+            r.headers
+                .as_mut()
+                .unwrap()
+                .push((name.into(), value.into()));
+        });
+        self
+    }
+    // @docs-group: Headers
+
+    /// Adds a trailer (a trailing header) that is sent after the response body, e.g., the
+    /// `grpc-status` of a gRPC response.
+    ///
+    /// Trailers need a framing that can carry fields after the body: HTTP/2 or HTTP/1.1 chunked
+    /// transfer encoding. Responses with trailers are therefore always sent chunked over
+    /// HTTP/1.1 and announce the trailer names in a `Trailer` header. HTTP/1.1 clients must
+    /// accept trailers by sending a `TE: trailers` request header. If the request does not allow
+    /// trailers (e.g., HTTP/1.0 requests or HTTP/1.1 requests without `TE: trailers`), the
+    /// trailers are ignored and a warning is logged. Trailers are also not sent if a
+    /// `Content-Length` header is set explicitly with [`header`](#method.header).
+    ///
+    /// # Parameters
+    /// - `name`: The name of the trailer.
+    /// - `value`: The value of the trailer.
     ///
     /// # Returns
-    /// `When`: Returns the modified `When` object with the new key-value count requirement added to the
-    /// `application/x-www-form-urlencoded` expectations.
-    pub fn form_urlencoded_tuple_count<KeyRegex: Into<Regex>, ValueRegex: Into<Regex>>(
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let m = server.mock(|when, then| {
+    ///     when.path("/grpc");
+    ///     then.status(200)
+    ///         .body("payload")
+    ///         .trailer("grpc-status", "0");
+    /// });
+    ///
+    /// let mut stream = TcpStream::connect(server.address()).unwrap();
+    /// stream
+    ///     .write_all(b"GET /grpc HTTP/1.1\r\nHost: localhost\r\nTE: trailers\r\nConnection: close\r\n\r\n")
+    ///     .unwrap();
+    /// let mut response = String::new();
+    /// stream.read_to_string(&mut response).unwrap();
+    ///
+    /// m.assert();
+    /// assert!(response.ends_with("0\r\ngrpc-status: 0\r\n\r\n"));
+    /// ```
+    pub fn trailer<KeyString: Into<String>, ValueString: Into<String>>(
         mut self,
-        key_regex: KeyRegex,
-        value_regex: ValueRegex,
-        count: usize,
+        name: KeyString,
+        value: ValueString,
     ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.form_urlencoded_tuple_count.is_none() {
-                e.form_urlencoded_tuple_count = Some(Vec::new());
-            }
-            e.form_urlencoded_tuple_count.as_mut().unwrap().push((
-                key_regex.into(),
-                value_regex.into(),
-                count,
-            ));
+        update_cell(&self.response_template, |r| {
+            r.trailers
+                .get_or_insert_with(Vec::new)
+                .push((name.into(), value.into()));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Headers
+
+    /// Sets a delay for the mock server response.
+    ///
+    /// This method configures the server to wait for a specified duration before sending a response,
+    /// which can be useful for testing timeout scenarios or asynchronous operations.
+    ///
+    /// # Parameters
+    /// - `duration`: The length of the delay as a `std::time::Duration`.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the specified duration results in a delay that cannot be represented as a 64-bit
+    /// unsigned integer of milliseconds (more than approximately 584 million years).
+    ///
+    /// # Example
+    /// Demonstrates setting a 3-second delay for a request to the path `/delay`.
+    ///
+    /// ```rust
+    /// use std::time::{SystemTime, Duration};
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Arrange
+    /// let _ = env_logger::try_init();
+    /// let start_time = SystemTime::now();
+    /// let three_seconds = Duration::from_secs(3);
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/delay");
+    ///     then.status(200)
+    ///         .delay(three_seconds);
+    /// });
+    ///
+    /// // Act
+    /// let response = Client::new()
+    ///     .get(server.url("/delay"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// // Assert
+    /// mock.assert();
+    /// assert!(start_time.elapsed().unwrap() >= three_seconds);
+    /// ```
+    pub fn delay<D: Into<Duration>>(mut self, duration: D) -> Self {
+        let duration = duration.into();
+
+        // Ensure the delay duration does not exceed the maximum u64 milliseconds limit
+        let millis = duration.as_millis();
+        let max = u64::MAX as u128;
+        if millis >= max {
+            panic!("A delay higher than {} milliseconds is not supported.", max)
+        }
+
+        update_cell(&self.response_template, |r| {
+            r.delay = Some(duration.as_millis() as u64);
+        });
+        self
+    }
+    // @docs-group: Network
+
+    /// Configures the mock server to take the response delay in milliseconds from the specified
+    /// request header (e.g., `x-mock-delay-ms: 250`), so that a single mock can simulate
+    /// different latencies. This is useful for load testing harnesses that vary the latency per
+    /// request without reconfiguring mocks.
+    ///
+    /// If the request does not contain the header, or the header value is not a non-negative
+    /// whole number, the delay set with [`delay`](#method.delay) is used (no delay if none was
+    /// set).
+    ///
+    /// # Parameters
+    /// - `header_name`: The name of the request header that contains the delay in milliseconds.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::{Duration, Instant};
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/slow");
+    ///     then.status(200).delay_from_header("x-mock-delay-ms");
+    /// });
+    ///
+    /// let start = Instant::now();
+    /// let response = Client::new()
+    ///     .get(server.url("/slow"))
+    ///     .header("x-mock-delay-ms", "250")
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert_eq!(response.status(), 200);
+    /// assert!(start.elapsed() >= Duration::from_millis(250));
+    /// ```
+    pub fn delay_from_header<IntoString: Into<String>>(mut self, header_name: IntoString) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.delay_from_header = Some(header_name.into());
+        });
+        self
+    }
+    // @docs-group: Network
+
+    /// Holds back requests that match this mock until `count` of them are waiting at the same
+    /// time, and then answers all of them at once. This works like a barrier and makes it
+    /// possible to deterministically test how a client behaves when several of its requests
+    /// are in flight concurrently (e.g., to exercise race conditions).
+    ///
+    /// To make sure that a miscounted number of requests does not block a test forever, waiting
+    /// requests are released after a timeout (10 seconds by default, see
+    /// [`wait_for_concurrent_timeout`](#method.wait_for_concurrent_timeout)), and a warning is
+    /// logged. Requests that time out are answered normally. A configured
+    /// [`delay`](#method.delay) is applied after the requests have been released.
+    ///
+    /// # Parameters
+    /// - `count`: The number of requests that must be waiting at the same time. Must be greater than zero.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use std::thread;
+    ///
+    /// let server = MockServer::start();
+    ///
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/reserve");
+    ///     then.status(200).wait_for_concurrent(3);
+    /// });
+    ///
+    /// // None of the requests is answered before all three of them have arrived
+    /// let handles: Vec<_> = (0..3)
+    ///     .map(|_| {
+    ///         let url = server.url("/reserve");
+    ///         thread::spawn(move || reqwest::blocking::get(url).unwrap().status())
+    ///     })
+    ///     .collect();
+    ///
+    /// for handle in handles {
+    ///     assert_eq!(handle.join().unwrap(), 200);
+    /// }
+    ///
+    /// mock.assert_calls(3);
+    /// ```
+    pub fn wait_for_concurrent(mut self, count: usize) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.wait_for_concurrent = Some(count);
+        });
+        self
+    }
+    // @docs-group: Network
+
+    /// Sets how long a request waits for other concurrent requests before it is released
+    /// anyway (see [`wait_for_concurrent`](#method.wait_for_concurrent)). The default is
+    /// 10 seconds.
+    ///
+    /// # Parameters
+    /// - `timeout`: The maximum time a request waits for other concurrent requests.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Panics
+    /// Panics if the timeout cannot be represented as a 64-bit unsigned integer of milliseconds.
+    pub fn wait_for_concurrent_timeout<D: Into<Duration>>(mut self, timeout: D) -> Self {
+        let millis = timeout.into().as_millis();
+        let max = u64::MAX as u128;
+        if millis >= max {
+            panic!(
+                "A timeout higher than {} milliseconds is not supported.",
+                max
+            )
+        }
+
+        update_cell(&self.response_template, |r| {
+            r.wait_for_concurrent_timeout = Some(millis as u64);
+        });
+        self
+    }
+    // @docs-group: Network
+
+    /// Configures the mock server to trigger a network fault instead of sending a regular response.
+    ///
+    /// This is useful for testing how a client copes with unreliable connections, such as
+    /// connections that are dropped mid-response or reset by the peer. A fault cannot be combined
+    /// with a response body. If a delay is configured, the fault is triggered after the delay.
+    ///
+    /// # Parameters
+    /// - `fault`: The [`Fault`](crate::Fault) to trigger when the mock matches a request.
+    ///
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    ///
+    /// # Example
+    /// Demonstrates closing the connection without sending a response for a request to the path `/flaky`.
+    ///
+    /// ```rust
+    /// use httpmock::{prelude::*, Fault};
+    /// use reqwest::blocking::Client;
+    ///
+    /// // Initialize the mock server
+    /// let server = MockServer::start();
+    ///
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/flaky");
+    ///     then.fault(Fault::EmptyResponse);
+    /// });
+    ///
+    /// // Send a request and verify that the client observed a connection error
+    /// let result = Client::new()
+    ///     .get(server.url("/flaky"))
+    ///     .send();
+    ///
+    /// mock.assert();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn fault(mut self, fault: Fault) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.fault = Some(fault);
+        });
+        self
+    }
+    // @docs-group: Network
 
-    /// Adds a custom matcher for expected HTTP requests. If this function returns true, the request
-    /// is considered a match, and the mock server will respond to the request
-    /// (given all other criteria are also met).
+    /// Configures the mock server to send the response body using chunked transfer encoding.
     ///
-    /// You can use this function to create custom expectations for your mock server based on any aspect
-    /// of the `HttpMockRequest` object.
+    /// The body is split into chunks of at most `chunk_size` bytes and sent with a
+    /// `Transfer-Encoding: chunked` header instead of a `Content-Length` header. This is useful
+    /// for testing clients that need to handle streamed responses. This also applies to bodies
+    /// that are read from a file.
     ///
     /// # Parameters
-    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    /// - `chunk_size`: The maximum number of body bytes per chunk. Must be greater than zero,
+    ///   otherwise creating the mock fails.
     ///
-    /// ## Example
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
+    /// // Initialize the mock server
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.matches(|req: &HttpMockRequest| {
-    ///         req.uri().path().contains("es")
-    ///    });
-    ///    then.status(200);
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/stream");
+    ///     then.status(200)
+    ///         .body("hello world")
+    ///         .chunked(4);
     /// });
     ///
-    /// // Act: Send the HTTP request
-    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    /// // Send a request and verify the response
+    /// let response = Client::new()
+    ///     .get(server.url("/stream"))
+    ///     .send()
+    ///     .unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
+    /// mock.assert();
+    /// assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    /// assert!(response.headers().get("content-length").is_none());
+    /// assert_eq!(response.text().unwrap(), "hello world");
     /// ```
-    ///
-    /// # Returns
-    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
-    #[deprecated(
-        since = "0.8.0",
-        note = "Please use the `is_true` and `is_false` function instead"
-    )]
-    pub fn matches(
-        mut self,
-        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
-    ) -> Self {
-        return self.is_true(matcher);
+    pub fn chunked(mut self, chunk_size: usize) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.chunk_size = Some(chunk_size);
+        });
+        self
     }
-    // @docs-group: Custom
+    // @docs-group: Network
 
-    /// Adds a custom matcher for expected HTTP requests. If this function returns true, the request
-    /// is considered a match, and the mock server will respond to the request
-    /// (given all other criteria are also met).
+    /// Streams the response body line by line, waiting for the given delay before sending each
+    /// line except the first. Every line is sent as a separate chunk using chunked transfer
+    /// encoding, so clients receive the lines as they are "produced". This is useful to test
+    /// clients that consume streaming APIs, such as newline-delimited JSON (see
+    /// [`ndjson`](#method.ndjson)) or log streams.
     ///
-    /// You can use this function to create custom expectations for your mock server based on any aspect
-    /// of the `HttpMockRequest` object.
+    /// Lines are separated by newline characters, which remain part of the line they terminate.
+    /// This setting takes precedence over [`chunked`](#method.chunked) and only applies to
+    /// bodies that are not served from a file.
     ///
     /// # Parameters
-    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    /// - `delay`: The time to wait between two lines.
     ///
-    /// ## Example
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
+    /// use serde_json::json;
+    /// use std::time::{Duration, Instant};
     ///
-    /// // Arrange
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.is_true(|req: &HttpMockRequest| {
-    ///         req.uri().path().contains("es")
-    ///    });
-    ///    then.status(200);
+    /// server.mock(|when, then| {
+    ///     when.path("/events");
+    ///     then.status(200)
+    ///         .ndjson(vec![json!({ "id": 1 }), json!({ "id": 2 }), json!({ "id": 3 })])
+    ///         .stream_lines(Duration::from_millis(50));
     /// });
     ///
-    /// // Act: Send the HTTP request
-    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    /// let start = Instant::now();
+    /// let response = reqwest::blocking::get(server.url("/events")).unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    /// assert_eq!(response.text().unwrap().lines().count(), 3);
+    /// assert!(start.elapsed() >= Duration::from_millis(100));
     /// ```
-    ///
-    /// # Returns
-    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
-    pub fn is_true(
-        mut self,
-        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.is_true.is_none() {
-                e.is_true = Some(Vec::new());
-            }
-            e.is_true.as_mut().unwrap().push(Arc::new(matcher));
+    pub fn stream_lines<D: Into<Duration>>(mut self, delay: D) -> Self {
+        let delay = delay.into();
+        update_cell(&self.response_template, |r| {
+            r.line_delay = Some(delay.as_millis() as u64);
         });
         self
     }
-    // @docs-group: Custom
+    // @docs-group: Network
 
-    /// Adds a custom matcher for expected HTTP requests. If this function returns false, the request
-    /// is considered a match, and the mock server will respond to the request
-    /// (given all other criteria are also met).
+    /// Compresses the response body if the client advertises support for it in the
+    /// `Accept-Encoding` request header, like many production servers do. The mock server
+    /// supports Brotli (`br`) and `gzip` and picks the supported encoding with the highest
+    /// quality value (preferring `br` if both are accepted equally). It then sets the
+    /// `Content-Encoding` header, adjusts the `Content-Length` header to the compressed size
+    /// and adds `Vary: Accept-Encoding` to the response.
     ///
-    /// You can use this function to create custom expectations for your mock server based on any aspect
-    /// of the `HttpMockRequest` object.
+    /// If the client does not accept any supported encoding, the body is sent uncompressed.
+    /// The body is also left untouched if the mock sets a `Content-Encoding` header itself or
+    /// serves the body from a file.
+    ///
+    /// **Note:** This method is only available if the `compression` feature is enabled.
     ///
     /// # Parameters
-    /// - `matcher`: A function that takes a reference to an `HttpMockRequest` and returns a boolean indicating whether the request matches.
+    /// - `enabled`: Whether the response body should be compressed automatically.
     ///
-    /// ## Example
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
+    ///
+    /// # Example
     /// ```rust
     /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
     /// let server = MockServer::start();
     ///
-    /// let m = server.mock(|when, then| {
-    ///    when.is_false(|req: &HttpMockRequest| {
-    ///         req.uri().path().contains("es")
-    ///    });
-    ///    then.status(404);
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/report");
+    ///     then.status(200)
+    ///         .body("a large report ".repeat(100))
+    ///         .auto_compress(true);
     /// });
     ///
-    /// // Act: Send the HTTP request
-    /// let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    /// let client = Client::new();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 404);
-    /// ```
+    /// let compressed = client
+    ///     .get(server.url("/report"))
+    ///     .header("accept-encoding", "gzip")
+    ///     .send()
+    ///     .unwrap();
+    /// assert_eq!(compressed.headers()["content-encoding"], "gzip");
     ///
-    /// # Returns
-    /// `When`: Returns the modified `When` object with the new custom matcher added to the expectations.
-    pub fn is_false(
-        mut self,
-        matcher: impl Fn(&HttpMockRequest) -> bool + Sync + Send + 'static,
-    ) -> Self {
-        update_cell(&self.expectations, |e| {
-            if e.is_false.is_none() {
-                e.is_false = Some(Vec::new());
-            }
-            e.is_false.as_mut().unwrap().push(Arc::new(matcher));
+    /// let uncompressed = client.get(server.url("/report")).send().unwrap();
+    /// assert!(uncompressed.headers().get("content-encoding").is_none());
+    /// assert_eq!(uncompressed.text().unwrap(), "a large report ".repeat(100));
+    ///
+    /// mock.assert_calls(2);
+    /// ```
+    #[cfg(feature = "compression")]
+    pub fn auto_compress(mut self, enabled: bool) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.auto_compress = Some(enabled);
         });
         self
     }
-    // @docs-group: Custom
+    // @docs-group: Network
 
-    /// Applies a specified function to enhance or modify the `When` instance. This method allows for the
-    /// encapsulation of multiple matching conditions into a single function, maintaining a clear and fluent
-    /// interface for setting up HTTP request expectations.
+    /// Prevents the mock server from sending a header that it normally adds to responses
+    /// automatically. This is an escape hatch for testing how clients deal with responses
+    /// that lack these headers.
     ///
-    /// This method is particularly useful for reusing common setup patterns across multiple test scenarios,
-    /// promoting cleaner and more maintainable test code.
+    /// The supported headers are `Content-Length`, `Date` and `Server` (case-insensitive).
+    /// Creating the mock fails for any other header name. The header is also removed if it was
+    /// set explicitly, through default response headers, or by an upstream server the mock
+    /// proxies to.
+    ///
+    /// If `Content-Length` is omitted, HTTP/1.1 responses are sent using chunked transfer
+    /// encoding. Clients that do not support chunked encoding (e.g., HTTP/1.0 clients) can
+    /// only tell where the body ends when the server closes the connection, so they read until
+    /// the connection is closed. This is often exactly the behavior a test wants to provoke.
     ///
     /// # Parameters
-    /// - `func`: A function that takes a `When` instance and returns it after applying some conditions.
+    /// - `name`: The name of the header to omit.
     ///
-    /// ## Example
-    /// ```rust
-    /// use httpmock::{prelude::*, When};
-    /// use httpmock::Method::POST;
+    /// # Returns
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
-    /// // Function to apply a standard authorization and content type setup for JSON POST requests
-    /// fn is_authorized_json_post_request(when: When) -> When {
-    ///     when.method(POST)
-    ///         .header("Authorization", "SOME API KEY")
-    ///         .header("Content-Type", "application/json")
-    /// }
+    /// # Example
+    /// ```rust
+    /// use httpmock::prelude::*;
+    /// use reqwest::blocking::Client;
     ///
-    /// // Usage example demonstrating how to maintain fluent interface style with complex setups.
-    /// // This approach keeps the chain of conditions clear and readable, enhancing test legibility
+    /// // Initialize the mock server
     /// let server = MockServer::start();
-    /// let m = server.mock(|when, then| {
-    ///     when.query_param("user_id", "12345")
-    ///         .and(is_authorized_json_post_request) // apply the function to include common setup
-    ///         .json_body_includes(r#"{"key": "value"}"#); // additional specific condition
-    ///     then.status(200);
+    ///
+    /// // Configure the mock
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/legacy");
+    ///     then.status(200)
+    ///         .body("hello")
+    ///         .omit_header("Date")
+    ///         .omit_header("Content-Length");
     /// });
-    /// ```
     ///
-    /// # Returns
-    /// `When`: The modified `When` instance with additional conditions applied, suitable for further chaining.
-    pub fn and(mut self, func: impl FnOnce(When) -> When) -> Self {
-        func(self)
+    /// // Send a request and verify the response
+    /// let response = Client::new()
+    ///     .get(server.url("/legacy"))
+    ///     .send()
+    ///     .unwrap();
+    ///
+    /// mock.assert();
+    /// assert!(response.headers().get("date").is_none());
+    /// assert!(response.headers().get("content-length").is_none());
+    /// assert_eq!(response.text().unwrap(), "hello");
+    /// ```
+    pub fn omit_header<IntoString: Into<String>>(mut self, name: IntoString) -> Self {
+        let name = name.into();
+        update_cell(&self.response_template, |r| {
+            r.omit_headers.get_or_insert_with(Vec::new).push(name);
+        });
+        self
     }
-    // @docs-group: Miscellaneous
-}
-
-/// Represents the configuration of HTTP responses in a mock server environment.
-///
-/// The `Then` structure is used to define the details of the HTTP response that will be sent if
-/// an incoming request meets the conditions specified by a corresponding `When` structure. It
-/// allows for detailed customization of response aspects such as status codes, headers, body
-/// content, and delays. This structure is integral to defining how the mock server behaves when
-/// it receives a request that matches the defined expectations.
-pub struct Then {
-    pub(crate) response_template: Rc<Cell<MockServerHttpResponse>>,
-}
+    // @docs-group: Network
 
-impl Then {
-    /// Configures the HTTP response status code that the mock server will return.
+    /// Sets the entity tag of the response, which is sent in the `ETag` header. The value is
+    /// wrapped in double quotes unless it is quoted already, so both `"v1"` and `v1` result in
+    /// the header value `"v1"`. Weak entity tags can be set by passing a quoted value with the
+    /// `W/` prefix, such as `W/"v1"`.
+    ///
+    /// Use [`conditional`](#method.conditional) to respond with `304 Not Modified` when the
+    /// `If-None-Match` request header contains this entity tag.
     ///
     /// # Parameters
-    /// - `status`: A `u16` HTTP status code that the mock server should return for the configured request.
+    /// - `etag`: The entity tag of the response.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting a 200 OK status for a request to the path `/hello`.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
     ///
-    /// // Initialize the mock server
     /// let server = MockServer::start();
     ///
-    /// // Configure the mock
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/hello");
-    ///     then.status(200);
+    /// server.mock(|when, then| {
+    ///     when.path("/config");
+    ///     then.status(200).etag("v1").body("{}");
     /// });
     ///
-    /// // Send a request and verify the response
-    /// let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    /// let response = reqwest::blocking::get(server.url("/config")).unwrap();
     ///
-    /// // Check that the mock was called as expected and the response status is as configured
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
+    /// assert_eq!(response.headers()["etag"], "\"v1\"");
     /// ```
-    pub fn status<U16: TryInto<u16>>(mut self, status: U16) -> Self
-    where
-        <U16 as TryInto<u16>>::Error: std::fmt::Debug,
-    {
+    pub fn etag<IntoString: Into<String>>(mut self, etag: IntoString) -> Self {
+        let etag = quote_entity_tag(&etag.into());
         update_cell(&self.response_template, |r| {
-            r.status = Some(
-                status
-                    .try_into()
-                    .expect("cannot parse status code to usize"),
-            );
+            r.etag = Some(etag);
         });
         self
     }
-    // @docs-group: Status
+    // @docs-group: Headers
 
-    /// Configures the HTTP response body that the mock server will return.
+    /// Sets the modification date of the response, which is sent in the `Last-Modified` header
+    /// as an HTTP date (e.g., `Wed, 21 Oct 2015 07:28:00 GMT`).
+    ///
+    /// Use [`conditional`](#method.conditional) to respond with `304 Not Modified` when the
+    /// date in the `If-Modified-Since` request header is not older than this date.
     ///
     /// # Parameters
-    /// - `body`: The content of the response body, provided as a type that can be referenced as a byte slice.
+    /// - `time`: The modification date of the response. HTTP dates have a precision of one
+    ///   second, so fractions of a second are dropped.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting a response body for a request to the path `/hello` with a 200 OK status.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
+    /// use std::time::{Duration, UNIX_EPOCH};
     ///
-    /// // Initialize the mock server
     /// let server = MockServer::start();
     ///
-    /// // Configure the mock
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/hello");
+    /// server.mock(|when, then| {
+    ///     when.path("/config");
     ///     then.status(200)
-    ///         .body("ohi!");
+    ///         .last_modified(UNIX_EPOCH + Duration::from_secs(1445412480));
     /// });
     ///
-    /// // Send a request and verify the response
-    /// let response = Client::new()
-    ///     .get(server.url("/hello"))
-    ///     .send()
-    ///     .unwrap();
+    /// let response = reqwest::blocking::get(server.url("/config")).unwrap();
     ///
-    /// // Check that the mock was called as expected and the response body is as configured
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
-    /// assert_eq!(response.text().unwrap(), "ohi!");
+    /// assert_eq!(response.headers()["last-modified"], "Wed, 21 Oct 2015 07:28:00 GMT");
     /// ```
-    pub fn body<SliceRef: AsRef<[u8]>>(mut self, body: SliceRef) -> Self {
+    pub fn last_modified(mut self, time: SystemTime) -> Self {
         update_cell(&self.response_template, |r| {
-            r.body = Some(HttpMockBytes::from(Bytes::copy_from_slice(body.as_ref())));
+            r.last_modified = Some(httpdate::fmt_http_date(time));
         });
         self
     }
-    // @docs-group: Body
+    // @docs-group: Headers
 
-    /// Configures the HTTP response body with content loaded from a specified file on the mock server.
+    /// Lets the mock server evaluate the conditional request headers `If-None-Match` and
+    /// `If-Modified-Since` against the validators set with [`etag`](#method.etag) and
+    /// [`last_modified`](#method.last_modified). If the client's cached representation is still
+    /// valid, the mock server responds with `304 Not Modified` and an empty body instead of the
+    /// configured response.
+    ///
+    /// The validators are evaluated as described in
+    /// [RFC 7232, section 6](https://datatracker.ietf.org/doc/html/rfc7232#section-6):
+    /// - Only `GET` and `HEAD` requests are considered, and only if the configured status code
+    ///   is a success (`2xx`) status code.
+    /// - `If-None-Match` takes precedence. If it is present, the response is `304 Not Modified`
+    ///   if it contains the entity tag of the response (or `*`), using the weak comparison
+    ///   function. `If-Modified-Since` is ignored in this case.
+    /// - Otherwise, the response is `304 Not Modified` if the date in `If-Modified-Since` is
+    ///   not older than the modification date of the response.
+    ///
+    /// A `304 Not Modified` response contains the `ETag` and `Last-Modified` headers and only
+    /// those configured headers that RFC 7232 allows (`Cache-Control`, `Content-Location`,
+    /// `Date`, `Expires` and `Vary`).
     ///
     /// # Parameters
-    /// - `resource_file_path`: A string representing the path to the file whose contents will be used as the response body. The path can be absolute or relative to the server's running directory.
+    /// - `enabled`: Whether conditional requests should be evaluated.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
-    ///
-    /// # Panics
-    /// Panics if the specified file cannot be read, or if the path provided cannot be resolved to an absolute path.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting the response body from a file for a request to the path `/hello` with a 200 OK status.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
     ///
-    /// // Initialize the mock server
     /// let server = MockServer::start();
     ///
-    /// // Configure the mock
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/hello");
+    /// server.mock(|when, then| {
+    ///     when.path("/config");
     ///     then.status(200)
-    ///         .body_from_file("tests/resources/simple_body.txt");
+    ///         .etag("v1")
+    ///         .conditional(true)
+    ///         .body("{ \"feature\": true }");
     /// });
     ///
-    /// // Send a request and verify the response
     /// let response = Client::new()
-    ///     .get(server.url("/hello"))
+    ///     .get(server.url("/config"))
+    ///     .header("If-None-Match", "\"v1\"")
     ///     .send()
     ///     .unwrap();
     ///
-    /// // Check that the mock was called as expected and the response body matches the file contents
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
-    /// assert_eq!(response.text().unwrap(), "ohi!");
+    /// assert_eq!(response.status(), 304);
+    /// assert_eq!(response.text().unwrap(), "");
     /// ```
-    pub fn body_from_file<IntoString: Into<String>>(
-        mut self,
-        resource_file_path: IntoString,
-    ) -> Self {
-        let resource_file_path = resource_file_path.into();
-        let path = Path::new(&resource_file_path);
-        let absolute_path = match path.is_absolute() {
-            true => path.to_path_buf(),
-            false => get_test_resource_file_path(&resource_file_path).expect(&format!(
-                "Cannot create absolute path from string '{}'",
-                &resource_file_path
-            )),
-        };
-        let content = read_file(&absolute_path).expect(&format!(
-            "Cannot read from file {}",
-            absolute_path.to_str().expect("Invalid OS path")
-        ));
-        self.body(content)
+    pub fn conditional(mut self, enabled: bool) -> Self {
+        update_cell(&self.response_template, |r| {
+            r.conditional = Some(enabled);
+        });
+        self
     }
-    // @docs-group: Body
+    // @docs-group: Headers
 
-    /// Sets the JSON body for the HTTP response that will be returned by the mock server.
+    /// Configures the mock server to forward requests that match this mock to an upstream
+    /// server and to respond with the response of the upstream server.
     ///
-    /// This function accepts a JSON object that must be serializable and deserializable by serde.
-    /// Note that this method does not automatically set the "Content-Type" header to "application/json".
-    /// You will need to set this header manually if required.
+    /// Unlike [`MockServer::forward_to`](crate::MockServer::forward_to), which forwards all
+    /// requests that match a server-wide rule, only requests that match this mock are forwarded,
+    /// while other mocks keep serving their configured responses. The request is forwarded by
+    /// replacing the scheme and authority of the request URL with those of `base_url`. The mock
+    /// keeps track of forwarded requests, so it can be asserted as usual.
+    ///
+    /// A proxied response cannot be combined with a response body or a fault. Use
+    /// [`proxy_to_with`](#method.proxy_to_with) to change request headers or to configure
+    /// timeouts and retries.
+    ///
+    /// > Note: This function is only available when the `proxy` feature is enabled.
     ///
     /// # Parameters
-    /// - `body`: The HTTP response body in the form of a `serde_json::Value` object.
+    /// - `base_url`: The base URL of the upstream server, e.g. `http://localhost:8080`.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates how to set a JSON body and a matching "Content-Type" header for a mock response.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use serde_json::{Value, json};
     /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
-    /// let _ = env_logger::try_init();
-    /// let server = MockServer::start();
-    ///
-    /// // Configure the mock
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/user");
-    ///     then.status(200)
-    ///         .header("content-type", "application/json")
-    ///         .json_body(json!({ "name": "Hans" }));
+    /// // A server that simulates the real backend
+    /// let backend = MockServer::start();
+    /// backend.mock(|when, then| {
+    ///     when.path("/users/1");
+    ///     then.status(200).body("Hi from the backend!");
     /// });
     ///
-    /// // Act
-    /// let response = Client::new()
-    ///     .get(server.url("/user"))
-    ///     .send()
-    ///     .unwrap();
-    ///
-    /// // Get the status code first
-    /// let status = response.status();
-    ///
-    /// // Extract the text from the response
-    /// let response_text = response.text().unwrap();
+    /// // Only requests to /users/1 are forwarded to the backend
+    /// let server = MockServer::start();
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/users/1");
+    ///     then.proxy_to(backend.base_url());
+    /// });
     ///
-    /// // Deserialize the JSON response
-    /// let user: Value =
-    ///     serde_json::from_str(&response_text).expect("cannot deserialize JSON");
+    /// let response = Client::new().get(server.url("/users/1")).send().unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(status, 200);
-    /// assert_eq!(user["name"], "Hans");
+    /// mock.assert();
+    /// assert_eq!(response.text().unwrap(), "Hi from the backend!");
     /// ```
-    pub fn json_body<V: Into<Value>>(mut self, body: V) -> Self {
-        update_cell(&self.response_template, |r| {
-            r.body = Some(HttpMockBytes::from(Bytes::from(body.into().to_string())));
-        });
-        self
+    #[cfg(feature = "proxy")]
+    pub fn proxy_to<IntoString: Into<String>>(self, base_url: IntoString) -> Self {
+        self.proxy_to_with(base_url, |_| {})
     }
-    // @docs-group: Body
+    // @docs-group: Network
 
-    /// Sets the JSON body that will be returned by the mock server using a serializable serde object.
+    /// Configures the mock server to forward requests that match this mock to an upstream
+    /// server, like [`proxy_to`](#method.proxy_to), and allows to configure how requests are
+    /// forwarded using the same options as forwarding rules, such as request header changes,
+    /// upstream timeouts, retries and upstream proxies.
     ///
-    /// This method converts the provided object into a JSON string. It does not automatically set
-    /// the "Content-Type" header to "application/json", so you must set this header manually if it's
-    /// needed.
+    /// Filters configured on the rule are ignored, since the conditions of the mock decide
+    /// which requests are forwarded.
+    ///
+    /// > Note: This function is only available when the `proxy` feature is enabled.
     ///
     /// # Parameters
-    /// - `body`: A reference to an object that implements the `serde::Serialize` trait.
+    /// - `base_url`: The base URL of the upstream server, e.g. `http://localhost:8080`.
+    /// - `rule`: A closure that configures the forwarding using a
+    ///   [`ForwardingRuleBuilder`](crate::ForwardingRuleBuilder).
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
-    ///
-    /// # Panics
-    /// Panics if the object cannot be serialized into a JSON string.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting a JSON body and the corresponding "Content-Type" header for a user object.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
     /// use reqwest::blocking::Client;
-    /// use serde::{Serialize, Deserialize};
+    /// use std::time::Duration;
     ///
-    /// #[derive(Serialize, Deserialize)]
-    /// struct TestUser {
-    ///     name: String,
-    /// }
+    /// let backend = MockServer::start();
+    /// backend.mock(|when, then| {
+    ///     when.path("/users/1").header("authorization", "Bearer secret");
+    ///     then.status(200).body("Hi from the backend!");
+    /// });
     ///
-    /// // Arrange
-    /// let _ = env_logger::try_init();
     /// let server = MockServer::start();
-    ///
-    /// // Configure the mock
-    /// let m = server.mock(|when, then| {
-    ///     when.path("/user");
-    ///     then.status(200)
-    ///         .header("content-type", "application/json")
-    ///         .json_body_obj(&TestUser {
-    ///             name: String::from("Hans"),
-    ///         });
+    /// let mock = server.mock(|when, then| {
+    ///     when.path("/users/1");
+    ///     then.proxy_to_with(backend.base_url(), |rule| {
+    ///         rule.set_request_header("authorization", "Bearer secret")
+    ///             .upstream_timeout(Duration::from_secs(5));
+    ///     });
     /// });
     ///
-    /// // Act
-    /// let response = Client::new()
-    ///     .get(server.url("/user"))
-    ///     .send()
-    ///     .unwrap();
-    ///
-    /// // Get the status code first
-    /// let status = response.status();
-    ///
-    /// // Extract the text from the response
-    /// let response_text = response.text().unwrap();
-    ///
-    /// // Deserialize the JSON response into a TestUser object
-    /// let user: TestUser =
-    ///     serde_json::from_str(&response_text).unwrap();
+    /// let response = Client::new().get(server.url("/users/1")).send().unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(status, 200);
-    /// assert_eq!(user.name, "Hans");
+    /// mock.assert();
+    /// assert_eq!(response.text().unwrap(), "Hi from the backend!");
     /// ```
-    pub fn json_body_obj<T: Serialize>(self, body: &T) -> Self {
-        let json_body =
-            serde_json::to_value(body).expect("Failed to serialize object to JSON string");
-        self.json_body(json_body)
+    #[cfg(feature = "proxy")]
+    pub fn proxy_to_with<IntoString, ForwardingRuleBuilderFn>(
+        mut self,
+        base_url: IntoString,
+        rule: ForwardingRuleBuilderFn,
+    ) -> Self
+    where
+        IntoString: Into<String>,
+        ForwardingRuleBuilderFn: FnOnce(ForwardingRuleBuilder),
+    {
+        let config = Rc::new(Cell::new(ForwardingRuleConfig {
+            target_base_url: base_url.into(),
+            ..ForwardingRuleConfig::default()
+        }));
+
+        rule(ForwardingRuleBuilder {
+            config: config.clone(),
+        });
+
+        let target = ForwardingTarget::from(&config.take());
+        update_cell(&self.response_template, |r| {
+            r.proxy_to = Some(target);
+        });
+        self
     }
-    // @docs-group: Body
+    // @docs-group: Network
 
-    /// Sets an HTTP header that the mock server will return in the response.
+    /// Configures a response that replaces the regular response after the mock has been called
+    /// `calls` times. This allows simulating services that fail for a while and recover, e.g.,
+    /// to test retries or circuit breakers, without creating multiple mocks.
     ///
-    /// This method configures a response header to be included when the mock server handles a request.
+    /// Calls are counted per mock, starting at 1. The regular response is returned for calls
+    /// `1..=calls`, and the response configured by `response` is returned from call `calls + 1`
+    /// on. With `calls = 0`, the configured response is always returned. Calls are counted
+    /// while the mock server holds its state lock, so the boundary is exact even if many
+    /// requests arrive concurrently.
+    ///
+    /// The configured response replaces the regular response completely. It does not inherit
+    /// the status, headers or body of the regular response. This method can be called multiple
+    /// times to define several stages. For each call, the response with the highest threshold
+    /// that has been exceeded is used.
     ///
     /// # Parameters
-    /// - `name`: The name of the header to set.
-    /// - `value`: The value of the header.
+    /// - `calls`: The number of calls that are answered with the regular response.
+    /// - `response`: A function that configures the response for all subsequent calls.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting the "Expires" header for a response to a request to the root path.
-    ///
     /// ```rust
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
     ///
-    /// // Arrange
-    /// let _ = env_logger::try_init();
     /// let server = MockServer::start();
     ///
-    /// // Configure the mock
+    /// // The first two calls fail, all later calls succeed
     /// let m = server.mock(|when, then| {
-    ///     when.path("/");
-    ///     then.status(200)
-    ///         .header("Expires", "Wed, 21 Oct 2050 07:28:00 GMT");
+    ///     when.path("/flaky");
+    ///     then.status(503)
+    ///         .respond_after(2, |then| {
+    ///             then.status(200).body("recovered");
+    ///         });
     /// });
     ///
-    /// // Act
-    /// let response = Client::new()
-    ///     .get(server.url("/"))
-    ///     .send()
-    ///     .unwrap();
+    /// let get = || reqwest::blocking::get(server.url("/flaky")).unwrap();
     ///
-    /// // Assert
-    /// m.assert();
-    /// assert_eq!(response.status(), 200);
-    /// assert_eq!(
-    ///     response.headers().get("Expires").unwrap().to_str().unwrap(),
-    ///     "Wed, 21 Oct 2050 07:28:00 GMT"
-    /// );
+    /// assert_eq!(get().status(), 503);
+    /// assert_eq!(get().status(), 503);
+    /// assert_eq!(get().text().unwrap(), "recovered");
+    ///
+    /// m.assert_calls(3);
     /// ```
-    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
-        mut self,
-        name: KeyString,
-        value: ValueString,
-    ) -> Self {
+    pub fn respond_after(mut self, calls: usize, response: impl FnOnce(Then)) -> Self {
+        let response_template = Rc::new(Cell::new(MockServerHttpResponse::new()));
+
+        response(Then {
+            response_template: response_template.clone(),
+        });
+
         update_cell(&self.response_template, |r| {
-            if r.headers.is_none() {
-                r.headers = Some(Vec::new());
+            if r.respond_after.is_none() {
+                r.respond_after = Some(Vec::new());
             }
-            r.headers
-                .as_mut()
-                .unwrap()
-                .push((name.into(), value.into()));
+            r.respond_after.as_mut().unwrap().push(CallCountResponse {
+                calls,
+                response: response_template.take(),
+            });
         });
         self
     }
-    // @docs-group: Headers
+    // @docs-group: Miscellaneous
 
-    /// Sets a delay for the mock server response.
+    /// Configures an HTTP request that the mock server sends after it has responded, e.g., to
+    /// simulate a webhook that calls back into the application under test.
     ///
-    /// This method configures the server to wait for a specified duration before sending a response,
-    /// which can be useful for testing timeout scenarios or asynchronous operations.
+    /// The callback request is sent in the background, so it never delays the mocked response.
+    /// Use [`CallbackRequestBuilder::delay`](crate::CallbackRequestBuilder::delay) to control
+    /// how long the mock server waits before sending it. If the callback request fails, the
+    /// error is logged and the mock server keeps running. The method can be called multiple
+    /// times to send several callback requests.
+    ///
+    /// Callbacks are only supported by local mock servers, since a remote mock server usually
+    /// cannot reach the application under test.
     ///
     /// # Parameters
-    /// - `duration`: The length of the delay as a `std::time::Duration`.
+    /// - `callback`: A function that configures the callback request. It must at least set the URL.
     ///
     /// # Returns
-    /// Returns `self` to allow chaining of method calls on the `Mock` object.
-    ///
-    /// # Panics
-    /// Panics if the specified duration results in a delay that cannot be represented as a 64-bit
-    /// unsigned integer of milliseconds (more than approximately 584 million years).
+    /// Returns `self` to allow chaining of method calls on the `Then` object.
     ///
     /// # Example
-    /// Demonstrates setting a 3-second delay for a request to the path `/delay`.
-    ///
     /// ```rust
-    /// use std::time::{SystemTime, Duration};
     /// use httpmock::prelude::*;
-    /// use reqwest::blocking::Client;
+    /// use std::time::Duration;
     ///
-    /// // Arrange
-    /// let _ = env_logger::try_init();
-    /// let start_time = SystemTime::now();
-    /// let three_seconds = Duration::from_secs(3);
-    /// let server = MockServer::start();
+    /// // The application under test receives the webhook
+    /// let app = MockServer::start();
+    /// let webhook = app.mock(|when, then| {
+    ///     when.method(POST).path("/webhooks/payments").body(r#"{"status":"settled"}"#);
+    ///     then.status(204);
+    /// });
     ///
-    /// // Configure the mock
-    /// let mock = server.mock(|when, then| {
-    ///     when.path("/delay");
-    ///     then.status(200)
-    ///         .delay(three_seconds);
+    /// // The payment provider accepts the payment and calls the webhook afterwards
+    /// let provider = MockServer::start();
+    /// let webhook_url = app.url("/webhooks/payments");
+    /// provider.mock(|when, then| {
+    ///     when.method(POST).path("/payments");
+    ///     then.status(202).after_respond(|callback| {
+    ///         callback
+    ///             .method(POST)
+    ///             .url(webhook_url)
+    ///             .header("content-type", "application/json")
+    ///             .body(r#"{"status":"settled"}"#)
+    ///             .delay(Duration::from_millis(100));
+    ///     });
     /// });
     ///
-    /// // Act
-    /// let response = Client::new()
-    ///     .get(server.url("/delay"))
+    /// let response = reqwest::blocking::Client::new()
+    ///     .post(provider.url("/payments"))
     ///     .send()
     ///     .unwrap();
+    /// assert_eq!(response.status(), 202);
     ///
-    /// // Assert
-    /// mock.assert();
-    /// assert!(start_time.elapsed().unwrap() >= three_seconds);
+    /// std::thread::sleep(Duration::from_millis(500));
+    /// webhook.assert();
     /// ```
-    pub fn delay<D: Into<Duration>>(mut self, duration: D) -> Self {
-        let duration = duration.into();
+    ///
+    /// # Feature
+    /// This method is only available when the `proxy` feature is enabled.
+    #[cfg(feature = "proxy")]
+    pub fn after_respond(mut self, callback: impl FnOnce(CallbackRequestBuilder)) -> Self {
+        let request = Rc::new(Cell::new(CallbackRequest::default()));
 
-        // Ensure the delay duration does not exceed the maximum u64 milliseconds limit
-        let millis = duration.as_millis();
-        let max = u64::MAX as u128;
-        if millis >= max {
-            panic!("A delay higher than {} milliseconds is not supported.", max)
-        }
+        callback(CallbackRequestBuilder {
+            request: request.clone(),
+        });
 
         update_cell(&self.response_template, |r| {
-            r.delay = Some(duration.as_millis() as u64);
+            r.after_respond
+                .get_or_insert_with(Vec::new)
+                .push(request.take());
         });
         self
     }
-    // @docs-group: Network
+    // @docs-group: Miscellaneous
 
     /// Applies a custom function to modify a `Then` instance, enhancing flexibility and readability
     /// in setting up mock server responses.
@@ -3,23 +3,27 @@ extern crate serde_regex;
 use crate::{
     common::{
         data::Error::{
-            HeaderDeserializationError, RequestConversionError, StaticMockConversionError,
+            HeaderDeserializationError, RequestConversionError, StateSnapshotError,
+            StaticMockConversionError,
         },
-        util::HttpMockBytes,
+        util::{normalize_http_version, normalize_url, read_file, HttpMockBytes},
     },
-    server::matchers::generic::MatchingStrategy,
+    server::matchers::{comparison::path_template_to_regex, generic::MatchingStrategy},
 };
 use bytes::Bytes;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use std::{
+    borrow::Cow,
     cmp::Ordering,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::{TryFrom, TryInto},
     fmt,
     fmt::Debug,
+    path::Path,
     str::FromStr,
-    sync::Arc,
+    sync::{Arc, OnceLock},
+    time::SystemTime,
 };
 use url::Url;
 
@@ -41,6 +45,8 @@ pub enum Error {
     InvalidRequestData(String),
     #[error("Cannot convert request to/from internal structure: {0}")]
     RequestConversionError(String),
+    #[error("invalid state snapshot: {0}")]
+    StateSnapshotError(String),
 }
 
 /// A general abstraction of an HTTP request of `httpmock`.
@@ -52,6 +58,14 @@ pub struct HttpMockRequest {
     headers: Vec<(String, String)>,
     version: String,
     body: HttpMockBytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_cert: Option<ClientCertificate>,
+    /// The header names in the exact case the client sent them, in the same order as `headers`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_header_names: Option<Vec<String>>,
+    /// The request line and header lines exactly as the client sent them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_head: Option<RawRequestHead>,
 }
 
 impl HttpMockRequest {
@@ -78,9 +92,35 @@ impl HttpMockRequest {
             headers,
             version,
             body,
+            client_cert: None,
+            original_header_names: None,
+            raw_head: None,
         }
     }
 
+    /// Sets the header names as the client sent them on the wire. The names are ignored if
+    /// they do not correspond to the headers of the request.
+    pub(crate) fn with_original_header_names(mut self, names: Option<Vec<String>>) -> Self {
+        self.original_header_names =
+            names.and_then(|names| align_header_names(&self.headers, names));
+        self
+    }
+
+    /// Sets the request head as the client sent it on the wire. Like the original header names,
+    /// the head is ignored if its header lines do not correspond to the headers of the request.
+    pub(crate) fn with_raw_head(mut self, head: Option<RawRequestHead>) -> Self {
+        self.raw_head = head.filter(|head| {
+            let names = head.header_names();
+            align_header_names(&self.headers, names).is_some()
+        });
+        self
+    }
+
+    pub(crate) fn with_client_cert(mut self, client_cert: Option<ClientCertificate>) -> Self {
+        self.client_cert = client_cert;
+        self
+    }
+
     /// Parses and returns the URI of the request.
     ///
     /// # Attention
@@ -224,7 +264,7 @@ impl HttpMockRequest {
             let header_name = http::HeaderName::from_bytes(key.as_bytes()).unwrap();
             let header_value = http::HeaderValue::from_str(&value).unwrap();
 
-            header_map.insert(header_name, header_value);
+            header_map.append(header_name, header_value);
         }
 
         header_map
@@ -234,6 +274,55 @@ impl HttpMockRequest {
         self.headers.as_ref()
     }
 
+    /// Returns the request headers like [`headers_vec`](#method.headers_vec), but with the
+    /// header names in the exact case the client sent them (e.g., `X-Request-ID` instead of
+    /// `x-request-id`).
+    ///
+    /// The original case is only known for HTTP/1 requests that were received by the mock
+    /// server. HTTP/2 requires lowercase header names, so for other requests the names are
+    /// returned as they are stored in the request.
+    pub fn headers_vec_original_case(&self) -> Vec<(String, String)> {
+        match &self.original_header_names {
+            Some(names) => names
+                .iter()
+                .zip(self.headers.iter())
+                .map(|(name, (_, value))| (name.clone(), value.clone()))
+                .collect(),
+            None => self.headers.clone(),
+        }
+    }
+
+    /// Returns the query string of the request exactly as it was received, without decoding it
+    /// and without splitting it into parameters. Returns `None` if the request has no query string.
+    pub fn raw_query(&self) -> Option<&str> {
+        self.uri.split_once('?').map(|(_, query)| query)
+    }
+
+    /// Reconstructs the absolute URL of the request from its scheme, host, port, path and query
+    /// string. This is the URL a client requested, regardless of whether it sent the request in
+    /// absolute form (e.g., when using the mock server as a forward proxy) or only sent the path.
+    ///
+    /// The URL is normalized, so that the host is lowercase and the port is omitted if it is the
+    /// default port of the scheme (80 for `http`, 443 for `https`).
+    pub fn absolute_url(&self) -> String {
+        let scheme = self.scheme();
+        let port = self.port();
+        let is_default_port =
+            (scheme == "http" && port == 80) || (scheme == "https" && port == 443);
+
+        let mut url = format!("{}://{}", scheme, self.host().unwrap_or_default());
+        if !is_default_port {
+            url.push_str(&format!(":{}", port));
+        }
+        url.push_str(self.uri().path());
+        if let Some(query) = self.raw_query() {
+            url.push('?');
+            url.push_str(query);
+        }
+
+        normalize_url(&url).unwrap_or(url)
+    }
+
     pub fn query_params(&self) -> HashMap<String, String> {
         self.query_params_vec().into_iter().collect()
     }
@@ -257,6 +346,20 @@ impl HttpMockRequest {
         self.body.to_string()
     }
 
+    /// Returns the body as a string slice. Returns `None` if the body is not valid UTF-8.
+    /// An empty body is returned as an empty string.
+    pub fn body_as_str(&self) -> Option<Cow<'_, str>> {
+        std::str::from_utf8(self.body.as_ref())
+            .ok()
+            .map(Cow::Borrowed)
+    }
+
+    /// Parses the body as JSON. If the body is not valid JSON, the returned error describes
+    /// the problem and the line and column where parsing failed.
+    pub fn body_as_json(&self) -> Result<Value, serde_json::Error> {
+        serde_json::from_slice(self.body.as_ref())
+    }
+
     pub fn body_ref<'a>(&'a self) -> &'a [u8] {
         self.body.as_ref()
     }
@@ -287,6 +390,32 @@ impl HttpMockRequest {
         self.version.as_ref()
     }
 
+    /// Returns the client certificate details captured from the TLS connection the request was
+    /// received on.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the mock server was not configured to request client certificates or the request
+    /// was not sent over HTTPS. Otherwise, a `ClientCertificate` that holds the subject common
+    /// name of the certificate the client presented, if any.
+    pub fn client_cert(&self) -> Option<&ClientCertificate> {
+        self.client_cert.as_ref()
+    }
+
+    /// Returns the request as the client sent it on the wire, before the mock server normalized
+    /// it. In contrast to [`headers_vec`](#method.headers_vec), the header lines keep their
+    /// exact case, whitespace and order, including the order of repeated headers.
+    ///
+    /// The raw request is only known for HTTP/1 requests that were received by the mock server.
+    /// HTTP/2 has no textual request head, so `None` is returned for all other requests.
+    pub fn raw_request(&self) -> Option<RawRequest> {
+        self.raw_head.as_ref().map(|head| RawRequest {
+            start_line: head.start_line.clone(),
+            header_lines: head.header_lines.clone(),
+            body: self.body.to_bytes(),
+        })
+    }
+
     #[cfg(feature = "cookies")]
     pub(crate) fn cookies(&self) -> Result<Vec<(String, String)>, Error> {
         let mut result = Vec::new();
@@ -303,6 +432,134 @@ impl HttpMockRequest {
     pub fn to_http_request(&self) -> http::Request<Bytes> {
         self.try_into().unwrap()
     }
+
+    /// Creates a builder for a synthetic request. This is useful to test custom matchers
+    /// (e.g., predicates passed to [`When::is_true`](crate::When::is_true)) without starting
+    /// a mock server.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::{HttpMockRequest, Method};
+    ///
+    /// let is_admin_request = |req: &HttpMockRequest| req.uri().path().starts_with("/admin");
+    ///
+    /// let req = HttpMockRequest::builder()
+    ///     .method(Method::POST)
+    ///     .path("/admin/users")
+    ///     .query_param("dry_run", "true")
+    ///     .header("content-type", "application/json")
+    ///     .body(r#"{ "name": "alice" }"#)
+    ///     .build();
+    ///
+    /// assert!(is_admin_request(&req));
+    /// assert_eq!(req.uri_str(), "/admin/users?dry_run=true");
+    /// ```
+    pub fn builder() -> HttpMockRequestBuilder {
+        HttpMockRequestBuilder::new()
+    }
+}
+
+/// Builds an [`HttpMockRequest`] without sending it to a mock server (see
+/// [`HttpMockRequest::builder`]). Unless set otherwise, the request is an HTTP/1.1 `GET`
+/// request to `/` with the `http` scheme, no headers and an empty body.
+pub struct HttpMockRequestBuilder {
+    scheme: String,
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    version: String,
+    body: Bytes,
+}
+
+impl HttpMockRequestBuilder {
+    fn new() -> Self {
+        Self {
+            scheme: "http".to_string(),
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            query: Vec::new(),
+            headers: Vec::new(),
+            version: "HTTP/1.1".to_string(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// Sets the scheme of the request, such as `http` or `https`.
+    pub fn scheme<IntoString: Into<String>>(mut self, scheme: IntoString) -> Self {
+        self.scheme = scheme.into();
+        self
+    }
+
+    /// Sets the HTTP method of the request.
+    pub fn method<TryIntoMethod: TryInto<Method>>(mut self, method: TryIntoMethod) -> Self
+    where
+        <TryIntoMethod as TryInto<Method>>::Error: std::fmt::Debug,
+    {
+        let method = method
+            .try_into()
+            .expect("cannot convert method into httpmock::Method");
+        self.method = method.to_string();
+        self
+    }
+
+    /// Sets the path of the request, such as `/users/1`.
+    pub fn path<IntoString: Into<String>>(mut self, path: IntoString) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Adds a query parameter to the request. Name and value are URL-encoded.
+    pub fn query_param<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Adds a header to the request. To set the host of the request, add a `Host` header.
+    pub fn header<KeyString: Into<String>, ValueString: Into<String>>(
+        mut self,
+        name: KeyString,
+        value: ValueString,
+    ) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body of the request.
+    pub fn body<SliceRef: AsRef<[u8]>>(mut self, body: SliceRef) -> Self {
+        self.body = Bytes::copy_from_slice(body.as_ref());
+        self
+    }
+
+    /// Builds the request.
+    ///
+    /// # Panics
+    /// This method panics if the path and query parameters do not form a valid URI.
+    pub fn build(self) -> HttpMockRequest {
+        let mut uri = self.path;
+        if !self.query.is_empty() {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.query)
+                .finish();
+            uri = format!("{}?{}", uri, query);
+        }
+
+        uri.parse::<http::Uri>()
+            .unwrap_or_else(|err| panic!("cannot build request with URI '{}': {}", uri, err));
+
+        HttpMockRequest::new(
+            self.scheme,
+            uri,
+            self.method,
+            self.headers,
+            self.version,
+            HttpMockBytes::from(self.body),
+        )
+    }
 }
 
 fn headers_to_vec(parts: &http::request::Parts) -> Vec<(String, String)> {
@@ -318,6 +575,24 @@ fn headers_to_vec(parts: &http::request::Parts) -> Vec<(String, String)> {
         .collect()
 }
 
+/// Orders header names as received on the wire like the given headers. Returns `None` if the
+/// names do not correspond to the headers.
+fn align_header_names(headers: &[(String, String)], mut names: Vec<String>) -> Option<Vec<String>> {
+    if names.len() != headers.len() {
+        return None;
+    }
+
+    headers
+        .iter()
+        .map(|(header_name, _)| {
+            let index = names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(header_name))?;
+            Some(names.remove(index))
+        })
+        .collect()
+}
+
 fn http_headers_to_vec<T>(req: &http::Request<T>) -> Result<Vec<(String, String)>, Error> {
     req.headers()
         .iter()
@@ -340,8 +615,8 @@ impl TryInto<http::Request<Bytes>> for &HttpMockRequest {
             .uri(self.uri())
             .version(self.version());
 
-        for (k, v) in self.headers() {
-            builder = builder.header(k.map_or(String::new(), |v| v.to_string()), v)
+        for (k, v) in self.headers_vec() {
+            builder = builder.header(k, v)
         }
 
         let req = builder
@@ -373,7 +648,55 @@ impl TryFrom<&http::Request<Bytes>> for HttpMockRequest {
             headers,
             format!("{:?}", value.version()),
             body,
-        ))
+        )
+        .with_client_cert(metadata.client_cert.clone())
+        .with_original_header_names(metadata.original_header_names.clone())
+        .with_raw_head(metadata.raw_head.clone()))
+    }
+}
+
+/// The request line and header lines of an HTTP/1 request head, without line terminators.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawRequestHead {
+    pub(crate) start_line: String,
+    pub(crate) header_lines: Vec<String>,
+}
+
+impl RawRequestHead {
+    /// Returns the header names in the exact case and order the client sent them.
+    pub(crate) fn header_names(&self) -> Vec<String> {
+        self.header_lines
+            .iter()
+            .map(|line| line.split(':').next().unwrap_or_default().to_string())
+            .collect()
+    }
+}
+
+/// A request exactly as the client sent it, before the mock server normalized it (see
+/// [`HttpMockRequest::raw_request`]). This is useful to verify signatures that are calculated
+/// over the canonical form of a request, such as HMAC request signing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRequest {
+    /// The request line, such as `POST /orders?id=1 HTTP/1.1`.
+    pub start_line: String,
+    /// The header lines, such as `X-Signature: abc`, in the order the client sent them.
+    pub header_lines: Vec<String>,
+    /// The request body. If the client used chunked transfer encoding, this is the body after
+    /// removing the chunk framing.
+    pub body: Bytes,
+}
+
+/// The client certificate details captured from a mutual TLS connection.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    /// The common name (CN) of the certificate subject or `None` if the client did not present
+    /// a certificate (or the certificate subject has no common name).
+    pub common_name: Option<String>,
+}
+
+impl fmt::Display for ClientCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.common_name.as_deref().unwrap_or(""))
     }
 }
 
@@ -381,21 +704,183 @@ impl TryFrom<&http::Request<Bytes>> for HttpMockRequest {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MockServerHttpResponse {
     pub status: Option<u16>,
+    #[serde(default)]
+    pub status_from_header: Option<String>,
+    /// A reason phrase that replaces the standard one in the HTTP/1 status line.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Whether to compress the body with an encoding from the `Accept-Encoding` request header.
+    #[serde(default)]
+    pub auto_compress: Option<bool>,
+    /// The delay in milliseconds between the lines of a body that is streamed line by line.
+    #[serde(default)]
+    pub line_delay: Option<u64>,
+    /// The entity tag that is sent in the `ETag` header (including quotes).
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// The modification date that is sent in the `Last-Modified` header, as an HTTP date.
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    /// Whether to respond with `304 Not Modified` if the request's validators match.
+    #[serde(default)]
+    pub conditional: Option<bool>,
     pub headers: Option<Vec<(String, String)>>,
+    /// Trailing headers that are sent after the body if the response framing allows it.
+    #[serde(default)]
+    pub trailers: Option<Vec<(String, String)>>,
     #[serde(default, with = "opt_vector_serde_base64")]
     pub body: Option<HttpMockBytes>,
+    pub body_file: Option<String>,
+    #[serde(default)]
+    pub json_body_merge: Option<Vec<Value>>,
+    /// A response body template whose placeholders are rendered for each request.
+    #[serde(default)]
+    pub body_template: Option<String>,
+    pub delay: Option<u64>,
+    /// The name of a request header that contains the response delay in milliseconds.
+    #[serde(default)]
+    pub delay_from_header: Option<String>,
+    pub fault: Option<Fault>,
+    #[serde(default)]
+    pub chunk_size: Option<usize>,
+    #[serde(default)]
+    pub proxy_to: Option<ForwardingTarget>,
+    /// The upstream round-trip duration in milliseconds that was observed when this response
+    /// was recorded. It does not delay the response unless the recording is played back
+    /// with timing.
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Responses that replace this response once the mock has been called more often than
+    /// their call count threshold.
+    #[serde(default)]
+    pub respond_after: Option<Vec<CallCountResponse>>,
+    /// Requests that the mock server sends in the background after it has responded.
+    #[serde(default)]
+    pub after_respond: Option<Vec<CallbackRequest>>,
+    /// The number of requests to this mock that must be waiting at the same time before all
+    /// of them are answered.
+    #[serde(default)]
+    pub wait_for_concurrent: Option<usize>,
+    /// The maximum time in milliseconds a request waits for other concurrent requests.
+    #[serde(default)]
+    pub wait_for_concurrent_timeout: Option<u64>,
+    /// Names of headers that the mock server adds automatically (`Content-Length`, `Date` and
+    /// `Server`) but must not send with this response.
+    #[serde(default)]
+    pub omit_headers: Option<Vec<String>>,
+}
+
+/// An HTTP request that the mock server sends after it has responded to a request,
+/// e.g., to simulate a webhook (see `Then::after_respond`).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CallbackRequest {
+    pub method: Option<String>,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default, with = "opt_vector_serde_base64")]
+    pub body: Option<HttpMockBytes>,
+    /// The time in milliseconds to wait after the response before sending the request.
+    #[serde(default)]
     pub delay: Option<u64>,
 }
 
+/// A response that replaces the regular mock response for all calls after the first `calls`
+/// calls of a mock (see `Then::respond_after`).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CallCountResponse {
+    pub calls: usize,
+    pub response: MockServerHttpResponse,
+}
+
 impl MockServerHttpResponse {
     pub fn new() -> Self {
         Self {
             status: None,
+            status_from_header: None,
+            reason: None,
+            auto_compress: None,
+            line_delay: None,
+            etag: None,
+            last_modified: None,
+            conditional: None,
             headers: None,
+            trailers: None,
             body: None,
+            body_file: None,
+            json_body_merge: None,
+            body_template: None,
             delay: None,
+            delay_from_header: None,
+            fault: None,
+            chunk_size: None,
+            proxy_to: None,
+            duration_ms: None,
+            respond_after: None,
+            after_respond: None,
+            wait_for_concurrent: None,
+            wait_for_concurrent_timeout: None,
+            omit_headers: None,
+        }
+    }
+
+    /// Reads a JSON document from a file and uses it as the response body. A `Content-Type:
+    /// application/json` header is added, unless a `Content-Type` header has already been set.
+    pub(crate) fn set_json_body_from_file(&mut self, path: &Path) -> Result<(), String> {
+        let content = read_file(path)
+            .map_err(|err| format!("cannot read JSON file {}: {}", path.display(), err))?;
+        let json: Value = serde_json::from_slice(&content)
+            .map_err(|err| format!("file {} is not valid JSON: {}", path.display(), err))?;
+
+        self.body = Some(HttpMockBytes::from(Bytes::from(json.to_string())));
+
+        let headers = self.headers.get_or_insert_with(Vec::new);
+        if !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push(("content-type".to_string(), "application/json".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Sets a newline-delimited JSON (NDJSON) body with one serialized value per line. A
+    /// `Content-Type` header with the value `application/x-ndjson` is added, unless a
+    /// `Content-Type` header has already been set.
+    pub(crate) fn set_ndjson_body(&mut self, values: Vec<Value>, trailing_newline: bool) {
+        let mut body = values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if trailing_newline && !values.is_empty() {
+            body.push('\n');
+        }
+
+        self.body = Some(HttpMockBytes::from(Bytes::from(body)));
+
+        let headers = self.headers.get_or_insert_with(Vec::new);
+        if !headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        {
+            headers.push((
+                "content-type".to_string(),
+                "application/x-ndjson".to_string(),
+            ));
         }
     }
+
+    /// Turns the response into a `429 Too Many Requests` response with the given `Retry-After`
+    /// header value. A previously set `Retry-After` header is replaced.
+    pub(crate) fn set_rate_limited(&mut self, retry_after: String) {
+        self.status = Some(429);
+
+        let headers = self.headers.get_or_insert_with(Vec::new);
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("retry-after"));
+        headers.push(("retry-after".to_string(), retry_after));
+    }
 }
 
 impl Default for MockServerHttpResponse {
@@ -420,17 +905,38 @@ impl TryFrom<&http::Response<Bytes>> for MockServerHttpResponse {
 
         Ok(Self {
             status: Some(value.status().as_u16()),
+            status_from_header: None,
+            reason: None,
+            auto_compress: None,
+            line_delay: None,
+            etag: None,
+            last_modified: None,
+            conditional: None,
             headers: if !headers.is_empty() {
                 Some(headers)
             } else {
                 None
             },
+            trailers: None,
             body: if !value.body().is_empty() {
                 Some(HttpMockBytes::from(value.body().clone()))
             } else {
                 None
             },
+            body_file: None,
+            json_body_merge: None,
+            body_template: None,
             delay: None,
+            delay_from_header: None,
+            fault: None,
+            chunk_size: None,
+            proxy_to: None,
+            duration_ms: None,
+            respond_after: None,
+            after_respond: None,
+            wait_for_concurrent: None,
+            wait_for_concurrent_timeout: None,
+            omit_headers: None,
         })
     }
 }
@@ -483,7 +989,9 @@ impl fmt::Debug for MockServerHttpResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MockServerHttpResponse")
             .field("status", &self.status)
+            .field("status_from_header", &self.status_from_header)
             .field("headers", &self.headers)
+            .field("trailers", &self.trailers)
             .field(
                 "body",
                 &self
@@ -491,11 +999,45 @@ impl fmt::Debug for MockServerHttpResponse {
                     .as_ref()
                     .map(|x| String::from_utf8_lossy(x.as_ref()).to_string()),
             )
+            .field("body_file", &self.body_file)
+            .field("json_body_merge", &self.json_body_merge)
+            .field("body_template", &self.body_template)
             .field("delay", &self.delay)
+            .field("delay_from_header", &self.delay_from_header)
+            .field("fault", &self.fault)
+            .field("chunk_size", &self.chunk_size)
+            .field("proxy_to", &self.proxy_to)
+            .field("duration_ms", &self.duration_ms)
+            .field("respond_after", &self.respond_after)
+            .field("after_respond", &self.after_respond)
+            .field("wait_for_concurrent", &self.wait_for_concurrent)
+            .field(
+                "wait_for_concurrent_timeout",
+                &self.wait_for_concurrent_timeout,
+            )
+            .field("omit_headers", &self.omit_headers)
+            .field("reason", &self.reason)
+            .field("auto_compress", &self.auto_compress)
+            .field("line_delay", &self.line_delay)
+            .field("etag", &self.etag)
+            .field("last_modified", &self.last_modified)
+            .field("conditional", &self.conditional)
             .finish()
     }
 }
 
+/// Represents a network fault that the mock server triggers instead of sending a regular response.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Sends the response status line and headers, then closes the connection before any
+    /// body data is sent.
+    CloseConnectionAfterHeaders,
+    /// Resets the connection (sends a TCP RST where the platform allows) without sending a response.
+    ResetConnection,
+    /// Closes the connection without sending any response data.
+    EmptyResponse,
+}
+
 /// A general abstraction of an HTTP request for all handlers.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct HttpMockRegex(#[serde(with = "serde_regex")] pub regex::Regex);
@@ -545,6 +1087,284 @@ impl fmt::Display for HttpMockRegex {
     }
 }
 
+/// A JSON schema that request bodies are validated against (see `When::json_schema`). The
+/// compiled validator is kept with the schema, so that the server compiles each schema only
+/// once when the mock is added instead of for every request it is matched against.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct JsonSchema {
+    pub schema: Value,
+    #[cfg(feature = "json-schema")]
+    #[serde(skip)]
+    validator: OnceLock<Result<Arc<jsonschema::Validator>, String>>,
+}
+
+impl JsonSchema {
+    /// Returns the compiled validator of the schema. The schema is compiled on the first call
+    /// and the result is reused by all later calls.
+    #[cfg(feature = "json-schema")]
+    pub fn validator(&self) -> Result<&jsonschema::Validator, String> {
+        self.validator
+            .get_or_init(|| {
+                jsonschema::validator_for(&self.schema)
+                    .map(Arc::new)
+                    .map_err(|err| format!("invalid JSON schema: {}", err))
+            })
+            .as_ref()
+            .map(|validator| validator.as_ref())
+            .map_err(|err| err.clone())
+    }
+}
+
+impl From<Value> for JsonSchema {
+    fn from(schema: Value) -> Self {
+        JsonSchema {
+            schema,
+            #[cfg(feature = "json-schema")]
+            validator: OnceLock::new(),
+        }
+    }
+}
+
+impl fmt::Display for JsonSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.schema)
+    }
+}
+
+/// A path template, such as `/users/{id}` (see `When::path_template`). The regex that the
+/// template is converted to is kept with the template, so that the server builds it only once
+/// when the mock is added instead of for every request it is matched against.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct PathTemplate {
+    pub template: String,
+    #[serde(skip)]
+    regex: OnceLock<Result<HttpMockRegex, String>>,
+}
+
+impl PathTemplate {
+    /// Returns the regex of the template. The regex is built on the first call and reused by
+    /// all later calls.
+    pub fn regex(&self) -> Result<&HttpMockRegex, String> {
+        self.regex
+            .get_or_init(|| path_template_to_regex(&self.template))
+            .as_ref()
+            .map_err(|err| err.clone())
+    }
+}
+
+impl From<String> for PathTemplate {
+    fn from(template: String) -> Self {
+        PathTemplate {
+            template,
+            regex: OnceLock::new(),
+        }
+    }
+}
+
+impl From<&str> for PathTemplate {
+    fn from(template: &str) -> Self {
+        PathTemplate::from(template.to_string())
+    }
+}
+
+impl fmt::Display for PathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+/// A list of values of which a request value must equal at least one (see `When::method_in`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct AnyOfValues(pub Vec<String>);
+
+impl fmt::Display for AnyOfValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+/// A list of regular expressions of which at least one must match a request value
+/// (see `When::body_matches_any`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct AnyOfPatterns(pub Vec<HttpMockRegex>);
+
+impl fmt::Display for AnyOfPatterns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let patterns: Vec<String> = self.0.iter().map(|p| p.to_string()).collect();
+        write!(f, "[{}]", patterns.join(", "))
+    }
+}
+
+/// Elements that the JSON array at a location in the request body must contain
+/// (see `When::json_body_includes_array`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct JsonArrayElements {
+    /// A JSON pointer (RFC 6901) to the array.
+    pub pointer: String,
+    pub elements: Vec<Value>,
+}
+
+impl fmt::Display for JsonArrayElements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "array at '{}' includes {}",
+            self.pointer,
+            Value::Array(self.elements.clone())
+        )
+    }
+}
+
+/// A field of a protobuf encoded request body that must have a specific value
+/// (see `When::protobuf_field`).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProtobufFieldExpectation {
+    /// The encoded `FileDescriptorSet` that contains the message type.
+    pub file_descriptor_set: HttpMockBytes,
+    /// The fully qualified name of the message type (e.g., `shop.v1.Order`).
+    pub message_name: String,
+    /// The dot-separated path to the field (e.g., `customer.name`).
+    pub field_path: String,
+    pub value: Value,
+}
+
+impl fmt::Display for ProtobufFieldExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{} = {}",
+            self.message_name, self.field_path, self.value
+        )
+    }
+}
+
+/// A body format that a request body can be validated against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyFormat {
+    Json,
+    Xml,
+    FormUrlencoded,
+}
+
+impl BodyFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            BodyFormat::Json => "JSON",
+            BodyFormat::Xml => "XML",
+            BodyFormat::FormUrlencoded => "form-urlencoded data",
+        }
+    }
+}
+
+impl fmt::Display for BodyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "valid {}", self.name())
+    }
+}
+
+/// The result of validating a request body against a [`BodyFormat`]. If the body could not
+/// be parsed, `error` contains the parser error (including the position where parsing failed).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BodyFormatValidation {
+    pub format: BodyFormat,
+    pub error: Option<String>,
+}
+
+impl fmt::Display for BodyFormatValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            None => write!(f, "valid {}", self.format.name()),
+            Some(err) => write!(f, "invalid {} ({})", self.format.name(), err),
+        }
+    }
+}
+
+/// A request body after the content codings listed in the `Content-Encoding` request header
+/// were removed.
+#[derive(Debug, Clone)]
+pub struct DecodedBody {
+    /// The value of the `Content-Encoding` header or `identity` if the header is missing.
+    pub encoding: String,
+    /// The decoded body or the reason why the body could not be decoded.
+    pub body: Result<HttpMockBytes, String>,
+}
+
+impl fmt::Display for DecodedBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.body {
+            Ok(body) => write!(f, "{}", body),
+            Err(_) => write!(f, "<{} encoded body>", self.encoding),
+        }
+    }
+}
+
+/// A reference to a matcher function that is registered on the server under a name,
+/// together with the arguments that are passed to it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NamedMatcherCall {
+    pub name: String,
+    pub args: Value,
+}
+
+/// An inclusive range of request body sizes in bytes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BodySizeRange {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl BodySizeRange {
+    pub fn contains(&self, size: usize) -> bool {
+        self.min <= size && size <= self.max
+    }
+}
+
+/// The host and port that a request was sent to. If the request does not specify a port
+/// explicitly, `port` is the default port of the request scheme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestAuthority {
+    pub host: String,
+    pub port: u16,
+    /// The default port of the request scheme (80 for `http`, 443 for `https`).
+    pub default_port: u16,
+}
+
+impl fmt::Display for RequestAuthority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+/// Whether a request carries a body. An empty body counts as no body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyPresence {
+    Present,
+    Absent,
+}
+
+impl fmt::Display for BodyPresence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyPresence::Present => write!(f, "present"),
+            BodyPresence::Absent => write!(f, "absent"),
+        }
+    }
+}
+
+impl fmt::Display for BodySizeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{} bytes", self.min)
+        } else {
+            write!(f, "between {} and {} bytes", self.min, self.max)
+        }
+    }
+}
+
 /// A general abstraction of an HTTP request for all handlers.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RequestRequirements {
@@ -633,6 +1453,49 @@ pub struct RequestRequirements {
     pub form_urlencoded_tuple_suffix_not: Option<Vec<(String, String)>>, // NEW
     pub form_urlencoded_tuple_matches: Option<Vec<(HttpMockRegex, HttpMockRegex)>>, // NEW
     pub form_urlencoded_tuple_count: Option<Vec<(HttpMockRegex, HttpMockRegex, usize)>>, // NEW
+    pub priority: Option<i32>,                              // NEW
+    pub is_json: Option<bool>,                              // NEW
+    pub is_valid_xml: Option<bool>,                         // NEW
+    pub is_form_urlencoded: Option<bool>,                   // NEW
+    pub json_schema: Option<Vec<JsonSchema>>,               // NEW
+    pub body_size: Option<Vec<BodySizeRange>>,              // NEW
+    pub query_param_all: Option<Vec<(String, Vec<String>)>>, // NEW
+    pub query_param_includes_all: Option<Vec<(String, Vec<String>)>>, // NEW
+    pub any_of: Option<Vec<Vec<RequestRequirements>>>,      // NEW
+    pub all_of: Option<Vec<RequestRequirements>>,           // NEW
+    pub matcher_named: Option<Vec<NamedMatcherCall>>,       // NEW
+    pub header_value_count: Option<Vec<(String, usize)>>,   // NEW
+    pub path_glob: Option<Vec<String>>,                     // NEW
+    pub client_cert_cn: Option<Vec<String>>,                // NEW
+    pub json_body_unordered: Option<Value>,                 // NEW
+    pub path_template: Option<Vec<PathTemplate>>,           // NEW
+    pub json_body_has_key: Option<Vec<String>>,             // NEW
+    pub json_body_missing_key: Option<Vec<String>>,         // NEW
+    pub strict_trailing_slash: Option<bool>,                // NEW
+    pub raw_query: Option<String>,                          // NEW
+    pub raw_query_includes: Option<Vec<String>>,            // NEW
+    pub raw_query_matches: Option<Vec<HttpMockRegex>>,      // NEW
+    pub url: Option<String>,                                // NEW
+    pub url_matches: Option<Vec<HttpMockRegex>>,            // NEW
+    pub json_body_includes_array: Option<Vec<JsonArrayElements>>, // NEW
+    pub method_in: Option<AnyOfValues>,                     // NEW
+    pub header_name_exact: Option<Vec<(String, String)>>,   // NEW
+    pub protobuf_field: Option<Vec<ProtobufFieldExpectation>>, // NEW
+    pub body_matches_any: Option<Vec<AnyOfPatterns>>,       // NEW
+    pub tags: Option<Vec<String>>,                          // NEW
+    pub expires_in: Option<u64>,                            // NEW
+    pub expires_at: Option<SystemTime>,                     // NEW
+    pub http_version: Option<String>,                       // NEW
+    pub body_decompressed_matches: Option<Vec<HttpMockRegex>>, // NEW
+    pub times: Option<usize>,                               // NEW
+    pub has_body: Option<bool>,                             // NEW
+    pub authority: Option<String>,                          // NEW
+    pub host_header: Option<String>,                        // NEW
+    pub total_header_count_min: Option<usize>,              // NEW
+    pub total_header_count_max: Option<usize>,              // NEW
+    pub cookie_count_min: Option<usize>,                    // NEW
+    pub cookie_count_max: Option<usize>,                    // NEW
+    pub raw_cookie_includes: Option<Vec<String>>,           // NEW
     #[serde(skip)]
     pub is_true: Option<Vec<Arc<dyn Fn(&HttpMockRequest) -> bool + Sync + Send>>>, // NEW + DEPRECATE matches() -> point to using "is_true" instead
     #[serde(skip)]
@@ -728,6 +1591,49 @@ impl RequestRequirements {
             form_urlencoded_tuple_suffix_not: None,
             form_urlencoded_tuple_matches: None,
             form_urlencoded_tuple_count: None,
+            priority: None,
+            is_json: None,
+            is_valid_xml: None,
+            is_form_urlencoded: None,
+            json_schema: None,
+            body_size: None,
+            query_param_all: None,
+            query_param_includes_all: None,
+            any_of: None,
+            all_of: None,
+            matcher_named: None,
+            header_value_count: None,
+            path_glob: None,
+            client_cert_cn: None,
+            json_body_unordered: None,
+            path_template: None,
+            json_body_has_key: None,
+            json_body_missing_key: None,
+            strict_trailing_slash: None,
+            raw_query: None,
+            raw_query_includes: None,
+            raw_query_matches: None,
+            url: None,
+            url_matches: None,
+            json_body_includes_array: None,
+            method_in: None,
+            header_name_exact: None,
+            protobuf_field: None,
+            body_matches_any: None,
+            tags: None,
+            expires_in: None,
+            expires_at: None,
+            http_version: None,
+            body_decompressed_matches: None,
+            times: None,
+            has_body: None,
+            authority: None,
+            host_header: None,
+            total_header_count_min: None,
+            total_header_count_max: None,
+            cookie_count_min: None,
+            cookie_count_max: None,
+            raw_cookie_includes: None,
             is_true: None,
             port_not: None,
             method_not: None,
@@ -746,6 +1652,47 @@ pub struct MockDefinition {
     pub response: MockServerHttpResponse,
 }
 
+/// The current version of the [`StateSnapshot`] format. It needs to be increased whenever the
+/// serialized layout of mock definitions changes in an incompatible way.
+pub const STATE_SNAPSHOT_VERSION: u32 = 1;
+
+/// A snapshot of all mocks configured on a mock server, which can be saved to a file
+/// and loaded into a mock server again later (e.g., after a restart).
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StateSnapshot {
+    pub version: u32,
+    pub mocks: Vec<MockDefinition>,
+}
+
+impl StateSnapshot {
+    pub fn new(mocks: Vec<MockDefinition>) -> Self {
+        Self {
+            version: STATE_SNAPSHOT_VERSION,
+            mocks,
+        }
+    }
+
+    /// Parses a snapshot from its JSON representation. The version is checked before the mock
+    /// definitions are read, so that snapshots of other versions are rejected with a clear
+    /// error instead of failing (or succeeding) on a different field layout.
+    pub fn from_json(json: &[u8]) -> Result<Self, Error> {
+        let value: Value = serde_json::from_slice(json)?;
+
+        match value.get("version").and_then(Value::as_u64) {
+            Some(version) if version == STATE_SNAPSHOT_VERSION as u64 => {}
+            Some(version) => {
+                return Err(StateSnapshotError(format!(
+                    "unsupported version {} (expected version {})",
+                    version, STATE_SNAPSHOT_VERSION
+                )))
+            }
+            None => return Err(StateSnapshotError("missing version".to_string())),
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
 impl MockDefinition {
     pub fn new(req: RequestRequirements, mock: MockServerHttpResponse) -> Self {
         Self {
@@ -761,6 +1708,16 @@ pub struct ActiveMock {
     pub call_counter: usize,
     pub definition: MockDefinition,
     pub is_static: bool,
+    /// Higher-priority mocks that served requests which this mock would also have matched,
+    /// mapped to the number of requests each of them took away from this mock.
+    #[serde(default)]
+    pub shadowed_by: BTreeMap<usize, usize>,
+    /// The wall-clock time at which this mock served a request for the last time.
+    #[serde(default)]
+    pub last_hit_at: Option<SystemTime>,
+    /// The wall-clock time after which the mock is removed from the mock server.
+    #[serde(default)]
+    pub expires_at: Option<SystemTime>,
 }
 
 impl ActiveMock {
@@ -775,8 +1732,17 @@ impl ActiveMock {
             definition,
             call_counter,
             is_static,
+            shadowed_by: BTreeMap::new(),
+            last_hit_at: None,
+            expires_at: None,
         }
     }
+
+    /// Returns `true` if the mock expired at the given point in time.
+    pub(crate) fn is_expired_at(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -820,13 +1786,46 @@ impl ActiveRecording {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// The request from the request history that came closest to matching a mock, together with
+/// the reasons why it did not match.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ClosestMatch {
     pub request: HttpMockRequest,
     pub request_index: usize,
     pub mismatches: Vec<Mismatch>,
 }
 
+/// Statistics about how often and when a mock served requests (see `Mock::stats`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MockStats {
+    /// The number of requests that the mock served.
+    pub hits: usize,
+    /// The wall-clock time at which the mock served a request for the last time, or `None` if
+    /// it has not served any request yet. The time is taken from the system clock of the mock
+    /// server, so it is not monotonic and may differ from the local time of a remote server's
+    /// clients.
+    pub last_hit_at: Option<SystemTime>,
+}
+
+impl From<&ActiveMock> for MockStats {
+    fn from(mock: &ActiveMock) -> Self {
+        MockStats {
+            hits: mock.call_counter,
+            last_hit_at: mock.last_hit_at,
+        }
+    }
+}
+
+/// A request from the request history that did not match any mock, together with the mock
+/// that came closest to matching it and the reasons why it did not match.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnmatchedRequest {
+    pub request: HttpMockRequest,
+    pub request_index: usize,
+    pub closest_mock_id: Option<usize>,
+    pub mismatches: Vec<Mismatch>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub message: String,
@@ -912,6 +1911,9 @@ pub struct Mismatch {
     pub matching_strategy: Option<MatchingStrategy>,
     pub best_match: bool,
     pub diff: Option<DiffResult>,
+    /// An explanation of why the values did not match, if the comparator can provide one.
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 // *************************************************************************************************
@@ -923,19 +1925,156 @@ pub struct RecordingRuleConfig {
     pub request_requirements: RequestRequirements,
     pub record_headers: Vec<String>,
     pub record_response_delays: bool,
+    /// Headers whose values are replaced by the placeholder in recorded responses. In recorded
+    /// requests, these headers are only required to be present, because a mock that expects
+    /// the placeholder would not match real requests when the recording is played back.
+    #[serde(default)]
+    pub redact_headers: Vec<String>,
+    /// Query parameters that recorded requests only require to be present.
+    #[serde(default)]
+    pub redact_query_params: Vec<String>,
+    /// JSON paths whose values are replaced by the placeholder in recorded response bodies and
+    /// removed from recorded request bodies, which are then matched partially.
+    #[serde(default)]
+    pub redact_json_paths: Vec<String>,
+    /// Replaces redacted response values. Defaults to `***`. Never written to recorded requests.
+    #[serde(default)]
+    pub redaction_placeholder: Option<String>,
+    #[serde(default)]
+    pub skip_response_larger_than: Option<usize>,
+    #[serde(default)]
+    pub skip_content_types: Vec<String>,
+}
+
+/// Controls how the mocks that are created from a recording are played back.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct PlaybackConfig {
+    /// Serve the recorded interactions only once each and strictly in the recorded order.
+    pub ordered: bool,
+    /// Delay each response by the upstream round-trip duration that was recorded for it.
+    pub replay_with_timing: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct ProxyRuleConfig {
     pub request_requirements: RequestRequirements,
     pub request_header: Vec<(String, String)>,
+    /// Upstream connect and response header timeout in milliseconds.
+    #[serde(default)]
+    pub upstream_timeout: Option<u64>,
+    #[serde(default)]
+    pub retries: usize,
+    /// Accept invalid TLS certificates of the upstream server. Only meant for test environments.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// A modification of a request header that is applied before a request is forwarded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RequestHeaderChange {
+    Add(String, String),
+    Set(String, String),
+    Append(String, String),
+    Remove(String),
+}
+
+/// Deserializes request header changes. Older clients send the header changes of a forwarding
+/// rule as a list of name-value pairs (in the `request_header` field), which are read as
+/// [`RequestHeaderChange::Add`] changes.
+fn deserialize_request_header_changes<'de, D>(
+    deserializer: D,
+) -> Result<Vec<RequestHeaderChange>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HeaderChangeRepr {
+        Change(RequestHeaderChange),
+        Legacy(String, String),
+    }
+
+    Ok(Vec::<HeaderChangeRepr>::deserialize(deserializer)?
+        .into_iter()
+        .map(|change| match change {
+            HeaderChangeRepr::Change(change) => change,
+            HeaderChangeRepr::Legacy(name, value) => RequestHeaderChange::Add(name, value),
+        })
+        .collect())
+}
+
+/// The upstream server that a request is forwarded to, together with the changes that are
+/// applied to the request before it is sent.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ForwardingTarget {
+    pub target_base_url: String,
+    #[serde(default)]
+    pub request_header_changes: Vec<RequestHeaderChange>,
+    /// Upstream connect and response header timeout in milliseconds.
+    #[serde(default)]
+    pub upstream_timeout: Option<u64>,
+    #[serde(default)]
+    pub retries: usize,
+    /// URL of the proxy server that forwarded requests are sent through. If not set, the proxy
+    /// configured by the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables is used.
+    #[serde(default)]
+    pub upstream_proxy: Option<String>,
+    /// Accept invalid TLS certificates of the target server. Only meant for test environments.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    /// The response that is returned if the request cannot be sent to the target server.
+    #[serde(default)]
+    pub on_error: Option<UpstreamErrorResponse>,
+}
+
+/// The response that the mock server returns if a request cannot be forwarded to the upstream
+/// server, e.g., because the connection was refused, the host name could not be resolved or
+/// the upstream server did not respond in time.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpstreamErrorResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl From<&ForwardingRuleConfig> for ForwardingTarget {
+    fn from(config: &ForwardingRuleConfig) -> Self {
+        ForwardingTarget {
+            target_base_url: config.target_base_url.clone(),
+            request_header_changes: config.request_header_changes.clone(),
+            upstream_timeout: config.upstream_timeout,
+            retries: config.retries,
+            upstream_proxy: config.upstream_proxy.clone(),
+            danger_accept_invalid_certs: config.danger_accept_invalid_certs,
+            on_error: config.on_error.clone(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct ForwardingRuleConfig {
     pub target_base_url: String,
     pub request_requirements: RequestRequirements,
-    pub request_header: Vec<(String, String)>,
+    #[serde(
+        alias = "request_header",
+        deserialize_with = "deserialize_request_header_changes"
+    )]
+    pub request_header_changes: Vec<RequestHeaderChange>,
+    /// Upstream connect and response header timeout in milliseconds.
+    #[serde(default)]
+    pub upstream_timeout: Option<u64>,
+    #[serde(default)]
+    pub retries: usize,
+    /// URL of the proxy server that forwarded requests are sent through. If not set, the proxy
+    /// configured by the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables is used.
+    #[serde(default)]
+    pub upstream_proxy: Option<String>,
+    /// Accept invalid TLS certificates of the target server. Only meant for test environments.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    /// The response that is returned if the request cannot be sent to the target server.
+    #[serde(default)]
+    pub on_error: Option<UpstreamErrorResponse>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -944,6 +2083,18 @@ pub struct NameValueStringPair {
     value: String,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct NameValuesPair {
+    name: String,
+    values: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct NameCountPair {
+    name: String,
+    count: usize,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct NameValuePatternPair {
     name: HttpMockRegex,
@@ -1178,6 +2329,88 @@ pub struct StaticRequestRequirements {
     pub form_urlencoded_matches: Option<Vec<NameValuePatternPair>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub form_urlencoded_count: Option<Vec<KeyValuePatternCountTriple>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_json: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_valid_xml: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_form_urlencoded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_size: Option<Vec<BodySizeRange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_param_all: Option<Vec<NameValuesPair>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_param_includes_all: Option<Vec<NameValuesPair>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub any_of: Option<Vec<Vec<StaticRequestRequirements>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all_of: Option<Vec<StaticRequestRequirements>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matcher_named: Option<Vec<NamedMatcherCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_value_count: Option<Vec<NameCountPair>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_glob: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_cert_cn: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_unordered: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_template: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_has_key: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_missing_key: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_trailing_slash: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_query: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_query_includes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_query_matches: Option<Vec<HttpMockRegex>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_matches: Option<Vec<HttpMockRegex>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_includes_array: Option<Vec<JsonArrayElements>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_in: Option<Vec<Method>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_name_exact: Option<Vec<NameValueStringPair>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protobuf_field: Option<Vec<ProtobufFieldExpectation>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_matches_any: Option<Vec<AnyOfPatterns>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_decompressed_matches: Option<Vec<HttpMockRegex>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub times: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_body: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_header_count_min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_header_count_max: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cookie_count_min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cookie_count_max: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_cookie_includes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1185,13 +2418,99 @@ pub struct StaticHTTPResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_from_header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_compress: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_delay: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditional: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub header: Option<Vec<NameValueStringPair>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub trailer: Option<Vec<NameValueStringPair>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body_base64: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_body_merge: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delay: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay_from_header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fault: Option<Fault>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_to: Option<ForwardingTarget>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub respond_after: Option<Vec<StaticCallCountResponse>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for_concurrent: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for_concurrent_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omit_headers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limited: Option<StaticRateLimit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaticCallCountResponse {
+    pub calls: usize,
+    pub then: StaticHTTPResponse,
+}
+
+/// Responds with `429 Too Many Requests` and a `Retry-After` header. Exactly one of the
+/// fields must be set.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaticRateLimit {
+    /// The number of seconds a client should wait before retrying.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// The time after which a client may retry as an HTTP date, such as
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_date: Option<String>,
+}
+
+impl TryFrom<StaticRateLimit> for String {
+    type Error = Error;
+
+    fn try_from(value: StaticRateLimit) -> Result<Self, Self::Error> {
+        match (value.retry_after_secs, value.retry_after_date) {
+            (Some(secs), None) => Ok(secs.to_string()),
+            (None, Some(date)) => {
+                httpdate::parse_http_date(&date).map_err(|_| {
+                    StaticMockConversionError(format!(
+                        "invalid rate limit retry date '{}': expected an HTTP date such as 'Wed, 21 Oct 2015 07:28:00 GMT'",
+                        date
+                    ))
+                })?;
+                Ok(date)
+            }
+            _ => Err(StaticMockConversionError(
+                "a rate limit requires exactly one of retry_after_secs and retry_after_date"
+                    .to_string(),
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1200,176 +2519,291 @@ pub struct StaticMockDefinition {
     then: StaticHTTPResponse,
 }
 
+impl TryInto<RequestRequirements> for StaticRequestRequirements {
+    type Error = Error;
+
+    fn try_into(self) -> Result<RequestRequirements, Self::Error> {
+        Ok(RequestRequirements {
+            // Scheme-related fields
+            scheme: self.scheme,
+            scheme_not: self.scheme_not,
+
+            // Host-related fields
+            host: self.host,
+            host_not: self.host_not,
+            host_contains: self.host_contains,
+            host_excludes: self.host_excludes,
+            host_prefix: self.host_prefix,
+            host_suffix: self.host_suffix,
+            host_prefix_not: self.host_prefix_not,
+            host_suffix_not: self.host_suffix_not,
+            host_matches: self.host_matches,
+
+            // Port-related fields
+            port: self.port,
+            port_not: self.port_not,
+
+            // Path-related fields
+            path: self.path,
+            path_not: self.path_not,
+            path_includes: self.path_contains,
+            path_excludes: self.path_excludes,
+            path_prefix: self.path_prefix,
+            path_suffix: self.path_suffix,
+            path_prefix_not: self.path_prefix_not,
+            path_suffix_not: self.path_suffix_not,
+            path_matches: self.path_matches,
+
+            // Method-related fields
+            method: self.method.map(|m| m.to_string()),
+            method_not: from_method_vec(self.method_not),
+            // Query Parameter-related fields
+            query_param: from_name_value_string_pair_vec(self.query_param),
+            query_param_not: from_name_value_string_pair_vec(self.query_param_not),
+            query_param_exists: self.query_param_exists,
+            query_param_missing: self.query_param_missing,
+            query_param_includes: from_name_value_string_pair_vec(self.query_param_contains),
+            query_param_excludes: from_name_value_string_pair_vec(self.query_param_excludes),
+            query_param_prefix: from_name_value_string_pair_vec(self.query_param_prefix),
+            query_param_suffix: from_name_value_string_pair_vec(self.query_param_suffix),
+            query_param_prefix_not: from_name_value_string_pair_vec(self.query_param_prefix_not),
+            query_param_suffix_not: from_name_value_string_pair_vec(self.query_param_suffix_not),
+            query_param_matches: from_name_value_pattern_pair_vec(self.query_param_matches),
+            query_param_count: from_key_value_pattern_count_triple_vec(self.query_param_count),
+
+            // Header-related fields
+            header: from_name_value_string_pair_vec(self.header),
+            header_not: from_name_value_string_pair_vec(self.header_not),
+            header_exists: self.header_exists,
+            header_missing: self.header_missing,
+            header_includes: from_name_value_string_pair_vec(self.header_contains),
+            header_excludes: from_name_value_string_pair_vec(self.header_excludes),
+            header_prefix: from_name_value_string_pair_vec(self.header_prefix),
+            header_suffix: from_name_value_string_pair_vec(self.header_suffix),
+            header_prefix_not: from_name_value_string_pair_vec(self.header_prefix_not),
+            header_suffix_not: from_name_value_string_pair_vec(self.header_suffix_not),
+            header_matches: from_name_value_pattern_pair_vec(self.header_matches),
+            header_count: from_key_value_pattern_count_triple_vec(self.header_count),
+            // Cookie-related fields
+            cookie: from_name_value_string_pair_vec(self.cookie),
+            cookie_not: from_name_value_string_pair_vec(self.cookie_not),
+            cookie_exists: self.cookie_exists,
+            cookie_missing: self.cookie_missing,
+            cookie_includes: from_name_value_string_pair_vec(self.cookie_contains),
+            cookie_excludes: from_name_value_string_pair_vec(self.cookie_excludes),
+            cookie_prefix: from_name_value_string_pair_vec(self.cookie_prefix),
+            cookie_suffix: from_name_value_string_pair_vec(self.cookie_suffix),
+            cookie_prefix_not: from_name_value_string_pair_vec(self.cookie_prefix_not),
+            cookie_suffix_not: from_name_value_string_pair_vec(self.cookie_suffix_not),
+            cookie_matches: from_name_value_pattern_pair_vec(self.cookie_matches),
+            cookie_count: from_key_value_pattern_count_triple_vec(self.cookie_count),
+
+            // Body-related fields
+            body: from_string_to_bytes_choose(self.body, self.body_base64),
+            body_not: to_bytes_vec(self.body_not, self.body_not_base64),
+            body_includes: to_bytes_vec(self.body_contains, self.body_contains_base64),
+            body_excludes: to_bytes_vec(self.body_excludes, self.body_excludes_base64),
+            body_prefix: to_bytes_vec(self.body_prefix, self.body_prefix_base64),
+            body_suffix: to_bytes_vec(self.body_suffix, self.body_suffix_base64),
+            body_prefix_not: to_bytes_vec(self.body_prefix_not, self.body_prefix_not_base64),
+            body_suffix_not: to_bytes_vec(self.body_suffix_not, self.body_suffix_not_base64),
+            body_matches: from_pattern_vec(self.body_matches),
+
+            // JSON Body-related fields
+            json_body: self.json_body,
+            json_body_not: self.json_body_not,
+            json_body_includes: self.json_body_includes,
+            json_body_excludes: self.json_body_excludes,
+
+            // x-www-form-urlencoded fields
+            form_urlencoded_tuple: from_name_value_string_pair_vec(self.form_urlencoded_tuple),
+            form_urlencoded_tuple_not: from_name_value_string_pair_vec(
+                self.form_urlencoded_tuple_not,
+            ),
+            form_urlencoded_tuple_exists: self.form_urlencoded_key_exists,
+            form_urlencoded_tuple_missing: self.form_urlencoded_key_missing,
+            form_urlencoded_tuple_includes: from_name_value_string_pair_vec(
+                self.form_urlencoded_contains,
+            ),
+            form_urlencoded_tuple_excludes: from_name_value_string_pair_vec(
+                self.form_urlencoded_excludes,
+            ),
+            form_urlencoded_tuple_prefix: from_name_value_string_pair_vec(
+                self.form_urlencoded_prefix,
+            ),
+            form_urlencoded_tuple_suffix: from_name_value_string_pair_vec(
+                self.form_urlencoded_suffix,
+            ),
+            form_urlencoded_tuple_prefix_not: from_name_value_string_pair_vec(
+                self.form_urlencoded_prefix_not,
+            ),
+            form_urlencoded_tuple_suffix_not: from_name_value_string_pair_vec(
+                self.form_urlencoded_suffix_not,
+            ),
+            form_urlencoded_tuple_matches: from_name_value_pattern_pair_vec(
+                self.form_urlencoded_matches,
+            ),
+
+            form_urlencoded_tuple_count: from_key_value_pattern_count_triple_vec(
+                self.form_urlencoded_count,
+            ),
+            priority: self.priority,
+            is_json: self.is_json,
+            is_valid_xml: self.is_valid_xml,
+            is_form_urlencoded: self.is_form_urlencoded,
+            json_schema: self
+                .json_schema
+                .map(|v| v.into_iter().map(JsonSchema::from).collect()),
+            body_size: self.body_size,
+            query_param_all: from_name_values_pair_vec(self.query_param_all),
+            query_param_includes_all: from_name_values_pair_vec(self.query_param_includes_all),
+            any_of: to_requirement_groups(self.any_of)?,
+            all_of: to_requirements(self.all_of)?,
+            matcher_named: self.matcher_named,
+            header_value_count: from_name_count_pair_vec(self.header_value_count),
+            path_glob: self.path_glob,
+            client_cert_cn: self.client_cert_cn,
+            json_body_unordered: self.json_body_unordered,
+            path_template: self
+                .path_template
+                .map(|v| v.into_iter().map(PathTemplate::from).collect()),
+            json_body_has_key: self.json_body_has_key,
+            json_body_missing_key: self.json_body_missing_key,
+            strict_trailing_slash: self.strict_trailing_slash,
+            raw_query: self.raw_query,
+            raw_query_includes: self.raw_query_includes,
+            raw_query_matches: self.raw_query_matches,
+            url: self
+                .url
+                .map(|url| {
+                    normalize_url(&url).map_err(|err| {
+                        StaticMockConversionError(format!("invalid URL '{}': {}", url, err))
+                    })
+                })
+                .transpose()?,
+            url_matches: self.url_matches,
+            json_body_includes_array: self.json_body_includes_array,
+            method_in: from_method_vec(self.method_in).map(AnyOfValues),
+            header_name_exact: from_name_value_string_pair_vec(self.header_name_exact),
+            protobuf_field: self.protobuf_field,
+            body_matches_any: self.body_matches_any,
+            tags: self.tags.clone(),
+            // Static mocks are loaded from files and do not expire.
+            expires_in: None,
+            expires_at: None,
+            http_version: self.http_version.map(|v| normalize_http_version(&v)),
+            body_decompressed_matches: self.body_decompressed_matches,
+            times: self.times,
+            has_body: self.has_body,
+            authority: self.authority,
+            host_header: self.host_header,
+            total_header_count_min: self.total_header_count_min,
+            total_header_count_max: self.total_header_count_max,
+            cookie_count_min: self.cookie_count_min,
+            cookie_count_max: self.cookie_count_max,
+            raw_cookie_includes: self.raw_cookie_includes,
+
+            // Boolean dynamic checks
+            is_true: None,
+            is_false: None,
+        })
+    }
+}
+
 impl TryInto<MockDefinition> for StaticMockDefinition {
     type Error = Error;
 
     fn try_into(self) -> Result<MockDefinition, Self::Error> {
         Ok(MockDefinition {
-            request: RequestRequirements {
-                // Scheme-related fields
-                scheme: self.when.scheme,
-                scheme_not: self.when.scheme_not,
-
-                // Host-related fields
-                host: self.when.host,
-                host_not: self.when.host_not,
-                host_contains: self.when.host_contains,
-                host_excludes: self.when.host_excludes,
-                host_prefix: self.when.host_prefix,
-                host_suffix: self.when.host_suffix,
-                host_prefix_not: self.when.host_prefix_not,
-                host_suffix_not: self.when.host_suffix_not,
-                host_matches: self.when.host_matches,
-
-                // Port-related fields
-                port: self.when.port,
-                port_not: self.when.port_not,
-
-                // Path-related fields
-                path: self.when.path,
-                path_not: self.when.path_not,
-                path_includes: self.when.path_contains,
-                path_excludes: self.when.path_excludes,
-                path_prefix: self.when.path_prefix,
-                path_suffix: self.when.path_suffix,
-                path_prefix_not: self.when.path_prefix_not,
-                path_suffix_not: self.when.path_suffix_not,
-                path_matches: self.when.path_matches,
-
-                // Method-related fields
-                method: self.when.method.map(|m| m.to_string()),
-                method_not: from_method_vec(self.when.method_not),
-                // Query Parameter-related fields
-                query_param: from_name_value_string_pair_vec(self.when.query_param),
-                query_param_not: from_name_value_string_pair_vec(self.when.query_param_not),
-                query_param_exists: self.when.query_param_exists,
-                query_param_missing: self.when.query_param_missing,
-                query_param_includes: from_name_value_string_pair_vec(
-                    self.when.query_param_contains,
-                ),
-                query_param_excludes: from_name_value_string_pair_vec(
-                    self.when.query_param_excludes,
-                ),
-                query_param_prefix: from_name_value_string_pair_vec(self.when.query_param_prefix),
-                query_param_suffix: from_name_value_string_pair_vec(self.when.query_param_suffix),
-                query_param_prefix_not: from_name_value_string_pair_vec(
-                    self.when.query_param_prefix_not,
-                ),
-                query_param_suffix_not: from_name_value_string_pair_vec(
-                    self.when.query_param_suffix_not,
-                ),
-                query_param_matches: from_name_value_pattern_pair_vec(
-                    self.when.query_param_matches,
-                ),
-                query_param_count: from_key_value_pattern_count_triple_vec(
-                    self.when.query_param_count,
-                ),
-
-                // Header-related fields
-                header: from_name_value_string_pair_vec(self.when.header),
-                header_not: from_name_value_string_pair_vec(self.when.header_not),
-                header_exists: self.when.header_exists,
-                header_missing: self.when.header_missing,
-                header_includes: from_name_value_string_pair_vec(self.when.header_contains),
-                header_excludes: from_name_value_string_pair_vec(self.when.header_excludes),
-                header_prefix: from_name_value_string_pair_vec(self.when.header_prefix),
-                header_suffix: from_name_value_string_pair_vec(self.when.header_suffix),
-                header_prefix_not: from_name_value_string_pair_vec(self.when.header_prefix_not),
-                header_suffix_not: from_name_value_string_pair_vec(self.when.header_suffix_not),
-                header_matches: from_name_value_pattern_pair_vec(self.when.header_matches),
-                header_count: from_key_value_pattern_count_triple_vec(self.when.header_count),
-                // Cookie-related fields
-                cookie: from_name_value_string_pair_vec(self.when.cookie),
-                cookie_not: from_name_value_string_pair_vec(self.when.cookie_not),
-                cookie_exists: self.when.cookie_exists,
-                cookie_missing: self.when.cookie_missing,
-                cookie_includes: from_name_value_string_pair_vec(self.when.cookie_contains),
-                cookie_excludes: from_name_value_string_pair_vec(self.when.cookie_excludes),
-                cookie_prefix: from_name_value_string_pair_vec(self.when.cookie_prefix),
-                cookie_suffix: from_name_value_string_pair_vec(self.when.cookie_suffix),
-                cookie_prefix_not: from_name_value_string_pair_vec(self.when.cookie_prefix_not),
-                cookie_suffix_not: from_name_value_string_pair_vec(self.when.cookie_suffix_not),
-                cookie_matches: from_name_value_pattern_pair_vec(self.when.cookie_matches),
-                cookie_count: from_key_value_pattern_count_triple_vec(self.when.cookie_count),
-
-                // Body-related fields
-                body: from_string_to_bytes_choose(self.when.body, self.when.body_base64),
-                body_not: to_bytes_vec(self.when.body_not, self.when.body_not_base64),
-                body_includes: to_bytes_vec(
-                    self.when.body_contains,
-                    self.when.body_contains_base64,
-                ),
-                body_excludes: to_bytes_vec(
-                    self.when.body_excludes,
-                    self.when.body_excludes_base64,
-                ),
-                body_prefix: to_bytes_vec(self.when.body_prefix, self.when.body_prefix_base64),
-                body_suffix: to_bytes_vec(self.when.body_suffix, self.when.body_suffix_base64),
-                body_prefix_not: to_bytes_vec(
-                    self.when.body_prefix_not,
-                    self.when.body_prefix_not_base64,
-                ),
-                body_suffix_not: to_bytes_vec(
-                    self.when.body_suffix_not,
-                    self.when.body_suffix_not_base64,
-                ),
-                body_matches: from_pattern_vec(self.when.body_matches),
-
-                // JSON Body-related fields
-                json_body: self.when.json_body,
-                json_body_not: self.when.json_body_not,
-                json_body_includes: self.when.json_body_includes,
-                json_body_excludes: self.when.json_body_excludes,
-
-                // x-www-form-urlencoded fields
-                form_urlencoded_tuple: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_tuple,
-                ),
-                form_urlencoded_tuple_not: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_tuple_not,
-                ),
-                form_urlencoded_tuple_exists: self.when.form_urlencoded_key_exists,
-                form_urlencoded_tuple_missing: self.when.form_urlencoded_key_missing,
-                form_urlencoded_tuple_includes: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_contains,
-                ),
-                form_urlencoded_tuple_excludes: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_excludes,
-                ),
-                form_urlencoded_tuple_prefix: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_prefix,
-                ),
-                form_urlencoded_tuple_suffix: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_suffix,
-                ),
-                form_urlencoded_tuple_prefix_not: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_prefix_not,
-                ),
-                form_urlencoded_tuple_suffix_not: from_name_value_string_pair_vec(
-                    self.when.form_urlencoded_suffix_not,
-                ),
-                form_urlencoded_tuple_matches: from_name_value_pattern_pair_vec(
-                    self.when.form_urlencoded_matches,
-                ),
-
-                form_urlencoded_tuple_count: from_key_value_pattern_count_triple_vec(
-                    self.when.form_urlencoded_count,
-                ),
-
-                // Boolean dynamic checks
-                is_true: None,
-                is_false: None,
-            },
-            response: MockServerHttpResponse {
-                status: self.then.status,
-                headers: from_name_value_string_pair_vec(self.then.header),
-                body: from_string_to_bytes_choose(self.then.body, self.then.body_base64),
-                delay: self.then.delay,
-            },
+            request: self.when.try_into()?,
+            response: self.then.try_into()?,
         })
     }
 }
 
+impl TryFrom<StaticHTTPResponse> for MockServerHttpResponse {
+    type Error = Error;
+
+    fn try_from(value: StaticHTTPResponse) -> Result<Self, Self::Error> {
+        let mut response = MockServerHttpResponse {
+            status: value.status,
+            status_from_header: value.status_from_header,
+            reason: value.reason,
+            auto_compress: value.auto_compress,
+            line_delay: value.line_delay,
+            etag: value.etag.as_deref().map(quote_entity_tag),
+            last_modified: value.last_modified,
+            conditional: value.conditional,
+            headers: from_name_value_string_pair_vec(value.header),
+            trailers: from_name_value_string_pair_vec(value.trailer),
+            body: from_string_to_bytes_choose(value.body, value.body_base64),
+            body_file: value.body_file,
+            json_body_merge: value.json_body_merge,
+            body_template: value.body_template,
+            delay: value.delay,
+            delay_from_header: value.delay_from_header,
+            fault: value.fault,
+            chunk_size: value.chunk_size,
+            proxy_to: value.proxy_to,
+            duration_ms: value.duration_ms,
+            respond_after: value
+                .respond_after
+                .map(|responses| {
+                    responses
+                        .into_iter()
+                        .map(|r| {
+                            Ok(CallCountResponse {
+                                calls: r.calls,
+                                response: r.then.try_into()?,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                })
+                .transpose()?,
+            after_respond: None,
+            wait_for_concurrent: value.wait_for_concurrent,
+            wait_for_concurrent_timeout: value.wait_for_concurrent_timeout,
+            omit_headers: value.omit_headers,
+        };
+
+        if let Some(path) = value.json_body_file {
+            response
+                .set_json_body_from_file(Path::new(&path))
+                .map_err(StaticMockConversionError)?;
+        }
+
+        if let Some(date) = &response.last_modified {
+            httpdate::parse_http_date(date).map_err(|_| {
+                StaticMockConversionError(format!(
+                    "invalid last modified date '{}': expected an HTTP date such as 'Wed, 21 Oct 2015 07:28:00 GMT'",
+                    date
+                ))
+            })?;
+        }
+
+        if let Some(rate_limit) = value.rate_limited {
+            if !matches!(response.status, None | Some(429)) {
+                return Err(StaticMockConversionError(
+                    "a rate limited response cannot have a status other than 429".to_string(),
+                ));
+            }
+            response.set_rate_limited(rate_limit.try_into()?);
+        }
+
+        Ok(response)
+    }
+}
+
+/// Wraps an entity tag in double quotes, unless it is quoted already (optionally with the
+/// weakness indicator `W/`).
+pub(crate) fn quote_entity_tag(etag: &str) -> String {
+    if etag.starts_with('"') || etag.starts_with("W/\"") {
+        etag.to_string()
+    } else {
+        format!("\"{}\"", etag)
+    }
+}
+
 fn to_method_vec(vec: Option<Vec<String>>) -> Option<Vec<Method>> {
     vec.map(|vec| vec.iter().map(|val| Method::from(val.as_str())).collect())
 }
@@ -1396,6 +2830,24 @@ fn from_name_value_string_pair_vec(
     kvp.map(|vec| vec.into_iter().map(|nvp| (nvp.name, nvp.value)).collect())
 }
 
+fn from_name_values_pair_vec(
+    kvp: Option<Vec<NameValuesPair>>,
+) -> Option<Vec<(String, Vec<String>)>> {
+    kvp.map(|vec| {
+        vec.into_iter()
+            .map(|pair| (pair.name, pair.values))
+            .collect()
+    })
+}
+
+fn from_name_count_pair_vec(kvp: Option<Vec<NameCountPair>>) -> Option<Vec<(String, usize)>> {
+    kvp.map(|vec| {
+        vec.into_iter()
+            .map(|pair| (pair.name, pair.count))
+            .collect()
+    })
+}
+
 fn from_name_value_pattern_pair_vec(
     kvp: Option<Vec<NameValuePatternPair>>,
 ) -> Option<Vec<(HttpMockRegex, HttpMockRegex)>> {
@@ -1450,6 +2902,66 @@ fn to_name_value_string_pair_vec(
     })
 }
 
+fn to_name_values_pair_vec(vec: Option<Vec<(String, Vec<String>)>>) -> Option<Vec<NameValuesPair>> {
+    vec.map(|vec| {
+        vec.into_iter()
+            .map(|(name, values)| NameValuesPair { name, values })
+            .collect()
+    })
+}
+
+fn to_name_count_pair_vec(vec: Option<Vec<(String, usize)>>) -> Option<Vec<NameCountPair>> {
+    vec.map(|vec| {
+        vec.into_iter()
+            .map(|(name, count)| NameCountPair { name, count })
+            .collect()
+    })
+}
+
+fn to_requirements(
+    vec: Option<Vec<StaticRequestRequirements>>,
+) -> Result<Option<Vec<RequestRequirements>>, Error> {
+    vec.map(|vec| vec.into_iter().map(TryInto::try_into).collect())
+        .transpose()
+}
+
+fn from_requirements(
+    vec: Option<Vec<RequestRequirements>>,
+) -> Result<Option<Vec<StaticRequestRequirements>>, Error> {
+    vec.map(|vec| {
+        vec.iter()
+            .map(StaticRequestRequirements::try_from)
+            .collect()
+    })
+    .transpose()
+}
+
+fn to_requirement_groups(
+    groups: Option<Vec<Vec<StaticRequestRequirements>>>,
+) -> Result<Option<Vec<Vec<RequestRequirements>>>, Error> {
+    groups
+        .map(|groups| {
+            groups
+                .into_iter()
+                .map(|group| Ok(to_requirements(Some(group))?.unwrap_or_default()))
+                .collect()
+        })
+        .transpose()
+}
+
+fn from_requirement_groups(
+    groups: Option<Vec<Vec<RequestRequirements>>>,
+) -> Result<Option<Vec<Vec<StaticRequestRequirements>>>, Error> {
+    groups
+        .map(|groups| {
+            groups
+                .into_iter()
+                .map(|group| Ok(from_requirements(Some(group))?.unwrap_or_default()))
+                .collect()
+        })
+        .transpose()
+}
+
 fn to_name_value_pattern_pair_vec(
     vec: Option<Vec<(HttpMockRegex, HttpMockRegex)>>,
 ) -> Option<Vec<NameValuePatternPair>> {
@@ -1587,203 +3099,279 @@ fn from_string_to_bytes_choose(
     return request_body.map(|s| HttpMockBytes::from(Bytes::from(s)));
 }
 
-impl TryFrom<&MockDefinition> for StaticMockDefinition {
+impl TryFrom<&RequestRequirements> for StaticRequestRequirements {
     type Error = Error;
 
-    fn try_from(value: &MockDefinition) -> Result<Self, Self::Error> {
+    fn try_from(value: &RequestRequirements) -> Result<Self, Self::Error> {
         let value = value.clone();
 
-        let (response_body, response_body_base64) = from_bytes_to_string(value.response.body);
-
-        let (request_body, request_body_base64) = from_bytes_to_string(value.request.body);
-        let (request_body_not, request_body_not_base64) =
-            bytes_to_string_vec(value.request.body_not);
+        let (request_body, request_body_base64) = from_bytes_to_string(value.body);
+        let (request_body_not, request_body_not_base64) = bytes_to_string_vec(value.body_not);
         let (request_body_includes, request_body_includes_base64) =
-            bytes_to_string_vec(value.request.body_includes);
+            bytes_to_string_vec(value.body_includes);
         let (request_body_excludes, request_body_excludes_base64) =
-            bytes_to_string_vec(value.request.body_excludes);
+            bytes_to_string_vec(value.body_excludes);
         let (request_body_prefix, request_body_prefix_base64) =
-            bytes_to_string_vec(value.request.body_prefix);
+            bytes_to_string_vec(value.body_prefix);
         let (request_body_suffix, request_body_suffix_base64) =
-            bytes_to_string_vec(value.request.body_suffix);
+            bytes_to_string_vec(value.body_suffix);
         let (request_body_prefix_not, request_body_prefix_not_base64) =
-            bytes_to_string_vec(value.request.body_prefix_not);
+            bytes_to_string_vec(value.body_prefix_not);
         let (request_body_suffix_not, request_body_suffix_not_base64) =
-            bytes_to_string_vec(value.request.body_suffix_not);
+            bytes_to_string_vec(value.body_suffix_not);
 
         let mut method = None;
-        if let Some(method_str) = value.request.method {
+        if let Some(method_str) = value.method {
             method = Some(
                 Method::from_str(&method_str)
                     .map_err(|err| StaticMockConversionError(err.to_string()))?,
             );
         }
 
+        Ok(StaticRequestRequirements {
+            // Scheme-related fields
+            scheme: value.scheme,
+            scheme_not: value.scheme_not,
+
+            // Method-related fields
+            method,
+            method_not: to_method_vec(value.method_not),
+            // Host-related fields
+            host: value.host,
+            host_not: value.host_not,
+            host_contains: value.host_contains,
+            host_excludes: value.host_excludes,
+            host_prefix: value.host_prefix,
+            host_suffix: value.host_suffix,
+            host_prefix_not: value.host_prefix_not,
+            host_suffix_not: value.host_suffix_not,
+            host_matches: value.host_matches,
+
+            // Port-related fields
+            port: value.port,
+            port_not: value.port_not,
+
+            // Path-related fields
+            path: value.path,
+            path_not: value.path_not,
+            path_contains: value.path_includes,
+            path_excludes: value.path_excludes,
+            path_prefix: value.path_prefix,
+            path_suffix: value.path_suffix,
+            path_prefix_not: value.path_prefix_not,
+            path_suffix_not: value.path_suffix_not,
+            path_matches: from_pattern_vec(value.path_matches),
+
+            // Header-related fields
+            header: from_string_pair_vec(value.header),
+            header_not: from_string_pair_vec(value.header_not),
+            header_exists: value.header_exists,
+            header_missing: value.header_missing,
+            header_contains: to_name_value_string_pair_vec(value.header_includes),
+            header_excludes: to_name_value_string_pair_vec(value.header_excludes),
+            header_prefix: to_name_value_string_pair_vec(value.header_prefix),
+            header_suffix: to_name_value_string_pair_vec(value.header_suffix),
+            header_prefix_not: to_name_value_string_pair_vec(value.header_prefix_not),
+            header_suffix_not: to_name_value_string_pair_vec(value.header_suffix_not),
+            header_matches: to_name_value_pattern_pair_vec(value.header_matches),
+            header_count: to_key_value_pattern_count_triple_vec(value.header_count),
+
+            // Cookie-related fields
+            cookie: from_string_pair_vec(value.cookie),
+            cookie_not: from_string_pair_vec(value.cookie_not),
+            cookie_exists: value.cookie_exists,
+            cookie_missing: value.cookie_missing,
+            cookie_contains: to_name_value_string_pair_vec(value.cookie_includes),
+            cookie_excludes: to_name_value_string_pair_vec(value.cookie_excludes),
+            cookie_prefix: to_name_value_string_pair_vec(value.cookie_prefix),
+            cookie_suffix: to_name_value_string_pair_vec(value.cookie_suffix),
+            cookie_prefix_not: to_name_value_string_pair_vec(value.cookie_prefix_not),
+            cookie_suffix_not: to_name_value_string_pair_vec(value.cookie_suffix_not),
+            cookie_matches: to_name_value_pattern_pair_vec(value.cookie_matches),
+
+            cookie_count: to_key_value_pattern_count_triple_vec(value.cookie_count),
+
+            // Query Parameter-related fields
+            query_param: from_string_pair_vec(value.query_param),
+            query_param_not: from_string_pair_vec(value.query_param_not),
+            query_param_exists: value.query_param_exists,
+            query_param_missing: value.query_param_missing,
+            query_param_contains: to_name_value_string_pair_vec(value.query_param_includes),
+            query_param_excludes: to_name_value_string_pair_vec(value.query_param_excludes),
+            query_param_prefix: to_name_value_string_pair_vec(value.query_param_prefix),
+            query_param_suffix: to_name_value_string_pair_vec(value.query_param_suffix),
+            query_param_prefix_not: to_name_value_string_pair_vec(value.query_param_prefix_not),
+            query_param_suffix_not: to_name_value_string_pair_vec(value.query_param_suffix_not),
+            query_param_matches: to_name_value_pattern_pair_vec(value.query_param_matches),
+            query_param_count: to_key_value_pattern_count_triple_vec(value.query_param_count),
+
+            // Body-related fields
+            body: request_body,
+            body_base64: request_body_base64,
+            body_not: request_body_not,
+            body_not_base64: request_body_not_base64,
+            body_contains: request_body_includes,
+            body_contains_base64: request_body_includes_base64,
+            body_excludes: request_body_excludes,
+            body_excludes_base64: request_body_excludes_base64,
+            body_prefix: request_body_prefix,
+            body_prefix_base64: request_body_prefix_base64,
+            body_suffix: request_body_suffix,
+            body_suffix_base64: request_body_suffix_base64,
+            body_prefix_not: request_body_prefix_not,
+            body_prefix_not_base64: request_body_prefix_not_base64,
+            body_suffix_not: request_body_suffix_not,
+            body_suffix_not_base64: request_body_suffix_not_base64,
+            body_matches: from_pattern_vec(value.body_matches),
+
+            // JSON Body-related fields
+            json_body: value.json_body,
+            json_body_not: value.json_body_not,
+            json_body_includes: value.json_body_includes,
+            json_body_excludes: value.json_body_excludes,
+
+            // Form URL-encoded fields
+            form_urlencoded_tuple: from_string_pair_vec(value.form_urlencoded_tuple),
+            form_urlencoded_tuple_not: from_string_pair_vec(value.form_urlencoded_tuple_not),
+            form_urlencoded_key_exists: value.form_urlencoded_tuple_exists,
+            form_urlencoded_key_missing: value.form_urlencoded_tuple_missing,
+            form_urlencoded_contains: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_includes,
+            ),
+            form_urlencoded_excludes: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_excludes,
+            ),
+            form_urlencoded_prefix: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_prefix,
+            ),
+            form_urlencoded_suffix: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_suffix,
+            ),
+            form_urlencoded_prefix_not: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_prefix_not,
+            ),
+            form_urlencoded_suffix_not: to_name_value_string_pair_vec(
+                value.form_urlencoded_tuple_suffix_not,
+            ),
+            form_urlencoded_matches: to_name_value_pattern_pair_vec(
+                value.form_urlencoded_tuple_matches,
+            ),
+
+            form_urlencoded_count: to_key_value_pattern_count_triple_vec(
+                value.form_urlencoded_tuple_count,
+            ),
+            priority: value.priority,
+            is_json: value.is_json,
+            is_valid_xml: value.is_valid_xml,
+            is_form_urlencoded: value.is_form_urlencoded,
+            json_schema: value
+                .json_schema
+                .map(|v| v.into_iter().map(|s| s.schema).collect()),
+            body_size: value.body_size,
+            query_param_all: to_name_values_pair_vec(value.query_param_all),
+            query_param_includes_all: to_name_values_pair_vec(value.query_param_includes_all),
+            any_of: from_requirement_groups(value.any_of)?,
+            all_of: from_requirements(value.all_of)?,
+            matcher_named: value.matcher_named,
+            header_value_count: to_name_count_pair_vec(value.header_value_count),
+            path_glob: value.path_glob,
+            client_cert_cn: value.client_cert_cn,
+            json_body_unordered: value.json_body_unordered,
+            path_template: value
+                .path_template
+                .map(|v| v.into_iter().map(|t| t.template).collect()),
+            json_body_has_key: value.json_body_has_key,
+            json_body_missing_key: value.json_body_missing_key,
+            strict_trailing_slash: value.strict_trailing_slash,
+            raw_query: value.raw_query,
+            raw_query_includes: value.raw_query_includes,
+            raw_query_matches: from_pattern_vec(value.raw_query_matches),
+            url: value.url,
+            url_matches: from_pattern_vec(value.url_matches),
+            json_body_includes_array: value.json_body_includes_array,
+            method_in: to_method_vec(value.method_in.map(|methods| methods.0)),
+            header_name_exact: from_string_pair_vec(value.header_name_exact),
+            protobuf_field: value.protobuf_field,
+            body_matches_any: value.body_matches_any.clone(),
+            tags: value.tags.clone(),
+            http_version: value.http_version.clone(),
+            body_decompressed_matches: value.body_decompressed_matches.clone(),
+            times: value.times,
+            has_body: value.has_body,
+            authority: value.authority,
+            host_header: value.host_header,
+            total_header_count_min: value.total_header_count_min,
+            total_header_count_max: value.total_header_count_max,
+            cookie_count_min: value.cookie_count_min,
+            cookie_count_max: value.cookie_count_max,
+            raw_cookie_includes: value.raw_cookie_includes,
+        })
+    }
+}
+
+impl TryFrom<&MockDefinition> for StaticMockDefinition {
+    type Error = Error;
+
+    fn try_from(value: &MockDefinition) -> Result<Self, Self::Error> {
+        let value = value.clone();
+
         Ok(StaticMockDefinition {
-            when: StaticRequestRequirements {
-                // Scheme-related fields
-                scheme: value.request.scheme,
-                scheme_not: value.request.scheme_not,
-
-                // Method-related fields
-                method,
-                method_not: to_method_vec(value.request.method_not),
-                // Host-related fields
-                host: value.request.host,
-                host_not: value.request.host_not,
-                host_contains: value.request.host_contains,
-                host_excludes: value.request.host_excludes,
-                host_prefix: value.request.host_prefix,
-                host_suffix: value.request.host_suffix,
-                host_prefix_not: value.request.host_prefix_not,
-                host_suffix_not: value.request.host_suffix_not,
-                host_matches: value.request.host_matches,
-
-                // Port-related fields
-                port: value.request.port,
-                port_not: value.request.port_not,
-
-                // Path-related fields
-                path: value.request.path,
-                path_not: value.request.path_not,
-                path_contains: value.request.path_includes,
-                path_excludes: value.request.path_excludes,
-                path_prefix: value.request.path_prefix,
-                path_suffix: value.request.path_suffix,
-                path_prefix_not: value.request.path_prefix_not,
-                path_suffix_not: value.request.path_suffix_not,
-                path_matches: from_pattern_vec(value.request.path_matches),
-
-                // Header-related fields
-                header: from_string_pair_vec(value.request.header),
-                header_not: from_string_pair_vec(value.request.header_not),
-                header_exists: value.request.header_exists,
-                header_missing: value.request.header_missing,
-                header_contains: to_name_value_string_pair_vec(value.request.header_includes),
-                header_excludes: to_name_value_string_pair_vec(value.request.header_excludes),
-                header_prefix: to_name_value_string_pair_vec(value.request.header_prefix),
-                header_suffix: to_name_value_string_pair_vec(value.request.header_suffix),
-                header_prefix_not: to_name_value_string_pair_vec(value.request.header_prefix_not),
-                header_suffix_not: to_name_value_string_pair_vec(value.request.header_suffix_not),
-                header_matches: to_name_value_pattern_pair_vec(value.request.header_matches),
-                header_count: to_key_value_pattern_count_triple_vec(value.request.header_count),
-
-                // Cookie-related fields
-                cookie: from_string_pair_vec(value.request.cookie),
-                cookie_not: from_string_pair_vec(value.request.cookie_not),
-                cookie_exists: value.request.cookie_exists,
-                cookie_missing: value.request.cookie_missing,
-                cookie_contains: to_name_value_string_pair_vec(value.request.cookie_includes),
-                cookie_excludes: to_name_value_string_pair_vec(value.request.cookie_excludes),
-                cookie_prefix: to_name_value_string_pair_vec(value.request.cookie_prefix),
-                cookie_suffix: to_name_value_string_pair_vec(value.request.cookie_suffix),
-                cookie_prefix_not: to_name_value_string_pair_vec(value.request.cookie_prefix_not),
-                cookie_suffix_not: to_name_value_string_pair_vec(value.request.cookie_suffix_not),
-                cookie_matches: to_name_value_pattern_pair_vec(value.request.cookie_matches),
-
-                cookie_count: to_key_value_pattern_count_triple_vec(value.request.cookie_count),
-
-                // Query Parameter-related fields
-                query_param: from_string_pair_vec(value.request.query_param),
-                query_param_not: from_string_pair_vec(value.request.query_param_not),
-                query_param_exists: value.request.query_param_exists,
-                query_param_missing: value.request.query_param_missing,
-                query_param_contains: to_name_value_string_pair_vec(
-                    value.request.query_param_includes,
-                ),
-                query_param_excludes: to_name_value_string_pair_vec(
-                    value.request.query_param_excludes,
-                ),
-                query_param_prefix: to_name_value_string_pair_vec(value.request.query_param_prefix),
-                query_param_suffix: to_name_value_string_pair_vec(value.request.query_param_suffix),
-                query_param_prefix_not: to_name_value_string_pair_vec(
-                    value.request.query_param_prefix_not,
-                ),
-                query_param_suffix_not: to_name_value_string_pair_vec(
-                    value.request.query_param_suffix_not,
-                ),
-                query_param_matches: to_name_value_pattern_pair_vec(
-                    value.request.query_param_matches,
-                ),
-                query_param_count: to_key_value_pattern_count_triple_vec(
-                    value.request.query_param_count,
-                ),
-
-                // Body-related fields
-                body: request_body,
-                body_base64: request_body_base64,
-                body_not: request_body_not,
-                body_not_base64: request_body_not_base64,
-                body_contains: request_body_includes,
-                body_contains_base64: request_body_includes_base64,
-                body_excludes: request_body_excludes,
-                body_excludes_base64: request_body_excludes_base64,
-                body_prefix: request_body_prefix,
-                body_prefix_base64: request_body_prefix_base64,
-                body_suffix: request_body_suffix,
-                body_suffix_base64: request_body_suffix_base64,
-                body_prefix_not: request_body_prefix_not,
-                body_prefix_not_base64: request_body_prefix_not_base64,
-                body_suffix_not: request_body_suffix_not,
-                body_suffix_not_base64: request_body_suffix_not_base64,
-                body_matches: from_pattern_vec(value.request.body_matches),
-
-                // JSON Body-related fields
-                json_body: value.request.json_body,
-                json_body_not: value.request.json_body_not,
-                json_body_includes: value.request.json_body_includes,
-                json_body_excludes: value.request.json_body_excludes,
-
-                // Form URL-encoded fields
-                form_urlencoded_tuple: from_string_pair_vec(value.request.form_urlencoded_tuple),
-                form_urlencoded_tuple_not: from_string_pair_vec(
-                    value.request.form_urlencoded_tuple_not,
-                ),
-                form_urlencoded_key_exists: value.request.form_urlencoded_tuple_exists,
-                form_urlencoded_key_missing: value.request.form_urlencoded_tuple_missing,
-                form_urlencoded_contains: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_includes,
-                ),
-                form_urlencoded_excludes: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_excludes,
-                ),
-                form_urlencoded_prefix: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_prefix,
-                ),
-                form_urlencoded_suffix: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_suffix,
-                ),
-                form_urlencoded_prefix_not: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_prefix_not,
-                ),
-                form_urlencoded_suffix_not: to_name_value_string_pair_vec(
-                    value.request.form_urlencoded_tuple_suffix_not,
-                ),
-                form_urlencoded_matches: to_name_value_pattern_pair_vec(
-                    value.request.form_urlencoded_tuple_matches,
-                ),
-
-                form_urlencoded_count: to_key_value_pattern_count_triple_vec(
-                    value.request.form_urlencoded_tuple_count,
-                ),
-            },
-            then: StaticHTTPResponse {
-                status: value.response.status,
-                header: from_string_pair_vec(value.response.headers),
-                body: response_body,
-                body_base64: response_body_base64,
-                // Reason for the cast to u64: The Duration::as_millis method returns the total
-                // number of milliseconds contained within the Duration as a u128. This is
-                // because Duration::as_millis needs to handle larger values that
-                // can result from multiplying the seconds (stored internally as a u64)
-                // by 1000 and adding the milliseconds (also a u64), potentially
-                // exceeding the u64 limit.
-                delay: value.response.delay,
-            },
+            when: StaticRequestRequirements::try_from(&value.request)?,
+            then: value.response.into(),
         })
     }
 }
 
+impl From<MockServerHttpResponse> for StaticHTTPResponse {
+    fn from(value: MockServerHttpResponse) -> Self {
+        let (response_body, response_body_base64) = from_bytes_to_string(value.body);
+
+        StaticHTTPResponse {
+            status: value.status,
+            status_from_header: value.status_from_header,
+            reason: value.reason,
+            auto_compress: value.auto_compress,
+            line_delay: value.line_delay,
+            etag: value.etag,
+            last_modified: value.last_modified,
+            conditional: value.conditional,
+            header: from_string_pair_vec(value.headers),
+            trailer: from_string_pair_vec(value.trailers),
+            body: response_body,
+            body_base64: response_body_base64,
+            body_file: value.body_file,
+            json_body_file: None,
+            json_body_merge: value.json_body_merge,
+            body_template: value.body_template,
+            // Reason for the cast to u64: The Duration::as_millis method returns the total
+            // number of milliseconds contained within the Duration as a u128. This is
+            // because Duration::as_millis needs to handle larger values that
+            // can result from multiplying the seconds (stored internally as a u64)
+            // by 1000 and adding the milliseconds (also a u64), potentially
+            // exceeding the u64 limit.
+            delay: value.delay,
+            delay_from_header: value.delay_from_header,
+            fault: value.fault,
+            chunk_size: value.chunk_size,
+            proxy_to: value.proxy_to,
+            duration_ms: value.duration_ms,
+            respond_after: value.respond_after.map(|responses| {
+                responses
+                    .into_iter()
+                    .map(|r| StaticCallCountResponse {
+                        calls: r.calls,
+                        then: r.response.into(),
+                    })
+                    .collect()
+            }),
+            wait_for_concurrent: value.wait_for_concurrent,
+            wait_for_concurrent_timeout: value.wait_for_concurrent_timeout,
+            omit_headers: value.omit_headers,
+            rate_limited: None,
+        }
+    }
+}
+
 /// Represents an HTTP method.
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Method {
@@ -1836,3 +3424,96 @@ impl std::fmt::Display for Method {
         std::fmt::Debug::fmt(self, f)
     }
 }
+
+#[cfg(test)]
+mod forwarding_rule_config_test {
+    use crate::common::data::{ForwardingRuleConfig, RequestHeaderChange, RequestRequirements};
+    use serde_json::json;
+
+    #[test]
+    fn reads_request_headers_of_older_clients() {
+        let config: ForwardingRuleConfig = serde_json::from_value(json!({
+            "target_base_url": "http://localhost:8080",
+            "request_requirements": RequestRequirements::new(),
+            "request_header": [["authorization", "Bearer token"]],
+        }))
+        .unwrap();
+
+        assert_eq!(
+            config.request_header_changes,
+            vec![RequestHeaderChange::Add(
+                "authorization".to_string(),
+                "Bearer token".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn reads_request_header_changes() {
+        let mut config = ForwardingRuleConfig::default();
+        config.request_header_changes = vec![
+            RequestHeaderChange::Append("x-tag".to_string(), "one".to_string()),
+            RequestHeaderChange::Remove("x-secret".to_string()),
+        ];
+
+        let config: ForwardingRuleConfig =
+            serde_json::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+
+        assert_eq!(
+            config.request_header_changes,
+            vec![
+                RequestHeaderChange::Append("x-tag".to_string(), "one".to_string()),
+                RequestHeaderChange::Remove("x-secret".to_string()),
+            ]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "json-schema"))]
+mod json_schema_test {
+    use crate::common::data::JsonSchema;
+    use serde_json::json;
+
+    #[test]
+    fn compiles_schema_only_once() {
+        let schema: JsonSchema = serde_json::from_value(json!({ "type": "object" })).unwrap();
+        let first = schema.validator().unwrap();
+
+        // Clones of a compiled schema share the validator instead of compiling it again.
+        let copy = schema.clone();
+        assert!(std::ptr::eq(first, schema.validator().unwrap()));
+        assert!(std::ptr::eq(first, copy.validator().unwrap()));
+
+        assert!(first.is_valid(&json!({})));
+        assert_eq!(
+            serde_json::to_value(&copy).unwrap(),
+            json!({ "type": "object" })
+        );
+    }
+
+    #[test]
+    fn keeps_compilation_error() {
+        let schema = JsonSchema::from(json!({ "type": 12 }));
+        assert!(schema
+            .validator()
+            .unwrap_err()
+            .starts_with("invalid JSON schema"));
+    }
+}
+
+#[cfg(test)]
+mod path_template_test {
+    use crate::common::data::PathTemplate;
+
+    #[test]
+    fn builds_regex_only_once() {
+        let template = PathTemplate::from("/users/{id}");
+        let first = template.regex().unwrap();
+
+        // Clones of a template keep the regex that was already built.
+        let copy = template.clone();
+        assert!(std::ptr::eq(first, template.regex().unwrap()));
+        assert!(first.0.is_match("/users/42"));
+        assert_eq!(copy.regex().unwrap().0.as_str(), first.0.as_str());
+    }
+}
@@ -8,10 +8,42 @@ use hyper_util::{
     client::legacy::{connect::HttpConnector, Client},
     rt::TokioExecutor,
 };
-use std::{convert::TryInto, sync::Arc};
+use std::{convert::TryInto, future::Future, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::runtime::Runtime;
 
+#[cfg(feature = "proxy")]
+use http::Uri;
+#[cfg(feature = "proxy")]
+use hyper_util::{
+    client::{
+        legacy::connect::{
+            proxy::{SocksV5, Tunnel},
+            Connected, Connection,
+        },
+        proxy::matcher::Matcher,
+    },
+    rt::TokioIo,
+};
+#[cfg(feature = "proxy")]
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{verify_tls12_signature, verify_tls13_signature},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    DigitallySignedStruct, SignatureScheme,
+};
+#[cfg(feature = "proxy")]
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+#[cfg(feature = "proxy")]
+use tokio::net::TcpStream;
+#[cfg(feature = "proxy")]
+use tower_service::Service;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("cannot send request: {0}")]
@@ -20,21 +52,159 @@ pub enum Error {
     HyperUtilError(#[from] hyper_util::client::legacy::Error),
     #[error("runtime error: {0}")]
     RuntimeError(#[from] tokio::task::JoinError),
+    #[error("no response received within {0:?}")]
+    Timeout(Duration),
+    #[error("invalid upstream proxy: {0}")]
+    InvalidProxy(String),
     #[error("unknown error")]
     Unknown,
 }
 
+impl Error {
+    /// Returns whether the request could not be sent to the upstream server or no response
+    /// was received (e.g., connection refused, DNS failure or timeout), as opposed to an
+    /// invalid configuration.
+    pub(crate) fn is_upstream_failure(&self) -> bool {
+        matches!(
+            self,
+            Error::HyperError(_) | Error::HyperUtilError(_) | Error::Timeout(_)
+        )
+    }
+
+    /// Returns the error message followed by the messages of all underlying errors, e.g.,
+    /// `cannot send request: client error (Connect): tcp connect error: Connection refused`.
+    pub(crate) fn detailed_message(&self) -> String {
+        let mut message = self.to_string();
+        let mut source = std::error::Error::source(self);
+        while let Some(err) = source {
+            message.push_str(": ");
+            message.push_str(&err.to_string());
+            source = err.source();
+        }
+        message
+    }
+}
+
 #[async_trait]
 pub trait HttpClient {
     async fn send(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error>;
+
+    /// Sends a request and fails with [`Error::Timeout`] if the connection could not be
+    /// established or no response headers were received within `timeout`. Receiving the
+    /// response body is not limited by the timeout.
+    async fn send_with_timeout(
+        &self,
+        req: Request<Bytes>,
+        timeout: Duration,
+    ) -> Result<Response<Bytes>, Error>;
+
+    /// Sends a request to an upstream server using the given connection settings. If the
+    /// upstream proxy does not apply to the request destination (see
+    /// [`UpstreamProxy::Environment`]), the request is sent directly. The `timeout` behaves like
+    /// in [`HttpClient::send_with_timeout`].
+    #[cfg(feature = "proxy")]
+    async fn send_upstream(
+        &self,
+        req: Request<Bytes>,
+        connection: &UpstreamConnection,
+        timeout: Option<Duration>,
+    ) -> Result<Response<Bytes>, Error>;
+}
+
+/// Describes how connections to an upstream server are established.
+#[cfg(feature = "proxy")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UpstreamConnection {
+    pub proxy: UpstreamProxy,
+    /// Accepts any TLS certificate presented by the upstream server, including self-signed
+    /// and expired certificates or certificates for a different host name.
+    pub danger_accept_invalid_certs: bool,
+}
+
+/// Describes the proxy server through which requests are sent to an upstream server.
+#[cfg(feature = "proxy")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UpstreamProxy {
+    /// Uses the proxy configured by the `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY`
+    /// environment variables (or their lowercase variants), except for loopback destinations
+    /// and destinations listed in `NO_PROXY`. Requests are sent directly if no proxy is
+    /// configured.
+    Environment,
+    /// Sends all requests through the proxy with the given URL. Supported URL schemes are
+    /// `http`, `socks5` and `socks5h`.
+    Url(String),
+    /// Sends all requests directly to the upstream server.
+    None,
+}
+
+#[cfg(feature = "proxy")]
+impl UpstreamProxy {
+    fn matcher(&self) -> Result<Matcher, Error> {
+        match self {
+            UpstreamProxy::Environment => {
+                let env = |names: &[&str]| {
+                    names
+                        .iter()
+                        .find_map(|name| std::env::var(name).ok())
+                        .unwrap_or_default()
+                };
+
+                // Loopback addresses are never proxied, as they usually point to other mock
+                // servers or services started by the test itself.
+                let no_proxy = format!(
+                    "{},localhost,127.0.0.0/8,::1",
+                    env(&["NO_PROXY", "no_proxy"])
+                );
+
+                Ok(Matcher::builder()
+                    .all(env(&["ALL_PROXY", "all_proxy"]))
+                    .http(env(&["HTTP_PROXY", "http_proxy"]))
+                    .https(env(&["HTTPS_PROXY", "https_proxy"]))
+                    .no(no_proxy)
+                    .build())
+            }
+            UpstreamProxy::Url(url) => {
+                validate_proxy_url(url)?;
+                Ok(Matcher::builder().all(url.clone()).build())
+            }
+            UpstreamProxy::None => Ok(Matcher::builder().build()),
+        }
+    }
+}
+
+/// Checks that `url` is an absolute URL with a proxy scheme that is supported by
+/// [`HttpClient::send_upstream`].
+#[cfg(feature = "proxy")]
+pub fn validate_proxy_url(url: &str) -> Result<(), Error> {
+    let uri: Uri = url
+        .parse()
+        .map_err(|err| Error::InvalidProxy(format!("{}: {}", url, err)))?;
+
+    match (uri.scheme_str(), uri.host()) {
+        (Some("http" | "socks5" | "socks5h"), Some(_)) => Ok(()),
+        (Some(scheme), Some(_)) => Err(Error::InvalidProxy(format!(
+            "{}: unsupported scheme '{}' (expected http, socks5 or socks5h)",
+            url, scheme
+        ))),
+        _ => Err(Error::InvalidProxy(format!(
+            "{}: expected an absolute URL, such as http://proxy:3128",
+            url
+        ))),
+    }
 }
 
+#[cfg(feature = "proxy")]
+type ProxyClient = Client<HttpsConnector<ProxyConnector>, Full<Bytes>>;
+
 pub struct HttpMockHttpClient {
     runtime: Option<Arc<Runtime>>,
     #[cfg(any(feature = "remote-https", feature = "https"))]
     client: Arc<Client<HttpsConnector<HttpConnector>, Full<Bytes>>>,
     #[cfg(not(any(feature = "remote-https", feature = "https")))]
     client: Arc<Client<HttpConnector, Full<Bytes>>>,
+    /// Clients that send requests to upstream servers, created on first use.
+    #[cfg(feature = "proxy")]
+    upstream_clients: Mutex<HashMap<UpstreamConnection, (Arc<Matcher>, Arc<ProxyClient>)>>,
 }
 
 impl<'a> HttpMockHttpClient {
@@ -57,6 +227,8 @@ impl<'a> HttpMockHttpClient {
         Self {
             runtime,
             client: Arc::new(Client::builder(TokioExecutor::new()).build(https_connector)),
+            #[cfg(feature = "proxy")]
+            upstream_clients: Mutex::new(HashMap::new()),
         }
     }
 
@@ -69,18 +241,32 @@ impl<'a> HttpMockHttpClient {
     }
 }
 
-#[async_trait]
-impl HttpClient for HttpMockHttpClient {
-    async fn send(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+impl HttpMockHttpClient {
+    async fn request(
+        &self,
+        req: Request<Bytes>,
+        timeout: Option<Duration>,
+    ) -> Result<Response<Bytes>, Error> {
+        self.execute(self.client.clone(), req, timeout).await
+    }
+
+    async fn execute<C>(
+        &self,
+        client: Arc<Client<C, Full<Bytes>>>,
+        req: Request<Bytes>,
+        timeout: Option<Duration>,
+    ) -> Result<Response<Bytes>, Error>
+    where
+        C: hyper_util::client::legacy::connect::Connect + Clone + Send + Sync + 'static,
+    {
         let (req_parts, req_body) = req.into_parts();
         let hyper_req = Request::from_parts(req_parts, Full::new(req_body));
 
         let res = if let Some(rt) = self.runtime.clone() {
-            let client = self.client.clone();
-            rt.spawn(async move { client.request(hyper_req).await })
+            rt.spawn(async move { with_timeout(timeout, client.request(hyper_req)).await })
                 .await??
         } else {
-            self.client.request(hyper_req).await?
+            with_timeout(timeout, client.request(hyper_req)).await?
         };
 
         let (res_parts, res_body) = res.into_parts();
@@ -88,4 +274,288 @@ impl HttpClient for HttpMockHttpClient {
 
         return Ok(Response::from_parts(res_parts, body));
     }
+
+    #[cfg(feature = "proxy")]
+    fn upstream_client(
+        &self,
+        connection: &UpstreamConnection,
+    ) -> Result<(Arc<Matcher>, Arc<ProxyClient>), Error> {
+        let mut clients = self.upstream_clients.lock().unwrap();
+        if let Some(entry) = clients.get(connection) {
+            return Ok(entry.clone());
+        }
+
+        let matcher = Arc::new(connection.proxy.matcher()?);
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        let builder = hyper_rustls::HttpsConnectorBuilder::new();
+        let builder = if connection.danger_accept_invalid_certs {
+            builder.with_tls_config(danger_accept_invalid_certs_config())
+        } else {
+            builder
+                .with_native_roots()
+                .expect("cannot set up using native root certificates")
+        };
+
+        let https_connector = builder
+            .https_or_http()
+            .enable_all_versions()
+            .wrap_connector(ProxyConnector {
+                http: http_connector,
+                matcher: matcher.clone(),
+            });
+
+        let client = Arc::new(Client::builder(TokioExecutor::new()).build(https_connector));
+        clients.insert(connection.clone(), (matcher.clone(), client.clone()));
+
+        Ok((matcher, client))
+    }
+}
+
+#[async_trait]
+impl HttpClient for HttpMockHttpClient {
+    async fn send(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+        self.request(req, None).await
+    }
+
+    async fn send_with_timeout(
+        &self,
+        req: Request<Bytes>,
+        timeout: Duration,
+    ) -> Result<Response<Bytes>, Error> {
+        self.request(req, Some(timeout)).await
+    }
+
+    #[cfg(feature = "proxy")]
+    async fn send_upstream(
+        &self,
+        mut req: Request<Bytes>,
+        connection: &UpstreamConnection,
+        timeout: Option<Duration>,
+    ) -> Result<Response<Bytes>, Error> {
+        let (matcher, client) = self.upstream_client(connection)?;
+
+        // Plain HTTP requests are not tunneled but sent to the proxy in absolute-form, so
+        // proxy credentials need to be added to the request itself.
+        if req.uri().scheme_str() == Some("http") {
+            if let Some(intercept) = matcher.intercept(req.uri()) {
+                if let Some(auth) = intercept.basic_auth() {
+                    req.headers_mut()
+                        .insert(http::header::PROXY_AUTHORIZATION, auth.clone());
+                }
+            }
+        }
+
+        self.execute(client, req, timeout).await
+    }
+}
+
+/// Creates a TLS client configuration that accepts any server certificate. This is only meant
+/// for upstream servers in test environments whose certificates are not trusted by the host.
+#[cfg(feature = "proxy")]
+fn danger_accept_invalid_certs_config() -> rustls::ClientConfig {
+    let provider = rustls::crypto::CryptoProvider::get_default()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+
+    rustls::ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert(provider)))
+        .with_no_client_auth()
+}
+
+/// A certificate verifier that accepts any server certificate. Handshake signatures are still
+/// verified, so that the connection is established with the server that presented the
+/// certificate.
+#[cfg(feature = "proxy")]
+#[derive(Debug)]
+struct AcceptAnyServerCert(Arc<rustls::crypto::CryptoProvider>);
+
+#[cfg(feature = "proxy")]
+impl ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Establishes connections to upstream servers, either directly or through the proxy that
+/// the [`Matcher`] selects for the destination. Plain HTTP requests are sent to HTTP proxies
+/// in absolute-form, while HTTPS connections are tunneled using `CONNECT`.
+#[cfg(feature = "proxy")]
+#[derive(Clone)]
+struct ProxyConnector {
+    http: HttpConnector,
+    matcher: Arc<Matcher>,
+}
+
+#[cfg(feature = "proxy")]
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+#[cfg(feature = "proxy")]
+impl Service<Uri> for ProxyConnector {
+    type Response = ProxyStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<ProxyStream, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let intercept = self.matcher.intercept(&dst);
+
+        Box::pin(async move {
+            let intercept = match intercept {
+                Some(intercept) => intercept,
+                None => return Ok(ProxyStream::new(http.call(dst).await?, false)),
+            };
+
+            let proxy_uri = intercept.uri().clone();
+            match (proxy_uri.scheme_str(), dst.scheme_str()) {
+                (Some("http"), Some("http")) => {
+                    Ok(ProxyStream::new(http.call(proxy_uri).await?, true))
+                }
+                (Some("http"), _) => {
+                    let mut tunnel = Tunnel::new(proxy_uri, http);
+                    if let Some(auth) = intercept.basic_auth() {
+                        tunnel = tunnel.with_auth(auth.clone());
+                    }
+                    Ok(ProxyStream::new(tunnel.call(dst).await?, false))
+                }
+                (Some(scheme @ ("socks5" | "socks5h")), _) => {
+                    let mut socks =
+                        SocksV5::new(proxy_uri.clone(), http).local_dns(scheme == "socks5");
+                    if let Some((user, pass)) = intercept.raw_auth() {
+                        socks = socks.with_auth(user.to_string(), pass.to_string());
+                    }
+                    Ok(ProxyStream::new(socks.call(dst).await?, false))
+                }
+                _ => Err(Error::InvalidProxy(format!(
+                    "{}: unsupported scheme (expected http, socks5 or socks5h)",
+                    proxy_uri
+                ))
+                .into()),
+            }
+        })
+    }
+}
+
+/// A connection to an upstream server or proxy. Connections to HTTP proxies that are not
+/// tunneled are marked as proxied, so that requests are written in absolute-form.
+#[cfg(feature = "proxy")]
+struct ProxyStream {
+    io: TokioIo<TcpStream>,
+    absolute_form: bool,
+}
+
+#[cfg(feature = "proxy")]
+impl ProxyStream {
+    fn new(io: TokioIo<TcpStream>, absolute_form: bool) -> Self {
+        Self { io, absolute_form }
+    }
+}
+
+#[cfg(feature = "proxy")]
+impl Connection for ProxyStream {
+    fn connected(&self) -> Connected {
+        self.io.connected().proxy(self.absolute_form)
+    }
+}
+
+#[cfg(feature = "proxy")]
+impl hyper::rt::Read for ProxyStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper::rt::ReadBufCursor<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_read(cx, buf)
+    }
+}
+
+#[cfg(feature = "proxy")]
+impl hyper::rt::Write for ProxyStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.io).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.io).poll_write_vectored(cx, bufs)
+    }
+}
+
+async fn with_timeout<F, T, E>(timeout: Option<Duration>, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, E>>,
+    Error: From<E>,
+{
+    match timeout {
+        None => Ok(future.await?),
+        Some(duration) => match tokio::time::timeout(duration, future).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(Error::Timeout(duration)),
+        },
+    }
 }
@@ -17,6 +17,16 @@ where
     LocalSet::new().block_on(&mut runtime, f)
 }
 
+/// Builds a multi-threaded runtime for a server that owns its runtime. If no number of worker
+/// threads is given, Tokio's default (one per CPU core) applies.
+pub(crate) fn new_server_runtime(worker_threads: Option<usize>) -> std::io::Result<Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    if let Some(worker_threads) = worker_threads {
+        builder.worker_threads(worker_threads);
+    }
+    builder.enable_all().build()
+}
+
 pub(crate) fn new(worker_threads: usize, blocking_threads: usize) -> std::io::Result<Runtime> {
     assert!(
         worker_threads > 0,
@@ -341,3 +341,352 @@ pub fn is_none_or_empty<T>(option: &Option<Vec<T>>) -> bool {
         Some(vec) => vec.is_empty(),
     }
 }
+
+/// Checks that a reason phrase can be written to an HTTP/1 status line as is. Only horizontal
+/// tabs, spaces and visible ASCII characters are allowed. Obsolete non-ASCII text is rejected,
+/// because clients may decode it differently.
+pub fn is_valid_reason_phrase(phrase: &str) -> bool {
+    phrase
+        .bytes()
+        .all(|b| b == b'\t' || b == b' ' || b.is_ascii_graphic())
+}
+
+/// Brings an HTTP version into the form that the mock server uses for received requests
+/// (e.g., `HTTP/1.1` or `HTTP/2.0`). The `HTTP/` prefix is optional and case-insensitive, and
+/// a missing minor version is added, so that `http/2` and `2` both become `HTTP/2.0`.
+pub fn normalize_http_version(version: &str) -> String {
+    let version = version.trim();
+    let number = match version.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("HTTP/") => &version[5..],
+        _ => version,
+    };
+
+    if number.contains('.') {
+        format!("HTTP/{}", number)
+    } else {
+        format!("HTTP/{}.0", number)
+    }
+}
+
+/// Parses an absolute URL and serializes it in normalized form: the scheme and host are
+/// lowercase, the default port of the scheme is omitted and an empty path becomes `/`.
+pub fn normalize_url(url: &str) -> Result<String, url::ParseError> {
+    url::Url::parse(url).map(|url| url.to_string())
+}
+
+/// Generates `size` pseudo-random bytes from a seed with the SplitMix64 generator. The output
+/// only depends on the seed and size, so it is the same on every platform and in every version.
+pub fn seeded_random_bytes(size: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    let mut bytes = Vec::with_capacity(size + 8);
+
+    while bytes.len() < size {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        bytes.extend_from_slice(&(z ^ (z >> 31)).to_le_bytes());
+    }
+
+    bytes.truncate(size);
+    bytes
+}
+
+/// Deep-merges `partial` onto `base`. Objects are merged key by key, while all other values
+/// (including arrays) replace the corresponding value in `base` wholesale.
+pub fn merge_json(base: &mut serde_json::Value, partial: &serde_json::Value) {
+    match (base, partial) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(partial)) => {
+            for (key, value) in partial {
+                match base.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, partial) => *base = partial.clone(),
+    }
+}
+
+/// Parses `body` as a JSON object and deep-merges all `partials` onto it in order
+/// (see [`merge_json`]).
+pub fn merge_json_body(body: &[u8], partials: &[serde_json::Value]) -> Result<Bytes, String> {
+    let mut base: serde_json::Value = serde_json::from_slice(body)
+        .map_err(|err| format!("the base response body is not valid JSON: {}", err))?;
+
+    if !base.is_object() {
+        return Err("the base response body is not a JSON object".to_string());
+    }
+
+    for partial in partials {
+        if !partial.is_object() {
+            return Err(format!(
+                "the partial JSON body {} is not a JSON object",
+                partial
+            ));
+        }
+        merge_json(&mut base, partial);
+    }
+
+    Ok(Bytes::from(base.to_string()))
+}
+
+/// Renders a response template by replacing every `{{ name }}` placeholder with the value that
+/// `resolve` returns for the (trimmed) placeholder name. Placeholders that cannot be resolved
+/// are rendered as an empty string and a warning is logged.
+pub fn render_template<F: Fn(&str) -> Option<String>>(template: &str, resolve: F) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start..].find("}}") {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        output.push_str(&rest[..start]);
+
+        let name = rest[start + 2..end].trim();
+        match resolve(name) {
+            Some(value) => output.push_str(&value),
+            None => log::warn!("cannot render unknown template placeholder '{}'", name),
+        }
+
+        rest = &rest[end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Checks that every `{{` in a response template is closed by `}}` and that no placeholder
+/// is empty. Returns a message describing the first problem found.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .map(|end| start + end)
+            .ok_or_else(|| "template contains an unclosed '{{' placeholder".to_string())?;
+
+        if rest[start + 2..end].trim().is_empty() {
+            return Err("template contains an empty placeholder".to_string());
+        }
+
+        rest = &rest[end + 2..];
+    }
+
+    Ok(())
+}
+
+/// A single segment of a JSON path expression (see [`parse_json_path`]).
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// Parses a simple JSON path expression, such as `$.user.token`, `items[0].id` or
+/// `items[*].id`. Object keys are separated by dots, array elements are addressed by their
+/// index in square brackets. A `*` matches all array elements or object values.
+pub fn parse_json_path(expr: &str) -> Result<Vec<JsonPathSegment>, String> {
+    let path = expr.strip_prefix('$').unwrap_or(expr);
+    let path = path.strip_prefix('.').unwrap_or(path);
+
+    let mut segments = Vec::new();
+    if path.is_empty() {
+        return Err(format!("JSON path '{}' does not address any value", expr));
+    }
+
+    for part in path.split('.') {
+        let (key, mut indexes) = match part.find('[') {
+            Some(idx) => part.split_at(idx),
+            None => (part, ""),
+        };
+
+        match key {
+            "" if indexes.is_empty() => {
+                return Err(format!("JSON path '{}' contains an empty segment", expr))
+            }
+            "" => {}
+            "*" => segments.push(JsonPathSegment::Wildcard),
+            key => segments.push(JsonPathSegment::Key(key.to_string())),
+        }
+
+        while !indexes.is_empty() {
+            let end = match (indexes.starts_with('['), indexes.find(']')) {
+                (true, Some(end)) => end,
+                _ => return Err(format!("JSON path '{}' contains an invalid index", expr)),
+            };
+
+            let segment = match &indexes[1..end] {
+                "*" => JsonPathSegment::Wildcard,
+                idx => JsonPathSegment::Index(
+                    idx.parse()
+                        .map_err(|_| format!("JSON path '{}' contains an invalid index", expr))?,
+                ),
+            };
+
+            segments.push(segment);
+            indexes = &indexes[end + 1..];
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod merge_json_test {
+    use crate::common::util::{merge_json, merge_json_body};
+    use serde_json::json;
+
+    #[test]
+    fn merges_objects_recursively_and_replaces_arrays() {
+        let mut base = json!({
+            "name": "Peter",
+            "address": { "town": "Quahog", "zip": "00093" },
+            "tags": ["a", "b"]
+        });
+
+        merge_json(
+            &mut base,
+            &json!({ "address": { "town": "Springfield" }, "tags": ["c"], "age": 45 }),
+        );
+
+        assert_eq!(
+            base,
+            json!({
+                "name": "Peter",
+                "address": { "town": "Springfield", "zip": "00093" },
+                "tags": ["c"],
+                "age": 45
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_non_object_bodies() {
+        assert!(merge_json_body(b"[1, 2]", &[json!({ "a": 1 })]).is_err());
+        assert!(merge_json_body(b"not json", &[json!({ "a": 1 })]).is_err());
+        assert!(merge_json_body(b"{}", &[json!([1])]).is_err());
+        assert_eq!(
+            merge_json_body(br#"{"a": 1}"#, &[json!({ "b": 2 })]).unwrap(),
+            bytes::Bytes::from(r#"{"a":1,"b":2}"#)
+        );
+    }
+}
+
+#[cfg(test)]
+mod seeded_random_bytes_test {
+    use crate::common::util::seeded_random_bytes;
+
+    #[test]
+    fn generates_the_splitmix64_sequence() {
+        assert_eq!(
+            seeded_random_bytes(10, 0),
+            vec![0xaf, 0xcd, 0x1d, 0x7b, 0x39, 0xa8, 0x20, 0xe2, 0xf4, 0x65]
+        );
+        assert_eq!(
+            seeded_random_bytes(8, 42),
+            vec![0x95, 0x6e, 0xeb, 0x2f, 0x26, 0x32, 0xd7, 0xbd]
+        );
+        assert!(seeded_random_bytes(0, 42).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod render_template_test {
+    use crate::common::util::{render_template, validate_template};
+
+    #[test]
+    fn replaces_known_and_removes_unknown_placeholders() {
+        let resolve = |name: &str| (name == "path.id").then(|| "42".to_string());
+
+        assert_eq!(
+            render_template(r#"{"id": {{ path.id }}, "name": "{{path.name}}"}"#, resolve),
+            r#"{"id": 42, "name": ""}"#
+        );
+        assert_eq!(
+            render_template("no placeholders", resolve),
+            "no placeholders"
+        );
+        assert_eq!(
+            render_template("unclosed {{ path.id", resolve),
+            "unclosed {{ path.id"
+        );
+    }
+
+    #[test]
+    fn validates_placeholders() {
+        assert!(validate_template("{{ path.id }} and {{query.q}}").is_ok());
+        assert!(validate_template("no placeholders").is_ok());
+        assert!(validate_template("unclosed {{ path.id")
+            .unwrap_err()
+            .contains("unclosed"));
+        assert!(validate_template("empty {{ }}")
+            .unwrap_err()
+            .contains("empty placeholder"));
+    }
+}
+
+#[cfg(test)]
+mod parse_json_path_test {
+    use crate::common::util::{parse_json_path, JsonPathSegment::*};
+
+    #[test]
+    fn parses_keys_indexes_and_wildcards() {
+        assert_eq!(
+            parse_json_path("$.user.token"),
+            Ok(vec![Key("user".into()), Key("token".into())])
+        );
+        assert_eq!(
+            parse_json_path("items[0].id"),
+            Ok(vec![Key("items".into()), Index(0), Key("id".into())])
+        );
+        assert_eq!(
+            parse_json_path("$[*].tokens.*"),
+            Ok(vec![Wildcard, Key("tokens".into()), Wildcard])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(parse_json_path("$").is_err());
+        assert!(parse_json_path("user..token").is_err());
+        assert!(parse_json_path("items[x]").is_err());
+        assert!(parse_json_path("items[0").is_err());
+        assert!(parse_json_path("items[0]x").is_err());
+    }
+}
+
+#[cfg(test)]
+mod normalize_http_version_test {
+    use crate::common::util::normalize_http_version;
+
+    #[test]
+    fn adds_prefix_and_minor_version() {
+        assert_eq!(normalize_http_version("HTTP/1.1"), "HTTP/1.1");
+        assert_eq!(normalize_http_version("http/1.0"), "HTTP/1.0");
+        assert_eq!(normalize_http_version("HTTP/2"), "HTTP/2.0");
+        assert_eq!(normalize_http_version("2"), "HTTP/2.0");
+        assert_eq!(normalize_http_version(" 1.1 "), "HTTP/1.1");
+    }
+}
+
+#[cfg(test)]
+mod reason_phrase_test {
+    use crate::common::util::is_valid_reason_phrase;
+
+    #[test]
+    fn accepts_only_tabs_spaces_and_visible_ascii() {
+        assert!(is_valid_reason_phrase("I'm a teapot"));
+        assert!(is_valid_reason_phrase("Custom\tReason"));
+        assert!(is_valid_reason_phrase(""));
+        assert!(!is_valid_reason_phrase("OK\r\nX-Injected: true"));
+        assert!(!is_valid_reason_phrase("Très bien"));
+    }
+}
@@ -105,7 +105,12 @@ use serde::{Deserialize, Serialize};
 use api::MockServerAdapter;
 use common::util::Join;
 
-pub use api::{Method, Mock, MockExt, MockServer, Regex, Then, When};
+pub use api::{
+    ClosestMatch, ConditionGroup, Fault, Method, Mismatch, Mock, MockExt, MockServer,
+    MockServerPool, MockStats, PooledMockServer, Regex, Then, When,
+};
+
+pub use common::data::{ActiveMock, HttpMockRequest, HttpMockRequestBuilder, RawRequest};
 
 mod api;
 pub mod common;
@@ -114,8 +119,13 @@ pub mod server;
 #[cfg(feature = "record")]
 pub use api::{Recording, RecordingRuleBuilder};
 
+#[cfg(feature = "protobuf")]
+pub use api::ProtobufDescriptor;
+
 #[cfg(feature = "proxy")]
-pub use api::{ForwardingRule, ForwardingRuleBuilder, ProxyRule, ProxyRuleBuilder};
+pub use api::{
+    CallbackRequestBuilder, ForwardingRule, ForwardingRuleBuilder, ProxyRule, ProxyRuleBuilder,
+};
 
 pub mod prelude {
     #[doc(no_inline)]
@@ -1,8 +1,8 @@
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, time::Duration};
 
 use clap::Parser;
 
-use httpmock::server::HttpMockServerBuilder;
+use httpmock::server::{AccessLogFormat, HttpMockServerBuilder, MatchStrategy};
 
 /// Holds command line parameters provided by the user.
 #[derive(Parser, Debug)]
@@ -17,8 +17,12 @@ struct ExecutionParameters {
     pub expose: bool,
     #[clap(short, long, env = "HTTPMOCK_MOCK_FILES_DIR")]
     pub mock_files_dir: Option<PathBuf>,
+    #[clap(long, env = "HTTPMOCK_WATCH_MOCK_FILES_DIR")]
+    pub watch_mock_files_dir: bool,
     #[clap(short, long, env = "HTTPMOCK_DISABLE_ACCESS_LOG")]
     pub disable_access_log: bool,
+    #[clap(long, env = "HTTPMOCK_ACCESS_LOG_FORMAT", default_value = "text")]
+    pub access_log_format: AccessLogFormat,
     #[clap(
         short,
         long,
@@ -26,10 +30,29 @@ struct ExecutionParameters {
         default_value = "100"
     )]
     pub request_history_limit: usize,
+    #[clap(long, env = "HTTPMOCK_DEBUG_HEADERS")]
+    pub debug_headers: bool,
+    #[clap(long, env = "HTTPMOCK_ENABLE_METRICS")]
+    pub enable_metrics: bool,
+    #[clap(long, env = "HTTPMOCK_MAX_REQUEST_BODY_SIZE")]
+    pub max_request_body_size: Option<usize>,
+    #[clap(long, env = "HTTPMOCK_DISABLE_KEEP_ALIVE")]
+    pub disable_keep_alive: bool,
+    #[clap(long, env = "HTTPMOCK_IDLE_TIMEOUT_SECS")]
+    pub idle_timeout_secs: Option<u64>,
+    #[clap(
+        long,
+        env = "HTTPMOCK_MATCH_STRATEGY",
+        default_value = "highest-priority"
+    )]
+    pub match_strategy: MatchStrategy,
+    #[clap(long, env = "HTTPMOCK_SHUTDOWN_TIMEOUT_SECS")]
+    pub shutdown_timeout_secs: Option<u64>,
+    #[clap(long, env = "HTTPMOCK_WORKERS")]
+    pub workers: Option<usize>,
 }
 
-#[tokio::main]
-async fn main() {
+fn main() {
     env_logger::init_from_env(env_logger::Env::default().default_filter_or("httpmock=info"));
 
     let params: ExecutionParameters = ExecutionParameters::parse();
@@ -53,14 +76,23 @@ async fn main() {
         .port(params.port)
         .expose(params.expose)
         .print_access_log(!params.disable_access_log)
+        .access_log_format(params.access_log_format)
         .history_limit(params.request_history_limit)
+        .match_strategy(params.match_strategy)
+        .debug_headers(params.debug_headers)
+        .enable_metrics(params.enable_metrics)
+        .max_request_body_size_option(params.max_request_body_size)
+        .keep_alive(!params.disable_keep_alive)
+        .idle_timeout_option(params.idle_timeout_secs.map(Duration::from_secs))
+        .shutdown_timeout_option(params.shutdown_timeout_secs.map(Duration::from_secs))
+        .worker_threads_option(params.workers)
         .static_mock_dir_option(params.mock_files_dir)
+        .watch_mock_dir(params.watch_mock_files_dir)
         .build()
         .unwrap();
 
     server
-        .start_with_signals(None, shutdown_signal())
-        .await
+        .run_with_signals(None, shutdown_signal())
         .expect("an error occurred during mock server execution");
 }
 
@@ -0,0 +1,135 @@
+use serde::Serialize;
+use std::{fmt, str::FromStr, time::Duration};
+
+/// The format of the access log that the mock server prints if
+/// [`HttpMockServerBuilder::print_access_log`](crate::server::HttpMockServerBuilder::print_access_log)
+/// is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessLogFormat {
+    /// Writes a human-readable line per request to the log at info level.
+    Text,
+    /// Writes a JSON object per request to standard output, one object per line (JSON lines).
+    ///
+    /// Each object has the following fields:
+    /// - `method`: The request method, e.g. `"GET"`.
+    /// - `path`: The request path without the query string, e.g. `"/users/1"`.
+    /// - `status`: The response status code, or `null` if the connection was closed without
+    ///   a response (e.g., because of an injected fault).
+    /// - `matched`: Whether the request matched a mock.
+    /// - `mock_id`: The ID of the mock that served the request, or `null`.
+    /// - `duration_ms`: The time it took to handle the request in milliseconds, including any
+    ///   configured response delay.
+    Json,
+}
+
+impl Default for AccessLogFormat {
+    fn default() -> Self {
+        AccessLogFormat::Text
+    }
+}
+
+impl FromStr for AccessLogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "text" => Ok(AccessLogFormat::Text),
+            "json" => Ok(AccessLogFormat::Json),
+            _ => Err(format!(
+                "unknown access log format '{}': expected 'text' or 'json'",
+                value
+            )),
+        }
+    }
+}
+
+/// A single entry of the access log. The field names are part of the documented JSON access
+/// log format (see [`AccessLogFormat::Json`]) and must not change.
+#[derive(Serialize, Debug)]
+pub(crate) struct AccessLogEntry {
+    pub method: String,
+    pub path: String,
+    pub status: Option<u16>,
+    pub matched: bool,
+    pub mock_id: Option<usize>,
+    pub duration_ms: f64,
+}
+
+impl AccessLogEntry {
+    pub(crate) fn new(method: String, path: String, duration: Duration) -> Self {
+        Self {
+            method,
+            path,
+            status: None,
+            matched: false,
+            mock_id: None,
+            duration_ms: duration.as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub(crate) fn print(&self, format: AccessLogFormat) {
+        match format {
+            AccessLogFormat::Text => log::info!("{}", self),
+            AccessLogFormat::Json => match serde_json::to_string(self) {
+                Ok(line) => println!("{}", line),
+                Err(err) => log::warn!("cannot serialize access log entry: {}", err),
+            },
+        }
+    }
+}
+
+impl fmt::Display for AccessLogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} -> ", self.method, self.path)?;
+
+        match self.status {
+            Some(status) => write!(f, "{}", status)?,
+            None => write!(f, "no response")?,
+        }
+
+        match (self.matched, self.mock_id) {
+            (true, Some(id)) => write!(f, " (mock {})", id)?,
+            (true, None) => write!(f, " (matched)")?,
+            (false, _) => write!(f, " (no matching mock)")?,
+        }
+
+        write!(f, " in {:.3} ms", self.duration_ms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AccessLogEntry, AccessLogFormat};
+    use std::time::Duration;
+
+    #[test]
+    fn parse_access_log_format() {
+        assert_eq!("json".parse(), Ok(AccessLogFormat::Json));
+        assert_eq!("Text".parse(), Ok(AccessLogFormat::Text));
+        assert!("xml".parse::<AccessLogFormat>().is_err());
+    }
+
+    #[test]
+    fn json_access_log_entry_fields() {
+        let mut entry =
+            AccessLogEntry::new("GET".into(), "/hello".into(), Duration::from_millis(2));
+        entry.status = Some(200);
+        entry.matched = true;
+        entry.mock_id = Some(3);
+
+        let json = serde_json::to_value(&entry).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "method": "GET",
+                "path": "/hello",
+                "status": 200,
+                "matched": true,
+                "mock_id": 3,
+                "duration_ms": 2.0
+            })
+        );
+        assert_eq!(entry.to_string(), "GET /hello -> 200 (mock 3) in 2.000 ms");
+    }
+}
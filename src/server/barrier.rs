@@ -0,0 +1,85 @@
+use std::{sync::Mutex, time::Duration};
+
+use tokio::sync::watch;
+
+/// Holds back requests until a given number of them are waiting at the same time and then
+/// releases all of them at once (see `Then::wait_for_concurrent`).
+pub(crate) struct RequestBarrier {
+    waiting: Mutex<usize>,
+    /// Incremented every time the waiting requests are released.
+    generation: watch::Sender<u64>,
+}
+
+impl RequestBarrier {
+    pub(crate) fn new() -> Self {
+        Self {
+            waiting: Mutex::new(0),
+            generation: watch::channel(0).0,
+        }
+    }
+
+    /// Waits until `count` requests (including this one) are waiting or until `timeout` has
+    /// elapsed. Returns `false` if the request was released because of the timeout.
+    pub(crate) async fn wait(&self, count: usize, timeout: Duration) -> bool {
+        let mut released = {
+            let mut waiting = self.waiting.lock().unwrap();
+            *waiting += 1;
+            if *waiting >= count {
+                *waiting = 0;
+                self.generation.send_modify(|generation| *generation += 1);
+                return true;
+            }
+            self.generation.subscribe()
+        };
+
+        if tokio::time::timeout(timeout, released.changed())
+            .await
+            .is_ok()
+        {
+            return true;
+        }
+
+        // The barrier may have been released between the timeout and acquiring the lock. In
+        // that case, this request was already removed from the waiting requests.
+        let mut waiting = self.waiting.lock().unwrap();
+        if released.has_changed().unwrap_or(true) {
+            return true;
+        }
+        *waiting -= 1;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestBarrier;
+    use std::{sync::Arc, time::Duration};
+
+    #[tokio::test]
+    async fn releases_all_requests_once_the_count_is_reached() {
+        let barrier = Arc::new(RequestBarrier::new());
+        let timeout = Duration::from_secs(10);
+
+        let waiting: Vec<_> = (0..2)
+            .map(|_| {
+                let barrier = barrier.clone();
+                tokio::spawn(async move { barrier.wait(3, timeout).await })
+            })
+            .collect();
+
+        assert!(barrier.wait(3, timeout).await);
+        for handle in waiting {
+            assert!(handle.await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn releases_requests_after_the_timeout() {
+        let barrier = RequestBarrier::new();
+
+        assert!(!barrier.wait(2, Duration::from_millis(10)).await);
+
+        // The timed out request is no longer counted as waiting.
+        assert!(!barrier.wait(2, Duration::from_millis(10)).await);
+    }
+}
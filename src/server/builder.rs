@@ -1,19 +1,33 @@
 #[cfg(feature = "proxy")]
 use crate::common::http::{HttpClient, HttpMockHttpClient};
 #[cfg(any(feature = "record", feature = "record"))]
-use crate::server::persistence::read_static_mock_definitions;
+use crate::server::persistence::{read_static_mock_definitions, StaticMockDirWatcher};
 #[cfg(feature = "https")]
 use crate::server::server::MockServerHttpsConfig;
 #[cfg(feature = "https")]
-use crate::server::tls::{CertificateResolverFactory, GeneratingCertificateResolverFactory};
+use crate::server::tls::{
+    build_client_cert_verifier, CertificateResolverFactory, GeneratingCertificateResolverFactory,
+};
 
-use crate::server::{
-    handler::HttpMockHandler,
-    server::{MockServer, MockServerConfig},
-    state::{HttpMockStateManager, StateManager},
-    HttpMockServer,
+use crate::{
+    common::data::HttpMockRequest,
+    server::{
+        access_log::AccessLogFormat,
+        cors::CorsConfig,
+        handler::{HttpMockHandler, HttpMockHandlerConfig},
+        matchers::NamedMatcherFn,
+        server::{MockServer, MockServerConfig},
+        state::{
+            sweep_expired_mocks, HttpMockStateManager, MatchStrategy, StateManager,
+            DEFAULT_HISTORY_LIMIT,
+        },
+        HttpMockServer,
+    },
+};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap, error::Error, net::IpAddr, path::PathBuf, sync::Arc, time::Duration,
 };
-use std::{error::Error, path::PathBuf, sync::Arc};
 
 const DEFAULT_CA_PRIVATE_KEY: &'static str = include_str!("../../certs/ca.key");
 const DEFAULT_CA_CERTIFICATE: &'static str = include_str!("../../certs/ca.pem");
@@ -29,6 +43,8 @@ pub struct HttpsConfigBuilder {
     ca_key_path: Option<PathBuf>,
     enable_https: Option<bool>,
     cert_resolver_factory: Option<Arc<dyn CertificateResolverFactory + Send + Sync>>,
+    client_ca_cert: Option<String>,
+    client_ca_cert_path: Option<PathBuf>,
 }
 
 #[cfg(feature = "https")]
@@ -41,6 +57,8 @@ impl HttpsConfigBuilder {
             ca_key_path: None,
             cert_resolver_factory: None,
             enable_https: None,
+            client_ca_cert: None,
+            client_ca_cert_path: None,
         }
     }
 
@@ -58,6 +76,10 @@ impl HttpsConfigBuilder {
             if (has_ca_cert || has_ca_cert_path) && has_cert_generator {
                 return Err("Both a CA certificate and a certificate generator were configured. Please use only one of them.".into());
             }
+
+            if self.client_ca_cert.is_some() && self.client_ca_cert_path.is_some() {
+                return Err("A client CA certificate and a client CA certificate path have both been configured. Please choose only one method.".into());
+            }
         }
 
         Ok(())
@@ -132,6 +154,35 @@ impl HttpsConfigBuilder {
         self
     }
 
+    /// Sets the CA certificate that client certificates must be issued by. If set, the server
+    /// requests a client certificate during the TLS handshake (mutual TLS).
+    ///
+    /// # Parameters
+    /// - `client_ca_cert`: An optional CA certificate as a string in PEM format.
+    ///
+    /// # Returns
+    /// A modified `HttpsConfigBuilder` instance for method chaining.
+    pub fn client_ca_cert<IntoString>(mut self, client_ca_cert: Option<IntoString>) -> Self
+    where
+        IntoString: Into<String>,
+    {
+        self.client_ca_cert = client_ca_cert.map(|b| b.into());
+        self
+    }
+
+    /// Sets the path to the CA certificate that client certificates must be issued by. If set,
+    /// the server requests a client certificate during the TLS handshake (mutual TLS).
+    ///
+    /// # Parameters
+    /// - `client_ca_cert_path`: An optional path to the CA certificate in PEM format.
+    ///
+    /// # Returns
+    /// A modified `HttpsConfigBuilder` instance for method chaining.
+    pub fn client_ca_cert_path(mut self, client_ca_cert_path: Option<PathBuf>) -> Self {
+        self.client_ca_cert_path = client_ca_cert_path;
+        self
+    }
+
     /// Enables or disables HTTPS.
     ///
     /// # Parameters
@@ -178,8 +229,20 @@ impl HttpsConfigBuilder {
             )?),
         };
 
+        let client_ca_cert = match (self.client_ca_cert, self.client_ca_cert_path) {
+            (Some(client_ca_cert), _) => Some(client_ca_cert),
+            (_, Some(client_ca_cert_path)) => Some(std::fs::read_to_string(client_ca_cert_path)?),
+            _ => None,
+        };
+
+        let client_cert_verifier = match client_ca_cert {
+            Some(client_ca_cert) => Some(build_client_cert_verifier(&client_ca_cert)?),
+            None => None,
+        };
+
         Ok(MockServerHttpsConfig {
             cert_resolver_factory,
+            client_cert_verifier,
         })
     }
 }
@@ -189,10 +252,25 @@ impl HttpsConfigBuilder {
 pub struct HttpMockServerBuilder {
     port: Option<u16>,
     expose: Option<bool>,
+    bind_address: Option<IpAddr>,
     print_access_log: Option<bool>,
+    access_log_format: Option<AccessLogFormat>,
     history_limit: Option<usize>,
+    match_strategy: Option<MatchStrategy>,
+    debug_headers: Option<bool>,
+    default_response_headers: Vec<(String, String)>,
+    cors: Option<CorsConfig>,
+    enable_metrics: Option<bool>,
+    max_request_body_size: Option<usize>,
+    keep_alive: Option<bool>,
+    idle_timeout: Option<Duration>,
+    shutdown_timeout: Option<Duration>,
+    worker_threads: Option<usize>,
+    named_matchers: BTreeMap<String, NamedMatcherFn>,
     #[cfg(feature = "record")]
     static_mock_dir: Option<PathBuf>,
+    #[cfg(feature = "record")]
+    watch_mock_dir: Option<bool>,
     #[cfg(feature = "https")]
     https_config_builder: HttpsConfigBuilder,
     #[cfg(feature = "proxy")]
@@ -207,11 +285,26 @@ impl HttpMockServerBuilder {
     pub fn new() -> Self {
         HttpMockServerBuilder {
             print_access_log: None,
+            access_log_format: None,
             port: None,
             expose: None,
+            bind_address: None,
             history_limit: None,
+            match_strategy: None,
+            debug_headers: None,
+            default_response_headers: Vec::new(),
+            cors: None,
+            enable_metrics: None,
+            max_request_body_size: None,
+            keep_alive: None,
+            idle_timeout: None,
+            shutdown_timeout: None,
+            worker_threads: None,
+            named_matchers: BTreeMap::new(),
             #[cfg(feature = "record")]
             static_mock_dir: None,
+            #[cfg(feature = "record")]
+            watch_mock_dir: None,
             #[cfg(feature = "proxy")]
             http_client: None,
             #[cfg(feature = "https")]
@@ -267,6 +360,31 @@ impl HttpMockServerBuilder {
         self
     }
 
+    /// Sets the IP address the HTTP mock server binds to (e.g., `::1` to listen on the IPv6
+    /// loopback interface). If set, this takes precedence over [`expose`](#method.expose).
+    ///
+    /// # Parameters
+    /// - `address`: The IP address to bind to.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn bind_address(mut self, address: IpAddr) -> Self {
+        self.bind_address = Some(address);
+        self
+    }
+
+    /// Sets the IP address the HTTP mock server binds to as an optional value.
+    ///
+    /// # Parameters
+    /// - `address`: An optional IP address to bind to.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn bind_address_option(mut self, address: Option<IpAddr>) -> Self {
+        self.bind_address = address;
+        self
+    }
+
     /// Sets whether to print access logs.
     ///
     /// # Parameters
@@ -291,8 +409,43 @@ impl HttpMockServerBuilder {
         self
     }
 
+    /// Sets the format of the access log. Has no effect unless the access log is enabled
+    /// using [`print_access_log`](#method.print_access_log). Defaults to
+    /// [`AccessLogFormat::Text`].
+    ///
+    /// [`AccessLogFormat::Json`] prints one JSON object per request to standard output, which
+    /// is easier to process by log aggregation tools. Its fields are documented on
+    /// [`AccessLogFormat::Json`].
+    ///
+    /// # Parameters
+    /// - `format`: The format of the access log entries.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn access_log_format(mut self, format: AccessLogFormat) -> Self {
+        self.access_log_format = Some(format);
+        self
+    }
+
+    /// Sets the format of the access log as an optional value.
+    ///
+    /// # Parameters
+    /// - `format`: An optional format of the access log entries.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn access_log_format_option(mut self, format: Option<AccessLogFormat>) -> Self {
+        self.access_log_format = format;
+        self
+    }
+
     /// Sets the history limit for the server.
     ///
+    /// The server keeps a history of received requests that is used to verify mocks. Once the
+    /// limit is reached, the oldest request is removed from the history for every new request.
+    /// A limit of `0` disables the request history. Call counts of mocks are not affected by
+    /// the history limit. Defaults to 100.
+    ///
     /// # Parameters
     /// - `limit`: The maximum number of history entries to keep.
     ///
@@ -315,6 +468,332 @@ impl HttpMockServerBuilder {
         self
     }
 
+    /// Sets how the server decides which mock serves a request that matches more than one mock.
+    ///
+    /// By default, the mock with the highest priority wins ([`MatchStrategy::HighestPriority`]).
+    /// Mocks that share the highest priority are evaluated in the order they were created, so
+    /// the first one wins. See [`MatchStrategy`] for the other strategies and how they break
+    /// ties.
+    ///
+    /// # Parameters
+    /// - `strategy`: The strategy to select the mock that serves a request.
+    ///
+    /// # Example
+    /// ```rust
+    /// use httpmock::{server::{HttpMockServerBuilder, MatchStrategy}, MockServer};
+    ///
+    /// let server = MockServer::start_with(
+    ///     HttpMockServerBuilder::new().match_strategy(MatchStrategy::MostSpecific),
+    /// );
+    ///
+    /// let generic = server.mock(|when, then| {
+    ///     when.path("/search");
+    ///     then.body("generic");
+    /// });
+    ///
+    /// let specific = server.mock(|when, then| {
+    ///     when.path("/search").query_param("query", "metallica");
+    ///     then.body("specific");
+    /// });
+    ///
+    /// let response = reqwest::blocking::get(server.url("/search?query=metallica")).unwrap();
+    ///
+    /// assert_eq!(response.text().unwrap(), "specific");
+    /// specific.assert();
+    /// generic.assert_calls(0);
+    /// ```
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn match_strategy(mut self, strategy: MatchStrategy) -> Self {
+        self.match_strategy = Some(strategy);
+        self
+    }
+
+    /// Sets how the server decides which mock serves a request that matches more than one mock
+    /// as an optional value.
+    ///
+    /// # Parameters
+    /// - `strategy`: An optional strategy to select the mock that serves a request.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn match_strategy_option(mut self, strategy: Option<MatchStrategy>) -> Self {
+        self.match_strategy = strategy;
+        self
+    }
+
+    /// Sets whether debug headers should be added to mock responses.
+    ///
+    /// When enabled, every response served by a mock carries an `x-httpmock-matched-id`
+    /// header containing the ID of the mock that matched the request. This is disabled by default.
+    ///
+    /// # Parameters
+    /// - `enabled`: A boolean indicating whether to add debug headers.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn debug_headers(mut self, enabled: bool) -> Self {
+        self.debug_headers = Some(enabled);
+        self
+    }
+
+    /// Sets whether debug headers should be added to mock responses as an optional value.
+    ///
+    /// # Parameters
+    /// - `enabled`: An optional boolean indicating whether to add debug headers.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn debug_headers_option(mut self, enabled: Option<bool>) -> Self {
+        self.debug_headers = enabled;
+        self
+    }
+
+    /// Adds a header that the server includes in every response, such as CORS headers or a
+    /// request ID. Mocks that explicitly set a header with the same name take precedence.
+    /// If this method is called multiple times for the same header name, all values are added.
+    ///
+    /// Default headers can be replaced at runtime using
+    /// [`MockServer::set_default_headers`](crate::MockServer::set_default_headers).
+    ///
+    /// # Parameters
+    /// - `name`: The header name.
+    /// - `value`: The header value.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn default_response_header<Name: Into<String>, Value: Into<String>>(
+        mut self,
+        name: Name,
+        value: Value,
+    ) -> Self {
+        self.default_response_headers
+            .push((name.into(), value.into()));
+        self
+    }
+
+    /// Enables automatic CORS handling. The server then answers CORS preflight requests
+    /// (`OPTIONS` requests with an `Origin` and an `Access-Control-Request-Method` header)
+    /// according to the given configuration and adds CORS headers to the responses of
+    /// cross-origin requests. Mocks that match a preflight request take precedence.
+    ///
+    /// # Parameters
+    /// - `config`: The CORS configuration.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn auto_cors(mut self, config: CorsConfig) -> Self {
+        self.cors = Some(config);
+        self
+    }
+
+    /// Sets whether the server provides a metrics endpoint at `/__httpmock__/metrics`.
+    ///
+    /// The endpoint returns request counters in the Prometheus text format, such as the total
+    /// number of requests, the number of matched and unmatched requests, and the number of
+    /// responses per status code. Requests to the endpoint are not matched against mocks and
+    /// are not counted. This is disabled by default.
+    ///
+    /// # Parameters
+    /// - `enabled`: A boolean indicating whether to provide the metrics endpoint.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn enable_metrics(mut self, enabled: bool) -> Self {
+        self.enable_metrics = Some(enabled);
+        self
+    }
+
+    /// Sets whether the server provides a metrics endpoint as an optional value.
+    ///
+    /// # Parameters
+    /// - `enabled`: An optional boolean indicating whether to provide the metrics endpoint.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn enable_metrics_option(mut self, enabled: Option<bool>) -> Self {
+        self.enable_metrics = enabled;
+        self
+    }
+
+    /// Sets the maximum size of request bodies in bytes.
+    ///
+    /// Requests with a larger body are answered with `413 Payload Too Large`. If the request
+    /// announces its body size in the `Content-Length` header, it is rejected without reading
+    /// the body. Otherwise, the body is read until the limit is exceeded. Rejected requests are
+    /// never matched against mocks and are not added to the request history, so matchers never
+    /// see a truncated body. There is no limit by default.
+    ///
+    /// # Parameters
+    /// - `bytes`: The maximum number of bytes a request body may have.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn max_request_body_size(mut self, bytes: usize) -> Self {
+        self.max_request_body_size = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum size of request bodies in bytes as an optional value.
+    ///
+    /// # Parameters
+    /// - `bytes`: An optional maximum number of bytes a request body may have.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn max_request_body_size_option(mut self, bytes: Option<usize>) -> Self {
+        self.max_request_body_size = bytes;
+        self
+    }
+
+    /// Enables or disables HTTP/1 connection keep-alive.
+    ///
+    /// When keep-alive is disabled, every response carries a `Connection: close` header and the
+    /// server closes the connection after the response was sent. This is useful to test how
+    /// clients handle connections that cannot be reused. Keep-alive is enabled by default.
+    ///
+    /// # Parameters
+    /// - `enabled`: A boolean value indicating whether connections should be kept alive.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn keep_alive(mut self, enabled: bool) -> Self {
+        self.keep_alive = Some(enabled);
+        self
+    }
+
+    /// Enables or disables HTTP/1 connection keep-alive as an optional value.
+    ///
+    /// # Parameters
+    /// - `enabled`: An optional boolean value indicating whether connections should be kept alive.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn keep_alive_option(mut self, enabled: Option<bool>) -> Self {
+        self.keep_alive = enabled;
+        self
+    }
+
+    /// Sets how long an HTTP/1 connection may stay idle before the server closes it.
+    ///
+    /// The timer starts whenever the server waits for the next request on a connection, so it
+    /// also limits how long a client may take to send the request headers. Connections are
+    /// never closed while a request is being processed. There is no idle timeout by default.
+    ///
+    /// # Parameters
+    /// - `timeout`: The maximum time a connection may stay idle.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long an HTTP/1 connection may stay idle before the server closes it as an
+    /// optional value.
+    ///
+    /// # Parameters
+    /// - `timeout`: An optional maximum time a connection may stay idle.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn idle_timeout_option(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sets how long the server waits for in-flight requests to be answered when it shuts down.
+    ///
+    /// On shutdown, the server stops accepting new connections and closes idle ones right away.
+    /// Requests that are still being processed, such as delayed or throttled responses, are
+    /// given up to this long to complete before their connections are cut off. By default, the
+    /// server does not wait for in-flight requests.
+    ///
+    /// # Parameters
+    /// - `timeout`: The maximum time to wait for in-flight requests.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long the server waits for in-flight requests to be answered when it shuts down
+    /// as an optional value.
+    ///
+    /// # Parameters
+    /// - `timeout`: An optional maximum time to wait for in-flight requests.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn shutdown_timeout_option(mut self, timeout: Option<Duration>) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Sets the number of worker threads of the Tokio runtime that executes the server.
+    ///
+    /// More worker threads can increase the throughput of the server if matching requests is
+    /// CPU-bound, such as with many mocks that use complex regular expressions. If no number
+    /// is set, the standalone server uses Tokio's default of one worker thread per CPU core,
+    /// and servers started with [`MockServer::start_with`](crate::MockServer::start_with) run
+    /// on a single thread.
+    ///
+    /// This setting only applies if the server owns its runtime. It is ignored if the server
+    /// runs on a runtime that is provided by the caller (see
+    /// [`MockServer::start_on`](crate::MockServer::start_on)).
+    ///
+    /// # Parameters
+    /// - `threads`: The number of worker threads. Must be larger than `0`, otherwise building the
+    ///   server fails.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn worker_threads(mut self, threads: usize) -> Self {
+        self.worker_threads = Some(threads);
+        self
+    }
+
+    /// Sets the number of worker threads of the Tokio runtime that executes the server as an
+    /// optional value.
+    ///
+    /// # Parameters
+    /// - `threads`: An optional number of worker threads.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn worker_threads_option(mut self, threads: Option<usize>) -> Self {
+        self.worker_threads = threads;
+        self
+    }
+
+    /// Registers a matcher function under a name, so that mocks can refer to it using
+    /// [`When::matcher_named`](crate::When::matcher_named). In contrast to
+    /// [`When::is_true`](crate::When::is_true), this also works for mocks that are created
+    /// by a client of a remote (standalone) mock server, because only the name and the
+    /// arguments of the matcher are sent to the server.
+    ///
+    /// The function receives the request and the JSON arguments that the mock passes to it.
+    /// Creating a mock that refers to a name that is not registered fails.
+    ///
+    /// # Parameters
+    /// - `name`: The name that mocks use to refer to the matcher.
+    /// - `matcher`: The matcher function.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    pub fn register_matcher<IntoString: Into<String>>(
+        mut self,
+        name: IntoString,
+        matcher: impl Fn(&HttpMockRequest, &Value) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.named_matchers.insert(name.into(), Arc::new(matcher));
+        self
+    }
+
     /// Sets the directory for static mock files.
     ///
     /// # Parameters
@@ -341,6 +820,21 @@ impl HttpMockServerBuilder {
         self
     }
 
+    /// Enables or disables watching the static mock directory for changes. If enabled, added,
+    /// changed and removed mock files are reloaded while the server is running. If a changed
+    /// file is invalid, an error is logged and the mocks previously loaded from it stay active.
+    ///
+    /// # Parameters
+    /// - `enable`: A boolean to enable or disable watching the static mock directory.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    #[cfg(feature = "record")]
+    pub fn watch_mock_dir(mut self, enable: bool) -> Self {
+        self.watch_mock_dir = Some(enable);
+        self
+    }
+
     /// Sets the certificate resolver factory for generating certificates.
     ///
     /// # Parameters
@@ -456,12 +950,67 @@ impl HttpMockServerBuilder {
         self
     }
 
+    /// Enables mutual TLS by setting the CA certificate that client certificates must be issued
+    /// by. The server then requests a client certificate during the TLS handshake and captures
+    /// its subject common name, which can be matched with
+    /// [`When::client_cert_cn`](crate::When::client_cert_cn). Clients that do not present a
+    /// certificate are still served.
+    ///
+    /// # Parameters
+    /// - `cert`: The CA certificate in PEM format.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    #[cfg(feature = "https")]
+    pub fn https_client_ca_cert<IntoString: Into<String>>(mut self, cert: IntoString) -> Self {
+        self.https_config_builder = self.https_config_builder.client_ca_cert(Some(cert));
+        self
+    }
+
+    /// Enables mutual TLS by setting the path to the CA certificate file that client
+    /// certificates must be issued by. See
+    /// [`https_client_ca_cert`](#method.https_client_ca_cert) for details.
+    ///
+    /// # Parameters
+    /// - `cert_path`: The path to the CA certificate file in PEM format.
+    ///
+    /// # Returns
+    /// A modified `HttpMockServerBuilder` instance for method chaining.
+    #[cfg(feature = "https")]
+    pub fn https_client_ca_cert_file<Path: Into<PathBuf>>(mut self, cert_path: Path) -> Self {
+        self.https_config_builder = self
+            .https_config_builder
+            .client_ca_cert_path(Some(cert_path.into()));
+        self
+    }
+
     /// Builds the `HttpMockServer` with the current settings.
     ///
     /// # Returns
     /// A `HttpMockServer` instance or an error if the build process fails.
     pub fn build(self) -> Result<HttpMockServer, Box<dyn Error>> {
-        self.build_with_state(Arc::new(HttpMockStateManager::default()))
+        self.build_with_shared_state().map(|(server, _)| server)
+    }
+
+    /// Builds the `HttpMockServer` with the current settings and returns it together with
+    /// its state manager, so that the state can be accessed without going through the network.
+    ///
+    /// # Returns
+    /// A tuple of the `HttpMockServer` instance and its state manager or an error if the
+    /// build process fails.
+    pub(crate) fn build_with_shared_state(
+        mut self,
+    ) -> Result<(HttpMockServer, Arc<HttpMockStateManager>), Box<dyn Error>> {
+        let named_matchers = Arc::new(std::mem::take(&mut self.named_matchers));
+        let state = Arc::new(
+            HttpMockStateManager::with_named_matchers(
+                self.history_limit.unwrap_or(DEFAULT_HISTORY_LIMIT),
+                named_matchers,
+            )
+            .with_match_strategy(self.match_strategy.unwrap_or_default()),
+        );
+        let server = self.build_with_state(state.clone())?;
+        Ok((server, state))
     }
 
     /// Builds the `MockServer` with the current settings and provided state manager.
@@ -478,31 +1027,65 @@ impl HttpMockServerBuilder {
     where
         S: StateManager + Send + Sync + 'static,
     {
+        if self.worker_threads == Some(0) {
+            return Err("the number of worker threads must be larger than 0".into());
+        }
+
         #[cfg(feature = "proxy")]
         let http_client = self
             .http_client
             .unwrap_or_else(|| Arc::new(HttpMockHttpClient::new(None)));
 
+        #[cfg(feature = "record")]
+        let mut mock_dir_watcher = None;
         #[cfg(feature = "record")]
         if let Some(dir) = self.static_mock_dir {
-            read_static_mock_definitions(dir, state.as_ref())?;
+            if self.watch_mock_dir.unwrap_or(false) {
+                mock_dir_watcher = Some(StaticMockDirWatcher::load(dir, &state)?.watch()?);
+            } else {
+                read_static_mock_definitions(dir, state.as_ref())?;
+            }
         }
 
+        let expired_mock_sweeper = sweep_expired_mocks(&state);
+
         let handler = HttpMockHandler::new(
             state,
+            HttpMockHandlerConfig {
+                debug_headers: self.debug_headers.unwrap_or(false),
+                default_response_headers: self.default_response_headers,
+                cors: self.cors,
+                metrics: self.enable_metrics.unwrap_or(false),
+            },
             #[cfg(feature = "proxy")]
             http_client,
         );
 
-        Ok(MockServer::new(
+        let server = MockServer::new(
             Box::new(handler),
             MockServerConfig {
                 static_port: self.port,
                 expose: self.expose.unwrap_or(false),
+                bind_address: self.bind_address,
                 print_access_log: self.print_access_log.unwrap_or(false),
+                access_log_format: self.access_log_format.unwrap_or_default(),
+                max_request_body_size: self.max_request_body_size,
+                keep_alive: self.keep_alive.unwrap_or(true),
+                idle_timeout: self.idle_timeout,
+                shutdown_timeout: self.shutdown_timeout,
+                worker_threads: self.worker_threads,
                 #[cfg(feature = "https")]
                 https: self.https_config_builder.build()?,
             },
-        )?)
+        )?
+        .with_background_task(expired_mock_sweeper);
+
+        #[cfg(feature = "record")]
+        let server = match mock_dir_watcher {
+            Some(watcher) => server.with_background_task(watcher),
+            None => server,
+        };
+
+        Ok(server)
     }
 }
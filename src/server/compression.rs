@@ -0,0 +1,160 @@
+use std::io::{self, Read, Write};
+
+use bytes::Bytes;
+
+/// A content coding that the mock server can apply to response bodies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ContentEncoding {
+    Brotli,
+    Gzip,
+}
+
+impl ContentEncoding {
+    /// The supported encodings in the order the server prefers them if the client accepts
+    /// several of them equally.
+    const PREFERENCE: [ContentEncoding; 2] = [ContentEncoding::Brotli, ContentEncoding::Gzip];
+
+    /// The token of the encoding in the `Accept-Encoding` and `Content-Encoding` headers.
+    pub(crate) fn token(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// Compresses the body with this encoding.
+    pub(crate) fn encode(&self, body: &[u8]) -> io::Result<Bytes> {
+        let mut encoded = Vec::new();
+        match self {
+            ContentEncoding::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+                writer.write_all(body)?;
+                writer.flush()?;
+            }
+            ContentEncoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(&mut encoded, flate2::Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()?;
+            }
+        }
+        Ok(Bytes::from(encoded))
+    }
+}
+
+/// Removes the content codings listed in a `Content-Encoding` header value from a body. The
+/// codings are removed in the reverse order in which they were applied (RFC 9110, section
+/// 8.4). Besides the encodings the server can apply to responses, `x-gzip`, `deflate` and
+/// `identity` are supported.
+pub(crate) fn decode(content_encoding: &str, body: &[u8]) -> Result<Bytes, String> {
+    let mut decoded = body.to_vec();
+
+    for coding in content_encoding.split(',').rev() {
+        let coding = coding.trim().to_ascii_lowercase();
+        let mut output = Vec::new();
+        let result = match coding.as_str() {
+            "" | "identity" => continue,
+            "gzip" | "x-gzip" => {
+                flate2::read::GzDecoder::new(&decoded[..]).read_to_end(&mut output)
+            }
+            "deflate" => flate2::read::ZlibDecoder::new(&decoded[..]).read_to_end(&mut output),
+            "br" => brotli::Decompressor::new(&decoded[..], 4096).read_to_end(&mut output),
+            _ => return Err(format!("unsupported content encoding '{}'", coding)),
+        };
+        result.map_err(|err| format!("invalid {} data: {}", coding, err))?;
+        decoded = output;
+    }
+
+    Ok(Bytes::from(decoded))
+}
+
+/// Selects the encoding for a response based on the `Accept-Encoding` header values of the
+/// request (RFC 9110, section 12.5.3). The supported encoding with the highest quality value
+/// wins. A wildcard (`*`) applies to all encodings that are not listed explicitly. Returns
+/// `None` if the client does not accept any supported encoding.
+pub(crate) fn negotiate<'a>(
+    accept_encoding: impl IntoIterator<Item = &'a str>,
+) -> Option<ContentEncoding> {
+    let mut explicit: Vec<(String, f32)> = Vec::new();
+    let mut wildcard = None;
+
+    for value in accept_encoding {
+        for element in value.split(',') {
+            let mut parts = element.split(';');
+            let coding = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            if coding.is_empty() {
+                continue;
+            }
+
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if coding == "*" {
+                wildcard = Some(quality);
+            } else {
+                explicit.push((coding, quality));
+            }
+        }
+    }
+
+    let quality_of = |encoding: &ContentEncoding| {
+        explicit
+            .iter()
+            .find(|(coding, _)| coding == encoding.token())
+            .map(|(_, quality)| *quality)
+            .or(wildcard)
+            .unwrap_or(0.0)
+    };
+
+    let mut best: Option<(ContentEncoding, f32)> = None;
+    for encoding in ContentEncoding::PREFERENCE.iter() {
+        let quality = quality_of(encoding);
+        if quality > 0.0 && best.map_or(true, |(_, best_quality)| quality > best_quality) {
+            best = Some((*encoding, quality));
+        }
+    }
+
+    best.map(|(encoding, _)| encoding)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, negotiate, ContentEncoding};
+
+    #[test]
+    fn negotiate_encoding() {
+        assert_eq!(negotiate(vec![]), None);
+        assert_eq!(negotiate(vec!["identity"]), None);
+        assert_eq!(negotiate(vec!["gzip"]), Some(ContentEncoding::Gzip));
+        assert_eq!(negotiate(vec!["gzip, br"]), Some(ContentEncoding::Brotli));
+        assert_eq!(
+            negotiate(vec!["br;q=0.5, GZIP"]),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            negotiate(vec!["deflate", "br"]),
+            Some(ContentEncoding::Brotli)
+        );
+        assert_eq!(negotiate(vec!["*"]), Some(ContentEncoding::Brotli));
+        assert_eq!(negotiate(vec!["br;q=0, *"]), Some(ContentEncoding::Gzip));
+        assert_eq!(negotiate(vec!["gzip;q=0, br;q=0"]), None);
+    }
+
+    #[test]
+    fn decode_body() {
+        let gzip = ContentEncoding::Gzip.encode(b"hello").unwrap();
+        let gzip_br = ContentEncoding::Brotli.encode(&gzip).unwrap();
+
+        assert_eq!(decode("gzip", &gzip).unwrap(), "hello");
+        assert_eq!(decode("gzip, br", &gzip_br).unwrap(), "hello");
+        assert_eq!(decode("identity", b"hello").unwrap(), "hello");
+        assert!(decode("br", b"hello")
+            .unwrap_err()
+            .contains("invalid br data"));
+        assert!(decode("zstd", b"hello")
+            .unwrap_err()
+            .contains("unsupported content encoding 'zstd'"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::SystemTime;
+
+use http::Method;
+
+use crate::common::data::HttpMockRequest;
+
+/// The headers a `304 Not Modified` response keeps from the configured response headers,
+/// in addition to the validators themselves (RFC 7232, section 4.1).
+const NOT_MODIFIED_HEADERS: [&str; 5] = [
+    "cache-control",
+    "content-location",
+    "date",
+    "expires",
+    "vary",
+];
+
+/// Returns `true` if the header with the given name may be sent in a `304 Not Modified`
+/// response.
+pub(crate) fn is_not_modified_header(name: &str) -> bool {
+    NOT_MODIFIED_HEADERS
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(name))
+}
+
+/// Evaluates the `If-None-Match` and `If-Modified-Since` request headers against the validators
+/// of a response (RFC 7232, section 6). Returns `true` if the client's cached representation is
+/// still valid, so that a `304 Not Modified` response can be sent.
+///
+/// Only `GET` and `HEAD` requests are considered. `If-None-Match` takes precedence: if it is
+/// present, `If-Modified-Since` is ignored, even if no entity tag is configured.
+pub(crate) fn is_not_modified(
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+    req: &HttpMockRequest,
+) -> bool {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return false;
+    }
+
+    let headers = req.headers_vec();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>()
+    };
+
+    let if_none_match = header("if-none-match");
+    if !if_none_match.is_empty() {
+        return etag.map_or(false, |etag| {
+            if_none_match
+                .iter()
+                .flat_map(|value| value.split(','))
+                .any(|candidate| etag_matches_weak(candidate.trim(), etag))
+        });
+    }
+
+    let if_modified_since = match header("if-modified-since").first() {
+        Some(value) => parse_http_date(value),
+        None => return false,
+    };
+
+    match (last_modified.and_then(parse_http_date), if_modified_since) {
+        (Some(last_modified), Some(since)) => last_modified <= since,
+        _ => false,
+    }
+}
+
+/// Compares two entity tags using the weak comparison function (RFC 7232, section 2.3.2),
+/// which ignores the weakness indicator. A `*` matches any entity tag.
+fn etag_matches_weak(candidate: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    candidate == "*" || opaque(candidate) == opaque(etag)
+}
+
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    httpdate::parse_http_date(value.trim()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_not_modified;
+    use crate::common::data::HttpMockRequest;
+
+    const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+    fn request(method: &str, headers: Vec<(&str, &str)>) -> HttpMockRequest {
+        headers
+            .into_iter()
+            .fold(
+                HttpMockRequest::builder().method(method),
+                |builder, (name, value)| builder.header(name, value),
+            )
+            .build()
+    }
+
+    #[test]
+    fn compares_entity_tags_weakly() {
+        let etag = Some("\"v1\"");
+        let req = |value| request("GET", vec![("If-None-Match", value)]);
+
+        assert!(is_not_modified(etag, None, &req("\"v1\"")));
+        assert!(is_not_modified(etag, None, &req("W/\"v1\"")));
+        assert!(is_not_modified(etag, None, &req("\"v0\", \"v1\"")));
+        assert!(is_not_modified(etag, None, &req("*")));
+        assert!(!is_not_modified(etag, None, &req("\"v2\"")));
+        assert!(!is_not_modified(None, None, &req("*")));
+    }
+
+    #[test]
+    fn compares_modification_dates() {
+        let last_modified = Some(LAST_MODIFIED);
+        let req = |value| request("GET", vec![("If-Modified-Since", value)]);
+
+        assert!(is_not_modified(None, last_modified, &req(LAST_MODIFIED)));
+        assert!(is_not_modified(
+            None,
+            last_modified,
+            &req("Thu, 22 Oct 2015 07:28:00 GMT")
+        ));
+        assert!(!is_not_modified(
+            None,
+            last_modified,
+            &req("Tue, 20 Oct 2015 07:28:00 GMT")
+        ));
+        assert!(!is_not_modified(None, last_modified, &req("yesterday")));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence() {
+        let req = request(
+            "GET",
+            vec![
+                ("If-None-Match", "\"v2\""),
+                ("If-Modified-Since", LAST_MODIFIED),
+            ],
+        );
+
+        assert!(!is_not_modified(Some("\"v1\""), Some(LAST_MODIFIED), &req));
+    }
+
+    #[test]
+    fn ignores_unsafe_methods() {
+        let req = request("POST", vec![("If-None-Match", "\"v1\"")]);
+
+        assert!(!is_not_modified(Some("\"v1\""), None, &req));
+    }
+}
@@ -0,0 +1,276 @@
+use http::{
+    header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+        ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD,
+        ORIGIN, VARY,
+    },
+    HeaderMap, HeaderValue, Method,
+};
+use std::time::Duration;
+
+/// Configures how the mock server answers CORS requests when automatic CORS handling is enabled
+/// using [`HttpMockServerBuilder::auto_cors`](crate::server::HttpMockServerBuilder::auto_cors).
+///
+/// By default, requests from any origin are allowed, and the requested method and headers of
+/// a preflight request are reflected in the response. Credentials are not allowed by default.
+#[derive(Clone, Debug, Default)]
+pub struct CorsConfig {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+    exposed_headers: Vec<String>,
+    allow_credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Creates a new `CorsConfig` that allows requests from any origin.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows requests from the given origin, such as `https://app.example.com`. If this method
+    /// is never called, requests from any origin are allowed.
+    pub fn allow_origin<IntoString: Into<String>>(mut self, origin: IntoString) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// Sets the methods that are allowed in cross-origin requests. If no methods are set,
+    /// the method requested by a preflight request is allowed.
+    pub fn allow_methods<IntoString: Into<String>>(
+        mut self,
+        methods: impl IntoIterator<Item = IntoString>,
+    ) -> Self {
+        self.allowed_methods
+            .extend(methods.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the request headers that are allowed in cross-origin requests. If no headers are
+    /// set, the headers requested by a preflight request are allowed.
+    pub fn allow_headers<IntoString: Into<String>>(
+        mut self,
+        headers: impl IntoIterator<Item = IntoString>,
+    ) -> Self {
+        self.allowed_headers
+            .extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the response headers that browsers expose to the calling script.
+    pub fn expose_headers<IntoString: Into<String>>(
+        mut self,
+        headers: impl IntoIterator<Item = IntoString>,
+    ) -> Self {
+        self.exposed_headers
+            .extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// Allows cross-origin requests to include credentials, such as cookies.
+    ///
+    /// As required by the CORS specification, the server then never responds with a wildcard
+    /// (`*`) origin, but reflects the origin of the request instead.
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// Sets how long browsers may cache the result of a preflight request.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns the headers of the response to a preflight request, or `None` if the origin
+    /// of the request is not allowed.
+    pub(crate) fn preflight_headers(&self, req_headers: &HeaderMap) -> Option<HeaderMap> {
+        let origin = req_headers.get(ORIGIN)?;
+
+        let mut headers = HeaderMap::new();
+        self.add_origin_headers(origin, &mut headers)?;
+
+        // Wildcards are only allowed for requests without credentials. Otherwise, they would
+        // be treated as literal method and header names, so requested values are reflected.
+        match (
+            self.allowed_methods.is_empty(),
+            req_headers.get(ACCESS_CONTROL_REQUEST_METHOD),
+        ) {
+            (false, _) => {
+                headers.insert(
+                    ACCESS_CONTROL_ALLOW_METHODS,
+                    join_header_values(&self.allowed_methods)?,
+                );
+            }
+            (true, Some(method)) => {
+                headers.insert(ACCESS_CONTROL_ALLOW_METHODS, method.clone());
+                headers.append(VARY, HeaderValue::from(ACCESS_CONTROL_REQUEST_METHOD));
+            }
+            (true, None) => {}
+        }
+
+        match (
+            self.allowed_headers.is_empty(),
+            req_headers.get(ACCESS_CONTROL_REQUEST_HEADERS),
+        ) {
+            (false, _) => {
+                headers.insert(
+                    ACCESS_CONTROL_ALLOW_HEADERS,
+                    join_header_values(&self.allowed_headers)?,
+                );
+            }
+            (true, Some(requested)) => {
+                headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+                headers.append(VARY, HeaderValue::from(ACCESS_CONTROL_REQUEST_HEADERS));
+            }
+            (true, None) => {}
+        }
+
+        if let Some(max_age) = self.max_age {
+            headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+        }
+
+        Some(headers)
+    }
+
+    /// Adds the CORS headers for a regular (non-preflight) cross-origin request to a response.
+    /// Nothing is added if the origin is not allowed or if the response already contains an
+    /// `Access-Control-Allow-Origin` header.
+    pub(crate) fn add_response_headers(&self, origin: &HeaderValue, res_headers: &mut HeaderMap) {
+        if res_headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN) {
+            return;
+        }
+
+        let mut headers = HeaderMap::new();
+        if self.add_origin_headers(origin, &mut headers).is_none() {
+            return;
+        }
+
+        if !self.exposed_headers.is_empty() {
+            if let Some(value) = join_header_values(&self.exposed_headers) {
+                headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, value);
+            }
+        }
+
+        for (name, value) in headers.iter() {
+            res_headers.append(name, value.clone());
+        }
+    }
+
+    /// Adds the `Access-Control-Allow-Origin` header (and the credentials header, if enabled).
+    /// Returns `None` if the origin is not allowed.
+    fn add_origin_headers(&self, origin: &HeaderValue, headers: &mut HeaderMap) -> Option<()> {
+        let any_origin =
+            self.allowed_origins.is_empty() || self.allowed_origins.iter().any(|o| o == "*");
+
+        if !any_origin
+            && !self
+                .allowed_origins
+                .iter()
+                .any(|allowed| origin.as_bytes().eq_ignore_ascii_case(allowed.as_bytes()))
+        {
+            return None;
+        }
+
+        // A wildcard origin must not be combined with credentials, so the request origin is
+        // reflected instead. The response then depends on the origin and must not be cached
+        // for other origins.
+        if any_origin && !self.allow_credentials {
+            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+        } else {
+            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+            headers.append(VARY, HeaderValue::from(ORIGIN));
+        }
+
+        if self.allow_credentials {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+
+        Some(())
+    }
+}
+
+/// Returns `true` if the request is a CORS preflight request.
+pub(crate) fn is_preflight_request(method: &Method, headers: &HeaderMap) -> bool {
+    method == Method::OPTIONS
+        && headers.contains_key(ORIGIN)
+        && headers.contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+fn join_header_values(values: &[String]) -> Option<HeaderValue> {
+    HeaderValue::from_str(&values.join(", ")).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::server::cors::CorsConfig;
+    use http::{HeaderMap, HeaderValue};
+
+    fn preflight_request(origin: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("origin", HeaderValue::from_str(origin).unwrap());
+        headers.insert("access-control-request-method", "PUT".parse().unwrap());
+        headers.insert(
+            "access-control-request-headers",
+            "content-type, x-token".parse().unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn preflight_allows_any_origin_by_default() {
+        let headers = CorsConfig::new()
+            .preflight_headers(&preflight_request("https://app.example.com"))
+            .unwrap();
+
+        assert_eq!(headers["access-control-allow-origin"], "*");
+        assert_eq!(headers["access-control-allow-methods"], "PUT");
+        assert_eq!(
+            headers["access-control-allow-headers"],
+            "content-type, x-token"
+        );
+        assert!(headers.get("access-control-allow-credentials").is_none());
+    }
+
+    #[test]
+    fn preflight_reflects_origin_with_credentials() {
+        let headers = CorsConfig::new()
+            .allow_credentials(true)
+            .allow_methods(["GET", "PUT"])
+            .preflight_headers(&preflight_request("https://app.example.com"))
+            .unwrap();
+
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+        assert_eq!(headers["access-control-allow-methods"], "GET, PUT");
+        assert!(headers
+            .get_all("vary")
+            .iter()
+            .any(|v| v.to_str().unwrap().eq_ignore_ascii_case("origin")));
+    }
+
+    #[test]
+    fn preflight_rejects_unknown_origins() {
+        let config = CorsConfig::new().allow_origin("https://app.example.com");
+
+        assert!(config
+            .preflight_headers(&preflight_request("https://evil.example.com"))
+            .is_none());
+
+        let headers = config
+            .preflight_headers(&preflight_request("https://app.example.com"))
+            .unwrap();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+    }
+}
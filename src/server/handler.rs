@@ -1,32 +1,60 @@
 use crate::common::data::{
-    ActiveForwardingRule, ActiveProxyRule, Error as DataError, ErrorResponse, MockDefinition,
-    RequestRequirements,
+    ActiveForwardingRule, ActiveProxyRule, Error as DataError, ErrorResponse, Fault,
+    ForwardingTarget, MockDefinition, MockServerHttpResponse, RequestHeaderChange,
+    RequestRequirements, StateSnapshot,
 };
 
 use crate::{
-    common::runtime,
+    common::{
+        runtime,
+        util::{merge_json_body, render_template},
+    },
     server::{
+        conditional,
+        cors::{is_preflight_request, CorsConfig},
         handler::Error::{
-            InvalidHeader, ParamError, ParamFormatError, RequestBodyDeserializeError,
-            RequestConversionError, ResponseBodyConversionError, ResponseBodySerializeError,
+            FaultInjected, InvalidHeader, ParamError, ParamFormatError,
+            RequestBodyDeserializeError, RequestBodyError, RequestConversionError,
+            ResponseBodyConversionError, ResponseBodyFileError, ResponseBodyMergeError,
+            ResponseBodySerializeError,
         },
+        matchers::comparison::path_template_params,
         state,
         state::StateManager,
     },
 };
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
 #[cfg(any(feature = "remote", feature = "proxy"))]
 use crate::common::http::{Error as HttpClientError, HttpClient};
+#[cfg(feature = "proxy")]
+use crate::common::{
+    data::{CallbackRequest, UpstreamErrorResponse},
+    http::{UpstreamConnection, UpstreamProxy},
+};
 
+#[cfg(feature = "record")]
+use crate::common::data::PlaybackConfig;
 use crate::common::data::{ForwardingRuleConfig, ProxyRuleConfig, RecordingRuleConfig};
+#[cfg(feature = "compression")]
+use crate::server::{compression, handler::Error::ResponseBodyCompressionError};
+#[cfg(feature = "compression")]
+use http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, VARY};
 
 use crate::prelude::HttpMockRequest;
 use async_std::{sync::Mutex, task};
 use async_trait::async_trait;
-use http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
-use http_body_util::BodyExt;
-use hyper::{body::Bytes, Method, Request, Response};
+use bytes::BytesMut;
+use http::{
+    header::{CONTENT_LENGTH, DATE, ETAG, LAST_MODIFIED, SERVER, TE, TRAILER},
+    HeaderMap, HeaderName, HeaderValue, StatusCode, Uri,
+};
+use http_body_util::{combinators::BoxBody, BodyExt, BodyStream, Full, StreamBody};
+use hyper::{
+    body::{Bytes, Frame},
+    ext::ReasonPhrase,
+    Method, Request, Response,
+};
 use path_tree::{Path, PathTree};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
@@ -37,7 +65,8 @@ use std::{
     time::Duration,
 };
 use thiserror::Error;
-use tokio::time::Instant;
+use tokio::{io::AsyncReadExt, time::Instant};
+use tracing::{field, Instrument, Span};
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -51,6 +80,13 @@ pub enum Error {
     ResponseBodySerializeError(serde_json::Error),
     #[error("cannot convert response body: {0}")]
     ResponseBodyConversionError(http::Error),
+    #[error("cannot read response body from file {0}: {1}")]
+    ResponseBodyFileError(String, std::io::Error),
+    #[error("cannot merge JSON response body: {0}")]
+    ResponseBodyMergeError(String),
+    #[cfg(feature = "compression")]
+    #[error("cannot compress response body: {0}")]
+    ResponseBodyCompressionError(std::io::Error),
     #[error("expected URL parameters not found")]
     ParamError,
     #[error("URL parameter format is invalid: {0}")]
@@ -66,30 +102,94 @@ pub enum Error {
     HttpClientError(#[from] HttpClientError),
     #[error("invalid header: {0}")]
     InvalidHeader(String),
+    #[error("fault injected: {0:?}")]
+    FaultInjected(Fault),
     #[error("unknown error")]
     Unknown,
 }
 
 enum RoutePath {
     Ping,
-    Reset,
+    State,
     MockCollection,
+    MockBatchCreation,
+    MockBatchDeletion,
+    TaggedMocks,
     SingleMock,
+    MockRequests,
+    MockStats,
     History,
+    UnmatchedRequests,
     Verify,
     SingleForwardingRule,
     ForwardingRuleCollection,
     ProxyRuleCollection,
     SingleProxyRule,
+    DefaultResponseHeaders,
+    Metrics,
     #[cfg(feature = "record")]
     RecordingCollection,
     #[cfg(feature = "record")]
     SingleRecording,
 }
 
+/// The name of the response header that carries the ID of the mock that served a request.
+const MATCHED_MOCK_ID_HEADER: &str = "x-httpmock-matched-id";
+
+/// How long a request waits for other concurrent requests (see `Then::wait_for_concurrent`)
+/// if no timeout was configured.
+const DEFAULT_WAIT_FOR_CONCURRENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The headers that the mock server adds to responses automatically and that a mock can omit
+/// (see `Then::omit_header`).
+pub(crate) const OMITTABLE_HEADERS: [HeaderName; 3] = [CONTENT_LENGTH, DATE, SERVER];
+
+/// A response extension that lists the headers that must not be sent with the response,
+/// including the ones that would otherwise be added automatically.
+#[derive(Clone, Debug)]
+pub(crate) struct OmittedHeaders(Vec<HeaderName>);
+
+/// A response extension that carries the ID of the mock that served the request.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MatchedMock(pub(crate) usize);
+
+impl OmittedHeaders {
+    fn from_response(res: &MockServerHttpResponse) -> Option<Self> {
+        let names: Vec<HeaderName> = res
+            .omit_headers
+            .iter()
+            .flatten()
+            .filter_map(|name| HeaderName::from_str(name).ok())
+            .collect();
+
+        if names.is_empty() {
+            return None;
+        }
+
+        Some(Self(names))
+    }
+
+    pub(crate) fn contains(&self, name: &HeaderName) -> bool {
+        self.0.contains(name)
+    }
+}
+
+pub(crate) type ResponseBody = BoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
 #[async_trait]
 pub(crate) trait Handler {
-    async fn handle(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error>;
+    async fn handle(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error>;
+}
+
+pub struct HttpMockHandlerConfig {
+    /// Whether debug headers, such as the ID of the matched mock, are added to responses.
+    pub debug_headers: bool,
+    /// Headers that are added to every response that does not already contain them.
+    pub default_response_headers: Vec<(String, String)>,
+    /// Answers CORS preflight requests and adds CORS headers to responses if set.
+    pub cors: Option<CorsConfig>,
+    /// Whether the metrics endpoint is available.
+    pub metrics: bool,
 }
 
 pub struct HttpMockHandler<S>
@@ -98,6 +198,7 @@ where
 {
     path_tree: PathTree<RoutePath>,
     state: Arc<S>,
+    config: HttpMockHandlerConfig,
     #[cfg(feature = "proxy")]
     http_client: Arc<dyn HttpClient + Send + Sync + 'static>,
 }
@@ -107,7 +208,48 @@ impl<H> Handler for HttpMockHandler<H>
 where
     H: StateManager + Send + Sync + 'static,
 {
-    async fn handle(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    async fn handle(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
+        // Spans are disabled when no tracing subscriber is installed, so recording is skipped.
+        let span = tracing::info_span!(
+            "httpmock.request",
+            method = %req.method(),
+            path = %req.uri().path(),
+            mock_id = field::Empty,
+            status = field::Empty,
+            duration_ms = field::Empty,
+        );
+
+        let start = Instant::now();
+        let result = self.route(req).instrument(span.clone()).await;
+
+        if !span.is_disabled() {
+            record_response(&span, &result, start.elapsed());
+        }
+
+        result
+    }
+}
+
+/// Records the outcome of a request on its tracing span.
+fn record_response(span: &Span, result: &Result<Response<ResponseBody>, Error>, elapsed: Duration) {
+    span.record("duration_ms", elapsed.as_secs_f64() * 1000.0);
+
+    match result {
+        Ok(res) => {
+            span.record("status", res.status().as_u16());
+            if let Some(mock) = res.extensions().get::<MatchedMock>() {
+                span.record("mock_id", mock.0);
+            }
+        }
+        Err(err) => tracing::debug!(parent: span, error = %err, "request failed"),
+    }
+}
+
+impl<H> HttpMockHandler<H>
+where
+    H: StateManager + Send + Sync + 'static,
+{
+    async fn route(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         log::trace!("Routing incoming request: {:?}", req);
 
         let method = req.method().clone();
@@ -119,8 +261,10 @@ where
                     Method::GET => return self.handle_ping(),
                     _ => {}
                 },
-                RoutePath::Reset => match method {
+                RoutePath::State => match method {
                     Method::DELETE => return self.handle_reset(),
+                    Method::GET => return self.handle_export_state(),
+                    Method::PUT => return self.handle_import_state(req),
                     _ => {}
                 },
                 RoutePath::SingleMock => match method {
@@ -128,7 +272,29 @@ where
                     Method::DELETE => return self.handle_delete_mock(params),
                     _ => {}
                 },
+                RoutePath::MockRequests => match method {
+                    Method::GET => return self.handle_read_mock_requests(params),
+                    _ => {}
+                },
+                RoutePath::MockStats => match method {
+                    Method::GET => return self.handle_read_mock_stats(params),
+                    _ => {}
+                },
+                RoutePath::MockBatchCreation => match method {
+                    Method::POST => return self.handle_add_mocks(req),
+                    _ => {}
+                },
+                RoutePath::MockBatchDeletion => match method {
+                    Method::POST => return self.handle_delete_mocks(req),
+                    _ => {}
+                },
+                RoutePath::TaggedMocks => match method {
+                    Method::GET => return self.handle_read_tagged_mocks(req),
+                    Method::DELETE => return self.handle_delete_tagged_mocks(req),
+                    _ => {}
+                },
                 RoutePath::MockCollection => match method {
+                    Method::GET => return self.handle_read_mocks(),
                     Method::POST => return self.handle_add_mock(req),
                     Method::DELETE => return self.handle_delete_all_mocks(),
                     _ => {}
@@ -137,6 +303,10 @@ where
                     Method::DELETE => return self.handle_delete_history(),
                     _ => {}
                 },
+                RoutePath::UnmatchedRequests => match method {
+                    Method::GET => return self.handle_read_unmatched_requests(),
+                    _ => {}
+                },
                 RoutePath::Verify => match method {
                     Method::POST => return self.handle_verify(req),
                     _ => {}
@@ -159,6 +329,14 @@ where
                     Method::DELETE => return self.handle_delete_proxy_rule(params),
                     _ => {}
                 },
+                RoutePath::DefaultResponseHeaders => match method {
+                    Method::PUT => return self.handle_set_default_response_headers(req),
+                    _ => {}
+                },
+                RoutePath::Metrics => match method {
+                    Method::GET => return self.handle_metrics(),
+                    _ => {}
+                },
                 #[cfg(feature = "record")]
                 RoutePath::RecordingCollection => match method {
                     Method::POST => return self.handle_add_recording_matcher(req),
@@ -175,7 +353,20 @@ where
             }
         }
 
-        return self.catch_all(req).await;
+        let origin = req.headers().get(http::header::ORIGIN).cloned();
+
+        let result = self.catch_all(req).await;
+        self.state
+            .record_request_metrics(result.as_ref().ok().map(|res| res.status().as_u16()));
+
+        let mut response = result?;
+        self.add_default_response_headers(&mut response)?;
+
+        if let (Some(cors), Some(origin)) = (&self.config.cors, origin) {
+            cors.add_response_headers(&origin, response.headers_mut());
+        }
+
+        Ok(remove_omitted_headers(response))
     }
 }
 
@@ -185,22 +376,40 @@ where
 {
     pub fn new(
         state: Arc<H>,
+        config: HttpMockHandlerConfig,
         #[cfg(feature = "proxy")] http_client: Arc<dyn HttpClient + Send + Sync + 'static>,
     ) -> Self {
         let mut path_tree: PathTree<RoutePath> = PathTree::new();
         #[allow(unused_must_use)]
         {
             path_tree.insert("/__httpmock__/ping", RoutePath::Ping);
-            path_tree.insert("/__httpmock__/state", RoutePath::Reset);
+            path_tree.insert("/__httpmock__/state", RoutePath::State);
             path_tree.insert("/__httpmock__/mocks", RoutePath::MockCollection);
             path_tree.insert("/__httpmock__/mocks/:id", RoutePath::SingleMock);
+            path_tree.insert("/__httpmock__/mocks/batch", RoutePath::MockBatchCreation);
+            path_tree.insert("/__httpmock__/mocks/delete", RoutePath::MockBatchDeletion);
+            path_tree.insert("/__httpmock__/mocks/tagged", RoutePath::TaggedMocks);
+            path_tree.insert("/__httpmock__/mocks/:id/requests", RoutePath::MockRequests);
+            path_tree.insert("/__httpmock__/mocks/:id/stats", RoutePath::MockStats);
             path_tree.insert("/__httpmock__/verify", RoutePath::Verify);
             path_tree.insert("/__httpmock__/history", RoutePath::History);
+            path_tree.insert(
+                "/__httpmock__/history/unmatched",
+                RoutePath::UnmatchedRequests,
+            );
+            path_tree.insert(
+                "/__httpmock__/default_headers",
+                RoutePath::DefaultResponseHeaders,
+            );
             path_tree.insert(
                 "/__httpmock__/forwarding_rules",
                 RoutePath::ForwardingRuleCollection,
             );
 
+            if config.metrics {
+                path_tree.insert("/__httpmock__/metrics", RoutePath::Metrics);
+            }
+
             #[cfg(feature = "record")]
             path_tree.insert("/__httpmock__/proxy_rules", RoutePath::ProxyRuleCollection);
             #[cfg(feature = "record")]
@@ -210,6 +419,7 @@ where
         Self {
             path_tree,
             state,
+            config,
             #[cfg(feature = "proxy")]
             http_client,
         }
@@ -219,22 +429,33 @@ where
         Ok(())
     }
 
-    fn handle_ping(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_ping(&self) -> Result<Response<ResponseBody>, Error> {
         return response::<()>(StatusCode::OK, None);
     }
 
-    fn handle_reset(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_reset(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.reset();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
-    fn handle_add_mock(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_add_mock(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         let definition: MockDefinition = parse_json_body(req)?;
         let active_mock = self.state.add_mock(definition, false)?;
         return response(StatusCode::CREATED, Some(active_mock));
     }
 
-    fn handle_read_mock(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_add_mocks(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
+        let definitions: Vec<MockDefinition> = parse_json_body(req)?;
+        let active_mocks = self.state.add_mocks(definitions, false)?;
+        return response(StatusCode::CREATED, Some(active_mocks));
+    }
+
+    fn handle_read_mocks(&self) -> Result<Response<ResponseBody>, Error> {
+        let mocks = self.state.read_mocks();
+        return response(StatusCode::OK, Some(mocks));
+    }
+
+    fn handle_read_mock(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let active_mock = self.state.read_mock(param("id", params)?)?;
         let status_code = active_mock
             .as_ref()
@@ -242,7 +463,21 @@ where
         return response(status_code, active_mock);
     }
 
-    fn handle_delete_mock(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_read_mock_requests(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
+        match self.state.received_requests(param("id", params)?)? {
+            Some(requests) => response(StatusCode::OK, Some(requests)),
+            None => response::<()>(StatusCode::NOT_FOUND, None),
+        }
+    }
+
+    fn handle_read_mock_stats(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
+        match self.state.mock_stats(param("id", params)?)? {
+            Some(stats) => response(StatusCode::OK, Some(stats)),
+            None => response::<()>(StatusCode::NOT_FOUND, None),
+        }
+    }
+
+    fn handle_delete_mock(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let deleted = self.state.delete_mock(param("id", params)?)?;
         let status_code = if deleted {
             StatusCode::NO_CONTENT
@@ -252,17 +487,44 @@ where
         return response::<()>(status_code, None);
     }
 
-    fn handle_delete_all_mocks(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_mocks(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
+        let ids: Vec<usize> = parse_json_body(req)?;
+        self.state.delete_mocks(&ids)?;
+        return response::<()>(StatusCode::NO_CONTENT, None);
+    }
+
+    fn handle_read_tagged_mocks(
+        &self,
+        req: Request<Bytes>,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let mocks = self.state.read_mocks_by_tag(&tag_param(&req)?);
+        return response(StatusCode::OK, Some(mocks));
+    }
+
+    fn handle_delete_tagged_mocks(
+        &self,
+        req: Request<Bytes>,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let deleted = self.state.delete_mocks_by_tag(&tag_param(&req)?);
+        return response(StatusCode::OK, Some(deleted));
+    }
+
+    fn handle_delete_all_mocks(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.delete_all_mocks();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
-    fn handle_delete_history(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_history(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.delete_history();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
-    fn handle_verify(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_read_unmatched_requests(&self) -> Result<Response<ResponseBody>, Error> {
+        let requests = self.state.unmatched_requests();
+        return response(StatusCode::OK, Some(requests));
+    }
+
+    fn handle_verify(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         let requirements: RequestRequirements = parse_json_body(req)?;
         let closest_match = self.state.verify(&requirements)?;
         let status_code = closest_match
@@ -271,13 +533,16 @@ where
         return response(status_code, closest_match);
     }
 
-    fn handle_add_forwarding_rule(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_add_forwarding_rule(
+        &self,
+        req: Request<Bytes>,
+    ) -> Result<Response<ResponseBody>, Error> {
         let config: ForwardingRuleConfig = parse_json_body(req)?;
         let active_forwarding_rule = self.state.create_forwarding_rule(config);
         return response(StatusCode::CREATED, Some(active_forwarding_rule));
     }
 
-    fn handle_delete_forwarding_rule(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_forwarding_rule(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let deleted = self.state.delete_forwarding_rule(param("id", params)?);
         let status_code = if deleted.is_some() {
             StatusCode::NO_CONTENT
@@ -287,18 +552,52 @@ where
         return response::<()>(status_code, None);
     }
 
-    fn handle_delete_all_forwarding_rules(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_all_forwarding_rules(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.delete_all_forwarding_rules();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
-    fn handle_add_proxy_rule(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_export_state(&self) -> Result<Response<ResponseBody>, Error> {
+        let snapshot = self.state.export_state()?;
+        return response(StatusCode::OK, Some(snapshot));
+    }
+
+    fn handle_import_state(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
+        let snapshot = StateSnapshot::from_json(req.body())
+            .map_err(|err| RequestBodyError(err.to_string()))?;
+        let mock_ids = self.state.import_state(snapshot)?;
+        return response(StatusCode::OK, Some(mock_ids));
+    }
+
+    fn handle_metrics(&self) -> Result<Response<ResponseBody>, Error> {
+        let body = self.state.metrics().to_prometheus_text();
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(
+                http::header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )
+            .body(full(body))
+            .map_err(|e| ResponseBodyConversionError(e))
+    }
+
+    fn handle_set_default_response_headers(
+        &self,
+        req: Request<Bytes>,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let headers: Vec<(String, String)> = parse_json_body(req)?;
+        self.state.set_default_response_headers(headers);
+        return response::<()>(StatusCode::NO_CONTENT, None);
+    }
+
+    fn handle_add_proxy_rule(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         let config: ProxyRuleConfig = parse_json_body(req)?;
         let active_proxy_rule = self.state.create_proxy_rule(config);
         return response(StatusCode::CREATED, Some(active_proxy_rule));
     }
 
-    fn handle_delete_proxy_rule(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_proxy_rule(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let deleted = self.state.delete_proxy_rule(param("id", params)?);
         let status_code = if deleted.is_some() {
             StatusCode::NO_CONTENT
@@ -308,20 +607,23 @@ where
         return response::<()>(status_code, None);
     }
 
-    fn handle_delete_all_proxy_rules(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_all_proxy_rules(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.delete_all_proxy_rules();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
     #[cfg(feature = "record")]
-    fn handle_add_recording_matcher(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_add_recording_matcher(
+        &self,
+        req: Request<Bytes>,
+    ) -> Result<Response<ResponseBody>, Error> {
         let req_req: RecordingRuleConfig = parse_json_body(req)?;
         let active_recording = self.state.create_recording(req_req);
         return response(StatusCode::CREATED, Some(active_recording));
     }
 
     #[cfg(feature = "record")]
-    fn handle_delete_recording(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_recording(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let deleted = self.state.delete_proxy_rule(param("id", params)?);
         let status_code = if deleted.is_some() {
             StatusCode::NO_CONTENT
@@ -332,13 +634,13 @@ where
     }
 
     #[cfg(feature = "record")]
-    fn handle_delete_all_recording_matchers(&self) -> Result<Response<Bytes>, Error> {
+    fn handle_delete_all_recording_matchers(&self) -> Result<Response<ResponseBody>, Error> {
         self.state.delete_all_recordings();
         return response::<()>(StatusCode::NO_CONTENT, None);
     }
 
     #[cfg(feature = "record")]
-    fn handle_read_recording(&self, params: Path) -> Result<Response<Bytes>, Error> {
+    fn handle_read_recording(&self, params: Path) -> Result<Response<ResponseBody>, Error> {
         let rec = self.state.export_recording(param("id", params)?)?;
         let status_code = rec
             .as_ref()
@@ -347,17 +649,57 @@ where
     }
 
     #[cfg(feature = "record")]
-    fn handle_load_recording(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    fn handle_load_recording(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         let recording_file_content = std::str::from_utf8(&req.body())
             .map_err(|err| RequestConversionError(err.to_string()))?;
 
+        let query_params = extract_query_params(&req)?;
+        let flag = |name: &str| query_params.iter().any(|(k, v)| k == name && v == "true");
+
+        let config = PlaybackConfig {
+            ordered: flag("ordered"),
+            replay_with_timing: flag("replay_with_timing"),
+        };
+
         let rec = self
             .state
-            .load_mocks_from_recording(recording_file_content)?;
+            .load_mocks_from_recording(recording_file_content, config)?;
         return response(StatusCode::OK, Some(rec));
     }
 
-    async fn catch_all(&self, req: Request<Bytes>) -> Result<Response<Bytes>, Error> {
+    /// Adds the default response headers to a response, unless the response already contains
+    /// a header with the same name. Default headers set at runtime replace the configured ones.
+    fn add_default_response_headers(
+        &self,
+        response: &mut Response<ResponseBody>,
+    ) -> Result<(), Error> {
+        let runtime_headers = self.state.default_response_headers();
+        let default_headers = runtime_headers
+            .as_ref()
+            .unwrap_or(&self.config.default_response_headers);
+
+        let explicit_headers: Vec<HeaderName> = response.headers().keys().cloned().collect();
+
+        for (name, value) in default_headers {
+            let name = HeaderName::from_str(name).map_err(|err| {
+                InvalidHeader(format!("invalid default header key: {}", err.to_string()))
+            })?;
+
+            if explicit_headers.contains(&name) {
+                continue;
+            }
+
+            let value = HeaderValue::from_str(value).map_err(|err| {
+                InvalidHeader(format!("invalid default header value: {}", err.to_string()))
+            })?;
+
+            response.headers_mut().append(name, value);
+        }
+
+        Ok(())
+    }
+
+    async fn catch_all(&self, req: Request<Bytes>) -> Result<Response<ResponseBody>, Error> {
         let internal_request: HttpMockRequest = (&req)
             .try_into()
             .map_err(|err: DataError| RequestConversionError(err.to_string()))?;
@@ -367,32 +709,43 @@ where
         let start = Instant::now();
 
         #[cfg(feature = "proxy")]
-        let res = if let Some(rule) = self.state.find_forward_rule(&internal_request)? {
-            self.forward(rule, req).await?
-        } else if let Some(rule) = self.state.find_proxy_rule(&internal_request)? {
-            is_proxied = true;
-            self.proxy(rule, req).await?
-        } else {
-            self.serve_mock(&internal_request).await?
-        };
+        {
+            let (res, on_error) =
+                if let Some(rule) = self.state.find_forward_rule(&internal_request)? {
+                    let target = ForwardingTarget::from(&rule.config);
+                    (self.forward(&target, req).await, target.on_error)
+                } else if let Some(rule) = self.state.find_proxy_rule(&internal_request)? {
+                    is_proxied = true;
+                    (self.proxy(rule, req).await, None)
+                } else {
+                    return self.serve_mock(internal_request, start).await;
+                };
+
+            let res = match res {
+                Err(Error::HttpClientError(err)) if err.is_upstream_failure() => {
+                    return upstream_error_response(on_error.as_ref(), &err)
+                }
+                res => res?,
+            };
 
-        #[cfg(not(feature = "proxy"))]
-        let res = self.serve_mock(&internal_request).await?;
+            #[cfg(feature = "record")]
+            self.state
+                .record(is_proxied, start.elapsed(), internal_request, &res)?;
 
-        #[cfg(feature = "record")]
-        self.state
-            .record(is_proxied, start.elapsed(), internal_request, &res)?;
+            Ok(res.map(full))
+        }
 
-        Ok(res)
+        #[cfg(not(feature = "proxy"))]
+        self.serve_mock(internal_request, start).await
     }
 
     #[cfg(feature = "proxy")]
     async fn forward(
         &self,
-        rule: ActiveForwardingRule,
+        target: &ForwardingTarget,
         req: Request<Bytes>,
     ) -> Result<Response<Bytes>, Error> {
-        let to_base_uri: Uri = rule.config.target_base_url.parse().unwrap();
+        let to_base_uri: Uri = target.target_base_url.parse().unwrap();
 
         let (mut req_parts, body) = req.into_parts();
 
@@ -404,22 +757,63 @@ where
         uri_parts.scheme = to_base_uri.scheme().map(|s| s.clone()).or(uri_parts.scheme);
         req_parts.uri = Uri::from_parts(uri_parts).unwrap();
 
-        if !rule.config.request_header.is_empty() {
-            for (key, value) in &rule.config.request_header {
-                let key = HeaderName::from_str(key).map_err(|err| {
-                    InvalidHeader(format!("invalid header key: {}", err.to_string()))
-                })?;
+        for change in &target.request_header_changes {
+            apply_header_change(&mut req_parts, change)?;
+        }
 
-                let value = HeaderValue::from_str(value).map_err(|err| {
-                    InvalidHeader(format!("invalid header value: {}", err.to_string()))
-                })?;
+        let connection = UpstreamConnection {
+            proxy: match &target.upstream_proxy {
+                Some(url) => UpstreamProxy::Url(url.clone()),
+                None => UpstreamProxy::Environment,
+            },
+            danger_accept_invalid_certs: target.danger_accept_invalid_certs,
+        };
+
+        let req = Request::from_parts(req_parts, body);
+        self.send_upstream(
+            req,
+            target.upstream_timeout,
+            target.retries,
+            Some(&connection),
+        )
+        .await
+    }
 
-                req_parts.headers.insert(key, value);
+    /// Forwards a request that matched a mock to the upstream server configured for the mock
+    /// and relays the upstream response.
+    #[cfg(feature = "proxy")]
+    async fn serve_proxied_mock(
+        &self,
+        mock_id: usize,
+        mock_response: MockServerHttpResponse,
+        target: &ForwardingTarget,
+        req: HttpMockRequest,
+        start: Instant,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let http_request = (&req)
+            .try_into()
+            .map_err(|err: DataError| RequestConversionError(err.to_string()))?;
+
+        let mut res = match self.forward(target, http_request).await {
+            Err(Error::HttpClientError(err)) if err.is_upstream_failure() => {
+                return upstream_error_response(target.on_error.as_ref(), &err)
             }
+            res => res?,
+        };
+
+        if self.config.debug_headers {
+            res.headers_mut()
+                .insert(MATCHED_MOCK_ID_HEADER, HeaderValue::from(mock_id));
         }
 
-        let req = Request::from_parts(req_parts, body);
-        Ok(self.http_client.send(req).await?)
+        if let Some(duration) = response_delay(&mock_response, &req) {
+            runtime::sleep(Duration::from_millis(duration)).await;
+        }
+
+        #[cfg(feature = "record")]
+        self.state.record(false, start.elapsed(), req, &res)?;
+
+        Ok(res.map(full))
     }
 
     #[cfg(feature = "proxy")]
@@ -444,45 +838,324 @@ where
             }
         }
 
-        Ok(self.http_client.send(req).await?)
+        // Requests are sent with the default client, unless certificate verification needs to
+        // be turned off.
+        let connection = rule
+            .config
+            .danger_accept_invalid_certs
+            .then(|| UpstreamConnection {
+                proxy: UpstreamProxy::None,
+                danger_accept_invalid_certs: true,
+            });
+
+        self.send_upstream(
+            req,
+            rule.config.upstream_timeout,
+            rule.config.retries,
+            connection.as_ref(),
+        )
+        .await
+    }
+
+    /// Sends a request to the upstream server of a forwarding or proxy rule, optionally with
+    /// custom connection settings (e.g., an upstream proxy). Requests with an idempotent method are retried up to `retries` times
+    /// if they fail or time out.
+    #[cfg(feature = "proxy")]
+    async fn send_upstream(
+        &self,
+        req: Request<Bytes>,
+        timeout_millis: Option<u64>,
+        retries: usize,
+        connection: Option<&UpstreamConnection>,
+    ) -> Result<Response<Bytes>, Error> {
+        let retries = if req.method().is_idempotent() {
+            retries
+        } else {
+            0
+        };
+
+        let mut attempt = 0;
+        loop {
+            let attempt_req = clone_request(&req);
+            let timeout = timeout_millis.map(Duration::from_millis);
+            let result = match (connection, timeout) {
+                (Some(connection), timeout) => {
+                    self.http_client
+                        .send_upstream(attempt_req, connection, timeout)
+                        .await
+                }
+                (None, Some(timeout)) => {
+                    self.http_client
+                        .send_with_timeout(attempt_req, timeout)
+                        .await
+                }
+                (None, None) => self.http_client.send(attempt_req).await,
+            };
+
+            match result {
+                Err(err) if attempt < retries => {
+                    attempt += 1;
+                    log::warn!(
+                        "upstream request failed, retrying ({} of {}): {}",
+                        attempt,
+                        retries,
+                        err
+                    );
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+
+    /// Sends the callback requests of a mock in the background, so that the mocked response is
+    /// not delayed. Failed callbacks are only logged, since the response was already produced.
+    #[cfg(feature = "proxy")]
+    fn send_callbacks(&self, callbacks: Vec<CallbackRequest>) {
+        for callback in callbacks {
+            let http_client = self.http_client.clone();
+            tokio::spawn(async move {
+                if let Some(delay) = callback.delay {
+                    runtime::sleep(Duration::from_millis(delay)).await;
+                }
+
+                let url = callback.url.clone().unwrap_or_default();
+                let result = match build_callback_request(callback) {
+                    Ok(req) => http_client.send(req).await.map_err(|err| err.to_string()),
+                    Err(err) => Err(err.to_string()),
+                };
+
+                match result {
+                    Ok(res) => log::debug!(
+                        "callback request to {} returned status {}",
+                        url,
+                        res.status()
+                    ),
+                    Err(err) => log::warn!("callback request to {} failed: {}", url, err),
+                }
+            });
+        }
     }
 
-    async fn serve_mock(&self, req: &HttpMockRequest) -> Result<Response<Bytes>, Error> {
-        let mock_response = self.state.serve_mock(req)?;
+    async fn serve_mock(
+        &self,
+        req: HttpMockRequest,
+        start: Instant,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let active_mock = match self.state.serve_mock(&req) {
+            Err(state::Error::PlaybackOrderError(msg)) => {
+                log::warn!("{}", msg);
+                return response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Some(ErrorResponse::new(&msg)),
+                );
+            }
+            result => result?,
+        };
+
+        if let Some(active_mock) = active_mock {
+            tracing::debug!(mock_id = active_mock.id, "request matched mock");
+
+            let mock_response = active_mock.definition.response;
+            let omitted_headers = OmittedHeaders::from_response(&mock_response);
+
+            if let Some(count) = mock_response.wait_for_concurrent {
+                let timeout = mock_response
+                    .wait_for_concurrent_timeout
+                    .map_or(DEFAULT_WAIT_FOR_CONCURRENT_TIMEOUT, Duration::from_millis);
+                let barrier = self.state.request_barrier(active_mock.id);
+                if !barrier.wait(count, timeout).await {
+                    log::warn!(
+                        "releasing request to mock {} after waiting {:?} for {} concurrent requests",
+                        active_mock.id,
+                        timeout,
+                        count
+                    );
+                }
+            }
+
+            #[cfg(feature = "proxy")]
+            if let Some(target) = mock_response.proxy_to.clone() {
+                let mut response = self
+                    .serve_proxied_mock(active_mock.id, mock_response, &target, req, start)
+                    .await?;
+                if let Some(omitted_headers) = omitted_headers {
+                    response.extensions_mut().insert(omitted_headers);
+                }
+                response
+                    .extensions_mut()
+                    .insert(MatchedMock(active_mock.id));
+                return Ok(response);
+            }
 
-        if let Some(mock_response) = mock_response {
             let status_code = match mock_response.status.as_ref() {
                 None => StatusCode::OK,
                 Some(c) => StatusCode::from_u16(c.clone())?,
             };
 
+            let status_code = match requested_status(&mock_response, &req) {
+                Ok(Some(requested)) => requested,
+                Ok(None) => status_code,
+                Err(msg) => {
+                    return response(StatusCode::BAD_REQUEST, Some(ErrorResponse::new(&msg)))
+                }
+            };
+
+            let not_modified = mock_response.conditional == Some(true)
+                && status_code.is_success()
+                && conditional::is_not_modified(
+                    mock_response.etag.as_deref(),
+                    mock_response.last_modified.as_deref(),
+                    &req,
+                );
+            let status_code = match not_modified {
+                true => StatusCode::NOT_MODIFIED,
+                false => status_code,
+            };
+
             let mut builder = Response::builder().status(status_code);
 
-            if let Some(headers) = mock_response.headers {
+            if let Some(headers) = &mock_response.headers {
                 for (name, value) in headers {
-                    builder = builder.header(name, value);
+                    if !not_modified || conditional::is_not_modified_header(name) {
+                        builder = builder.header(name, value);
+                    }
                 }
             }
 
-            let response = builder
-                .body(
-                    mock_response
-                        .body
-                        .map_or(Bytes::new(), |bytes| bytes.to_bytes()),
-                )
+            if let Some(etag) = &mock_response.etag {
+                builder = builder.header(ETAG, etag);
+            }
+
+            if let Some(last_modified) = &mock_response.last_modified {
+                builder = builder.header(LAST_MODIFIED, last_modified);
+            }
+
+            if self.config.debug_headers {
+                builder = builder.header(MATCHED_MOCK_ID_HEADER, active_mock.id);
+            }
+
+            let body = match (&mock_response.fault, &mock_response.body_file) {
+                (Some(Fault::CloseConnectionAfterHeaders), _) => aborted_body(),
+                _ if not_modified => full(Bytes::new()),
+                (_, Some(path)) => {
+                    let file = tokio::fs::File::open(path)
+                        .await
+                        .map_err(|e| ResponseBodyFileError(path.to_string(), e))?;
+                    let metadata = file
+                        .metadata()
+                        .await
+                        .map_err(|e| ResponseBodyFileError(path.to_string(), e))?;
+                    let omit_content_length = omitted_headers
+                        .as_ref()
+                        .map_or(false, |omitted| omitted.contains(&CONTENT_LENGTH));
+                    match mock_response.chunk_size {
+                        Some(chunk_size) => file_stream(file, chunk_size),
+                        None if omit_content_length => file_stream(file, FILE_CHUNK_SIZE),
+                        None => {
+                            builder = builder.header(http::header::CONTENT_LENGTH, metadata.len());
+                            file_stream(file, FILE_CHUNK_SIZE)
+                        }
+                    }
+                }
+                (_, None) => {
+                    let body = match &mock_response.body_template {
+                        Some(template) => {
+                            render_body_template(template, &active_mock.definition.request, &req)
+                        }
+                        None => mock_response
+                            .body
+                            .as_ref()
+                            .map_or(Bytes::new(), |bytes| bytes.to_bytes()),
+                    };
+                    let body = match &mock_response.json_body_merge {
+                        Some(partials) => {
+                            merge_json_body(&body, partials).map_err(ResponseBodyMergeError)?
+                        }
+                        None => body,
+                    };
+                    #[cfg(feature = "compression")]
+                    let body = match mock_response.auto_compress {
+                        Some(true) => compress_body(body, &req, &mut builder)?,
+                        _ => body,
+                    };
+                    match (mock_response.line_delay, mock_response.chunk_size) {
+                        (Some(delay), _) => line_body(body, Duration::from_millis(delay)),
+                        (None, Some(chunk_size)) => chunked_body(body, chunk_size),
+                        (None, None) => full(body),
+                    }
+                }
+            };
+
+            let body = match &mock_response.trailers {
+                Some(trailers) if !not_modified && mock_response.fault.is_none() => {
+                    if trailers_supported(&req) {
+                        let names: Vec<&str> =
+                            trailers.iter().map(|(name, _)| name.as_str()).collect();
+                        builder = builder.header(TRAILER, names.join(", "));
+                        with_trailers(body, trailers)?
+                    } else {
+                        log::warn!(
+                            "ignoring the trailers of mock {}: the {:?} request does not allow trailers (HTTP/1.1 requests need a 'TE: trailers' header)",
+                            active_mock.id,
+                            req.version()
+                        );
+                        body
+                    }
+                }
+                _ => body,
+            };
+
+            let mut response = builder
+                .body(body)
                 .map_err(|e| ResponseBodyConversionError(e))?;
 
-            if let Some(duration) = mock_response.delay {
+            if let Some(omitted_headers) = omitted_headers {
+                response.extensions_mut().insert(omitted_headers);
+            }
+            response
+                .extensions_mut()
+                .insert(MatchedMock(active_mock.id));
+
+            // Reason phrases are validated when the mock is created, so conversion cannot fail.
+            if let Some(reason) = &mock_response.reason {
+                if let Ok(reason) = ReasonPhrase::try_from(reason.as_bytes()) {
+                    response.extensions_mut().insert(reason);
+                }
+            }
+
+            if let Some(duration) = response_delay(&mock_response, &req) {
                 runtime::sleep(Duration::from_millis(duration)).await;
             }
 
+            match mock_response.fault {
+                None | Some(Fault::CloseConnectionAfterHeaders) => {}
+                Some(fault) => return Err(FaultInjected(fault)),
+            }
+
+            #[cfg(feature = "proxy")]
+            if let Some(callbacks) = &mock_response.after_respond {
+                self.send_callbacks(callbacks.clone());
+            }
+
+            #[cfg(feature = "record")]
+            self.state
+                .record(false, start.elapsed(), req, mock_response)?;
+
             return Ok(response);
         }
 
-        let status_code = mock_response.map_or(StatusCode::NOT_FOUND, |_| StatusCode::OK);
+        tracing::debug!("request did not match any mock");
+
+        // Preflight requests are only answered automatically if no mock matched, so that
+        // mocks can still intercept OPTIONS requests explicitly.
+        if let Some(cors) = &self.config.cors {
+            if is_preflight_request(&req.method(), &req.headers()) {
+                return cors_preflight_response(cors, &req);
+            }
+        }
 
         return response(
-            status_code,
+            StatusCode::NOT_FOUND,
             Some(ErrorResponse::new(
                 &"Request did not match any route or mock",
             )),
@@ -490,6 +1163,19 @@ where
     }
 }
 
+#[cfg(feature = "proxy")]
+fn build_callback_request(callback: CallbackRequest) -> Result<Request<Bytes>, http::Error> {
+    let mut builder = Request::builder()
+        .method(callback.method.as_deref().unwrap_or("POST"))
+        .uri(callback.url.unwrap_or_default());
+
+    for (name, value) in callback.headers {
+        builder = builder.header(name, value);
+    }
+
+    builder.body(callback.body.map_or(Bytes::new(), |body| body.to_bytes()))
+}
+
 fn param<T>(name: &str, tree_path: Path) -> Result<T, Error>
 where
     T: FromStr,
@@ -506,7 +1192,197 @@ where
     Err(ParamError)
 }
 
-fn response<T>(status: StatusCode, body: Option<T>) -> Result<Response<Bytes>, Error>
+/// Compresses a response body with the best encoding that the client accepts and updates the
+/// response headers accordingly. Bodies of responses that already declare an encoding are
+/// returned unchanged.
+#[cfg(feature = "compression")]
+fn compress_body(
+    body: Bytes,
+    req: &HttpMockRequest,
+    builder: &mut http::response::Builder,
+) -> Result<Bytes, Error> {
+    let headers = match builder.headers_mut() {
+        Some(headers) => headers,
+        None => return Ok(body),
+    };
+
+    if headers.contains_key(CONTENT_ENCODING) {
+        return Ok(body);
+    }
+
+    headers.append(VARY, HeaderValue::from_static("accept-encoding"));
+
+    let request_headers = req.headers();
+    let accept_encoding = request_headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+
+    let encoding = match compression::negotiate(accept_encoding) {
+        Some(encoding) => encoding,
+        None => return Ok(body),
+    };
+
+    let body = encoding
+        .encode(&body)
+        .map_err(ResponseBodyCompressionError)?;
+
+    // The length of the compressed body is set by hyper.
+    headers.remove(CONTENT_LENGTH);
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding.token()));
+
+    Ok(body)
+}
+
+/// Renders a response body template for a request. A `path.<name>` placeholder refers to
+/// the path parameter `<name>` captured by one of the mock's path templates, a `query.<name>`
+/// placeholder to the first query parameter and a `request.header.<name>` placeholder to the
+/// first request header with the given (case-insensitive) name.
+fn render_body_template(
+    template: &str,
+    requirements: &RequestRequirements,
+    req: &HttpMockRequest,
+) -> Bytes {
+    let path_params = requirements
+        .path_template
+        .iter()
+        .flatten()
+        .filter_map(|template| template.regex().ok())
+        .find_map(|regex| path_template_params(&regex.0, req.uri().path()))
+        .unwrap_or_default();
+
+    let rendered = render_template(template, |name| {
+        if let Some(param) = name.strip_prefix("path.") {
+            return path_params
+                .iter()
+                .find(|(key, _)| key == param)
+                .map(|(_, value)| value.clone());
+        }
+
+        if let Some(param) = name.strip_prefix("query.") {
+            return req
+                .query_params_vec()
+                .into_iter()
+                .find(|(key, _)| key == param)
+                .map(|(_, value)| value);
+        }
+
+        let header = name.strip_prefix("request.header.")?;
+        req.headers_vec()
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(header))
+            .map(|(_, value)| value.clone())
+    });
+
+    Bytes::from(rendered)
+}
+
+/// Reads the response status code from the request header configured with
+/// `Then::status_from_header`. Returns `None` if the header is absent or does not contain a
+/// number, and an error message if the number is not a valid HTTP status code.
+fn requested_status(
+    res: &MockServerHttpResponse,
+    req: &HttpMockRequest,
+) -> Result<Option<StatusCode>, String> {
+    let header_name = match &res.status_from_header {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let value = req
+        .headers_vec()
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(header_name))
+        .map(|(_, value)| value.trim());
+
+    let code = match value.and_then(|v| v.parse::<u64>().ok()) {
+        Some(code) => code,
+        None => return Ok(None),
+    };
+
+    code.try_into()
+        .ok()
+        .and_then(|code: u16| StatusCode::from_u16(code).ok())
+        .map(Some)
+        .ok_or_else(|| {
+            format!(
+                "request header '{}' contains an invalid HTTP status code: {}",
+                header_name, code
+            )
+        })
+}
+
+/// Returns the response delay in milliseconds. If `Then::delay_from_header` is configured and
+/// the request header contains a number, that number is used. Otherwise, the delay set with
+/// `Then::delay` is used.
+fn response_delay(res: &MockServerHttpResponse, req: &HttpMockRequest) -> Option<u64> {
+    let requested = res.delay_from_header.as_ref().and_then(|header_name| {
+        req.headers_vec()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(header_name))
+            .and_then(|(_, value)| value.trim().parse::<u64>().ok())
+    });
+
+    requested.or(res.delay)
+}
+
+/// Returns whether trailers can be sent in the response to a request. HTTP/2 always supports
+/// trailers. HTTP/1.1 only supports them with chunked transfer encoding and hyper only sends
+/// them if the client announced that it accepts trailers with a `TE: trailers` header.
+fn trailers_supported(req: &HttpMockRequest) -> bool {
+    match req.version() {
+        http::Version::HTTP_2 => true,
+        http::Version::HTTP_11 => req
+            .headers()
+            .get_all(TE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|token| token.trim().eq_ignore_ascii_case("trailers")),
+        _ => false,
+    }
+}
+
+/// Appends trailers to a response body. The body is streamed without a size hint, so that hyper
+/// sends an HTTP/1.1 body using chunked transfer encoding, which is required for trailers.
+fn with_trailers(body: ResponseBody, trailers: &[(String, String)]) -> Result<ResponseBody, Error> {
+    let mut trailer_map = HeaderMap::new();
+    for (name, value) in trailers {
+        let name = HeaderName::from_str(name)
+            .map_err(|err| InvalidHeader(format!("invalid trailer key: {}", err)))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|err| InvalidHeader(format!("invalid trailer value: {}", err)))?;
+        trailer_map.append(name, value);
+    }
+
+    Ok(StreamBody::new(BodyStream::new(body))
+        .with_trailers(async move { Some(Ok(trailer_map)) })
+        .boxed())
+}
+
+/// Creates the response to a CORS preflight request. Requests from origins that are not
+/// allowed are rejected without any CORS headers, so that browsers block the actual request.
+fn cors_preflight_response(
+    cors: &CorsConfig,
+    req: &HttpMockRequest,
+) -> Result<Response<ResponseBody>, Error> {
+    let headers = match cors.preflight_headers(&req.headers()) {
+        Some(headers) => headers,
+        None => {
+            return response(
+                StatusCode::FORBIDDEN,
+                Some(ErrorResponse::new(&"CORS request from origin not allowed")),
+            )
+        }
+    };
+
+    let mut res = response::<()>(StatusCode::NO_CONTENT, None)?;
+    res.headers_mut().extend(headers);
+
+    Ok(res)
+}
+
+fn response<T>(status: StatusCode, body: Option<T>) -> Result<Response<ResponseBody>, Error>
 where
     T: Serialize,
 {
@@ -519,15 +1395,216 @@ where
             serde_json::to_vec(&body_obj).map_err(|e| ResponseBodySerializeError(e))?;
 
         return Ok(builder
-            .body(Bytes::from(body_bytes))
+            .body(full(body_bytes))
             .map_err(|e| ResponseBodyConversionError(e))?);
     }
 
     return Ok(builder
-        .body(Bytes::new())
+        .body(full(Bytes::new()))
         .map_err(|e| ResponseBodyConversionError(e))?);
 }
 
+/// Creates the response to a request that could not be sent to the upstream server. Unless a
+/// custom error response is configured, timeouts result in a `504` response and all other
+/// failures (e.g., connection refused or DNS failures) in a `502` response with a short
+/// diagnostic message.
+#[cfg(feature = "proxy")]
+fn upstream_error_response(
+    on_error: Option<&UpstreamErrorResponse>,
+    err: &HttpClientError,
+) -> Result<Response<ResponseBody>, Error> {
+    log::warn!(
+        "cannot forward request to upstream server: {}",
+        err.detailed_message()
+    );
+
+    if let Some(on_error) = on_error {
+        return Response::builder()
+            .status(StatusCode::from_u16(on_error.status)?)
+            .body(full(on_error.body.clone()))
+            .map_err(ResponseBodyConversionError);
+    }
+
+    let (status, body) = match err {
+        HttpClientError::Timeout(timeout) => (
+            StatusCode::GATEWAY_TIMEOUT,
+            format!(
+                "httpmock: upstream server did not respond within {} ms",
+                timeout.as_millis()
+            ),
+        ),
+        err => (
+            StatusCode::BAD_GATEWAY,
+            format!(
+                "httpmock: cannot reach upstream server: {}",
+                err.detailed_message()
+            ),
+        ),
+    };
+
+    Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "text/plain")
+        .body(full(body))
+        .map_err(ResponseBodyConversionError)
+}
+
+/// Applies a header change to an outgoing forwarding request. A `Host` header is sent like any
+/// other header and never changes where the request is sent to. If no `Host` header is set,
+/// the HTTP client derives it from the target URL.
+#[cfg(feature = "proxy")]
+fn apply_header_change(
+    parts: &mut http::request::Parts,
+    change: &RequestHeaderChange,
+) -> Result<(), Error> {
+    let header_name = |name: &str| {
+        HeaderName::from_str(name)
+            .map_err(|err| InvalidHeader(format!("invalid header key: {}", err.to_string())))
+    };
+    let header_value = |value: &str| {
+        HeaderValue::from_str(value)
+            .map_err(|err| InvalidHeader(format!("invalid header value: {}", err.to_string())))
+    };
+
+    match change {
+        RequestHeaderChange::Add(name, value) | RequestHeaderChange::Set(name, value) => {
+            parts
+                .headers
+                .insert(header_name(name)?, header_value(value)?);
+        }
+        RequestHeaderChange::Append(name, value) => {
+            parts
+                .headers
+                .append(header_name(name)?, header_value(value)?);
+        }
+        RequestHeaderChange::Remove(name) => {
+            parts.headers.remove(header_name(name)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "proxy")]
+fn clone_request(req: &Request<Bytes>) -> Request<Bytes> {
+    let mut clone = Request::new(req.body().clone());
+    *clone.method_mut() = req.method().clone();
+    *clone.uri_mut() = req.uri().clone();
+    *clone.version_mut() = req.version();
+    *clone.headers_mut() = req.headers().clone();
+    clone
+}
+
+/// Removes the headers that a mock omits from its response. Hyper derives the `Content-Length`
+/// header from the exact size hint of the body, so the size hint is dropped as well if
+/// `Content-Length` is omitted. Hyper then sends the body using chunked transfer encoding, or
+/// by closing the connection after the body if the client does not support chunked encoding.
+fn remove_omitted_headers(mut response: Response<ResponseBody>) -> Response<ResponseBody> {
+    let omitted_headers = match response.extensions().get::<OmittedHeaders>() {
+        Some(omitted_headers) => omitted_headers.clone(),
+        None => return response,
+    };
+
+    for name in &omitted_headers.0 {
+        response.headers_mut().remove(name);
+    }
+
+    if omitted_headers.contains(&CONTENT_LENGTH) {
+        // Mapping the frames discards the size hint of the body.
+        response = response.map(|body| body.map_frame(|frame| frame).boxed());
+    }
+
+    response
+}
+
+pub(crate) fn full<T: Into<Bytes>>(chunk: T) -> ResponseBody {
+    Full::new(chunk.into())
+        .map_err(|never| match never {})
+        .boxed()
+}
+
+fn aborted_body() -> ResponseBody {
+    let chunks = futures_util::stream::once(async {
+        // Yield once so that the connection gets a chance to flush the response head
+        // before the body fails and the connection is torn down.
+        tokio::task::yield_now().await;
+        Err::<Frame<Bytes>, _>(std::io::Error::new(
+            std::io::ErrorKind::ConnectionAborted,
+            "connection closed by fault injection",
+        ))
+    });
+
+    StreamBody::new(chunks)
+        .map_err(|err: std::io::Error| err.into())
+        .boxed()
+}
+
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+fn file_stream(file: tokio::fs::File, chunk_size: usize) -> ResponseBody {
+    let chunks = futures_util::stream::try_unfold(file, move |mut file| async move {
+        let mut buffer = BytesMut::with_capacity(chunk_size);
+        match (&mut file)
+            .take(chunk_size as u64)
+            .read_buf(&mut buffer)
+            .await?
+        {
+            0 => Ok(None),
+            _ => Ok(Some((Frame::data(buffer.freeze()), file))),
+        }
+    });
+
+    StreamBody::new(chunks)
+        .map_err(|err: std::io::Error| err.into())
+        .boxed()
+}
+
+/// Streams an in-memory body as a sequence of frames of at most `chunk_size` bytes. Since the
+/// body has no known length, hyper sends it using chunked transfer encoding.
+fn chunked_body(body: Bytes, chunk_size: usize) -> ResponseBody {
+    let chunks = futures_util::stream::iter(
+        (0..body.len())
+            .step_by(chunk_size)
+            .map(|start| {
+                let end = usize::min(start + chunk_size, body.len());
+                Ok::<_, std::io::Error>(Frame::data(body.slice(start..end)))
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    StreamBody::new(chunks)
+        .map_err(|err: std::io::Error| err.into())
+        .boxed()
+}
+
+/// Streams an in-memory body as a sequence of frames that contain one line each (including the
+/// line terminator) and waits for `delay` before sending each line except the first. Like
+/// [`chunked_body`], the body is sent using chunked transfer encoding.
+fn line_body(body: Bytes, delay: Duration) -> ResponseBody {
+    let lines =
+        futures_util::stream::try_unfold((body, true), move |(mut rest, first)| async move {
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if !first {
+                runtime::sleep(delay).await;
+            }
+
+            let end = rest
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .map_or(rest.len(), |index| index + 1);
+            let line = rest.split_to(end);
+
+            Ok::<_, std::io::Error>(Some((Frame::data(line), (rest, false))))
+        });
+
+    StreamBody::new(lines)
+        .map_err(|err: std::io::Error| err.into())
+        .boxed()
+}
+
 fn parse_json_body<T>(req: Request<Bytes>) -> Result<T, Error>
 where
     T: DeserializeOwned,
@@ -551,6 +1628,15 @@ fn extract_query_params(req: &Request<Bytes>) -> Result<Vec<(String, String)>, E
     Ok(query_params)
 }
 
+/// Returns the value of the `tag` query parameter that selects the mocks of tag-scoped operations.
+fn tag_param(req: &Request<Bytes>) -> Result<String, Error> {
+    extract_query_params(req)?
+        .into_iter()
+        .find(|(name, _)| name == "tag")
+        .map(|(_, value)| value)
+        .ok_or(ParamError)
+}
+
 fn headers_to_vec<T>(req: &Request<T>) -> Result<Vec<(String, String)>, Error> {
     req.headers()
         .iter()
@@ -563,3 +1649,220 @@ fn headers_to_vec<T>(req: &Request<T>) -> Result<Vec<(String, String)>, Error> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::{render_body_template, Handler, HttpMockHandler, HttpMockHandlerConfig};
+    use crate::{
+        common::data::{
+            HttpMockRequest, MockDefinition, MockServerHttpResponse, PathTemplate,
+            RequestRequirements,
+        },
+        server::{
+            state::{HttpMockStateManager, StateManager},
+            RequestMetadata,
+        },
+    };
+    use bytes::Bytes;
+    use hyper::Request;
+    use std::{
+        collections::BTreeMap,
+        fmt::Debug,
+        sync::{Arc, Mutex, Once},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    /// Collects the fields of all spans and the messages of all events.
+    #[derive(Clone, Default)]
+    struct RecordingSubscriber {
+        spans: Arc<Mutex<Vec<(&'static str, BTreeMap<String, String>)>>>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = BTreeMap::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata().name(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = BTreeMap::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events
+                .lock()
+                .unwrap()
+                .push(fields.remove("message").unwrap_or_default());
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    fn handler() -> (HttpMockHandler<HttpMockStateManager>, usize) {
+        let state = Arc::new(HttpMockStateManager::new(100));
+
+        let mut request = RequestRequirements::new();
+        request.path = Some("/hello".to_string());
+        let mut response = MockServerHttpResponse::new();
+        response.status = Some(201);
+        let mock = state
+            .add_mock(MockDefinition { request, response }, false)
+            .unwrap();
+
+        let handler = HttpMockHandler::new(
+            state,
+            HttpMockHandlerConfig {
+                debug_headers: false,
+                default_response_headers: Vec::new(),
+                cors: None,
+                metrics: false,
+            },
+            #[cfg(feature = "proxy")]
+            Arc::new(crate::common::http::HttpMockHttpClient::new(None)),
+        );
+
+        (handler, mock.id)
+    }
+
+    fn handle_traced(
+        handler: &HttpMockHandler<HttpMockStateManager>,
+        path: &str,
+    ) -> RecordingSubscriber {
+        let subscriber = RecordingSubscriber::default();
+        let mut request = Request::get(path).body(Bytes::new()).unwrap();
+        request
+            .extensions_mut()
+            .insert(RequestMetadata::new("http"));
+
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(handler.handle(request))
+                .unwrap();
+        });
+
+        subscriber
+    }
+
+    #[test]
+    fn request_span_for_matched_request() {
+        let (handler, mock_id) = handler();
+
+        let subscriber = handle_traced(&handler, "/hello");
+
+        let spans = subscriber.spans.lock().unwrap();
+        let (name, fields) = &spans[0];
+        assert_eq!(*name, "httpmock.request");
+        assert_eq!(fields["method"], "GET");
+        assert_eq!(fields["path"], "/hello");
+        assert_eq!(fields["status"], "201");
+        assert_eq!(fields["mock_id"], mock_id.to_string());
+        assert!(fields.contains_key("duration_ms"));
+
+        let events = subscriber.events.lock().unwrap();
+        assert_eq!(*events, vec!["request matched mock".to_string()]);
+    }
+
+    #[test]
+    fn request_span_for_unmatched_request() {
+        let (handler, _) = handler();
+
+        let subscriber = handle_traced(&handler, "/other");
+
+        let spans = subscriber.spans.lock().unwrap();
+        let (_, fields) = &spans[0];
+        assert_eq!(fields["path"], "/other");
+        assert_eq!(fields["status"], "404");
+        assert!(!fields.contains_key("mock_id"));
+
+        let events = subscriber.events.lock().unwrap();
+        assert_eq!(*events, vec!["request did not match any mock".to_string()]);
+    }
+
+    /// Collects the messages of all log records emitted while the tests are running.
+    struct RecordingLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl log::Log for RecordingLogger {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            self.messages
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", record.level(), record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: RecordingLogger = RecordingLogger {
+        messages: Mutex::new(Vec::new()),
+    };
+
+    fn install_logger() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
+    #[test]
+    fn body_template_renders_request_values() {
+        install_logger();
+
+        let mut requirements = RequestRequirements::new();
+        requirements.path_template = Some(vec![PathTemplate::from("/users/{id}")]);
+        let req = HttpMockRequest::builder()
+            .path("/users/7")
+            .query_param("q", "admins")
+            .header("X-Tenant", "acme")
+            .build();
+
+        let body = render_body_template(
+            "{{ path.id }}/{{ query.q }}/{{ request.header.x-tenant }}/{{ query.missing_param }}",
+            &requirements,
+            &req,
+        );
+
+        assert_eq!(body, Bytes::from("7/admins/acme/"));
+        assert!(LOGGER.messages.lock().unwrap().contains(
+            &"WARN cannot render unknown template placeholder 'query.missing_param'".to_string()
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    collections::VecDeque,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::common::data::RawRequestHead;
+
+/// The maximum size of a request head that is inspected. Larger heads are passed through
+/// without recording their header names.
+const MAX_HEAD_SIZE: usize = 1024 * 1024;
+
+/// The maximum number of headers per request that is inspected.
+const MAX_HEADERS: usize = 256;
+
+/// The heads of the requests received on a connection exactly as the client sent them, in the
+/// order the requests were received.
+pub(crate) type RequestHeadQueue = Arc<Mutex<VecDeque<RawRequestHead>>>;
+
+/// Wraps the stream of an HTTP/1 connection and records the request line and header lines of
+/// every request exactly as the client sent them. Hyper normalizes header names to lowercase
+/// and does not expose the original head of a request, so it is read from the bytes of the
+/// request heads before hyper parses them.
+///
+/// Request bodies are skipped based on their `Content-Length` or chunked transfer encoding.
+/// Once the stream contains anything that is not an HTTP/1 request (e.g., an HTTP/2 preface
+/// or an upgraded connection), recording stops and requests fall back to lowercase names.
+pub(crate) struct HeaderCaseRecorder<S> {
+    stream: S,
+    parser: RequestStreamParser,
+}
+
+impl<S> HeaderCaseRecorder<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            parser: RequestStreamParser::new(),
+        }
+    }
+
+    /// Returns the queue that receives the head of every request on this connection.
+    pub(crate) fn request_heads(&self) -> RequestHeadQueue {
+        self.parser.heads.clone()
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for HeaderCaseRecorder<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        let before = buf.filled().len();
+        let result = Pin::new(&mut this.stream).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            this.parser.feed(&buf.filled()[before..]);
+        }
+
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for HeaderCaseRecorder<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
+enum State {
+    Head(Vec<u8>),
+    Body(u64),
+    ChunkSize(Vec<u8>),
+    ChunkData(u64),
+    Trailers(Vec<u8>),
+    Passthrough,
+}
+
+/// Follows the requests of an HTTP/1 connection and extracts the head of each request.
+struct RequestStreamParser {
+    state: State,
+    heads: RequestHeadQueue,
+}
+
+impl RequestStreamParser {
+    fn new() -> Self {
+        Self {
+            state: State::Head(Vec::new()),
+            heads: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    fn feed(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            match &mut self.state {
+                State::Passthrough => return,
+                State::Head(head) => {
+                    let (byte, rest) = (data[0], &data[1..]);
+                    data = rest;
+
+                    // Empty lines in front of a request line are ignored (RFC 9112, section 2.2).
+                    if head.is_empty() && (byte == b'\r' || byte == b'\n') {
+                        continue;
+                    }
+
+                    head.push(byte);
+                    if head.ends_with(b"\r\n\r\n") {
+                        let head = std::mem::take(head);
+                        self.state = self.parse_head(&head);
+                    } else if head.len() > MAX_HEAD_SIZE {
+                        self.state = State::Passthrough;
+                    }
+                }
+                State::Body(remaining) | State::ChunkData(remaining) => {
+                    let consumed = (data.len() as u64).min(*remaining);
+                    *remaining -= consumed;
+                    data = &data[consumed as usize..];
+
+                    if *remaining == 0 {
+                        self.state = match self.state {
+                            State::ChunkData(_) => State::ChunkSize(Vec::new()),
+                            _ => State::Head(Vec::new()),
+                        };
+                    }
+                }
+                State::ChunkSize(line) => {
+                    let (byte, rest) = (data[0], &data[1..]);
+                    data = rest;
+                    line.push(byte);
+
+                    if line.ends_with(b"\r\n") {
+                        self.state = match parse_chunk_size(line) {
+                            // The chunk data is followed by a CRLF.
+                            Some(0) => State::Trailers(Vec::new()),
+                            Some(size) => State::ChunkData(size.saturating_add(2)),
+                            None => State::Passthrough,
+                        };
+                    } else if line.len() > MAX_HEAD_SIZE {
+                        self.state = State::Passthrough;
+                    }
+                }
+                State::Trailers(line) => {
+                    let (byte, rest) = (data[0], &data[1..]);
+                    data = rest;
+                    line.push(byte);
+
+                    if line.as_slice() == b"\r\n" {
+                        self.state = State::Head(Vec::new());
+                    } else if line.ends_with(b"\r\n") {
+                        line.clear();
+                    } else if line.len() > MAX_HEAD_SIZE {
+                        self.state = State::Passthrough;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records a complete request head and determines how the request body is framed.
+    fn parse_head(&self, head: &[u8]) -> State {
+        let mut headers = vec![httparse::EMPTY_HEADER; MAX_HEADERS];
+        let mut req = httparse::Request::new(&mut headers);
+
+        match req.parse(head) {
+            Ok(httparse::Status::Complete(_)) => {}
+            _ => return State::Passthrough,
+        }
+
+        // An HTTP/2 connection preface or a CONNECT tunnel is no HTTP/1 request stream.
+        if matches!(req.method, Some("PRI") | Some("CONNECT")) {
+            return State::Passthrough;
+        }
+
+        let mut chunked = false;
+        let mut content_length = 0;
+        let mut upgrade = false;
+        for header in req.headers.iter() {
+            let value = String::from_utf8_lossy(header.value);
+            if header.name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.rsplit(',').next().map_or(false, |coding| {
+                    coding.trim().eq_ignore_ascii_case("chunked")
+                });
+            } else if header.name.eq_ignore_ascii_case("content-length") {
+                match value.trim().parse::<u64>() {
+                    Ok(length) => content_length = length,
+                    Err(_) => return State::Passthrough,
+                }
+            } else if header.name.eq_ignore_ascii_case("upgrade") {
+                upgrade = true;
+            }
+        }
+
+        let mut lines = head
+            .split(|byte| *byte == b'\n')
+            .map(|line| String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)))
+            .filter(|line| !line.is_empty());
+        let start_line = lines.next().unwrap_or_default().into_owned();
+        let header_lines = lines.map(|line| line.into_owned()).collect();
+        self.heads.lock().unwrap().push_back(RawRequestHead {
+            start_line,
+            header_lines,
+        });
+
+        // The connection may carry a different protocol after the upgrade.
+        if upgrade {
+            State::Passthrough
+        } else if chunked {
+            State::ChunkSize(Vec::new())
+        } else if content_length > 0 {
+            State::Body(content_length)
+        } else {
+            State::Head(Vec::new())
+        }
+    }
+}
+
+fn parse_chunk_size(line: &[u8]) -> Option<u64> {
+    let line = std::str::from_utf8(line).ok()?;
+    let size = line.trim_end().split(';').next()?.trim();
+    u64::from_str_radix(size, 16).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::RequestStreamParser;
+    use crate::common::data::RawRequestHead;
+
+    fn parse(stream: &[u8], split_at: usize) -> Vec<Vec<String>> {
+        parse_heads(stream, split_at)
+            .iter()
+            .map(RawRequestHead::header_names)
+            .collect()
+    }
+
+    fn parse_heads(stream: &[u8], split_at: usize) -> Vec<RawRequestHead> {
+        let mut parser = RequestStreamParser::new();
+        let (first, second) = stream.split_at(split_at);
+        parser.feed(first);
+        parser.feed(second);
+
+        let heads = parser.heads.lock().unwrap();
+        heads.iter().cloned().collect()
+    }
+
+    #[test]
+    fn records_header_names_of_pipelined_requests() {
+        let stream =
+            b"POST /a HTTP/1.1\r\nHost: x\r\nContent-Length: 5\r\nX-Custom: 1\r\n\r\nhello\
+                       POST /b HTTP/1.1\r\nTransfer-Encoding: chunked\r\nx-LOWER: 1\r\n\r\n\
+                       3;ext=1\r\nabc\r\n0\r\nTrailer: t\r\n\r\n\
+                       GET /c HTTP/1.1\r\nACCEPT: */*\r\n\r\n";
+
+        for split_at in 0..stream.len() {
+            assert_eq!(
+                parse(stream, split_at),
+                vec![
+                    vec!["Host", "Content-Length", "X-Custom"],
+                    vec!["Transfer-Encoding", "x-LOWER"],
+                    vec!["ACCEPT"],
+                ],
+                "split at {}",
+                split_at
+            );
+        }
+    }
+
+    #[test]
+    fn records_raw_request_lines_in_order() {
+        let stream = b"GET /a?b=c HTTP/1.1\r\nX-B:  2\r\nHost: x\r\nx-b: 1\r\n\r\n";
+
+        assert_eq!(
+            parse_heads(stream, 7),
+            vec![RawRequestHead {
+                start_line: "GET /a?b=c HTTP/1.1".to_string(),
+                header_lines: vec![
+                    "X-B:  2".to_string(),
+                    "Host: x".to_string(),
+                    "x-b: 1".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn stops_recording_for_http2() {
+        let stream = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\nGET / HTTP/1.1\r\nHost: x\r\n\r\n";
+        assert!(parse(stream, 10).is_empty());
+    }
+}
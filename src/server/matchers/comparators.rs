@@ -5,13 +5,19 @@ use std::{borrow::Cow, convert::TryInto, ops::Deref, sync::Arc};
 
 use crate::{
     common::{
-        data::{HttpMockRegex, HttpMockRequest},
+        data::{
+            AnyOfPatterns, AnyOfValues, BodyFormat, BodyFormatValidation, BodyPresence,
+            BodySizeRange, ClientCertificate, DecodedBody, HttpMockRegex, HttpMockRequest,
+            JsonArrayElements, JsonSchema, PathTemplate, RequestAuthority,
+        },
         util::HttpMockBytes,
     },
     server::matchers::comparison::{
         distance_for, distance_for_prefix, distance_for_substring, distance_for_suffix,
-        equal_weight_distance_for, hostname_equals, regex_unmatched_length, string_contains,
+        equal_weight_distance_for, glob_to_regex, hostname_equals, json_array_includes,
+        json_equals_unordered, regex_unmatched_length, split_authority, string_contains,
         string_distance, string_equals, string_has_prefix, string_has_suffix,
+        without_trailing_slash,
     },
 };
 
@@ -21,16 +27,24 @@ pub trait ValueComparator<S: ?Sized, T: ?Sized> {
     fn matches(&self, mock_value: &Option<&S>, req_value: &Option<&T>) -> bool;
     fn name(&self) -> &str;
     fn distance(&self, mock_value: &Option<&S>, req_value: &Option<&T>) -> usize;
+
+    /// Explains why the values do not match. Only comparators that can provide more details
+    /// than the compared values themselves need to implement this.
+    fn reason(&self, _mock_value: &Option<&S>, _req_value: &Option<&T>) -> Option<String> {
+        None
+    }
 }
 
 // ************************************************************************************************
 // JSONExactMatchComparator
 // ************************************************************************************************
-pub struct JSONExactMatchComparator {}
+pub struct JSONExactMatchComparator {
+    pub negated: bool,
+}
 
 impl JSONExactMatchComparator {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(negated: bool) -> Self {
+        Self { negated }
     }
 }
 
@@ -38,20 +52,40 @@ impl ValueComparator<Value, Value> for JSONExactMatchComparator {
     fn matches(&self, mock_value: &Option<&Value>, req_value: &Option<&Value>) -> bool {
         match (mock_value, req_value) {
             (None, _) => true,
-            (Some(_), None) => false,
+            // A request body that is not valid JSON is never equal to the expected value.
+            (Some(_), None) => self.negated,
             (Some(mv), Some(rv)) => {
                 let config = Config::new(CompareMode::Strict);
-                assert_json_matches_no_panic(rv, mv, config).is_ok()
+                let matches = assert_json_matches_no_panic(rv, mv, config).is_ok();
+                if self.negated {
+                    !matches
+                } else {
+                    matches
+                }
             }
         }
     }
 
     fn name(&self) -> &str {
-        "equals"
+        if self.negated {
+            return "not equal to";
+        }
+
+        return "equals";
     }
 
     fn distance(&self, mock_value: &Option<&Value>, req_value: &Option<&Value>) -> usize {
         let mv_bytes = mock_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+
+        if self.negated {
+            // negation is taken care of in matches!
+            return if self.matches(mock_value, req_value) {
+                0
+            } else {
+                mv_bytes.len()
+            };
+        }
+
         let rv_bytes = req_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
         distance_for(&mv_bytes, &rv_bytes)
     }
@@ -108,6 +142,183 @@ impl ValueComparator<Value, Value> for JSONContainsMatchComparator {
     }
 }
 
+// ************************************************************************************************
+// JSONUnorderedMatchComparator
+// ************************************************************************************************
+pub struct JSONUnorderedMatchComparator {}
+
+impl JSONUnorderedMatchComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<Value, Value> for JSONUnorderedMatchComparator {
+    fn matches(&self, mock_value: &Option<&Value>, req_value: &Option<&Value>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => json_equals_unordered(mv, rv),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "equals (ignoring array order)"
+    }
+
+    fn distance(&self, mock_value: &Option<&Value>, req_value: &Option<&Value>) -> usize {
+        if self.matches(mock_value, req_value) {
+            return 0;
+        }
+
+        let mv_bytes = mock_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+        let rv_bytes = req_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+        distance_for(&mv_bytes, &rv_bytes)
+    }
+}
+
+// ************************************************************************************************
+// JSONKeyPresenceComparator
+// ************************************************************************************************
+pub struct JSONKeyPresenceComparator {
+    negated: bool,
+}
+
+impl JSONKeyPresenceComparator {
+    pub fn new(negated: bool) -> Self {
+        Self { negated }
+    }
+}
+
+impl ValueComparator<String, Value> for JSONKeyPresenceComparator {
+    fn matches(&self, mock_value: &Option<&String>, req_value: &Option<&Value>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            // A body that is not valid JSON has no keys at all.
+            (Some(_), None) => self.negated,
+            (Some(pointer), Some(rv)) => rv.pointer(pointer).is_some() != self.negated,
+        }
+    }
+
+    fn name(&self) -> &str {
+        if self.negated {
+            "does not contain key"
+        } else {
+            "contains key"
+        }
+    }
+
+    fn distance(&self, mock_value: &Option<&String>, req_value: &Option<&Value>) -> usize {
+        if self.matches(mock_value, req_value) {
+            return 0;
+        }
+
+        let mv_bytes = mock_value.map_or(Vec::new(), |v| v.as_bytes().to_vec());
+        let rv_bytes = req_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+        distance_for(&mv_bytes, &rv_bytes)
+    }
+}
+
+// ************************************************************************************************
+// JSONArrayIncludesComparator
+// ************************************************************************************************
+pub struct JSONArrayIncludesComparator {}
+
+impl JSONArrayIncludesComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<JsonArrayElements, Value> for JSONArrayIncludesComparator {
+    fn matches(&self, mock_value: &Option<&JsonArrayElements>, req_value: &Option<&Value>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => match rv.pointer(&mv.pointer) {
+                Some(Value::Array(actual)) => json_array_includes(&mv.elements, actual),
+                _ => false,
+            },
+        }
+    }
+
+    fn name(&self) -> &str {
+        "includes"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&JsonArrayElements>,
+        req_value: &Option<&Value>,
+    ) -> usize {
+        if self.matches(mock_value, req_value) {
+            return 0;
+        }
+
+        let mv_bytes = mock_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+        let rv_bytes = req_value.map_or(Vec::new(), |v| v.to_string().into_bytes());
+        distance_for(&mv_bytes, &rv_bytes)
+    }
+}
+
+// ************************************************************************************************
+// AuthorityEqualsComparator
+// ************************************************************************************************
+pub struct AuthorityEqualsComparator {}
+
+impl AuthorityEqualsComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Resolves the expected authority to a host and port. If the expectation does not contain
+    /// a port, the default port of the request scheme is expected.
+    fn expected(mock_value: &str, req_value: &RequestAuthority) -> Option<(String, u16)> {
+        let (host, port) = split_authority(mock_value).ok()?;
+        Some((host, port.unwrap_or(req_value.default_port)))
+    }
+}
+
+impl ValueComparator<String, RequestAuthority> for AuthorityEqualsComparator {
+    fn matches(&self, mock_value: &Option<&String>, req_value: &Option<&RequestAuthority>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => match Self::expected(mv, rv) {
+                Some((host, port)) => {
+                    port == rv.port && hostname_equals(false, &Some(&host), &Some(&rv.host))
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn name(&self) -> &str {
+        "equals"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&String>,
+        req_value: &Option<&RequestAuthority>,
+    ) -> usize {
+        if self.matches(mock_value, req_value) {
+            return 0;
+        }
+
+        let expected = match (mock_value, req_value) {
+            (Some(mv), Some(rv)) => Self::expected(mv, rv)
+                .map(|(host, port)| format!("{}:{}", host, port))
+                .unwrap_or_else(|| mv.to_string()),
+            (Some(mv), None) => mv.to_string(),
+            (None, _) => return 0,
+        };
+        let actual = req_value.map(|rv| rv.to_string());
+
+        string_distance(false, false, &Some(&expected), &actual.as_ref())
+    }
+}
+
 // ************************************************************************************************
 // StringExactMatchComparator
 // ************************************************************************************************
@@ -179,6 +390,44 @@ impl ValueComparator<String, String> for StringEqualsComparator {
     }
 }
 
+// ************************************************************************************************
+// StringAnyOfComparator
+// ************************************************************************************************
+pub struct StringAnyOfComparator {
+    case_sensitive: bool,
+}
+
+impl StringAnyOfComparator {
+    pub fn new(case_sensitive: bool) -> Self {
+        Self { case_sensitive }
+    }
+}
+
+impl ValueComparator<AnyOfValues, String> for StringAnyOfComparator {
+    fn matches(&self, mock_value: &Option<&AnyOfValues>, req_value: &Option<&String>) -> bool {
+        match mock_value {
+            None => true,
+            Some(mv) => {
+                mv.0.iter()
+                    .any(|v| string_equals(self.case_sensitive, false, &Some(v), req_value))
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "is one of"
+    }
+
+    fn distance(&self, mock_value: &Option<&AnyOfValues>, req_value: &Option<&String>) -> usize {
+        mock_value.map_or(0, |mv| {
+            mv.0.iter()
+                .map(|v| string_distance(self.case_sensitive, false, &Some(v), req_value))
+                .min()
+                .unwrap_or(0)
+        })
+    }
+}
+
 // ************************************************************************************************
 // StringIncludesMatchComparator
 // ************************************************************************************************
@@ -416,6 +665,138 @@ impl ValueComparator<HttpMockRegex, HttpMockBytes> for HttpMockBytesPatternCompa
     }
 }
 
+// ************************************************************************************************
+// DecodedBodyPatternComparator
+// ************************************************************************************************
+pub struct DecodedBodyPatternComparator {}
+
+impl DecodedBodyPatternComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<HttpMockRegex, DecodedBody> for DecodedBodyPatternComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&HttpMockRegex>,
+        req_value: &Option<&DecodedBody>,
+    ) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => match &rv.body {
+                Ok(body) => mv.0.is_match(&body.to_maybe_lossy_str()),
+                Err(_) => false,
+            },
+        }
+    }
+
+    fn name(&self) -> &str {
+        "matches regex"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&HttpMockRegex>,
+        req_value: &Option<&DecodedBody>,
+    ) -> usize {
+        let rv = match req_value.map(|rv| &rv.body) {
+            Some(Ok(body)) => body.to_maybe_lossy_str(),
+            _ => Cow::Borrowed(""),
+        };
+
+        let default_pattern = HttpMockRegex(regex::Regex::new(".*").unwrap());
+
+        let mv = mock_value.unwrap_or(&default_pattern);
+
+        regex_unmatched_length(&rv, &mv)
+    }
+
+    fn reason(
+        &self,
+        _mock_value: &Option<&HttpMockRegex>,
+        req_value: &Option<&DecodedBody>,
+    ) -> Option<String> {
+        let rv = req_value.as_ref()?;
+        rv.body.as_ref().err().map(|err| {
+            format!(
+                "cannot decode the request body with content encoding '{}': {}",
+                rv.encoding, err
+            )
+        })
+    }
+}
+
+// ************************************************************************************************
+// HttpMockBytesAnyPatternComparator
+// ************************************************************************************************
+pub struct HttpMockBytesAnyPatternComparator {}
+
+impl HttpMockBytesAnyPatternComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<AnyOfPatterns, HttpMockBytes> for HttpMockBytesAnyPatternComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&AnyOfPatterns>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => {
+                let body = rv.to_maybe_lossy_str();
+                mv.0.iter().any(|pattern| pattern.0.is_match(&body))
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "matches any regex of"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&AnyOfPatterns>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> usize {
+        let rv = match req_value {
+            Some(s) => s.to_maybe_lossy_str(),
+            None => Cow::Borrowed(""),
+        };
+
+        mock_value.map_or(0, |mv| {
+            mv.0.iter()
+                .map(|pattern| regex_unmatched_length(&rv, pattern))
+                .min()
+                .unwrap_or(0)
+        })
+    }
+
+    fn reason(
+        &self,
+        mock_value: &Option<&AnyOfPatterns>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> Option<String> {
+        if self.matches(mock_value, req_value) {
+            return None;
+        }
+
+        let patterns = mock_value.map_or(Vec::new(), |mv| {
+            mv.0.iter().map(|p| format!("'{}'", p)).collect()
+        });
+
+        Some(format!(
+            "none of the regexes matched: {}",
+            patterns.join(", ")
+        ))
+    }
+}
+
 // ************************************************************************************************
 // StringExactMatchComparator
 // ************************************************************************************************
@@ -451,6 +832,165 @@ impl ValueComparator<HttpMockRegex, String> for StringRegexMatchComparator {
     }
 }
 
+// ************************************************************************************************
+// StringGlobMatchComparator
+// ************************************************************************************************
+pub struct StringGlobMatchComparator {}
+
+impl StringGlobMatchComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<String, String> for StringGlobMatchComparator {
+    fn matches(&self, mock_value: &Option<&String>, req_value: &Option<&String>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => glob_to_regex(mv).0.is_match(rv),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "matches glob"
+    }
+
+    fn distance(&self, mock_value: &Option<&String>, req_value: &Option<&String>) -> usize {
+        let rv = req_value.map_or("", |s| s.as_str());
+        match mock_value {
+            None => 0,
+            Some(mv) => regex_unmatched_length(rv, &glob_to_regex(mv)),
+        }
+    }
+}
+
+// ************************************************************************************************
+// PathTemplateMatchComparator
+// ************************************************************************************************
+pub struct PathTemplateMatchComparator {}
+
+impl PathTemplateMatchComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<PathTemplate, String> for PathTemplateMatchComparator {
+    fn matches(&self, mock_value: &Option<&PathTemplate>, req_value: &Option<&String>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => mv.regex().map_or(false, |regex| regex.0.is_match(rv)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "matches template"
+    }
+
+    fn distance(&self, mock_value: &Option<&PathTemplate>, req_value: &Option<&String>) -> usize {
+        let rv = req_value.map_or("", |s| s.as_str());
+        match mock_value.map(|mv| mv.regex()) {
+            None => 0,
+            Some(Ok(regex)) => regex_unmatched_length(rv, regex),
+            Some(Err(_)) => rv.len(),
+        }
+    }
+}
+
+// ************************************************************************************************
+// PathEqualsIgnoringTrailingSlashComparator
+// ************************************************************************************************
+pub struct PathEqualsIgnoringTrailingSlashComparator {
+    negated: bool,
+}
+
+impl PathEqualsIgnoringTrailingSlashComparator {
+    pub fn new(negated: bool) -> Self {
+        Self { negated }
+    }
+}
+
+impl ValueComparator<String, String> for PathEqualsIgnoringTrailingSlashComparator {
+    fn matches(&self, mock_value: &Option<&String>, req_value: &Option<&String>) -> bool {
+        let mv = mock_value.map(|v| without_trailing_slash(v));
+        let rv = req_value.map(|v| without_trailing_slash(v));
+        string_equals(true, self.negated, &mv.as_ref(), &rv.as_ref())
+    }
+
+    fn name(&self) -> &str {
+        if self.negated {
+            return "not equal to (ignoring trailing slash)";
+        }
+
+        return "equals (ignoring trailing slash)";
+    }
+
+    fn distance(&self, mock_value: &Option<&String>, req_value: &Option<&String>) -> usize {
+        let mv = mock_value.map(|v| without_trailing_slash(v));
+        let rv = req_value.map(|v| without_trailing_slash(v));
+        string_distance(true, self.negated, &mv.as_ref(), &rv.as_ref())
+    }
+}
+
+// ************************************************************************************************
+// ClientCertCommonNameComparator
+// ************************************************************************************************
+pub struct ClientCertCommonNameComparator {}
+
+impl ClientCertCommonNameComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<String, ClientCertificate> for ClientCertCommonNameComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&String>,
+        req_value: &Option<&ClientCertificate>,
+    ) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => rv.common_name.as_ref() == Some(*mv),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "equals"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&String>,
+        req_value: &Option<&ClientCertificate>,
+    ) -> usize {
+        let rv = req_value.and_then(|cert| cert.common_name.as_ref());
+        string_distance(true, false, mock_value, &rv)
+    }
+
+    fn reason(
+        &self,
+        _mock_value: &Option<&String>,
+        req_value: &Option<&ClientCertificate>,
+    ) -> Option<String> {
+        match req_value {
+            None => Some(
+                "client certificates are not captured because mutual TLS was not configured \
+                 on the mock server (see HttpMockServerBuilder::https_client_ca_cert) or the \
+                 request was not sent over HTTPS"
+                    .to_string(),
+            ),
+            Some(cert) if cert.common_name.is_none() => Some(
+                "the client did not present a certificate with a subject common name".to_string(),
+            ),
+            Some(_) => None,
+        }
+    }
+}
+
 // ************************************************************************************************
 // IntegerExactMatchComparator
 // ************************************************************************************************
@@ -482,6 +1022,221 @@ impl ValueComparator<u16, u16> for U16ExactMatchComparator {
     }
 }
 
+// ************************************************************************************************
+// CountBoundComparator
+// ************************************************************************************************
+/// Checks that a count is at least (lower bound) or at most (upper bound) the expected value.
+pub struct CountBoundComparator {
+    upper_bound: bool,
+}
+
+impl CountBoundComparator {
+    pub fn new(upper_bound: bool) -> Self {
+        Self { upper_bound }
+    }
+}
+
+impl ValueComparator<usize, usize> for CountBoundComparator {
+    fn matches(&self, mock_value: &Option<&usize>, req_value: &Option<&usize>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(bound), Some(count)) if self.upper_bound => count <= bound,
+            (Some(bound), Some(count)) => count >= bound,
+        }
+    }
+
+    fn name(&self) -> &str {
+        if self.upper_bound {
+            return "to be at most";
+        }
+
+        return "to be at least";
+    }
+
+    fn distance(&self, mock_value: &Option<&usize>, req_value: &Option<&usize>) -> usize {
+        match (mock_value, req_value) {
+            (None, _) => 0,
+            (Some(bound), None) => **bound,
+            (Some(bound), Some(count)) if self.upper_bound => count.saturating_sub(**bound),
+            (Some(bound), Some(count)) => bound.saturating_sub(**count),
+        }
+    }
+}
+
+// ************************************************************************************************
+// JSONSchemaComparator
+// ************************************************************************************************
+#[cfg(feature = "json-schema")]
+pub struct JSONSchemaComparator {}
+
+#[cfg(feature = "json-schema")]
+impl JSONSchemaComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl ValueComparator<JsonSchema, Value> for JSONSchemaComparator {
+    fn matches(&self, mock_value: &Option<&JsonSchema>, req_value: &Option<&Value>) -> bool {
+        self.reason(mock_value, req_value).is_none()
+    }
+
+    fn name(&self) -> &str {
+        "to match JSON schema"
+    }
+
+    fn distance(&self, mock_value: &Option<&JsonSchema>, req_value: &Option<&Value>) -> usize {
+        match self.matches(mock_value, req_value) {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    fn reason(
+        &self,
+        mock_value: &Option<&JsonSchema>,
+        req_value: &Option<&Value>,
+    ) -> Option<String> {
+        let (schema, body) = match (mock_value, req_value) {
+            (None, _) => return None,
+            (Some(_), None) => return Some("request body is not valid JSON".to_string()),
+            (Some(mv), Some(rv)) => (mv, rv),
+        };
+
+        // The schema was compiled when the mock was added, so this only reads the validator.
+        let validator = match schema.validator() {
+            Ok(validator) => validator,
+            Err(err) => return Some(err),
+        };
+
+        validator.validate(body).err().map(|err| {
+            format!(
+                "{} (at '{}', schema path '{}')",
+                err, err.instance_path, err.schema_path
+            )
+        })
+    }
+}
+
+// ************************************************************************************************
+// BodyFormatComparator
+// ************************************************************************************************
+pub struct BodyFormatComparator {}
+
+impl BodyFormatComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<BodyFormat, BodyFormatValidation> for BodyFormatComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&BodyFormat>,
+        req_value: &Option<&BodyFormatValidation>,
+    ) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => rv.format == **mv && rv.error.is_none(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "to be"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&BodyFormat>,
+        req_value: &Option<&BodyFormatValidation>,
+    ) -> usize {
+        match self.matches(mock_value, req_value) {
+            true => 0,
+            false => 1,
+        }
+    }
+}
+
+// ************************************************************************************************
+// BodySizeComparator
+// ************************************************************************************************
+pub struct BodySizeComparator {}
+
+impl BodySizeComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<BodySizeRange, usize> for BodySizeComparator {
+    fn matches(&self, mock_value: &Option<&BodySizeRange>, req_value: &Option<&usize>) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(mv), rv) => mv.contains(rv.map_or(0, |v| *v)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "to be"
+    }
+
+    fn distance(&self, mock_value: &Option<&BodySizeRange>, req_value: &Option<&usize>) -> usize {
+        let (range, size) = match (mock_value, req_value) {
+            (None, _) => return 0,
+            (Some(mv), rv) => (mv, rv.map_or(0, |v| *v)),
+        };
+
+        if size < range.min {
+            range.min - size
+        } else {
+            size.saturating_sub(range.max)
+        }
+    }
+}
+
+// ************************************************************************************************
+// BodyPresenceComparator
+// ************************************************************************************************
+pub struct BodyPresenceComparator {}
+
+impl BodyPresenceComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<BodyPresence, BodyPresence> for BodyPresenceComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&BodyPresence>,
+        req_value: &Option<&BodyPresence>,
+    ) -> bool {
+        match (mock_value, req_value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(mv), Some(rv)) => mv == rv,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "to be"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&BodyPresence>,
+        req_value: &Option<&BodyPresence>,
+    ) -> usize {
+        match self.matches(mock_value, req_value) {
+            true => 0,
+            false => 1,
+        }
+    }
+}
+
 // ************************************************************************************************
 // BytesExactMatchComparator
 // ************************************************************************************************
@@ -844,7 +1599,7 @@ mod test {
     #[test]
     fn json_exact_match_comparator_match() {
         run_test(
-            &JSONExactMatchComparator::new(),
+            &JSONExactMatchComparator::new(false),
             &json!({"name" : "Peter", "surname" : "Griffin"}),
             &json!({"name" : "Peter", "surname" : "Griffin"}),
             true,
@@ -856,7 +1611,7 @@ mod test {
     #[test]
     fn json_exact_match_comparator_no_match() {
         run_test(
-            &JSONExactMatchComparator::new(),
+            &JSONExactMatchComparator::new(false),
             &json!({"name" : "Peter", "surname" : "Griffin"}),
             &json!({"name" : "Walter", "surname" : "White"}),
             false,
@@ -1,5 +1,10 @@
-use crate::common::{data::HttpMockRegex, util::HttpMockBytes};
+use crate::common::{
+    data::{BodyFormat, HttpMockRegex},
+    util::HttpMockBytes,
+};
+use assert_json_diff::{assert_json_matches_no_panic, CompareMode, Config};
 use regex::Regex;
+use serde_json::Value;
 use std::{convert::TryInto, ops::Deref};
 use stringmetrics::LevWeights;
 
@@ -949,6 +954,28 @@ pub fn string_equals(
     }
 }
 
+/// Splits an authority (e.g., `github.com:8080` or `[::1]:8080`) into its lowercase host and
+/// its port, if the authority contains one.
+pub fn split_authority(authority: &str) -> Result<(String, Option<u16>), String> {
+    let parsed = authority
+        .parse::<http::uri::Authority>()
+        .map_err(|err| format!("invalid authority '{}': {}", authority, err))?;
+
+    if authority.contains('@') {
+        return Err(format!(
+            "invalid authority '{}': user information is not supported",
+            authority
+        ));
+    }
+
+    let port = parsed.port_u16();
+    if port.is_none() && parsed.host().len() != authority.len() {
+        return Err(format!("invalid authority '{}': invalid port", authority));
+    }
+
+    Ok((parsed.host().to_lowercase(), port))
+}
+
 pub fn hostname_equals(
     negated: bool,
     mock_value: &Option<&String>,
@@ -1597,6 +1624,324 @@ mod string_matches_regex_tests {
 /// # Returns
 /// * `usize` - The computed distance. In the negated case, it returns the number of characters that did match the regex.
 ///             In the non-negated case, it returns the number of characters that did not match the regex.
+/// Converts a glob pattern into an anchored regular expression. `**` matches any number of
+/// characters including `/`, while `*` matches any number of characters within a single path
+/// segment. A `**/` sequence also matches no segment at all. All other characters are matched
+/// literally.
+pub fn glob_to_regex(glob: &str) -> HttpMockRegex {
+    let mut pattern = String::from("^");
+    let mut rest = glob;
+
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("**/") {
+            pattern.push_str("(?:.*/)?");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("**") {
+            pattern.push_str(".*");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix('*') {
+            pattern.push_str("[^/]*");
+            rest = tail;
+        } else {
+            let literal_end = rest.find('*').unwrap_or(rest.len());
+            pattern.push_str(&regex::escape(&rest[..literal_end]));
+            rest = &rest[literal_end..];
+        }
+    }
+
+    pattern.push('$');
+    HttpMockRegex(Regex::new(&pattern).expect("glob pattern must compile to a valid regex"))
+}
+
+#[cfg(test)]
+mod glob_to_regex_tests {
+    use super::*;
+
+    fn glob_matches(glob: &str, path: &str) -> bool {
+        glob_to_regex(glob).0.is_match(path)
+    }
+
+    #[test]
+    fn test_single_star_matches_within_segment() {
+        assert!(glob_matches("/api/*/items", "/api/v1/items"));
+        assert!(glob_matches("/api/*/items", "/api//items"));
+        assert!(glob_matches("/files/*.json", "/files/data.json"));
+        assert!(!glob_matches("/api/*/items", "/api/v1/v2/items"));
+        assert!(!glob_matches("/files/*.json", "/files/a/data.json"));
+    }
+
+    #[test]
+    fn test_double_star_crosses_segments() {
+        assert!(glob_matches("/api/**/items", "/api/v1/v2/items"));
+        assert!(glob_matches("/api/**/items", "/api/items"));
+        assert!(glob_matches("/api/**", "/api/v1/users/1"));
+        assert!(!glob_matches("/api/**/items", "/other/v1/items"));
+    }
+
+    #[test]
+    fn test_literals_are_escaped() {
+        assert!(glob_matches("/a.b/(c)", "/a.b/(c)"));
+        assert!(!glob_matches("/a.b", "/axb"));
+        assert!(!glob_matches("/api", "/api/items"));
+    }
+}
+
+/// Converts a path template, such as `/users/{id}`, to an anchored regex. Each `{name}`
+/// placeholder matches exactly one non-empty path segment and captures it under its name.
+/// All other characters are matched literally against the whole path.
+pub fn path_template_to_regex(template: &str) -> Result<HttpMockRegex, String> {
+    let mut pattern = String::from("^");
+    let mut names: Vec<&str> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        pattern.push_str(&regex::escape(&rest[..start]));
+
+        let end = rest[start..].find('}').ok_or_else(|| {
+            format!(
+                "path template '{}' contains an unclosed '{{' placeholder",
+                template
+            )
+        })? + start;
+
+        let name = &rest[start + 1..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "path template '{}' contains an invalid parameter name '{}'",
+                template, name
+            ));
+        }
+        if names.contains(&name) {
+            return Err(format!(
+                "path template '{}' contains the parameter '{}' more than once",
+                template, name
+            ));
+        }
+
+        names.push(name);
+        pattern.push_str(&format!("(?P<{}>[^/]+)", name));
+        rest = &rest[end + 1..];
+    }
+
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+
+    Regex::new(&pattern)
+        .map(HttpMockRegex)
+        .map_err(|err| format!("invalid path template '{}': {}", template, err))
+}
+
+/// Removes a single trailing slash from a path, so that `/users` and `/users/` compare as equal.
+pub fn without_trailing_slash(path: &str) -> String {
+    path.strip_suffix('/').unwrap_or(path).to_string()
+}
+
+/// Extracts the values of all path parameters from the given path, using the regex of a path
+/// template (see [`path_template_to_regex`]). Returns `None` if the path does not match it.
+pub fn path_template_params(regex: &Regex, path: &str) -> Option<Vec<(String, String)>> {
+    let captures = regex.captures(path)?;
+
+    Some(
+        regex
+            .capture_names()
+            .flatten()
+            .filter_map(|name| {
+                captures
+                    .name(name)
+                    .map(|value| (name.to_string(), value.as_str().to_string()))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod path_template_tests {
+    use super::*;
+
+    #[test]
+    fn test_parameters_match_exactly_one_segment() {
+        let regex = path_template_to_regex("/users/{id}/orders/{order_id}")
+            .unwrap()
+            .0;
+        assert!(regex.is_match("/users/42/orders/7"));
+        assert!(!regex.is_match("/users/42/orders"));
+        assert!(!regex.is_match("/users/42/orders/7/items"));
+        assert!(!regex.is_match("/users//orders/7"));
+        assert!(!regex.is_match("/users/4/2/orders/7"));
+    }
+
+    #[test]
+    fn test_params_are_extracted_by_name() {
+        let regex = path_template_to_regex("/users/{id}.{format}").unwrap().0;
+        assert_eq!(
+            path_template_params(&regex, "/users/42.json"),
+            Some(vec![
+                ("id".to_string(), "42".to_string()),
+                ("format".to_string(), "json".to_string())
+            ])
+        );
+        assert_eq!(path_template_params(&regex, "/teams/42.json"), None);
+    }
+
+    #[test]
+    fn test_invalid_templates_are_rejected() {
+        assert!(path_template_to_regex("/users/{id").is_err());
+        assert!(path_template_to_regex("/users/{}").is_err());
+        assert!(path_template_to_regex("/users/{user-id}").is_err());
+        assert!(path_template_to_regex("/users/{id}/friends/{id}").is_err());
+    }
+}
+
+/// Compares two JSON values with deep structural equality, but treats arrays as multisets at
+/// every nesting level: two arrays are equal if they have the same length and every element
+/// of one array can be paired with a distinct, equal element of the other. Objects are equal
+/// if they have the same keys with equal values. All other values (including numbers, which
+/// are compared by their JSON representation, so `1` does not equal `1.0`) must be equal.
+pub fn json_equals_unordered(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() != actual.len() {
+                return false;
+            }
+
+            // Equality modulo array order is an equivalence relation, so pairing each expected
+            // element with the first equal unused actual element cannot miss a valid pairing.
+            let mut used = vec![false; actual.len()];
+            expected.iter().all(|e| {
+                let index = actual
+                    .iter()
+                    .enumerate()
+                    .position(|(i, a)| !used[i] && json_equals_unordered(e, a));
+
+                index.map_or(false, |i| {
+                    used[i] = true;
+                    true
+                })
+            })
+        }
+        (Value::Object(expected), Value::Object(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().all(|(key, e)| {
+                    actual
+                        .get(key)
+                        .map_or(false, |a| json_equals_unordered(e, a))
+                })
+        }
+        (expected, actual) => expected == actual,
+    }
+}
+
+/// Checks that every expected element is included in a distinct element of the actual array,
+/// regardless of order. Elements are compared like `json_body_includes` does: an expected object
+/// matches an actual object that has at least the expected fields, while all other values must
+/// be equal. Since partial matches do not form an equivalence relation, the pairing is found
+/// by backtracking.
+pub fn json_array_includes(expected: &[Value], actual: &[Value]) -> bool {
+    fn assign(expected: &[Value], actual: &[Value], used: &mut Vec<bool>) -> bool {
+        let (first, rest) = match expected.split_first() {
+            None => return true,
+            Some(split) => split,
+        };
+
+        for (i, candidate) in actual.iter().enumerate() {
+            if used[i] || !json_includes(first, candidate) {
+                continue;
+            }
+
+            used[i] = true;
+            if assign(rest, actual, used) {
+                return true;
+            }
+            used[i] = false;
+        }
+
+        false
+    }
+
+    expected.len() <= actual.len() && assign(expected, actual, &mut vec![false; actual.len()])
+}
+
+fn json_includes(expected: &Value, actual: &Value) -> bool {
+    let config = Config::new(CompareMode::Inclusive);
+    assert_json_matches_no_panic(actual, expected, config).is_ok()
+}
+
+#[cfg(test)]
+mod json_array_includes_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_elements_are_matched_in_any_order() {
+        let actual = [json!(3), json!(1), json!(2)];
+        assert!(json_array_includes(&[json!(2), json!(3)], &actual));
+        assert!(!json_array_includes(&[json!(4)], &actual));
+    }
+
+    #[test]
+    fn test_objects_are_matched_partially() {
+        let actual = [json!({"id": 1, "name": "a"}), json!({"id": 2, "name": "b"})];
+        assert!(json_array_includes(&[json!({"id": 2})], &actual));
+        assert!(!json_array_includes(
+            &[json!({"id": 2, "name": "a"})],
+            &actual
+        ));
+    }
+
+    #[test]
+    fn test_each_element_is_matched_only_once() {
+        let actual = [json!({"id": 1, "tag": "x"}), json!({"id": 1})];
+        assert!(json_array_includes(
+            &[json!({"id": 1}), json!({"tag": "x"})],
+            &actual
+        ));
+        assert!(!json_array_includes(&[json!(1), json!(1)], &[json!(1)]));
+    }
+}
+
+#[cfg(test)]
+mod json_equals_unordered_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_arrays_are_compared_as_multisets() {
+        assert!(json_equals_unordered(&json!([1, 2, 3]), &json!([3, 1, 2])));
+        assert!(json_equals_unordered(&json!([1, 1, 2]), &json!([1, 2, 1])));
+        assert!(!json_equals_unordered(&json!([1, 1, 2]), &json!([1, 2, 2])));
+        assert!(!json_equals_unordered(&json!([1, 2]), &json!([1, 2, 3])));
+    }
+
+    #[test]
+    fn test_nested_arrays_and_objects() {
+        assert!(json_equals_unordered(
+            &json!({"tags": ["a", "b"], "items": [{"id": 1, "ids": [1, 2]}, {"id": 2}]}),
+            &json!({"items": [{"id": 2}, {"ids": [2, 1], "id": 1}], "tags": ["b", "a"]})
+        ));
+        assert!(json_equals_unordered(
+            &json!([[1, 2], [3, 4]]),
+            &json!([[4, 3], [2, 1]])
+        ));
+        assert!(!json_equals_unordered(
+            &json!([[1, 2], [3, 4]]),
+            &json!([[1, 3], [2, 4]])
+        ));
+    }
+
+    #[test]
+    fn test_objects_are_compared_structurally() {
+        assert!(!json_equals_unordered(
+            &json!({"id": 1}),
+            &json!({"id": 1, "name": "x"})
+        ));
+        assert!(!json_equals_unordered(
+            &json!({"id": 1}),
+            &json!({"id": 1.0})
+        ));
+        assert!(!json_equals_unordered(&json!([1]), &json!({"0": 1})));
+    }
+}
+
 pub fn regex_string_distance(
     negated: bool,
     case_sensitive: bool,
@@ -1771,3 +2116,114 @@ mod regex_string_distance_tests {
         );
     }
 }
+
+/// Tries to parse `body` in the given format. Returns `None` if the body is valid, otherwise
+/// a description of the parse error including the position where parsing failed.
+pub fn body_format_error(format: &BodyFormat, body: &[u8]) -> Option<String> {
+    match format {
+        BodyFormat::Json => serde_json::from_slice::<serde::de::IgnoredAny>(body)
+            .err()
+            .map(|e| e.to_string()),
+        BodyFormat::Xml => match std::str::from_utf8(body) {
+            Err(e) => Some(format!("invalid UTF-8 at byte {}", e.valid_up_to())),
+            Ok(text) => roxmltree::Document::parse(text)
+                .err()
+                .map(|e| e.to_string()),
+        },
+        BodyFormat::FormUrlencoded => form_urlencoded_error(body),
+    }
+}
+
+/// Reports malformed percent-escapes only. Any other byte is accepted, the same way
+/// `form_urlencoded::parse` accepts it when the body is matched.
+fn form_urlencoded_error(body: &[u8]) -> Option<String> {
+    let mut idx = 0;
+    while idx < body.len() {
+        if body[idx] == b'%' {
+            let escape = body.get(idx + 1..idx + 3);
+            if !escape.map_or(false, |e| e.iter().all(u8::is_ascii_hexdigit)) {
+                return Some(format!("invalid percent-encoding at byte {}", idx));
+            }
+            idx += 3;
+            continue;
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod body_format_error_test {
+    use crate::{common::data::BodyFormat, server::matchers::comparison::body_format_error};
+
+    #[test]
+    fn valid_bodies_have_no_error() {
+        assert_eq!(
+            body_format_error(&BodyFormat::Json, br#"{"a":[1,2]}"#),
+            None
+        );
+        assert_eq!(
+            body_format_error(&BodyFormat::Xml, b"<a><b x=\"1\"/></a>"),
+            None
+        );
+        assert_eq!(
+            body_format_error(
+                &BodyFormat::FormUrlencoded,
+                b"name=Peter+Griffin&age=%34%32"
+            ),
+            None
+        );
+        assert_eq!(body_format_error(&BodyFormat::FormUrlencoded, b""), None);
+        assert_eq!(
+            body_format_error(
+                &BodyFormat::FormUrlencoded,
+                b"redirect=https://example.com/a,b&user=a@b"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_bodies_report_position() {
+        assert_eq!(
+            body_format_error(&BodyFormat::Json, b"{\"a\":}"),
+            Some("expected value at line 1 column 6".to_string())
+        );
+        assert!(body_format_error(&BodyFormat::Xml, b"<a><b></a>")
+            .unwrap()
+            .ends_with("at 1:7"));
+        assert_eq!(
+            body_format_error(&BodyFormat::FormUrlencoded, b"a=1&b=%4"),
+            Some("invalid percent-encoding at byte 6".to_string())
+        );
+        assert_eq!(
+            body_format_error(&BodyFormat::FormUrlencoded, b"a=%zz"),
+            Some("invalid percent-encoding at byte 2".to_string())
+        );
+    }
+}
+
+#[cfg(test)]
+mod split_authority_test {
+    use crate::server::matchers::comparison::split_authority;
+
+    #[test]
+    fn split_authority_test() {
+        assert_eq!(
+            split_authority("GitHub.com:8080"),
+            Ok(("github.com".to_string(), Some(8080)))
+        );
+        assert_eq!(
+            split_authority("github.com"),
+            Ok(("github.com".to_string(), None))
+        );
+        assert_eq!(
+            split_authority("[::1]:80"),
+            Ok(("[::1]".to_string(), Some(80)))
+        );
+        assert!(split_authority("github.com:").is_err());
+        assert!(split_authority("user@github.com").is_err());
+        assert!(split_authority("").is_err());
+    }
+}
@@ -1,17 +1,17 @@
 use serde::{Deserialize, Serialize};
 use similar::{ChangeTag, TextDiff};
-use std::{collections::HashSet, fmt::Display};
+use std::{collections::HashSet, fmt::Display, sync::OnceLock};
 
 use crate::{
     common::{
         data::{
             Diff, DiffResult, FunctionComparison, HttpMockRequest, KeyValueComparison,
             KeyValueComparisonAttribute, KeyValueComparisonKeyValuePair, Mismatch,
-            RequestRequirements, SingleValueComparison, Tokenizer,
+            NamedMatcherCall, RequestRequirements, SingleValueComparison, Tokenizer,
         },
         util::is_none_or_empty,
     },
-    server::matchers::{comparators::ValueComparator, Matcher},
+    server::matchers::{all, comparators::ValueComparator, Matcher, NamedMatchers},
 };
 
 // ************************************************************************************************
@@ -60,6 +60,10 @@ where
     S: Display,
     T: Display,
 {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock).map_or(0, |values| values.len())
+    }
+
     fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
         let mock_value = (self.expectation)(mock);
         if is_none_or_empty(&mock_value) {
@@ -94,6 +98,12 @@ where
         self.find_unmatched(&req_value, &mock_value)
             .into_iter()
             .map(|mock_value| {
+                let reason = match self.with_reason {
+                    true => self
+                        .comparator
+                        .reason(&Some(mock_value), &req_value.as_ref()),
+                    false => None,
+                };
                 let mock_value = mock_value.to_string();
                 let req_value = req_value.as_ref().map_or(String::new(), |v| v.to_string());
                 Mismatch {
@@ -108,6 +118,7 @@ where
                     entity: self.entity_name.to_string(),
                     diff: self.diff_with.map(|t| diff_str(&mock_value, &req_value, t)),
                     best_match: false,
+                    reason,
                     matching_strategy: Some(self.matching_strategy.clone()),
                 }
             })
@@ -243,6 +254,10 @@ where
     RK: Display,
     RV: Display,
 {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock).map_or(0, |values| values.len())
+    }
+
     fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
         let mock_values = (self.expectation)(mock).unwrap_or(Vec::new());
         if mock_values.is_empty() {
@@ -318,6 +333,394 @@ where
                     matching_strategy: Some(self.matching_strategy.clone()),
                     diff: None,
                     best_match: best_match.is_some(),
+                    reason: None,
+                }
+            })
+            .collect()
+    }
+}
+
+// ************************************************************************************************
+// MultiValueSetMatcher
+// ************************************************************************************************
+/// Compares all values of a key that may occur multiple times in a request (e.g., a repeated
+/// query parameter) with a set of expected values, irrespective of their order. If `exact` is
+/// `true`, the request must not contain any values other than the expected ones.
+pub(crate) struct MultiValueSetMatcher {
+    pub entity_name: &'static str,
+    pub matcher_method: &'static str,
+    pub expectation:
+        for<'a> fn(&'a RequestRequirements) -> Option<Vec<(&'a String, &'a Vec<String>)>>,
+    pub request_value: fn(&HttpMockRequest) -> Option<Vec<(String, Option<String>)>>,
+    pub exact: bool,
+    pub weight: usize,
+}
+
+/// The values that are missing in or unexpectedly present in a request for a key.
+struct ValueSetDifference {
+    actual: Vec<String>,
+    missing: Vec<String>,
+    unexpected: Vec<String>,
+}
+
+impl MultiValueSetMatcher {
+    fn difference(
+        &self,
+        key: &str,
+        expected: &[String],
+        req_values: &[(String, Option<String>)],
+    ) -> ValueSetDifference {
+        let actual: Vec<String> = req_values
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.clone().unwrap_or_default())
+            .collect();
+
+        // Values are compared as multisets, so every expected value needs its own occurrence.
+        let mut unexpected = actual.clone();
+        let mut missing = Vec::new();
+        for value in expected {
+            match unexpected.iter().position(|v| v == value) {
+                Some(idx) => {
+                    unexpected.remove(idx);
+                }
+                None => missing.push(value.clone()),
+            }
+        }
+
+        if !self.exact {
+            unexpected.clear();
+        }
+
+        ValueSetDifference {
+            actual,
+            missing,
+            unexpected,
+        }
+    }
+
+    fn find_unmatched<'a>(
+        &self,
+        req_values: &[(String, Option<String>)],
+        mock_values: &[(&'a String, &'a Vec<String>)],
+    ) -> Vec<(&'a String, &'a Vec<String>, ValueSetDifference)> {
+        mock_values
+            .iter()
+            .map(|(key, expected)| (*key, *expected, self.difference(key, expected, req_values)))
+            .filter(|(_, _, diff)| !diff.missing.is_empty() || !diff.unexpected.is_empty())
+            .collect()
+    }
+
+    fn operator(&self) -> &'static str {
+        match self.exact {
+            true => "to be",
+            false => "to include",
+        }
+    }
+}
+
+impl Matcher for MultiValueSetMatcher {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock).map_or(0, |values| values.len())
+    }
+
+    fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
+        let mock_values = (self.expectation)(mock).unwrap_or_default();
+        if mock_values.is_empty() {
+            return true;
+        }
+
+        let req_values = (self.request_value)(req).unwrap_or_default();
+        self.find_unmatched(&req_values, &mock_values).is_empty()
+    }
+
+    fn distance(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> usize {
+        let mock_values = (self.expectation)(mock).unwrap_or_default();
+        if mock_values.is_empty() {
+            return 0;
+        }
+
+        let req_values = (self.request_value)(req).unwrap_or_default();
+        self.find_unmatched(&req_values, &mock_values)
+            .iter()
+            .map(|(_, _, diff)| (diff.missing.len() + diff.unexpected.len()) * self.weight)
+            .sum()
+    }
+
+    fn mismatches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> Vec<Mismatch> {
+        let mock_values = (self.expectation)(mock).unwrap_or_default();
+        if mock_values.is_empty() {
+            return Vec::new();
+        }
+
+        let req_values = (self.request_value)(req).unwrap_or_default();
+        self.find_unmatched(&req_values, &mock_values)
+            .into_iter()
+            .map(|(key, expected, diff)| {
+                let mut reasons = Vec::new();
+                if !diff.missing.is_empty() {
+                    reasons.push(format!("missing values: {}", diff.missing.join(", ")));
+                }
+                if !diff.unexpected.is_empty() {
+                    reasons.push(format!("unexpected values: {}", diff.unexpected.join(", ")));
+                }
+
+                Mismatch {
+                    matcher_method: self.matcher_method.to_string(),
+                    comparison: Some(SingleValueComparison {
+                        operator: self.operator().to_string(),
+                        expected: format!("{}=[{}]", key, expected.join(", ")),
+                        actual: format!("{}=[{}]", key, diff.actual.join(", ")),
+                    }),
+                    key_value_comparison: None,
+                    function_comparison: None,
+                    entity: self.entity_name.to_string(),
+                    diff: None,
+                    best_match: false,
+                    reason: Some(reasons.join("; ")),
+                    matching_strategy: Some(MatchingStrategy::Presence),
+                }
+            })
+            .collect()
+    }
+}
+
+// ************************************************************************************************
+// ConditionGroupMatcher
+// ************************************************************************************************
+/// Matches nested groups of request requirements, such as the ones created by `When::any_of`
+/// and `When::all_of`. Nested requirements are evaluated using all matchers.
+pub(crate) struct ConditionGroupMatcher {
+    pub entity_name: &'static str,
+    pub matcher_method: &'static str,
+    pub expectation: for<'a> fn(&'a RequestRequirements) -> Option<Vec<&'a [RequestRequirements]>>,
+    /// Whether a group matches if any of its conditions matches (instead of all of them).
+    pub any: bool,
+    named_matchers: NamedMatchers,
+    nested_matchers: OnceLock<Vec<Box<dyn Matcher + Sync + Send>>>,
+}
+
+impl ConditionGroupMatcher {
+    pub fn new(
+        entity_name: &'static str,
+        matcher_method: &'static str,
+        expectation: for<'a> fn(&'a RequestRequirements) -> Option<Vec<&'a [RequestRequirements]>>,
+        any: bool,
+        named_matchers: NamedMatchers,
+    ) -> Self {
+        Self {
+            entity_name,
+            matcher_method,
+            expectation,
+            any,
+            named_matchers,
+            nested_matchers: OnceLock::new(),
+        }
+    }
+
+    // The nested matchers are created lazily, because this matcher is itself part of them.
+    fn nested_matchers(&self) -> &Vec<Box<dyn Matcher + Sync + Send>> {
+        self.nested_matchers
+            .get_or_init(|| all(&self.named_matchers))
+    }
+
+    fn condition_matches(&self, req: &HttpMockRequest, condition: &RequestRequirements) -> bool {
+        self.nested_matchers()
+            .iter()
+            .all(|matcher| matcher.matches(req, condition))
+    }
+
+    fn condition_distance(&self, req: &HttpMockRequest, condition: &RequestRequirements) -> usize {
+        self.nested_matchers()
+            .iter()
+            .map(|matcher| matcher.distance(req, condition))
+            .sum()
+    }
+
+    fn condition_mismatches(
+        &self,
+        req: &HttpMockRequest,
+        condition: &RequestRequirements,
+    ) -> Vec<Mismatch> {
+        self.nested_matchers()
+            .iter()
+            .flat_map(|matcher| matcher.mismatches(req, condition))
+            .collect()
+    }
+
+    fn group_matches(&self, req: &HttpMockRequest, group: &[RequestRequirements]) -> bool {
+        match self.any {
+            true => group.iter().any(|c| self.condition_matches(req, c)),
+            false => group.iter().all(|c| self.condition_matches(req, c)),
+        }
+    }
+
+    fn group_distance(&self, req: &HttpMockRequest, group: &[RequestRequirements]) -> usize {
+        let distances = group.iter().map(|c| self.condition_distance(req, c));
+        match self.any {
+            true => distances.min().unwrap_or(0),
+            false => distances.sum(),
+        }
+    }
+
+    /// Describes how close each alternative of a group came to matching the request.
+    fn describe_alternatives(
+        &self,
+        req: &HttpMockRequest,
+        group: &[RequestRequirements],
+    ) -> String {
+        group
+            .iter()
+            .enumerate()
+            .map(|(idx, condition)| {
+                let failures: Vec<String> = self
+                    .condition_mismatches(req, condition)
+                    .iter()
+                    .map(|mm| match &mm.comparison {
+                        Some(c) => format!(
+                            "{} {} {} (received {})",
+                            mm.entity, c.operator, c.expected, c.actual
+                        ),
+                        None => format!("{} did not match ({})", mm.entity, mm.matcher_method),
+                    })
+                    .collect();
+
+                format!(
+                    "alternative {} (distance {}): {}",
+                    idx + 1,
+                    self.condition_distance(req, condition),
+                    failures.join("; ")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Matcher for ConditionGroupMatcher {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        let groups = (self.expectation)(mock).unwrap_or_default();
+        // Each alternative group counts as one constraint, while all-of conditions count
+        // individually, just like top-level conditions.
+        match self.any {
+            true => groups.len(),
+            false => groups.iter().map(|group| group.len()).sum(),
+        }
+    }
+
+    fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
+        (self.expectation)(mock)
+            .unwrap_or_default()
+            .iter()
+            .all(|group| self.group_matches(req, group))
+    }
+
+    fn distance(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock)
+            .unwrap_or_default()
+            .iter()
+            .map(|group| self.group_distance(req, group))
+            .sum()
+    }
+
+    fn mismatches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> Vec<Mismatch> {
+        let groups = (self.expectation)(mock).unwrap_or_default();
+
+        // The conditions of an all-of group are shown like any other top-level mismatch.
+        if !self.any {
+            return groups
+                .iter()
+                .flat_map(|group| group.iter())
+                .flat_map(|condition| self.condition_mismatches(req, condition))
+                .collect();
+        }
+
+        groups
+            .into_iter()
+            .filter(|group| !self.group_matches(req, group))
+            .map(|group| Mismatch {
+                matcher_method: self.matcher_method.to_string(),
+                comparison: Some(SingleValueComparison {
+                    operator: "to match".to_string(),
+                    expected: format!("any of {} alternatives", group.len()),
+                    actual: format!("{} {}", req.method_str(), req.uri_str()),
+                }),
+                key_value_comparison: None,
+                function_comparison: None,
+                entity: self.entity_name.to_string(),
+                diff: None,
+                best_match: false,
+                reason: Some(self.describe_alternatives(req, group)),
+                matching_strategy: Some(MatchingStrategy::Presence),
+            })
+            .collect()
+    }
+}
+
+// ************************************************************************************************
+// NamedFunctionMatcher
+// ************************************************************************************************
+/// Matches requests using matcher functions that are registered on the server by name.
+pub(crate) struct NamedFunctionMatcher {
+    pub entity_name: &'static str,
+    pub matcher_method: &'static str,
+    pub named_matchers: NamedMatchers,
+    pub weight: usize,
+}
+
+impl NamedFunctionMatcher {
+    fn get_unmatched<'a>(
+        &self,
+        req: &HttpMockRequest,
+        mock: &'a RequestRequirements,
+    ) -> Vec<&'a NamedMatcherCall> {
+        mock.matcher_named
+            .iter()
+            .flatten()
+            .filter(|call| match self.named_matchers.get(&call.name) {
+                Some(matcher) => !matcher(req, &call.args),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+impl Matcher for NamedFunctionMatcher {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        mock.matcher_named.as_ref().map_or(0, |calls| calls.len())
+    }
+
+    fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
+        self.get_unmatched(req, mock).is_empty()
+    }
+
+    fn distance(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> usize {
+        self.get_unmatched(req, mock).len() * self.weight
+    }
+
+    fn mismatches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> Vec<Mismatch> {
+        self.get_unmatched(req, mock)
+            .into_iter()
+            .map(|call| {
+                let reason = match self.named_matchers.contains_key(&call.name) {
+                    true => format!("matcher '{}' did not match the request", call.name),
+                    false => format!("no matcher named '{}' is registered", call.name),
+                };
+
+                Mismatch {
+                    matcher_method: self.matcher_method.to_string(),
+                    comparison: Some(SingleValueComparison {
+                        operator: "to match".to_string(),
+                        expected: format!("{}({})", call.name, call.args),
+                        actual: format!("{} {}", req.method_str(), req.uri_str()),
+                    }),
+                    key_value_comparison: None,
+                    function_comparison: None,
+                    entity: self.entity_name.to_string(),
+                    diff: None,
+                    best_match: false,
+                    reason: Some(reason),
+                    matching_strategy: Some(MatchingStrategy::Presence),
                 }
             })
             .collect()
@@ -425,6 +828,10 @@ where
     RK: Display,
     RV: Display,
 {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock).map_or(0, |values| values.len())
+    }
+
     fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
         let mock_values = (self.expectation)(mock).unwrap_or_default();
         if mock_values.is_empty() {
@@ -492,6 +899,7 @@ where
                     function_comparison: None,
                     diff: None,
                     best_match: false,
+                    reason: None,
                 }
             })
             .collect()
@@ -540,6 +948,10 @@ impl<S, T> FunctionValueMatcher<S, T> {
 }
 
 impl<S, T> Matcher for FunctionValueMatcher<S, T> {
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize {
+        (self.expectation)(mock).map_or(0, |values| values.len())
+    }
+
     fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool {
         let mock_values = (self.expectation)(mock);
         if is_none_or_empty(&mock_values) {
@@ -577,6 +989,7 @@ impl<S, T> Matcher for FunctionValueMatcher<S, T> {
                 key_value_comparison: None,
                 diff: None,
                 best_match: false,
+                reason: None,
                 matching_strategy: None,
             })
             .collect()
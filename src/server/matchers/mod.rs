@@ -1,29 +1,50 @@
-use std::{convert::TryInto, fmt::Display, ops::Deref};
+use std::{collections::BTreeMap, convert::TryInto, fmt::Display, ops::Deref, sync::Arc};
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::common::data::{HttpMockRequest, Mismatch, RequestRequirements, Tokenizer};
 
 use crate::server::matchers::comparators::{
-    AnyValueComparator, BytesExactMatchComparator, BytesIncludesComparator, BytesPrefixComparator,
-    BytesSuffixComparator, FunctionMatchesRequestComparator, HostEqualsComparator,
-    HttpMockBytesPatternComparator, JSONContainsMatchComparator, JSONExactMatchComparator,
-    StringContainsComparator, StringEqualsComparator, StringPatternMatchComparator,
+    AnyValueComparator, AuthorityEqualsComparator, BodyFormatComparator, BodyPresenceComparator,
+    BodySizeComparator, BytesExactMatchComparator, BytesIncludesComparator, BytesPrefixComparator,
+    BytesSuffixComparator, ClientCertCommonNameComparator, CountBoundComparator,
+    DecodedBodyPatternComparator, FunctionMatchesRequestComparator, HostEqualsComparator,
+    HttpMockBytesAnyPatternComparator, HttpMockBytesPatternComparator, JSONArrayIncludesComparator,
+    JSONContainsMatchComparator, JSONExactMatchComparator, JSONKeyPresenceComparator,
+    JSONUnorderedMatchComparator, PathEqualsIgnoringTrailingSlashComparator,
+    PathTemplateMatchComparator, StringAnyOfComparator, StringContainsComparator,
+    StringEqualsComparator, StringGlobMatchComparator, StringPatternMatchComparator,
     StringPrefixMatchComparator, StringRegexMatchComparator, StringSuffixMatchComparator,
     U16ExactMatchComparator,
 };
 
+#[cfg(feature = "json-schema")]
+use crate::server::matchers::comparators::JSONSchemaComparator;
+#[cfg(feature = "protobuf")]
+use crate::server::matchers::protobuf::ProtobufFieldComparator;
+
 use crate::server::matchers::generic::{
-    FunctionValueMatcher, KeyValueOperator, MatchingStrategy, MultiValueCountMatcher,
-    MultiValueMatcher, SingleValueMatcher,
+    ConditionGroupMatcher, FunctionValueMatcher, KeyValueOperator, MatchingStrategy,
+    MultiValueCountMatcher, MultiValueMatcher, MultiValueSetMatcher, NamedFunctionMatcher,
+    SingleValueMatcher,
 };
 
 pub mod comparators;
-mod comparison;
+pub(crate) mod comparison;
 pub mod generic;
+#[cfg(feature = "protobuf")]
+pub(crate) mod protobuf;
 pub mod readers;
 
-pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
+/// A matcher function that is registered on the server under a name. It receives the request
+/// and the arguments that a mock passes to it using `When::matcher_named`.
+pub(crate) type NamedMatcherFn = Arc<dyn Fn(&HttpMockRequest, &Value) -> bool + Send + Sync>;
+
+/// All matcher functions that are registered on a server, by name.
+pub(crate) type NamedMatchers = Arc<BTreeMap<String, NamedMatcherFn>>;
+
+pub(crate) fn all(named_matchers: &NamedMatchers) -> Vec<Box<dyn Matcher + Sync + Send>> {
     vec![
         //*************************************************************************************
         // Scheme matchers
@@ -51,6 +72,20 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             weight: 3,
         }),
         //*************************************************************************************
+        // HTTP version matchers
+        //*************************************************************************************
+        Box::new(SingleValueMatcher {
+            entity_name: "HTTP version",
+            matcher_method: "http_version",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringEqualsComparator::new(false, false)),
+            expectation: readers::expectations::http_version_equal_to,
+            request_value: readers::request_value::http_version,
+            with_reason: true,
+            diff_with: None,
+            weight: 3,
+        }),
+        //*************************************************************************************
         // Method matchers
         //*************************************************************************************
         Box::new(SingleValueMatcher {
@@ -64,6 +99,17 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 3,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "method",
+            matcher_method: "method_in",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringAnyOfComparator::new(false)),
+            expectation: readers::expectations::method_in,
+            request_value: readers::request_value::method,
+            with_reason: true,
+            diff_with: None,
+            weight: 3,
+        }),
         Box::new(SingleValueMatcher {
             entity_name: "method",
             matcher_method: "method_not",
@@ -177,6 +223,17 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 3,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "Host header",
+            matcher_method: "host_header",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringEqualsComparator::new(true, false)),
+            expectation: readers::expectations::host_header,
+            request_value: readers::request_value::host_header,
+            with_reason: true,
+            diff_with: None,
+            weight: 3,
+        }),
         //*************************************************************************************
         // Port matchers
         //*************************************************************************************
@@ -202,6 +259,31 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 2,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "authority",
+            matcher_method: "authority",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(AuthorityEqualsComparator::new()),
+            expectation: readers::expectations::authority,
+            request_value: readers::request_value::authority,
+            with_reason: true,
+            diff_with: None,
+            weight: 3,
+        }),
+        //*************************************************************************************
+        // Client certificate matchers
+        //*************************************************************************************
+        Box::new(SingleValueMatcher {
+            entity_name: "client certificate common name",
+            matcher_method: "client_cert_cn",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(ClientCertCommonNameComparator::new()),
+            expectation: readers::expectations::client_cert_cn,
+            request_value: readers::request_value::client_cert,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         //*************************************************************************************
         // Path matchers
         //*************************************************************************************
@@ -227,6 +309,28 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 10,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "path",
+            matcher_method: "path",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(PathEqualsIgnoringTrailingSlashComparator::new(false)),
+            expectation: readers::expectations::path_equal_to_ignoring_trailing_slash,
+            request_value: readers::request_value::path,
+            with_reason: true,
+            diff_with: None,
+            weight: 10,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "path",
+            matcher_method: "path_not",
+            matching_strategy: MatchingStrategy::Absence,
+            comparator: Box::new(PathEqualsIgnoringTrailingSlashComparator::new(true)),
+            expectation: readers::expectations::path_not_equal_to_ignoring_trailing_slash,
+            request_value: readers::request_value::path,
+            with_reason: true,
+            diff_with: None,
+            weight: 10,
+        }),
         Box::new(SingleValueMatcher {
             entity_name: "path",
             matcher_method: "path_includes",
@@ -305,6 +409,89 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 10,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "path",
+            matcher_method: "path_glob",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringGlobMatchComparator::new()),
+            expectation: readers::expectations::path_glob,
+            request_value: readers::request_value::path,
+            with_reason: true,
+            diff_with: None,
+            weight: 10,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "path",
+            matcher_method: "path_template",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(PathTemplateMatchComparator::new()),
+            expectation: readers::expectations::path_template,
+            request_value: readers::request_value::path,
+            with_reason: true,
+            diff_with: None,
+            weight: 10,
+        }),
+        //*************************************************************************************
+        // Absolute URL matchers
+        //*************************************************************************************
+        Box::new(SingleValueMatcher {
+            entity_name: "absolute URL",
+            matcher_method: "url",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringEqualsComparator::new(true, false)),
+            expectation: readers::expectations::url,
+            request_value: readers::request_value::absolute_url,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "absolute URL",
+            matcher_method: "url_matches",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringRegexMatchComparator::new()),
+            expectation: readers::expectations::url_matches,
+            request_value: readers::request_value::absolute_url,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        //*************************************************************************************
+        // Raw query string matchers
+        //*************************************************************************************
+        Box::new(SingleValueMatcher {
+            entity_name: "raw query string",
+            matcher_method: "raw_query",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringEqualsComparator::new(true, false)),
+            expectation: readers::expectations::raw_query,
+            request_value: readers::request_value::raw_query,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "raw query string",
+            matcher_method: "raw_query_includes",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringContainsComparator::new(true, false)),
+            expectation: readers::expectations::raw_query_includes,
+            request_value: readers::request_value::raw_query,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "raw query string",
+            matcher_method: "raw_query_matches",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringRegexMatchComparator::new()),
+            expectation: readers::expectations::raw_query_matches,
+            request_value: readers::request_value::raw_query,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         //*************************************************************************************
         // Query param matchers
         //*************************************************************************************
@@ -478,6 +665,22 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 1,
         }),
+        Box::new(MultiValueSetMatcher {
+            entity_name: "query parameter",
+            matcher_method: "query_param_all",
+            expectation: readers::expectations::query_param_all,
+            request_value: readers::request_value::query_params,
+            exact: true,
+            weight: 1,
+        }),
+        Box::new(MultiValueSetMatcher {
+            entity_name: "query parameter",
+            matcher_method: "query_param_includes_all",
+            expectation: readers::expectations::query_param_includes_all,
+            request_value: readers::request_value::query_params,
+            exact: false,
+            weight: 1,
+        }),
         //************************************************************************************
         // Header matchers
         //************************************************************************************
@@ -495,6 +698,20 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 1,
         }),
+        Box::new(MultiValueMatcher {
+            entity_name: "header",
+            matcher_method: "header_name_exact",
+            matching_strategy: MatchingStrategy::Presence,
+            operator: KeyValueOperator::AND,
+            expectation: readers::expectations::header_name_exact,
+            request_value: readers::request_value::headers_original_case,
+            key_required: true,
+            key_comparator: Box::new(StringEqualsComparator::new(true, false)),
+            value_comparator: Box::new(StringEqualsComparator::new(true, false)),
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         Box::new(MultiValueMatcher {
             entity_name: "header",
             matcher_method: "header_not",
@@ -650,6 +867,39 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 1,
         }),
+        Box::new(MultiValueCountMatcher {
+            entity_name: "header",
+            matcher_method: "header_value_count",
+            expectation: readers::expectations::header_value_count,
+            request_value: readers::request_value::headers,
+            key_comparator: Box::new(StringEqualsComparator::new(false, false)),
+            value_comparator: Box::new(StringEqualsComparator::new(false, false)),
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "total header count",
+            matcher_method: "total_header_count_min",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(CountBoundComparator::new(false)),
+            expectation: readers::expectations::total_header_count_min,
+            request_value: readers::request_value::total_header_count,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "total header count",
+            matcher_method: "total_header_count_max",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(CountBoundComparator::new(true)),
+            expectation: readers::expectations::total_header_count_max,
+            request_value: readers::request_value::total_header_count,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         // ***********************************************************************************
         // Cookie matchers
         // ***********************************************************************************
@@ -835,6 +1085,41 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 1,
         }),
+        #[cfg(feature = "cookies")]
+        Box::new(SingleValueMatcher {
+            entity_name: "cookie count",
+            matcher_method: "cookie_count_min",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(CountBoundComparator::new(false)),
+            expectation: readers::expectations::cookie_count_min,
+            request_value: readers::request_value::cookie_count,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        #[cfg(feature = "cookies")]
+        Box::new(SingleValueMatcher {
+            entity_name: "cookie count",
+            matcher_method: "cookie_count_max",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(CountBoundComparator::new(true)),
+            expectation: readers::expectations::cookie_count_max,
+            request_value: readers::request_value::cookie_count,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "Cookie header",
+            matcher_method: "raw_cookie_includes",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(StringContainsComparator::new(true, false)),
+            expectation: readers::expectations::raw_cookie_includes,
+            request_value: readers::request_value::raw_cookie,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         // ************************************************************************************
         // Body matchers
         // ************************************************************************************
@@ -937,6 +1222,51 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: Some(Tokenizer::Line),
             weight: 1,
         }),
+        #[cfg(feature = "compression")]
+        Box::new(SingleValueMatcher {
+            entity_name: "decompressed body",
+            matcher_method: "body_decompressed_matches",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(DecodedBodyPatternComparator::new()),
+            expectation: readers::expectations::body_decompressed_matches,
+            request_value: readers::request_value::decoded_body,
+            with_reason: true,
+            diff_with: Some(Tokenizer::Line),
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "body_matches_any",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(HttpMockBytesAnyPatternComparator::new()),
+            expectation: readers::expectations::body_matches_any,
+            request_value: readers::request_value::body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body size",
+            matcher_method: "body_size",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(BodySizeComparator::new()),
+            expectation: readers::expectations::body_size,
+            request_value: readers::request_value::body_size,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "has_body",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(BodyPresenceComparator::new()),
+            expectation: readers::expectations::has_body,
+            request_value: readers::request_value::body_presence,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         //************************************************************************************
         // JSON body matchers
         //************************************************************************************
@@ -944,13 +1274,35 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             entity_name: "body",
             matcher_method: "json_body",
             matching_strategy: MatchingStrategy::Presence,
-            comparator: Box::new(JSONExactMatchComparator::new()),
+            comparator: Box::new(JSONExactMatchComparator::new(false)),
             expectation: readers::expectations::json_body,
             request_value: readers::request_value::json_body,
             with_reason: true,
             diff_with: Some(Tokenizer::Line),
             weight: 1,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "json_body_not",
+            matching_strategy: MatchingStrategy::Absence,
+            comparator: Box::new(JSONExactMatchComparator::new(true)),
+            expectation: readers::expectations::json_body_not,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: Some(Tokenizer::Line),
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "json_body_unordered",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(JSONUnorderedMatchComparator::new()),
+            expectation: readers::expectations::json_body_unordered,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: Some(Tokenizer::Line),
+            weight: 1,
+        }),
         Box::new(SingleValueMatcher {
             entity_name: "JSON body",
             matcher_method: "json_body_includes",
@@ -973,6 +1325,99 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: Some(Tokenizer::Line),
             weight: 1,
         }),
+        Box::new(SingleValueMatcher {
+            entity_name: "JSON body",
+            matcher_method: "json_body_includes_array",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(JSONArrayIncludesComparator::new()),
+            expectation: readers::expectations::json_body_includes_array,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "JSON body",
+            matcher_method: "json_body_has_key",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(JSONKeyPresenceComparator::new(false)),
+            expectation: readers::expectations::json_body_has_key,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "JSON body",
+            matcher_method: "json_body_missing_key",
+            matching_strategy: MatchingStrategy::Absence,
+            comparator: Box::new(JSONKeyPresenceComparator::new(true)),
+            expectation: readers::expectations::json_body_missing_key,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        #[cfg(feature = "json-schema")]
+        Box::new(SingleValueMatcher {
+            entity_name: "JSON body",
+            matcher_method: "json_schema",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(JSONSchemaComparator::new()),
+            expectation: readers::expectations::json_schema,
+            request_value: readers::request_value::json_body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        #[cfg(feature = "protobuf")]
+        Box::new(SingleValueMatcher {
+            entity_name: "protobuf body",
+            matcher_method: "protobuf_field",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(ProtobufFieldComparator::new()),
+            expectation: readers::expectations::protobuf_field,
+            request_value: readers::request_value::body,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        //************************************************************************************
+        // Body format matchers
+        //************************************************************************************
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "is_json",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(BodyFormatComparator::new()),
+            expectation: readers::expectations::is_json,
+            request_value: readers::request_value::json_body_format,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "is_valid_xml",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(BodyFormatComparator::new()),
+            expectation: readers::expectations::is_valid_xml,
+            request_value: readers::request_value::xml_body_format,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
+        Box::new(SingleValueMatcher {
+            entity_name: "body",
+            matcher_method: "is_form_urlencoded",
+            matching_strategy: MatchingStrategy::Presence,
+            comparator: Box::new(BodyFormatComparator::new()),
+            expectation: readers::expectations::is_form_urlencoded,
+            request_value: readers::request_value::form_urlencoded_body_format,
+            with_reason: true,
+            diff_with: None,
+            weight: 1,
+        }),
         Box::new(FunctionValueMatcher {
             entity_name: "custom matcher function",
             matcher_function: "is_true",
@@ -1162,6 +1607,32 @@ pub fn all() -> Vec<Box<dyn Matcher + Sync + Send>> {
             diff_with: None,
             weight: 1,
         }),
+        //*************************************************************************************
+        // Named matchers
+        //*************************************************************************************
+        Box::new(NamedFunctionMatcher {
+            entity_name: "request",
+            matcher_method: "matcher_named",
+            named_matchers: named_matchers.clone(),
+            weight: 1,
+        }),
+        //*************************************************************************************
+        // Condition groups
+        //*************************************************************************************
+        Box::new(ConditionGroupMatcher::new(
+            "request",
+            "any_of",
+            readers::expectations::any_of,
+            true,
+            named_matchers.clone(),
+        )),
+        Box::new(ConditionGroupMatcher::new(
+            "request",
+            "all_of",
+            readers::expectations::all_of,
+            false,
+            named_matchers.clone(),
+        )),
     ]
 }
 
@@ -1169,4 +1640,7 @@ pub trait Matcher {
     fn matches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> bool;
     fn distance(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> usize;
     fn mismatches(&self, req: &HttpMockRequest, mock: &RequestRequirements) -> Vec<Mismatch>;
+    /// Returns the number of expectations of the mock that this matcher checks. It is used to
+    /// find the most specific mock for a request.
+    fn constraint_count(&self, mock: &RequestRequirements) -> usize;
 }
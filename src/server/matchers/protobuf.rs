@@ -0,0 +1,213 @@
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, Value as ProtoValue,
+};
+use serde_json::Value;
+
+use crate::{
+    common::{data::ProtobufFieldExpectation, util::HttpMockBytes},
+    server::matchers::comparators::ValueComparator,
+};
+
+/// Looks up a message type in an encoded `FileDescriptorSet`.
+pub(crate) fn message_descriptor(
+    file_descriptor_set: &[u8],
+    message_name: &str,
+) -> Result<MessageDescriptor, String> {
+    let pool = DescriptorPool::decode(file_descriptor_set)
+        .map_err(|err| format!("invalid protobuf file descriptor set: {}", err))?;
+
+    pool.get_message_by_name(message_name).ok_or_else(|| {
+        format!(
+            "protobuf message type '{}' not found in file descriptor set",
+            message_name
+        )
+    })
+}
+
+/// Resolves a dot-separated field path (e.g., `customer.name`) to the descriptors of all fields
+/// along the path. All fields except the last one must be singular message fields. The last field
+/// must be a singular scalar or enum field.
+pub(crate) fn resolve_field_path(
+    message: &MessageDescriptor,
+    field_path: &str,
+) -> Result<Vec<FieldDescriptor>, String> {
+    let mut fields = Vec::new();
+    let mut current = message.clone();
+
+    let segments: Vec<&str> = field_path.split('.').collect();
+    for (idx, segment) in segments.iter().enumerate() {
+        let field = current.get_field_by_name(segment).ok_or_else(|| {
+            format!(
+                "protobuf message type '{}' has no field '{}'",
+                current.full_name(),
+                segment
+            )
+        })?;
+
+        if field.is_list() || field.is_map() {
+            return Err(format!(
+                "protobuf field '{}' is a repeated field, which is not supported",
+                field.full_name()
+            ));
+        }
+
+        let is_last = idx == segments.len() - 1;
+        match (field.kind(), is_last) {
+            (Kind::Message(nested), false) => current = nested,
+            (Kind::Message(_), true) => {
+                return Err(format!(
+                "protobuf field '{}' is a message field, but only scalar fields can be compared",
+                field.full_name()
+            ))
+            }
+            (_, false) => {
+                return Err(format!(
+                    "protobuf field '{}' is not a message field and has no field '{}'",
+                    field.full_name(),
+                    segments[idx + 1]
+                ))
+            }
+            (_, true) => {}
+        }
+
+        fields.push(field);
+    }
+
+    Ok(fields)
+}
+
+/// Decodes the request body and reads the value of the expected field. Unset fields have their
+/// default value, just like they have in generated protobuf code. Besides the JSON
+/// representation of the value, the number of enum values is returned.
+fn read_field(
+    expectation: &ProtobufFieldExpectation,
+    body: &[u8],
+) -> Result<(Value, Option<i32>), String> {
+    let descriptor = message_descriptor(
+        &expectation.file_descriptor_set.0,
+        &expectation.message_name,
+    )?;
+    let fields = resolve_field_path(&descriptor, &expectation.field_path)?;
+
+    let mut message = DynamicMessage::decode(descriptor, body).map_err(|err| {
+        format!(
+            "request body cannot be decoded as protobuf message '{}': {}",
+            expectation.message_name, err
+        )
+    })?;
+
+    let (leaf, path) = fields.split_last().expect("field path must not be empty");
+    for field in path {
+        message = match message.get_field(field).into_owned() {
+            ProtoValue::Message(nested) => nested,
+            _ => unreachable!("field path was resolved to message fields"),
+        };
+    }
+
+    let value = message.get_field(leaf);
+    let enum_number = value.as_enum_number();
+
+    Ok((to_json(&value, &leaf.kind()), enum_number))
+}
+
+/// Converts a scalar protobuf value to JSON. Bytes are represented as base64 and enum values by
+/// their name, if the enum type defines one for the number.
+fn to_json(value: &ProtoValue, kind: &Kind) -> Value {
+    match value {
+        ProtoValue::Bool(v) => Value::from(*v),
+        ProtoValue::I32(v) => Value::from(*v),
+        ProtoValue::I64(v) => Value::from(*v),
+        ProtoValue::U32(v) => Value::from(*v),
+        ProtoValue::U64(v) => Value::from(*v),
+        ProtoValue::F32(v) => Value::from(*v as f64),
+        ProtoValue::F64(v) => Value::from(*v),
+        ProtoValue::String(v) => Value::from(v.as_str()),
+        ProtoValue::Bytes(v) => Value::from(base64::encode(v)),
+        ProtoValue::EnumNumber(number) => match kind {
+            Kind::Enum(enum_type) => match enum_type.get_value(*number) {
+                Some(enum_value) => Value::from(enum_value.name()),
+                None => Value::from(*number),
+            },
+            _ => Value::from(*number),
+        },
+        ProtoValue::Message(_) | ProtoValue::List(_) | ProtoValue::Map(_) => Value::Null,
+    }
+}
+
+/// Compares an expected JSON value to the JSON representation of a protobuf field value.
+/// Numbers are compared by value, so that `1` matches a `double` field that contains `1.0`.
+/// Enum fields can be compared using either their name or their number.
+fn values_equal(expected: &Value, actual: &Value, number: Option<i32>) -> bool {
+    match (expected, actual) {
+        (Value::Number(e), _) if number.is_some() => e.as_i64() == number.map(i64::from),
+        (Value::Number(e), Value::Number(a)) => match (e.as_i64(), a.as_i64()) {
+            (Some(e), Some(a)) => e == a,
+            _ => match (e.as_u64(), a.as_u64()) {
+                (Some(e), Some(a)) => e == a,
+                _ => e.as_f64() == a.as_f64(),
+            },
+        },
+        _ => expected == actual,
+    }
+}
+
+// ************************************************************************************************
+// ProtobufFieldComparator
+// ************************************************************************************************
+pub struct ProtobufFieldComparator {}
+
+impl ProtobufFieldComparator {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ValueComparator<ProtobufFieldExpectation, HttpMockBytes> for ProtobufFieldComparator {
+    fn matches(
+        &self,
+        mock_value: &Option<&ProtobufFieldExpectation>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> bool {
+        self.reason(mock_value, req_value).is_none()
+    }
+
+    fn name(&self) -> &str {
+        "to have field"
+    }
+
+    fn distance(
+        &self,
+        mock_value: &Option<&ProtobufFieldExpectation>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> usize {
+        match self.matches(mock_value, req_value) {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    fn reason(
+        &self,
+        mock_value: &Option<&ProtobufFieldExpectation>,
+        req_value: &Option<&HttpMockBytes>,
+    ) -> Option<String> {
+        let expectation = match mock_value {
+            None => return None,
+            Some(mv) => mv,
+        };
+
+        let body = req_value.map(|b| b.0.as_ref()).unwrap_or_default();
+        let (actual, enum_number) = match read_field(expectation, body) {
+            Ok(field) => field,
+            Err(err) => return Some(err),
+        };
+
+        match values_equal(&expectation.value, &actual, enum_number) {
+            true => None,
+            false => Some(format!(
+                "field '{}' has value {}",
+                expectation.field_path, actual
+            )),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 pub mod expectations {
     use crate::{
         common::{
-            data::{HttpMockRegex, RequestRequirements},
+            data::{
+                AnyOfPatterns, AnyOfValues, BodyFormat, BodyPresence, BodySizeRange, HttpMockRegex,
+                JsonArrayElements, JsonSchema, PathTemplate, ProtobufFieldExpectation,
+                RequestRequirements,
+            },
             util::HttpMockBytes,
         },
         prelude::HttpMockRequest,
@@ -19,11 +23,21 @@ pub mod expectations {
         mock.scheme_not.as_ref().map(|b| vec![b])
     }
 
+    #[inline]
+    pub fn http_version_equal_to(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.http_version.as_ref().map(|v| vec![v])
+    }
+
     #[inline]
     pub fn method_equal_to(mock: &RequestRequirements) -> Option<Vec<&String>> {
         mock.method.as_ref().map(|b| vec![b])
     }
 
+    #[inline]
+    pub fn method_in(mock: &RequestRequirements) -> Option<Vec<&AnyOfValues>> {
+        mock.method_in.as_ref().map(|v| vec![v])
+    }
+
     #[inline]
     pub fn method_not_equal_to(mock: &RequestRequirements) -> Option<Vec<&String>> {
         mock.method_not
@@ -41,6 +55,16 @@ pub mod expectations {
         mock.host_not.as_ref().map(|v| v.iter().collect())
     }
 
+    #[inline]
+    pub fn authority(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.authority.as_ref().map(|b| vec![b])
+    }
+
+    #[inline]
+    pub fn host_header(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.host_header.as_ref().map(|b| vec![b])
+    }
+
     #[inline]
     pub fn host_includes(mock: &RequestRequirements) -> Option<Vec<&String>> {
         mock.host_contains
@@ -102,19 +126,76 @@ pub mod expectations {
 
     #[inline]
     pub fn path_equal_to(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        if !strict_trailing_slash(mock) {
+            return None;
+        }
         mock.path.as_ref().map(|b| vec![b])
     }
 
     #[inline]
     pub fn path_not_equal_to(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        if !strict_trailing_slash(mock) {
+            return None;
+        }
         mock.path_not.as_ref().map(|v| v.iter().collect())
     }
 
+    #[inline]
+    pub fn path_equal_to_ignoring_trailing_slash(
+        mock: &RequestRequirements,
+    ) -> Option<Vec<&String>> {
+        if strict_trailing_slash(mock) {
+            return None;
+        }
+        mock.path.as_ref().map(|b| vec![b])
+    }
+
+    #[inline]
+    pub fn path_not_equal_to_ignoring_trailing_slash(
+        mock: &RequestRequirements,
+    ) -> Option<Vec<&String>> {
+        if strict_trailing_slash(mock) {
+            return None;
+        }
+        mock.path_not.as_ref().map(|v| v.iter().collect())
+    }
+
+    /// Paths are compared strictly unless trailing slashes were explicitly allowed.
+    #[inline]
+    fn strict_trailing_slash(mock: &RequestRequirements) -> bool {
+        mock.strict_trailing_slash.unwrap_or(true)
+    }
+
     #[inline]
     pub fn path_includes(mock: &RequestRequirements) -> Option<Vec<&String>> {
         mock.path_includes.as_ref().map(|v| v.iter().collect())
     }
 
+    #[inline]
+    pub fn url(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.url.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn url_matches(mock: &RequestRequirements) -> Option<Vec<&HttpMockRegex>> {
+        mock.url_matches.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn raw_query(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.raw_query.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn raw_query_includes(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.raw_query_includes.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn raw_query_matches(mock: &RequestRequirements) -> Option<Vec<&HttpMockRegex>> {
+        mock.raw_query_matches.as_ref().map(|v| v.iter().collect())
+    }
+
     #[inline]
     pub fn path_excludes(mock: &RequestRequirements) -> Option<Vec<&String>> {
         mock.path_excludes.as_ref().map(|v| v.iter().collect())
@@ -147,6 +228,21 @@ pub mod expectations {
             .map(|b| b.into_iter().map(|v| v).collect())
     }
 
+    #[inline]
+    pub fn path_glob(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.path_glob.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn path_template(mock: &RequestRequirements) -> Option<Vec<&PathTemplate>> {
+        mock.path_template.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn client_cert_cn(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.client_cert_cn.as_ref().map(|v| v.iter().collect())
+    }
+
     #[inline]
     pub fn query_param(mock: &RequestRequirements) -> Option<Vec<(&String, Option<&String>)>> {
         mock.query_param
@@ -251,6 +347,22 @@ pub mod expectations {
             .map(|v| v.iter().map(|(k, v, c)| (Some(k), Some(v), *c)).collect())
     }
 
+    #[inline]
+    pub fn query_param_all(mock: &RequestRequirements) -> Option<Vec<(&String, &Vec<String>)>> {
+        mock.query_param_all
+            .as_ref()
+            .map(|v| v.iter().map(|(k, v)| (k, v)).collect())
+    }
+
+    #[inline]
+    pub fn query_param_includes_all(
+        mock: &RequestRequirements,
+    ) -> Option<Vec<(&String, &Vec<String>)>> {
+        mock.query_param_includes_all
+            .as_ref()
+            .map(|v| v.iter().map(|(k, v)| (k, v)).collect())
+    }
+
     #[inline]
     pub fn header(mock: &RequestRequirements) -> Option<Vec<(&String, Option<&String>)>> {
         mock.header
@@ -258,6 +370,15 @@ pub mod expectations {
             .map(|v| v.into_iter().map(|(k, v)| (k, Some(v))).collect())
     }
 
+    #[inline]
+    pub fn header_name_exact(
+        mock: &RequestRequirements,
+    ) -> Option<Vec<(&String, Option<&String>)>> {
+        mock.header_name_exact
+            .as_ref()
+            .map(|v| v.into_iter().map(|(k, v)| (k, Some(v))).collect())
+    }
+
     #[inline]
     pub fn header_not(mock: &RequestRequirements) -> Option<Vec<(&String, Option<&String>)>> {
         mock.header_not
@@ -343,6 +464,25 @@ pub mod expectations {
             .map(|v| v.iter().map(|(k, v, c)| (Some(k), Some(v), *c)).collect())
     }
 
+    #[inline]
+    pub fn total_header_count_min(mock: &RequestRequirements) -> Option<Vec<&usize>> {
+        mock.total_header_count_min.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn total_header_count_max(mock: &RequestRequirements) -> Option<Vec<&usize>> {
+        mock.total_header_count_max.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn header_value_count(
+        mock: &RequestRequirements,
+    ) -> Option<Vec<(Option<&String>, Option<&String>, usize)>> {
+        mock.header_value_count
+            .as_ref()
+            .map(|v| v.iter().map(|(k, c)| (Some(k), None, *c)).collect())
+    }
+
     #[inline]
     pub fn cookie(mock: &RequestRequirements) -> Option<Vec<(&String, Option<&String>)>> {
         mock.cookie
@@ -426,6 +566,23 @@ pub mod expectations {
             .map(|v| v.into_iter().map(|(k, v)| (k, Some(v))).collect())
     }
 
+    #[inline]
+    pub fn cookie_count_min(mock: &RequestRequirements) -> Option<Vec<&usize>> {
+        mock.cookie_count_min.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn cookie_count_max(mock: &RequestRequirements) -> Option<Vec<&usize>> {
+        mock.cookie_count_max.as_ref().map(|v| vec![v])
+    }
+
+    #[inline]
+    pub fn raw_cookie_includes(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.raw_cookie_includes
+            .as_ref()
+            .map(|v| v.iter().collect())
+    }
+
     #[inline]
     pub fn cookie_count(
         mock: &RequestRequirements,
@@ -482,11 +639,52 @@ pub mod expectations {
             .map(|b| b.into_iter().map(|v| v).collect())
     }
 
+    #[inline]
+    pub fn body_decompressed_matches(mock: &RequestRequirements) -> Option<Vec<&HttpMockRegex>> {
+        mock.body_decompressed_matches
+            .as_ref()
+            .map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn body_matches_any(mock: &RequestRequirements) -> Option<Vec<&AnyOfPatterns>> {
+        mock.body_matches_any.as_ref().map(|v| v.iter().collect())
+    }
+
     #[inline]
     pub fn json_body(mock: &RequestRequirements) -> Option<Vec<&Value>> {
         mock.json_body.as_ref().map(|b| vec![b])
     }
 
+    #[inline]
+    pub fn json_body_not(mock: &RequestRequirements) -> Option<Vec<&Value>> {
+        mock.json_body_not.as_ref().map(|b| vec![b])
+    }
+
+    #[inline]
+    pub fn json_body_unordered(mock: &RequestRequirements) -> Option<Vec<&Value>> {
+        mock.json_body_unordered.as_ref().map(|b| vec![b])
+    }
+
+    #[inline]
+    pub fn json_body_includes_array(mock: &RequestRequirements) -> Option<Vec<&JsonArrayElements>> {
+        mock.json_body_includes_array
+            .as_ref()
+            .map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn json_body_has_key(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.json_body_has_key.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn json_body_missing_key(mock: &RequestRequirements) -> Option<Vec<&String>> {
+        mock.json_body_missing_key
+            .as_ref()
+            .map(|v| v.iter().collect())
+    }
+
     #[inline]
     pub fn json_body_includes(mock: &RequestRequirements) -> Option<Vec<&serde_json::Value>> {
         mock.json_body_includes
@@ -501,6 +699,48 @@ pub mod expectations {
             .map(|b| b.into_iter().collect())
     }
 
+    #[inline]
+    pub fn body_size(mock: &RequestRequirements) -> Option<Vec<&BodySizeRange>> {
+        mock.body_size.as_ref().map(|v| v.iter().collect())
+    }
+
+    #[inline]
+    pub fn has_body(mock: &RequestRequirements) -> Option<Vec<&BodyPresence>> {
+        mock.has_body.map(|v| match v {
+            true => vec![&BodyPresence::Present],
+            false => vec![&BodyPresence::Absent],
+        })
+    }
+
+    #[inline]
+    pub fn json_schema(mock: &RequestRequirements) -> Option<Vec<&JsonSchema>> {
+        mock.json_schema.as_ref().map(|b| b.iter().collect())
+    }
+
+    #[inline]
+    pub fn protobuf_field(mock: &RequestRequirements) -> Option<Vec<&ProtobufFieldExpectation>> {
+        mock.protobuf_field.as_ref().map(|b| b.iter().collect())
+    }
+
+    #[inline]
+    pub fn is_json(mock: &RequestRequirements) -> Option<Vec<&BodyFormat>> {
+        mock.is_json.filter(|v| *v).map(|_| vec![&BodyFormat::Json])
+    }
+
+    #[inline]
+    pub fn is_valid_xml(mock: &RequestRequirements) -> Option<Vec<&BodyFormat>> {
+        mock.is_valid_xml
+            .filter(|v| *v)
+            .map(|_| vec![&BodyFormat::Xml])
+    }
+
+    #[inline]
+    pub fn is_form_urlencoded(mock: &RequestRequirements) -> Option<Vec<&BodyFormat>> {
+        mock.is_form_urlencoded
+            .filter(|v| *v)
+            .map(|_| vec![&BodyFormat::FormUrlencoded])
+    }
+
     #[inline]
     pub fn is_true(
         mock: &RequestRequirements,
@@ -611,10 +851,33 @@ pub mod expectations {
             .as_ref()
             .map(|v| v.iter().map(|(k, v, c)| (Some(k), Some(v), *c)).collect())
     }
+
+    #[inline]
+    pub fn any_of(mock: &RequestRequirements) -> Option<Vec<&[RequestRequirements]>> {
+        mock.any_of
+            .as_ref()
+            .map(|groups| groups.iter().map(|g| g.as_slice()).collect())
+    }
+
+    #[inline]
+    pub fn all_of(mock: &RequestRequirements) -> Option<Vec<&[RequestRequirements]>> {
+        mock.all_of.as_ref().map(|g| vec![g.as_slice()])
+    }
 }
 
 pub mod request_value {
-    use crate::{common::util::HttpMockBytes, prelude::HttpMockRequest};
+    #[cfg(feature = "compression")]
+    use crate::{common::data::DecodedBody, server::compression};
+    use crate::{
+        common::{
+            data::{
+                BodyFormat, BodyFormatValidation, BodyPresence, ClientCertificate, RequestAuthority,
+            },
+            util::HttpMockBytes,
+        },
+        prelude::HttpMockRequest,
+        server::matchers::comparison::body_format_error,
+    };
     use serde_json::Value;
 
     #[inline]
@@ -622,6 +885,11 @@ pub mod request_value {
         Some(req.scheme())
     }
 
+    #[inline]
+    pub fn http_version(req: &HttpMockRequest) -> Option<String> {
+        Some(req.version_ref().to_string())
+    }
+
     #[inline]
     pub fn method(req: &HttpMockRequest) -> Option<String> {
         Some(req.method().to_string())
@@ -632,11 +900,43 @@ pub mod request_value {
         req.host().map(|h| h.to_string())
     }
 
+    #[inline]
+    pub fn host_header(req: &HttpMockRequest) -> Option<String> {
+        req.headers_vec()
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+            .map(|(_, value)| value.clone())
+    }
+
     #[inline]
     pub fn port(req: &HttpMockRequest) -> Option<u16> {
         Some(req.port())
     }
 
+    #[inline]
+    pub fn authority(req: &HttpMockRequest) -> Option<RequestAuthority> {
+        let default_port = match req.scheme().as_str() {
+            "https" => 443,
+            _ => 80,
+        };
+
+        req.host().map(|host| RequestAuthority {
+            host: host.to_lowercase(),
+            port: req.port(),
+            default_port,
+        })
+    }
+
+    #[inline]
+    pub fn absolute_url(req: &HttpMockRequest) -> Option<String> {
+        Some(req.absolute_url())
+    }
+
+    #[inline]
+    pub fn raw_query(req: &HttpMockRequest) -> Option<String> {
+        Some(req.raw_query().unwrap_or_default().to_string())
+    }
+
     #[inline]
     pub fn path(req: &HttpMockRequest) -> Option<String> {
         Some(req.uri().path().to_string())
@@ -662,6 +962,16 @@ pub mod request_value {
         )
     }
 
+    #[inline]
+    pub fn headers_original_case(req: &HttpMockRequest) -> Option<Vec<(String, Option<String>)>> {
+        Some(
+            req.headers_vec_original_case()
+                .into_iter()
+                .map(|(k, v)| (k, Some(v)))
+                .collect(),
+        )
+    }
+
     #[cfg(feature = "cookies")]
     #[inline]
     pub fn cookies(req: &HttpMockRequest) -> Option<Vec<(String, Option<String>)>> {
@@ -674,11 +984,73 @@ pub mod request_value {
         )
     }
 
+    #[cfg(feature = "cookies")]
+    #[inline]
+    pub fn cookie_count(req: &HttpMockRequest) -> Option<usize> {
+        Some(req.cookies().expect("cannot parse cookies").len())
+    }
+
+    /// Returns the unparsed `Cookie` header. HTTP/2 clients may split cookies across multiple
+    /// `Cookie` headers, which are joined with `; ` as they would be in a single header.
+    #[inline]
+    pub fn raw_cookie(req: &HttpMockRequest) -> Option<String> {
+        let values: Vec<&str> = req
+            .headers_vec()
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("cookie"))
+            .map(|(_, value)| value.as_str())
+            .collect();
+
+        match values.is_empty() {
+            true => None,
+            false => Some(values.join("; ")),
+        }
+    }
+
     #[inline]
     pub fn body(req: &HttpMockRequest) -> Option<HttpMockBytes> {
         Some(req.body().clone())
     }
 
+    #[cfg(feature = "compression")]
+    pub fn decoded_body(req: &HttpMockRequest) -> Option<DecodedBody> {
+        let encoding = req
+            .headers_vec()
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if encoding.is_empty() {
+            return Some(DecodedBody {
+                encoding: "identity".to_string(),
+                body: Ok(req.body().clone()),
+            });
+        }
+
+        let body = compression::decode(&encoding, req.body_ref()).map(HttpMockBytes::from);
+        Some(DecodedBody { encoding, body })
+    }
+
+    #[inline]
+    pub fn total_header_count(req: &HttpMockRequest) -> Option<usize> {
+        Some(req.headers_vec().len())
+    }
+
+    #[inline]
+    pub fn body_size(req: &HttpMockRequest) -> Option<usize> {
+        Some(req.body_ref().len())
+    }
+
+    #[inline]
+    pub fn body_presence(req: &HttpMockRequest) -> Option<BodyPresence> {
+        match req.body_ref().is_empty() {
+            true => Some(BodyPresence::Absent),
+            false => Some(BodyPresence::Present),
+        }
+    }
+
     #[inline]
     pub fn json_body(req: &HttpMockRequest) -> Option<serde_json::Value> {
         let body = req.body_ref();
@@ -695,6 +1067,28 @@ pub mod request_value {
         }
     }
 
+    #[inline]
+    pub fn json_body_format(req: &HttpMockRequest) -> Option<BodyFormatValidation> {
+        Some(body_format(req, BodyFormat::Json))
+    }
+
+    #[inline]
+    pub fn xml_body_format(req: &HttpMockRequest) -> Option<BodyFormatValidation> {
+        Some(body_format(req, BodyFormat::Xml))
+    }
+
+    #[inline]
+    pub fn form_urlencoded_body_format(req: &HttpMockRequest) -> Option<BodyFormatValidation> {
+        Some(body_format(req, BodyFormat::FormUrlencoded))
+    }
+
+    fn body_format(req: &HttpMockRequest, format: BodyFormat) -> BodyFormatValidation {
+        BodyFormatValidation {
+            error: body_format_error(&format, req.body_ref()),
+            format,
+        }
+    }
+
     pub fn form_urlencoded_body(req: &HttpMockRequest) -> Option<Vec<(String, Option<String>)>> {
         Some(
             form_urlencoded::parse(req.body_ref())
@@ -708,4 +1102,9 @@ pub mod request_value {
     pub fn full_request(req: &HttpMockRequest) -> Option<&HttpMockRequest> {
         Some(req)
     }
+
+    #[inline]
+    pub fn client_cert(req: &HttpMockRequest) -> Option<ClientCertificate> {
+        req.client_cert().cloned()
+    }
 }
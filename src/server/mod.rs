@@ -1,7 +1,10 @@
 #![allow(clippy::trivial_regex)]
 use std::{borrow::Borrow, str::FromStr};
 
-use crate::server::matchers::Matcher;
+use crate::{
+    common::data::{ClientCertificate, RawRequestHead},
+    server::matchers::Matcher,
+};
 use bytes::Bytes;
 use futures_util::task::Spawn;
 use hyper::body::{Body, Buf};
@@ -10,23 +13,33 @@ use std::{future::Future, net::SocketAddr};
 use futures_util::{FutureExt, TryStreamExt};
 use http_body_util::BodyExt;
 
+mod access_log;
+mod barrier;
 mod builder;
+#[cfg(feature = "compression")]
+mod compression;
+mod conditional;
+mod cors;
 mod handler;
+mod header_case;
 pub mod matchers;
 mod server;
 pub mod state;
 mod util;
 
 #[cfg(feature = "record")]
-mod persistence;
+pub(crate) mod persistence;
 
 #[cfg(feature = "https")]
 mod tls;
 
 use crate::server::{handler::HttpMockHandler, server::MockServer, state::HttpMockStateManager};
 
+pub use access_log::AccessLogFormat;
 pub use builder::HttpMockServerBuilder;
+pub use cors::CorsConfig;
 pub use server::Error;
+pub use state::MatchStrategy;
 
 // We want to expose this error to the user
 pub type HttpMockServer = MockServer<HttpMockHandler<HttpMockStateManager>>;
@@ -34,10 +47,35 @@ pub type HttpMockServer = MockServer<HttpMockHandler<HttpMockStateManager>>;
 #[derive(Clone)]
 pub struct RequestMetadata {
     pub scheme: &'static str,
+    pub client_cert: Option<ClientCertificate>,
+    /// The header names of the request in the exact case and order the client sent them, if
+    /// they are known.
+    pub original_header_names: Option<Vec<String>>,
+    pub(crate) raw_head: Option<RawRequestHead>,
 }
 
 impl RequestMetadata {
     pub fn new(scheme: &'static str) -> Self {
-        Self { scheme }
+        Self {
+            scheme,
+            client_cert: None,
+            original_header_names: None,
+            raw_head: None,
+        }
+    }
+
+    pub fn with_client_cert(mut self, client_cert: Option<ClientCertificate>) -> Self {
+        self.client_cert = client_cert;
+        self
+    }
+
+    pub fn with_original_header_names(mut self, names: Option<Vec<String>>) -> Self {
+        self.original_header_names = names;
+        self
+    }
+
+    pub(crate) fn with_raw_head(mut self, head: Option<RawRequestHead>) -> Self {
+        self.raw_head = head;
+        self
     }
 }
@@ -1,12 +1,20 @@
 use bytes::{BufMut, Bytes, BytesMut};
 use std::{
+    collections::{BTreeMap, BTreeSet},
     convert::{TryFrom, TryInto},
     fs::read_dir,
-    path::PathBuf,
+    future::Future,
+    path::{Path, PathBuf},
     str::FromStr,
+    sync::{Arc, Weak},
+    time::Duration,
 };
 
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc::unbounded_channel;
+
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::common::data;
 use serde_yaml::{Deserializer, Value as YamlValue};
@@ -14,11 +22,11 @@ use thiserror::Error;
 
 use crate::{
     common::{
-        data::{MockDefinition, StaticMockDefinition},
-        util::read_file,
+        data::{MockDefinition, RecordingRuleConfig, StaticMockDefinition},
+        util::{parse_json_path, read_file, HttpMockBytes, JsonPathSegment},
     },
     server::{
-        persistence::Error::{DeserializationError, FileReadError},
+        persistence::Error::{DeserializationError, FileReadError, RedactionError, WatchError},
         state,
         state::{Error::DataConversionError, StateManager},
     },
@@ -34,6 +42,10 @@ pub enum Error {
     DeserializationError(String),
     #[error("cannot convert data structures: {0}")]
     DataConversionError(#[from] data::Error),
+    #[error("cannot redact recording: {0}")]
+    RedactionError(String),
+    #[error("cannot watch mock directory: {0}")]
+    WatchError(String),
     #[error("unknown data store error")]
     Unknown,
 }
@@ -55,10 +67,8 @@ fn read_static_mocks(path: PathBuf) -> Result<Vec<StaticMockDefinition>, Error>
     let paths = read_dir(path).expect("cannot list files in directory");
     for file_path in paths {
         let file_path = file_path.unwrap().path();
-        if let Some(ext) = file_path.extension() {
-            if !"yaml".eq(ext) && !"yml".eq(ext) {
-                continue;
-            }
+        if !is_mock_file(&file_path) {
+            continue;
         }
 
         log::info!(
@@ -75,6 +85,240 @@ fn read_static_mocks(path: PathBuf) -> Result<Vec<StaticMockDefinition>, Error>
     return Ok(definitions);
 }
 
+fn is_mock_file(path: &Path) -> bool {
+    path.extension()
+        .map_or(true, |ext| "yaml".eq(ext) || "yml".eq(ext))
+}
+
+/// How long to wait for further file system notifications before a watched static mock
+/// directory is reloaded.
+const MOCK_DIR_DEBOUNCE_DELAY: Duration = Duration::from_millis(100);
+
+struct LoadedMockFile {
+    content: Vec<u8>,
+    mock_ids: Vec<usize>,
+}
+
+/// Keeps the static mocks of a mock server in sync with the YAML files in a directory.
+///
+/// Each file is reloaded on its own and replaces the mocks previously loaded from it in a single
+/// step. If a file cannot be read or parsed (e.g., because it is saved while still being
+/// edited), the error is logged and the mocks previously loaded from the file stay active.
+pub struct StaticMockDirWatcher<S> {
+    dir: PathBuf,
+    state: Weak<S>,
+    files: BTreeMap<PathBuf, LoadedMockFile>,
+}
+
+impl<S> StaticMockDirWatcher<S>
+where
+    S: StateManager + Send + Sync + 'static,
+{
+    /// Loads all mock files from the directory. Unlike later reloads, this fails if any of
+    /// the files is invalid.
+    pub fn load(dir: PathBuf, state: &Arc<S>) -> Result<Self, Error> {
+        let mut watcher = Self {
+            dir,
+            state: Arc::downgrade(state),
+            files: BTreeMap::new(),
+        };
+
+        for file_path in list_mock_files(&watcher.dir)? {
+            log::info!(
+                "Loading static mock file from '{}'",
+                file_path.to_string_lossy()
+            );
+
+            let content = read_file(&file_path).map_err(FileReadError)?;
+            let mock_ids = state.replace_static_mocks(&[], parse_mock_file(&content)?)?;
+            watcher
+                .files
+                .insert(file_path, LoadedMockFile { content, mock_ids });
+        }
+
+        Ok(watcher)
+    }
+
+    /// Starts watching the directory for file system notifications. The returned future applies
+    /// the changes and runs as a background task of the mock server, so that watching stops when
+    /// the server shuts down. It also ends when the server state is dropped.
+    pub fn watch(mut self) -> Result<impl Future<Output = ()> + Send + 'static, Error> {
+        let (sender, mut receiver) = unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // Sending only fails after the task was aborted, which means the server stopped.
+            let _ = sender.send(event);
+        })
+        .map_err(|err| WatchError(err.to_string()))?;
+        watcher
+            .watch(&self.dir, RecursiveMode::NonRecursive)
+            .map_err(|err| WatchError(err.to_string()))?;
+
+        Ok(async move {
+            // The watcher stops sending notifications when it is dropped together with the task.
+            let _watcher = watcher;
+
+            while let Some(event) = receiver.recv().await {
+                // Saving a file usually causes several notifications in a row.
+                tokio::time::sleep(MOCK_DIR_DEBOUNCE_DELAY).await;
+
+                let mut changed_files = BTreeSet::new();
+                self.collect_changed_files(event, &mut changed_files);
+                while let Ok(event) = receiver.try_recv() {
+                    self.collect_changed_files(event, &mut changed_files);
+                }
+
+                let state = match self.state.upgrade() {
+                    Some(state) => state,
+                    None => return,
+                };
+
+                self.sync(state.as_ref(), changed_files);
+            }
+        })
+    }
+
+    fn collect_changed_files(
+        &self,
+        event: notify::Result<notify::Event>,
+        changed_files: &mut BTreeSet<PathBuf>,
+    ) {
+        let event = match event {
+            Ok(event) => event,
+            Err(err) => {
+                log::error!(
+                    "cannot watch static mock directory '{}': {}",
+                    self.dir.to_string_lossy(),
+                    err
+                );
+                return;
+            }
+        };
+
+        // Notifications may have been lost, so all files need to be checked.
+        if event.need_rescan() {
+            changed_files.extend(self.files.keys().cloned());
+            match list_mock_files(&self.dir) {
+                Ok(file_paths) => changed_files.extend(file_paths),
+                Err(err) => log::error!(
+                    "cannot list static mock files in '{}': {}",
+                    self.dir.to_string_lossy(),
+                    err
+                ),
+            }
+            return;
+        }
+
+        // Paths are rebuilt from the file names, so that they are the same as the ones that were
+        // used when the directory was loaded.
+        changed_files.extend(
+            event
+                .paths
+                .iter()
+                .filter_map(|path| path.file_name())
+                .map(|name| self.dir.join(name))
+                .filter(|path| is_mock_file(path)),
+        );
+    }
+
+    fn sync(&mut self, state: &S, changed_files: BTreeSet<PathBuf>) {
+        for file_path in changed_files {
+            if file_path.is_file() {
+                if let Err(err) = self.reload_file(state, &file_path) {
+                    log::error!(
+                        "cannot reload static mock file '{}', keeping the previously loaded mocks: {}",
+                        file_path.to_string_lossy(),
+                        err
+                    );
+                }
+                continue;
+            }
+
+            let mock_ids = match self.files.get(&file_path) {
+                Some(file) => &file.mock_ids,
+                None => continue,
+            };
+
+            match state.replace_static_mocks(mock_ids, Vec::new()) {
+                Ok(_) => {
+                    log::info!(
+                        "Unloaded removed static mock file '{}'",
+                        file_path.to_string_lossy()
+                    );
+                    self.files.remove(&file_path);
+                }
+                Err(err) => log::error!(
+                    "cannot unload static mock file '{}': {}",
+                    file_path.to_string_lossy(),
+                    err
+                ),
+            }
+        }
+    }
+
+    fn reload_file(&mut self, state: &S, file_path: &Path) -> Result<(), Error> {
+        let content = read_file(file_path).map_err(FileReadError)?;
+
+        let previous = self.files.get(file_path);
+        if previous.map_or(false, |file| file.content == content) {
+            return Ok(());
+        }
+
+        let previous_ids = previous.map_or(Vec::new(), |file| file.mock_ids.clone());
+        let result = parse_mock_file(&content).and_then(|definitions| {
+            // A file is usually empty for a moment while it is being rewritten.
+            if definitions.is_empty() {
+                return Err(DeserializationError(
+                    "file does not contain any mock definitions".to_string(),
+                ));
+            }
+            Ok(state.replace_static_mocks(&previous_ids, definitions)?)
+        });
+
+        // The content is remembered even if it is invalid, so that an error is only reported
+        // once per change.
+        let file = self
+            .files
+            .entry(file_path.to_path_buf())
+            .or_insert_with(|| LoadedMockFile {
+                content: Vec::new(),
+                mock_ids: Vec::new(),
+            });
+        file.content = content;
+        file.mock_ids = result?;
+
+        log::info!(
+            "Reloaded static mock file '{}'",
+            file_path.to_string_lossy()
+        );
+
+        Ok(())
+    }
+}
+
+fn list_mock_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let entries = read_dir(dir).map_err(|err| FileReadError(err.to_string()))?;
+
+    let mut file_paths = Vec::new();
+    for entry in entries {
+        let file_path = entry.map_err(|err| FileReadError(err.to_string()))?.path();
+        if file_path.is_file() && is_mock_file(&file_path) {
+            file_paths.push(file_path);
+        }
+    }
+
+    file_paths.sort();
+    Ok(file_paths)
+}
+
+fn parse_mock_file(content: &[u8]) -> Result<Vec<MockDefinition>, Error> {
+    let content = std::str::from_utf8(content).map_err(|err| FileReadError(err.to_string()))?;
+
+    deserialize_mock_defs_from_yaml(content)?
+        .into_iter()
+        .map(|definition| Ok(definition.try_into()?))
+        .collect()
+}
+
 pub fn deserialize_mock_defs_from_yaml(
     yaml_content: &str,
 ) -> Result<Vec<StaticMockDefinition>, Error> {
@@ -93,6 +337,16 @@ pub fn deserialize_mock_defs_from_yaml(
     Ok(definitions)
 }
 
+/// Deserializes a single mock definition from a YAML document. Unlike
+/// [`deserialize_mock_defs_from_yaml`], the document is deserialized in one pass, so that errors
+/// caused by invalid values still point to the line and column in the YAML document.
+pub fn deserialize_mock_def_from_yaml(yaml_content: &str) -> Result<MockDefinition, Error> {
+    let definition: StaticMockDefinition =
+        serde_yaml::from_str(yaml_content).map_err(|err| DeserializationError(err.to_string()))?;
+
+    Ok(definition.try_into()?)
+}
+
 pub fn serialize_mock_defs_to_yaml(mocks: &Vec<MockDefinition>) -> Result<Bytes, Error> {
     let mut buffer = BytesMut::new();
 
@@ -110,3 +364,256 @@ pub fn serialize_mock_defs_to_yaml(mocks: &Vec<MockDefinition>) -> Result<Bytes,
 
     Ok(buffer.freeze())
 }
+
+const DEFAULT_REDACTION_PLACEHOLDER: &str = "***";
+
+/// Removes recorded interactions whose responses are excluded by the recording rule, either
+/// because the response body is too large or because of its content type. Each skipped
+/// interaction is logged, since the saved recording is incomplete on purpose.
+pub fn skip_excluded_mock_definitions(
+    mocks: &[MockDefinition],
+    config: &RecordingRuleConfig,
+) -> Vec<MockDefinition> {
+    mocks
+        .iter()
+        .filter(|mock| match skip_reason(mock, config) {
+            None => true,
+            Some(reason) => {
+                log::info!(
+                    "skipping recorded interaction {} {}: {}",
+                    mock.request.method.as_deref().unwrap_or("<any method>"),
+                    mock.request.path.as_deref().unwrap_or("<any path>"),
+                    reason
+                );
+                false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+fn skip_reason(mock: &MockDefinition, config: &RecordingRuleConfig) -> Option<String> {
+    let response = &mock.response;
+
+    if let Some(limit) = config.skip_response_larger_than {
+        let size = response.body.as_ref().map_or(0, |body| body.0.len());
+        if size > limit {
+            return Some(format!(
+                "response body has {} bytes, which exceeds the limit of {} bytes",
+                size, limit
+            ));
+        }
+    }
+
+    let content_type = response
+        .headers
+        .iter()
+        .flatten()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.to_lowercase());
+
+    if let Some(content_type) = content_type {
+        if let Some(skipped) = config
+            .skip_content_types
+            .iter()
+            .find(|skipped| content_type.contains(skipped.as_str()))
+        {
+            return Some(format!(
+                "response content type '{}' matches skipped content type '{}'",
+                content_type, skipped
+            ));
+        }
+    }
+
+    None
+}
+
+/// Removes sensitive values from recorded mock definitions according to the redaction
+/// settings of the recording rule.
+///
+/// Response values are replaced by the configured placeholder. Request values are not replaced,
+/// because a mock that expects the placeholder would never match a real request again. Instead,
+/// redacted request headers and query parameters are only required to be present, and redacted
+/// JSON body fields are removed from a (then partial) JSON body requirement.
+pub fn redact_mock_definitions(
+    mocks: &[MockDefinition],
+    config: &RecordingRuleConfig,
+) -> Result<Vec<MockDefinition>, Error> {
+    let json_paths = config
+        .redact_json_paths
+        .iter()
+        .map(|expr| parse_json_path(expr))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(RedactionError)?;
+
+    let placeholder = config
+        .redaction_placeholder
+        .as_deref()
+        .unwrap_or(DEFAULT_REDACTION_PLACEHOLDER);
+
+    mocks
+        .iter()
+        .map(|mock| redact_mock_definition(mock.clone(), config, &json_paths, placeholder))
+        .collect()
+}
+
+fn redact_mock_definition(
+    mut mock: MockDefinition,
+    config: &RecordingRuleConfig,
+    json_paths: &[Vec<JsonPathSegment>],
+    placeholder: &str,
+) -> Result<MockDefinition, Error> {
+    let is_redacted_header = |name: &str| {
+        config
+            .redact_headers
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+    };
+    let is_redacted_query_param = |name: &str| config.redact_query_params.iter().any(|p| p == name);
+
+    // ************************************************************************************
+    // Request
+    let request = &mut mock.request;
+
+    let (redacted, kept) = partition_pairs(request.header.take(), is_redacted_header);
+    request.header = kept;
+    extend_names(&mut request.header_exists, redacted);
+
+    let (redacted, kept) = partition_pairs(request.query_param.take(), is_redacted_query_param);
+    request.query_param = kept;
+    extend_names(&mut request.query_param_exists, redacted);
+
+    if let Some(mut json) = parse_json_body(&request.body) {
+        if redact_json_paths(&mut json, json_paths, None) > 0 {
+            request.body = None;
+            request
+                .json_body_includes
+                .get_or_insert_with(Vec::new)
+                .push(json);
+        }
+    }
+
+    // ************************************************************************************
+    // Response
+    let response = &mut mock.response;
+
+    for (name, value) in response.headers.iter_mut().flatten() {
+        if is_redacted_header(name) {
+            *value = placeholder.to_string();
+        }
+    }
+
+    if let Some(mut json) = parse_json_body(&response.body) {
+        if redact_json_paths(&mut json, json_paths, Some(placeholder)) > 0 {
+            let body = serde_json::to_vec(&json).map_err(|err| RedactionError(err.to_string()))?;
+            response.body = Some(HttpMockBytes::from(Bytes::from(body)));
+
+            // The recorded content length does not fit the redacted body anymore.
+            if let Some(headers) = response.headers.as_mut() {
+                headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-length"));
+            }
+        }
+    }
+
+    Ok(mock)
+}
+
+fn partition_pairs<F: Fn(&str) -> bool>(
+    pairs: Option<Vec<(String, String)>>,
+    is_redacted: F,
+) -> (Vec<String>, Option<Vec<(String, String)>>) {
+    let pairs = match pairs {
+        None => return (Vec::new(), None),
+        Some(pairs) => pairs,
+    };
+
+    let (redacted, kept): (Vec<_>, Vec<_>) =
+        pairs.into_iter().partition(|(name, _)| is_redacted(name));
+
+    let redacted = redacted.into_iter().map(|(name, _)| name).collect();
+    (redacted, if kept.is_empty() { None } else { Some(kept) })
+}
+
+fn extend_names(names: &mut Option<Vec<String>>, redacted: Vec<String>) {
+    if redacted.is_empty() {
+        return;
+    }
+
+    let names = names.get_or_insert_with(Vec::new);
+    for name in redacted {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+}
+
+fn parse_json_body(body: &Option<HttpMockBytes>) -> Option<Value> {
+    body.as_ref()
+        .and_then(|body| serde_json::from_slice(body.as_ref()).ok())
+}
+
+/// Redacts all values that are addressed by the provided JSON paths. If a placeholder is
+/// provided, values are replaced by it, otherwise they are removed. Returns the number of
+/// redacted values.
+fn redact_json_paths(
+    json: &mut Value,
+    paths: &[Vec<JsonPathSegment>],
+    placeholder: Option<&str>,
+) -> usize {
+    paths
+        .iter()
+        .map(|path| redact_json_path(json, path, placeholder))
+        .sum()
+}
+
+fn redact_json_path(
+    value: &mut Value,
+    path: &[JsonPathSegment],
+    placeholder: Option<&str>,
+) -> usize {
+    let (segment, rest) = match path.split_first() {
+        None => return 0,
+        Some(v) => v,
+    };
+
+    if !rest.is_empty() {
+        return children_mut(value, segment)
+            .into_iter()
+            .map(|child| redact_json_path(child, rest, placeholder))
+            .sum();
+    }
+
+    if let Some(placeholder) = placeholder {
+        let children = children_mut(value, segment);
+        let count = children.len();
+        for child in children {
+            *child = Value::String(placeholder.to_string());
+        }
+        return count;
+    }
+
+    match (segment, value) {
+        (JsonPathSegment::Key(key), Value::Object(map)) => map.remove(key).map_or(0, |_| 1),
+        (JsonPathSegment::Index(idx), Value::Array(arr)) if *idx < arr.len() => {
+            arr.remove(*idx);
+            1
+        }
+        (JsonPathSegment::Wildcard, Value::Array(arr)) => arr.drain(..).count(),
+        (JsonPathSegment::Wildcard, Value::Object(map)) => {
+            let count = map.len();
+            map.clear();
+            count
+        }
+        _ => 0,
+    }
+}
+
+fn children_mut<'a>(value: &'a mut Value, segment: &JsonPathSegment) -> Vec<&'a mut Value> {
+    match (segment, value) {
+        (JsonPathSegment::Key(key), Value::Object(map)) => map.get_mut(key).into_iter().collect(),
+        (JsonPathSegment::Index(idx), Value::Array(arr)) => arr.get_mut(*idx).into_iter().collect(),
+        (JsonPathSegment::Wildcard, Value::Array(arr)) => arr.iter_mut().collect(),
+        (JsonPathSegment::Wildcard, Value::Object(map)) => map.values_mut().collect(),
+        _ => Vec::new(),
+    }
+}
@@ -1,15 +1,25 @@
 use futures_util::{stream::StreamExt, FutureExt};
-use http::{Request, StatusCode};
-use http_body_util::{combinators::BoxBody, BodyExt, Empty, Full};
+use http::{
+    header::{HeaderValue, CONNECTION, DATE},
+    Request, StatusCode, Version,
+};
+use http_body_util::{combinators::BoxBody, BodyExt, Empty, Full, LengthLimitError, Limited};
 use hyper::body::{Bytes, Incoming};
 use std::{
     future::{pending, Future},
-    net::SocketAddr,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
 };
 
-use hyper_util::server::conn::auto::Builder as ServerBuilder;
+use hyper_util::server::{
+    conn::auto::Builder as ServerBuilder,
+    graceful::{GracefulShutdown, Watcher},
+};
 
 use crate::server;
 use hyper::{http, service::service_fn, upgrade::on as upgrade_on, Method, Response};
@@ -21,17 +31,21 @@ use tokio::{
     task::spawn,
 };
 
+use crate::common::{data::Fault, runtime};
 use crate::server::{
-    handler::Handler,
+    access_log::{AccessLogEntry, AccessLogFormat},
+    handler::{Error::FaultInjected, Handler, MatchedMock, OmittedHeaders, ResponseBody},
+    header_case::HeaderCaseRecorder,
     server::Error::{
-        BufferError, LocalSocketAddrError, PublishSocketAddrError, RouterError, SocketBindError,
+        BufferError, LocalSocketAddrError, PublishSocketAddrError, RequestBodyTooLarge,
+        RouterError, SocketBindError,
     },
 };
 
 use std::io;
 
 #[cfg(feature = "https")]
-use rustls::ServerConfig;
+use rustls::{server::danger::ClientCertVerifier, ServerConfig};
 #[cfg(feature = "https")]
 use tokio_rustls::TlsAcceptor;
 
@@ -49,6 +63,8 @@ pub enum Error {
     ResponseConstructionError(http::Error),
     #[error("buffering error: {0}")]
     BufferError(hyper::Error),
+    #[error("request body exceeds the limit of {0} bytes")]
+    RequestBodyTooLarge(usize),
     #[error("HTTP error: {0}")]
     HTTPError(#[from] http::Error),
     #[error("cannot process request: {0}")]
@@ -70,12 +86,22 @@ pub enum Error {
 #[cfg(feature = "https")]
 pub struct MockServerHttpsConfig {
     pub cert_resolver_factory: Arc<dyn CertificateResolverFactory + Send + Sync>,
+    /// Requests client certificates during the TLS handshake if set (mutual TLS).
+    pub client_cert_verifier: Option<Arc<dyn ClientCertVerifier>>,
 }
 
 pub struct MockServerConfig {
     pub static_port: Option<u16>,
     pub expose: bool,
+    /// The IP address to bind to. Takes precedence over `expose` if set.
+    pub bind_address: Option<IpAddr>,
     pub print_access_log: bool,
+    pub access_log_format: AccessLogFormat,
+    pub max_request_body_size: Option<usize>,
+    pub keep_alive: bool,
+    pub idle_timeout: Option<Duration>,
+    pub shutdown_timeout: Option<Duration>,
+    pub worker_threads: Option<usize>,
     #[cfg(feature = "https")]
     pub https: MockServerHttpsConfig,
 }
@@ -87,6 +113,7 @@ where
 {
     handler: Box<H>,
     config: MockServerConfig,
+    background_tasks: Mutex<Vec<Pin<Box<dyn Future<Output = ()> + Send>>>>,
 }
 
 impl<H> MockServer<H>
@@ -102,7 +129,24 @@ where
     /// # Returns
     /// A `Result` containing the new `MockServer` instance or an `Error` if creation fails.
     pub fn new(handler: Box<H>, config: MockServerConfig) -> Result<Self, Error> {
-        Ok(MockServer { handler, config })
+        Ok(MockServer {
+            handler,
+            config,
+            background_tasks: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Adds a task that runs on the runtime of the server while the server is running. The task
+    /// is aborted when the server shuts down.
+    pub(crate) fn with_background_task<F>(mut self, task: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.background_tasks
+            .get_mut()
+            .unwrap()
+            .push(Box::pin(task));
+        self
     }
 
     /// Starts the mock server asynchronously.
@@ -112,6 +156,10 @@ where
 
     /// Starts the mock server asynchronously with support for handling external shutdown signals.
     ///
+    /// When the shutdown future resolves, the server stops accepting new connections. If a
+    /// shutdown timeout is configured, it then waits up to that long for in-flight requests
+    /// to be answered before it returns.
+    ///
     /// # Parameters
     /// - `socket_addr_sender`: An optional `Sender` to send the server's socket address once it's bound.
     /// - `shutdown`: A future that resolves when the server should shut down.
@@ -124,19 +172,9 @@ where
     where
         F: Future<Output = ()>,
     {
-        let host = if self.config.expose {
-            "0.0.0.0"
-        } else {
-            "127.0.0.1"
-        };
-        let addr: SocketAddr =
-            format!("{}:{}", host, self.config.static_port.unwrap_or(0)).parse()?;
-        let listener = TcpListener::bind(addr)
-            .await
-            .map_err(|e| SocketBindError(addr, e))?;
+        let (listener, addr) = self.bind().await?;
 
         if let Some(sender) = socket_addr_sender {
-            let addr = listener.local_addr().map_err(|e| LocalSocketAddrError(e))?;
             sender
                 .send(addr)
                 .map_err(|addr| PublishSocketAddrError(addr))?;
@@ -148,12 +186,70 @@ where
         self.run_accept_loop(listener, shutdown).await
     }
 
+    /// Starts the mock server on a multi-threaded Tokio runtime that is owned by the server and
+    /// blocks the current thread until the server has shut down. The runtime uses the number
+    /// of worker threads that is configured for the server, or Tokio's default if none is set.
+    ///
+    /// # Parameters
+    /// - `socket_addr_sender`: An optional `Sender` to send the server's socket address once it's bound.
+    /// - `shutdown`: A future that resolves when the server should shut down.
+    ///
+    pub fn run_with_signals<F>(
+        self,
+        socket_addr_sender: Option<Sender<SocketAddr>>,
+        shutdown: F,
+    ) -> Result<(), Error>
+    where
+        F: Future<Output = ()>,
+    {
+        if self.config.worker_threads == Some(0) {
+            return Err(ConfigurationError(
+                "the number of worker threads must be larger than 0".to_string(),
+            ));
+        }
+
+        let runtime = runtime::new_server_runtime(self.config.worker_threads).map_err(IOError)?;
+        runtime.block_on(self.start_with_signals(socket_addr_sender, shutdown))
+    }
+
+    /// Returns the number of worker threads configured for the runtime of the server, if any.
+    pub(crate) fn worker_threads(&self) -> Option<usize> {
+        self.config.worker_threads
+    }
+
+    /// Binds the TCP listener and returns it together with the address it is bound to. When
+    /// no static port is configured, the returned address contains the port that was assigned
+    /// by the operating system, so it can be published before the first connection is accepted.
+    async fn bind(&self) -> Result<(TcpListener, SocketAddr), Error> {
+        let host = match self.config.bind_address {
+            Some(address) => address,
+            None if self.config.expose => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            None => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        };
+        let addr = SocketAddr::new(host, self.config.static_port.unwrap_or(0));
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| SocketBindError(addr, e))?;
+        let bound_addr = listener.local_addr().map_err(|e| LocalSocketAddrError(e))?;
+
+        Ok((listener, bound_addr))
+    }
+
     pub async fn run_accept_loop<F>(self, listener: TcpListener, shutdown: F) -> Result<(), Error>
     where
         F: Future<Output = ()>,
     {
+        let background_tasks: Vec<_> = self
+            .background_tasks
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(spawn)
+            .collect();
+
         let shutdown = shutdown.shared();
         let server = Arc::new(self);
+        let graceful = GracefulShutdown::new();
 
         loop {
             tokio::select! {
@@ -161,8 +257,9 @@ where
                     match accepted {
                         Ok((tcp_stream, remote_address)) => {
                             let server = server.clone();
+                            let watcher = graceful.watcher();
                             spawn(async move {
-                               if let Err(err) = server.handle_tcp_stream(tcp_stream, remote_address).await {
+                               if let Err(err) = server.handle_tcp_stream(tcp_stream, remote_address, watcher).await {
                                     log::error!("{:?}", err);
                                 }
                             });
@@ -178,13 +275,46 @@ where
             }
         }
 
+        drop(listener);
+        for task in background_tasks {
+            task.abort();
+        }
+        server.drain(graceful).await;
+
         Ok(())
     }
 
+    /// Waits for in-flight requests to be answered after the server stopped accepting new
+    /// connections. Idle connections are closed right away. Connections that are still busy
+    /// when the shutdown timeout elapses are cut off once the server returns.
+    async fn drain(&self, graceful: GracefulShutdown) {
+        let timeout = match self.config.shutdown_timeout {
+            Some(timeout) => timeout,
+            None => return,
+        };
+
+        log::debug!(
+            "waiting up to {:?} for {} connection(s) to finish",
+            timeout,
+            graceful.count()
+        );
+
+        if tokio::time::timeout(timeout, graceful.shutdown())
+            .await
+            .is_err()
+        {
+            log::warn!(
+                "shutdown timeout of {:?} elapsed before all in-flight requests were answered",
+                timeout
+            );
+        }
+    }
+
     async fn service(
         self: Arc<Self>,
         req: Request<Incoming>,
-    ) -> Result<Response<BoxBody<Bytes, hyper::Error>>, Error> {
+        connection_reset: Arc<AtomicBool>,
+    ) -> Result<Response<ResponseBody>, Error> {
         log::trace!("New HTTP request received: {}", req.uri());
 
         if req.method() == Method::CONNECT {
@@ -192,15 +322,23 @@ where
             // return handle_connect(req).await;
         }
 
-        let req = match buffer_request(req).await {
+        let req = match buffer_request(req, self.config.max_request_body_size).await {
             Ok(req) => req,
-            Err(err) => {
-                return error_response(StatusCode::INTERNAL_SERVER_ERROR, BufferError(err));
+            Err(err @ RequestBodyTooLarge(_)) => {
+                return error_response(StatusCode::PAYLOAD_TOO_LARGE, err);
             }
+            Err(err) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, err),
         };
 
         match self.handler.handle(req).await {
-            Ok(response) => to_service_response(response),
+            Ok(response) => Ok(response),
+            // Returning an error to hyper makes it close the connection without a response.
+            Err(FaultInjected(fault)) => {
+                if fault == Fault::ResetConnection {
+                    connection_reset.store(true, Ordering::SeqCst);
+                }
+                Err(RouterError(FaultInjected(fault)))
+            }
             Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, RouterError(err)),
         }
     }
@@ -209,9 +347,12 @@ where
         self: Arc<Self>,
         tcp_stream: TcpStream,
         remote_address: SocketAddr,
+        watcher: Watcher,
     ) -> Result<(), Error> {
         log::trace!("new TCP connection incoming");
 
+        let connection_reset = Arc::new(AtomicBool::new(false));
+
         #[cfg(feature = "https")]
         {
             let mut peek_buffer = TcpStreamPeekBuffer::new(&tcp_stream);
@@ -221,9 +362,13 @@ where
                 let tcp_address = tcp_stream.local_addr().map_err(|err| IOError(err))?;
 
                 let cert_resolver = self.config.https.cert_resolver_factory.build(tcp_address);
-                let mut server_config = ServerConfig::builder()
-                    .with_no_client_auth()
-                    .with_cert_resolver(cert_resolver);
+                let server_config_builder = match &self.config.https.client_cert_verifier {
+                    Some(verifier) => {
+                        ServerConfig::builder().with_client_cert_verifier(verifier.clone())
+                    }
+                    None => ServerConfig::builder().with_no_client_auth(),
+                };
+                let mut server_config = server_config_builder.with_cert_resolver(cert_resolver);
 
                 #[cfg(feature = "http2")]
                 {
@@ -236,11 +381,32 @@ where
                 }
 
                 let tls_acceptor = TlsAcceptor::from(Arc::new(server_config));
+                let tcp_stream = ResettableTcpStream::new(tcp_stream, connection_reset.clone());
                 let tls_stream = tls_acceptor.accept(tcp_stream).await.map_err(|e| {
                     TlsError(format!("Could not accept TLS from TCP stream: {:?}", e))
                 })?;
 
-                return serve_connection(self.clone(), tls_stream, "https").await;
+                // The client certificate is only captured if the server requested it, so that
+                // matchers can tell apart "no certificate presented" from "mTLS not configured".
+                let client_cert = self.config.https.client_cert_verifier.as_ref().map(|_| {
+                    let common_name = tls_stream
+                        .get_ref()
+                        .1
+                        .peer_certificates()
+                        .and_then(|certs| certs.first())
+                        .and_then(subject_common_name);
+                    ClientCertificate { common_name }
+                });
+
+                let metadata = RequestMetadata::new("https").with_client_cert(client_cert);
+                return serve_connection(
+                    self.clone(),
+                    tls_stream,
+                    metadata,
+                    connection_reset,
+                    watcher,
+                )
+                .await;
             }
 
             if log::max_level() >= log::LevelFilter::Trace {
@@ -255,14 +421,25 @@ where
 
         log::trace!("TCP connection is not TLS encrypted");
 
-        return serve_connection(self.clone(), tcp_stream, "http").await;
+        let tcp_stream = ResettableTcpStream::new(tcp_stream, connection_reset.clone());
+        let metadata = RequestMetadata::new("http");
+        return serve_connection(
+            self.clone(),
+            tcp_stream,
+            metadata,
+            connection_reset,
+            watcher,
+        )
+        .await;
     }
 }
 
 async fn serve_connection<H, S>(
     server: Arc<MockServer<H>>,
     stream: S,
-    scheme: &'static str,
+    metadata: RequestMetadata,
+    connection_reset: Arc<AtomicBool>,
+    watcher: Watcher,
 ) -> Result<(), Error>
 where
     H: Handler + Send + Sync + 'static,
@@ -270,26 +447,131 @@ where
 {
     let mut server_builder = ServerBuilder::new(TokioExecutor::new());
 
-    server_builder.http1().preserve_header_case(true);
+    let keep_alive = server.config.keep_alive;
+    // The Date header is added to responses below instead of by hyper, so that mocks can omit it.
+    server_builder
+        .http1()
+        .preserve_header_case(true)
+        .auto_date_header(false)
+        .keep_alive(keep_alive);
+
+    // Hyper only starts the header read timer while it waits for the next request, so it
+    // closes connections that stay idle between requests for longer than the timeout.
+    if let Some(idle_timeout) = server.config.idle_timeout {
+        server_builder
+            .http1()
+            .timer(TokioTimer::new())
+            .header_read_timeout(idle_timeout);
+    }
 
-    server_builder.http2();
+    server_builder.http2().auto_date_header(false);
     //.enable_connect_protocol();
 
-    server_builder
-        .serve_connection_with_upgrades(
-            TokioIo::new(stream),
-            service_fn(|mut req| {
-                req.extensions_mut().insert(RequestMetadata::new(scheme));
-                server.clone().service(req)
-            }),
-        )
-        .await
-        .map_err(|err| ServerConnectionError(err))
+    let stream = HeaderCaseRecorder::new(stream);
+    let request_heads = stream.request_heads();
+
+    let connection = server_builder.serve_connection_with_upgrades(
+        TokioIo::new(stream),
+        service_fn(|mut req| {
+            // Hyper only calls the service once it has read the request head, so the
+            // head of this request has already been recorded.
+            let raw_head = match req.version() {
+                Version::HTTP_10 | Version::HTTP_11 => request_heads.lock().unwrap().pop_front(),
+                _ => None,
+            };
+            req.extensions_mut().insert(
+                metadata
+                    .clone()
+                    .with_original_header_names(raw_head.as_ref().map(|head| head.header_names()))
+                    .with_raw_head(raw_head),
+            );
+            let server = server.clone();
+            let connection_reset = connection_reset.clone();
+            async move {
+                let start = Instant::now();
+                let request_line = server
+                    .config
+                    .print_access_log
+                    .then(|| (req.method().to_string(), req.uri().path().to_string()));
+
+                let result = server.clone().service(req, connection_reset).await;
+
+                if let Some((method, path)) = request_line {
+                    access_log_entry(method, path, &result, start.elapsed())
+                        .print(server.config.access_log_format);
+                }
+
+                let mut res = result?;
+                add_date_header(&mut res);
+                // Hyper closes the connection after the response when keep-alive is
+                // disabled, but it does not tell the client unless this header is set.
+                if !keep_alive {
+                    res.headers_mut()
+                        .insert(CONNECTION, HeaderValue::from_static("close"));
+                }
+                Ok::<_, Error>(res)
+            }
+        }),
+    );
+
+    // The watcher lets the connection finish its current request and then close, once the
+    // server shuts down.
+    let result = watcher.watch(connection).await;
+
+    match result {
+        Err(err) if is_idle_timeout(err.as_ref()) => {
+            log::debug!("closing idle connection");
+            Ok(())
+        }
+        result => result.map_err(|err| ServerConnectionError(err)),
+    }
 }
 
-async fn handle_connect(
-    req: Request<Incoming>,
-) -> Result<Response<BoxBody<Bytes, hyper::Error>>, Error> {
+fn access_log_entry(
+    method: String,
+    path: String,
+    result: &Result<Response<ResponseBody>, Error>,
+    duration: Duration,
+) -> AccessLogEntry {
+    let mut entry = AccessLogEntry::new(method, path, duration);
+
+    match result {
+        Ok(res) => {
+            entry.status = Some(res.status().as_u16());
+            entry.mock_id = res.extensions().get::<MatchedMock>().map(|mock| mock.0);
+            entry.matched = entry.mock_id.is_some();
+        }
+        // Faults are only injected by mocks, so the request must have matched one.
+        Err(err) => entry.matched = matches!(err, RouterError(FaultInjected(_))),
+    }
+
+    entry
+}
+
+/// Adds a `Date` header with the current time to a response, unless the response already
+/// contains one or the mock that served it omits the header.
+fn add_date_header(res: &mut Response<ResponseBody>) {
+    let omitted = res
+        .extensions()
+        .get::<OmittedHeaders>()
+        .map_or(false, |omitted| omitted.contains(&DATE));
+
+    if omitted || res.headers().contains_key(DATE) {
+        return;
+    }
+
+    let date = httpdate::fmt_http_date(SystemTime::now());
+    if let Ok(value) = HeaderValue::from_str(&date) {
+        res.headers_mut().insert(DATE, value);
+    }
+}
+
+fn is_idle_timeout(err: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    err.downcast_ref::<hyper::Error>()
+        .map_or(false, |err| err.is_timeout())
+}
+
+async fn handle_connect(req: Request<Incoming>) -> Result<Response<ResponseBody>, Error> {
     if let Some(addr) = host_addr(req.uri()) {
         spawn(async move {
             match upgrade_on(req).await {
@@ -316,23 +598,55 @@ async fn handle_connect(
     }
 }
 
-async fn buffer_request(req: Request<Incoming>) -> Result<Request<Bytes>, hyper::Error> {
+/// Reads the whole request body into memory. If a limit is set, requests that announce a larger
+/// body in their `Content-Length` header are rejected before any of the body is read. Bodies
+/// without a `Content-Length` header (e.g., chunked bodies) are read until the limit is exceeded.
+async fn buffer_request(
+    req: Request<Incoming>,
+    max_body_size: Option<usize>,
+) -> Result<Request<Bytes>, Error> {
     let (parts, body) = req.into_parts();
-    let body = body.collect().await?.to_bytes();
-    return Ok(Request::from_parts(parts, body));
+
+    let limit = match max_body_size {
+        None => {
+            let body = body.collect().await.map_err(BufferError)?.to_bytes();
+            return Ok(Request::from_parts(parts, body));
+        }
+        Some(limit) => limit,
+    };
+
+    let announced_size = parts
+        .headers
+        .get(http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if announced_size.map_or(false, |size| size > limit as u64) {
+        return Err(RequestBodyTooLarge(limit));
+    }
+
+    let body = match Limited::new(body, limit).collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(err) if err.is::<LengthLimitError>() => return Err(RequestBodyTooLarge(limit)),
+        Err(err) => match err.downcast::<hyper::Error>() {
+            Ok(err) => return Err(BufferError(*err)),
+            Err(err) => return Err(ServerConnectionError(err)),
+        },
+    };
+
+    Ok(Request::from_parts(parts, body))
 }
 
 fn host_addr(uri: &http::Uri) -> Option<String> {
     uri.authority().and_then(|auth| Some(auth.to_string()))
 }
 
-fn full<T: Into<Bytes>>(chunk: T) -> BoxBody<Bytes, hyper::Error> {
+fn full<T: Into<Bytes>>(chunk: T) -> ResponseBody {
     Full::new(chunk.into())
         .map_err(|never| match never {})
         .boxed()
 }
 
-fn empty() -> BoxBody<Bytes, hyper::Error> {
+fn empty() -> ResponseBody {
     Empty::<Bytes>::new()
         .map_err(|never| match never {})
         .boxed()
@@ -356,40 +670,89 @@ async fn tunnel(upgraded: hyper::upgrade::Upgraded, addr: String) -> std::io::Re
     Ok(())
 }
 
-fn error_response(
-    code: StatusCode,
-    err: Error,
-) -> Result<Response<BoxBody<Bytes, hyper::Error>>, Error> {
+fn error_response(code: StatusCode, err: Error) -> Result<Response<ResponseBody>, Error> {
     log::error!("failed to process request: {}", err.to_string());
     Ok(Response::builder()
         .status(code)
         .body(full(err.to_string()))?)
 }
 
-fn to_service_response(
-    response: Response<Bytes>,
-) -> Result<Response<BoxBody<Bytes, hyper::Error>>, Error> {
-    let (parts, body) = response.into_parts();
-    Ok(Response::from_parts(parts, full(body)))
-}
-
-use crate::server::Error::{IOError, ServerConnectionError, ServerError, TlsError, Unknown};
+use crate::server::Error::{
+    ConfigurationError, IOError, ServerConnectionError, ServerError, TlsError, Unknown,
+};
 use async_trait::async_trait;
 use bytes::BytesMut;
-use hyper_util::rt::TokioExecutor;
+use hyper_util::rt::{TokioExecutor, TokioTimer};
 use std::{
     pin::Pin,
     task::{Context, Poll},
 };
 
 #[cfg(feature = "https")]
-use crate::server::tls::{CertificateResolverFactory, TcpStreamPeekBuffer};
+use crate::{
+    common::data::ClientCertificate,
+    server::tls::{subject_common_name, CertificateResolverFactory, TcpStreamPeekBuffer},
+};
 
 use crate::server::RequestMetadata;
 #[cfg(feature = "https")]
 use tls_detect::is_encrypted;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
+/// A TCP stream that sends a TCP RST instead of a regular FIN when it is closed after a
+/// connection reset has been requested (e.g., by fault injection).
+struct ResettableTcpStream {
+    stream: TcpStream,
+    reset: Arc<AtomicBool>,
+}
+
+impl ResettableTcpStream {
+    fn new(stream: TcpStream, reset: Arc<AtomicBool>) -> Self {
+        Self { stream, reset }
+    }
+}
+
+impl Drop for ResettableTcpStream {
+    fn drop(&mut self) {
+        if self.reset.load(Ordering::SeqCst) {
+            // A linger timeout of zero makes the OS abort the connection with a TCP RST
+            // when the socket is closed. This does not block the thread on drop.
+            #[allow(deprecated)]
+            if let Err(err) = self.stream.set_linger(Some(Duration::ZERO)) {
+                log::warn!("cannot reset TCP connection: {}", err);
+            }
+        }
+    }
+}
+
+impl AsyncRead for ResettableTcpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ResettableTcpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>> {
+        Pin::new(&mut self.get_mut().stream).poll_shutdown(cx)
+    }
+}
+
 struct RecordingStream<S> {
     stream: S,
     read_bytes: BytesMut,    // Buffer to store bytes read from the stream
@@ -3,30 +3,54 @@ use crate::{
         data,
         data::{
             ActiveForwardingRule, ActiveMock, ActiveProxyRule, ActiveRecording, ClosestMatch,
-            Mismatch, MockDefinition, MockServerHttpResponse, RequestRequirements,
+            JsonSchema, Mismatch, MockDefinition, MockServerHttpResponse, MockStats,
+            ProtobufFieldExpectation, RequestRequirements, StateSnapshot, UnmatchedRequest,
         },
+        util::{is_valid_reason_phrase, merge_json_body},
     },
     prelude::HttpMockRequest,
     server::{
+        barrier::RequestBarrier,
+        handler::OMITTABLE_HEADERS,
         matchers,
-        matchers::{all, Matcher},
-        state::Error::{BodyMethodInvalid, DataConversionError, StaticMockError, ValidationError},
+        matchers::{all, comparison::split_authority, Matcher, NamedMatchers},
+        state::Error::{
+            BodyMethodInvalid, DataConversionError, PlaybackOrderError, StaticMockError,
+            ValidationError,
+        },
     },
 };
 
+use crate::api::output::create_mismatch_output;
 #[cfg(feature = "record")]
-use crate::server::persistence::{deserialize_mock_defs_from_yaml, serialize_mock_defs_to_yaml};
+use crate::server::persistence::{
+    deserialize_mock_defs_from_yaml, redact_mock_definitions, serialize_mock_defs_to_yaml,
+    skip_excluded_mock_definitions,
+};
+
+#[cfg(feature = "protobuf")]
+use crate::server::matchers::protobuf;
 
+#[cfg(feature = "record")]
+use crate::common::data::PlaybackConfig;
 use crate::common::data::{ForwardingRuleConfig, ProxyRuleConfig, RecordingRuleConfig};
 use bytes::Bytes;
+use serde_json::Value;
 use std::{
-    collections::BTreeMap,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, VecDeque},
     convert::{TryFrom, TryInto},
+    future::Future,
+    str::FromStr,
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 use thiserror::Error;
 
+/// How often expired mocks are removed from the state. Expired mocks never serve a request,
+/// even if they have not been removed yet.
+const EXPIRED_MOCK_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("The mock is static and cannot be deleted")]
@@ -37,54 +61,266 @@ pub enum Error {
     DataConversionError(String),
     #[error("validation error: {0}")]
     ValidationError(String),
+    #[error("unexpected request during ordered playback: {0}")]
+    PlaybackOrderError(String),
     #[error("unknown error")]
     Unknown,
 }
 
+/// The number of requests that a mock server keeps in its request history by default.
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Decides which mock serves a request if the request matches more than one mock
+/// (see [`HttpMockServerBuilder::match_strategy`](crate::server::HttpMockServerBuilder::match_strategy)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchStrategy {
+    /// The mock that was created first serves the request. Mock priorities are ignored.
+    FirstDefined,
+    /// The mock with the most constraints serves the request. Every expectation counts as one
+    /// constraint, e.g. `when.path("/a").query_param("b", "c")` has two. Alternatives added
+    /// with `any_of` count as one constraint per call. Ties are broken by priority first and
+    /// then by creation order.
+    MostSpecific,
+    /// The mock with the highest priority (see [`When::priority`](crate::When::priority))
+    /// serves the request. If multiple matching mocks share the highest priority, the mock
+    /// that was created first serves the request. This is the default.
+    HighestPriority,
+}
+
+impl Default for MatchStrategy {
+    fn default() -> Self {
+        MatchStrategy::HighestPriority
+    }
+}
+
+impl FromStr for MatchStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().replace('_', "-").as_str() {
+            "first-defined" => Ok(MatchStrategy::FirstDefined),
+            "most-specific" => Ok(MatchStrategy::MostSpecific),
+            "highest-priority" => Ok(MatchStrategy::HighestPriority),
+            _ => Err(format!(
+                "unknown match strategy '{}': expected 'first-defined', 'most-specific' or 'highest-priority'",
+                value
+            )),
+        }
+    }
+}
+
 pub struct MockServerState {
+    /// The maximum number of requests to keep in the request history. A limit of `0` disables
+    /// the request history.
     history_limit: usize,
+    /// The number of requests that were removed from the history because the history limit
+    /// was reached since the history was last cleared.
+    history_dropped: usize,
     next_mock_id: usize,
     next_forwarding_rule_id: usize,
     next_proxy_rule_id: usize,
     next_recording_id: usize,
     pub mocks: BTreeMap<usize, ActiveMock>,
-    pub history: Vec<Arc<HttpMockRequest>>,
+    pub history: Vec<HistoryEntry>,
     pub matchers: Vec<Box<dyn Matcher + Sync + Send>>,
     pub forwarding_rules: BTreeMap<usize, ActiveForwardingRule>,
     pub proxy_rules: BTreeMap<usize, ActiveProxyRule>,
     pub recordings: BTreeMap<usize, ActiveRecording>,
+    /// The IDs of all mocks that were loaded from a recording for ordered playback.
+    ordered_playback_mocks: BTreeSet<usize>,
+    /// The IDs of the ordered playback mocks that have not been served yet, in recorded order.
+    /// Only the first of them is eligible to serve the next request.
+    ordered_playback_queue: VecDeque<usize>,
+    /// The barriers that hold back requests to mocks that wait for concurrent requests.
+    request_barriers: BTreeMap<usize, Arc<RequestBarrier>>,
+    /// Default response headers set at runtime. If set, they replace the default response
+    /// headers that the server was configured with until the server is reset.
+    pub default_response_headers: Option<Vec<(String, String)>>,
+    /// Request counters that are exposed by the metrics endpoint. They are not cleared when
+    /// the server is reset.
+    pub metrics: Metrics,
+}
+
+/// A request in the request history, together with the mock that served it.
+pub struct HistoryEntry {
+    pub request: Arc<HttpMockRequest>,
+    /// The ID of the mock that served the request, or `None` if no mock matched it.
+    pub matched_mock_id: Option<usize>,
+}
+
+/// Request counters that are maintained by the mock server.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    /// The number of requests that were not addressed to the management API.
+    pub total_requests: u64,
+    /// The number of requests that were served by a mock.
+    pub matched_requests: u64,
+    /// The number of requests that no mock matched.
+    pub unmatched_requests: u64,
+    /// The number of responses per HTTP status code.
+    pub responses_by_status: BTreeMap<u16, u64>,
+    /// The number of currently active mocks.
+    pub active_mocks: usize,
+}
+
+impl Metrics {
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn to_prometheus_text(&self) -> String {
+        let mut text = String::new();
+
+        let counters = [
+            (
+                "httpmock_requests_total",
+                "Total number of requests received by the mock server.",
+                self.total_requests,
+            ),
+            (
+                "httpmock_matched_requests_total",
+                "Number of requests that were served by a mock.",
+                self.matched_requests,
+            ),
+            (
+                "httpmock_unmatched_requests_total",
+                "Number of requests that did not match any mock.",
+                self.unmatched_requests,
+            ),
+        ];
+
+        for (name, help, value) in counters {
+            text.push_str(&format!("# HELP {} {}\n", name, help));
+            text.push_str(&format!("# TYPE {} counter\n", name));
+            text.push_str(&format!("{} {}\n", name, value));
+        }
+
+        text.push_str("# HELP httpmock_responses_total Number of responses by HTTP status code.\n");
+        text.push_str("# TYPE httpmock_responses_total counter\n");
+        for (status, count) in &self.responses_by_status {
+            text.push_str(&format!(
+                "httpmock_responses_total{{status=\"{}\"}} {}\n",
+                status, count
+            ));
+        }
+
+        text.push_str("# HELP httpmock_active_mocks Number of currently active mocks.\n");
+        text.push_str("# TYPE httpmock_active_mocks gauge\n");
+        text.push_str(&format!("httpmock_active_mocks {}\n", self.active_mocks));
+
+        text
+    }
 }
 
 impl MockServerState {
-    pub fn new(history_limit: usize) -> Self {
+    pub(crate) fn new(history_limit: usize, named_matchers: &NamedMatchers) -> Self {
         MockServerState {
             mocks: BTreeMap::new(),
             forwarding_rules: BTreeMap::new(),
             proxy_rules: BTreeMap::new(),
             recordings: BTreeMap::new(),
+            ordered_playback_mocks: BTreeSet::new(),
+            ordered_playback_queue: VecDeque::new(),
+            request_barriers: BTreeMap::new(),
             history_limit,
+            history_dropped: 0,
             history: Vec::new(),
             next_mock_id: 0,
             next_forwarding_rule_id: 0,
             next_proxy_rule_id: 0,
             next_recording_id: 0,
-            matchers: matchers::all(),
+            matchers: matchers::all(named_matchers),
+            default_response_headers: None,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Adds a request to the request history. If the history limit is reached, the oldest
+    /// request is removed from the history.
+    fn record_request(&mut self, request: Arc<HttpMockRequest>, matched_mock_id: Option<usize>) {
+        if self.history_limit == 0 {
+            return;
+        }
+
+        if self.history.len() >= self.history_limit {
+            self.history.remove(0);
+            self.history_dropped += 1;
         }
+
+        self.history.push(HistoryEntry {
+            request,
+            matched_mock_id,
+        });
+    }
+
+    /// Removes all mocks that expired at the given point in time and returns their number.
+    fn remove_expired_mocks(&mut self, now: SystemTime) -> usize {
+        let expired: Vec<usize> = self
+            .mocks
+            .values()
+            .filter(|mock| mock.is_expired_at(now))
+            .map(|mock| mock.id)
+            .collect();
+
+        if expired.is_empty() {
+            return 0;
+        }
+
+        log::debug!("Removing expired mocks with ids={:?}", expired);
+
+        for id in &expired {
+            self.mocks.remove(id);
+        }
+        self.prune_deleted_mocks();
+
+        expired.len()
+    }
+
+    /// Forgets ordered playback interactions and request barriers whose mocks have been deleted.
+    fn prune_deleted_mocks(&mut self) {
+        let mocks = &self.mocks;
+        self.request_barriers.retain(|id, _| mocks.contains_key(id));
+        self.ordered_playback_mocks
+            .retain(|id| mocks.contains_key(id));
+        self.ordered_playback_queue
+            .retain(|id| mocks.contains_key(id));
     }
 }
 
 pub(crate) trait StateManager {
     fn reset(&self);
     fn add_mock(&self, definition: MockDefinition, is_static: bool) -> Result<ActiveMock, Error>;
+    /// Adds all mocks or none of them if any definition is invalid. The returned mocks are in
+    /// the same order as the definitions.
+    fn add_mocks(
+        &self,
+        definitions: Vec<MockDefinition>,
+        is_static: bool,
+    ) -> Result<Vec<ActiveMock>, Error>;
     fn read_mock(&self, id: usize) -> Result<Option<ActiveMock>, Error>;
+    fn mock_stats(&self, id: usize) -> Result<Option<MockStats>, Error>;
     fn delete_mock(&self, id: usize) -> Result<bool, Error>;
+    fn delete_mocks(&self, ids: &[usize]) -> Result<(), Error>;
     fn delete_all_mocks(&self);
 
+    /// Returns all mocks that are currently registered, ordered by their ID.
+    fn read_mocks(&self) -> Vec<ActiveMock>;
+    /// Returns all mocks that carry the given tag, ordered by their ID.
+    fn read_mocks_by_tag(&self, tag: &str) -> Vec<ActiveMock>;
+    /// Deletes all mocks that carry the given tag, except for static mocks, and returns the
+    /// number of deleted mocks.
+    fn delete_mocks_by_tag(&self, tag: &str) -> usize;
+    /// Deletes all mocks whose time to live has passed and returns the number of deleted mocks.
+    fn delete_expired_mocks(&self) -> usize;
+
     fn delete_history(&self);
+    fn received_requests(&self, mock_id: usize) -> Result<Option<Vec<HttpMockRequest>>, Error>;
 
     fn verify(&self, requirements: &RequestRequirements) -> Result<Option<ClosestMatch>, Error>;
+    fn unmatched_requests(&self) -> Vec<UnmatchedRequest>;
+
+    fn serve_mock(&self, req: &HttpMockRequest) -> Result<Option<ActiveMock>, Error>;
 
-    fn serve_mock(&self, req: &HttpMockRequest) -> Result<Option<MockServerHttpResponse>, Error>;
+    /// Returns the barrier that holds back requests to a mock until enough of them are
+    /// waiting concurrently.
+    fn request_barrier(&self, mock_id: usize) -> Arc<RequestBarrier>;
 
     fn create_forwarding_rule(&self, config: ForwardingRuleConfig) -> ActiveForwardingRule;
     fn delete_forwarding_rule(&self, id: usize) -> Option<ActiveForwardingRule>;
@@ -98,11 +334,33 @@ pub(crate) trait StateManager {
     fn delete_recording(&self, recording_id: usize) -> Option<ActiveRecording>;
     fn delete_all_recordings(&self);
 
+    fn set_default_response_headers(&self, headers: Vec<(String, String)>);
+    fn default_response_headers(&self) -> Option<Vec<(String, String)>>;
+
+    fn export_state(&self) -> Result<StateSnapshot, Error>;
+    fn import_state(&self, snapshot: StateSnapshot) -> Result<Vec<usize>, Error>;
+
+    fn record_request_metrics(&self, status: Option<u16>);
+    fn metrics(&self) -> Metrics;
+
     #[cfg(feature = "record")]
     fn export_recording(&self, id: usize) -> Result<Option<Bytes>, Error>;
 
     #[cfg(feature = "record")]
-    fn load_mocks_from_recording(&self, recording_file_content: &str) -> Result<Vec<usize>, Error>;
+    fn load_mocks_from_recording(
+        &self,
+        recording_file_content: &str,
+        config: PlaybackConfig,
+    ) -> Result<Vec<usize>, Error>;
+
+    /// Replaces a set of static mocks with new ones in a single step. If any of the new mock
+    /// definitions is invalid, the state remains unchanged.
+    #[cfg(feature = "record")]
+    fn replace_static_mocks(
+        &self,
+        ids: &[usize],
+        definitions: Vec<MockDefinition>,
+    ) -> Result<Vec<usize>, Error>;
 
     fn find_forward_rule<'a>(
         &'a self,
@@ -125,19 +383,51 @@ pub(crate) trait StateManager {
 
 pub struct HttpMockStateManager {
     state: Mutex<MockServerState>,
+    named_matchers: NamedMatchers,
+    match_strategy: MatchStrategy,
 }
 
 impl HttpMockStateManager {
     pub fn new(history_limit: usize) -> Self {
+        Self::with_named_matchers(history_limit, NamedMatchers::default())
+    }
+
+    pub(crate) fn with_named_matchers(history_limit: usize, named_matchers: NamedMatchers) -> Self {
         Self {
-            state: Mutex::new(MockServerState::new(history_limit)),
+            state: Mutex::new(MockServerState::new(history_limit, &named_matchers)),
+            named_matchers,
+            match_strategy: MatchStrategy::default(),
         }
     }
+
+    pub(crate) fn with_match_strategy(mut self, match_strategy: MatchStrategy) -> Self {
+        self.match_strategy = match_strategy;
+        self
+    }
+
+    /// Makes sure that all named matchers that the requirements refer to are registered.
+    fn validate_named_matchers(&self, req: &RequestRequirements) -> Result<(), Error> {
+        for call in req.matcher_named.iter().flatten() {
+            if !self.named_matchers.contains_key(&call.name) {
+                return Err(ValidationError(format!(
+                    "unknown matcher '{}': matchers must be registered on the server using HttpMockServerBuilder::register_matcher",
+                    call.name
+                )));
+            }
+        }
+
+        let nested = req.all_of.iter().flatten();
+        for nested_req in nested.chain(req.any_of.iter().flatten().flatten()) {
+            self.validate_named_matchers(nested_req)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for HttpMockStateManager {
     fn default() -> Self {
-        HttpMockStateManager::new(usize::MAX)
+        HttpMockStateManager::new(DEFAULT_HISTORY_LIMIT)
     }
 }
 
@@ -148,15 +438,20 @@ impl StateManager for HttpMockStateManager {
         self.delete_all_forwarding_rules();
         self.delete_all_proxy_rules();
         self.delete_all_recordings();
+        self.state.lock().unwrap().default_response_headers = None;
     }
 
     fn add_mock(&self, definition: MockDefinition, is_static: bool) -> Result<ActiveMock, Error> {
         validate_request_requirements(&definition.request)?;
+        self.validate_named_matchers(&definition.request)?;
+        validate_response(&definition.response)?;
 
         let mut state = self.state.lock().unwrap();
 
         let id = state.next_mock_id;
-        let active_mock = ActiveMock::new(id, definition, 0, is_static);
+        let expires_at = expiry_time(&definition.request, SystemTime::now());
+        let mut active_mock = ActiveMock::new(id, definition, 0, is_static);
+        active_mock.expires_at = expires_at;
 
         log::debug!("Adding new mock with ID={}", id);
 
@@ -167,6 +462,43 @@ impl StateManager for HttpMockStateManager {
         Ok(active_mock)
     }
 
+    fn add_mocks(
+        &self,
+        definitions: Vec<MockDefinition>,
+        is_static: bool,
+    ) -> Result<Vec<ActiveMock>, Error> {
+        for definition in &definitions {
+            validate_request_requirements(&definition.request)?;
+            self.validate_named_matchers(&definition.request)?;
+            validate_response(&definition.response)?;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let now = SystemTime::now();
+
+        let active_mocks: Vec<ActiveMock> = definitions
+            .into_iter()
+            .map(|definition| {
+                let id = state.next_mock_id;
+                let expires_at = expiry_time(&definition.request, now);
+                let mut active_mock = ActiveMock::new(id, definition, 0, is_static);
+                active_mock.expires_at = expires_at;
+
+                state.mocks.insert(id, active_mock.clone());
+                state.next_mock_id += 1;
+
+                active_mock
+            })
+            .collect();
+
+        log::debug!(
+            "Added new mocks with IDs={:?}",
+            active_mocks.iter().map(|m| m.id).collect::<Vec<_>>()
+        );
+
+        Ok(active_mocks)
+    }
+
     fn read_mock(&self, id: usize) -> Result<Option<ActiveMock>, Error> {
         let mut state = self.state.lock().unwrap();
 
@@ -177,6 +509,11 @@ impl StateManager for HttpMockStateManager {
         }
     }
 
+    fn mock_stats(&self, id: usize) -> Result<Option<MockStats>, Error> {
+        let state = self.state.lock().unwrap();
+        Ok(state.mocks.get(&id).map(MockStats::from))
+    }
+
     fn delete_mock(&self, id: usize) -> Result<bool, Error> {
         let mut state = self.state.lock().unwrap();
 
@@ -188,7 +525,32 @@ impl StateManager for HttpMockStateManager {
 
         log::debug!("Deleting mock with id={}", id);
 
-        Ok(state.mocks.remove(&id).is_some())
+        let deleted = state.mocks.remove(&id).is_some();
+        state.prune_deleted_mocks();
+
+        Ok(deleted)
+    }
+
+    fn delete_mocks(&self, ids: &[usize]) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+
+        // Validate all IDs first so that either all or none of the mocks are deleted.
+        if ids
+            .iter()
+            .filter_map(|id| state.mocks.get(id))
+            .any(|m| m.is_static)
+        {
+            return Err(StaticMockError);
+        }
+
+        log::debug!("Deleting mocks with ids={:?}", ids);
+
+        for id in ids {
+            state.mocks.remove(id);
+        }
+        state.prune_deleted_mocks();
+
+        Ok(())
     }
 
     fn delete_all_mocks(&self) {
@@ -204,22 +566,95 @@ impl StateManager for HttpMockStateManager {
         ids.iter().for_each(|k| {
             state.mocks.remove(k);
         });
+        state.prune_deleted_mocks();
 
         log::trace!("Deleted all mocks");
     }
 
+    fn read_mocks(&self) -> Vec<ActiveMock> {
+        let state = self.state.lock().unwrap();
+
+        state.mocks.values().cloned().collect()
+    }
+
+    fn read_mocks_by_tag(&self, tag: &str) -> Vec<ActiveMock> {
+        let state = self.state.lock().unwrap();
+
+        state
+            .mocks
+            .values()
+            .filter(|mock| has_tag(mock, tag))
+            .cloned()
+            .collect()
+    }
+
+    fn delete_mocks_by_tag(&self, tag: &str) -> usize {
+        let mut state = self.state.lock().unwrap();
+
+        let ids: Vec<usize> = state
+            .mocks
+            .values()
+            .filter(|mock| !mock.is_static && has_tag(mock, tag))
+            .map(|mock| mock.id)
+            .collect();
+
+        log::debug!("Deleting mocks with tag '{}' (ids={:?})", tag, ids);
+
+        for id in &ids {
+            state.mocks.remove(id);
+        }
+        state.prune_deleted_mocks();
+
+        ids.len()
+    }
+
+    fn delete_expired_mocks(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        state.remove_expired_mocks(SystemTime::now())
+    }
+
     fn delete_history(&self) {
         let mut state = self.state.lock().unwrap();
         state.history.clear();
+        state.history_dropped = 0;
         log::trace!("Deleted request history");
     }
 
+    fn received_requests(&self, mock_id: usize) -> Result<Option<Vec<HttpMockRequest>>, Error> {
+        let state = self.state.lock().unwrap();
+
+        let mock = match state.mocks.get(&mock_id) {
+            Some(mock) => mock,
+            None => return Ok(None),
+        };
+
+        let requests = state
+            .history
+            .iter()
+            .filter(|entry| {
+                request_matches(&state.matchers, &entry.request, &mock.definition.request)
+            })
+            .map(|entry| entry.request.as_ref().clone())
+            .collect();
+
+        Ok(Some(requests))
+    }
+
     fn verify(&self, requirements: &RequestRequirements) -> Result<Option<ClosestMatch>, Error> {
-        let mut state = self.state.lock().unwrap();
+        let state = self.state.lock().unwrap();
+
+        if state.history_dropped > 0 {
+            log::warn!(
+                "{} request(s) were removed from the request history because the history limit of {} was reached. Verification only considers the remaining requests.",
+                state.history_dropped,
+                state.history_limit
+            );
+        }
 
         let non_matching_requests: Vec<&Arc<HttpMockRequest>> = state
             .history
             .iter()
+            .map(|entry| &entry.request)
             .filter(|req| !request_matches(&state.matchers, req, requirements))
             .collect();
 
@@ -242,26 +677,81 @@ impl StateManager for HttpMockStateManager {
         }))
     }
 
-    fn serve_mock(&self, req: &HttpMockRequest) -> Result<Option<MockServerHttpResponse>, Error> {
+    fn unmatched_requests(&self) -> Vec<UnmatchedRequest> {
+        let state = self.state.lock().unwrap();
+
+        state
+            .history
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matched_mock_id.is_none())
+            .map(|(idx, entry)| {
+                let closest_mock = state.mocks.values().min_by_key(|mock| {
+                    get_request_distance(&entry.request, &mock.definition.request, &state.matchers)
+                });
+
+                UnmatchedRequest {
+                    request: entry.request.as_ref().clone(),
+                    request_index: idx,
+                    closest_mock_id: closest_mock.map(|mock| mock.id),
+                    mismatches: closest_mock.map_or_else(Vec::new, |mock| {
+                        get_request_mismatches(
+                            &entry.request,
+                            &mock.definition.request,
+                            &state.matchers,
+                        )
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    fn serve_mock(&self, req: &HttpMockRequest) -> Result<Option<ActiveMock>, Error> {
         let mut state = self.state.lock().unwrap();
 
+        // The sweeper removes expired mocks only periodically, so they are removed here as well
+        // to make sure they never serve a request.
+        state.remove_expired_mocks(SystemTime::now());
+
         let req = Arc::new(req.clone());
 
-        if state.history.len() > 100 {
-            // TODO: Make max history configurable
-            state.history.remove(0);
-        }
-        state.history.push(req.clone());
+        // Of the mocks loaded for ordered playback, only the next interaction in recorded order
+        // may serve the request.
+        let next_playback_mock_id = state.ordered_playback_queue.front().copied();
 
-        let result = state
+        // Candidates are ordered by the match strategy. The sort is stable, so mocks that rank
+        // equally are evaluated in the order of creation. Mocks that already served as many
+        // requests as they are allowed to are skipped, so that other mocks can take over.
+        let mut candidates: Vec<&ActiveMock> = state
             .mocks
             .values()
-            .find(|&mock| request_matches(&state.matchers, &req, &mock.definition.request));
+            .filter(|mock| {
+                !state.ordered_playback_mocks.contains(&mock.id)
+                    || Some(mock.id) == next_playback_mock_id
+            })
+            .filter(|mock| {
+                mock.definition
+                    .request
+                    .times
+                    .map_or(true, |times| mock.call_counter < times)
+            })
+            .collect();
+        let priority_of = |mock: &ActiveMock| mock.definition.request.priority.unwrap_or(0);
+        match self.match_strategy {
+            MatchStrategy::FirstDefined => {}
+            MatchStrategy::MostSpecific => candidates.sort_by_cached_key(|mock| {
+                let specificity = constraint_count(&state.matchers, &mock.definition.request);
+                Reverse((specificity, priority_of(mock)))
+            }),
+            MatchStrategy::HighestPriority => {
+                candidates.sort_by_key(|mock| Reverse(priority_of(mock)))
+            }
+        }
 
-        let found_mock_id = match result {
-            Some(mock) => Some(mock.id),
-            None => None,
-        };
+        let found_mock_id = candidates
+            .iter()
+            .find(|mock| request_matches(&state.matchers, &req, &mock.definition.request))
+            .map(|mock| mock.id);
 
         if let Some(found_id) = found_mock_id {
             log::debug!(
@@ -270,17 +760,55 @@ impl StateManager for HttpMockStateManager {
                 req
             );
 
+            // Priorities are ignored if the first defined mock always wins.
+            let shadowed_mock_ids = match self.match_strategy {
+                MatchStrategy::FirstDefined => Vec::new(),
+                _ => find_shadowed_mock_ids(&state, &candidates, found_id, &req),
+            };
+            for id in shadowed_mock_ids {
+                *state
+                    .mocks
+                    .get_mut(&id)
+                    .unwrap()
+                    .shadowed_by
+                    .entry(found_id)
+                    .or_insert(0) += 1;
+            }
+
+            state.record_request(req.clone(), Some(found_id));
+            state.metrics.matched_requests += 1;
+
+            if Some(found_id) == next_playback_mock_id {
+                state.ordered_playback_queue.pop_front();
+            }
+
             let mock = state.mocks.get_mut(&found_id).unwrap();
             mock.call_counter += 1;
+            mock.last_hit_at = Some(SystemTime::now());
+
+            let mut served_mock = mock.clone();
+            served_mock.definition.response =
+                response_for_call(&mock.definition.response, mock.call_counter);
 
-            return Ok(Some(mock.definition.response.clone()));
+            return Ok(Some(served_mock));
         }
 
+        state.record_request(req.clone(), None);
+        state.metrics.unmatched_requests += 1;
+
         log::debug!(
             "Could not match any mock to the following request: {:#?}",
             req
         );
 
+        if !state.ordered_playback_mocks.is_empty() {
+            return Err(PlaybackOrderError(describe_unexpected_playback_request(
+                &state,
+                &req,
+                next_playback_mock_id,
+            )));
+        }
+
         Ok(None)
     }
 
@@ -402,13 +930,97 @@ impl StateManager for HttpMockStateManager {
         log::debug!("Deleted all recorders");
     }
 
+    fn set_default_response_headers(&self, headers: Vec<(String, String)>) {
+        let mut state = self.state.lock().unwrap();
+        state.default_response_headers = Some(headers);
+
+        log::debug!("Replaced default response headers");
+    }
+
+    fn default_response_headers(&self) -> Option<Vec<(String, String)>> {
+        let state = self.state.lock().unwrap();
+        state.default_response_headers.clone()
+    }
+
+    fn export_state(&self) -> Result<StateSnapshot, Error> {
+        let state = self.state.lock().unwrap();
+
+        // Static mocks are loaded from the static mock directory on startup,
+        // so they are not part of the snapshot.
+        let mut mocks = Vec::new();
+        for mock in state.mocks.values().filter(|m| !m.is_static) {
+            if uses_custom_matchers(&mock.definition.request) {
+                return Err(ValidationError(format!(
+                    "mock with ID {} uses custom matcher functions, which cannot be persisted",
+                    mock.id
+                )));
+            }
+
+            mocks.push(mock.definition.clone());
+        }
+
+        Ok(StateSnapshot::new(mocks))
+    }
+
+    fn import_state(&self, snapshot: StateSnapshot) -> Result<Vec<usize>, Error> {
+        // Validate all mocks first so that the current mocks are only replaced
+        // if the whole snapshot can be loaded.
+        for definition in &snapshot.mocks {
+            validate_request_requirements(&definition.request)?;
+            self.validate_named_matchers(&definition.request)?;
+            validate_response(&definition.response)?;
+        }
+
+        // Swap the mocks while holding the lock, so that no request is served
+        // from a partially imported state.
+        let mut state = self.state.lock().unwrap();
+
+        state.mocks.retain(|_, mock| mock.is_static);
+        state.prune_deleted_mocks();
+
+        let now = SystemTime::now();
+        let mut mock_ids = Vec::with_capacity(snapshot.mocks.len());
+        for definition in snapshot.mocks {
+            let id = state.next_mock_id;
+            let mut active_mock = ActiveMock::new(id, definition, 0, false);
+            active_mock.expires_at = expiry_time(&active_mock.definition.request, now);
+            state.mocks.insert(id, active_mock);
+            state.next_mock_id += 1;
+            mock_ids.push(id);
+        }
+
+        log::debug!("Imported {} mocks from state snapshot", mock_ids.len());
+
+        Ok(mock_ids)
+    }
+
+    fn record_request_metrics(&self, status: Option<u16>) {
+        let mut state = self.state.lock().unwrap();
+        state.metrics.total_requests += 1;
+
+        if let Some(status) = status {
+            *state.metrics.responses_by_status.entry(status).or_insert(0) += 1;
+        }
+    }
+
+    fn metrics(&self) -> Metrics {
+        let state = self.state.lock().unwrap();
+
+        let mut metrics = state.metrics.clone();
+        metrics.active_mocks = state.mocks.len();
+        metrics
+    }
+
     #[cfg(feature = "record")]
     fn export_recording(&self, id: usize) -> Result<Option<Bytes>, Error> {
         let mut state = self.state.lock().unwrap();
 
         if let Some(rec) = state.recordings.get(&id) {
+            let mocks = skip_excluded_mock_definitions(&rec.mocks, &rec.config);
+            let mocks = redact_mock_definitions(&mocks, &rec.config)
+                .map_err(|err| DataConversionError(err.to_string()))?;
             return Ok(Some(
-                serialize_mock_defs_to_yaml(&rec.mocks)
+                serialize_mock_defs_to_yaml(&mocks)
                     .map_err(|err| DataConversionError(err.to_string()))?,
             ));
         }
@@ -417,7 +1029,11 @@ impl StateManager for HttpMockStateManager {
     }
 
     #[cfg(feature = "record")]
-    fn load_mocks_from_recording(&self, recording_file_content: &str) -> Result<Vec<usize>, Error> {
+    fn load_mocks_from_recording(
+        &self,
+        recording_file_content: &str,
+        config: PlaybackConfig,
+    ) -> Result<Vec<usize>, Error> {
         let all_static_mock_defs = deserialize_mock_defs_from_yaml(recording_file_content)
             .map_err(|err| DataConversionError(err.to_string()))?;
 
@@ -430,17 +1046,71 @@ impl StateManager for HttpMockStateManager {
         let mut mock_ids = Vec::with_capacity(all_static_mock_defs.len());
 
         for static_mock_def in all_static_mock_defs {
-            let mock_def: MockDefinition = static_mock_def
+            let mut mock_def: MockDefinition = static_mock_def
                 .try_into()
                 .map_err(|err: data::Error| DataConversionError(err.to_string()))?;
 
+            // An explicitly recorded delay takes precedence over the observed duration.
+            if config.replay_with_timing && mock_def.response.delay.is_none() {
+                mock_def.response.delay = mock_def.response.duration_ms;
+            }
+
             let active_mock = self.add_mock(mock_def, false)?;
             mock_ids.push(active_mock.id);
         }
 
+        if config.ordered {
+            let mut state = self.state.lock().unwrap();
+            state.ordered_playback_mocks.extend(mock_ids.iter());
+            state.ordered_playback_queue.extend(mock_ids.iter());
+        }
+
         Ok(mock_ids)
     }
 
+    #[cfg(feature = "record")]
+    fn replace_static_mocks(
+        &self,
+        ids: &[usize],
+        definitions: Vec<MockDefinition>,
+    ) -> Result<Vec<usize>, Error> {
+        for definition in &definitions {
+            validate_request_requirements(&definition.request)?;
+            self.validate_named_matchers(&definition.request)?;
+            validate_response(&definition.response)?;
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        for id in ids {
+            state.mocks.remove(id);
+        }
+        state.prune_deleted_mocks();
+
+        let mut new_ids = Vec::with_capacity(definitions.len());
+        for definition in definitions {
+            let id = state.next_mock_id;
+            state
+                .mocks
+                .insert(id, ActiveMock::new(id, definition, 0, true));
+            state.next_mock_id += 1;
+            new_ids.push(id);
+        }
+
+        log::debug!("Replaced static mocks {:?} with {:?}", ids, new_ids);
+
+        Ok(new_ids)
+    }
+
+    fn request_barrier(&self, mock_id: usize) -> Arc<RequestBarrier> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .request_barriers
+            .entry(mock_id)
+            .or_insert_with(|| Arc::new(RequestBarrier::new()))
+            .clone()
+    }
+
     fn find_forward_rule<'a>(
         &'a self,
         req: &'a HttpMockRequest,
@@ -659,11 +1329,55 @@ fn build_mock_definition(
         json_body_excludes: None,
         form_urlencoded_tuple_not: None,
         is_false: None,
+        priority: None,
+        is_json: None,
+        is_valid_xml: None,
+        is_form_urlencoded: None,
+        json_schema: None,
+        body_size: None,
+        query_param_all: None,
+        query_param_includes_all: None,
+        any_of: None,
+        all_of: None,
+        matcher_named: None,
+        header_value_count: None,
+        path_glob: None,
+        client_cert_cn: None,
+        json_body_unordered: None,
+        path_template: None,
+        json_body_has_key: None,
+        json_body_missing_key: None,
+        strict_trailing_slash: None,
+        raw_query: None,
+        raw_query_includes: None,
+        raw_query_matches: None,
+        url: None,
+        url_matches: None,
+        json_body_includes_array: None,
+        method_in: None,
+        header_name_exact: None,
+        protobuf_field: None,
+        body_matches_any: None,
+        tags: None,
+        expires_in: None,
+        expires_at: None,
+        http_version: None,
+        body_decompressed_matches: None,
+        times: None,
+        has_body: None,
+        authority: None,
+        host_header: None,
+        total_header_count_min: None,
+        total_header_count_max: None,
+        cookie_count_min: None,
+        cookie_count_max: None,
+        raw_cookie_includes: None,
     };
 
     // ************************************************************************************
     // Response
     let mut response = response.clone();
+    response.duration_ms = Some(time_taken.as_millis() as u64);
 
     if config.record_response_delays {
         response.delay = Some(time_taken.as_millis() as u64)
@@ -672,6 +1386,16 @@ fn build_mock_definition(
     Ok(MockDefinition { request, response })
 }
 
+fn uses_custom_matchers(req: &RequestRequirements) -> bool {
+    let mut nested = req
+        .all_of
+        .iter()
+        .flatten()
+        .chain(req.any_of.iter().flatten().flatten());
+
+    req.is_true.is_some() || req.is_false.is_some() || nested.any(|r| uses_custom_matchers(r))
+}
+
 fn validate_request_requirements(req: &RequestRequirements) -> Result<(), Error> {
     const NON_BODY_METHODS: &[&str] = &["GET", "HEAD"];
 
@@ -682,9 +1406,352 @@ fn validate_request_requirements(req: &RequestRequirements) -> Result<(), Error>
             }
         }
     }
+
+    // Builds the regexes of the templates, so that they are stored with the mock.
+    for template in req.path_template.iter().flatten() {
+        template.regex().map_err(ValidationError)?;
+    }
+
+    if let Some(authority) = &req.authority {
+        split_authority(authority).map_err(ValidationError)?;
+    }
+
+    let key_pointers = req.json_body_has_key.iter().flatten();
+    let array_pointers = req
+        .json_body_includes_array
+        .iter()
+        .flatten()
+        .map(|a| &a.pointer);
+    for pointer in key_pointers
+        .chain(req.json_body_missing_key.iter().flatten())
+        .chain(array_pointers)
+    {
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(ValidationError(format!(
+                "invalid JSON pointer '{}': a JSON pointer must start with '/'",
+                pointer
+            )));
+        }
+    }
+
+    for expectation in req.protobuf_field.iter().flatten() {
+        validate_protobuf_field(expectation)?;
+    }
+
+    for schema in req.json_schema.iter().flatten() {
+        validate_json_schema(schema)?;
+    }
+
+    #[cfg(not(feature = "compression"))]
+    if req.body_decompressed_matches.is_some() {
+        return Err(ValidationError(
+            "matching decompressed request bodies requires the `compression` feature".to_string(),
+        ));
+    }
+
+    // An empty group of alternatives can never be satisfied, so it is most likely a mistake.
+    let groups = req.any_of.iter().flatten();
+    if req.any_of.as_ref().map_or(false, Vec::is_empty) || groups.clone().any(Vec::is_empty) {
+        return Err(ValidationError(
+            "any_of requires at least one alternative condition".to_string(),
+        ));
+    }
+
+    if req.all_of.as_ref().map_or(false, Vec::is_empty) {
+        return Err(ValidationError(
+            "all_of requires at least one condition".to_string(),
+        ));
+    }
+
+    let nested = req.all_of.iter().flatten();
+    for nested_req in nested.chain(groups.flatten()) {
+        validate_request_requirements(nested_req)?;
+    }
+
     Ok(())
 }
 
+#[cfg(feature = "protobuf")]
+fn validate_protobuf_field(expectation: &ProtobufFieldExpectation) -> Result<(), Error> {
+    let descriptor = protobuf::message_descriptor(
+        &expectation.file_descriptor_set.0,
+        &expectation.message_name,
+    )
+    .map_err(ValidationError)?;
+
+    protobuf::resolve_field_path(&descriptor, &expectation.field_path)
+        .map(|_| ())
+        .map_err(ValidationError)
+}
+
+#[cfg(not(feature = "protobuf"))]
+fn validate_protobuf_field(_expectation: &ProtobufFieldExpectation) -> Result<(), Error> {
+    Err(ValidationError(
+        "matching protobuf request bodies requires the protobuf feature".to_string(),
+    ))
+}
+
+/// Compiles the schema, so that the validator is stored with the mock and reused for every
+/// request the mock is matched against.
+#[cfg(feature = "json-schema")]
+fn validate_json_schema(schema: &JsonSchema) -> Result<(), Error> {
+    schema.validator().map(|_| ()).map_err(ValidationError)
+}
+
+#[cfg(not(feature = "json-schema"))]
+fn validate_json_schema(_schema: &JsonSchema) -> Result<(), Error> {
+    Err(ValidationError(
+        "matching request bodies against a JSON schema requires the json-schema feature"
+            .to_string(),
+    ))
+}
+
+/// Selects the response for the given call of a mock (counting from 1). A response configured
+/// with `respond_after(n, ..)` replaces the regular response from call `n + 1` on. If several
+/// of them apply, the one with the highest threshold is used.
+fn response_for_call(response: &MockServerHttpResponse, call: usize) -> MockServerHttpResponse {
+    response
+        .respond_after
+        .iter()
+        .flatten()
+        .filter(|r| call > r.calls)
+        .max_by_key(|r| r.calls)
+        .map_or_else(|| response.clone(), |r| r.response.clone())
+}
+
+fn validate_response(res: &MockServerHttpResponse) -> Result<(), Error> {
+    for r in res.respond_after.iter().flatten() {
+        if r.response.respond_after.is_some() {
+            return Err(ValidationError(
+                "respond_after cannot be used within a respond_after response".to_string(),
+            ));
+        }
+        validate_response(&r.response)?;
+    }
+
+    let has_body = res.body.is_some() || res.body_file.is_some() || res.body_template.is_some();
+
+    if res.fault.is_some() && has_body {
+        return Err(ValidationError(
+            "a response fault cannot be combined with a response body".to_string(),
+        ));
+    }
+
+    // See RFC 9110, Sections 15.3.5 and 15.4.5.
+    if let Some(status @ (204 | 304)) = res.status {
+        if has_body || res.json_body_merge.is_some() {
+            return Err(ValidationError(format!(
+                "responses with status code {} must not have a body",
+                status
+            )));
+        }
+    }
+
+    if let Some(path) = &res.body_file {
+        match std::fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => {}
+            Ok(_) => {
+                return Err(ValidationError(format!(
+                    "cannot stream response body from {}: not a file",
+                    path
+                )))
+            }
+            Err(err) => {
+                return Err(ValidationError(format!(
+                    "cannot stream response body from file {}: {}",
+                    path, err
+                )))
+            }
+        }
+    }
+
+    if res.body_template.is_some() && (res.body.is_some() || res.body_file.is_some()) {
+        return Err(ValidationError(
+            "a response body template cannot be combined with another response body".to_string(),
+        ));
+    }
+
+    if let Some(target) = &res.proxy_to {
+        if !cfg!(feature = "proxy") {
+            return Err(ValidationError(
+                "proxying a mock response requires the proxy feature".to_string(),
+            ));
+        }
+
+        if has_body || res.fault.is_some() {
+            return Err(ValidationError(
+                "a proxied response cannot be combined with a response body or fault".to_string(),
+            ));
+        }
+
+        let uri = target.target_base_url.parse::<http::Uri>();
+        if !matches!(uri, Ok(uri) if uri.scheme().is_some() && uri.authority().is_some()) {
+            return Err(ValidationError(format!(
+                "invalid upstream URL '{}': expected an absolute URL such as http://localhost:8080",
+                target.target_base_url
+            )));
+        }
+    }
+
+    if let Some(reason) = &res.reason {
+        if !is_valid_reason_phrase(reason) {
+            return Err(ValidationError(format!(
+                "invalid reason phrase '{}': only tabs, spaces and visible ASCII characters are allowed",
+                reason
+            )));
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    if res.auto_compress == Some(true) {
+        return Err(ValidationError(
+            "automatic response compression requires the `compression` feature".to_string(),
+        ));
+    }
+
+    if res.wait_for_concurrent == Some(0) {
+        return Err(ValidationError(
+            "the number of concurrent requests to wait for must be greater than zero".to_string(),
+        ));
+    }
+
+    for name in res.omit_headers.iter().flatten() {
+        if !OMITTABLE_HEADERS
+            .iter()
+            .any(|header| header.as_str().eq_ignore_ascii_case(name))
+        {
+            return Err(ValidationError(format!(
+                "cannot omit response header '{}': only Content-Length, Date and Server can be omitted",
+                name
+            )));
+        }
+    }
+
+    if res.chunk_size == Some(0) {
+        return Err(ValidationError(
+            "the chunk size of a chunked response must be greater than zero".to_string(),
+        ));
+    }
+
+    for callback in res.after_respond.iter().flatten() {
+        if !cfg!(feature = "proxy") {
+            return Err(ValidationError(
+                "sending callback requests requires the proxy feature".to_string(),
+            ));
+        }
+
+        if res.proxy_to.is_some() {
+            return Err(ValidationError(
+                "a proxied response cannot send callback requests".to_string(),
+            ));
+        }
+
+        let url = callback.url.as_deref().unwrap_or_default();
+        let uri = url.parse::<http::Uri>();
+        if !matches!(uri, Ok(uri) if uri.scheme().is_some() && uri.authority().is_some()) {
+            return Err(ValidationError(format!(
+                "invalid callback URL '{}': expected an absolute URL such as http://localhost:8080/webhooks",
+                url
+            )));
+        }
+
+        if let Some(method) = &callback.method {
+            http::Method::from_bytes(method.as_bytes()).map_err(|_| {
+                ValidationError(format!("invalid callback request method '{}'", method))
+            })?;
+        }
+    }
+
+    if let Some(partials) = &res.json_body_merge {
+        if res.body_file.is_some() {
+            return Err(ValidationError(
+                "a JSON body merge cannot be combined with a response body file".to_string(),
+            ));
+        }
+
+        let body = res.body.as_ref().ok_or_else(|| {
+            ValidationError("a JSON body merge requires a JSON response body".to_string())
+        })?;
+
+        merge_json_body(body.as_ref(), partials)
+            .map_err(|err| ValidationError(format!("cannot merge JSON body: {}", err)))?;
+    }
+
+    Ok(())
+}
+
+/// Finds all mocks that would also have matched the request, but were not considered because
+/// the mock with ID `found_id` has a higher priority. If no priorities are in use, no mock can
+/// have a lower priority, so no additional matching is performed.
+fn find_shadowed_mock_ids(
+    state: &MockServerState,
+    candidates: &Vec<&ActiveMock>,
+    found_id: usize,
+    req: &HttpMockRequest,
+) -> Vec<usize> {
+    let priority_of = |mock: &ActiveMock| mock.definition.request.priority.unwrap_or(0);
+
+    let found_priority = priority_of(state.mocks.get(&found_id).unwrap());
+
+    candidates
+        .iter()
+        .filter(|mock| priority_of(mock) < found_priority)
+        .filter(|mock| request_matches(&state.matchers, req, &mock.definition.request))
+        .map(|mock| mock.id)
+        .collect()
+}
+
+/// Returns the point in time at which a mock that is created at `now` expires, if it has a
+/// time to live. If both a relative and an absolute expiry are set, the earlier one applies.
+fn expiry_time(requirements: &RequestRequirements, now: SystemTime) -> Option<SystemTime> {
+    let expires_in = requirements
+        .expires_in
+        .map(|millis| now + Duration::from_millis(millis));
+
+    match (expires_in, requirements.expires_at) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Periodically deletes expired mocks until the state is dropped. The returned future is run
+/// as a task on the runtime of the mock server while the server is running.
+pub(crate) fn sweep_expired_mocks<S>(state: &Arc<S>) -> impl Future<Output = ()> + Send + 'static
+where
+    S: StateManager + Send + Sync + 'static,
+{
+    let state = Arc::downgrade(state);
+    async move {
+        loop {
+            tokio::time::sleep(EXPIRED_MOCK_SWEEP_INTERVAL).await;
+
+            match state.upgrade() {
+                Some(state) => state.delete_expired_mocks(),
+                None => return,
+            };
+        }
+    }
+}
+
+fn has_tag(mock: &ActiveMock, tag: &str) -> bool {
+    mock.definition
+        .request
+        .tags
+        .as_ref()
+        .map_or(false, |tags| tags.iter().any(|t| t == tag))
+}
+
+/// Returns the number of constraints of a mock, which is used to find the most specific mock.
+fn constraint_count(
+    matchers: &[Box<dyn Matcher + Sync + Send>],
+    request_requirements: &RequestRequirements,
+) -> usize {
+    matchers
+        .iter()
+        .map(|matcher| matcher.constraint_count(request_requirements))
+        .sum()
+}
+
 fn request_matches(
     matchers: &Vec<Box<dyn Matcher + Sync + Send>>,
     req: &HttpMockRequest,
@@ -738,6 +1805,43 @@ fn get_min_distance_requests(request_distances: &BTreeMap<usize, usize>) -> Vec<
         .collect()
 }
 
+/// Explains why a request was rejected during ordered playback by comparing it with the next
+/// recorded interaction.
+fn describe_unexpected_playback_request(
+    state: &MockServerState,
+    req: &Arc<HttpMockRequest>,
+    next_playback_mock_id: Option<usize>,
+) -> String {
+    let total = state.ordered_playback_mocks.len();
+    let next_mock = match next_playback_mock_id.and_then(|id| state.mocks.get(&id)) {
+        Some(mock) => mock,
+        None => {
+            return format!(
+                "all {} recorded interactions have already been played back, but received {} {}",
+                total,
+                req.method(),
+                req.uri()
+            )
+        }
+    };
+
+    let position = total - state.ordered_playback_queue.len() + 1;
+    let mut message = format!(
+        "{} {} does not match the next recorded interaction (number {} of {}):\n",
+        req.method(),
+        req.uri(),
+        position,
+        total
+    );
+
+    let mismatches = get_request_mismatches(req, &next_mock.definition.request, &state.matchers);
+    for (idx, mismatch) in mismatches.iter().enumerate() {
+        message.push_str(&create_mismatch_output(idx, mismatch).0);
+    }
+
+    message
+}
+
 fn get_request_mismatches(
     req: &Arc<HttpMockRequest>,
     mock_rr: &RequestRequirements,
@@ -1,12 +1,15 @@
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 
-use crate::server::tls::Error::{CaCertificateError, GenerateCertificateError};
+use crate::server::tls::Error::{
+    CaCertificateError, ClientCaCertificateError, GenerateCertificateError,
+};
 use async_trait::async_trait;
 use rcgen::{Certificate, CertificateParams, KeyPair};
 use rustls::{
     crypto::ring::sign::any_supported_type,
-    server::{ClientHello, ResolvesServerCert},
+    server::{danger::ClientCertVerifier, ClientHello, ResolvesServerCert, WebPkiClientVerifier},
     sign::CertifiedKey,
+    RootCertStore,
 };
 use std::{
     collections::HashMap,
@@ -16,6 +19,7 @@ use std::{
     sync::{Arc, Mutex, RwLock},
 };
 use thiserror::Error;
+use x509_parser::prelude::{FromDer, X509Certificate};
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -23,6 +27,8 @@ pub enum Error {
     CaCertificateError(String),
     #[error("cannot generate certificate: {0}")]
     GenerateCertificateError(String),
+    #[error("client CA certificate error: {0}")]
+    ClientCaCertificateError(String),
 }
 
 pub trait CertificateResolverFactory {
@@ -241,6 +247,45 @@ impl ResolvesServerCert for GeneratingCertificateResolver {
     }
 }
 
+/// Builds a verifier that requests a client certificate during the TLS handshake and accepts
+/// certificates issued by the provided CA (PEM format). Clients that do not present a
+/// certificate are still accepted, so that the mock server can serve them as well. Such requests
+/// just don't match client certificate requirements.
+pub fn build_client_cert_verifier(ca_cert: &str) -> Result<Arc<dyn ClientCertVerifier>, Error> {
+    let mut roots = RootCertStore::empty();
+    for cert_result in rustls_pemfile::certs(&mut Cursor::new(ca_cert.as_bytes())) {
+        let cert = cert_result.map_err(|err| {
+            ClientCaCertificateError(format!("cannot parse client CA certificate: {:?}", err))
+        })?;
+        roots.add(cert).map_err(|err| {
+            ClientCaCertificateError(format!("cannot use client CA certificate: {:?}", err))
+        })?;
+    }
+
+    if roots.is_empty() {
+        return Err(ClientCaCertificateError(
+            "expected at least one certificate in PEM format".to_string(),
+        ));
+    }
+
+    WebPkiClientVerifier::builder(Arc::new(roots))
+        .allow_unauthenticated()
+        .build()
+        .map_err(|err| {
+            ClientCaCertificateError(format!(
+                "cannot build client certificate verifier: {:?}",
+                err
+            ))
+        })
+}
+
+/// Extracts the common name (CN) from the subject of a DER encoded certificate.
+pub fn subject_common_name(cert: &CertificateDer) -> Option<String> {
+    let (_, cert) = X509Certificate::from_der(cert.as_ref()).ok()?;
+    let common_name = cert.subject().iter_common_name().next()?;
+    common_name.as_str().ok().map(|cn| cn.to_string())
+}
+
 pub struct TcpStreamPeekBuffer<'a> {
     stream: &'a tokio::net::TcpStream,
     buffer: Vec<u8>,
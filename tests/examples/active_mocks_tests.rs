@@ -0,0 +1,61 @@
+use httpmock::prelude::*;
+use reqwest::blocking::get;
+
+#[test]
+fn active_mocks_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let users = server.mock(|when, then| {
+        when.path("/users");
+        then.status(200);
+    });
+
+    let mut orders = server.mock(|when, then| {
+        when.method(POST).path("/orders");
+        then.status(201);
+    });
+
+    // Act
+    get(server.url("/users")).unwrap();
+    get(server.url("/users")).unwrap();
+
+    // Assert
+    let mocks = server.active_mocks();
+    assert_eq!(mocks.len(), 2);
+
+    assert_eq!(mocks[0].id, users.id);
+    assert_eq!(mocks[0].call_counter, 2);
+    assert_eq!(mocks[0].definition.request.path.as_deref(), Some("/users"));
+
+    assert_eq!(mocks[1].id, orders.id);
+    assert_eq!(mocks[1].call_counter, 0);
+    assert_eq!(mocks[1].definition.response.status, Some(201));
+
+    // Deleted mocks are no longer listed
+    orders.delete();
+    let mocks = server.active_mocks();
+    assert_eq!(mocks.len(), 1);
+    assert_eq!(mocks[0].id, users.id);
+}
+
+#[tokio::test]
+async fn active_mocks_async_test() {
+    // Arrange
+    let server = MockServer::start_async().await;
+    assert!(server.active_mocks_async().await.is_empty());
+
+    server
+        .mock_async(|when, then| {
+            when.path("/users");
+            then.status(200);
+        })
+        .await;
+
+    // Act
+    let mocks = server.active_mocks_async().await;
+
+    // Assert
+    assert_eq!(mocks.len(), 1);
+    assert!(serde_json::to_string(&mocks).unwrap().contains("/users"));
+}
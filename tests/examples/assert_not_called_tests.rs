@@ -0,0 +1,85 @@
+use httpmock::prelude::*;
+use reqwest::blocking::{get, Client};
+use std::panic::AssertUnwindSafe;
+
+#[test]
+fn assert_not_called_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/fallback");
+        then.status(200);
+    });
+
+    // Act: Send a request that does not match the mock
+    get(server.url("/primary")).unwrap();
+
+    // Assert
+    mock.assert_not_called();
+}
+
+#[test]
+#[should_panic(expected = "Expected the mock not to be called, but 1 request(s) matched")]
+fn assert_not_called_fails_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/fallback");
+        then.status(200);
+    });
+
+    // Act
+    get(server.url("/fallback")).unwrap();
+
+    // Assert
+    mock.assert_not_called();
+}
+
+#[test]
+fn assert_not_called_reports_offending_requests_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/fallback");
+        then.status(200);
+    });
+
+    // Act
+    Client::new()
+        .post(server.url("/fallback?attempt=1"))
+        .header("x-origin", "cache-miss")
+        .body("payload")
+        .send()
+        .unwrap();
+
+    // Assert
+    let panic =
+        std::panic::catch_unwind(AssertUnwindSafe(|| mock.assert_not_called())).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("POST /fallback?attempt=1"));
+    assert!(message.contains("x-origin: cache-miss"));
+    assert!(message.contains("payload"));
+}
+
+#[tokio::test]
+async fn assert_not_called_async_test() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.path("/fallback");
+            then.status(200);
+        })
+        .await;
+
+    // Act
+    reqwest::get(server.url("/primary")).await.unwrap();
+
+    // Assert
+    mock.assert_not_called_async().await;
+}
@@ -22,6 +22,71 @@ fn binary_body_test() {
     assert_eq!(body_to_vec(&mut response), binary_content.to_vec());
 }
 
+#[test]
+fn body_bytes_test() {
+    // Arrange
+    let binary_content: Vec<u8> = (0..=255).collect();
+
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method("GET").path("/image");
+        then.status(200)
+            .header("content-type", "application/octet-stream")
+            .body_bytes(bytes::Bytes::from(binary_content.clone()));
+    });
+
+    // Act
+    let mut response = reqwest::blocking::get(server.url("/image")).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-length"], "256");
+    assert_eq!(body_to_vec(&mut response), binary_content);
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn body_bytes_record_and_playback_test() {
+    // Arrange
+    let binary_content = vec![0x89, 0x50, 0x4e, 0x47, 0x00, 0xff, 0xfe];
+
+    let target_server = MockServer::start();
+    target_server.mock(|when, then| {
+        when.path("/image");
+        then.status(200).body_bytes(binary_content.clone());
+    });
+
+    let recording_server = MockServer::start();
+    recording_server.forward_to(target_server.base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+    let recording = recording_server.record(|rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    reqwest::blocking::get(recording_server.url("/image")).unwrap();
+
+    // Act: the body is stored base64 encoded and decoded again during playback
+    let target_path = recording.save("body_bytes_scenario").unwrap();
+    let content = std::fs::read_to_string(&target_path).unwrap();
+
+    let playback_server = MockServer::start();
+    playback_server.playback(target_path);
+
+    let mut response = reqwest::blocking::get(playback_server.url("/image")).unwrap();
+
+    // Assert
+    assert!(content.contains("body_base64"));
+    assert_eq!(response.headers()["content-length"], "7");
+    assert_eq!(body_to_vec(&mut response), binary_content);
+}
+
 fn body_to_vec(response: &mut reqwest::blocking::Response) -> Vec<u8> {
     let mut buf: Vec<u8> = Vec::new();
     response
@@ -29,3 +94,35 @@ fn body_to_vec(response: &mut reqwest::blocking::Response) -> Vec<u8> {
         .expect("Cannot read from body");
     buf
 }
+
+#[test]
+fn random_body_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m1 = server.mock(|when, then| {
+        when.path("/seed/1");
+        then.status(200).random_body(4096, 1);
+    });
+    let m2 = server.mock(|when, then| {
+        when.path("/seed/2");
+        then.status(200).random_body(4096, 2);
+    });
+
+    // Act
+    let first = reqwest::blocking::get(server.url("/seed/1")).unwrap();
+    let first_len = first.headers()["content-length"].clone();
+    let first = first.bytes().unwrap();
+    let second = reqwest::blocking::get(server.url("/seed/2"))
+        .unwrap()
+        .bytes()
+        .unwrap();
+
+    // Assert
+    m1.assert();
+    m2.assert();
+    assert_eq!(first_len, "4096");
+    assert_eq!(first.len(), 4096);
+    assert_eq!(&first[..4], &[0xc1, 0x5c, 0x02, 0x89]);
+    assert_ne!(first, second);
+}
@@ -0,0 +1,81 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn after_respond_test() {
+    // Arrange: the application under test receives the webhook
+    let app = MockServer::start();
+    let webhook = app.mock(|when, then| {
+        when.method(POST)
+            .path("/webhooks/orders")
+            .header("x-signature", "abc")
+            .body(r#"{"order":42,"status":"shipped"}"#);
+        then.status(204);
+    });
+
+    let provider = MockServer::start();
+    let webhook_url = app.url("/webhooks/orders");
+    let m = provider.mock(|when, then| {
+        when.method(POST).path("/orders");
+        then.status(202).after_respond(|callback| {
+            callback
+                .url(webhook_url)
+                .header("x-signature", "abc")
+                .body(r#"{"order":42,"status":"shipped"}"#)
+                .delay(Duration::from_millis(500));
+        });
+    });
+
+    // Act
+    let start = Instant::now();
+    let response = Client::new().post(provider.url("/orders")).send().unwrap();
+
+    // Assert: the response is not delayed by the callback
+    assert_eq!(response.status(), 202);
+    assert!(start.elapsed() < Duration::from_millis(500));
+    m.assert();
+    webhook.assert_calls(0);
+
+    thread::sleep(Duration::from_millis(1000));
+    webhook.assert_calls(1);
+}
+
+#[test]
+fn after_respond_failure_is_ignored_test() {
+    // Arrange: nothing listens on the callback target
+    let provider = MockServer::start();
+    let unreachable_url = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/webhooks", listener.local_addr().unwrap())
+    };
+
+    let m = provider.mock(|when, then| {
+        when.path("/orders");
+        then.status(202).after_respond(|callback| {
+            callback.method(PUT).url(unreachable_url);
+        });
+    });
+
+    // Act & Assert: failed callbacks do not affect the mock server
+    for _ in 0..2 {
+        let response = Client::new().post(provider.url("/orders")).send().unwrap();
+        assert_eq!(response.status(), 202);
+    }
+    m.assert_calls(2);
+}
+
+#[test]
+#[should_panic]
+fn after_respond_invalid_url_test() {
+    let provider = MockServer::start();
+    provider.mock(|when, then| {
+        when.path("/orders");
+        then.after_respond(|callback| {
+            callback.url("/webhooks");
+        });
+    });
+}
@@ -0,0 +1,121 @@
+use httpmock::prelude::*;
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+#[test]
+fn chunked_response_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/stream");
+        then.status(200).body("hello chunked world").chunked(5);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let response = reqwest::blocking::get(server.url("/stream")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert!(response.headers().get("content-length").is_none());
+    assert_eq!(response.text().unwrap(), "hello chunked world");
+}
+
+#[test]
+fn chunked_file_response_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/download");
+        then.status(200)
+            .body_from_file("tests/resources/simple_body.txt")
+            .chunked(1);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let response = reqwest::blocking::get(server.url("/download")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert!(response.headers().get("content-length").is_none());
+    assert_eq!(response.text().unwrap(), "ohi!");
+}
+
+#[test]
+#[should_panic(expected = "the chunk size of a chunked response must be greater than zero")]
+fn chunked_response_zero_chunk_size_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/stream");
+        then.status(200).body("hello").chunked(0);
+    });
+}
+
+fn send_raw_request(server: &MockServer, headers: &str) -> String {
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    let request = format!(
+        "GET /grpc HTTP/1.1\r\nHost: {}\r\n{}Connection: close\r\n\r\n",
+        server.address(),
+        headers
+    );
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.to_lowercase()
+}
+
+#[test]
+fn trailers_response_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/grpc");
+        then.status(200)
+            .body("payload")
+            .trailer("grpc-status", "0")
+            .trailer("grpc-message", "ok");
+    });
+
+    // Act
+    let response = send_raw_request(&server, "TE: trailers\r\n");
+
+    // Assert
+    mock.assert();
+    assert!(response.contains("transfer-encoding: chunked\r\n"));
+    assert!(response.contains("trailer: grpc-status, grpc-message\r\n"));
+    assert!(response.ends_with("0\r\ngrpc-status: 0\r\ngrpc-message: ok\r\n\r\n"));
+}
+
+#[test]
+fn trailers_ignored_without_te_header_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/grpc");
+        then.status(200).body("payload").trailer("grpc-status", "0");
+    });
+
+    // Act
+    let response = send_raw_request(&server, "");
+
+    // Assert
+    mock.assert();
+    assert!(response.contains("content-length: 7\r\n"));
+    assert!(!response.contains("grpc-status"));
+    assert!(response.ends_with("\r\n\r\npayload"));
+}
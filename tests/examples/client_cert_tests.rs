@@ -0,0 +1,153 @@
+use httpmock::prelude::*;
+
+#[test]
+fn client_cert_cn_without_mtls_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.client_cert_cn("billing-service");
+        then.status(200);
+    });
+
+    // Act
+    let response = reqwest::blocking::get(format!("http://{}/invoices", server.address())).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 404);
+    m.assert_calls(0);
+
+    let closest_match = m.closest_match().expect("expected a closest match");
+    assert_eq!(closest_match.mismatches.len(), 1);
+
+    let mismatch = &closest_match.mismatches[0];
+    assert_eq!(mismatch.matcher_method, "client_cert_cn");
+    assert!(mismatch
+        .reason
+        .as_ref()
+        .unwrap()
+        .contains("mutual TLS was not configured"));
+}
+
+#[cfg(feature = "https")]
+#[tokio::test]
+async fn client_cert_cn_test() {
+    use httpmock::server::HttpMockServerBuilder;
+    use rcgen::{
+        BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    };
+    use reqwest::{
+        tls::{Certificate as ReqwestCertificate, Identity},
+        Client,
+    };
+    use std::{fs::read, path::PathBuf};
+
+    // Arrange: a CA that issues client certificates and a client certificate signed by it
+    let mut ca_params = CertificateParams::new(Vec::new());
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "httpmock test client CA");
+    let client_ca = Certificate::from_params(ca_params).unwrap();
+
+    let mut client_params = CertificateParams::new(Vec::new());
+    client_params
+        .distinguished_name
+        .push(DnType::CommonName, "billing-service");
+    client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let client_cert = Certificate::from_params(client_params).unwrap();
+
+    let identity_pem = format!(
+        "{}{}",
+        client_cert.serialize_pem_with_signer(&client_ca).unwrap(),
+        client_cert.serialize_private_key_pem()
+    );
+
+    let server = MockServer::start_with_async(
+        HttpMockServerBuilder::new().https_client_ca_cert(client_ca.serialize_pem().unwrap()),
+    )
+    .await;
+
+    let matching = server
+        .mock_async(|when, then| {
+            when.path("/invoices").client_cert_cn("billing-service");
+            then.status(200);
+        })
+        .await;
+
+    let not_matching = server
+        .mock_async(|when, then| {
+            when.path("/orders").client_cert_cn("order-service");
+            then.status(200);
+        })
+        .await;
+
+    let project_dir = env!("CARGO_MANIFEST_DIR");
+    let server_ca_path = PathBuf::from(project_dir).join("certs/ca.pem");
+    let server_ca = ReqwestCertificate::from_pem(&read(server_ca_path).unwrap()).unwrap();
+
+    let base_url = format!("https://localhost:{}", server.port());
+
+    // Act
+    let client = Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(server_ca.clone())
+        .identity(Identity::from_pem(identity_pem.as_bytes()).unwrap())
+        .build()
+        .unwrap();
+
+    let invoices_response = client
+        .get(format!("{}/invoices", base_url))
+        .send()
+        .await
+        .unwrap();
+    let orders_response = client
+        .get(format!("{}/orders", base_url))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(invoices_response.status(), 200);
+    assert_eq!(orders_response.status(), 404);
+
+    matching.assert_async().await;
+    not_matching.assert_calls_async(0).await;
+
+    let closest_match = not_matching
+        .closest_match_async()
+        .await
+        .expect("expected a closest match");
+    let comparison = closest_match.mismatches[0].comparison.as_ref().unwrap();
+    assert_eq!(comparison.actual, "billing-service");
+
+    // Act: requests without a client certificate are served but never match
+    server.reset_history_async().await;
+
+    let anonymous_client = Client::builder()
+        .use_rustls_tls()
+        .add_root_certificate(server_ca)
+        .build()
+        .unwrap();
+
+    let anonymous_response = anonymous_client
+        .get(format!("{}/invoices", base_url))
+        .send()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(anonymous_response.status(), 404);
+
+    let closest_match = matching
+        .closest_match_async()
+        .await
+        .expect("expected a closest match");
+    let mismatch = &closest_match.mismatches[0];
+    assert_eq!(mismatch.matcher_method, "client_cert_cn");
+    assert!(mismatch
+        .reason
+        .as_ref()
+        .unwrap()
+        .contains("did not present a certificate"));
+}
@@ -0,0 +1,35 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+
+#[test]
+fn closest_match_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/orders").header("x-tenant", "acme");
+        then.status(201);
+    });
+
+    assert!(m.closest_match().is_none());
+
+    // Act
+    Client::new()
+        .post(server.url("/orders"))
+        .header("x-tenant", "globex")
+        .send()
+        .unwrap();
+
+    // Assert
+    let closest_match = m.closest_match().expect("expected a closest match");
+    assert_eq!(closest_match.request.uri().path(), "/orders");
+    assert_eq!(closest_match.mismatches.len(), 1);
+
+    let mismatch = &closest_match.mismatches[0];
+    assert_eq!(mismatch.entity, "header");
+    assert_eq!(mismatch.matcher_method, "header");
+
+    let json = serde_json::to_value(&closest_match).unwrap();
+    assert_eq!(json["mismatches"][0]["entity"], "header");
+    assert_eq!(json["request_index"], 0);
+}
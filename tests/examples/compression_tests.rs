@@ -0,0 +1,193 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::io::{Read, Write};
+
+const BODY: &str = "The quick brown fox jumps over the lazy dog. ";
+
+fn get_with_accept_encoding(
+    server: &MockServer,
+    accept_encoding: Option<&str>,
+) -> reqwest::blocking::Response {
+    let mut request = Client::new().get(server.url("/text"));
+    if let Some(accept_encoding) = accept_encoding {
+        request = request.header("accept-encoding", accept_encoding);
+    }
+    request.send().unwrap()
+}
+
+#[test]
+fn auto_compress_gzip_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/text");
+        then.status(200).body(BODY.repeat(50)).auto_compress(true);
+    });
+
+    // Act
+    let response = get_with_accept_encoding(&server, Some("deflate, gzip;q=0.8"));
+
+    // Assert
+    m.assert();
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    assert_eq!(response.headers()["vary"], "accept-encoding");
+
+    let content_length: usize = response.headers()["content-length"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let compressed = response.bytes().unwrap();
+    assert_eq!(content_length, compressed.len());
+    assert!(compressed.len() < BODY.len() * 50);
+
+    let mut body = String::new();
+    flate2::read::GzDecoder::new(compressed.as_ref())
+        .read_to_string(&mut body)
+        .unwrap();
+    assert_eq!(body, BODY.repeat(50));
+}
+
+#[test]
+fn auto_compress_prefers_brotli_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/text");
+        then.status(200).body(BODY).auto_compress(true);
+    });
+
+    // Act
+    let response = get_with_accept_encoding(&server, Some("gzip, br"));
+
+    // Assert
+    assert_eq!(response.headers()["content-encoding"], "br");
+
+    let compressed = response.bytes().unwrap();
+    let mut body = String::new();
+    brotli::Decompressor::new(compressed.as_ref(), 4096)
+        .read_to_string(&mut body)
+        .unwrap();
+    assert_eq!(body, BODY);
+}
+
+#[test]
+fn auto_compress_without_accepted_encoding_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/text");
+        then.status(200).body(BODY).auto_compress(true);
+    });
+
+    // Act
+    let without_header = get_with_accept_encoding(&server, None);
+    let unsupported = get_with_accept_encoding(&server, Some("deflate, identity"));
+
+    // Assert
+    for response in [without_header, unsupported] {
+        assert!(response.headers().get("content-encoding").is_none());
+        assert_eq!(response.headers()["content-length"], BODY.len().to_string());
+        assert_eq!(response.text().unwrap(), BODY);
+    }
+}
+
+#[test]
+fn auto_compress_keeps_explicit_content_encoding_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/text");
+        then.status(200)
+            .header("content-encoding", "custom")
+            .body(BODY)
+            .auto_compress(true);
+    });
+
+    // Act
+    let response = get_with_accept_encoding(&server, Some("gzip"));
+
+    // Assert
+    assert_eq!(response.headers()["content-encoding"], "custom");
+    assert_eq!(response.text().unwrap(), BODY);
+}
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn post_with_content_encoding(
+    server: &MockServer,
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+) -> reqwest::blocking::Response {
+    let mut request = Client::new().post(server.url("/events")).body(body);
+    if let Some(content_encoding) = content_encoding {
+        request = request.header("content-encoding", content_encoding);
+    }
+    request.send().unwrap()
+}
+
+#[test]
+fn body_decompressed_matches_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.body_decompressed_matches("\"event\":\\s*\"signup\"");
+        then.status(202);
+    });
+
+    let mut brotli_body = Vec::new();
+    {
+        let mut writer = brotli::CompressorWriter::new(&mut brotli_body, 4096, 5, 22);
+        writer.write_all(br#"{"event": "signup"}"#).unwrap();
+    }
+
+    // Act
+    let gzip_response =
+        post_with_content_encoding(&server, Some("gzip"), gzip(br#"{"event": "signup"}"#));
+    let brotli_response = post_with_content_encoding(&server, Some("br"), brotli_body);
+    let plain_response =
+        post_with_content_encoding(&server, None, br#"{"event": "signup"}"#.to_vec());
+    let other_response =
+        post_with_content_encoding(&server, Some("gzip"), gzip(br#"{"event": "login"}"#));
+
+    // Assert
+    m.assert_calls(3);
+    assert_eq!(gzip_response.status(), 202);
+    assert_eq!(brotli_response.status(), 202);
+    assert_eq!(plain_response.status(), 202);
+    assert_eq!(other_response.status(), 404);
+}
+
+#[test]
+fn body_decompressed_matches_invalid_body_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.body_decompressed_matches("signup");
+        then.status(202);
+    });
+
+    // Act
+    let response = post_with_content_encoding(&server, Some("gzip"), b"signup".to_vec());
+
+    // Assert
+    assert_eq!(response.status(), 404);
+
+    let closest_match = m.closest_match().expect("no request received");
+    let reason = closest_match.mismatches[0].reason.as_deref().unwrap();
+    assert!(
+        reason.contains("cannot decode the request body with content encoding 'gzip'"),
+        "{}",
+        reason
+    );
+}
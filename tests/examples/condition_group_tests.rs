@@ -0,0 +1,119 @@
+use crate::matchers::expect_fails_with;
+use httpmock::prelude::*;
+
+#[test]
+fn any_of_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(GET).any_of(|group| {
+            group
+                .when(|when| when.path("/a"))
+                .when(|when| when.path("/b").query_param("page", "1"));
+        });
+        then.status(200);
+    });
+
+    // Act
+    let a = reqwest::blocking::get(server.url("/a")).unwrap();
+    let b = reqwest::blocking::get(server.url("/b?page=1")).unwrap();
+    let b_without_page = reqwest::blocking::get(server.url("/b")).unwrap();
+    let c = reqwest::blocking::get(server.url("/c")).unwrap();
+
+    // Assert
+    m.assert_calls(2);
+    assert_eq!(a.status(), 200);
+    assert_eq!(b.status(), 200);
+    assert_eq!(b_without_page.status(), 404);
+    assert_eq!(c.status(), 404);
+}
+
+#[test]
+fn nested_all_of_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.any_of(|group| {
+            group
+                .when(|when| {
+                    when.all_of(|group| {
+                        group
+                            .when(|when| when.method(GET))
+                            .when(|when| when.path("/items"));
+                    })
+                })
+                .when(|when| when.method(POST).path("/search"));
+        });
+        then.status(200);
+    });
+
+    // Act
+    let get_items = reqwest::blocking::get(server.url("/items")).unwrap();
+    let post_items = reqwest::blocking::Client::new()
+        .post(server.url("/items"))
+        .send()
+        .unwrap();
+    let post_search = reqwest::blocking::Client::new()
+        .post(server.url("/search"))
+        .send()
+        .unwrap();
+
+    // Assert
+    m.assert_calls(2);
+    assert_eq!(get_items.status(), 200);
+    assert_eq!(post_items.status(), 404);
+    assert_eq!(post_search.status(), 200);
+}
+
+#[test]
+fn any_of_failure_test() {
+    expect_fails_with(
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.any_of(|group| {
+                    group
+                        .when(|when| when.path("/a"))
+                        .when(|when| when.path("/b"));
+                });
+                then.status(200);
+            });
+
+            // Act
+            reqwest::blocking::get(server.url("/c")).unwrap();
+
+            // Assert
+            m.assert();
+        },
+        vec![
+            "Request Mismatch",
+            "Expected request to match:",
+            "any of 2 alternatives",
+            "Received:",
+            "GET /c",
+            "Reason:",
+            "alternative 1 (distance 10): path equals /a (received /c)",
+            "alternative 2 (distance 10): path equals /b (received /c)",
+            "Matcher:  any_of",
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "contains the parameter 'id' more than once")]
+fn invalid_condition_in_group_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.any_of(|group| {
+            group
+                .when(|when| when.path("/users"))
+                .when(|when| when.path_template("/users/{id}/friends/{id}"));
+        });
+        then.status(200);
+    });
+}
@@ -0,0 +1,127 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::time::{Duration, UNIX_EPOCH};
+
+const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+#[test]
+fn if_none_match_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/config");
+        then.status(200)
+            .header("content-type", "application/json")
+            .header("cache-control", "max-age=60")
+            .etag("v1")
+            .conditional(true)
+            .body("{}");
+    });
+
+    let get = |etag: &str| {
+        Client::new()
+            .get(server.url("/config"))
+            .header("If-None-Match", etag)
+            .send()
+            .unwrap()
+    };
+
+    // Act
+    let not_modified = get("\"v1\"");
+    let modified = get("\"v0\"");
+
+    // Assert
+    mock.assert_calls(2);
+
+    assert_eq!(not_modified.status(), 304);
+    assert_eq!(not_modified.headers()["etag"], "\"v1\"");
+    assert_eq!(not_modified.headers()["cache-control"], "max-age=60");
+    assert!(not_modified.headers().get("content-type").is_none());
+    assert_eq!(not_modified.text().unwrap(), "");
+
+    assert_eq!(modified.status(), 200);
+    assert_eq!(modified.headers()["etag"], "\"v1\"");
+    assert_eq!(modified.text().unwrap(), "{}");
+}
+
+#[test]
+fn if_modified_since_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/report");
+        then.status(200)
+            .last_modified(UNIX_EPOCH + Duration::from_secs(1445412480))
+            .conditional(true)
+            .body("report");
+    });
+
+    let get = |since: &str| {
+        Client::new()
+            .get(server.url("/report"))
+            .header("If-Modified-Since", since)
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert
+    let response = get(LAST_MODIFIED);
+    assert_eq!(response.status(), 304);
+    assert_eq!(response.headers()["last-modified"], LAST_MODIFIED);
+
+    let response = get("Tue, 20 Oct 2015 07:28:00 GMT");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().unwrap(), "report");
+}
+
+#[test]
+fn if_none_match_takes_precedence_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/report");
+        then.status(200)
+            .etag("v2")
+            .last_modified(UNIX_EPOCH + Duration::from_secs(1445412480))
+            .conditional(true)
+            .body("report");
+    });
+
+    // Act: The date is current, but the entity tag is outdated
+    let response = Client::new()
+        .get(server.url("/report"))
+        .header("If-None-Match", "\"v1\"")
+        .header("If-Modified-Since", LAST_MODIFIED)
+        .send()
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().unwrap(), "report");
+}
+
+#[test]
+fn validators_without_conditional_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/config");
+        then.status(200).etag("W/\"v1\"").body("{}");
+    });
+
+    // Act
+    let response = Client::new()
+        .get(server.url("/config"))
+        .header("If-None-Match", "W/\"v1\"")
+        .send()
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["etag"], "W/\"v1\"");
+    assert_eq!(response.text().unwrap(), "{}");
+}
@@ -17,3 +17,31 @@ fn my_custom_request_matcher_test() {
     mock.assert();
     assert_eq!(response.status(), 201);
 }
+
+#[test]
+fn custom_request_matcher_with_synthetic_request_test() {
+    // Arrange: a predicate that is tested without starting a server
+    let is_json_upload = |req: &HttpMockRequest| {
+        req.method() == Method::PUT
+            && req.query_params().get("overwrite") == Some(&"yes please".to_string())
+            && req.headers().get("content-type").map(|v| v.as_bytes()) == Some(b"application/json")
+            && req.body_string().starts_with('{')
+    };
+
+    let request = |method: Method| {
+        HttpMockRequest::builder()
+            .method(method)
+            .path("/files/report.json")
+            .query_param("overwrite", "yes please")
+            .header("Content-Type", "application/json")
+            .body(r#"{ "title": "report" }"#)
+            .build()
+    };
+
+    // Act & Assert
+    let upload = request(PUT);
+    assert_eq!(upload.uri().path(), "/files/report.json");
+    assert_eq!(upload.uri_str(), "/files/report.json?overwrite=yes+please");
+    assert!(is_json_upload(&upload));
+    assert!(!is_json_upload(&request(POST)));
+}
@@ -1,5 +1,12 @@
 use httpmock::prelude::*;
-use std::time::{Duration, SystemTime};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
 
 #[test]
 fn delay_test() {
@@ -22,3 +29,98 @@ fn delay_test() {
     assert_eq!(response.status(), 200);
     assert_eq!(start_time.elapsed().unwrap() > delay, true);
 }
+
+#[test]
+fn delay_from_header_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/delay");
+        then.status(200)
+            .delay(Duration::from_millis(300))
+            .delay_from_header("x-mock-delay-ms");
+    });
+
+    let client = reqwest::blocking::Client::new();
+    let request = |value: Option<&str>| {
+        let mut request = client.get(server.url("/delay"));
+        if let Some(value) = value {
+            request = request.header("x-mock-delay-ms", value);
+        }
+        let start_time = SystemTime::now();
+        assert_eq!(request.send().unwrap().status(), 200);
+        start_time.elapsed().unwrap()
+    };
+
+    // Act & Assert: the header value is used as the delay
+    assert!(request(Some("1000")) >= Duration::from_millis(1000));
+    assert!(request(Some("0")) < Duration::from_millis(300));
+
+    // Act & Assert: missing or invalid values fall back to the configured delay
+    let elapsed = request(None);
+    assert!(elapsed >= Duration::from_millis(300) && elapsed < Duration::from_millis(1000));
+    let elapsed = request(Some("slow"));
+    assert!(elapsed >= Duration::from_millis(300) && elapsed < Duration::from_millis(1000));
+
+    mock.assert_calls(4);
+}
+
+#[test]
+fn wait_for_concurrent_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/reserve");
+        then.status(200).wait_for_concurrent(3);
+    });
+
+    let answered = Arc::new(AtomicUsize::new(0));
+    let send = || {
+        let url = server.url("/reserve");
+        let answered = answered.clone();
+        thread::spawn(move || {
+            let status = reqwest::blocking::get(url).unwrap().status();
+            answered.fetch_add(1, Ordering::SeqCst);
+            status
+        })
+    };
+
+    // Act: the first two requests are held back until the third one arrives
+    let mut handles = vec![send(), send()];
+    thread::sleep(Duration::from_millis(500));
+    let answered_before_third = answered.load(Ordering::SeqCst);
+    handles.push(send());
+
+    // Assert
+    assert_eq!(answered_before_third, 0);
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 200);
+    }
+    mock.assert_calls(3);
+}
+
+#[test]
+fn wait_for_concurrent_timeout_test() {
+    // Arrange
+    let start_time = SystemTime::now();
+    let timeout = Duration::from_millis(300);
+
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/reserve");
+        then.status(200)
+            .wait_for_concurrent(2)
+            .wait_for_concurrent_timeout(timeout);
+    });
+
+    // Act: the second request never arrives
+    let response = reqwest::blocking::get(server.url("/reserve")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.status(), 200);
+    assert!(start_time.elapsed().unwrap() >= timeout);
+}
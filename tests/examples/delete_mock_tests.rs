@@ -30,3 +30,156 @@ fn explicit_delete_mock_test() {
     // Assert that the request failed because the mock has been deleted
     assert_eq!(response.status(), 404);
 }
+
+#[test]
+fn delete_multiple_mocks_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m1 = server.mock(|when, then| {
+        when.path("/one");
+        then.status(200);
+    });
+
+    let m2 = server.mock(|when, then| {
+        when.path("/two");
+        then.status(200);
+    });
+
+    let m3 = server.mock(|when, then| {
+        when.path("/three");
+        then.status(200);
+    });
+
+    // Act: Delete a subset of the mocks
+    server.delete_mocks(&[&m1, &m2]);
+
+    // Assert: Only the remaining mock still serves requests
+    assert_eq!(
+        reqwest::blocking::get(server.url("/one")).unwrap().status(),
+        404
+    );
+    assert_eq!(
+        reqwest::blocking::get(server.url("/two")).unwrap().status(),
+        404
+    );
+    assert_eq!(
+        reqwest::blocking::get(server.url("/three"))
+            .unwrap()
+            .status(),
+        200
+    );
+    m3.assert();
+}
+
+#[test]
+#[cfg(feature = "remote")]
+fn delete_multiple_mocks_remote_test() {
+    use crate::with_standalone_server;
+
+    // Arrange
+    with_standalone_server();
+    let server = MockServer::connect("localhost:5050");
+
+    let m1 = server.mock(|when, then| {
+        when.path("/delete-multiple-one");
+        then.status(200);
+    });
+
+    let m2 = server.mock(|when, then| {
+        when.path("/delete-multiple-two");
+        then.status(200);
+    });
+
+    // Act: Delete the mocks in a single round trip
+    server.delete_mocks(&[&m1, &m2]);
+
+    // Assert
+    let response = reqwest::blocking::get(server.url("/delete-multiple-one")).unwrap();
+    assert_eq!(response.status(), 404);
+    let response = reqwest::blocking::get(server.url("/delete-multiple-two")).unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn delete_mocks_by_tag_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    for path in ["/billing/invoices", "/billing/payments"] {
+        server.mock(|when, then| {
+            when.path(path).tag("billing").tag("v1");
+            then.status(200);
+        });
+    }
+
+    let users_mock = server.mock(|when, then| {
+        when.path("/users").tag("users");
+        then.status(200);
+    });
+
+    // Act
+    let deleted = server.delete_mocks_by_tag("billing");
+
+    // Assert
+    assert_eq!(deleted, 2);
+    assert_eq!(server.delete_mocks_by_tag("billing"), 0);
+    assert_eq!(server.delete_mocks_by_tag("v1"), 0);
+
+    let status = |path: &str| reqwest::blocking::get(server.url(path)).unwrap().status();
+    assert_eq!(status("/billing/invoices"), 404);
+    assert_eq!(status("/billing/payments"), 404);
+    assert_eq!(status("/users"), 200);
+    users_mock.assert();
+}
+
+#[test]
+fn assert_tag_called_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    for path in ["/billing/invoices", "/billing/payments"] {
+        server.mock(|when, then| {
+            when.path(path).tag("billing");
+            then.status(200);
+        });
+    }
+
+    // Act
+    for path in [
+        "/billing/invoices",
+        "/billing/payments",
+        "/billing/payments",
+    ] {
+        reqwest::blocking::get(server.url(path)).unwrap();
+    }
+
+    // Assert
+    server.assert_tag_called("billing", 3);
+}
+
+#[test]
+#[should_panic(expected = "were expected to serve 1 request(s) in total, but served 2")]
+fn assert_tag_called_fails_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/billing/invoices").tag("billing");
+        then.status(200);
+    });
+
+    // Act
+    reqwest::blocking::get(server.url("/billing/invoices")).unwrap();
+    reqwest::blocking::get(server.url("/billing/invoices")).unwrap();
+
+    // Assert
+    server.assert_tag_called("billing", 1);
+}
+
+#[test]
+#[should_panic(expected = "No mock with tag 'unknown' found")]
+fn assert_tag_called_without_tagged_mocks_test() {
+    let server = MockServer::start();
+    server.assert_tag_called("unknown", 0);
+}
@@ -0,0 +1,83 @@
+use httpmock::prelude::*;
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+#[test]
+fn expires_in_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let fallback = server.mock(|when, then| {
+        when.path("/flags").priority(-1);
+        then.status(200).body("fallback");
+    });
+
+    server.mock(|when, then| {
+        when.path("/flags").expires_in(Duration::from_secs(1));
+        then.status(200).body("temporary");
+    });
+
+    let body = || {
+        reqwest::blocking::get(server.url("/flags"))
+            .unwrap()
+            .text()
+            .unwrap()
+    };
+
+    // Act & Assert: the expired mock behaves as if it never existed
+    assert_eq!(body(), "temporary");
+    thread::sleep(Duration::from_millis(1500));
+    assert_eq!(body(), "fallback");
+    fallback.assert();
+}
+
+#[test]
+fn expires_at_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let expired = server.mock(|when, then| {
+        when.path("/expired")
+            .expires_at(SystemTime::now() - Duration::from_secs(1));
+        then.status(200);
+    });
+
+    // The earlier of both expiry times applies.
+    let valid = server.mock(|when, then| {
+        when.path("/valid")
+            .expires_in(Duration::from_secs(60))
+            .expires_at(SystemTime::now() + Duration::from_secs(3600));
+        then.status(200);
+    });
+
+    let status = |path: &str| reqwest::blocking::get(server.url(path)).unwrap().status();
+
+    // Act & Assert
+    assert_eq!(status("/expired"), 404);
+    assert_eq!(status("/valid"), 200);
+    valid.assert();
+
+    // The expired mock was removed from the server.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| expired.calls()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn expired_mocks_are_swept_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/swept").expires_in(Duration::from_millis(50));
+        then.status(200);
+    });
+
+    // Act: no request is sent, so only the background sweeper can remove the mock
+    thread::sleep(Duration::from_millis(2500));
+
+    // Assert
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| m.calls()));
+    assert!(result.is_err());
+}
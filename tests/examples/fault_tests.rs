@@ -0,0 +1,70 @@
+use httpmock::{prelude::*, Fault};
+
+#[test]
+fn fault_empty_response_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/flaky");
+        then.fault(Fault::EmptyResponse);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let result = reqwest::blocking::get(server.url("/flaky"));
+
+    // Assert
+    mock.assert();
+    assert!(result.is_err());
+}
+
+#[test]
+fn fault_reset_connection_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/flaky");
+        then.fault(Fault::ResetConnection);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let result = reqwest::blocking::get(server.url("/flaky"));
+
+    // Assert
+    mock.assert();
+    assert!(result.is_err());
+}
+
+#[test]
+fn fault_close_connection_after_headers_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/flaky");
+        then.status(200)
+            .header("x-custom", "value")
+            .fault(Fault::CloseConnectionAfterHeaders);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let response = reqwest::blocking::get(server.url("/flaky")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("x-custom").unwrap(), "value");
+    assert!(response.bytes().is_err());
+}
+
+#[test]
+#[should_panic]
+fn fault_with_body_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/flaky");
+        then.body("hello").fault(Fault::EmptyResponse);
+    });
+}
@@ -18,3 +18,112 @@ fn file_body_test() {
     assert_eq!(response.status(), 200);
     assert_eq!(response.text().unwrap(), "ohi!");
 }
+
+#[test]
+fn file_body_stream_test() {
+    // Arrange
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.path("/download");
+        then.status(200)
+            .body_from_file_stream("tests/resources/simple_body.txt");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/download")).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.content_length(), Some(4));
+    assert_eq!(response.text().unwrap(), "ohi!");
+}
+
+#[test]
+#[should_panic(expected = "cannot stream response body from file")]
+fn file_body_stream_missing_file_test() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/download");
+        then.body_from_file_stream("tests/resources/does_not_exist.txt");
+    });
+}
+
+#[test]
+fn json_body_from_file_test() {
+    // Arrange
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.path("/user");
+        then.status(200)
+            .json_body_from_file("tests/resources/user.json");
+    });
+
+    let custom_content_type = server.mock(|when, then| {
+        when.path("/user/v2");
+        then.status(200)
+            .header("content-type", "application/vnd.api+json")
+            .json_body_from_file("tests/resources/user.json");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/user")).unwrap();
+    let v2_response = reqwest::blocking::get(server.url("/user/v2")).unwrap();
+
+    // Assert
+    m.assert();
+    custom_content_type.assert();
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap(),
+        serde_json::json!({ "name": "Fred", "roles": ["admin", "developer"] })
+    );
+    assert_eq!(
+        v2_response.headers().get_all("content-type").iter().count(),
+        1
+    );
+    assert_eq!(
+        v2_response.headers()["content-type"],
+        "application/vnd.api+json"
+    );
+}
+
+#[test]
+#[should_panic(expected = "is not valid JSON")]
+fn json_body_from_invalid_file_test() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.json_body_from_file("tests/resources/simple_body.txt");
+    });
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn json_body_file_in_yaml_test() {
+    use httpmock::server::HttpMockServerBuilder;
+    use std::fs;
+
+    // Arrange
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("json_body_file_in_yaml_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("user.yaml"),
+        "when:\n  path: /user\nthen:\n  status: 200\n  json_body_file: tests/resources/user.json\n",
+    )
+    .unwrap();
+
+    let server = MockServer::start_with(HttpMockServerBuilder::new().static_mock_dir(dir));
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/user")).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&response.text().unwrap()).unwrap()["name"],
+        "Fred"
+    );
+}
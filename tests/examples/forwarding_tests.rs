@@ -68,3 +68,320 @@ fn forward_to_github_test() {
     assert_eq!(true, response.text().unwrap().contains("\"private\":false"));
 }
 // @example-end
+
+#[cfg(feature = "proxy")]
+#[test]
+fn forwarding_upstream_timeout_test() {
+    use std::time::Duration;
+
+    // Arrange
+    let target_server = MockServer::start();
+    let get_mock = target_server.mock(|when, then| {
+        when.method(GET);
+        then.status(200).delay(Duration::from_secs(3));
+    });
+    let post_mock = target_server.mock(|when, then| {
+        when.method(POST);
+        then.status(200).delay(Duration::from_secs(3));
+    });
+
+    let server = MockServer::start();
+    server.forward_to(target_server.base_url(), |rule| {
+        rule.upstream_timeout(Duration::from_millis(200))
+            .retries(2)
+            .filter(|when| {
+                when.any_request();
+            });
+    });
+
+    let client = Client::new();
+
+    // Act
+    let get_response = client.get(server.url("/get")).send().unwrap();
+    let post_response = client.post(server.url("/post")).send().unwrap();
+
+    // Assert: Both requests time out, but only the idempotent request is retried
+    assert_eq!(get_response.status().as_u16(), 504);
+    assert_eq!(
+        get_response.text().unwrap(),
+        "httpmock: upstream server did not respond within 200 ms"
+    );
+    get_mock.assert_calls(3);
+
+    assert_eq!(post_response.status().as_u16(), 504);
+    post_mock.assert_calls(1);
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn forwarding_request_header_changes_test() {
+    // Arrange
+    let target_server = MockServer::start();
+    let target_mock = target_server.mock(|when, then| {
+        when.header("authorization", "Bearer upstream-token")
+            .header("x-tag", "one")
+            .header("x-tag", "two")
+            .header("x-replaced", "new")
+            .header_value_count("x-replaced", 1)
+            .header_missing("x-client-secret")
+            .header("host", "upstream.example");
+        then.status(200);
+    });
+
+    let server = MockServer::start();
+    server.forward_to(target_server.base_url(), |rule| {
+        rule.set_request_header("Authorization", "Bearer upstream-token")
+            .remove_request_header("X-Client-Secret")
+            .append_request_header("X-Tag", "two")
+            .add_request_header("X-Replaced", "new")
+            // Sent as a header only, the request still goes to the target server
+            .set_request_header("Host", "upstream.example")
+            .filter(|when| {
+                when.any_request();
+            });
+    });
+
+    // Act
+    let response = Client::new()
+        .get(server.url("/get"))
+        .header("Authorization", "Bearer client-token")
+        .header("X-Client-Secret", "secret")
+        .header("X-Tag", "one")
+        .header("X-Replaced", "old")
+        .send()
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 200);
+    target_mock.assert();
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn forwarding_via_proxy_test() {
+    // The target server only answers requests that passed the proxy server.
+    let target_server = MockServer::start();
+    let target_mock = target_server.mock(|when, then| {
+        when.path("/hello").header("x-via-proxy", "true");
+        then.status(200).body("Hi from the target!");
+    });
+
+    let proxy_server = MockServer::start();
+    proxy_server.proxy(|rule| {
+        rule.filter(|when| {
+            when.host(target_server.host()).port(target_server.port());
+        })
+        .add_request_header("x-via-proxy", "true");
+    });
+
+    let server = MockServer::start();
+    server.forward_to(target_server.base_url(), |rule| {
+        rule.via_proxy(proxy_server.base_url());
+    });
+
+    let response = Client::new().get(server.url("/hello")).send().unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().unwrap(), "Hi from the target!");
+    target_mock.assert();
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+#[should_panic(expected = "unsupported scheme 'ftp'")]
+fn forwarding_via_unsupported_proxy_test() {
+    let server = MockServer::start();
+    server.forward_to("http://localhost:8080", |rule| {
+        rule.via_proxy("ftp://proxy.example.com");
+    });
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn proxy_to_test() {
+    // Arrange
+    let target_server = MockServer::start();
+    let target_mock = target_server.mock(|when, then| {
+        when.path("/users/1")
+            .header("authorization", "Bearer secret");
+        then.status(202)
+            .header("x-upstream", "yes")
+            .body("Hi from the target!");
+    });
+
+    let server = MockServer::start();
+    let proxied_mock = server.mock(|when, then| {
+        when.path("/users/1");
+        then.proxy_to_with(target_server.base_url(), |rule| {
+            rule.set_request_header("authorization", "Bearer secret");
+        });
+    });
+    let local_mock = server.mock(|when, then| {
+        when.path("/users/2");
+        then.status(200).body("Hi from the mock!");
+    });
+
+    let client = Client::new();
+
+    // Act
+    let proxied = client.get(server.url("/users/1")).send().unwrap();
+    let local = client.get(server.url("/users/2")).send().unwrap();
+
+    // Assert: only the request that matches the proxying mock is forwarded
+    assert_eq!(proxied.status().as_u16(), 202);
+    assert_eq!(proxied.headers()["x-upstream"], "yes");
+    assert_eq!(proxied.text().unwrap(), "Hi from the target!");
+    assert_eq!(local.text().unwrap(), "Hi from the mock!");
+
+    proxied_mock.assert();
+    local_mock.assert();
+    target_mock.assert();
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn proxy_to_upstream_timeout_test() {
+    use std::time::Duration;
+
+    // Arrange
+    let target_server = MockServer::start();
+    target_server.mock(|when, then| {
+        when.any_request();
+        then.status(200).delay(Duration::from_secs(3));
+    });
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.any_request();
+        then.proxy_to_with(target_server.base_url(), |rule| {
+            rule.upstream_timeout(Duration::from_millis(200));
+        });
+    });
+
+    // Act
+    let response = Client::new().get(server.url("/slow")).send().unwrap();
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 504);
+}
+
+/// Returns the base URL of a local port that no server is listening on.
+#[cfg(feature = "proxy")]
+fn unreachable_base_url() -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn forward_to_unreachable_upstream_test() {
+    // Arrange
+    let server = MockServer::start();
+    server.forward_to(unreachable_base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    // Act
+    let response = Client::new().get(server.url("/get")).send().unwrap();
+
+    // Assert
+    assert_eq!(response.status().as_u16(), 502);
+    assert!(response
+        .text()
+        .unwrap()
+        .starts_with("httpmock: cannot reach upstream server"));
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn forward_to_on_error_test() {
+    use std::time::Duration;
+
+    // Arrange
+    let slow_server = MockServer::start();
+    slow_server.mock(|when, then| {
+        when.any_request();
+        then.status(200).delay(Duration::from_secs(3));
+    });
+
+    let server = MockServer::start();
+    server.forward_to(unreachable_base_url(), |rule| {
+        rule.on_error(503, "upstream unavailable").filter(|when| {
+            when.path("/refused");
+        });
+    });
+    server.forward_to(slow_server.base_url(), |rule| {
+        rule.upstream_timeout(Duration::from_millis(200))
+            .on_error(599, "upstream timeout")
+            .filter(|when| {
+                when.path("/slow");
+            });
+    });
+
+    let client = Client::new();
+
+    // Act
+    let refused_response = client.get(server.url("/refused")).send().unwrap();
+    let slow_response = client.get(server.url("/slow")).send().unwrap();
+
+    // Assert
+    assert_eq!(refused_response.status().as_u16(), 503);
+    assert_eq!(refused_response.text().unwrap(), "upstream unavailable");
+    assert_eq!(slow_response.status().as_u16(), 599);
+    assert_eq!(slow_response.text().unwrap(), "upstream timeout");
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+#[should_panic(expected = "a proxied response cannot be combined with a response body or fault")]
+fn proxy_to_with_body_test() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.any_request();
+        then.body("hello").proxy_to("http://localhost:8080");
+    });
+}
+
+#[cfg(all(feature = "proxy", feature = "https"))]
+#[test]
+fn forwarding_danger_accept_invalid_certs_test() {
+    // The target server uses a certificate that is signed by the httpmock CA, which is not
+    // trusted by the host.
+    let target_server = MockServer::start();
+    let target_mock = target_server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).body("Hi from the target!");
+    });
+    let target_url = format!("https://{}", target_server.address());
+
+    let verifying_server = MockServer::start();
+    verifying_server.forward_to(target_url.clone(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let server = MockServer::start();
+    server.forward_to(target_url, |rule| {
+        rule.danger_accept_invalid_certs(true);
+    });
+
+    // Act: the mock servers are called via plain HTTP, so that only the upstream connection
+    // uses TLS
+    let rejected = Client::new()
+        .get(format!("http://{}/hello", verifying_server.address()))
+        .send()
+        .unwrap();
+    let accepted = Client::new()
+        .get(format!("http://{}/hello", server.address()))
+        .send()
+        .unwrap();
+
+    // Assert
+    assert_ne!(rejected.status(), 200);
+    assert_eq!(accepted.status(), 200);
+    assert_eq!(accepted.text().unwrap(), "Hi from the target!");
+    target_mock.assert();
+}
@@ -59,3 +59,157 @@ fn headers_test_2() {
     // Assert: Verify that the mock was called at least once
     mock.assert();
 }
+
+#[test]
+fn default_headers_test() {
+    // Arrange
+    let server = MockServer::start();
+    server.set_default_headers(vec![
+        ("access-control-allow-origin", "*"),
+        ("x-request-id", "default"),
+    ]);
+
+    let m = server.mock(|when, then| {
+        when.path("/test");
+        then.status(200).header("x-request-id", "explicit");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    let not_found = reqwest::blocking::get(server.url("/unknown")).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.headers().get_all("x-request-id").iter().count(), 1);
+    assert_eq!(response.headers()["x-request-id"], "explicit");
+
+    assert_eq!(not_found.status(), 404);
+    assert_eq!(not_found.headers()["x-request-id"], "default");
+
+    // Clearing the default headers at runtime
+    server.set_default_headers(Vec::<(String, String)>::new());
+    let response = reqwest::blocking::get(server.url("/test")).unwrap();
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+}
+
+#[test]
+fn status_from_header_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/test");
+        then.status(202).status_from_header("x-mock-status");
+    });
+
+    let client = Client::new();
+    let send = |status: Option<&str>| {
+        let mut request = client.get(server.url("/test"));
+        if let Some(status) = status {
+            request = request.header("x-mock-status", status);
+        }
+        request.send().unwrap()
+    };
+
+    // Act & Assert: the status code is taken from the request header
+    assert_eq!(send(Some("503")).status(), 503);
+    assert_eq!(send(Some("418")).status(), 418);
+
+    // Act & Assert: the configured status is used if the header is absent or not a number
+    assert_eq!(send(None).status(), 202);
+    assert_eq!(send(Some("unavailable")).status(), 202);
+
+    // Act & Assert: invalid status codes are rejected
+    let response = send(Some("1000"));
+    assert_eq!(response.status(), 400);
+    assert!(response
+        .text()
+        .unwrap()
+        .contains("request header 'x-mock-status' contains an invalid HTTP status code: 1000"));
+
+    m.assert_calls(5);
+}
+
+#[test]
+fn header_value_count_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.header_value_count("Forwarded", 2)
+            .header("forwarded", "for=198.51.100.17");
+        then.status(200);
+    });
+
+    let send = |values: &[&str]| {
+        let mut request = Client::new().get(server.url("/test"));
+        for value in values {
+            request = request.header("forwarded", *value);
+        }
+        request.send().unwrap()
+    };
+
+    // Act & Assert: the header must appear exactly twice, and single-value matchers
+    // match against any of its values
+    assert_eq!(send(&["for=192.0.2.60", "for=198.51.100.17"]).status(), 200);
+    assert_eq!(send(&["for=198.51.100.17"]).status(), 404);
+    assert_eq!(
+        send(&["for=192.0.2.60", "for=198.51.100.17", "for=203.0.113.1"]).status(),
+        404
+    );
+
+    m.assert_calls(1);
+
+    // All values of a repeated header are preserved in the recorded request
+    let request = m.last_request().unwrap();
+    let values: Vec<_> = request
+        .headers()
+        .get_all("forwarded")
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(values, vec!["for=192.0.2.60", "for=198.51.100.17"]);
+
+    let http_request = request.to_http_request();
+    assert_eq!(
+        http_request.headers().get_all("forwarded").iter().count(),
+        2
+    );
+}
+
+#[test]
+fn common_header_shortcuts_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .user_agent_includes("billing-client")
+            .accept_includes("application/json")
+            .content_type("application/json")
+            .authorization("Bearer abc123");
+        then.status(201);
+    });
+
+    let send = |content_type: &str| {
+        Client::new()
+            .post(server.url("/invoices"))
+            .header("USER-AGENT", "billing-client/2.1")
+            .header("accept", "text/html, application/json;q=0.9")
+            .header("Content-Type", content_type)
+            .header("Authorization", "Bearer abc123")
+            .body("{}")
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert: header names are matched case-insensitively, values exactly
+    assert_eq!(send("application/json").status(), 201);
+    assert_eq!(send("application/json; charset=utf-8").status(), 404);
+
+    m.assert_calls(1);
+}
@@ -139,3 +139,238 @@ fn partial_json_body_test() {
     m.assert();
     assert_eq!(response.status(), 201);
 }
+
+#[test]
+fn json_body_merge_test() {
+    // Arrange
+    let base_user = json!({
+        "name": "Peter",
+        "address": { "town": "Quahog", "zip": "00093" },
+        "roles": ["father", "husband"]
+    });
+
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/users/1");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(base_user)
+            .json_body_merge(json!({ "address": { "town": "Springfield" } }))
+            .json_body_merge(json!({ "roles": ["admin"], "age": 45 }));
+    });
+
+    // Act
+    let response = Client::new().get(server.url("/users/1")).send().unwrap();
+    let user: Value = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(
+        user,
+        json!({
+            "name": "Peter",
+            "address": { "town": "Springfield", "zip": "00093" },
+            "roles": ["admin"],
+            "age": 45
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "the base response body is not a JSON object")]
+fn json_body_merge_onto_non_object_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/users");
+        then.json_body(json!([{ "name": "Peter" }]))
+            .json_body_merge(json!({ "name": "Lois" }));
+    });
+}
+
+#[test]
+fn json_body_unordered_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/orders")
+            .json_body_unordered(json!({
+                "tags": ["express", "gift"],
+                "items": [
+                    { "sku": "A-1", "options": ["red", "large"] },
+                    { "sku": "B-2", "options": [] }
+                ]
+            }));
+        then.status(201);
+    });
+
+    let send = |body: Value| {
+        Client::new()
+            .post(server.url("/orders"))
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert: arrays are compared regardless of order at every level
+    let reordered = send(json!({
+        "items": [
+            { "options": [], "sku": "B-2" },
+            { "sku": "A-1", "options": ["large", "red"] }
+        ],
+        "tags": ["gift", "express"]
+    }));
+    assert_eq!(reordered.status(), 201);
+
+    // ... but elements must still be deeply equal and appear equally often
+    let duplicated_tag = send(json!({
+        "items": [
+            { "sku": "A-1", "options": ["red", "large"] },
+            { "sku": "B-2", "options": [] }
+        ],
+        "tags": ["gift", "gift"]
+    }));
+    assert_eq!(duplicated_tag.status(), 404);
+
+    let moved_option = send(json!({
+        "items": [
+            { "sku": "A-1", "options": ["red"] },
+            { "sku": "B-2", "options": ["large"] }
+        ],
+        "tags": ["express", "gift"]
+    }));
+    assert_eq!(moved_option.status(), 404);
+
+    m.assert_calls(1);
+}
+
+#[test]
+fn json_body_not_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/users")
+            .json_body_not(json!({ "name": "Peter", "roles": ["admin"] }));
+        then.status(201);
+    });
+
+    let send = |body: &str| {
+        Client::new()
+            .post(server.url("/users"))
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert: structurally equal bodies do not match, regardless of formatting
+    assert_eq!(
+        send(r#"{ "roles": ["admin"], "name": "Peter" }"#).status(),
+        404
+    );
+    assert_eq!(send(r#"{"name":"Peter","roles":["user"]}"#).status(), 201);
+
+    // A body that is not valid JSON is not equal to the value either
+    assert_eq!(send("name=Peter").status(), 201);
+
+    m.assert_calls(2);
+}
+
+#[test]
+fn json_body_has_key_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/orders")
+            .json_body_has_key("/meta/requestId")
+            .json_body_missing_key("/debug");
+        then.status(201);
+    });
+
+    let send = |body: &str| {
+        Client::new()
+            .post(server.url("/orders"))
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert: any value is accepted, including null
+    assert_eq!(
+        send(r#"{ "meta": { "requestId": "7b0c4f0e" } }"#).status(),
+        201
+    );
+    assert_eq!(send(r#"{ "meta": { "requestId": 42 } }"#).status(), 201);
+    assert_eq!(send(r#"{ "meta": { "requestId": null } }"#).status(), 201);
+
+    // The key is missing or a forbidden key is present (even if it is null)
+    assert_eq!(send(r#"{ "meta": {} }"#).status(), 404);
+    assert_eq!(
+        send(r#"{ "meta": { "requestId": "7b0c4f0e" }, "debug": null }"#).status(),
+        404
+    );
+
+    m.assert_calls(3);
+}
+
+#[test]
+#[should_panic]
+fn json_body_has_key_invalid_pointer_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.json_body_has_key("meta.requestId");
+        then.status(201);
+    });
+}
+
+#[test]
+fn json_body_includes_array_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/orders")
+            .json_body_includes_array("/items", &[json!({ "sku": "b" }), json!({ "sku": "a" })])
+            .json_body_includes_array("/tags", &["gift"]);
+        then.status(201);
+    });
+
+    let send = |body: &str| {
+        Client::new()
+            .post(server.url("/orders"))
+            .header("content-type", "application/json")
+            .body(body.to_string())
+            .send()
+            .unwrap()
+    };
+
+    // Act & Assert: elements are matched partially and in any order
+    assert_eq!(
+        send(r#"{ "items": [{ "sku": "a", "qty": 1 }, { "sku": "c" }, { "sku": "b" }], "tags": ["new", "gift"] }"#)
+            .status(),
+        201
+    );
+
+    // An element is missing or the value is not an array
+    assert_eq!(
+        send(r#"{ "items": [{ "sku": "a" }], "tags": ["gift"] }"#).status(),
+        404
+    );
+    assert_eq!(
+        send(r#"{ "items": [{ "sku": "a" }, { "sku": "b" }], "tags": "gift" }"#).status(),
+        404
+    );
+
+    m.assert_calls(1);
+}
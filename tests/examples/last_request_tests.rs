@@ -0,0 +1,142 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+};
+
+#[test]
+fn last_request_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/orders");
+        then.status(201);
+    });
+
+    assert!(m.last_request().is_none());
+
+    // Act
+    let client = Client::new();
+    for id in ["first", "second"] {
+        client
+            .post(server.url("/orders"))
+            .header("x-request-id", id)
+            .body(format!("{{\"id\":\"{}\"}}", id))
+            .send()
+            .unwrap();
+    }
+
+    // This request does not match the mock and must not be returned.
+    client.get(server.url("/orders")).send().unwrap();
+
+    // Assert
+    let request = m.last_request().expect("no request received");
+    assert_eq!(request.headers()["x-request-id"], "second");
+    assert_eq!(request.body_string(), "{\"id\":\"second\"}");
+}
+
+#[tokio::test]
+async fn last_request_async_test() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let m = server
+        .mock_async(|when, then| {
+            when.path("/orders");
+            then.status(201);
+        })
+        .await;
+
+    // Act
+    reqwest::get(server.url("/orders?id=42")).await.unwrap();
+
+    // Assert
+    let request = m.last_request_async().await.expect("no request received");
+    assert_eq!(request.uri().query(), Some("id=42"));
+}
+
+#[test]
+fn last_raw_request_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/webhook");
+        then.status(204);
+    });
+
+    // Act: Send the request by hand to control the exact bytes, including the order and
+    // case of repeated headers.
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .write_all(
+            b"POST /webhook?id=1 HTTP/1.1\r\n\
+              X-Trace: b\r\n\
+              Host: localhost\r\n\
+              x-trace:  a\r\n\
+              Content-Length: 7\r\n\
+              Connection: close\r\n\r\n\
+              payload",
+        )
+        .unwrap();
+    stream.read_to_end(&mut Vec::new()).unwrap();
+
+    // Assert
+    let raw = m.last_raw_request().expect("no raw request received");
+    assert_eq!(raw.start_line, "POST /webhook?id=1 HTTP/1.1");
+    assert_eq!(
+        raw.header_lines,
+        vec![
+            "X-Trace: b",
+            "Host: localhost",
+            "x-trace:  a",
+            "Content-Length: 7",
+            "Connection: close",
+        ]
+    );
+    assert_eq!(raw.body, "payload");
+}
+
+#[test]
+fn request_body_helpers_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/orders");
+        then.status(201);
+    });
+
+    let client = Client::new();
+    let send = |body: Vec<u8>| {
+        client
+            .post(server.url("/orders"))
+            .body(body)
+            .send()
+            .unwrap();
+        m.last_request().expect("no request received")
+    };
+
+    // Act & Assert: valid JSON
+    let request = send(br#"{"id":1,"items":["apple"]}"#.to_vec());
+    assert_eq!(
+        request.body_as_str().unwrap(),
+        r#"{"id":1,"items":["apple"]}"#
+    );
+    assert_eq!(
+        request.body_as_json().unwrap(),
+        serde_json::json!({ "id": 1, "items": ["apple"] })
+    );
+
+    // Act & Assert: parse errors contain the position
+    let request = send(b"{\n  \"id\": }".to_vec());
+    let err = request.body_as_json().unwrap_err();
+    assert_eq!(err.to_string(), "expected value at line 2 column 9");
+
+    // Act & Assert: binary bodies
+    let request = send(vec![0xff, 0xfe]);
+    assert!(request.body_as_str().is_none());
+    assert!(request.body_as_json().is_err());
+}
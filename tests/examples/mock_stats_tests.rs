@@ -0,0 +1,55 @@
+use httpmock::prelude::*;
+use reqwest::blocking::get;
+use std::time::SystemTime;
+
+#[test]
+fn mock_stats_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/stats");
+        then.status(200);
+    });
+
+    let stats = m.stats();
+    assert_eq!(stats.hits, 0);
+    assert_eq!(stats.last_hit_at, None);
+
+    // Act
+    let before = SystemTime::now();
+    get(server.url("/stats")).unwrap();
+    get(server.url("/stats")).unwrap();
+    let after = SystemTime::now();
+
+    // This request does not match the mock and must not be counted.
+    get(server.url("/other")).unwrap();
+
+    // Assert
+    let stats = m.stats();
+    assert_eq!(stats.hits, 2);
+
+    let last_hit_at = stats.last_hit_at.expect("no hit time recorded");
+    assert!(before <= last_hit_at && last_hit_at <= after);
+}
+
+#[tokio::test]
+async fn mock_stats_async_test() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let m = server
+        .mock_async(|when, then| {
+            when.path("/stats");
+            then.status(200);
+        })
+        .await;
+
+    // Act
+    reqwest::get(server.url("/stats")).await.unwrap();
+
+    // Assert
+    let stats = m.stats_async().await;
+    assert_eq!(stats.hits, 1);
+    assert!(stats.last_hit_at.is_some());
+}
@@ -1,21 +1,47 @@
+mod active_mocks_tests;
+mod assert_not_called_tests;
 mod binary_body_tests;
+#[cfg(feature = "proxy")]
+mod callback_tests;
+mod chunked_response_tests;
+mod client_cert_tests;
+mod closest_match_tests;
+#[cfg(feature = "compression")]
+mod compression_tests;
+mod condition_group_tests;
+mod conditional_tests;
 mod cookie_tests;
 mod custom_request_matcher_tests;
 mod delay_tests;
 mod delete_mock_tests;
+mod expiry_tests;
+mod fault_tests;
 mod file_body_tests;
 mod forwarding_tests;
 mod getting_started_tests;
 mod headers_tests;
 mod https_tests;
 mod json_body_tests;
+mod last_request_tests;
+mod mock_stats_tests;
 mod multi_server_tests;
+mod ndjson_tests;
+mod openapi_tests;
+mod priority_tests;
 mod proxy_tests;
 mod query_param_tests;
 mod record_and_playback_tests;
 mod reset_tests;
+mod respond_after_tests;
 mod showcase_tests;
 mod standalone_tests;
+mod state_tests;
+mod status_tests;
 mod string_body_tests;
+mod times_tests;
+mod unmatched_requests_tests;
 mod url_matching_tests;
+mod with_mocks_tests;
 mod x_www_form_urlencoded_tests;
+#[cfg(feature = "record")]
+mod yaml_mock_tests;
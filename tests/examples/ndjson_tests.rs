@@ -0,0 +1,74 @@
+use httpmock::prelude::*;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[test]
+fn ndjson_body_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/events");
+        then.status(200)
+            .ndjson(vec![json!({ "id": 1 }), json!({ "id": 2 })]);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let response = reqwest::blocking::get(server.url("/events")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    assert_eq!(response.text().unwrap(), "{\"id\":1}\n{\"id\":2}\n");
+}
+
+#[test]
+fn ndjson_keeps_existing_content_type_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/events");
+        then.status(200)
+            .header("Content-Type", "application/jsonl")
+            .ndjson_without_trailing_newline(vec![json!(1), json!("two")]);
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let response = reqwest::blocking::get(server.url("/events")).unwrap();
+
+    // Assert
+    assert_eq!(response.headers()["content-type"], "application/jsonl");
+    assert_eq!(response.text().unwrap(), "1\n\"two\"");
+}
+
+#[test]
+fn ndjson_stream_lines_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/events");
+        then.status(200)
+            .ndjson(vec![
+                json!({ "id": 1 }),
+                json!({ "id": 2 }),
+                json!({ "id": 3 }),
+            ])
+            .stream_lines(Duration::from_millis(100));
+    });
+
+    // Act: Send the HTTP request using reqwest
+    let start = Instant::now();
+    let response = reqwest::blocking::get(server.url("/events")).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert!(response.headers().get("content-length").is_none());
+    assert_eq!(
+        response.text().unwrap(),
+        "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n"
+    );
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "openapi")]
+use httpmock::prelude::*;
+#[cfg(feature = "openapi")]
+use reqwest::blocking::Client;
+
+#[cfg(feature = "openapi")]
+#[test]
+fn mocks_from_openapi_file_test() {
+    // Arrange
+    let server = MockServer::start();
+    let mocks = server.mocks_from_openapi("tests/resources/openapi_petstore.yaml");
+
+    // The operation referring to an external document is skipped
+    assert_eq!(mocks.ids.len(), 4);
+
+    let client = Client::new();
+
+    // Act & Assert: Schema derived example body
+    let response = client.get(server.url("/v1/pets")).send().unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert_eq!(
+        response.text().unwrap(),
+        r#"[{"id":0,"name":"Rex","tag":"dog"}]"#
+    );
+
+    // Act & Assert: Declared example body
+    let response = client.post(server.url("/v1/pets")).send().unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.text().unwrap(), r#"{"id":7,"name":"Fluffy"}"#);
+
+    // Act & Assert: Path templates match any value
+    let response = client.get(server.url("/v1/pets/42")).send().unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.text().unwrap(),
+        r#"{"id":0,"name":"Rex","tag":"dog"}"#
+    );
+
+    let response = client.delete(server.url("/v1/pets/42")).send().unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = client.get(server.url("/v1/pets/42/toys")).send().unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[cfg(feature = "openapi")]
+#[test]
+#[should_panic(expected = "unsupported OpenAPI version: 2.0")]
+fn mocks_from_openapi_unsupported_version_test() {
+    let server = MockServer::start();
+    server.mocks_from_openapi(r#"{ "openapi": "2.0", "paths": {} }"#);
+}
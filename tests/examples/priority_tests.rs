@@ -0,0 +1,159 @@
+use crate::matchers::expect_fails_with;
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServerBuilder, MatchStrategy},
+};
+
+#[test]
+fn priority_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let generic_mock = server.mock(|when, then| {
+        when.path("/search");
+        then.status(200).body("generic");
+    });
+
+    let specific_mock = server.mock(|when, then| {
+        when.path("/search")
+            .query_param("query", "metallica")
+            .priority(10);
+        then.status(200).body("specific");
+    });
+
+    let fallback_mock = server.mock(|when, then| {
+        when.any_request().priority(-1);
+        then.status(404);
+    });
+
+    // Act
+    let specific = reqwest::blocking::get(server.url("/search?query=metallica")).unwrap();
+    let generic = reqwest::blocking::get(server.url("/search?query=slayer")).unwrap();
+    let fallback = reqwest::blocking::get(server.url("/other")).unwrap();
+
+    // Assert
+    specific_mock.assert();
+    generic_mock.assert();
+    fallback_mock.assert();
+    assert_eq!(specific.text().unwrap(), "specific");
+    assert_eq!(generic.text().unwrap(), "generic");
+    assert_eq!(fallback.status(), 404);
+}
+
+#[test]
+fn priority_shadowed_mock_failure_test() {
+    expect_fails_with(
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.path("/search");
+                then.status(200);
+            });
+
+            let shadowing_mock = server.mock(|when, then| {
+                when.path_includes("search").priority(1);
+                then.status(200);
+            });
+
+            // Act
+            reqwest::blocking::get(server.url("/search")).unwrap();
+
+            // Assert
+            shadowing_mock.assert();
+            m.assert()
+        },
+        vec![
+            "0 of 1 expected requests matched the mock specification",
+            "1 request(s) matching this mock were served by a mock with a higher priority",
+        ],
+    )
+}
+
+#[test]
+fn first_defined_match_strategy_test() {
+    // Arrange
+    let server = MockServer::start_with(
+        HttpMockServerBuilder::new().match_strategy(MatchStrategy::FirstDefined),
+    );
+
+    let first_mock = server.mock(|when, then| {
+        when.path("/search");
+        then.status(200).body("first");
+    });
+
+    let prioritized_mock = server.mock(|when, then| {
+        when.path("/search").priority(10);
+        then.status(200).body("prioritized");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/search")).unwrap();
+
+    // Assert
+    assert_eq!(response.text().unwrap(), "first");
+    first_mock.assert();
+    prioritized_mock.assert_calls(0);
+}
+
+#[test]
+fn most_specific_match_strategy_test() {
+    // Arrange
+    let server = MockServer::start_with(
+        HttpMockServerBuilder::new().match_strategy(MatchStrategy::MostSpecific),
+    );
+
+    let generic_mock = server.mock(|when, then| {
+        when.path("/search");
+        then.status(200).body("generic");
+    });
+
+    let specific_mock = server.mock(|when, then| {
+        when.path("/search")
+            .query_param("query", "metallica")
+            .header_exists("accept");
+        then.status(200).body("specific");
+    });
+
+    // Same number of constraints as the generic mock, so the higher priority decides.
+    let prioritized_mock = server.mock(|when, then| {
+        when.query_param("query", "slayer").priority(1);
+        then.status(200).body("prioritized");
+    });
+
+    let client = reqwest::blocking::Client::new();
+    let get = |path: &str| {
+        client
+            .get(server.url(path))
+            .header("accept", "*/*")
+            .send()
+            .unwrap()
+            .text()
+            .unwrap()
+    };
+
+    // Act
+    let specific = get("/search?query=metallica");
+    let prioritized = get("/search?query=slayer");
+    let generic = get("/search?query=ghost");
+
+    // Assert
+    assert_eq!(specific, "specific");
+    assert_eq!(prioritized, "prioritized");
+    assert_eq!(generic, "generic");
+    specific_mock.assert();
+    prioritized_mock.assert();
+    generic_mock.assert();
+}
+
+#[test]
+fn parse_match_strategy_test() {
+    assert_eq!("first-defined".parse(), Ok(MatchStrategy::FirstDefined));
+    assert_eq!("Most_Specific".parse(), Ok(MatchStrategy::MostSpecific));
+    assert_eq!(
+        "highest-priority".parse(),
+        Ok(MatchStrategy::HighestPriority)
+    );
+    assert!("random".parse::<MatchStrategy>().is_err());
+}
@@ -70,3 +70,106 @@ fn url_param_encoding_issue_56() {
     // Assert
     m.assert();
 }
+
+#[test]
+fn url_with_query_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/search")
+            .query_param("query", "Guns N' Roses & Metallica")
+            .query_param("page", "2")
+            .query_param("sort", "a=b");
+        then.status(200);
+    });
+
+    // Act
+    let url = server.url_with_query(
+        "/search?page=2",
+        &[("query", "Guns N' Roses & Metallica"), ("sort", "a=b")],
+    );
+    let response = reqwest::blocking::get(&url).unwrap();
+
+    // Assert
+    assert_eq!(
+        url,
+        format!(
+            "{}/search?page=2&query=Guns+N%27+Roses+%26+Metallica&sort=a%3Db",
+            server.base_url()
+        )
+    );
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        server.url_with_query::<_, &str, &str>("/search", &[]),
+        server.url("/search")
+    );
+    m.assert();
+}
+
+#[test]
+fn raw_query_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let exact = server.mock(|when, then| {
+        when.path("/exact").raw_query("debug;debug;id=a%2Fb");
+        then.status(200);
+    });
+
+    let partial = server.mock(|when, then| {
+        when.path("/partial")
+            .raw_query_includes("id=a%2Fb")
+            .raw_query_matches(r"^(debug;)+");
+        then.status(200);
+    });
+
+    let no_query = server.mock(|when, then| {
+        when.path("/none").raw_query("");
+        then.status(200);
+    });
+
+    let get = |url: String| reqwest::blocking::get(url).unwrap().status();
+
+    // Act & Assert: the query string is compared without decoding it
+    assert_eq!(get(server.url("/exact?debug;debug;id=a%2Fb")), 200);
+    assert_eq!(get(server.url("/exact?debug;id=a%2Fb;debug")), 404);
+    assert_eq!(get(server.url("/exact?debug;debug;id=a/b")), 404);
+
+    assert_eq!(get(server.url("/partial?debug;debug;id=a%2Fb")), 200);
+    assert_eq!(get(server.url("/partial?id=a%2Fb;debug")), 404);
+
+    assert_eq!(get(server.url("/none")), 200);
+    assert_eq!(get(server.url("/none?x")), 404);
+
+    exact.assert_calls(1);
+    partial.assert_calls(1);
+    no_query.assert_calls(1);
+}
+
+#[test]
+fn query_param_value_matches_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    // The name contains regex meta characters, which must be matched literally.
+    let m = server.mock(|when, then| {
+        when.path("/search")
+            .query_param_value_matches("filter[id]", r"^\d+$");
+        then.status(200);
+    });
+
+    let get = |url: String| reqwest::blocking::get(url).unwrap().status();
+
+    // Act & Assert
+    assert_eq!(get(server.url("/search?filter%5Bid%5D=42")), 200);
+    assert_eq!(
+        get(server.url("/search?filter%5Bid%5D=abc&filter%5Bid%5D=7")),
+        200
+    );
+    assert_eq!(get(server.url("/search?filter%5Bid%5D=abc")), 404);
+    assert_eq!(get(server.url("/search?filteri=42")), 404);
+    assert_eq!(get(server.url("/search?my_filter%5Bid%5D=42")), 404);
+
+    m.assert_calls(2);
+}
@@ -206,3 +206,329 @@ fn playback_github_api() {
     assert!(response.text().unwrap().contains("\"private\":false"));
 }
 // @example-end
+
+#[cfg(feature = "record")]
+#[test]
+fn record_with_redaction_test() {
+    // Arrange
+    let target_server = MockServer::start();
+    target_server.mock(|when, then| {
+        when.path("/login");
+        then.status(200)
+            .header("set-cookie", "session=secret-session")
+            .body(r#"{"user":"peter","token":"secret-token"}"#);
+    });
+
+    let recording_server = MockServer::start();
+    recording_server.forward_to(target_server.base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let recording = recording_server.record(|rule| {
+        rule.record_request_header("Authorization")
+            .redact_header("Authorization")
+            .redact_header("Set-Cookie")
+            .redact_json_path("$.password")
+            .redact_json_path("$.token")
+            .redaction_placeholder("<redacted>")
+            .filter(|when| {
+                when.any_request();
+            });
+    });
+
+    let client = Client::new();
+    let send_login = |base_url: String| {
+        client
+            .post(format!("{}/login", base_url))
+            .header("Authorization", "Bearer secret-bearer")
+            .body(r#"{"user":"peter","password":"secret-password"}"#)
+            .send()
+            .unwrap()
+    };
+
+    // Act
+    let response = send_login(recording_server.base_url());
+    assert_eq!(response.status(), 200);
+
+    let target_path = recording.save("redaction_scenario").unwrap();
+
+    // Assert
+    let content = std::fs::read_to_string(&target_path).unwrap();
+    assert!(!content.contains("secret"));
+    assert!(content.contains("<redacted>"));
+
+    let playback_server = MockServer::start();
+    playback_server.playback(target_path);
+
+    let response = send_login(playback_server.base_url());
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("set-cookie").unwrap(), "<redacted>");
+    assert_eq!(
+        response.text().unwrap(),
+        r#"{"token":"<redacted>","user":"peter"}"#
+    );
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn record_with_skipped_responses_test() {
+    // Arrange
+    let target_server = MockServer::start();
+    target_server.mock(|when, then| {
+        when.path("/user");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(r#"{"user":"peter"}"#);
+    });
+    target_server.mock(|when, then| {
+        when.path("/avatar");
+        then.status(200)
+            .header("content-type", "image/png")
+            .body(vec![0u8; 16]);
+    });
+    target_server.mock(|when, then| {
+        when.path("/export");
+        then.status(200)
+            .header("content-type", "text/csv")
+            .body("a".repeat(2048));
+    });
+
+    let recording_server = MockServer::start();
+    recording_server.forward_to(target_server.base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let recording = recording_server.record(|rule| {
+        rule.skip_response_larger_than(1024)
+            .skip_content_type("IMAGE/")
+            .filter(|when| {
+                when.any_request();
+            });
+    });
+
+    // Act
+    for path in ["/user", "/avatar", "/export"] {
+        let response = reqwest::blocking::get(recording_server.url(path)).unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    let target_path = recording.save("skipped_responses_scenario").unwrap();
+
+    // Assert: only the small JSON response was saved
+    let content = std::fs::read_to_string(&target_path).unwrap();
+    assert!(content.contains("/user"));
+    assert!(!content.contains("/avatar"));
+    assert!(!content.contains("/export"));
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn playback_ordered_test() {
+    // Arrange: record two identical requests that received different responses
+    let target_server = MockServer::start();
+    let mut pending_mock = target_server.mock(|when, then| {
+        when.path("/job");
+        then.status(200).body("pending");
+    });
+
+    let recording_server = MockServer::start();
+    recording_server.forward_to(target_server.base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let recording = recording_server.record(|rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let client = Client::new();
+    client.get(recording_server.url("/job")).send().unwrap();
+
+    pending_mock.delete();
+    target_server.mock(|when, then| {
+        when.path("/job");
+        then.status(200).body("done");
+    });
+    client.get(recording_server.url("/job")).send().unwrap();
+
+    let target_path = recording.save("ordered_playback_scenario").unwrap();
+
+    // Act
+    let playback_server = MockServer::start();
+    playback_server.playback_ordered(target_path);
+
+    let get = |path: &str| client.get(playback_server.url(path)).send().unwrap();
+
+    // Assert: each interaction is served once and in the recorded order
+    assert_eq!(get("/job").text().unwrap(), "pending");
+    assert_eq!(get("/job").text().unwrap(), "done");
+
+    let response = get("/job");
+    assert_eq!(response.status(), 500);
+    assert!(response
+        .text()
+        .unwrap()
+        .contains("all 2 recorded interactions have already been played back"));
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn playback_ordered_out_of_order_test() {
+    // Arrange
+    let recording = r#"
+when:
+  method: GET
+  path: /first
+then:
+  status: 200
+  body: first
+---
+when:
+  method: GET
+  path: /second
+then:
+  status: 200
+  body: second
+"#;
+
+    let path = std::env::temp_dir().join("httpmock_playback_ordered_out_of_order_test.yaml");
+    std::fs::write(&path, recording).unwrap();
+
+    let server = MockServer::start();
+    let regular_mock = server.mock(|when, then| {
+        when.path("/health");
+        then.status(204);
+    });
+    server.playback_ordered(&path);
+
+    // Act
+    let health_response = reqwest::blocking::get(server.url("/health")).unwrap();
+    let out_of_order_response = reqwest::blocking::get(server.url("/second")).unwrap();
+    let first_response = reqwest::blocking::get(server.url("/first")).unwrap();
+    let second_response = reqwest::blocking::get(server.url("/second")).unwrap();
+
+    // Assert: regular mocks are not affected by the ordered playback
+    assert_eq!(health_response.status(), 204);
+    regular_mock.assert();
+
+    assert_eq!(out_of_order_response.status(), 500);
+    let message = out_of_order_response.text().unwrap();
+    assert!(message
+        .contains("GET /second does not match the next recorded interaction (number 1 of 2)"));
+    assert!(message.contains("Path Mismatch"));
+    assert!(message.contains("/first"));
+
+    assert_eq!(first_response.text().unwrap(), "first");
+    assert_eq!(second_response.text().unwrap(), "second");
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn playback_with_timing_test() {
+    use std::time::{Duration, Instant};
+
+    // Arrange: record a request against a slow upstream server
+    let target_server = MockServer::start();
+    target_server.mock(|when, then| {
+        when.path("/report");
+        then.status(200)
+            .body("done")
+            .delay(Duration::from_millis(300));
+    });
+
+    let recording_server = MockServer::start();
+    recording_server.forward_to(target_server.base_url(), |rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let recording = recording_server.record(|rule| {
+        rule.filter(|when| {
+            when.any_request();
+        });
+    });
+
+    let client = Client::new();
+    client.get(recording_server.url("/report")).send().unwrap();
+
+    let target_path = recording.save("playback_with_timing_scenario").unwrap();
+
+    let content = std::fs::read_to_string(&target_path).unwrap();
+    assert!(content.contains("duration_ms:"));
+    assert!(!content.contains("delay:"));
+
+    let timed_get = |server: &MockServer| {
+        let start = Instant::now();
+        let response = client.get(server.url("/report")).send().unwrap();
+        assert_eq!(response.text().unwrap(), "done");
+        start.elapsed()
+    };
+
+    // Act & Assert: a regular playback ignores the recorded duration ...
+    let playback_server = MockServer::start();
+    playback_server.playback(&target_path);
+    assert!(timed_get(&playback_server) < Duration::from_millis(300));
+
+    // ... while a playback with timing reproduces it
+    let timed_playback_server = MockServer::start();
+    timed_playback_server.playback_with_timing(&target_path);
+    assert!(timed_get(&timed_playback_server) >= Duration::from_millis(300));
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn playback_har_test() {
+    // Arrange
+    let server = MockServer::start();
+    let mocks = server.playback_har("tests/resources/users.har");
+    assert_eq!(mocks.ids.len(), 2);
+
+    let client = Client::new();
+
+    // Act & Assert: Query parameters may be sent in a different order
+    let response = client
+        .get(server.url("/users?limit=10&page=2"))
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    assert!(response.headers().get("content-encoding").is_none());
+    assert_eq!(
+        response.text().unwrap(),
+        r#"[{"id":11,"name":"Peter Griffin"}]"#
+    );
+
+    // Act & Assert: Base64 encoded bodies are decoded
+    let response = client.post(server.url("/users")).send().unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.text().unwrap(), "created");
+
+    // Act & Assert: Requests that do not match any entry are not served
+    let response = client.get(server.url("/users?page=3")).send().unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[cfg(feature = "record")]
+#[test]
+#[should_panic(expected = "invalid HAR entry 0: request.url is missing")]
+fn playback_har_invalid_file_test() {
+    let path = std::env::temp_dir().join("httpmock_playback_har_invalid_file_test.har");
+    std::fs::write(
+        &path,
+        r#"{ "log": { "entries": [{ "request": { "method": "GET" }, "response": {} }] } }"#,
+    )
+    .unwrap();
+
+    MockServer::start().playback_har(&path);
+}
@@ -31,3 +31,55 @@ async fn reset_server_test() {
     hello_mock.assert();
     assert_eq!(response.status(), 200);
 }
+
+#[test]
+fn reset_history_test() {
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/items").query_param_exists("page");
+        then.status(200);
+    });
+
+    for page in 1..=3 {
+        reqwest::blocking::get(server.url(format!("/items?page={}", page))).unwrap();
+
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.query_params()["page"], page.to_string());
+
+        server.reset_history();
+        assert!(mock.last_request().is_none());
+    }
+
+    // The mock was kept between iterations and call counts are not reset
+    mock.assert_calls(3);
+}
+
+#[test]
+fn reset_mocks_test() {
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/items");
+        then.status(200);
+    });
+
+    reqwest::blocking::get(server.url("/other")).unwrap();
+    assert!(mock.closest_match().is_some());
+
+    server.reset_mocks();
+
+    let response = reqwest::blocking::get(server.url("/items")).unwrap();
+    assert_eq!(response.status(), 404);
+
+    // The request history is kept
+    let new_mock = server.mock(|when, then| {
+        when.path("/items");
+        then.status(200);
+    });
+    assert_eq!(new_mock.calls(), 0);
+    assert_eq!(
+        new_mock.closest_match().unwrap().request.uri().path(),
+        "/other"
+    );
+}
@@ -0,0 +1,57 @@
+use httpmock::prelude::*;
+
+#[test]
+fn respond_after_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/orders");
+        then.status(503)
+            .respond_after(2, |then| {
+                then.status(200).body("processing");
+            })
+            .respond_after(4, |then| {
+                then.status(201).body("done");
+            });
+    });
+
+    // Act
+    let responses: Vec<(u16, String)> = (0..6)
+        .map(|_| {
+            let response = reqwest::blocking::get(server.url("/orders")).unwrap();
+            (response.status().as_u16(), response.text().unwrap())
+        })
+        .collect();
+
+    // Assert
+    let expected = [
+        (503, ""),
+        (503, ""),
+        (200, "processing"),
+        (200, "processing"),
+        (201, "done"),
+        (201, "done"),
+    ];
+    for (actual, expected) in responses.iter().zip(expected.iter()) {
+        assert_eq!(actual.0, expected.0);
+        assert_eq!(actual.1, expected.1);
+    }
+
+    mock.assert_calls(6);
+}
+
+#[test]
+#[should_panic]
+fn respond_after_nested_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/orders");
+        then.status(503).respond_after(1, |then| {
+            then.status(200).respond_after(2, |then| {
+                then.status(201);
+            });
+        });
+    });
+}
@@ -142,3 +142,61 @@ fn binary_body_standalone_test() {
 
     assert_eq!(buf, binary_content.to_vec());
 }
+
+#[cfg(feature = "remote")]
+#[test]
+fn tagged_mocks_standalone_test() {
+    use crate::with_standalone_server;
+    use httpmock::MockServer;
+
+    // Arrange
+    with_standalone_server();
+    let server = MockServer::connect("localhost:5050");
+
+    // The standalone server is shared between tests, so the tag must be unique.
+    let tag = "standalone tags & more";
+    server.mock(|when, then| {
+        when.path("/tagged-standalone").tag(tag);
+        then.status(200);
+    });
+
+    // Act
+    reqwest::blocking::get(server.url("/tagged-standalone")).unwrap();
+
+    // Assert
+    server.assert_tag_called(tag, 1);
+    assert_eq!(server.delete_mocks_by_tag(tag), 1);
+    let response = reqwest::blocking::get(server.url("/tagged-standalone")).unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn active_mocks_standalone_test() {
+    use crate::with_standalone_server;
+    use httpmock::MockServer;
+
+    // Arrange
+    with_standalone_server();
+    let server = MockServer::connect("localhost:5050");
+
+    // The standalone server is shared between tests, so other mocks may be registered too.
+    let mut mock = server.mock(|when, then| {
+        when.path("/active-mocks-standalone");
+        then.status(200);
+    });
+
+    // Act
+    let mocks = server.active_mocks();
+
+    // Assert
+    let active = mocks
+        .iter()
+        .find(|m| m.id == mock.id)
+        .expect("mock not listed");
+    assert_eq!(
+        active.definition.request.path.as_deref(),
+        Some("/active-mocks-standalone")
+    );
+    mock.delete();
+}
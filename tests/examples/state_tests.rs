@@ -0,0 +1,128 @@
+use httpmock::prelude::*;
+use reqwest::blocking::get;
+use std::{env, fs};
+
+#[test]
+fn save_and_load_state_test() {
+    // Arrange
+    let path = env::temp_dir().join("httpmock_save_and_load_state_test.json");
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/hello");
+        then.status(200).body("world");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/teapot");
+        then.status(418);
+    });
+
+    // Act
+    server.save_state(&path).unwrap();
+
+    let restored = MockServer::start();
+    restored.mock(|when, then| {
+        when.path("/previous");
+        then.status(200);
+    });
+    restored.load_state(&path).unwrap();
+
+    // Assert: the mocks of the snapshot replace the existing mocks
+    let response = get(restored.url("/hello")).unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().unwrap(), "world");
+    assert_eq!(get(restored.url("/teapot")).unwrap().status(), 418);
+    assert_eq!(get(restored.url("/previous")).unwrap().status(), 404);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn load_state_unsupported_version_test() {
+    // Arrange
+    let path = env::temp_dir().join("httpmock_load_state_unsupported_version_test.json");
+    fs::write(&path, r#"{ "version": 0, "mocks": [] }"#).unwrap();
+
+    let server = MockServer::start();
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    let result = server.load_state(&path);
+
+    // Assert: loading fails and the existing mocks are left unchanged
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.contains("unsupported version 0 (expected version 1)"),
+        "{}",
+        err
+    );
+
+    assert_eq!(get(server.url("/hello")).unwrap().status(), 200);
+    m.assert();
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn save_state_custom_matcher_test() {
+    // Arrange
+    let path = env::temp_dir().join("httpmock_save_state_custom_matcher_test.json");
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.is_true(|req| req.uri().path() == "/hello");
+        then.status(200);
+    });
+
+    // Act
+    let result = server.save_state(&path);
+
+    // Assert
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("uses custom matcher functions"), "{}", err);
+}
+
+#[test]
+fn load_state_invalid_mock_test() {
+    // Arrange
+    let path = env::temp_dir().join("httpmock_load_state_invalid_mock_test.json");
+
+    let source = MockServer::start();
+    source.mock(|when, then| {
+        when.path("/valid");
+        then.status(200);
+    });
+    source.mock(|when, then| {
+        when.path("/invalid");
+        then.status(200);
+    });
+    source.save_state(&path).unwrap();
+
+    // The second mock refers to a matcher that is not registered on the server
+    let mut snapshot: serde_json::Value =
+        serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    snapshot["mocks"][1]["request"]["matcher_named"] =
+        serde_json::json!([{ "name": "unknown", "args": null }]);
+    fs::write(&path, serde_json::to_vec(&snapshot).unwrap()).unwrap();
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    let result = server.load_state(&path);
+
+    // Assert: none of the snapshot mocks is loaded and the existing mocks are left unchanged
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("unknown matcher 'unknown'"), "{}", err);
+
+    assert_eq!(get(server.url("/hello")).unwrap().status(), 200);
+    assert_eq!(get(server.url("/valid")).unwrap().status(), 404);
+
+    fs::remove_file(&path).unwrap();
+}
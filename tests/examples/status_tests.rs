@@ -0,0 +1,227 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn status_shorthand_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let created = server.mock(|when, then| {
+        when.method(POST).path("/users");
+        then.created().body("{}");
+    });
+
+    let missing = server.mock(|when, then| {
+        when.path("/missing");
+        then.not_found();
+    });
+
+    let failing = server.mock(|when, then| {
+        when.path("/failing");
+        then.internal_server_error();
+    });
+
+    let client = Client::new();
+
+    // Act
+    let created_response = client.post(server.url("/users")).send().unwrap();
+    let missing_response = client.get(server.url("/missing")).send().unwrap();
+    let failing_response = client.get(server.url("/failing")).send().unwrap();
+
+    // Assert
+    assert_eq!(created_response.status(), 201);
+    assert_eq!(missing_response.status(), 404);
+    assert_eq!(failing_response.status(), 500);
+    created.assert();
+    missing.assert();
+    failing.assert();
+}
+
+#[test]
+fn no_content_removes_body_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    // The body is configured before the status, so it is removed
+    let deleted = server.mock(|when, then| {
+        when.method(DELETE).path("/users/1");
+        then.body("ignored").no_content();
+    });
+
+    let unchanged = server.mock(|when, then| {
+        when.path("/users/1");
+        then.body("ignored").not_modified();
+    });
+
+    let client = Client::new();
+
+    // Act
+    let deleted_response = client.delete(server.url("/users/1")).send().unwrap();
+    let unchanged_response = client.get(server.url("/users/1")).send().unwrap();
+
+    // Assert
+    assert_eq!(deleted_response.status(), 204);
+    assert_eq!(deleted_response.text().unwrap(), "");
+    assert_eq!(unchanged_response.status(), 304);
+    assert_eq!(unchanged_response.text().unwrap(), "");
+    deleted.assert();
+    unchanged.assert();
+}
+
+#[test]
+#[should_panic(expected = "responses with status code 304 must not have a body")]
+fn body_after_not_modified_is_rejected_test() {
+    let server = MockServer::start();
+
+    // The body is configured after the status, so it cannot be removed silently
+    server.mock(|when, then| {
+        when.path("/users/1");
+        then.not_modified().body("ignored");
+    });
+}
+
+#[test]
+#[should_panic(expected = "responses with status code 204 must not have a body")]
+fn body_with_no_content_status_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/users/1");
+        then.status(204)
+            .json_body_merge(serde_json::json!({ "id": 1 }));
+    });
+}
+
+#[test]
+fn rate_limited_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let seconds = server.mock(|when, then| {
+        when.path("/seconds");
+        then.header("Retry-After", "100")
+            .rate_limited(Duration::from_millis(2500))
+            .body("slow down");
+    });
+
+    let date = server.mock(|when, then| {
+        when.path("/date");
+        then.rate_limited_until(UNIX_EPOCH + Duration::from_secs(1445412480));
+    });
+
+    let client = Client::new();
+
+    // Act
+    let seconds_response = client.get(server.url("/seconds")).send().unwrap();
+    let date_response = client.get(server.url("/date")).send().unwrap();
+
+    // Assert
+    seconds.assert();
+    date.assert();
+    assert_eq!(seconds_response.status(), 429);
+    assert_eq!(
+        seconds_response
+            .headers()
+            .get_all("retry-after")
+            .iter()
+            .collect::<Vec<_>>(),
+        vec!["3"]
+    );
+    assert_eq!(seconds_response.text().unwrap(), "slow down");
+    assert_eq!(date_response.status(), 429);
+    assert_eq!(
+        date_response.headers()["retry-after"],
+        "Wed, 21 Oct 2015 07:28:00 GMT"
+    );
+}
+
+#[cfg(feature = "record")]
+#[test]
+fn rate_limited_in_yaml_test() {
+    use httpmock::server::HttpMockServerBuilder;
+    use std::fs;
+
+    // Arrange
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("rate_limited_in_yaml_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("rate_limit.yaml"),
+        "when:\n  path: /api\nthen:\n  rate_limited:\n    retry_after_secs: 30\n",
+    )
+    .unwrap();
+
+    let server = MockServer::start_with(HttpMockServerBuilder::new().static_mock_dir(dir));
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/api")).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 429);
+    assert_eq!(response.headers()["retry-after"], "30");
+}
+
+fn read_status_line(server: &MockServer, path: &str) -> String {
+    use std::io::{Read, Write};
+
+    let mut stream = std::net::TcpStream::connect(server.address()).unwrap();
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path,
+        server.address()
+    );
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.lines().next().unwrap_or_default().to_string()
+}
+
+#[test]
+fn custom_reason_phrase_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let custom = server.mock(|when, then| {
+        when.path("/custom");
+        then.status(200).reason("All Good");
+    });
+
+    let standard = server.mock(|when, then| {
+        when.path("/standard");
+        then.status(404);
+    });
+
+    // Act
+    let custom_status_line = read_status_line(&server, "/custom");
+    let standard_status_line = read_status_line(&server, "/standard");
+
+    // Assert
+    assert_eq!(custom_status_line, "HTTP/1.1 200 All Good");
+    assert_eq!(standard_status_line, "HTTP/1.1 404 Not Found");
+    custom.assert();
+    standard.assert();
+}
+
+#[test]
+#[should_panic(expected = "invalid reason phrase")]
+fn non_ascii_reason_phrase_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/custom");
+        then.status(200).reason("Très bien");
+    });
+}
+
+#[test]
+#[should_panic(expected = "invalid reason phrase")]
+fn reason_phrase_with_line_break_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/custom");
+        then.status(200).reason("OK\r\nX-Injected: true");
+    });
+}
@@ -0,0 +1,78 @@
+use httpmock::prelude::*;
+
+#[test]
+fn times_fallback_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let failing_mock = server.mock(|when, then| {
+        when.path("/orders").times(2);
+        then.status(503);
+    });
+
+    let fallback_mock = server.mock(|when, then| {
+        when.path("/orders");
+        then.status(200).body("ok");
+    });
+
+    // Act
+    let statuses: Vec<u16> = (0..4)
+        .map(|_| {
+            reqwest::blocking::get(server.url("/orders"))
+                .unwrap()
+                .status()
+                .as_u16()
+        })
+        .collect();
+
+    // Assert
+    assert_eq!(statuses, vec![503, 503, 200, 200]);
+    failing_mock.assert_calls(2);
+    fallback_mock.assert_calls(2);
+}
+
+#[test]
+fn times_exhausted_without_fallback_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/token").times(1);
+        then.status(200);
+    });
+
+    // Act
+    let first = reqwest::blocking::get(server.url("/token")).unwrap();
+    let second = reqwest::blocking::get(server.url("/token")).unwrap();
+
+    // Assert
+    assert_eq!(first.status(), 200);
+    assert_eq!(second.status(), 404);
+    mock.assert();
+}
+
+#[test]
+fn times_with_priority_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let fallback_mock = server.mock(|when, then| {
+        when.path("/orders");
+        then.status(200);
+    });
+
+    let limited_mock = server.mock(|when, then| {
+        when.path("/orders").times(1).priority(10);
+        then.status(429);
+    });
+
+    // Act
+    let first = reqwest::blocking::get(server.url("/orders")).unwrap();
+    let second = reqwest::blocking::get(server.url("/orders")).unwrap();
+
+    // Assert
+    assert_eq!(first.status(), 429);
+    assert_eq!(second.status(), 200);
+    limited_mock.assert();
+    fallback_mock.assert();
+}
@@ -0,0 +1,81 @@
+use httpmock::prelude::*;
+use reqwest::blocking::Client;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn no_unmatched_requests_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    m.assert();
+    server.assert_no_unmatched_requests();
+}
+
+#[test]
+fn unmatched_requests_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST).path("/orders").header("x-tenant", "acme");
+        then.status(201);
+    });
+
+    // Act
+    Client::new()
+        .post(server.url("/orders"))
+        .header("x-tenant", "acme")
+        .send()
+        .unwrap();
+    Client::new()
+        .post(server.url("/orders"))
+        .header("x-tenant", "globex")
+        .send()
+        .unwrap();
+
+    // Assert
+    let result = catch_unwind(AssertUnwindSafe(|| server.assert_no_unmatched_requests()));
+    let err = result.expect_err("expected unmatched requests");
+    let message = err.downcast_ref::<String>().unwrap();
+
+    assert!(message.contains("1 request(s) did not match any mock."));
+    assert!(message.contains("Request number 2: POST /orders"));
+    assert!(message.contains(&format!("most similar mock (mock ID {})", m.id)));
+    assert!(message.contains("Header Mismatch"));
+    assert!(message.contains("x-tenant"));
+}
+
+#[test]
+#[should_panic(expected = "No mocks were defined on the mock server.")]
+fn unmatched_requests_without_mocks_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    // Act
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    server.assert_no_unmatched_requests();
+}
+
+#[test]
+fn unmatched_requests_reset_history_test() {
+    // Arrange
+    let server = MockServer::start();
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Act
+    server.reset_history();
+
+    // Assert
+    server.assert_no_unmatched_requests();
+}
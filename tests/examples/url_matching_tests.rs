@@ -19,3 +19,154 @@ fn url_matching_test() {
     // Assert
     m.assert();
 }
+
+#[test]
+fn path_template_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(GET).path_template("/users/{user_id}/orders/{order_id}");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body_template(
+                r#"{"user": "{{ path.user_id }}", "order": "{{path.order_id}}", "note": "{{ path.unknown }}"}"#,
+            );
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/users/alice/orders/17")).unwrap();
+    let too_long = reqwest::blocking::get(server.url("/users/alice/orders/17/items")).unwrap();
+    let too_short = reqwest::blocking::get(server.url("/users/alice/orders")).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.text().unwrap(),
+        r#"{"user": "alice", "order": "17", "note": ""}"#
+    );
+    assert_eq!(too_long.status(), 404);
+    assert_eq!(too_short.status(), 404);
+
+    m.assert_calls(1);
+}
+
+#[test]
+fn body_from_template_file_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(GET).path_template("/users/{id}");
+        then.status(200)
+            .body_from_template_file("tests/resources/user_template.json");
+    });
+
+    // Act
+    let response = reqwest::blocking::Client::new()
+        .get(server.url("/users/7?q=admins"))
+        .header("X-Tenant", "acme")
+        .send()
+        .unwrap();
+    let without_values = reqwest::blocking::get(server.url("/users/8")).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.text().unwrap().trim_end(),
+        r#"{"id": "7", "query": "admins", "tenant": "acme"}"#
+    );
+    assert_eq!(
+        without_values.text().unwrap().trim_end(),
+        r#"{"id": "8", "query": "", "tenant": ""}"#
+    );
+
+    m.assert_calls(2);
+}
+
+#[test]
+#[should_panic(expected = "Cannot read from file")]
+fn body_from_template_file_with_missing_file_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|_, then| {
+        then.body_from_template_file("tests/resources/does_not_exist.json");
+    });
+}
+
+#[test]
+#[should_panic(expected = "contains the parameter 'id' more than once")]
+fn path_template_with_duplicate_parameter_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path_template("/users/{id}/friends/{id}");
+        then.status(200);
+    });
+}
+
+#[test]
+fn strict_trailing_slash_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let strict = server.mock(|when, then| {
+        when.path("/strict");
+        then.status(200);
+    });
+
+    let lenient = server.mock(|when, then| {
+        when.path("/lenient/").strict_trailing_slash(false);
+        then.status(200);
+    });
+
+    let get = |path: &str| reqwest::blocking::get(server.url(path)).unwrap().status();
+
+    // Act & Assert: by default, a trailing slash is significant
+    assert_eq!(get("/strict"), 200);
+    assert_eq!(get("/strict/"), 404);
+    strict.assert_calls(1);
+
+    // Without strict trailing slash handling, both variants match
+    assert_eq!(get("/lenient"), 200);
+    assert_eq!(get("/lenient/"), 200);
+    assert_eq!(get("/lenient//"), 404);
+    lenient.assert_calls(2);
+}
+
+#[test]
+fn absolute_url_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let proxied = server.mock(|when, then| {
+        when.url("HTTP://GitHub.com:80/search?q=httpmock");
+        then.status(200).body("proxied");
+    });
+
+    let direct = server.mock(|when, then| {
+        when.url_matches(r"^http://127\.0\.0\.1:\d+/users/\w+$")
+            .path_includes("alice");
+        then.status(200).body("direct");
+    });
+
+    let client = reqwest::blocking::Client::builder()
+        .proxy(reqwest::Proxy::all(server.base_url()).unwrap())
+        .build()
+        .unwrap();
+
+    // Act: an absolute-form request sent through the proxy and a regular request
+    let proxied_response = client
+        .get("http://github.com/search?q=httpmock")
+        .send()
+        .unwrap();
+    let direct_response = reqwest::blocking::get(server.url("/users/alice")).unwrap();
+    let unmatched_response = reqwest::blocking::get(server.url("/users/bob")).unwrap();
+
+    // Assert
+    assert_eq!(proxied_response.text().unwrap(), "proxied");
+    assert_eq!(direct_response.text().unwrap(), "direct");
+    assert_eq!(unmatched_response.status(), 404);
+    proxied.assert();
+    direct.assert();
+}
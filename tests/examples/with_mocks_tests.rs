@@ -0,0 +1,83 @@
+use httpmock::prelude::*;
+use httpmock::{Then, When};
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn with_mocks_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let statuses = [201, 202, 203];
+    let mocks = server.with_mocks(statuses.iter().map(|status| {
+        move |when: When, then: Then| {
+            when.path(format!("/{}", status));
+            then.status(*status);
+        }
+    }));
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/202")).unwrap();
+
+    // Assert: the mocks are returned in the order of the specifications
+    assert_eq!(response.status(), 202);
+    assert_eq!(mocks.len(), 3);
+    mocks[0].assert_calls(0);
+    mocks[1].assert();
+    mocks[2].assert_calls(0);
+}
+
+#[test]
+fn with_mocks_is_all_or_nothing_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let specs: Vec<Box<dyn Fn(When, Then)>> = vec![
+        Box::new(|when, then| {
+            when.path("/valid");
+            then.status(200);
+        }),
+        Box::new(|when, then| {
+            when.path("/invalid");
+            then.status(200).body("hello").chunked(0);
+        }),
+    ];
+
+    // Act
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        server.with_mocks(specs);
+    }));
+
+    // Assert
+    assert!(result.is_err());
+    let response = reqwest::blocking::get(server.url("/valid")).unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[cfg(feature = "remote")]
+#[test]
+fn with_mocks_remote_test() {
+    use crate::with_standalone_server;
+
+    // Arrange
+    with_standalone_server();
+    let server = MockServer::connect("localhost:5050");
+
+    let mocks = server.with_mocks(vec![
+        |when: When, then: Then| {
+            when.path("/with_mocks/one");
+            then.status(200).body("one");
+        },
+        |when: When, then: Then| {
+            when.path("/with_mocks/two");
+            then.status(200).body("two");
+        },
+    ]);
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/with_mocks/two")).unwrap();
+
+    // Assert
+    assert_eq!(response.text().unwrap(), "two");
+    mocks[0].assert_calls(0);
+    mocks[1].assert();
+}
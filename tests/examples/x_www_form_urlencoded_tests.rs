@@ -29,3 +29,39 @@ fn body_test_xxx_form_url_encoded() {
     m.assert();
     assert_eq!(response.status(), 202);
 }
+
+#[test]
+fn form_url_encoded_repeated_keys_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method(POST)
+            .path("/example")
+            .form_urlencoded_tuple_matches("^tag$", "^rust-.*$")
+            .form_urlencoded_tuple_count("^tag$", "^rust-.*$", 2)
+            .form_urlencoded_tuple_count("^tag$", ".*", 3);
+        then.status(202);
+    });
+
+    let client = Client::new();
+    let send = |body: &'static str| {
+        client
+            .post(server.url("/example"))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .unwrap()
+    };
+
+    // Act
+    let matching = send("tag=rust-lang&tag=go&tag=rust-analyzer");
+    let too_few = send("tag=rust-lang&tag=go&tag=python");
+    let too_many = send("tag=rust-lang&tag=rust-analyzer&tag=rust-fmt");
+
+    // Assert
+    m.assert();
+    assert_eq!(matching.status(), 202);
+    assert_eq!(too_few.status(), 404);
+    assert_eq!(too_many.status(), 404);
+}
@@ -0,0 +1,106 @@
+use httpmock::prelude::*;
+use std::panic::AssertUnwindSafe;
+
+#[test]
+fn mock_from_yaml_str_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock_from_yaml_str(
+        r#"
+when:
+  method: POST
+  path: /orders
+  header:
+    - name: content-type
+      value: application/json
+then:
+  status: 201
+  header:
+    - name: location
+      value: /orders/42
+  body: created
+"#,
+    );
+
+    // Act
+    let response = reqwest::blocking::Client::new()
+        .post(server.url("/orders"))
+        .header("content-type", "application/json")
+        .body("{}")
+        .send()
+        .unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.headers()["location"], "/orders/42");
+    assert_eq!(response.text().unwrap(), "created");
+}
+
+#[test]
+fn mock_from_yaml_str_error_location_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    // Act
+    let panic = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        server.mock_from_yaml_str(
+            r#"when:
+  path: /orders
+then:
+  status: created
+"#,
+        );
+    }))
+    .unwrap_err();
+
+    // Assert
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(
+        message.contains("cannot create mock from YAML"),
+        "{}",
+        message
+    );
+    assert!(message.contains("line 4 column 11"), "{}", message);
+}
+
+#[tokio::test]
+async fn mock_from_yaml_str_async_test() {
+    // Arrange
+    let server = MockServer::start_async().await;
+
+    let mock = server
+        .mock_from_yaml_str_async(
+            r#"
+when:
+  path: /hello
+then:
+  status: 200
+  body: world
+"#,
+        )
+        .await;
+
+    // Act
+    let response = reqwest::get(server.url("/hello")).await.unwrap();
+
+    // Assert
+    mock.assert_async().await;
+    assert_eq!(response.text().await.unwrap(), "world");
+}
+
+#[test]
+#[should_panic(expected = "any_of requires at least one alternative condition")]
+fn empty_any_of_from_yaml_is_rejected_test() {
+    let server = MockServer::start();
+
+    server.mock_from_yaml_str(
+        r#"
+when:
+  any_of: []
+then:
+  status: 200
+"#,
+    );
+}
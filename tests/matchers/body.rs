@@ -375,18 +375,416 @@ fn body_matches_fail_message() {
     )
 }
 
+#[test]
+fn body_matches_multiple_calls_are_anded() {
+    run_test(
+        "all patterns match",
+        |when| when.body_matches("id=\\d+").body_matches("ts=\\d+"),
+        "id=1&ts=1700000000",
+        None,
+    );
+    run_test(
+        "one pattern does not match",
+        |when| when.body_matches("id=\\d+").body_matches("ts=\\d+"),
+        "id=1",
+        Some(vec!["Expected body matches regex:", "ts=\\d+"]),
+    );
+}
+
+#[test]
+fn body_matches_any() {
+    run_test(
+        "first pattern matches",
+        |when| when.body_matches_any(["^id=", "^ts="]),
+        "id=1",
+        None,
+    );
+    run_test(
+        "second pattern matches",
+        |when| when.body_matches_any(["^id=", "^ts="]),
+        "ts=1700000000",
+        None,
+    );
+    run_test(
+        "each call must match",
+        |when| {
+            when.body_matches_any(["^id="])
+                .body_matches_any(["ts=", "date="])
+        },
+        "id=1",
+        Some(vec!["[ts=, date=]"]),
+    );
+}
+
+#[test]
+fn body_matches_any_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.body_matches_any(["^id=\\d+$", "^ts=\\d+$"]),
+        "name=Peter",
+        Some(vec![
+            "Expected body matches any regex of:",
+            "[^id=\\d+$, ^ts=\\d+$]",
+            "",
+            "Received:",
+            "name=Peter",
+            "",
+            "Reason:",
+            "none of the regexes matched: '^id=\\d+$', '^ts=\\d+$'",
+            "",
+            "Matcher:  body_matches_any",
+        ]),
+    )
+}
+
+#[test]
+fn is_json() {
+    run_test(
+        "valid JSON",
+        |when| when.is_json(),
+        r#"{"a": [1, 2]}"#,
+        None,
+    )
+}
+
+#[test]
+fn is_json_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.is_json(),
+        r#"{"a": }"#,
+        Some(vec![
+            "Expected body to be:",
+            "valid JSON",
+            "",
+            "Received:",
+            "invalid JSON (expected value at line 1 column 7)",
+            "",
+            "Matcher:  is_json",
+        ]),
+    )
+}
+
+#[test]
+fn is_valid_xml() {
+    run_test(
+        "valid XML",
+        |when| when.is_valid_xml(),
+        r#"<order><item id="1"/></order>"#,
+        None,
+    )
+}
+
+#[test]
+fn is_valid_xml_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.is_valid_xml(),
+        "<order><item></order>",
+        Some(vec![
+            "Expected body to be:",
+            "valid XML",
+            "",
+            "Received:",
+            "invalid XML (",
+            "at 1:14)",
+            "",
+            "Matcher:  is_valid_xml",
+        ]),
+    )
+}
+
+#[test]
+fn is_form_urlencoded() {
+    run_test(
+        "valid form-urlencoded data",
+        |when| when.is_form_urlencoded(),
+        "name=Peter+Griffin&town=Quahog%21",
+        None,
+    )
+}
+
+#[test]
+fn is_form_urlencoded_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.is_form_urlencoded(),
+        "name=Peter&town=%zz",
+        Some(vec![
+            "Expected body to be:",
+            "valid form-urlencoded data",
+            "",
+            "Received:",
+            "invalid form-urlencoded data (invalid percent-encoding at byte 16)",
+            "",
+            "Matcher:  is_form_urlencoded",
+        ]),
+    )
+}
+
+#[test]
+fn body_size() {
+    run_test("within range", |when| when.body_size(3, 5), "abcd", None);
+    run_test("lower bound", |when| when.body_size(4, 5), "abcd", None);
+    run_test("exact size", |when| when.body_size_eq(4), "abcd", None);
+    run_test("empty body", |when| when.body_size_eq(0), "", None);
+}
+
+#[test]
+fn body_size_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.body_size(5, 10),
+        "abcd",
+        Some(vec![
+            "Expected body size to be:",
+            "between 5 and 10 bytes",
+            "",
+            "Received:",
+            "4",
+            "",
+            "Matcher:  body_size",
+        ]),
+    )
+}
+
+#[test]
+fn has_body() {
+    run_test("body present", |when| when.has_body(), "abcd", None);
+    run_test("no body", |when| when.no_body(), "", None);
+    run_test("last call wins", |when| when.has_body().no_body(), "", None);
+}
+
+#[test]
+fn has_body_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.has_body(),
+        "",
+        Some(vec![
+            "Expected body to be:",
+            "present",
+            "",
+            "Received:",
+            "absent",
+            "",
+            "Matcher:  has_body",
+        ]),
+    );
+    run_test(
+        "fail message format",
+        |when| when.no_body(),
+        "abcd",
+        Some(vec![
+            "Expected body to be:",
+            "absent",
+            "",
+            "Received:",
+            "present",
+            "",
+            "Matcher:  has_body",
+        ]),
+    )
+}
+
+#[test]
+#[should_panic(expected = "must not be greater than the maximum body size")]
+fn body_size_invalid_range() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.body_size(10, 5);
+        then.status(200);
+    });
+}
+
+#[cfg(feature = "json-schema")]
+const USER_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "name": { "type": "string" },
+        "age": { "type": "integer", "minimum": 0 }
+    },
+    "required": ["name"]
+}"#;
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema() {
+    run_test(
+        "valid according to schema",
+        |when| when.json_schema(USER_SCHEMA),
+        r#"{"name": "Peter Griffin", "age": 45}"#,
+        None,
+    )
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema_from_file() {
+    run_test(
+        "valid according to schema file",
+        |when| when.json_schema_from_file("tests/resources/user_schema.json"),
+        r#"{"name": "Peter Griffin"}"#,
+        None,
+    )
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.json_schema(USER_SCHEMA),
+        r#"{"name": "Peter Griffin", "age": -1}"#,
+        Some(vec![
+            "Expected JSON body to match JSON schema:",
+            "Received:",
+            "Reason:",
+            "-1 is less than the minimum of 0 (at '/age', schema path '/properties/age/minimum')",
+            "Matcher:  json_schema",
+        ]),
+    )
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+fn json_schema_invalid_json_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.json_schema(USER_SCHEMA),
+        "name=Peter",
+        Some(vec![
+            "Expected JSON body to match JSON schema:",
+            "Reason:",
+            "request body is not valid JSON",
+            "Matcher:  json_schema",
+        ]),
+    )
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+#[should_panic(expected = "invalid JSON schema")]
+fn json_schema_invalid_schema_is_rejected() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.json_schema(r#"{"type": "no-such-type"}"#);
+        then.status(200);
+    });
+}
+
 fn generate_data() -> SingleValueMatcherDataSet<&'static str, &'static str> {
     SingleValueMatcherDataSet::generate("body", "Body Mismatch", true)
 }
 
-fn run_test<F, S>(
-    name: S,
-    set_expectation: F,
-    actual: &'static str,
-    error_msg: Option<Vec<&'static str>>,
-) where
+#[cfg(feature = "protobuf")]
+fn order_descriptor() -> httpmock::ProtobufDescriptor {
+    let descriptor_set = std::fs::read("tests/resources/shop.desc").unwrap();
+    httpmock::ProtobufDescriptor::new(descriptor_set, "shop.v1.Order")
+}
+
+// Order { id: 42, customer: Customer { name: "Alice" } }
+#[cfg(feature = "protobuf")]
+const ORDER: &[u8] = b"\x08\x2a\x12\x07\x0a\x05Alice";
+
+// Order { total: 9.5, status: STATUS_PAID }
+#[cfg(feature = "protobuf")]
+const PAID_ORDER: &[u8] = b"\x19\x00\x00\x00\x00\x00\x00\x23\x40\x20\x01";
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_field() {
+    run_test(
+        "scalar and nested fields",
+        |when| {
+            let order = order_descriptor();
+            when.protobuf_field(&order, "id", 42)
+                .protobuf_field(&order, "customer.name", "Alice")
+                .protobuf_field(&order, "customer.vip", false)
+                .protobuf_field(&order, "status", "STATUS_UNSPECIFIED")
+        },
+        ORDER,
+        None,
+    )
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_field_numbers_and_enums() {
+    run_test(
+        "floating point and enum fields",
+        |when| {
+            let order = order_descriptor();
+            when.protobuf_field(&order, "total", 9.5)
+                .protobuf_field(&order, "status", "STATUS_PAID")
+                .protobuf_field(&order, "status", 1)
+                .protobuf_field(&order, "id", 0)
+        },
+        PAID_ORDER,
+        None,
+    )
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_field_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.protobuf_field(&order_descriptor(), "customer.name", "Bob"),
+        ORDER,
+        Some(vec![
+            "Expected protobuf body to have field:",
+            "shop.v1.Order.customer.name = \"Bob\"",
+            "Reason:",
+            "field 'customer.name' has value \"Alice\"",
+            "Matcher:  protobuf_field",
+        ]),
+    )
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_field_invalid_body_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.protobuf_field(&order_descriptor(), "id", 42),
+        "not protobuf",
+        Some(vec![
+            "Expected protobuf body to have field:",
+            "Reason:",
+            "request body cannot be decoded as protobuf message 'shop.v1.Order'",
+            "Matcher:  protobuf_field",
+        ]),
+    )
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+#[should_panic(expected = "protobuf field 'shop.v1.Order.items' is a repeated field")]
+fn protobuf_field_repeated_field() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.protobuf_field(&order_descriptor(), "items", "apple");
+        then.status(200);
+    });
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+#[should_panic(expected = "protobuf message type 'shop.v1.Customer' has no field 'email'")]
+fn protobuf_field_unknown_field() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.protobuf_field(&order_descriptor(), "customer.email", "alice@example.com");
+        then.status(200);
+    });
+}
+
+fn run_test<F, S, B>(name: S, set_expectation: F, actual: B, error_msg: Option<Vec<&'static str>>)
+where
     F: Fn(When) -> When + std::panic::UnwindSafe + std::panic::RefUnwindSafe,
     S: Into<String>,
+    B: AsRef<[u8]> + std::panic::RefUnwindSafe,
 {
     println!("{}", name.into());
 
@@ -402,7 +800,7 @@ fn run_test<F, S>(
         // Act
         let response = reqwest::blocking::Client::new()
             .get(server.url("/test"))
-            .body(actual)
+            .body(actual.as_ref().to_vec())
             .send()
             .unwrap();
 
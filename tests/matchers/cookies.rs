@@ -194,6 +194,61 @@ fn cookie_count() {
     }
 }
 
+#[test]
+#[cfg(feature = "cookies")]
+fn cookie_count_min_max() {
+    let cookies = vec![("SESSION", "1"), ("TRACK", "2"), ("CONSENT", "3")];
+
+    run_test(
+        "cookie_count_min success",
+        |when| when.cookie_count_min(3),
+        cookies.clone(),
+        None,
+    );
+    run_test(
+        "cookie_count_max success",
+        |when| when.cookie_count_max(3),
+        cookies.clone(),
+        None,
+    );
+    run_test(
+        "cookie_count_min failure",
+        |when| when.cookie_count_min(4),
+        cookies.clone(),
+        Some(vec![
+            "Cookie Count Mismatch",
+            "Expected cookie count to be at least",
+        ]),
+    );
+    run_test(
+        "cookie_count_max failure",
+        |when| when.cookie_count_max(2),
+        cookies,
+        Some(vec![
+            "Cookie Count Mismatch",
+            "Expected cookie count to be at most",
+        ]),
+    );
+}
+
+#[test]
+fn raw_cookie_includes() {
+    let cookies = vec![("SESSION", "1"), ("TRACK", "2")];
+
+    run_test(
+        "raw_cookie_includes success",
+        |when| when.raw_cookie_includes("SESSION=1;TRACK"),
+        cookies.clone(),
+        None,
+    );
+    run_test(
+        "raw_cookie_includes failure",
+        |when| when.raw_cookie_includes("session=1"),
+        cookies,
+        Some(vec!["Cookie Header Mismatch", "session=1"]),
+    );
+}
+
 fn generate_data() -> MultiValueMatcherTestSet<&'static str, &'static str, usize, &'static str> {
     MultiValueMatcherTestSet::generate("cookie", "Cookie Mismatch", false)
 }
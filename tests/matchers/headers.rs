@@ -1,5 +1,9 @@
 use crate::matchers::{expect_fails_with2, MultiValueMatcherTestSet};
 use httpmock::{MockServer, When};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+};
 
 #[test]
 fn header() {
@@ -185,6 +189,108 @@ fn generate_data() -> MultiValueMatcherTestSet<&'static str, &'static str, usize
     MultiValueMatcherTestSet::generate("header", "Header Mismatch", false)
 }
 
+#[test]
+fn header_name_exact() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.header_name_exact("X-API-Key", "secret")
+            .header("x-api-key", "secret");
+        then.status(200);
+    });
+
+    // Act: reqwest sends lowercase header names, so the requests are written by hand
+    let wrong_case = send_raw_request(&server, "x-api-key: secret");
+    let exact_case = send_raw_request(&server, "X-API-Key: secret");
+
+    // Assert
+    m.assert_calls(1);
+    assert!(wrong_case.starts_with("HTTP/1.1 404"));
+    assert!(exact_case.starts_with("HTTP/1.1 200"));
+}
+
+#[test]
+fn header_name_exact_failure() {
+    expect_fails_with2(
+        vec![
+            "Header Mismatch",
+            "key",
+            "equals",
+            "X-API-Key",
+            "Received",
+            "X-Api-Key=secret",
+        ],
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.header_name_exact("X-API-Key", "secret");
+                then.status(200);
+            });
+
+            // Act
+            send_raw_request(&server, "X-Api-Key: secret");
+
+            // Assert
+            m.assert();
+        },
+    );
+}
+
+fn send_raw_request(server: &MockServer, header_line: &str) -> String {
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    write!(
+        stream,
+        "GET /test HTTP/1.1\r\nHost: {}\r\n{}\r\nConnection: close\r\n\r\n",
+        server.address(),
+        header_line
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn total_header_count() {
+    // reqwest adds the "accept" and "host" headers, and multi-valued headers count once per value
+    let headers = vec![("x-a", "1"), ("x-b", "2"), ("x-b", "3")];
+
+    run_test(
+        "total_header_count_min success",
+        |when| when.total_header_count_min(5),
+        headers.clone(),
+        None,
+    );
+    run_test(
+        "total_header_count_max success",
+        |when| when.total_header_count_max(5),
+        headers.clone(),
+        None,
+    );
+    run_test(
+        "total_header_count_min failure",
+        |when| when.total_header_count_min(6),
+        headers.clone(),
+        Some(vec![
+            "Total Header Count Mismatch",
+            "Expected total header count to be at least",
+        ]),
+    );
+    run_test(
+        "total_header_count_max failure",
+        |when| when.total_header_count_max(4),
+        headers,
+        Some(vec![
+            "Total Header Count Mismatch",
+            "Expected total header count to be at most",
+        ]),
+    );
+}
+
 fn run_test<F, S>(
     name: S,
     set_expectation: F,
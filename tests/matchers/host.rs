@@ -145,3 +145,59 @@ fn path_failure_table_test() {
         });
     }
 }
+
+#[test]
+fn host_header_test() {
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.host_header("API.example.com:8080");
+        then.status(200);
+    });
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+        .get(server.url("/"))
+        .header("Host", "API.example.com:8080")
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // The value is compared as-is, so neither letter case nor port may differ.
+    let response = client
+        .get(server.url("/"))
+        .header("Host", "api.example.com:8080")
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client
+        .get(server.url("/"))
+        .header("Host", "API.example.com")
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    m.assert_calls(1);
+}
+
+#[test]
+fn host_header_failure_test() {
+    expect_fails_with2(vec!["Host Header Mismatch", "api.example.com"], || {
+        let server = MockServer::start();
+
+        let m = server.mock(|when, then| {
+            when.host_header("api.example.com");
+            then.status(200);
+        });
+
+        let response = reqwest::blocking::Client::new()
+            .get(server.url("/"))
+            .send()
+            .unwrap();
+        assert_eq!(response.status(), 404);
+
+        m.assert();
+    });
+}
@@ -1,6 +1,6 @@
 use crate::matchers::expect_fails_with;
 use httpmock::{
-    Method::{GET, POST},
+    Method::{GET, PATCH, POST, PUT},
     MockServer,
 };
 use reqwest::blocking::get;
@@ -94,3 +94,150 @@ fn failure_method_not() {
         ],
     )
 }
+
+#[test]
+fn success_method_in() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.method_in(&[GET, POST, PUT]).method_not(PUT);
+        then.status(200);
+    });
+
+    let client = reqwest::blocking::Client::new();
+
+    // Act
+    let get_response = get(server.base_url()).unwrap();
+    let post_response = client.post(server.base_url()).send().unwrap();
+    let put_response = client.put(server.base_url()).send().unwrap();
+
+    // Assert
+    m.assert_calls(2);
+    assert_eq!(get_response.status(), 200);
+    assert_eq!(post_response.status(), 200);
+    assert_eq!(put_response.status(), 404);
+}
+
+#[test]
+fn failure_method_in() {
+    expect_fails_with(
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.method_in(&[POST, PATCH]);
+                then.status(200);
+            });
+
+            // Act
+            get(server.base_url()).unwrap();
+
+            m.assert()
+        },
+        vec![
+            "Method Mismatch",
+            "Expected method is one of",
+            "[POST, PATCH]",
+            "Received",
+            "GET",
+        ],
+    )
+}
+
+#[test]
+fn success_http_version() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.http_version("HTTP/1.1");
+        then.status(200);
+    });
+
+    // Act
+    let response = get(server.base_url()).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.status(), 200);
+}
+
+#[test]
+fn success_http_version_http10() {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.http_version("http/1.0");
+        then.status(200);
+    });
+
+    // Act
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.0\r\nHost: localhost\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    // Assert
+    m.assert();
+    assert!(response.starts_with("HTTP/1.0 200"));
+}
+
+#[test]
+fn success_http_version_http2() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.http_version("HTTP/2");
+        then.status(200);
+    });
+
+    // Act
+    let response = reqwest::blocking::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap()
+        .get(server.base_url())
+        .send()
+        .unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.version(), reqwest::Version::HTTP_2);
+    assert_eq!(response.status(), 200);
+}
+
+#[test]
+fn failure_http_version() {
+    expect_fails_with(
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.http_version("HTTP/1.0");
+                then.status(200);
+            });
+
+            // Act
+            get(server.base_url()).unwrap();
+
+            m.assert()
+        },
+        vec![
+            "Http Version Mismatch",
+            "Expected HTTP version equals",
+            "HTTP/1.0",
+            "Received",
+            "HTTP/1.1",
+        ],
+    )
+}
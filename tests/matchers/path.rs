@@ -177,3 +177,70 @@ fn run_test<F, S>(
         run();
     }
 }
+
+#[test]
+fn path_glob() {
+    let data: Vec<(&str, &str, Option<Vec<&str>>)> = vec![
+        ("/api/*/items", "api/v1/items", None),
+        ("/api/**/items", "api/v1/v2/items", None),
+        ("/api/**/items", "api/items", None),
+        ("/files/*.json", "files/data.json", None),
+        (
+            "/api/*/items",
+            "api/v1/v2/items",
+            Some(vec![
+                "Path Mismatch",
+                "Expected path matches glob:\n/api/*/items",
+            ]),
+        ),
+    ];
+
+    for (idx, (glob, actual, failure_msg)) in data.into_iter().enumerate() {
+        run_test(
+            format!(
+                "Running test case with index '{}' and glob '{}' for path '{}'",
+                idx, glob, actual
+            ),
+            |when| when.path_glob(glob),
+            actual,
+            failure_msg,
+        )
+    }
+}
+
+#[test]
+fn path_template() {
+    let data: Vec<(&str, &str, Option<Vec<&str>>)> = vec![
+        ("/users/{id}", "users/42", None),
+        ("/users/{id}/orders/{order_id}", "users/42/orders/7", None),
+        ("/files/{name}.json", "files/data.json", None),
+        (
+            "/users/{id}",
+            "users/42/orders",
+            Some(vec![
+                "Path Mismatch",
+                "Expected path matches template:\n/users/{id}",
+            ]),
+        ),
+        (
+            "/users/{id}/orders",
+            "users/orders",
+            Some(vec![
+                "Path Mismatch",
+                "Expected path matches template:\n/users/{id}/orders",
+            ]),
+        ),
+    ];
+
+    for (idx, (template, actual, failure_msg)) in data.into_iter().enumerate() {
+        run_test(
+            format!(
+                "Running test case with index '{}' and template '{}' for path '{}'",
+                idx, template, actual
+            ),
+            |when| when.path_template(template),
+            actual,
+            failure_msg,
+        )
+    }
+}
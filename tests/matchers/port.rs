@@ -111,3 +111,77 @@ fn host_not_failure() {
         ],
     );
 }
+
+#[test]
+fn authority_default_port_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let explicit_port = server.mock(|when, then| {
+        when.authority("github.com:80").path("/");
+        then.status(200).body("explicit");
+    });
+    let implicit_port = server.mock(|when, then| {
+        when.authority("GITHUB.com").path("/implicit");
+        then.status(200).body("implicit");
+    });
+
+    let client = reqwest::blocking::Client::builder()
+        .proxy(reqwest::Proxy::all(server.base_url()).unwrap())
+        .build()
+        .unwrap();
+
+    // Act & Assert: requests without a port use the default port of the scheme
+    let response = client.get("http://github.com").send().unwrap();
+    assert_eq!(response.text().unwrap(), "explicit");
+
+    // Act & Assert: authorities without a port expect the default port of the scheme
+    let response = client.get("http://github.com:80/implicit").send().unwrap();
+    assert_eq!(response.text().unwrap(), "implicit");
+
+    let response = client
+        .get("http://github.com:8080/implicit")
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    explicit_port.assert();
+    implicit_port.assert();
+}
+
+#[test]
+fn authority_failure() {
+    expect_fails_with(
+        || {
+            // Arrange
+            let server = MockServer::start();
+
+            let m = server.mock(|when, then| {
+                when.authority("github.com:81");
+                then.status(200);
+            });
+
+            // Act
+            get(server.base_url()).unwrap();
+
+            m.assert()
+        },
+        vec![
+            "Authority Mismatch",
+            "Expected authority equals",
+            "github.com:81",
+            "Received",
+            "127.0.0.1:",
+        ],
+    )
+}
+
+#[test]
+#[should_panic(expected = "invalid authority 'github.com:http'")]
+fn authority_invalid() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.authority("github.com:http");
+        then.status(200);
+    });
+}
@@ -181,6 +181,70 @@ fn query_param_count() {
     }
 }
 
+#[test]
+fn query_param_all() {
+    run_test(
+        "same values in different order",
+        |when| when.query_param_all("tag", &["a", "b", "c"]),
+        vec![("tag", "c"), ("other", "x"), ("tag", "a"), ("tag", "b")],
+        None,
+    );
+    run_test(
+        "repeated values",
+        |when| when.query_param_all("tag", &["a", "a"]),
+        vec![("tag", "a"), ("tag", "a")],
+        None,
+    );
+}
+
+#[test]
+fn query_param_all_fail_message() {
+    run_test(
+        "fail message format",
+        |when| when.query_param_all("tag", &["a", "b", "c"]),
+        vec![("tag", "a"), ("tag", "b"), ("tag", "d")],
+        Some(vec![
+            "Expected query parameter to be:",
+            "tag=[a, b, c]",
+            "",
+            "Received:",
+            "tag=[a, b, d]",
+            "",
+            "Reason:",
+            "missing values: c; unexpected values: d",
+            "",
+            "Matcher:  query_param_all",
+        ]),
+    );
+}
+
+#[test]
+fn query_param_includes_all() {
+    run_test(
+        "additional values",
+        |when| when.query_param_includes_all("tag", &["a", "b"]),
+        vec![("tag", "b"), ("tag", "c"), ("tag", "a")],
+        None,
+    );
+    run_test(
+        "repeated value missing",
+        |when| when.query_param_includes_all("tag", &["a", "a"]),
+        vec![("tag", "a"), ("tag", "b")],
+        Some(vec![
+            "Expected query parameter to include:",
+            "tag=[a, a]",
+            "",
+            "Received:",
+            "tag=[a, b]",
+            "",
+            "Reason:",
+            "missing values: a",
+            "",
+            "Matcher:  query_param_includes_all",
+        ]),
+    );
+}
+
 fn generate_data() -> MultiValueMatcherTestSet<&'static str, &'static str, usize, &'static str> {
     MultiValueMatcherTestSet::generate("query_param", "Query Parameter Mismatch", false)
 }
@@ -0,0 +1,61 @@
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServer, HttpMockServerBuilder},
+};
+use std::{net::SocketAddr, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+fn start_standalone_server() -> SocketAddr {
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    addr_receiver.blocking_recv().unwrap()
+}
+
+#[test]
+fn connect_to_multiple_servers_test() {
+    // Arrange
+    let first_addr = start_standalone_server();
+    let second_addr = start_standalone_server();
+
+    // Act: Both connections are held at the same time
+    let first = MockServer::connect(&first_addr.to_string());
+    let second = MockServer::connect(&second_addr.to_string());
+
+    let m1 = first.mock(|when, then| {
+        when.path("/hello");
+        then.status(201);
+    });
+    let m2 = second.mock(|when, then| {
+        when.path("/hello");
+        then.status(202);
+    });
+
+    // Assert: Each connection talks to the server it was created for
+    assert_eq!(first.address(), &first_addr);
+    assert_eq!(second.address(), &second_addr);
+    assert_eq!(
+        reqwest::blocking::get(first.url("/hello"))
+            .unwrap()
+            .status(),
+        201
+    );
+    assert_eq!(
+        reqwest::blocking::get(second.url("/hello"))
+            .unwrap()
+            .status(),
+        202
+    );
+    m1.assert();
+    m2.assert();
+}
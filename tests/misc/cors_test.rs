@@ -0,0 +1,133 @@
+use httpmock::{
+    prelude::*,
+    server::{CorsConfig, HttpMockServer, HttpMockServerBuilder},
+};
+use reqwest::{blocking::Client, Method};
+use std::{net::SocketAddr, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+#[test]
+fn auto_cors_test() {
+    // Arrange
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .auto_cors(
+                CorsConfig::new()
+                    .allow_origin("https://app.example.com")
+                    .allow_credentials(true)
+                    .expose_headers(["x-request-id"]),
+            )
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    let addr = addr_receiver.blocking_recv().unwrap();
+    let server = MockServer::connect(&addr.to_string());
+
+    let m = server.mock(|when, then| {
+        when.method(PUT).path("/users");
+        then.status(200).header("x-request-id", "1");
+    });
+
+    let client = Client::new();
+
+    // Act: a preflight request that no mock matches is answered automatically
+    let response = client
+        .request(Method::OPTIONS, server.url("/users"))
+        .header("origin", "https://app.example.com")
+        .header("access-control-request-method", "PUT")
+        .header("access-control-request-headers", "content-type")
+        .send()
+        .unwrap();
+
+    // Assert: credentials require the origin to be reflected instead of a wildcard
+    assert_eq!(response.status(), 204);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        response.headers()["access-control-allow-credentials"],
+        "true"
+    );
+    assert_eq!(response.headers()["access-control-allow-methods"], "PUT");
+    assert_eq!(
+        response.headers()["access-control-allow-headers"],
+        "content-type"
+    );
+    assert!(response
+        .headers()
+        .get_all("vary")
+        .iter()
+        .any(|v| v == "origin"));
+
+    // Act: the actual request receives CORS headers as well
+    let response = client
+        .put(server.url("/users"))
+        .header("origin", "https://app.example.com")
+        .send()
+        .unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        response.headers()["access-control-expose-headers"],
+        "x-request-id"
+    );
+
+    // Act: origins that are not allowed receive no CORS headers
+    let response = client
+        .request(Method::OPTIONS, server.url("/users"))
+        .header("origin", "https://evil.example.com")
+        .header("access-control-request-method", "PUT")
+        .send()
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 403);
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+    assert!(response
+        .headers()
+        .get("access-control-allow-credentials")
+        .is_none());
+
+    // Act: mocks that match a preflight request take precedence
+    let options_mock = server.mock(|when, then| {
+        when.method(OPTIONS).path("/users");
+        then.status(200)
+            .header("access-control-allow-origin", "https://other.example.com");
+    });
+
+    let response = client
+        .request(Method::OPTIONS, server.url("/users"))
+        .header("origin", "https://app.example.com")
+        .header("access-control-request-method", "PUT")
+        .send()
+        .unwrap();
+
+    // Assert
+    options_mock.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://other.example.com"
+    );
+    assert!(response
+        .headers()
+        .get("access-control-allow-methods")
+        .is_none());
+}
@@ -0,0 +1,60 @@
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServer, HttpMockServerBuilder},
+};
+use std::{net::SocketAddr, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+#[test]
+fn matched_mock_id_debug_header_test() {
+    // Arrange
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .debug_headers(true)
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    let addr = addr_receiver.blocking_recv().unwrap();
+    let server = MockServer::connect(&addr.to_string());
+
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(
+        response.headers().get("x-httpmock-matched-id").unwrap(),
+        &m.id.to_string()
+    );
+}
+
+#[test]
+fn debug_headers_disabled_by_default_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    m.assert();
+    assert!(response.headers().get("x-httpmock-matched-id").is_none());
+}
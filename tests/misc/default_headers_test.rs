@@ -0,0 +1,55 @@
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServer, HttpMockServerBuilder},
+};
+use std::{net::SocketAddr, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+#[test]
+fn default_response_headers_test() {
+    // Arrange
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .default_response_header("access-control-allow-origin", "*")
+            .default_response_header("x-request-id", "default")
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    let addr = addr_receiver.blocking_recv().unwrap();
+    let server = MockServer::connect(&addr.to_string());
+
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).header("x-request-id", "explicit");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.headers()["x-request-id"], "explicit");
+
+    // Default headers set at runtime replace the configured ones until the server is reset
+    server.set_default_headers(vec![("x-request-id", "runtime")]);
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+
+    server.reset();
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.headers()["x-request-id"], "default");
+}
@@ -0,0 +1,57 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+/// Sends a request to a mock that delays its response and shuts down the server while the
+/// request is still being processed. Returns what the client received.
+fn shutdown_during_request(builder: HttpMockServerBuilder) -> String {
+    let server = MockServer::start_with(builder);
+
+    server.mock(|when, then| {
+        when.path("/slow");
+        then.status(200)
+            .body("done")
+            .delay(Duration::from_millis(500));
+    });
+
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    let request = format!("GET /slow HTTP/1.1\r\nHost: {}\r\n\r\n", server.address());
+    stream.write_all(request.as_bytes()).unwrap();
+
+    // Give the server time to start processing the request before shutting it down.
+    thread::sleep(Duration::from_millis(100));
+    drop(server);
+
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    response.to_lowercase()
+}
+
+#[test]
+fn shutdown_timeout_drains_in_flight_requests_test() {
+    // Act
+    let response = shutdown_during_request(
+        HttpMockServerBuilder::new().shutdown_timeout(Duration::from_secs(5)),
+    );
+
+    // Assert
+    assert!(response.starts_with("http/1.1 200"));
+    assert!(response.ends_with("done"));
+}
+
+#[test]
+fn no_shutdown_timeout_cuts_off_in_flight_requests_test() {
+    // Act
+    let response = shutdown_during_request(HttpMockServerBuilder::new());
+
+    // Assert
+    assert_eq!(response, "");
+}
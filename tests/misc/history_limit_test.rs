@@ -0,0 +1,44 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+
+#[test]
+fn history_limit_test() {
+    // Arrange
+    let server = MockServer::start_with(HttpMockServerBuilder::new().history_limit(2));
+
+    let m = server.mock(|when, then| {
+        when.path("/hello").query_param("id", "1");
+        then.status(200);
+    });
+
+    // Act: the first request is dropped from the history by the following two
+    for id in 1..=3 {
+        reqwest::blocking::get(server.url(format!("/hello?id={}", id))).unwrap();
+    }
+
+    // Assert: call counts are not affected by the history limit
+    m.assert_calls(1);
+    assert!(m.last_request().is_none());
+
+    let closest_match = m.closest_match().expect("expected a closest match");
+    assert_eq!(closest_match.request.uri().query(), Some("id=2"));
+}
+
+#[test]
+fn history_disabled_test() {
+    // Arrange
+    let server = MockServer::start_with(HttpMockServerBuilder::new().history_limit(0));
+
+    let m = server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200);
+    });
+
+    // Act
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+    reqwest::blocking::get(server.url("/other")).unwrap();
+
+    // Assert
+    m.assert();
+    assert!(m.last_request().is_none());
+    assert!(m.closest_match().is_none());
+}
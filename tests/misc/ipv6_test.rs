@@ -0,0 +1,37 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use std::net::{Ipv6Addr, TcpListener};
+
+#[test]
+fn ipv6_url_test() {
+    // The sandbox or CI host may not have an IPv6 loopback interface.
+    if TcpListener::bind((Ipv6Addr::LOCALHOST, 0)).is_err() {
+        return;
+    }
+
+    // Arrange
+    let server = MockServer::start_with(
+        HttpMockServerBuilder::new().bind_address(Ipv6Addr::LOCALHOST.into()),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.path("/path");
+        then.status(200);
+    });
+
+    // Act
+    let url = server.url("/path");
+    let response = reqwest::blocking::get(&url).unwrap();
+
+    // Assert
+    mock.assert();
+    assert_eq!(response.status(), 200);
+    assert_eq!(server.host(), "::1");
+    assert_eq!(
+        url,
+        format!("{}://[::1]:{}/path", server.scheme(), server.port())
+    );
+    assert_eq!(
+        server.base_url(),
+        format!("{}://[::1]:{}", server.scheme(), server.port())
+    );
+}
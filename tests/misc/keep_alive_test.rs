@@ -0,0 +1,68 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+fn send_request(stream: &mut TcpStream, server: &MockServer) {
+    let request = format!("GET /hello HTTP/1.1\r\nHost: {}\r\n\r\n", server.address());
+    stream.write_all(request.as_bytes()).unwrap();
+}
+
+#[test]
+fn disabled_keep_alive_test() {
+    // Arrange
+    let server = MockServer::start_with(HttpMockServerBuilder::new().keep_alive(false));
+
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).body("hi");
+    });
+
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    // Act: read until the server closes the connection
+    send_request(&mut stream, &server);
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    // Assert
+    let response = response.to_lowercase();
+    assert!(response.starts_with("http/1.1 200"));
+    assert!(response.contains("connection: close"));
+    assert!(response.ends_with("hi"));
+}
+
+#[test]
+fn idle_timeout_test() {
+    // Arrange
+    let server = MockServer::start_with(
+        HttpMockServerBuilder::new().idle_timeout(Duration::from_millis(200)),
+    );
+
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).body("hi");
+    });
+
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    // Act: the connection stays open after the response and is closed once it was idle
+    send_request(&mut stream, &server);
+    let mut buffer = [0u8; 1024];
+    let response_size = stream.read(&mut buffer).unwrap();
+    let response = String::from_utf8_lossy(&buffer[..response_size]).to_lowercase();
+    let size_after_idle = stream.read(&mut buffer).unwrap();
+
+    // Assert
+    assert!(response.starts_with("http/1.1 200"));
+    assert!(!response.contains("connection: close"));
+    assert_eq!(size_after_idle, 0);
+}
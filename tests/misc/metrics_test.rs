@@ -0,0 +1,60 @@
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServer, HttpMockServerBuilder},
+};
+use std::{net::SocketAddr, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+#[test]
+fn metrics_endpoint_test() {
+    // Arrange
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .enable_metrics(true)
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    let addr = addr_receiver.blocking_recv().unwrap();
+    let server = MockServer::connect(&addr.to_string());
+
+    // A mock that would match the metrics endpoint must not be served.
+    server.mock(|when, then| {
+        when.path_includes("metrics");
+        then.status(500);
+    });
+
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(201);
+    });
+
+    // Act
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+    reqwest::blocking::get(server.url("/hello")).unwrap();
+    reqwest::blocking::get(server.url("/unknown")).unwrap();
+
+    let response = reqwest::blocking::get(server.url("/__httpmock__/metrics")).unwrap();
+
+    // Assert
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+
+    let text = response.text().unwrap();
+    assert!(text.contains("# TYPE httpmock_requests_total counter\nhttpmock_requests_total 3\n"));
+    assert!(text.contains("\nhttpmock_matched_requests_total 2\n"));
+    assert!(text.contains("\nhttpmock_unmatched_requests_total 1\n"));
+    assert!(text.contains("\nhttpmock_responses_total{status=\"201\"} 2\n"));
+    assert!(text.contains("\nhttpmock_responses_total{status=\"404\"} 1\n"));
+    assert!(text.contains("\nhttpmock_active_mocks 2\n"));
+}
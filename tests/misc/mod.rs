@@ -1,6 +1,30 @@
+#[cfg(feature = "remote")]
+mod connect_test;
+#[cfg(feature = "remote")]
+mod cors_test;
+#[cfg(feature = "remote")]
+mod debug_headers_test;
+#[cfg(feature = "remote")]
+mod default_headers_test;
 mod extensions_test;
+mod graceful_shutdown_test;
+mod history_limit_test;
+mod ipv6_test;
+mod keep_alive_test;
 #[cfg(feature = "remote")]
 mod large_body_test;
 mod loop_test;
+#[cfg(feature = "remote")]
+mod metrics_test;
+#[cfg(feature = "remote")]
+mod named_matcher_test;
+mod omit_headers_test;
+mod random_port_test;
+mod request_body_limit_test;
 #[cfg(all(feature = "proxy", feature = "remote"))]
 mod runtimes_test;
+mod server_pool_test;
+mod start_on_test;
+#[cfg(feature = "record")]
+mod watch_mock_dir_test;
+mod worker_threads_test;
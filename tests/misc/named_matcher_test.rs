@@ -0,0 +1,76 @@
+use crate::matchers::expect_fails_with;
+use httpmock::{
+    prelude::*,
+    server::{HttpMockServer, HttpMockServerBuilder},
+};
+use serde_json::json;
+use std::{net::SocketAddr, panic::AssertUnwindSafe, thread};
+use tokio::{runtime::Builder, sync::oneshot, task::LocalSet};
+
+fn start_server() -> MockServer {
+    let (addr_sender, addr_receiver) = oneshot::channel::<SocketAddr>();
+    thread::spawn(move || {
+        let srv: HttpMockServer = HttpMockServerBuilder::new()
+            .register_matcher("header_is_even", |req, args| {
+                let name = args["header"].as_str().unwrap_or_default();
+                req.headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .is_some_and(|v| v % 2 == 0)
+            })
+            .build()
+            .expect("cannot create mock server");
+
+        let runtime = Builder::new_current_thread().enable_all().build().unwrap();
+        LocalSet::new().block_on(
+            &runtime,
+            srv.start_with_signals(Some(addr_sender), std::future::pending()),
+        )
+    });
+
+    let addr = addr_receiver.blocking_recv().unwrap();
+    MockServer::connect(&addr.to_string())
+}
+
+#[test]
+fn named_matcher_test() {
+    // Arrange
+    let server = start_server();
+
+    let m = server.mock(|when, then| {
+        when.path("/numbers")
+            .matcher_named("header_is_even", json!({ "header": "x-number" }));
+        then.status(200);
+    });
+
+    let client = reqwest::blocking::Client::new();
+
+    // Act
+    let even = client
+        .get(server.url("/numbers"))
+        .header("x-number", "42")
+        .send()
+        .unwrap();
+    let odd = client
+        .get(server.url("/numbers"))
+        .header("x-number", "7")
+        .send()
+        .unwrap();
+
+    // Assert
+    m.assert();
+    assert_eq!(even.status(), 200);
+    assert_eq!(odd.status(), 404);
+
+    // Unknown matcher names are rejected when the mock is created.
+    expect_fails_with(
+        AssertUnwindSafe(|| {
+            server.mock(|when, then| {
+                when.matcher_named("unknown_matcher", json!(null));
+                then.status(200);
+            });
+        }),
+        vec!["unknown matcher 'unknown_matcher'"],
+    );
+}
@@ -0,0 +1,104 @@
+use httpmock::MockServer;
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+fn send_raw_request(server: &MockServer, request_line: &str) -> String {
+    let mut stream = TcpStream::connect(server.address()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+
+    let request = format!(
+        "{}\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        request_line,
+        server.address()
+    );
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.to_lowercase()
+}
+
+#[test]
+fn date_header_is_sent_by_default_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).body("hi");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+
+    // Assert
+    assert!(response.headers().get("date").is_some());
+    assert_eq!(response.headers()["content-length"], "2");
+}
+
+#[test]
+fn omit_date_and_content_length_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    let mock = server.mock(|when, then| {
+        when.path("/legacy");
+        then.status(200)
+            .header("Server", "legacy-server")
+            .body("hello")
+            .omit_header("date")
+            .omit_header("Content-Length")
+            .omit_header("Server");
+    });
+
+    // Act: an HTTP/1.0 client cannot receive chunked bodies, so the body ends when the
+    // connection is closed
+    let response = send_raw_request(&server, "GET /legacy HTTP/1.0");
+
+    // Assert
+    mock.assert();
+    assert!(response.starts_with("http/1.0 200"));
+    assert!(!response.contains("date:"));
+    assert!(!response.contains("content-length:"));
+    assert!(!response.contains("transfer-encoding:"));
+    assert!(!response.contains("server:"));
+    assert!(response.ends_with("\r\n\r\nhello"));
+}
+
+#[test]
+fn omit_content_length_uses_chunked_encoding_test() {
+    // Arrange
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/download");
+        then.status(200)
+            .body_from_file("tests/resources/simple_body.txt")
+            .omit_header("Content-Length");
+    });
+
+    // Act
+    let response = reqwest::blocking::get(server.url("/download")).unwrap();
+
+    // Assert
+    assert!(response.headers().get("content-length").is_none());
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert!(response.headers().get("date").is_some());
+    assert_eq!(response.text().unwrap(), "ohi!");
+}
+
+#[test]
+#[should_panic(expected = "cannot omit response header 'Content-Type'")]
+fn omit_unsupported_header_test() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.path("/hello");
+        then.status(200).omit_header("Content-Type");
+    });
+}
@@ -0,0 +1,17 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use tokio::net::TcpStream;
+
+#[tokio::test]
+async fn random_port_is_known_after_start_test() {
+    // Arrange: let the operating system choose the port
+    let server = MockServer::start_with_async(HttpMockServerBuilder::new().port(0)).await;
+
+    // Act: use the port before any request has been sent to the server
+    let port = server.port();
+    let stream = TcpStream::connect(("127.0.0.1", port)).await;
+
+    // Assert
+    assert_ne!(port, 0);
+    assert_eq!(server.address().port(), port);
+    assert!(stream.is_ok());
+}
@@ -0,0 +1,41 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use reqwest::blocking::{Body, Client};
+use std::io::Cursor;
+
+#[test]
+fn max_request_body_size_test() {
+    // Arrange
+    let server = MockServer::start_with(HttpMockServerBuilder::new().max_request_body_size(1024));
+
+    let m = server.mock(|when, then| {
+        when.path("/upload");
+        then.status(201);
+    });
+
+    let client = Client::new();
+
+    // Act: bodies with a Content-Length header
+    let small = client
+        .post(server.url("/upload"))
+        .body(vec![b'a'; 1024])
+        .send()
+        .unwrap();
+    let large = client
+        .post(server.url("/upload"))
+        .body(vec![b'a'; 1025])
+        .send()
+        .unwrap();
+
+    // Act: a chunked body of unknown length
+    let chunked = client
+        .post(server.url("/upload"))
+        .body(Body::new(Cursor::new(vec![b'a'; 2048])))
+        .send()
+        .unwrap();
+
+    // Assert: oversized requests are rejected and never reach the mock
+    assert_eq!(small.status(), 201);
+    assert_eq!(large.status(), 413);
+    assert_eq!(chunked.status(), 413);
+    m.assert_calls(1);
+}
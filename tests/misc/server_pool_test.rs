@@ -0,0 +1,59 @@
+use httpmock::MockServerPool;
+use std::thread;
+
+#[test]
+fn server_pool_test() {
+    // Arrange
+    let pool = MockServerPool::new(1);
+
+    let address = {
+        let server = pool.get();
+        server.mock(|when, then| {
+            when.path("/hello");
+            then.status(200);
+        });
+
+        let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+        assert_eq!(response.status(), 200);
+
+        *server.address()
+    };
+
+    // Act: the only server of the pool is handed out again
+    let server = pool.get();
+
+    // Assert: it is the same server, but the previous mocks have been removed
+    assert_eq!(server.address(), &address);
+
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[test]
+fn server_pool_recycles_server_of_panicking_test() {
+    // Arrange
+    let pool = MockServerPool::new(1);
+
+    let thread_pool = pool.clone();
+    let address = thread::spawn(move || {
+        let server = thread_pool.get();
+        server.mock(|when, then| {
+            when.path("/hello");
+            then.status(200);
+        });
+        panic!("test failure while using {}", server.address());
+    })
+    .join()
+    .unwrap_err()
+    .downcast::<String>()
+    .unwrap();
+
+    // Act: this would block forever if the server had not been returned to the pool
+    let server = pool.get();
+
+    // Assert
+    assert!(address.ends_with(&server.address().to_string()));
+
+    let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+    assert_eq!(response.status(), 404);
+}
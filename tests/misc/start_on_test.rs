@@ -0,0 +1,63 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use tokio::{net::TcpStream, runtime::Handle};
+
+#[tokio::test(flavor = "current_thread")]
+async fn start_on_current_thread_runtime_test() {
+    // Arrange: the server runs as a task on the runtime of this test
+    let server = MockServer::start_on(Handle::current()).await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.path("/hello");
+            then.status(200).body("hi");
+        })
+        .await;
+
+    // Act
+    let response = reqwest::get(server.url("/hello")).await.unwrap();
+
+    // Assert
+    mock.assert_async().await;
+    assert_eq!(response.text().await.unwrap(), "hi");
+}
+
+#[tokio::test]
+async fn start_with_on_shuts_down_when_dropped_test() {
+    // Arrange
+    let server =
+        MockServer::start_with_on(HttpMockServerBuilder::new().port(0), Handle::current()).await;
+    let address = *server.address();
+    assert!(TcpStream::connect(address).await.is_ok());
+
+    // Act
+    drop(server);
+
+    // Assert: the server task stops listening shortly after it was dropped
+    for _ in 0..50 {
+        if TcpStream::connect(address).await.is_err() {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("mock server is still accepting connections after it was dropped");
+}
+
+#[actix_rt::test]
+async fn start_on_actix_runtime_test() {
+    // Arrange
+    let server = MockServer::start_on(Handle::current()).await;
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.path("/hello");
+            then.status(202);
+        })
+        .await;
+
+    // Act
+    let response = reqwest::get(server.url("/hello")).await.unwrap();
+
+    // Assert
+    mock.assert_async().await;
+    assert_eq!(response.status(), 202);
+}
@@ -0,0 +1,57 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use std::{fs, path::Path, thread, time::Duration};
+
+fn mock_file(path: &str, body: &str) -> String {
+    format!(
+        "when:\n  method: GET\n  path: {}\nthen:\n  status: 200\n  body: {}\n",
+        path, body
+    )
+}
+
+fn wait_for_reload() {
+    thread::sleep(Duration::from_millis(1500));
+}
+
+fn get(server: &MockServer, path: &str) -> (u16, String) {
+    let response = reqwest::blocking::get(server.url(path)).unwrap();
+    (response.status().as_u16(), response.text().unwrap())
+}
+
+#[test]
+fn watch_mock_dir_test() {
+    // Arrange
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("watch_mock_dir_test");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(dir.join("hello.yaml"), mock_file("/hello", "hello")).unwrap();
+
+    let server = MockServer::start_with(
+        HttpMockServerBuilder::new()
+            .static_mock_dir(dir.clone())
+            .watch_mock_dir(true),
+    );
+
+    assert_eq!(get(&server, "/hello"), (200, "hello".to_string()));
+
+    // Act & Assert: added and changed files are loaded
+    fs::write(dir.join("bye.yml"), mock_file("/bye", "bye")).unwrap();
+    fs::write(dir.join("hello.yaml"), mock_file("/hello", "hi")).unwrap();
+    wait_for_reload();
+
+    assert_eq!(get(&server, "/bye"), (200, "bye".to_string()));
+    assert_eq!(get(&server, "/hello"), (200, "hi".to_string()));
+
+    // Act & Assert: a malformed file keeps the previously loaded mocks
+    fs::write(dir.join("hello.yaml"), "when:\n  method: [GET\n").unwrap();
+    wait_for_reload();
+
+    assert_eq!(get(&server, "/hello"), (200, "hi".to_string()));
+
+    // Act & Assert: removed files are unloaded
+    fs::remove_file(dir.join("bye.yml")).unwrap();
+    wait_for_reload();
+
+    assert_eq!(get(&server, "/bye").0, 404);
+    assert_eq!(get(&server, "/hello"), (200, "hi".to_string()));
+}
@@ -0,0 +1,47 @@
+use httpmock::{server::HttpMockServerBuilder, MockServer};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+#[test]
+fn worker_threads_test() {
+    // Arrange
+    let server = MockServer::start_with(HttpMockServerBuilder::new().worker_threads(2));
+
+    let thread_names = Arc::new(Mutex::new(Vec::new()));
+    let names = thread_names.clone();
+
+    let mock = server.mock(|when, then| {
+        when.path("/hello").is_true(move |_| {
+            let name = thread::current().name().map(String::from);
+            names.lock().unwrap().push(name);
+            true
+        });
+        then.status(200);
+    });
+
+    // Act
+    for _ in 0..3 {
+        let response = reqwest::blocking::get(server.url("/hello")).unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    // Assert
+    mock.assert_calls(3);
+    let thread_names = thread_names.lock().unwrap();
+    // Without worker threads, the server runs on an unnamed thread, whereas Tokio names the
+    // worker threads of its multi-threaded runtime.
+    assert!(!thread_names.is_empty());
+    assert!(thread_names.iter().all(|name| name.is_some()));
+}
+
+#[test]
+fn zero_worker_threads_are_rejected_test() {
+    let result = HttpMockServerBuilder::new().worker_threads(0).build();
+
+    let err = result.err().expect("building the server should fail");
+    assert!(err
+        .to_string()
+        .contains("number of worker threads must be larger than 0"));
+}